@@ -43,6 +43,14 @@ pub(crate) struct Cli {
     )]
     pub master_xpriv_path: Option<String>,
 
+    /// Path to the Unix socket of a signing service holding the operator's key.
+    /// If set, the master xpriv is not needed.
+    #[argh(
+        option,
+        description = "path to the socket of a signing service holding the operator's key (replaces the master xpriv)"
+    )]
+    pub signer_socket: Option<String>,
+
     /// Host address for the RPC server. Defaults to `127.0.0.1` if not specified.
     #[argh(
         option,
@@ -99,6 +107,20 @@ pub(crate) struct Cli {
         description = "duty timeout duration in seconds (default: 600)"
     )]
     pub duty_timeout_duration: Option<u64>,
+
+    /// Host address for the metrics server. Defaults to `127.0.0.1` if not specified.
+    #[argh(
+        option,
+        description = "host to serve the prometheus metrics on (default: 127.0.0.1)"
+    )]
+    pub metrics_host: Option<String>,
+
+    /// Port number for the metrics server. Metrics are not served if not specified.
+    #[argh(
+        option,
+        description = "port to serve the prometheus metrics on (default: disabled)"
+    )]
+    pub metrics_port: Option<u16>,
 }
 
 /// Represents the operational mode of the client.
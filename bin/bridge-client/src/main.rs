@@ -12,9 +12,14 @@ pub(crate) mod rpc_server;
 pub(crate) mod xpriv;
 
 use args::{Cli, OperationMode};
+use constants::DEFAULT_METRICS_HOST;
 use modes::{challenger, operator};
-use strata_common::logging::{self, LoggerConfig};
-use tracing::info;
+use strata_common::{
+    logging::{self, LoggerConfig},
+    metrics,
+};
+use strata_config::MetricsConfig;
+use tracing::{error, info};
 
 #[tokio::main]
 async fn main() {
@@ -31,6 +36,20 @@ async fn main() {
 
     info!("running bridge client in {} mode", mode);
 
+    if let Some(port) = cli_args.metrics_port {
+        let host = cli_args
+            .metrics_host
+            .clone()
+            .unwrap_or_else(|| DEFAULT_METRICS_HOST.to_string());
+        let metrics_server = metrics::init(&MetricsConfig { host, port }).expect("init metrics");
+
+        tokio::spawn(async move {
+            if let Err(e) = metrics_server.await {
+                error!(error = %e, "metrics server exited");
+            }
+        });
+    }
+
     match mode {
         OperationMode::Operator => {
             operator::bootstrap(cli_args)
@@ -38,7 +57,9 @@ async fn main() {
                 .expect("bootstrap operator node");
         }
         OperationMode::Challenger => {
-            challenger::bootstrap().await;
+            challenger::bootstrap(cli_args)
+                .await
+                .expect("bootstrap challenger node");
         }
     }
 }
@@ -0,0 +1,104 @@
+//! Module to bootstrap the challenger node by hooking up all the required services.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use jsonrpsee::{core::client::async_client::Client as L2RpcClient, ws_client::WsClientBuilder};
+use strata_btcio::rpc::{traits::Reader, BitcoinClient};
+use strata_rocksdb::{
+    bridge::db::{BridgeDutyRocksDb, ChallengeReportRocksDb},
+    DbOpsConfig,
+};
+use strata_rpc_api::StrataApiClient;
+use strata_storage::ops::{
+    bridge_duty::Context as DutyContext, challenge_report::Context as ChallengeReportContext,
+};
+use threadpool::ThreadPool;
+use tracing::error;
+
+use super::{constants::DB_THREAD_COUNT, watcher::Watcher};
+use crate::{
+    args::Cli,
+    constants::{DEFAULT_RPC_HOST, DEFAULT_RPC_PORT, ROCKSDB_RETRY_COUNT},
+    db::open_rocksdb_database,
+    rpc_server::{self, BridgeRpc},
+};
+
+/// Bootstraps the bridge client in Challenger mode by hooking up all the required auxiliary
+/// services including database, rpc server, etc. Logging needs to be initialized at the call
+/// site (main function) itself.
+pub(crate) async fn bootstrap(args: Cli) -> anyhow::Result<()> {
+    // Parse dirs
+    let data_dir = args.datadir.map(PathBuf::from);
+
+    // Initialize a rocksdb instance with the required column families.
+    let rbdb = open_rocksdb_database(data_dir)?;
+    let retry_count = args.retry_count.unwrap_or(ROCKSDB_RETRY_COUNT);
+    let ops_config = DbOpsConfig::new(retry_count);
+
+    // Setup Threadpool for the database I/O ops.
+    let bridge_db_pool = ThreadPool::new(DB_THREAD_COUNT);
+
+    // Setup databases.
+    let bridge_duty_db = BridgeDutyRocksDb::new(rbdb.clone(), ops_config);
+    let bridge_duty_db_ctx = DutyContext::new(Arc::new(bridge_duty_db));
+    let bridge_duty_db_ops = Arc::new(bridge_duty_db_ctx.into_ops(bridge_db_pool.clone()));
+
+    let challenge_report_db = ChallengeReportRocksDb::new(rbdb, ops_config);
+    let challenge_report_db_ctx = ChallengeReportContext::new(Arc::new(challenge_report_db));
+    let challenge_report_db_ops = Arc::new(challenge_report_db_ctx.into_ops(bridge_db_pool));
+
+    // Setup RPC clients.
+    let l1_rpc_client = Arc::new(
+        BitcoinClient::new(args.btc_url, args.btc_user, args.btc_pass)
+            .expect("error creating the bitcoin client"),
+    );
+    let l2_rpc_client: L2RpcClient = WsClientBuilder::default()
+        .build(args.rollup_url)
+        .await
+        .expect("failed to connect to the rollup RPC server");
+
+    let network = l1_rpc_client.network().await?;
+
+    // Spawn RPC server.
+    let bridge_rpc = BridgeRpc::new(bridge_duty_db_ops, challenge_report_db_ops.clone());
+
+    let rpc_host = args.rpc_host.as_deref().unwrap_or(DEFAULT_RPC_HOST);
+    let rpc_port = args.rpc_port.unwrap_or(DEFAULT_RPC_PORT);
+    let rpc_addr = format!("{rpc_host}:{rpc_port}");
+
+    let rpc_task = tokio::spawn(async move {
+        if let Err(e) = rpc_server::start(&bridge_rpc, rpc_addr.as_str()).await {
+            error!(error = %e, "could not start RPC server");
+        }
+    });
+
+    let rollup_block_time = l2_rpc_client
+        .block_time()
+        .await
+        .expect("should be able to get block time from rollup RPC client");
+
+    let duty_polling_interval = args.duty_interval.map_or(
+        Duration::from_millis(rollup_block_time),
+        Duration::from_millis,
+    );
+
+    let watcher = Watcher {
+        l1_rpc_client,
+        l2_rpc_client,
+        challenge_report_ops: challenge_report_db_ops,
+        network,
+    };
+
+    // TODO: wrap these in `strata-tasks`
+    let watch_task = tokio::spawn(async move {
+        if let Err(e) = watcher.start(duty_polling_interval).await {
+            error!(error = %e, "could not start watcher");
+        };
+    });
+
+    // Wait for all tasks to run
+    // They are supposed to run indefinitely in most cases
+    tokio::try_join!(rpc_task, watch_task)?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! Defines magic values for the bridge client in challenger mode.
+//!
+//! Separating these out so that they can split into configurable items later if necessary.
+
+use strata_primitives::bridge::OperatorIdx;
+
+/// The number of threads allocated in the [`ThreadPool`](threadpool::ThreadPool) for the database
+/// I/O operations.
+///
+/// This is set to be the sum of:
+///
+/// * `1` for `duty` db as the challenger only serves duty statuses over RPC.
+/// * `1` for `challenge_report` db as reports are written sequentially by the watcher.
+pub(super) const DB_THREAD_COUNT: usize = 1 + 1;
+
+/// The operator index used when querying the rollup for bridge duties.
+///
+/// The challenger is not an operator. The full node does not filter the duties it returns by the
+/// operator index, so any value works here but a sentinel makes the intent clear in the node's
+/// logs.
+pub(super) const CHALLENGER_OPERATOR_IDX: OperatorIdx = OperatorIdx::MAX;
+
+/// The number of bitcoin blocks below the last scanned one that the challenger rescans on
+/// startup.
+///
+/// The scanned blocks are persisted but the fulfillments seen in them are not, so this bounds how
+/// far back a fulfillment is picked up again before its withdrawal is checked against its
+/// deadline.
+pub(super) const L1_RESCAN_DEPTH: u64 = 144;
@@ -1,5 +1,7 @@
 //! Defines the main loop for the bridge-client in challenger mode.
 
-pub(crate) async fn bootstrap() {
-    unimplemented!("Challenger mode");
-}
+mod bootstrap;
+mod constants;
+mod watcher;
+
+pub(crate) use bootstrap::*;
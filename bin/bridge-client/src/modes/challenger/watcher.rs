@@ -0,0 +1,710 @@
+//! Watches the bridge duties assigned by the rollup and the bitcoin chain for operator
+//! misbehaviour.
+//!
+//! The watcher keeps track of the deposit UTXOs in the rollup's deposits table, of the withdrawals
+//! dispatched against them and of the reimbursements due for fulfilled withdrawals. It then scans
+//! every new bitcoin block for withdrawal fulfillments and for transactions that spend one of the
+//! deposit UTXOs and reports an operator fault if:
+//!
+//! * a deposit UTXO is spent by a transaction that does not pay out exactly what either the
+//!   reimbursement of the operator that fulfilled its withdrawal or the withdrawal itself would, or
+//! * a dispatched withdrawal is not fulfilled before its `exec_deadline`.
+//!
+//! The hash of every scanned block is persisted, so that the scan resumes where it left off
+//! across restarts and rolls back to the fork point when bitcoin reorgs, before any fault is
+//! reported on the new chain.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use bitcoin::{Network, OutPoint, ScriptBuf, Transaction};
+use strata_bridge_tx_builder::{
+    prelude::{withdrawal_fulfillment_script, CooperativeWithdrawalInfo, TxBuildContext},
+    TxKind,
+};
+use strata_btcio::rpc::traits::Reader;
+use strata_primitives::{
+    bridge::{BitcoinBlockHeight, WithdrawOutput},
+    buf::Buf32,
+    l1::XOnlyPk,
+};
+use strata_rpc_api::StrataApiClient;
+use strata_rpc_types::RpcBridgeDuties;
+use strata_state::{
+    bridge_duties::{BridgeDuty, ChallengeReport, OperatorFault},
+    bridge_state::DispatchCommand,
+};
+use strata_storage::ops::challenge_report::ChallengeReportOps;
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+
+use super::constants::{CHALLENGER_OPERATOR_IDX, L1_RESCAN_DEPTH};
+
+pub(super) struct Watcher<L1Client, L2Client>
+where
+    L1Client: Reader + Sync + Send,
+    L2Client: StrataApiClient + Sync + Send,
+{
+    pub(super) l1_rpc_client: Arc<L1Client>,
+    pub(super) l2_rpc_client: L2Client,
+    pub(super) challenge_report_ops: Arc<ChallengeReportOps>,
+    pub(super) network: Network,
+}
+
+/// The view of the bridge that the [`Watcher`] builds up across polls.
+#[derive(Debug, Default)]
+struct WatchState {
+    /// The deposit UTXOs currently tracked in the rollup's deposits table.
+    deposits: HashSet<OutPoint>,
+
+    /// The withdrawals currently dispatched by the rollup keyed by the deposit UTXO that they
+    /// are to be serviced from.
+    withdrawals: HashMap<OutPoint, CooperativeWithdrawalInfo>,
+
+    /// The metadata scripts that mark the fulfillment of the dispatched withdrawals by their
+    /// assignees, mapped to the deposit UTXO of the withdrawal.
+    fulfillment_scripts: HashMap<ScriptBuf, OutPoint>,
+
+    /// The unsigned transactions that are allowed to spend each deposit UTXO, i.e. the
+    /// reimbursement currently due and the withdrawal paid out of the deposit itself.
+    authorized_spends: HashMap<OutPoint, Vec<Transaction>>,
+
+    /// The deposit UTXOs whose withdrawal has been observed being fulfilled on bitcoin, mapped to
+    /// the height of the block the fulfillment was seen in.
+    fulfilled: HashMap<OutPoint, BitcoinBlockHeight>,
+
+    /// The deposit UTXOs that have been observed being spent on bitcoin, mapped to the height of
+    /// the block the spend was seen in.
+    spent: HashMap<OutPoint, BitcoinBlockHeight>,
+
+    /// The index from which to fetch the next batch of duties.
+    duty_index: u64,
+
+    /// The next bitcoin block height to scan, if scanning has started.
+    next_l1_height: Option<BitcoinBlockHeight>,
+}
+
+impl<L1Client, L2Client> Watcher<L1Client, L2Client>
+where
+    L1Client: Reader + Sync + Send,
+    L2Client: StrataApiClient + Sync + Send,
+{
+    pub(super) async fn start(&self, polling_interval: Duration) -> anyhow::Result<()> {
+        info!(?polling_interval, "Starting to watch operators");
+
+        let mut state = WatchState::default();
+        loop {
+            if let Err(e) = self.poll(&mut state).await {
+                // the state is only advanced on success, so the next poll picks up from where
+                // this one failed.
+                warn!(error = %e, "could not complete watch cycle");
+            }
+
+            sleep(polling_interval).await;
+        }
+    }
+
+    /// Refreshes the view of the bridge and checks it against bitcoin.
+    async fn poll(&self, state: &mut WatchState) -> anyhow::Result<()> {
+        self.sync_duties(state).await?;
+        self.sync_deposits(state).await?;
+
+        let tip_height = self.scan_l1(state).await?;
+        self.check_deadlines(state, tip_height).await?;
+
+        Ok(())
+    }
+
+    /// Fetches the withdrawal and reimbursement duties currently due according to the rollup.
+    ///
+    /// These duties are always computed from the current chain state, so the previous sets are
+    /// replaced entirely. This also picks up reassignments to a different operator.
+    async fn sync_duties(&self, state: &mut WatchState) -> anyhow::Result<()> {
+        let RpcBridgeDuties {
+            duties, stop_index, ..
+        } = self
+            .l2_rpc_client
+            .get_bridge_duties(CHALLENGER_OPERATOR_IDX, state.duty_index)
+            .await?;
+
+        // The spends are matched by their outputs, so build them the same way the operators do.
+        let pubkey_table = self
+            .l2_rpc_client
+            .get_active_operator_chain_pubkey_set()
+            .await?;
+        let retired_pubkey_table = self
+            .l2_rpc_client
+            .get_retired_operator_chain_pubkey_set()
+            .await?;
+        let build_context =
+            TxBuildContext::new(self.network, pubkey_table, CHALLENGER_OPERATOR_IDX)
+                .with_retired_pubkeys(retired_pubkey_table);
+
+        state.withdrawals.clear();
+        state.authorized_spends.clear();
+        for duty in duties {
+            match duty {
+                BridgeDuty::FulfillWithdrawal(withdrawal) => {
+                    let deposit_outpoint = withdrawal.deposit_outpoint();
+                    match withdrawal.construct_signing_data(&build_context) {
+                        Ok(signing_data) => {
+                            let tx = signing_data.psbt.inner().unsigned_tx.clone();
+                            state
+                                .authorized_spends
+                                .entry(deposit_outpoint)
+                                .or_default()
+                                .push(tx);
+                        }
+                        Err(err) => {
+                            warn!(%err, %deposit_outpoint, "could not build withdrawal tx");
+                        }
+                    }
+
+                    state.withdrawals.insert(deposit_outpoint, withdrawal);
+                }
+                BridgeDuty::ReimburseOperator(reimbursement) => {
+                    let deposit_outpoint = reimbursement.deposit_outpoint();
+                    match reimbursement.construct_signing_data(&build_context) {
+                        Ok(signing_data) => {
+                            let tx = signing_data.psbt.inner().unsigned_tx.clone();
+                            state
+                                .authorized_spends
+                                .entry(deposit_outpoint)
+                                .or_default()
+                                .push(tx);
+                        }
+                        Err(err) => {
+                            warn!(%err, %deposit_outpoint, "could not build reimbursement tx");
+                        }
+                    }
+                }
+                BridgeDuty::SignDeposit(_) => {}
+            }
+        }
+        state.fulfillment_scripts = state
+            .withdrawals
+            .iter()
+            .map(|(deposit_outpoint, withdrawal)| {
+                let script = withdrawal_fulfillment_script(
+                    deposit_outpoint,
+                    withdrawal.assigned_operator_idx(),
+                );
+                (script, *deposit_outpoint)
+            })
+            .collect();
+        state.duty_index = stop_index;
+
+        debug!(
+            num_withdrawals = state.withdrawals.len(),
+            num_authorized_spends = state.authorized_spends.len(),
+            "synced duties"
+        );
+
+        Ok(())
+    }
+
+    /// Fetches the deposit UTXOs currently tracked by the rollup.
+    async fn sync_deposits(&self, state: &mut WatchState) -> anyhow::Result<()> {
+        let deposit_ids = self.l2_rpc_client.get_current_deposits().await?;
+
+        let mut deposits = HashSet::with_capacity(deposit_ids.len());
+        for deposit_id in deposit_ids {
+            let entry = self
+                .l2_rpc_client
+                .get_current_deposit_by_id(deposit_id)
+                .await?;
+
+            deposits.insert(*entry.output().outpoint());
+        }
+        state.deposits = deposits;
+
+        // deposits that are no longer tracked are done with, so forget what was seen of them.
+        let WatchState {
+            deposits,
+            fulfilled,
+            spent,
+            ..
+        } = state;
+        fulfilled.retain(|deposit_outpoint, _| deposits.contains(deposit_outpoint));
+        spent.retain(|deposit_outpoint, _| deposits.contains(deposit_outpoint));
+
+        debug!(num_deposits = state.deposits.len(), "synced deposits");
+
+        Ok(())
+    }
+
+    /// Scans the bitcoin blocks since the last scan for withdrawal fulfillments and spends of
+    /// deposit UTXOs and returns the height of the current tip.
+    ///
+    /// If the blocks scanned before have been reorged out, the scan restarts from the fork point
+    /// and whatever was seen in the orphaned blocks is forgotten.
+    async fn scan_l1(&self, state: &mut WatchState) -> anyhow::Result<BitcoinBlockHeight> {
+        let tip_height = self.l1_rpc_client.get_block_count().await?;
+        let next_height = match state.next_l1_height {
+            Some(next_height) => next_height,
+            None => self
+                .challenge_report_ops
+                .get_last_scanned_height_async()
+                .await?
+                .map_or(tip_height, |last_height| last_height + 1)
+                .saturating_sub(L1_RESCAN_DEPTH),
+        };
+
+        let start_height = self.find_fork_point(next_height, tip_height).await?;
+        if start_height < next_height {
+            warn!(%start_height, %next_height, "bitcoin reorged, rolling back scan");
+
+            state.fulfilled.retain(|_, seen_at| *seen_at < start_height);
+            state.spent.retain(|_, seen_at| *seen_at < start_height);
+            state.next_l1_height = Some(start_height);
+        }
+
+        for height in start_height..=tip_height {
+            let block = self.l1_rpc_client.get_block_at(height).await?;
+
+            // the chain may have reorged since the fork point was found, in which case the next
+            // poll rolls back.
+            let parent_hash = Buf32::from(block.header.prev_blockhash);
+            if self
+                .get_scanned_block(height.checked_sub(1))
+                .await?
+                .is_some_and(|scanned_hash| scanned_hash != parent_hash)
+            {
+                bail!("bitcoin reorged during scan at height {height}");
+            }
+
+            for tx in &block.txdata {
+                for deposit_outpoint in find_fulfillments(tx, &state.fulfillment_scripts) {
+                    let Some(withdrawal) = state.withdrawals.get(&deposit_outpoint) else {
+                        continue;
+                    };
+
+                    if pays_users(tx, withdrawal) {
+                        let txid = tx.compute_txid();
+                        info!(%deposit_outpoint, %txid, "withdrawal fulfilled");
+
+                        state.fulfilled.entry(deposit_outpoint).or_insert(height);
+                    }
+                }
+
+                for deposit_outpoint in find_deposit_spends(tx, &state.deposits) {
+                    let authorized_spends = state
+                        .authorized_spends
+                        .get(&deposit_outpoint)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+
+                    match classify_spend(tx, authorized_spends) {
+                        Some(fault) => self.report(deposit_outpoint, fault, height).await?,
+                        None => {
+                            let txid = tx.compute_txid();
+                            info!(%deposit_outpoint, %txid, "deposit spent as authorized");
+                        }
+                    }
+
+                    state.spent.entry(deposit_outpoint).or_insert(height);
+                }
+            }
+
+            self.challenge_report_ops
+                .put_scanned_block_async(height, block.block_hash().into())
+                .await?;
+            state.next_l1_height = Some(height + 1);
+        }
+
+        Ok(tip_height)
+    }
+
+    /// Walks back from the next height to scan until the last block scanned below it is still
+    /// part of the bitcoin chain and returns the height to resume scanning from.
+    async fn find_fork_point(
+        &self,
+        next_height: BitcoinBlockHeight,
+        tip_height: BitcoinBlockHeight,
+    ) -> anyhow::Result<BitcoinBlockHeight> {
+        let mut height = next_height;
+        while let Some(scanned_hash) = self.get_scanned_block(height.checked_sub(1)).await? {
+            let parent_height = height - 1;
+            if parent_height <= tip_height {
+                let block_hash = self.l1_rpc_client.get_block_hash(parent_height).await?;
+                if Buf32::from(block_hash) == scanned_hash {
+                    break;
+                }
+            }
+
+            height = parent_height;
+        }
+
+        Ok(height)
+    }
+
+    /// Gets the hash of the block scanned at the given height, if any.
+    async fn get_scanned_block(
+        &self,
+        height: Option<BitcoinBlockHeight>,
+    ) -> anyhow::Result<Option<Buf32>> {
+        let Some(height) = height else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .challenge_report_ops
+            .get_scanned_block_async(height)
+            .await?)
+    }
+
+    /// Reports the withdrawals whose deadline has passed without them being fulfilled.
+    async fn check_deadlines(
+        &self,
+        state: &WatchState,
+        tip_height: BitcoinBlockHeight,
+    ) -> anyhow::Result<()> {
+        for (deposit_outpoint, withdrawal) in &state.withdrawals {
+            let handled = state.fulfilled.contains_key(deposit_outpoint)
+                || state.spent.contains_key(deposit_outpoint);
+            if handled || !withdrawal.is_expired_at(tip_height) {
+                continue;
+            }
+
+            let fault = OperatorFault::MissedWithdrawalDeadline {
+                assignee: withdrawal.assigned_operator_idx(),
+                exec_deadline: withdrawal.exec_deadline(),
+            };
+
+            self.report(*deposit_outpoint, fault, tip_height).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Persists a [`ChallengeReport`] unless the same fault has already been reported for the
+    /// deposit.
+    ///
+    /// Reports are keyed by [`ChallengeReport::id`], so different faults on the same deposit are
+    /// all kept.
+    async fn report(
+        &self,
+        deposit_outpoint: OutPoint,
+        fault: OperatorFault,
+        detected_at: BitcoinBlockHeight,
+    ) -> anyhow::Result<()> {
+        let report = ChallengeReport::new(deposit_outpoint.into(), fault, detected_at);
+        let report_id = report.id();
+
+        if self
+            .challenge_report_ops
+            .get_report_async(report_id)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        warn!(%deposit_outpoint, fault = ?report.fault(), %detected_at, "detected operator fault");
+
+        self.challenge_report_ops
+            .put_report_async(report_id, report)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Finds the deposit UTXOs from the given set that are spent by the transaction.
+fn find_deposit_spends<'a>(
+    tx: &'a Transaction,
+    deposits: &'a HashSet<OutPoint>,
+) -> impl Iterator<Item = OutPoint> + 'a {
+    tx.input
+        .iter()
+        .map(|input| input.previous_output)
+        .filter(|prevout| deposits.contains(prevout))
+}
+
+/// Finds the deposit UTXOs whose withdrawal the transaction claims to fulfill.
+fn find_fulfillments<'a>(
+    tx: &'a Transaction,
+    fulfillment_scripts: &'a HashMap<ScriptBuf, OutPoint>,
+) -> impl Iterator<Item = OutPoint> + 'a {
+    tx.output
+        .iter()
+        .filter_map(|output| fulfillment_scripts.get(&output.script_pubkey).copied())
+}
+
+/// Checks if the transaction pays out every user in the withdrawal batch, by the same rules the
+/// rollup applies to withdrawal fulfillments.
+fn pays_users(tx: &Transaction, withdrawal: &CooperativeWithdrawalInfo) -> bool {
+    // every taproot output is a potential payout to a user
+    let outputs: Vec<WithdrawOutput> = tx
+        .output
+        .iter()
+        .filter(|output| output.script_pubkey.is_p2tr())
+        .filter_map(|output| {
+            // p2tr script is `OP_1 OP_PUSHBYTES_32 <output key>`
+            let output_key = Buf32::try_from(&output.script_pubkey.as_bytes()[2..34]).ok()?;
+
+            Some(WithdrawOutput::new(
+                XOnlyPk::new(output_key),
+                output.value.into(),
+            ))
+        })
+        .collect();
+
+    DispatchCommand::new(withdrawal.withdraw_outputs().to_vec()).is_paid_by(&outputs)
+}
+
+/// Classifies a transaction that spends a deposit UTXO.
+///
+/// A spend is only valid if it pays out exactly what one of the authorized spends of the deposit
+/// does, i.e. the reimbursement of the operator that fulfilled the withdrawal or the withdrawal
+/// paid out of the deposit by its assignee. Otherwise, the spend is an
+/// [`OperatorFault::UnauthorizedDepositSpend`].
+fn classify_spend(tx: &Transaction, authorized_spends: &[Transaction]) -> Option<OperatorFault> {
+    if authorized_spends
+        .iter()
+        .any(|authorized| pays_same_outputs(tx, authorized))
+    {
+        return None;
+    }
+
+    Some(OperatorFault::UnauthorizedDepositSpend {
+        spending_txid: tx.compute_txid().into(),
+    })
+}
+
+/// Checks if the transaction pays the same amounts to the same scripts, in the same order, as the
+/// expected one.
+fn pays_same_outputs(tx: &Transaction, expected: &Transaction) -> bool {
+    tx.output.len() == expected.output.len()
+        && tx
+            .output
+            .iter()
+            .zip(&expected.output)
+            .all(|(output, expected)| {
+                output.script_pubkey == expected.script_pubkey && output.value == expected.value
+            })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::{
+        absolute::LockTime,
+        hashes::{sha256d, Hash},
+        key::Keypair,
+        secp256k1::{SecretKey, SECP256K1},
+        transaction::Version,
+        Amount, ScriptBuf, TxIn, TxOut, Txid,
+    };
+    use strata_bridge_tx_builder::prelude::{operator_fee, BRIDGE_DENOMINATION};
+    use strata_primitives::l1::BitcoinAmount;
+
+    use super::*;
+
+    fn user_pk(seed: u8) -> XOnlyPk {
+        let sk = SecretKey::from_slice(&[seed; 32]).expect("must be a valid secret key");
+        let (x_only_pk, _) = Keypair::from_secret_key(SECP256K1, &sk).x_only_public_key();
+
+        XOnlyPk::new(x_only_pk.into())
+    }
+
+    fn deposit_outpoint() -> OutPoint {
+        OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[1u8; 32])), 0)
+    }
+
+    fn spending_tx(prevout: OutPoint, script_pubkey: ScriptBuf) -> Transaction {
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: prevout,
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(1_000),
+                script_pubkey,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_find_deposit_spends() {
+        let deposits = HashSet::from([deposit_outpoint()]);
+
+        let tx = spending_tx(deposit_outpoint(), ScriptBuf::new());
+        let spends: Vec<OutPoint> = find_deposit_spends(&tx, &deposits).collect();
+        assert_eq!(
+            spends,
+            vec![deposit_outpoint()],
+            "deposit spend must be found"
+        );
+
+        let other_outpoint = OutPoint::new(deposit_outpoint().txid, 1);
+        let tx = spending_tx(other_outpoint, ScriptBuf::new());
+        assert!(
+            find_deposit_spends(&tx, &deposits).next().is_none(),
+            "unrelated spends must be ignored"
+        );
+    }
+
+    #[test]
+    fn test_find_fulfillments() {
+        let fulfillment_scripts = HashMap::from([(
+            withdrawal_fulfillment_script(&deposit_outpoint(), 1),
+            deposit_outpoint(),
+        )]);
+
+        let mut tx = spending_tx(OutPoint::null(), ScriptBuf::new());
+        assert!(
+            find_fulfillments(&tx, &fulfillment_scripts)
+                .next()
+                .is_none(),
+            "transactions without the metadata must be ignored"
+        );
+
+        tx.output.push(TxOut {
+            value: Amount::ZERO,
+            script_pubkey: withdrawal_fulfillment_script(&deposit_outpoint(), 0),
+        });
+        assert!(
+            find_fulfillments(&tx, &fulfillment_scripts)
+                .next()
+                .is_none(),
+            "fulfillments by other operators than the assignee must be ignored"
+        );
+
+        tx.output.push(TxOut {
+            value: Amount::ZERO,
+            script_pubkey: withdrawal_fulfillment_script(&deposit_outpoint(), 1),
+        });
+        let fulfillments: Vec<OutPoint> = find_fulfillments(&tx, &fulfillment_scripts).collect();
+        assert_eq!(
+            fulfillments,
+            vec![deposit_outpoint()],
+            "fulfillment must be found"
+        );
+    }
+
+    #[test]
+    fn test_classify_spend() {
+        let reimbursement = spending_tx(deposit_outpoint(), ScriptBuf::new());
+        let withdrawal = spending_tx(deposit_outpoint(), ScriptBuf::from_bytes(vec![0x52]));
+        let authorized_spends = [reimbursement.clone(), withdrawal.clone()];
+
+        assert!(
+            classify_spend(&reimbursement, &authorized_spends).is_none(),
+            "spend reimbursing the operator must be valid"
+        );
+        assert!(
+            classify_spend(&withdrawal, &authorized_spends).is_none(),
+            "withdrawal paid out of the deposit must be valid"
+        );
+
+        let mut short = reimbursement.clone();
+        short.output[0].value -= Amount::from_sat(1);
+        assert_eq!(
+            classify_spend(&short, &authorized_spends),
+            Some(OperatorFault::UnauthorizedDepositSpend {
+                spending_txid: short.compute_txid().into()
+            }),
+            "spend paying a different amount must be a fault"
+        );
+
+        let theft = spending_tx(deposit_outpoint(), ScriptBuf::from_bytes(vec![0x51]));
+        assert_eq!(
+            classify_spend(&theft, &authorized_spends),
+            Some(OperatorFault::UnauthorizedDepositSpend {
+                spending_txid: theft.compute_txid().into()
+            }),
+            "spend other than the authorized ones must be a fault"
+        );
+
+        assert!(
+            classify_spend(&reimbursement, &[]).is_some(),
+            "spend without a due reimbursement or withdrawal must be a fault"
+        );
+    }
+
+    #[test]
+    fn test_report_ids() {
+        let fault = OperatorFault::MissedWithdrawalDeadline {
+            assignee: 0,
+            exec_deadline: 100,
+        };
+        let report = ChallengeReport::new(deposit_outpoint().into(), fault.clone(), 101);
+
+        assert_eq!(
+            report.id(),
+            ChallengeReport::new(deposit_outpoint().into(), fault, 105).id(),
+            "the same fault must be reported under the same id"
+        );
+
+        let reassigned = OperatorFault::MissedWithdrawalDeadline {
+            assignee: 1,
+            exec_deadline: 200,
+        };
+        assert_ne!(
+            report.id(),
+            ChallengeReport::new(deposit_outpoint().into(), reassigned, 201).id(),
+            "a later fault on the same deposit must not replace the earlier one"
+        );
+    }
+
+    #[test]
+    fn test_pays_users_batch() {
+        let network = Network::Regtest;
+        let withdraw_outputs = vec![
+            WithdrawOutput::new(user_pk(1), BitcoinAmount::from_int_btc(4)),
+            WithdrawOutput::new(user_pk(2), BitcoinAmount::from_int_btc(5)),
+        ];
+        let withdrawal = CooperativeWithdrawalInfo::new(
+            deposit_outpoint(),
+            BRIDGE_DENOMINATION.into(),
+            vec![0],
+            withdraw_outputs,
+            0,
+            100,
+        );
+
+        let user_script_pubkeys = [user_pk(1), user_pk(2)].map(|pk| {
+            pk.to_p2tr_address(network)
+                .expect("must be a valid address")
+                .script_pubkey()
+        });
+
+        let payouts = [4, 5].map(|btc| {
+            let amt = Amount::from_int_btc(btc);
+            amt - operator_fee(amt)
+        });
+
+        let mut partial = spending_tx(OutPoint::null(), user_script_pubkeys[0].clone());
+        partial.output[0].value = payouts[0];
+        assert!(
+            !pays_users(&partial, &withdrawal),
+            "tx paying only some of the users must not fulfill the withdrawal"
+        );
+
+        let mut short = partial.clone();
+        short.output.push(TxOut {
+            value: payouts[1] - Amount::from_sat(1),
+            script_pubkey: user_script_pubkeys[1].clone(),
+        });
+        assert!(
+            !pays_users(&short, &withdrawal),
+            "tx paying a user less than the withdrawal must not fulfill it"
+        );
+
+        let mut fulfillment = partial;
+        fulfillment.output.push(TxOut {
+            value: payouts[1],
+            script_pubkey: user_script_pubkeys[1].clone(),
+        });
+        assert!(
+            pays_users(&fulfillment, &withdrawal),
+            "tx paying all the users must fulfill the withdrawal"
+        );
+    }
+}
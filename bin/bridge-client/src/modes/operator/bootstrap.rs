@@ -1,28 +1,38 @@
 //! Module to bootstrap the operator node by hooking up all the required services.
 
-use std::{path::PathBuf, sync::Arc, time::Duration};
-
-use bitcoin::{
-    key::{Keypair, Parity},
-    secp256k1::{PublicKey, SecretKey, XOnlyPublicKey, SECP256K1},
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
 };
+
+use bitcoin::Network;
 use jsonrpsee::{core::client::async_client::Client as L2RpcClient, ws_client::WsClientBuilder};
 use strata_bridge_exec::handler::ExecHandler;
-use strata_bridge_sig_manager::prelude::SignatureManager;
-use strata_bridge_tx_builder::prelude::TxBuildContext;
+use strata_bridge_sig_manager::{
+    prelude::{BridgeSigner, KeypairSigner, SignatureManager},
+    remote::RemoteSigner,
+};
+use strata_bridge_tx_builder::prelude::{BuildContext, TxBuildContext};
 use strata_btcio::rpc::{traits::Reader, BitcoinClient};
 use strata_primitives::bridge::OperatorIdx;
 use strata_rocksdb::{
-    bridge::db::{BridgeDutyIndexRocksDb, BridgeDutyRocksDb, BridgeTxRocksDb},
+    bridge::db::{
+        BridgeDutyIndexRocksDb, BridgeDutyRocksDb, BridgeTxRocksDb, ChallengeReportRocksDb,
+        SignerSessionRocksDb,
+    },
     DbOpsConfig,
 };
 use strata_rpc_api::StrataApiClient;
 use strata_storage::ops::{
     bridge::Context as TxContext, bridge_duty::Context as DutyContext,
     bridge_duty_index::Context as DutyIndexContext,
+    challenge_report::Context as ChallengeReportContext,
+    signer_session::Context as SignerSessionContext,
 };
 use threadpool::ThreadPool;
-use tracing::{error, info};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
 
 use super::{constants::DB_THREAD_COUNT, task_manager::TaskManager};
 use crate::{
@@ -59,6 +69,12 @@ pub(crate) async fn bootstrap(args: Cli) -> anyhow::Result<()> {
     let bridge_duty_idx_db_ctx = DutyIndexContext::new(Arc::new(bridge_duty_idx_db));
     let bridge_duty_idx_db_ops = Arc::new(bridge_duty_idx_db_ctx.into_ops(bridge_db_pool.clone()));
 
+    // The operator does not produce challenge reports but still serves them over RPC.
+    let challenge_report_db = ChallengeReportRocksDb::new(rbdb.clone(), ops_config);
+    let challenge_report_db_ctx = ChallengeReportContext::new(Arc::new(challenge_report_db));
+    let challenge_report_db_ops =
+        Arc::new(challenge_report_db_ctx.into_ops(bridge_db_pool.clone()));
+
     // Setup RPC clients.
     let l1_rpc_client = Arc::new(
         BitcoinClient::new(args.btc_url, args.btc_user, args.btc_pass)
@@ -69,27 +85,42 @@ pub(crate) async fn bootstrap(args: Cli) -> anyhow::Result<()> {
         .await
         .expect("failed to connect to the rollup RPC server");
 
-    // Get the keypair after deriving the wallet xpriv.
-    let operator_keys = resolve_xpriv(args.master_xpriv, args.master_xpriv_path)?;
-    let wallet_xpriv = operator_keys.wallet_xpriv();
-
-    let mut keypair = wallet_xpriv.to_keypair(SECP256K1);
-    let mut sk = SecretKey::from_keypair(&keypair);
-
-    // adjust for parity, which should always be even
-    let (_, parity) = XOnlyPublicKey::from_keypair(&keypair);
-    if matches!(parity, Parity::Odd) {
-        sk = sk.negate();
-        keypair = Keypair::from_secret_key(SECP256K1, &sk);
+    // Set up the signer, either with a separate signing service or with the keypair derived from
+    // the wallet xpriv.
+    let signer: Arc<dyn BridgeSigner> = match args.signer_socket {
+        Some(socket) => {
+            info!(%socket, "using remote signer");
+            Arc::new(RemoteSigner::connect(&socket).await?)
+        }
+        None => {
+            let operator_keys = resolve_xpriv(args.master_xpriv, args.master_xpriv_path)?;
+
+            // Persist the signing sessions so that the transactions being signed can still be
+            // signed after a restart.
+            let signer_session_db = SignerSessionRocksDb::new(rbdb.clone(), ops_config);
+            let signer_session_db_ctx = SignerSessionContext::new(Arc::new(signer_session_db));
+            let signer_session_db_ops =
+                Arc::new(signer_session_db_ctx.into_ops(bridge_db_pool.clone()));
+
+            Arc::new(
+                KeypairSigner::from_wallet_xpriv(operator_keys.wallet_xpriv())
+                    .with_session_ops(signer_session_db_ops)
+                    .await?,
+            )
+        }
     };
+    let pubkey = signer.pubkey();
 
-    let pubkey = PublicKey::from_secret_key(SECP256K1, &sk);
-
-    // Get this client's pubkey from the bitcoin wallet.
+    // Get this client's pubkey from the bitcoin wallet.  An operator that was removed from the
+    // active set still signs for the deposits it's a notary of.
     let operator_pubkeys = l2_rpc_client.get_active_operator_chain_pubkey_set().await?;
+    let retired_pubkeys = l2_rpc_client
+        .get_retired_operator_chain_pubkey_set()
+        .await?;
     let own_index: OperatorIdx = operator_pubkeys
         .0
         .iter()
+        .chain(retired_pubkeys.0.iter())
         .find_map(|(id, pk)| if pk == &pubkey { Some(*id) } else { None })
         .expect("could not find this operator's pubkey in the rollup pubkey table");
 
@@ -99,14 +130,15 @@ pub(crate) async fn bootstrap(args: Cli) -> anyhow::Result<()> {
     let bridge_tx_db = BridgeTxRocksDb::new(rbdb, ops_config);
     let bridge_tx_db_ctx = TxContext::new(Arc::new(bridge_tx_db));
     let bridge_tx_db_ops = Arc::new(bridge_tx_db_ctx.into_ops(bridge_db_pool));
-    let sig_manager = SignatureManager::new(bridge_tx_db_ops, own_index, keypair);
+    let sig_manager = SignatureManager::new(bridge_tx_db_ops, own_index, signer.clone());
 
     // Set up the TxBuildContext.
     let network = l1_rpc_client.network().await?;
-    let tx_context = TxBuildContext::new(network, operator_pubkeys, own_index);
+    let tx_context = TxBuildContext::new(network, operator_pubkeys, own_index)
+        .with_retired_pubkeys(retired_pubkeys);
 
     // Spawn RPC server.
-    let bridge_rpc = BridgeRpc::new(bridge_duty_db_ops.clone());
+    let bridge_rpc = BridgeRpc::new(bridge_duty_db_ops.clone(), challenge_report_db_ops);
 
     let rpc_host = args.rpc_host.as_deref().unwrap_or(DEFAULT_RPC_HOST);
     let rpc_port = args.rpc_port.unwrap_or(DEFAULT_RPC_PORT);
@@ -129,17 +161,17 @@ pub(crate) async fn bootstrap(args: Cli) -> anyhow::Result<()> {
     );
 
     // Spawn poll duties task.
-    let exec_handler = ExecHandler {
-        tx_build_ctx: tx_context,
+    let exec_handler = Arc::new(ExecHandler {
+        tx_build_ctx: RwLock::new(tx_context),
         sig_manager,
         l2_rpc_client,
-        keypair,
+        signer,
         own_index,
         msg_polling_interval,
-    };
+    });
 
     let task_manager = TaskManager {
-        exec_handler: Arc::new(exec_handler),
+        exec_handler: exec_handler.clone(),
         broadcaster: l1_rpc_client,
         bridge_duty_db_ops,
         bridge_duty_idx_db_ops,
@@ -165,9 +197,52 @@ pub(crate) async fn bootstrap(args: Cli) -> anyhow::Result<()> {
         };
     });
 
+    let pubkey_task = tokio::spawn(refresh_pubkey_tables(
+        exec_handler,
+        network,
+        duty_polling_interval,
+    ));
+
     // Wait for all tasks to run
     // They are supposed to run indefinitely in most cases
-    tokio::try_join!(rpc_task, duty_task)?;
+    tokio::try_join!(rpc_task, duty_task, pubkey_task)?;
 
     Ok(())
 }
+
+/// Keeps the pubkey tables in the build context in sync with the operator set on the rollup, which
+/// can change at the end of every epoch.
+async fn refresh_pubkey_tables(
+    exec_handler: Arc<ExecHandler<L2RpcClient, TxBuildContext>>,
+    network: Network,
+    interval: Duration,
+) {
+    loop {
+        sleep(interval).await;
+
+        let l2_rpc_client = &exec_handler.l2_rpc_client;
+        let pubkey_tables = tokio::try_join!(
+            l2_rpc_client.get_active_operator_chain_pubkey_set(),
+            l2_rpc_client.get_retired_operator_chain_pubkey_set(),
+        );
+        let (operator_pubkeys, retired_pubkeys) = match pubkey_tables {
+            Ok(pubkey_tables) => pubkey_tables,
+            Err(e) => {
+                warn!(error = %e, "could not fetch the operator pubkeys");
+                continue;
+            }
+        };
+
+        let tx_context = exec_handler.build_context();
+        if tx_context.pubkey_table() == &operator_pubkeys
+            && tx_context.retired_pubkey_table() == &retired_pubkeys
+        {
+            continue;
+        }
+
+        info!("operator set changed, updating the build context");
+        let tx_context = TxBuildContext::new(network, operator_pubkeys, exec_handler.own_index)
+            .with_retired_pubkeys(retired_pubkeys);
+        exec_handler.set_build_context(tx_context);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Separating these out so that they can split into configurable items later if necessary.
 
+use std::time::Duration;
+
 /// The number of threads allocated in the [`ThreadPool`](threadpool::ThreadPool) for the database
 /// I/O operations.
 ///
@@ -15,3 +17,6 @@
 ///
 /// At the moment, the threadpool is only used for channel-based db operations.
 pub(super) const DB_THREAD_COUNT: usize = 2 + 1 + 2;
+
+/// How long a withdrawal fulfillment may stay unconfirmed before its fee is bumped.
+pub(super) const FULFILLMENT_BUMP_AFTER: Duration = Duration::from_secs(30 * 60);
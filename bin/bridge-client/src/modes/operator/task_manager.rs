@@ -2,15 +2,24 @@
 
 // TODO:  consider moving this module to the `bridge-exec` crate instead.
 
-use std::{fmt::Debug, sync::Arc, time::Duration};
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use bitcoin::Txid;
+use bitcoin::{consensus, Network, Transaction, Txid};
+use metrics::counter;
 use strata_bridge_exec::{
     errors::{ExecError, ExecResult},
     handler::ExecHandler,
 };
-use strata_bridge_tx_builder::{prelude::BuildContext, TxKind};
-use strata_btcio::rpc::traits::Broadcaster;
+use strata_bridge_tx_builder::{
+    prelude::{BuildContext, CooperativeWithdrawalInfo},
+    TxKind,
+};
+use strata_btcio::rpc::traits::{Broadcaster, Signer, Wallet};
+use strata_common::metrics::{BRIDGE_DUTY_STATUS, STATUS_LABEL};
 use strata_rpc_api::StrataApiClient;
 use strata_rpc_types::RpcBridgeDuties;
 use strata_state::bridge_duties::{BridgeDuty, BridgeDutyStatus};
@@ -19,13 +28,15 @@ use tokio::{
     task::JoinSet,
     time::{sleep, timeout},
 };
-use tracing::{error, info, trace, warn};
+use tracing::{debug, error, info, trace, warn};
+
+use super::constants::FULFILLMENT_BUMP_AFTER;
 
 pub(super) struct TaskManager<L2Client, TxBuildContext, Bcast>
 where
     L2Client: StrataApiClient + Sync + Send,
     TxBuildContext: BuildContext + Sync + Send,
-    Bcast: Broadcaster,
+    Bcast: Broadcaster + Wallet + Signer,
 {
     pub(super) exec_handler: Arc<ExecHandler<L2Client, TxBuildContext>>,
     pub(super) broadcaster: Arc<Bcast>,
@@ -36,8 +47,8 @@ where
 impl<L2Client, TxBuildContext, Bcast> TaskManager<L2Client, TxBuildContext, Bcast>
 where
     L2Client: StrataApiClient + Sync + Send + 'static,
-    TxBuildContext: BuildContext + Sync + Send + 'static,
-    Bcast: Broadcaster + Sync + Send + 'static,
+    TxBuildContext: BuildContext + Clone + Sync + Send + 'static,
+    Bcast: Broadcaster + Wallet + Signer + Sync + Send + 'static,
 {
     pub(super) async fn start(
         &self,
@@ -114,9 +125,10 @@ where
              *
              * The point of duty tracking is to check the status of duties.
              * There are no duty IDs in the rollup but each deposit request or deposit outpoint
-             * creates a unique deposit or withdrawal duty respectively. An external
-             * user can get the outpoint from the chainstate or bitcoin itself and then,
-             * use the `Txid` in it to query for the status of the corresponding duty.
+             * creates a unique deposit or withdrawal duty respectively, and each withdrawal
+             * fulfillment a unique reimbursement duty. An external user can get the outpoint
+             * or fulfillment from the chainstate or bitcoin itself and then, use the `Txid` in
+             * it to query for the status of the corresponding duty.
              *
              * Using the txid of the transaction that the bridge client is supposed to create is
              * not feasible as that would make tracking cumbersome since the caller will
@@ -126,6 +138,7 @@ where
             let tracker_txid = match &duty {
                 BridgeDuty::SignDeposit(deposit) => deposit.deposit_request_outpoint().txid,
                 BridgeDuty::FulfillWithdrawal(withdrawal) => withdrawal.deposit_outpoint().txid,
+                BridgeDuty::ReimburseOperator(reimbursement) => reimbursement.fulfillment_txid(),
             };
 
             let status = match self.bridge_duty_db_ops.get_status_async(tracker_txid).await {
@@ -170,8 +183,8 @@ async fn process_duty<L2Client, TxBuildContext, Bcast>(
 ) -> ExecResult<()>
 where
     L2Client: StrataApiClient + Sync + Send,
-    TxBuildContext: BuildContext + Sync + Send,
-    Bcast: Broadcaster,
+    TxBuildContext: BuildContext + Clone + Sync + Send,
+    Bcast: Broadcaster + Wallet + Signer,
 {
     match duty {
         BridgeDuty::SignDeposit(deposit_info) => {
@@ -187,16 +200,47 @@ where
             )
             .await?;
         }
-        BridgeDuty::FulfillWithdrawal(cooperative_withdrawal_info) => {
-            let tracker_txid = cooperative_withdrawal_info.deposit_outpoint().txid;
+        BridgeDuty::FulfillWithdrawal(withdrawal_info) => {
+            let tracker_txid = withdrawal_info.deposit_outpoint().txid;
+
+            // Only the assignee fronts the funds, the rest of the operators get involved once the
+            // fulfillment is seen on chain and the assignee is to be reimbursed.
+            if withdrawal_info.assigned_operator_idx() != exec_handler.own_index {
+                trace!(%tracker_txid, "withdrawal assigned to another operator, skipping");
+                return Ok(());
+            }
+
             trace!(%tracker_txid, "fulfilling withdrawal duty");
 
+            fulfill_withdrawal(
+                *exec_handler.build_context().network(),
+                broadcaster,
+                duty_status_ops,
+                tracker_txid,
+                withdrawal_info,
+            )
+            .await?;
+        }
+        BridgeDuty::ReimburseOperator(reimbursement_info) => {
+            let tracker_txid = reimbursement_info.fulfillment_txid();
+
+            // The deposit can only be spent by the operators that notarized it.
+            if !reimbursement_info
+                .notary_operators()
+                .contains(&exec_handler.own_index)
+            {
+                trace!(%tracker_txid, "not a notary of the deposit, skipping reimbursement");
+                return Ok(());
+            }
+
+            trace!(%tracker_txid, "fulfilling reimbursement duty");
+
             execute_duty(
                 exec_handler,
                 broadcaster,
                 duty_status_ops,
                 tracker_txid,
-                cooperative_withdrawal_info.clone(),
+                reimbursement_info.clone(),
             )
             .await?;
         }
@@ -233,10 +277,11 @@ async fn execute_duty<L2Client, TxBuildContext, Tx, Bcast>(
 ) -> ExecResult<()>
 where
     L2Client: StrataApiClient + Sync + Send,
-    TxBuildContext: BuildContext + Sync + Send,
+    TxBuildContext: BuildContext + Clone + Sync + Send,
     Tx: TxKind + Debug,
     Bcast: Broadcaster,
 {
+    counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "received").increment(1);
     if let Err(e) = duty_status_ops
         .put_duty_status_async(tracker_txid, BridgeDutyStatus::Received)
         .await
@@ -257,6 +302,7 @@ where
             .await
             {
                 error!(error = %e, %tracker_txid, "could not execute duty");
+                counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "failed").increment(1);
                 if let Err(e) = duty_status_ops
                     .put_duty_status_async(tracker_txid, BridgeDutyStatus::Failed(e.to_string()))
                     .await
@@ -269,6 +315,82 @@ where
         }
         Err(e) => {
             error!(error = %e, %tracker_txid, "could not execute duty");
+            counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "failed").increment(1);
+            if let Err(e) = duty_status_ops
+                .put_duty_status_async(tracker_txid, BridgeDutyStatus::Failed(e.to_string()))
+                .await
+            {
+                error!(db_err = %e, %tracker_txid, status="failed", "and could not update status in db either");
+            }
+
+            return Err(e);
+        }
+    };
+
+    counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "executed").increment(1);
+    if let Err(e) = duty_status_ops
+        .put_duty_status_async(tracker_txid, BridgeDutyStatus::Executed)
+        .await
+    {
+        error!(db_err = %e, %tracker_txid, status=?BridgeDutyStatus::Executed, "could not update status in db");
+    }
+
+    Ok(())
+}
+
+/// Fulfills a withdrawal assigned to this operator by paying it out from the operator's own wallet.
+///
+/// Like [`execute_duty`], it also updates the status of the duty. The duty is only executed once
+/// the fulfillment confirms. The fulfillment is stored before it is broadcasted and, until it
+/// confirms, it is looked up on every poll and rebroadcasted, or fee bumped if it is stuck, so that
+/// the operator never pays out the same withdrawal twice, even after a crash.
+async fn fulfill_withdrawal<Bcast>(
+    network: Network,
+    broadcaster: Arc<Bcast>,
+    duty_status_ops: Arc<BridgeDutyOps>,
+    tracker_txid: Txid,
+    withdrawal_info: &CooperativeWithdrawalInfo,
+) -> ExecResult<()>
+where
+    Bcast: Broadcaster + Wallet + Signer,
+{
+    let res = match load_fulfillment(&duty_status_ops, tracker_txid).await {
+        Ok(Some(fulfillment)) => {
+            follow_up_fulfillment(
+                broadcaster.as_ref(),
+                &duty_status_ops,
+                tracker_txid,
+                fulfillment,
+            )
+            .await
+        }
+        Ok(None) => {
+            counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "received").increment(1);
+            if let Err(e) = duty_status_ops
+                .put_duty_status_async(tracker_txid, BridgeDutyStatus::Received)
+                .await
+            {
+                warn!(error = %e, %tracker_txid, status=?BridgeDutyStatus::Received, "could not update duty status")
+            }
+
+            fund_and_broadcast(
+                network,
+                broadcaster.as_ref(),
+                &duty_status_ops,
+                tracker_txid,
+                withdrawal_info,
+            )
+            .await
+            .map(|_| false)
+        }
+        Err(e) => Err(e),
+    };
+
+    let is_confirmed = match res {
+        Ok(is_confirmed) => is_confirmed,
+        Err(e) => {
+            error!(error = %e, %tracker_txid, "could not execute duty");
+            counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "failed").increment(1);
             if let Err(e) = duty_status_ops
                 .put_duty_status_async(tracker_txid, BridgeDutyStatus::Failed(e.to_string()))
                 .await
@@ -280,6 +402,11 @@ where
         }
     };
 
+    if !is_confirmed {
+        return Ok(());
+    }
+
+    counter!(BRIDGE_DUTY_STATUS, STATUS_LABEL => "executed").increment(1);
     if let Err(e) = duty_status_ops
         .put_duty_status_async(tracker_txid, BridgeDutyStatus::Executed)
         .await
@@ -290,6 +417,164 @@ where
     Ok(())
 }
 
+/// Loads the stored transaction that fulfills the withdrawal tracked by `tracker_txid`, if the
+/// withdrawal has already been paid out.
+async fn load_fulfillment(
+    duty_status_ops: &BridgeDutyOps,
+    tracker_txid: Txid,
+) -> ExecResult<Option<Transaction>> {
+    let fulfillment_raw = duty_status_ops
+        .get_fulfillment_async(tracker_txid)
+        .await
+        .map_err(|e| ExecError::Execution(format!("could not load fulfillment: {e}")))?;
+
+    fulfillment_raw
+        .map(|raw| consensus::deserialize(&raw))
+        .transpose()
+        .map_err(|e| ExecError::Execution(format!("invalid stored fulfillment: {e}")))
+}
+
+/// Stores the transaction that fulfills the withdrawal tracked by `tracker_txid`, replacing the
+/// one stored earlier.
+async fn store_fulfillment(
+    duty_status_ops: &BridgeDutyOps,
+    tracker_txid: Txid,
+    fulfillment: &Transaction,
+) -> ExecResult<()> {
+    duty_status_ops
+        .put_fulfillment_async(tracker_txid, consensus::serialize(fulfillment))
+        .await
+        .map_err(|e| ExecError::Execution(format!("could not store fulfillment: {e}")))
+}
+
+/// Makes sure that the stored `fulfillment` of the withdrawal tracked by `tracker_txid` confirms.
+///
+/// The replacements of the fulfillment are followed and stored in its place. A fulfillment that is
+/// not confirmed yet is kept in the mempool, bumping its fee if it has been pending for longer than
+/// [`FULFILLMENT_BUMP_AFTER`].
+///
+/// Returns whether the fulfillment is confirmed.
+async fn follow_up_fulfillment<Bcast>(
+    broadcaster: &Bcast,
+    duty_status_ops: &BridgeDutyOps,
+    tracker_txid: Txid,
+    mut fulfillment: Transaction,
+) -> ExecResult<bool>
+where
+    Bcast: Broadcaster + Wallet,
+{
+    let wallet_tx = loop {
+        let txid = fulfillment.compute_txid();
+
+        // The wallet only knows about the fulfillment once it has been broadcasted, which may not
+        // have happened if the client stopped right after storing it.
+        let wallet_tx = match broadcaster.get_transaction(&txid).await {
+            Ok(wallet_tx) => wallet_tx,
+            Err(e) => {
+                debug!(%txid, %e, "withdrawal fulfillment not in the wallet, broadcasting it");
+                broadcaster
+                    .send_raw_transaction(&fulfillment)
+                    .await
+                    .map_err(|e| ExecError::Broadcast(e.to_string()))?;
+
+                return Ok(false);
+            }
+        };
+
+        let Some(replacement) = &wallet_tx.replaced_by_txid else {
+            break wallet_tx;
+        };
+
+        let replacement: Txid = replacement
+            .parse()
+            .map_err(|e| ExecError::Execution(format!("invalid replacement txid: {e}")))?;
+        fulfillment = broadcaster.get_transaction(&replacement).await?.hex;
+        store_fulfillment(duty_status_ops, tracker_txid, &fulfillment).await?;
+
+        debug!(%txid, %replacement, "following replaced withdrawal fulfillment");
+    };
+
+    let txid = wallet_tx.txid;
+    if wallet_tx.confirmations > 0 {
+        trace!(%txid, "withdrawal fulfillment confirmed");
+        return Ok(true);
+    }
+
+    let received_at = UNIX_EPOCH + Duration::from_secs(wallet_tx.time);
+    let pending_for = SystemTime::now()
+        .duration_since(received_at)
+        .unwrap_or_default();
+    if pending_for >= FULFILLMENT_BUMP_AFTER {
+        let bumped = broadcaster.bump_fee(&txid).await?;
+        let replacement = bumped.txid;
+        info!(%txid, %replacement, fee = %bumped.fee, "bumped fee of stuck fulfillment");
+
+        let replacement_tx = broadcaster.get_transaction(&replacement).await?.hex;
+        store_fulfillment(duty_status_ops, tracker_txid, &replacement_tx).await?;
+
+        return Ok(false);
+    }
+
+    // The fulfillment may have been dropped from the mempool, for example if the node restarted.
+    if let Err(e) = broadcaster.send_raw_transaction(&wallet_tx.hex).await {
+        debug!(%txid, %e, "could not rebroadcast withdrawal fulfillment");
+    }
+
+    Ok(false)
+}
+
+/// Funds the withdrawal fulfillment transaction with the operator's wallet, signs it, stores it as
+/// the fulfillment of the withdrawal tracked by `tracker_txid` and then, broadcasts it to Bitcoin.
+async fn fund_and_broadcast<Bcast>(
+    network: Network,
+    broadcaster: &Bcast,
+    duty_status_ops: &BridgeDutyOps,
+    tracker_txid: Txid,
+    withdrawal_info: &CooperativeWithdrawalInfo,
+) -> ExecResult<()>
+where
+    Bcast: Broadcaster + Wallet + Signer,
+{
+    let unfunded_tx = withdrawal_info.create_fulfillment_tx(network)?;
+
+    let funded = broadcaster
+        .fund_raw_transaction(&unfunded_tx)
+        .await
+        .map_err(|e| {
+            if e.is_insufficient_funds() {
+                ExecError::InsufficientFunds
+            } else {
+                ExecError::L1Rpc(e)
+            }
+        })?;
+
+    let signed = broadcaster
+        .sign_raw_transaction_with_wallet(&funded.hex)
+        .await?;
+    if !signed.complete {
+        return Err(ExecError::Signing(format!(
+            "wallet could not fully sign withdrawal fulfillment {}",
+            funded.hex.compute_txid()
+        )));
+    }
+
+    let signed_tx: Transaction = consensus::encode::deserialize_hex(&signed.hex)
+        .map_err(|e| ExecError::Signing(e.to_string()))?;
+
+    // Once stored, this is the only transaction ever broadcasted to pay out the withdrawal.
+    store_fulfillment(duty_status_ops, tracker_txid, &signed_tx).await?;
+
+    let txid = broadcaster
+        .send_raw_transaction(&signed_tx)
+        .await
+        .map_err(|e| ExecError::Broadcast(e.to_string()))?;
+
+    info!(%txid, fee = %funded.fee, "broadcasted withdrawal fulfillment");
+    trace!(?signed_tx, "broadcasted withdrawal fulfillment");
+
+    Ok(())
+}
+
 /// Aggregates nonces and signatures for a given [`Txid`] and then, broadcasts the fully signed
 /// transaction to Bitcoin.
 async fn aggregate_and_broadcast<L2Client, TxBuildContext, Bcast>(
@@ -299,7 +584,7 @@ async fn aggregate_and_broadcast<L2Client, TxBuildContext, Bcast>(
 ) -> ExecResult<()>
 where
     L2Client: StrataApiClient + Sync + Send,
-    TxBuildContext: BuildContext + Sync + Send,
+    TxBuildContext: BuildContext + Clone + Sync + Send,
     Bcast: Broadcaster,
 {
     exec_handler.collect_nonces(txid).await?;
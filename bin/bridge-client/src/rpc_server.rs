@@ -10,11 +10,13 @@ use strata_bridge_rpc_api::{
     StrataBridgeControlApiServer, StrataBridgeNetworkApiServer, StrataBridgeTrackerApiServer,
 };
 use strata_rpc_types::RpcServerError;
-use strata_state::bridge_duties::BridgeDutyStatus;
-use strata_storage::ops::bridge_duty::BridgeDutyOps;
+use strata_state::bridge_duties::{BridgeDutyStatus, ChallengeReport};
+use strata_storage::ops::{bridge_duty::BridgeDutyOps, challenge_report::ChallengeReportOps};
 use tokio::sync::oneshot;
 use tracing::{info, warn};
 
+use crate::constants::MAX_CHALLENGE_REPORTS_PER_REQUEST;
+
 pub(crate) async fn start<T>(rpc_impl: &T, rpc_addr: &str) -> anyhow::Result<()>
 where
     T: StrataBridgeControlApiServer
@@ -64,13 +66,18 @@ where
 pub(crate) struct BridgeRpc {
     start_time: DateTime<Utc>,
     duty_ops: Arc<BridgeDutyOps>,
+    challenge_report_ops: Arc<ChallengeReportOps>,
 }
 
 impl BridgeRpc {
-    pub fn new(duty_ops: Arc<BridgeDutyOps>) -> Self {
+    pub fn new(
+        duty_ops: Arc<BridgeDutyOps>,
+        challenge_report_ops: Arc<ChallengeReportOps>,
+    ) -> Self {
         Self {
             start_time: Utc::now(),
             duty_ops,
+            challenge_report_ops,
         }
     }
 }
@@ -96,6 +103,30 @@ impl StrataBridgeControlApiServer for BridgeRpc {
 
         Ok(current_time.abs_diff(start_time))
     }
+
+    async fn get_challenge_reports(&self, start_idx: u64) -> RpcResult<Vec<ChallengeReport>> {
+        let next_idx = self
+            .challenge_report_ops
+            .get_next_report_idx_async()
+            .await
+            .map_err(RpcServerError::Db)?;
+
+        let stop_idx = next_idx.min(start_idx.saturating_add(MAX_CHALLENGE_REPORTS_PER_REQUEST));
+
+        let mut reports = Vec::new();
+        for idx in start_idx..stop_idx {
+            if let Some(report) = self
+                .challenge_report_ops
+                .get_report_by_idx_async(idx)
+                .await
+                .map_err(RpcServerError::Db)?
+            {
+                reports.push(report);
+            }
+        }
+
+        Ok(reports)
+    }
 }
 
 #[async_trait]
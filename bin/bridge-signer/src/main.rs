@@ -0,0 +1,113 @@
+//! Reference signing service for bridge operators.
+//!
+//! Holds the operator's key and serves signatures to a bridge client started with
+//! `--signer-socket` over a Unix socket. Meant for testing the remote signer setup; production
+//! deployments are expected to put the key in their own signing service implementing the same
+//! RPCs.
+
+use std::{env, fs, path::Path, sync::Arc};
+
+use argh::FromArgs;
+use bitcoin::bip32::Xpriv;
+use rockbound::{rocksdb, OptimisticTransactionDB};
+use strata_bridge_sig_manager::{
+    remote::start_signer_server,
+    signer::{BridgeSigner, KeypairSigner},
+};
+use strata_common::logging::{self, LoggerConfig};
+use strata_key_derivation::operator::OperatorKeys;
+use strata_rocksdb::{
+    bridge::db::SignerSessionRocksDb, DbOpsConfig, ROCKSDB_NAME, STORE_COLUMN_FAMILIES,
+};
+use strata_storage::ops::signer_session::Context as SignerSessionContext;
+use threadpool::ThreadPool;
+use tracing::info;
+
+/// The environment variable that contains the operator's master [`Xpriv`].
+const OPXPRIV_ENVVAR: &str = "STRATA_OP_MASTER_XPRIV";
+
+/// The number of times to retry a rocksdb transaction.
+const ROCKSDB_RETRY_COUNT: u16 = 3;
+
+/// The number of threads for the database I/O ops.
+const DB_THREAD_COUNT: usize = 1;
+
+#[derive(Debug, FromArgs)]
+#[argh(name = "strata-bridge-signer")]
+#[argh(description = "Reference signing service for Strata bridge operators")]
+struct Args {
+    /// Path of the Unix socket to listen on.
+    #[argh(option, description = "path of the unix socket to listen on")]
+    socket: String,
+
+    /// Path to the file containing the master operator's xpriv.
+    /// Defaults to the environment variable `STRATA_OP_MASTER_XPRIV` if not provided.
+    #[argh(
+        option,
+        description = "path to the file containing the master operator's xpriv (default: envvar STRATA_OP_MASTER_XPRIV)"
+    )]
+    master_xpriv_path: Option<String>,
+
+    /// Directory to persist the open signing sessions in, so that the transactions being signed
+    /// can still be signed after a restart.
+    #[argh(
+        option,
+        description = "directory to persist the open signing sessions in"
+    )]
+    datadir: String,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    logging::init(LoggerConfig::with_base_name("strata-bridge-signer"));
+
+    let args: Args = argh::from_env();
+
+    let xpriv_str = match args.master_xpriv_path {
+        Some(path) => fs::read_to_string(path)?,
+        None => env::var(OPXPRIV_ENVVAR).map_err(|_| {
+            anyhow::anyhow!("must either set {OPXPRIV_ENVVAR} envvar or pass --master-xpriv-path")
+        })?,
+    };
+    let master_xpriv = xpriv_str.trim().parse::<Xpriv>()?;
+    let operator_keys =
+        OperatorKeys::new(&master_xpriv).map_err(|_| anyhow::anyhow!("invalid master xpriv"))?;
+
+    let rbdb = open_rocksdb_database(Path::new(&args.datadir))?;
+    let signer_session_db = SignerSessionRocksDb::new(rbdb, DbOpsConfig::new(ROCKSDB_RETRY_COUNT));
+    let signer_session_db_ops = SignerSessionContext::new(Arc::new(signer_session_db))
+        .into_ops(ThreadPool::new(DB_THREAD_COUNT));
+
+    let signer = Arc::new(
+        KeypairSigner::from_wallet_xpriv(operator_keys.wallet_xpriv())
+            .with_session_ops(Arc::new(signer_session_db_ops))
+            .await?,
+    );
+    let server = start_signer_server(&args.socket, signer.clone()).await?;
+
+    info!(socket = %args.socket, pubkey = %signer.pubkey(), "serving signer");
+
+    tokio::signal::ctrl_c().await?;
+    server.stop()?;
+    server.stopped().await;
+
+    Ok(())
+}
+
+/// Opens or creates the rocksdb database in `database_dir`.
+fn open_rocksdb_database(database_dir: &Path) -> anyhow::Result<Arc<OptimisticTransactionDB>> {
+    fs::create_dir_all(database_dir)?;
+
+    let mut opts = rocksdb::Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+
+    let rbdb = OptimisticTransactionDB::open(
+        database_dir,
+        ROCKSDB_NAME,
+        STORE_COLUMN_FAMILIES.iter().map(|s| s.to_string()),
+        &opts,
+    )?;
+
+    Ok(Arc::new(rbdb))
+}
@@ -14,7 +14,7 @@ pub(crate) struct Args {
 
     #[argh(
         option,
-        description = "data directory (unused) (default cwd)",
+        description = "node data directory, used by the db subcommands (default cwd)",
         short = 'd'
     )]
     pub(crate) datadir: Option<PathBuf>,
@@ -31,6 +31,7 @@ pub(crate) enum Subcommand {
     SeqPrivkey(SubcSeqPrivkey),
     OpXpub(SubcOpXpub),
     Params(SubcParams),
+    Db(SubcDb),
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -64,6 +65,13 @@ pub(crate) struct SubcSeqPubkey {
         short = 'E'
     )]
     pub(crate) key_from_env: bool,
+
+    #[argh(
+        option,
+        description = "rotation of the sequencer key to derive (default 0)",
+        default = "0"
+    )]
+    pub(crate) rotation: u32,
 }
 
 /// Generate the sequencer pubkey to pass around.
@@ -174,9 +182,120 @@ pub(crate) struct SubcParams {
     pub(crate) proof_timeout: Option<u32>,
 }
 
+/// Inspect or repair a node's database.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "db",
+    description = "inspects or repairs the database of a stopped node"
+)]
+pub(crate) struct SubcDb {
+    #[argh(subcommand)]
+    pub(crate) subc: DbSubcommand,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+pub(crate) enum DbSubcommand {
+    Chainstate(SubcDbChainstate),
+    ClientState(SubcDbClientState),
+    Blocks(SubcDbBlocks),
+    Checkpoint(SubcDbCheckpoint),
+    Broadcast(SubcDbBroadcast),
+    VerifyMmr(SubcDbVerifyMmr),
+    Revert(SubcDbRevert),
+}
+
+/// Dump a chainstate.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "chainstate",
+    description = "prints the chainstate at an L2 height as JSON"
+)]
+pub(crate) struct SubcDbChainstate {
+    #[argh(positional, description = "L2 height (default latest)")]
+    pub(crate) idx: Option<u64>,
+}
+
+/// Dump a client state.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "clientstate",
+    description = "prints the client state at a sync event index as JSON"
+)]
+pub(crate) struct SubcDbClientState {
+    #[argh(positional, description = "sync event index (default latest)")]
+    pub(crate) idx: Option<u64>,
+}
+
+/// List the L2 blocks at a height.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "blocks",
+    description = "lists the L2 blocks at a height along with their status"
+)]
+pub(crate) struct SubcDbBlocks {
+    #[argh(positional, description = "L2 height")]
+    pub(crate) height: u64,
+}
+
+/// Show a checkpoint entry.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "checkpoint",
+    description = "prints a checkpoint entry as JSON"
+)]
+pub(crate) struct SubcDbCheckpoint {
+    #[argh(positional, description = "checkpoint index (default latest)")]
+    pub(crate) idx: Option<u64>,
+}
+
+/// Show the L1 broadcast queue.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "broadcast",
+    description = "lists the txs in the L1 broadcast queue"
+)]
+pub(crate) struct SubcDbBroadcast {
+    #[argh(
+        option,
+        description = "index of the first entry to list (default 0)",
+        default = "0"
+    )]
+    pub(crate) from: u64,
+}
+
+/// Verify the stored L1 data.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "verifymmr",
+    description = "checks the stored L1 manifests and MMR checkpoints against each other"
+)]
+pub(crate) struct SubcDbVerifyMmr {}
+
+/// Roll the node back so it resyncs.
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(
+    subcommand,
+    name = "revert",
+    description = "rolls the database back to an L1 height and/or L2 block"
+)]
+pub(crate) struct SubcDbRevert {
+    #[argh(option, description = "L1 height to keep the blocks up to")]
+    pub(crate) l1_height: Option<u64>,
+
+    #[argh(option, description = "id of the L2 block to make the new tip, in hex")]
+    pub(crate) l2_block: Option<String>,
+}
+
 pub(crate) struct CmdContext {
     /// Resolved datadir for the network.
-    #[allow(unused)]
     pub(crate) datadir: PathBuf,
 
     /// The Bitcoin network we're building on top of.
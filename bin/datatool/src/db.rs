@@ -0,0 +1,650 @@
+//! Offline inspection and repair of a node's database.
+//!
+//! These open the RocksDB database in the datadir directly, so the node has to be stopped first.
+
+use std::{path::Path, str::FromStr, sync::Arc};
+
+use anyhow::{anyhow, bail, Context};
+use bitcoin::{block::Header, consensus::deserialize, hashes::Hash, BlockHash, Txid};
+use serde::Serialize;
+use sha2::Sha256;
+use strata_consensus_logic::csm::state_tracker::reconstruct_state;
+use strata_db::{
+    errors::DbError,
+    traits::*,
+    types::{CheckpointEntry, L1TxStatus},
+};
+use strata_mmr::MerkleMr;
+use strata_primitives::buf::Buf32;
+use strata_rocksdb::{
+    init_broadcaster_database, init_core_dbs, open_rocksdb_database, CommonDb, DbOpsConfig,
+    ROCKSDB_NAME,
+};
+use strata_state::{batch::BatchInfo, chain_state::Chainstate, header::L2Header, id::L2BlockId};
+
+use crate::args::{
+    CmdContext, DbSubcommand, SubcDb, SubcDbBlocks, SubcDbBroadcast, SubcDbChainstate,
+    SubcDbCheckpoint, SubcDbClientState, SubcDbRevert, SubcDbVerifyMmr,
+};
+
+/// Number of retries of the database operations.
+const DB_RETRY_COUNT: u16 = 5;
+
+/// Executes a `db` subcommand.
+pub(super) fn exec_db(cmd: SubcDb, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    match cmd.subc {
+        DbSubcommand::Chainstate(subc) => exec_db_chainstate(subc, ctx),
+        DbSubcommand::ClientState(subc) => exec_db_clientstate(subc, ctx),
+        DbSubcommand::Blocks(subc) => exec_db_blocks(subc, ctx),
+        DbSubcommand::Checkpoint(subc) => exec_db_checkpoint(subc, ctx),
+        DbSubcommand::Broadcast(subc) => exec_db_broadcast(subc, ctx),
+        DbSubcommand::VerifyMmr(subc) => exec_db_verifymmr(subc, ctx),
+        DbSubcommand::Revert(subc) => exec_db_revert(subc, ctx),
+    }
+}
+
+/// Opens the RocksDB database in the datadir, without creating it if it doesn't exist.
+fn open_rocksdb(datadir: &Path) -> anyhow::Result<Arc<rockbound::OptimisticTransactionDB>> {
+    if !datadir.join("rocksdb").exists() {
+        bail!("no database found in datadir {datadir:?}");
+    }
+    open_rocksdb_database(datadir, ROCKSDB_NAME).context("is the node still running?")
+}
+
+fn open_core_dbs(datadir: &Path) -> anyhow::Result<Arc<CommonDb>> {
+    let rbdb = open_rocksdb(datadir)?;
+    Ok(init_core_dbs(rbdb, DbOpsConfig::new(DB_RETRY_COUNT)))
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// The parts of a [`Chainstate`] that are useful to look at when debugging.
+#[derive(Serialize)]
+struct ChainstateSummary {
+    idx: u64,
+    tip_blkid: L2BlockId,
+    tip_slot: u64,
+    epoch: u64,
+    state_root: Buf32,
+    l1_safe_height: u64,
+    l1_tip_height: u64,
+    exec_update_idx: u64,
+    exec_state_root: Buf32,
+    pending_deposits: usize,
+    operators: u32,
+    deposits: u32,
+}
+
+impl ChainstateSummary {
+    fn new(idx: u64, chs: &Chainstate) -> Self {
+        let exec_env = chs.exec_env_state();
+        Self {
+            idx,
+            tip_blkid: chs.chain_tip_blockid(),
+            tip_slot: chs.chain_tip_slot(),
+            epoch: chs.epoch(),
+            state_root: chs.compute_state_root(),
+            l1_safe_height: chs.l1_view().safe_height(),
+            l1_tip_height: chs.l1_view().tip_height(),
+            exec_update_idx: exec_env.update_idx(),
+            exec_state_root: *exec_env.cur_state_root(),
+            pending_deposits: exec_env.pending_deposits().len(),
+            operators: chs.operator_table().len(),
+            deposits: chs.deposits_table().len(),
+        }
+    }
+}
+
+/// Executes the `db chainstate` subcommand.
+fn exec_db_chainstate(cmd: SubcDbChainstate, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    let database = open_core_dbs(&ctx.datadir)?;
+    let chs_db = database.chain_state_db();
+
+    let idx = match cmd.idx {
+        Some(idx) => idx,
+        None => chs_db.get_last_state_idx()?,
+    };
+    let chs = chs_db
+        .get_toplevel_state(idx)?
+        .ok_or_else(|| anyhow!("no chainstate at index {idx}"))?;
+
+    print_json(&ChainstateSummary::new(idx, &chs))
+}
+
+/// Executes the `db clientstate` subcommand.
+fn exec_db_clientstate(cmd: SubcDbClientState, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    let database = open_core_dbs(&ctx.datadir)?;
+    let cs_db = database.client_state_db();
+
+    let idx = match cmd.idx {
+        Some(idx) => idx,
+        None => cs_db.get_last_write_idx()?,
+    };
+    let state = reconstruct_state(cs_db.as_ref(), idx)?;
+
+    print_json(&state)
+}
+
+#[derive(Serialize)]
+struct BlockEntry {
+    blkid: L2BlockId,
+    parent: L2BlockId,
+    timestamp: u64,
+    /// Not set if the block hasn't been looked at by the fork choice manager yet.
+    status: Option<String>,
+}
+
+/// Executes the `db blocks` subcommand.
+fn exec_db_blocks(cmd: SubcDbBlocks, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    let database = open_core_dbs(&ctx.datadir)?;
+    let l2_db = database.l2_db();
+
+    let entries = l2_db
+        .get_blocks_at_height(cmd.height)?
+        .into_iter()
+        .map(|blkid| {
+            let block = l2_db
+                .get_block_data(blkid)?
+                .ok_or_else(|| anyhow!("missing data of block {blkid:?}"))?;
+            let status = l2_db.get_block_status(blkid)?;
+            Ok(BlockEntry {
+                blkid,
+                parent: *block.header().parent(),
+                timestamp: block.header().timestamp(),
+                status: status.map(|status| format!("{status:?}")),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    print_json(&entries)
+}
+
+#[derive(Serialize)]
+struct CheckpointSummary {
+    idx: u64,
+    batch_info: BatchInfo,
+    proving_status: String,
+    confirmation_status: String,
+}
+
+impl CheckpointSummary {
+    fn new(idx: u64, entry: CheckpointEntry) -> Self {
+        Self {
+            idx,
+            proving_status: format!("{:?}", entry.proving_status),
+            confirmation_status: format!("{:?}", entry.confirmation_status),
+            batch_info: entry.batch_info,
+        }
+    }
+}
+
+/// Executes the `db checkpoint` subcommand.
+fn exec_db_checkpoint(cmd: SubcDbCheckpoint, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    let database = open_core_dbs(&ctx.datadir)?;
+    let ckpt_db = database.checkpoint_db();
+
+    let idx = match cmd.idx {
+        Some(idx) => idx,
+        None => ckpt_db
+            .get_last_batch_idx()?
+            .ok_or_else(|| anyhow!("no checkpoints in database"))?,
+    };
+    let entry = ckpt_db
+        .get_batch_checkpoint(idx)?
+        .ok_or_else(|| anyhow!("no checkpoint at index {idx}"))?;
+
+    print_json(&CheckpointSummary::new(idx, entry))
+}
+
+#[derive(Serialize)]
+struct BroadcastEntry {
+    idx: u64,
+    txid: Txid,
+    status: L1TxStatus,
+    published_height: Option<u64>,
+}
+
+/// Executes the `db broadcast` subcommand.
+fn exec_db_broadcast(cmd: SubcDbBroadcast, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    let rbdb = open_rocksdb(&ctx.datadir)?;
+    let broadcast_db = init_broadcaster_database(rbdb, DbOpsConfig::new(DB_RETRY_COUNT));
+    let l1_broadcast_db = broadcast_db.l1_broadcast_db();
+
+    let mut entries = Vec::new();
+    for idx in cmd.from..l1_broadcast_db.get_next_tx_idx()? {
+        let txid = l1_broadcast_db
+            .get_txid(idx)?
+            .ok_or_else(|| anyhow!("missing txid of entry {idx}"))?;
+        let entry = l1_broadcast_db
+            .get_tx_entry(idx)?
+            .ok_or_else(|| anyhow!("missing entry {idx}"))?;
+        entries.push(BroadcastEntry {
+            idx,
+            txid: Txid::from_byte_array(txid.0),
+            status: entry.status,
+            published_height: entry.published_height,
+        });
+    }
+
+    print_json(&entries)
+}
+
+/// Executes the `db verifymmr` subcommand.
+///
+/// Checks that the stored manifests have valid headers that chain up, and that every stored MMR
+/// checkpoint matches the MMR of the block hashes up to its height.
+fn exec_db_verifymmr(_cmd: SubcDbVerifyMmr, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    let database = open_core_dbs(&ctx.datadir)?;
+    let l1_db = database.l1_db();
+
+    let Some(tip) = l1_db.get_chain_tip()? else {
+        println!("no L1 blocks in database");
+        return Ok(());
+    };
+
+    // Blocks are stored contiguously up to the tip.
+    let block_hashes = l1_db.get_blockid_range(0, tip + 1)?;
+    let first = tip + 1 - block_hashes.len() as u64;
+
+    let mut problems = Vec::new();
+    let mut prev_hash: Option<BlockHash> = None;
+    let mut mmr: Option<MerkleMr<Sha256>> = None;
+    let mut num_checkpoints = 0;
+    for (i, hash) in block_hashes.iter().enumerate() {
+        let height = first + i as u64;
+        let manifest = l1_db
+            .get_block_manifest(height)?
+            .ok_or_else(|| anyhow!("missing manifest at height {height}"))?;
+
+        match deserialize::<Header>(manifest.header()) {
+            Ok(header) => {
+                if Buf32::from(header.block_hash()) != *hash {
+                    problems.push(format!("header at height {height} doesn't match its hash"));
+                }
+                if prev_hash.is_some_and(|prev| header.prev_blockhash != prev) {
+                    problems.push(format!(
+                        "header at height {height} doesn't extend its parent"
+                    ));
+                }
+                prev_hash = Some(header.block_hash());
+            }
+            Err(e) => {
+                problems.push(format!("invalid header at height {height}: {e}"));
+                prev_hash = None;
+            }
+        }
+
+        if let Some(mmr) = mmr.as_mut() {
+            mmr.add_leaf(hash.0);
+        }
+
+        let Some(stored) = l1_db.get_last_mmr_to(height)? else {
+            continue;
+        };
+        num_checkpoints += 1;
+
+        // The MMR starts from the first stored block, with the capacity of the stored ones.
+        let mmr = mmr.get_or_insert_with(|| {
+            let cap_log2 = MerkleMr::<Sha256>::from_compact(&stored).peaks.len();
+            let mut mmr = MerkleMr::new(cap_log2);
+            for hash in &block_hashes[..=i] {
+                mmr.add_leaf(hash.0);
+            }
+            mmr
+        });
+        if mmr.to_compact() != stored {
+            problems.push(format!("MMR checkpoint at height {height} doesn't match"));
+        }
+    }
+
+    println!("checked L1 blocks {first}..={tip} and {num_checkpoints} MMR checkpoints");
+    if !problems.is_empty() {
+        for problem in &problems {
+            println!("{problem}");
+        }
+        bail!("found {} problems in the L1 data", problems.len());
+    }
+
+    println!("no problems found");
+    Ok(())
+}
+
+/// Executes the `db revert` subcommand.
+///
+/// The client state is rolled back to the last sync event that didn't go past the given L1 height
+/// and L2 block.  The sync events after it are removed, along with the L1 blocks, L2 blocks and
+/// chainstates that client state hasn't seen yet, so the node fetches them again when it
+/// restarts.  This can't go below the buried L1 height or the finalized L2 block.
+fn exec_db_revert(cmd: SubcDbRevert, ctx: &mut CmdContext) -> anyhow::Result<()> {
+    if cmd.l1_height.is_none() && cmd.l2_block.is_none() {
+        bail!("nothing to revert, pass --l1-height and/or --l2-block");
+    }
+
+    let l2_block = cmd
+        .l2_block
+        .map(|l2_block| {
+            Buf32::from_str(&l2_block)
+                .map(L2BlockId::from)
+                .map_err(|e| anyhow!("invalid L2 block id: {e}"))
+        })
+        .transpose()?;
+
+    let database = open_core_dbs(&ctx.datadir)?;
+    let target = find_revert_target(&database, cmd.l1_height, l2_block)?;
+    let deleted = revert_to(&database, &target)?;
+
+    let RevertTarget {
+        sync_event_idx,
+        l1_height,
+        l2_tip: (blkid, height),
+    } = target;
+    println!("reverted client state to sync event {sync_event_idx}");
+    println!("reverted L1 blocks to height {l1_height}");
+    println!("reverted L2 chain to block {blkid:?} at height {height}, deleted {deleted} blocks");
+    Ok(())
+}
+
+/// What's left in the database after a revert.
+#[derive(Debug, Eq, PartialEq)]
+struct RevertTarget {
+    /// Index of the last sync event kept.
+    sync_event_idx: u64,
+
+    /// Height of the last L1 block kept.
+    l1_height: u64,
+
+    /// Last L2 block kept, along with its height.
+    l2_tip: (L2BlockId, u64),
+}
+
+/// Finds the latest client state that hasn't seen L1 blocks above `l1_height` nor L2 blocks above
+/// `l2_block`, and whose tip is still on the canonical chain.
+fn find_revert_target(
+    database: &CommonDb,
+    l1_height: Option<u64>,
+    l2_block: Option<L2BlockId>,
+) -> anyhow::Result<RevertTarget> {
+    let cs_db = database.client_state_db();
+    let last_idx = cs_db.get_last_write_idx()?;
+    let client_state = reconstruct_state(cs_db.as_ref(), last_idx)?;
+    let sync = client_state
+        .sync()
+        .ok_or_else(|| anyhow!("can't revert before genesis"))?;
+
+    let buried_height = client_state.l1_view().buried_l1_height();
+    if l1_height.is_some_and(|height| height < buried_height) {
+        bail!("can't revert below the buried L1 block at height {buried_height}");
+    }
+
+    let finalized_height = block_height(database, *sync.finalized_blkid())?;
+    let l2_height = match l2_block {
+        Some(blkid) => {
+            let height = block_height(database, blkid)?;
+            if !is_canonical(database, blkid, height)? {
+                bail!("block {blkid:?} is not on the canonical chain");
+            }
+            if height < finalized_height {
+                bail!("can't revert below the finalized L2 block at height {finalized_height}");
+            }
+            Some(height)
+        }
+        None => None,
+    };
+
+    for idx in (0..=last_idx).rev() {
+        // The client state can't be rebuilt from before its first checkpoint.
+        if matches!(
+            cs_db.get_prev_checkpoint_at(idx),
+            Err(DbError::NotBootstrapped)
+        ) {
+            break;
+        }
+
+        let state = reconstruct_state(cs_db.as_ref(), idx)?;
+        let Some(sync) = state.sync() else {
+            break;
+        };
+
+        let next_l1_height = state.next_exp_l1_block();
+        let tip = (*sync.chain_tip_blkid(), sync.chain_tip_height());
+        if l1_height.is_some_and(|height| next_l1_height > height + 1)
+            || l2_height.is_some_and(|height| tip.1 > height)
+            || !is_canonical(database, tip.0, tip.1)?
+        {
+            continue;
+        }
+
+        if tip.1 < finalized_height {
+            bail!("can't revert below the finalized L2 block at height {finalized_height}");
+        }
+
+        return Ok(RevertTarget {
+            sync_event_idx: idx,
+            l1_height: next_l1_height.saturating_sub(1),
+            l2_tip: tip,
+        });
+    }
+
+    bail!("no client state to revert to")
+}
+
+fn block_height(database: &CommonDb, blkid: L2BlockId) -> anyhow::Result<u64> {
+    let block = database
+        .l2_db()
+        .get_block_data(blkid)?
+        .ok_or_else(|| anyhow!("no block {blkid:?} in database"))?;
+    Ok(block.header().blockidx())
+}
+
+/// Checks whether the chainstate at `height` was built on `blkid`.
+fn is_canonical(database: &CommonDb, blkid: L2BlockId, height: u64) -> anyhow::Result<bool> {
+    let chs = database.chain_state_db().get_toplevel_state(height)?;
+    Ok(chs.is_some_and(|chs| chs.chain_tip_blockid() == blkid))
+}
+
+/// Reverts the database to `target`, returning the number of L2 blocks deleted.
+fn revert_to(database: &CommonDb, target: &RevertTarget) -> anyhow::Result<usize> {
+    database
+        .client_state_db()
+        .revert_to_idx(target.sync_event_idx)?;
+    database
+        .sync_event_db()
+        .revert_to_idx(target.sync_event_idx)?;
+
+    let l1_db = database.l1_db();
+    if l1_db
+        .get_chain_tip()?
+        .is_some_and(|tip| tip > target.l1_height)
+    {
+        l1_db.revert_to_height(target.l1_height)?;
+    }
+
+    let (tip_blkid, tip_height) = target.l2_tip;
+    database.chain_state_db().rollback_writes_to(tip_height)?;
+
+    // The siblings of the tip go too, so they don't get picked as the tip on restart.
+    let l2_db = database.l2_db();
+    let mut deleted = 0;
+    for id in l2_db.get_blocks_at_height(tip_height)? {
+        if id != tip_blkid {
+            l2_db.del_block_data(id)?;
+            deleted += 1;
+        }
+    }
+    for h in tip_height + 1.. {
+        let blkids = l2_db.get_blocks_at_height(h)?;
+        if blkids.is_empty() {
+            break;
+        }
+        for id in blkids {
+            l2_db.del_block_data(id)?;
+            deleted += 1;
+        }
+    }
+
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_primitives::l1::L1BlockManifest;
+    use strata_rocksdb::test_utils::get_common_db;
+    use strata_state::{
+        block::L2BlockBundle,
+        client_state::{ClientState, L1Checkpoint, SyncState},
+        genesis::GenesisStateData,
+        operation::{ClientStateWrite, ClientUpdateOutput},
+        state_op::WriteBatch,
+        sync_event::SyncEvent,
+    };
+    use strata_test_utils::{
+        l2::{gen_block, gen_l2_chain},
+        ArbitraryGenerator,
+    };
+
+    use super::*;
+
+    /// Height of the last L2 block, also the number of sync events.
+    const TIP_HEIGHT: u64 = 6;
+
+    /// Makes a chainstate built on `blkid`.
+    fn chainstate_on(blkid: L2BlockId) -> Chainstate {
+        let mut arb = ArbitraryGenerator::new();
+        let gdata = GenesisStateData::new(blkid, arb.generate(), arb.generate(), arb.generate());
+        Chainstate::from_genesis(&gdata)
+    }
+
+    /// Stores an L2 chain with a fork at height 4, and the sync events of a client state that
+    /// accepts a new L1 and L2 block with each of them.  The last one finalizes the block at
+    /// height 2 and buries the L1 blocks below height 2.
+    fn setup_db(database: &CommonDb) -> (Vec<L2BlockBundle>, L2BlockBundle) {
+        let mut arb = ArbitraryGenerator::new();
+        let blocks = gen_l2_chain(None, TIP_HEIGHT as usize);
+        let fork = gen_block(Some(blocks[3].header()));
+        let blkid = |i: usize| blocks[i].header().get_blockid();
+
+        for block in blocks.iter().chain([&fork]) {
+            database.l2_db().put_block_data(block.clone()).unwrap();
+        }
+
+        let chs_db = database.chain_state_db();
+        chs_db
+            .write_genesis_state(&chainstate_on(blkid(0)))
+            .unwrap();
+        for height in 1..=TIP_HEIGHT {
+            let batch = WriteBatch::new_replace(chainstate_on(blkid(height as usize)));
+            chs_db.write_state_update(height, &batch).unwrap();
+        }
+
+        for height in 0..TIP_HEIGHT {
+            let mf: L1BlockManifest = arb.generate();
+            database
+                .l1_db()
+                .put_block_data(height, mf, Vec::new())
+                .unwrap();
+        }
+
+        let cs_db = database.client_state_db();
+        let mut state = ClientState::from_genesis_params(0, 0);
+        state.set_sync_state(SyncState::from_genesis_blkid(blkid(0)));
+        cs_db.write_client_state_checkpoint(0, state).unwrap();
+
+        for idx in 1..=TIP_HEIGHT {
+            let ev: SyncEvent = arb.generate();
+            database.sync_event_db().write_sync_event(ev).unwrap();
+
+            let mut writes = vec![
+                ClientStateWrite::AcceptL1Block(arb.generate()),
+                ClientStateWrite::AcceptL2Block(blkid(idx as usize), idx),
+            ];
+            if idx == TIP_HEIGHT {
+                let mut batch_info: BatchInfo = arb.generate();
+                batch_info.idx = 0;
+                batch_info.l2_blockid = blkid(2);
+                let checkpoint = L1Checkpoint::new(batch_info, arb.generate(), true, 0);
+                writes.extend([
+                    ClientStateWrite::CheckpointsReceived(vec![checkpoint]),
+                    ClientStateWrite::CheckpointFinalized(0),
+                    ClientStateWrite::UpdateBuried(2),
+                ]);
+            }
+            let output = ClientUpdateOutput::new(writes, Vec::new());
+            cs_db.write_client_update_output(idx, output).unwrap();
+        }
+
+        (blocks, fork)
+    }
+
+    #[test]
+    fn test_find_revert_target() {
+        let database = get_common_db();
+        let (blocks, fork) = setup_db(&database);
+        let blkid = |i: usize| blocks[i].header().get_blockid();
+
+        let target = find_revert_target(&database, None, Some(blkid(4))).unwrap();
+        assert_eq!(
+            target,
+            RevertTarget {
+                sync_event_idx: 4,
+                l1_height: 3,
+                l2_tip: (blkid(4), 4),
+            }
+        );
+
+        let target = find_revert_target(&database, Some(2), None).unwrap();
+        assert_eq!(
+            target,
+            RevertTarget {
+                sync_event_idx: 3,
+                l1_height: 2,
+                l2_tip: (blkid(3), 3),
+            }
+        );
+
+        let target = find_revert_target(&database, Some(4), Some(blkid(3))).unwrap();
+        assert_eq!(target.sync_event_idx, 3, "the lower of the two wins");
+
+        let fork_blkid = fork.header().get_blockid();
+        let res = find_revert_target(&database, None, Some(fork_blkid));
+        assert!(res.is_err(), "the fork is not canonical");
+
+        let res = find_revert_target(&database, None, Some(blkid(1)));
+        assert!(res.is_err(), "block 1 is below the finalized block");
+
+        let res = find_revert_target(&database, Some(1), None);
+        assert!(res.is_err(), "L1 height 1 is buried");
+    }
+
+    #[test]
+    fn test_revert_to() {
+        let database = get_common_db();
+        let (blocks, fork) = setup_db(&database);
+        let blkid = |i: usize| blocks[i].header().get_blockid();
+
+        let target = find_revert_target(&database, None, Some(blkid(4))).unwrap();
+        let deleted = revert_to(&database, &target).unwrap();
+        assert_eq!(deleted, 3, "the fork and the blocks at heights 5 and 6");
+
+        let cs_db = database.client_state_db();
+        assert_eq!(cs_db.get_last_write_idx().unwrap(), 4);
+        let state = reconstruct_state(cs_db.as_ref(), 4).unwrap();
+        assert_eq!(state.sync().unwrap().chain_tip_blkid(), &blkid(4));
+        assert_eq!(database.sync_event_db().get_last_idx().unwrap(), Some(4));
+
+        assert_eq!(database.l1_db().get_chain_tip().unwrap(), Some(3));
+        assert_eq!(database.chain_state_db().get_last_state_idx().unwrap(), 4);
+
+        let l2_db = database.l2_db();
+        assert_eq!(l2_db.get_blocks_at_height(4).unwrap(), vec![blkid(4)]);
+        assert!(l2_db.get_blocks_at_height(5).unwrap().is_empty());
+        let fork_blkid = fork.header().get_blockid();
+        assert!(l2_db.get_block_data(fork_blkid).unwrap().is_none());
+
+        // The node picks up right after the new tip.
+        let ev: SyncEvent = ArbitraryGenerator::new().generate();
+        assert_eq!(database.sync_event_db().write_sync_event(ev).unwrap(), 5);
+    }
+}
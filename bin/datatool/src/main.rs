@@ -1,10 +1,12 @@
-//! Command line tool for generating test data for Strata.
+//! Command line tool for generating test data for Strata, and for inspecting and repairing the
+//! database of a node.
 //!
 //! # Warning
 //!
 //! This tool is intended for use in testing and development only. It generates
 //! keys and other data that should not be used in production.
 mod args;
+mod db;
 mod util;
 
 use std::path::PathBuf;
@@ -29,8 +29,11 @@ use strata_primitives::{
 };
 use zeroize::Zeroize;
 
-use crate::args::{
-    CmdContext, SubcOpXpub, SubcParams, SubcSeqPrivkey, SubcSeqPubkey, SubcXpriv, Subcommand,
+use crate::{
+    args::{
+        CmdContext, SubcOpXpub, SubcParams, SubcSeqPrivkey, SubcSeqPubkey, SubcXpriv, Subcommand,
+    },
+    db,
 };
 
 /// Sequencer key environment variable.
@@ -54,7 +57,7 @@ pub(super) fn resolve_network(arg: Option<&str>) -> anyhow::Result<Network> {
     }
 }
 
-/// Executes a `gen*` subcommand.
+/// Executes a subcommand.
 pub(super) fn exec_subc(cmd: Subcommand, ctx: &mut CmdContext) -> anyhow::Result<()> {
     match cmd {
         Subcommand::Xpriv(subc) => exec_genxpriv(subc, ctx),
@@ -62,6 +65,7 @@ pub(super) fn exec_subc(cmd: Subcommand, ctx: &mut CmdContext) -> anyhow::Result
         Subcommand::SeqPrivkey(subc) => exec_genseqprivkey(subc, ctx),
         Subcommand::OpXpub(subc) => exec_genopxpub(subc, ctx),
         Subcommand::Params(subc) => exec_genparams(subc, ctx),
+        Subcommand::Db(subc) => db::exec_db(subc, ctx),
     }
 }
 
@@ -145,7 +149,7 @@ fn exec_genseqpubkey(cmd: SubcSeqPubkey, _ctx: &mut CmdContext) -> anyhow::Resul
         anyhow::bail!("privkey unset");
     };
 
-    let seq_keys = SequencerKeys::new(&xpriv)?;
+    let seq_keys = SequencerKeys::new_rotated(&xpriv, cmd.rotation)?;
     let seq_xpub = seq_keys.derived_xpub();
     let raw_buf = seq_xpub.to_x_only_pub().serialize();
     let s = base58::encode_check(&raw_buf);
@@ -462,6 +466,8 @@ fn construct_params(config: ParamsConfig) -> RollupParams {
             .unwrap_or(ProofPublishMode::Strict),
         // TODO make configurable
         max_deposits_in_block: 16,
+        forced_inclusion_delay: 6,
+        max_forced_incls_in_block: 16,
         network: config.bitcoin_network,
     }
 }
@@ -2,10 +2,14 @@ use std::{collections::HashMap, fs, path::PathBuf};
 
 use argh::FromArgs;
 use serde_json::from_str;
+use strata_config::MetricsConfig;
 use strata_primitives::{params::RollupParams, proof::ProofZkVm};
 
+use crate::retry::RetryConfig;
+
 pub(super) const DEV_RPC_PORT: usize = 4844;
 pub(super) const DEV_RPC_URL: &str = "0.0.0.0";
+pub(super) const DEFAULT_METRICS_HOST: &str = "0.0.0.0";
 
 /// Command-line arguments used to configure the prover-client in both development and production
 /// modes.
@@ -125,6 +129,29 @@ pub struct Args {
     /// Defaults to `true`.
     #[argh(option, description = "enable prover client dev rpc", default = "true")]
     pub enable_dev_rpcs: bool,
+
+    /// Path to the JSON file configuring the retries of failed proving tasks.
+    ///
+    /// The file holds a `default` retry policy and policy `overrides` keyed by the kind of proof
+    /// context. Failed tasks are retried up to 3 times with exponential backoff if not set.
+    #[argh(option, description = "retry config path")]
+    pub retry_config: Option<PathBuf>,
+
+    /// The host address to serve the Prometheus metrics on.
+    ///
+    /// Defaults to `DEFAULT_METRICS_HOST`.
+    #[argh(
+        option,
+        description = "metrics host",
+        default = "DEFAULT_METRICS_HOST.to_string()"
+    )]
+    pub metrics_host: String,
+
+    /// The port to serve the Prometheus metrics on.
+    ///
+    /// The metrics endpoint is disabled if this is not set.
+    #[argh(option, description = "metrics port")]
+    pub metrics_port: Option<u16>,
 }
 
 impl Args {
@@ -135,6 +162,14 @@ impl Args {
         format!("{}:{}", self.rpc_url, self.rpc_port)
     }
 
+    /// Returns the configuration of the metrics endpoint if a `metrics_port` is set.
+    pub fn get_metrics_config(&self) -> Option<MetricsConfig> {
+        self.metrics_port.map(|port| MetricsConfig {
+            host: self.metrics_host.clone(),
+            port,
+        })
+    }
+
     /// Returns the Sequencer RPC URL as a `String`.
     ///
     /// Useful for configuring communication with the Sequencer service.
@@ -179,6 +214,17 @@ impl Args {
         workers
     }
 
+    /// Reads the retry config from the `retry_config` file, or returns the default config if it is
+    /// not set.
+    pub fn resolve_retry_config(&self) -> anyhow::Result<RetryConfig> {
+        let Some(path) = &self.retry_config else {
+            return Ok(RetryConfig::default());
+        };
+
+        let json = fs::read_to_string(path)?;
+        Ok(from_str::<RetryConfig>(&json)?)
+    }
+
     /// Resolves the rollup params file to use, from a path, and validates
     /// it to ensure it passes sanity checks.
     pub fn resolve_and_validate_rollup_params(&self) -> anyhow::Result<RollupParams> {
@@ -10,11 +10,11 @@ use operators::ProofOperator;
 use prover_manager::ProverManager;
 use rpc_server::ProverClientRpc;
 use strata_btcio::rpc::BitcoinClient;
-use strata_common::logging;
+use strata_common::{logging, metrics};
 use strata_rocksdb::{prover::db::ProofDb, DbOpsConfig};
 use task_tracker::TaskTracker;
 use tokio::{spawn, sync::Mutex};
-use tracing::debug;
+use tracing::{debug, error};
 
 mod args;
 mod db;
@@ -22,6 +22,7 @@ mod errors;
 mod hosts;
 mod operators;
 mod prover_manager;
+mod retry;
 mod rpc_server;
 mod status;
 mod task_tracker;
@@ -45,6 +46,16 @@ async fn main_inner(args: Args) -> anyhow::Result<()> {
 
     debug!("Running prover client with args {:?}", args);
 
+    if let Some(metrics_config) = args.get_metrics_config() {
+        let metrics_server =
+            metrics::init(&metrics_config).context("Failed to initialize metrics")?;
+        spawn(async move {
+            if let Err(e) = metrics_server.await {
+                error!(%e, "metrics server exited");
+            }
+        });
+    }
+
     let rollup_params = args
         .resolve_and_validate_rollup_params()
         .context("Failed to resolve and validate rollup parameters")?;
@@ -70,13 +81,20 @@ async fn main_inner(args: Args) -> anyhow::Result<()> {
         cl_client,
         rollup_params,
     ));
-    let task_tracker = Arc::new(Mutex::new(TaskTracker::new()));
+
+    let retry_config = args
+        .resolve_retry_config()
+        .context("Failed to resolve the retry config")?;
 
     let rbdb =
         open_rocksdb_database(&args.datadir).context("Failed to open the RocksDB database")?;
     let db_ops = DbOpsConfig { retry_count: 3 };
     let db = Arc::new(ProofDb::new(rbdb, db_ops));
 
+    let task_tracker = Arc::new(Mutex::new(
+        TaskTracker::new(db.clone(), retry_config).context("Failed to load the proving tasks")?,
+    ));
+
     let manager = ProverManager::new(
         task_tracker.clone(),
         operator.clone(),
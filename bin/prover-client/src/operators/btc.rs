@@ -42,11 +42,10 @@ impl ProvingOp for BtcBlockspaceOperator {
         &self,
         block_id: Self::Params,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         let context = ProofContext::BtcBlockspace(block_id);
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.create_tasks(context, vec![], db)
+        task_tracker.create_tasks(context, vec![])
     }
 
     async fn fetch_input(
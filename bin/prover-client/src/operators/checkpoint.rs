@@ -105,7 +105,6 @@ impl ProvingOp for CheckpointOperator {
         &self,
         ckp_idx: u64,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         info!(%ckp_idx, "Start creating task");
         let checkpoint_info = self.fetch_ckp_info(ckp_idx).await?;
@@ -126,11 +125,7 @@ impl ProvingOp for CheckpointOperator {
 
         let l1_batch_keys = self
             .l1_batch_operator
-            .create_task(
-                (start_l1_block_id, end_l1_block_id),
-                task_tracker.clone(),
-                db,
-            )
+            .create_task((start_l1_block_id, end_l1_block_id), task_tracker.clone())
             .await?;
         let l1_batch_id = l1_batch_keys
             .first()
@@ -147,7 +142,7 @@ impl ProvingOp for CheckpointOperator {
 
         let l2_batch_keys = self
             .l2_batch_operator
-            .create_task(l2_range, task_tracker.clone(), db)
+            .create_task(l2_range, task_tracker.clone())
             .await?;
 
         let l2_batch_id = l2_batch_keys
@@ -158,11 +153,8 @@ impl ProvingOp for CheckpointOperator {
 
         let deps = vec![*l1_batch_id, *l2_batch_id];
 
-        db.put_proof_deps(ckp_proof_id, deps.clone())
-            .map_err(ProvingTaskError::DatabaseError)?;
-
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.create_tasks(ckp_proof_id, deps, db)
+        task_tracker.create_tasks(ckp_proof_id, deps)
     }
 
     async fn fetch_input(
@@ -37,7 +37,6 @@ impl ProvingOp for ClAggOperator {
         &self,
         batches: Self::Params,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         let mut cl_stf_deps = Vec::with_capacity(batches.len());
 
@@ -57,16 +56,13 @@ impl ProvingOp for ClAggOperator {
         for (start_blkid, end_blkid) in batches {
             let proof_id = ProofContext::ClStf(start_blkid, end_blkid);
             self.cl_stf_operator
-                .create_task((start_blkid, end_blkid), task_tracker.clone(), db)
+                .create_task((start_blkid, end_blkid), task_tracker.clone())
                 .await?;
             cl_stf_deps.push(proof_id);
         }
 
-        db.put_proof_deps(cl_agg_proof_id, cl_stf_deps.clone())
-            .map_err(ProvingTaskError::DatabaseError)?;
-
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.create_tasks(cl_agg_proof_id, cl_stf_deps, db)
+        task_tracker.create_tasks(cl_agg_proof_id, cl_stf_deps)
     }
 
     async fn fetch_input(
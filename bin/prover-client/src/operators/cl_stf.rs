@@ -121,7 +121,6 @@ impl ProvingOp for ClStfOperator {
         &self,
         block_range: Self::Params,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         let (start_block_id, end_block_id) = block_range;
 
@@ -130,11 +129,7 @@ impl ProvingOp for ClStfOperator {
 
         let evm_ee_tasks = self
             .evm_ee_operator
-            .create_task(
-                (el_start_block_id, el_end_block_id),
-                task_tracker.clone(),
-                db,
-            )
+            .create_task((el_start_block_id, el_end_block_id), task_tracker.clone())
             .await?;
 
         let evm_ee_id = evm_ee_tasks
@@ -144,11 +139,8 @@ impl ProvingOp for ClStfOperator {
 
         let cl_stf_id = ProofContext::ClStf(start_block_id, end_block_id);
 
-        db.put_proof_deps(cl_stf_id, vec![*evm_ee_id])
-            .map_err(ProvingTaskError::DatabaseError)?;
-
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.create_tasks(cl_stf_id, vec![*evm_ee_id], db)
+        task_tracker.create_tasks(cl_stf_id, vec![*evm_ee_id])
     }
 
     async fn fetch_input(
@@ -4,12 +4,15 @@ use alloy_rpc_types::{Block, Header};
 use jsonrpsee::{core::client::ClientT, http_client::HttpClient, rpc_params};
 use strata_primitives::{
     buf::Buf32,
+    evm_exec::create_evm_extra_payload,
     proof::{ProofContext, ProofKey},
 };
 use strata_proofimpl_evm_ee_stf::{
     primitives::EvmEeProofInput, prover::EvmEeProver, EvmBlockStfInput,
 };
 use strata_rocksdb::prover::db::ProofDb;
+use strata_rpc_api::StrataApiClient;
+use strata_state::{exec_update::ELForcedInclusionData, id::L2BlockId};
 use tokio::sync::Mutex;
 use tracing::error;
 
@@ -20,16 +23,21 @@ use crate::{errors::ProvingTaskError, task_tracker::TaskTracker};
 /// Transition Function (STF) proofs.
 ///
 /// It is responsible for interfacing with the `Reth` client and fetching necessary data required by
-/// the [`EvmEeProver`] for the proof generation.
+/// the [`EvmEeProver`] for the proof generation.  The forced inclusions processed by each block
+/// aren't known to the EL, so they're fetched from the CL Client.
 #[derive(Debug, Clone)]
 pub struct EvmEeOperator {
     el_client: HttpClient,
+    cl_client: HttpClient,
 }
 
 impl EvmEeOperator {
     /// Creates a new EL operations instance.
-    pub fn new(el_client: HttpClient) -> Self {
-        Self { el_client }
+    pub fn new(el_client: HttpClient, cl_client: HttpClient) -> Self {
+        Self {
+            el_client,
+            cl_client,
+        }
     }
 
     /// Retrieves the EVM EE [`Block`] for a given block number.
@@ -54,6 +62,42 @@ impl EvmEeOperator {
             .map_err(|e| ProvingTaskError::RpcError(e.to_string()))?;
         Ok(block.header)
     }
+
+    /// Retrieves the forced inclusions processed by the EVM EE block with the given hash and
+    /// number, from the exec update of the CL block that carries it.
+    async fn get_forced_inclusions(
+        &self,
+        blkid: Buf32,
+        block_num: u64,
+    ) -> Result<Vec<ELForcedInclusionData>, ProvingTaskError> {
+        let headers = self
+            .cl_client
+            .get_headers_at_idx(block_num)
+            .await
+            .inspect_err(|_| error!(%block_num, "Failed to fetch l2_headers"))
+            .map_err(|e| ProvingTaskError::RpcError(e.to_string()))?
+            .unwrap_or_default();
+
+        let extra_payload = create_evm_extra_payload(blkid);
+        for header in headers {
+            let l2_blkid = L2BlockId::from(Buf32::from(header.block_id));
+            let exec_update = self
+                .cl_client
+                .get_exec_update_by_id(l2_blkid)
+                .await
+                .inspect_err(|_| error!(%l2_blkid, "Failed to fetch exec update"))
+                .map_err(|e| ProvingTaskError::RpcError(e.to_string()))?;
+
+            if let Some(exec_update) = exec_update {
+                if exec_update.extra_payload == extra_payload {
+                    return Ok(exec_update.forced_inclusions);
+                }
+            }
+        }
+
+        error!(%blkid, %block_num, "No L2 block found for EVM Block");
+        Err(ProvingTaskError::WitnessNotFound)
+    }
 }
 
 impl ProvingOp for EvmEeOperator {
@@ -64,13 +108,12 @@ impl ProvingOp for EvmEeOperator {
         &self,
         block_range: Self::Params,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         let (start_blkid, end_blkid) = block_range;
         let context = ProofContext::EvmEeStf(start_blkid, end_blkid);
 
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.create_tasks(context, vec![], db)
+        task_tracker.create_tasks(context, vec![])
     }
 
     async fn fetch_input(
@@ -87,12 +130,15 @@ impl ProvingOp for EvmEeOperator {
 
         let mut blkid = end_block_hash;
         loop {
-            let witness: EvmBlockStfInput = self
+            let mut witness: EvmBlockStfInput = self
                 .el_client
                 .request("strataee_getBlockWitness", rpc_params![blkid, true])
                 .await
                 .map_err(|e| ProvingTaskError::RpcError(e.to_string()))?;
 
+            let block_num = witness.parent_header.number + 1;
+            witness.forced_inclusions = self.get_forced_inclusions(blkid, block_num).await?;
+
             mini_batch.push(witness);
 
             if blkid == start_block_hash {
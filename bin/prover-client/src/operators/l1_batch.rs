@@ -102,13 +102,12 @@ impl ProvingOp for L1BatchOperator {
         &self,
         params: Self::Params,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         let (start_blkid, end_blkid) = params;
         let l1_batch_proof_id = ProofContext::L1Batch(start_blkid, end_blkid);
 
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.create_tasks(l1_batch_proof_id, vec![], db)
+        task_tracker.create_tasks(l1_batch_proof_id, vec![])
     }
 
     async fn fetch_input(
@@ -58,7 +58,6 @@ pub trait ProvingOp {
     /// # Arguments
     /// - `params`: The parameters specific to the operation.
     /// - `task_tracker`: A shared task tracker for managing task dependencies.
-    /// - `db`: A reference to the proof database.
     ///
     /// # Returns
     /// A tuple containing the primary `ProofContext` and a vector of dependent `ProofContext`s.
@@ -66,7 +65,6 @@ pub trait ProvingOp {
         &self,
         params: Self::Params,
         task_tracker: Arc<Mutex<TaskTracker>>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError>;
 
     /// Fetches the input required for the proof computation.
@@ -65,7 +65,7 @@ impl ProofOperator {
         let btc_blockspace_operator =
             BtcBlockspaceOperator::new(btc_client.clone(), rollup_params.clone());
         let l1_batch_operator = L1BatchOperator::new(btc_client.clone(), rollup_params.clone());
-        let evm_ee_operator = EvmEeOperator::new(evm_ee_client.clone());
+        let evm_ee_operator = EvmEeOperator::new(evm_ee_client.clone(), cl_client.clone());
         let cl_stf_operator = ClStfOperator::new(
             cl_client.clone(),
             Arc::new(evm_ee_operator.clone()),
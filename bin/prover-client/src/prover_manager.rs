@@ -38,9 +38,14 @@ impl ProverManager {
 
     pub async fn process_pending_tasks(&self) {
         loop {
-            // Step 1: Fetch pending tasks without holding the lock
+            // Step 1: Re-queue the failed tasks that are due for a retry and fetch pending tasks
+            // without holding the lock
             let (pending_tasks, in_progress_tasks) = {
-                let task_tracker = self.task_tracker.lock().await;
+                let mut task_tracker = self.task_tracker.lock().await;
+                if let Err(err) = task_tracker.retry_due_tasks() {
+                    error!(?err, "Failed to retry failed tasks");
+                }
+
                 let pending_tasks = task_tracker
                     .get_tasks_by_status(|status| matches!(status, ProvingTaskStatus::Pending));
                 (pending_tasks, task_tracker.get_in_progress_tasks().clone())
@@ -85,21 +90,27 @@ pub async fn make_proof(
     task: ProofKey,
     db: Arc<ProofDb>,
 ) -> Result<(), ProvingTaskError> {
-    {
+    let attempt = {
         let mut task_tracker = task_tracker.lock().await;
-        task_tracker.update_status(task, ProvingTaskStatus::ProvingInProgress)?;
-    }
+        task_tracker.start_task(task)?
+    };
 
     let res = operator.process_proof(&task, &db).await;
 
     {
         let mut task_tracker = task_tracker.lock().await;
+
+        // The task might have been cancelled, and even retried, while it was being proven
+        if !task_tracker.is_current_attempt(task, attempt) {
+            info!(?task, %attempt, "Discarding result of stale attempt");
+            return Ok(());
+        }
+
         match res {
             Ok(_) => task_tracker.update_status(task, ProvingTaskStatus::Completed)?,
-            // TODO: handle different errors for different failure condition
             Err(e) => {
                 error!(?task, ?e, "proving task failed");
-                task_tracker.update_status(task, ProvingTaskStatus::Failed)?
+                task_tracker.fail_task(task, e.to_string())?
             }
         }
     }
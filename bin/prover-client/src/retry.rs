@@ -0,0 +1,107 @@
+//! Policies for automatically retrying failed proving tasks.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use strata_primitives::proof::ProofContextKind;
+
+/// Policy for automatically retrying failed proving tasks with exponential backoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt.
+    pub max_retries: u32,
+
+    /// Delay before the first retry, in milliseconds.
+    pub base_delay_ms: u64,
+
+    /// Upper bound of the delay between retries, in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay_ms: 5_000,
+            max_delay_ms: 300_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay in milliseconds before a task that has failed after the given number of
+    /// attempts is retried, or `None` if the task has run out of retries.
+    ///
+    /// The delay doubles with every attempt, up to `max_delay_ms`.
+    pub fn retry_delay(&self, attempts: u32) -> Option<u64> {
+        let retries = attempts.saturating_sub(1);
+        if retries >= self.max_retries {
+            return None;
+        }
+
+        let delay = 1u64
+            .checked_shl(retries)
+            .and_then(|factor| self.base_delay_ms.checked_mul(factor))
+            .unwrap_or(u64::MAX)
+            .min(self.max_delay_ms);
+
+        Some(delay)
+    }
+}
+
+/// Retry policies of the proving tasks, keyed by the kind of their proof context.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RetryConfig {
+    /// The policy used for the proof context kinds without an override.
+    #[serde(default)]
+    pub default: RetryPolicy,
+
+    /// Policies for specific proof context kinds.
+    #[serde(default)]
+    pub overrides: HashMap<ProofContextKind, RetryPolicy>,
+}
+
+impl RetryConfig {
+    /// Returns the policy for the given kind of proof context.
+    pub fn policy(&self, kind: ProofContextKind) -> &RetryPolicy {
+        self.overrides.get(&kind).unwrap_or(&self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy {
+            max_retries: 4,
+            base_delay_ms: 1_000,
+            max_delay_ms: 5_000,
+        };
+
+        assert_eq!(policy.retry_delay(1), Some(1_000));
+        assert_eq!(policy.retry_delay(2), Some(2_000));
+        assert_eq!(policy.retry_delay(3), Some(4_000));
+        assert_eq!(
+            policy.retry_delay(4),
+            Some(5_000),
+            "delay should be capped at max_delay_ms"
+        );
+        assert_eq!(policy.retry_delay(5), None, "retries should be exhausted");
+    }
+
+    #[test]
+    fn test_retry_config_overrides() {
+        let json = r#"{
+            "default": { "max_retries": 1, "base_delay_ms": 10, "max_delay_ms": 100 },
+            "overrides": {
+                "checkpoint": { "max_retries": 10, "base_delay_ms": 1000, "max_delay_ms": 60000 }
+            }
+        }"#;
+        let config: RetryConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.policy(ProofContextKind::Checkpoint).max_retries, 10);
+        assert_eq!(config.policy(ProofContextKind::ClStf).max_retries, 1);
+    }
+}
@@ -9,7 +9,7 @@ use strata_db::traits::ProofDatabase;
 use strata_primitives::buf::Buf32;
 use strata_prover_client_rpc_api::StrataProverClientApiServer;
 use strata_rocksdb::prover::db::ProofDb;
-use strata_rpc_types::ProofKey;
+use strata_rpc_types::{ProofKey, RpcProvingTask, RpcProvingTaskFilter};
 use strata_rpc_utils::to_jsonrpsee_error;
 use strata_state::{id::L2BlockId, l1::L1BlockId};
 use strata_zkvm::ProofReceipt;
@@ -87,7 +87,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
     async fn prove_btc_block(&self, block_id: L1BlockId) -> RpcResult<Vec<ProofKey>> {
         self.operator
             .btc_operator()
-            .create_task(block_id, self.task_tracker.clone(), &self.db)
+            .create_task(block_id, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error("failed to create task for btc block"))
     }
@@ -95,7 +95,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
     async fn prove_el_blocks(&self, el_block_range: (Buf32, Buf32)) -> RpcResult<Vec<ProofKey>> {
         self.operator
             .evm_ee_operator()
-            .create_task(el_block_range, self.task_tracker.clone(), &self.db)
+            .create_task(el_block_range, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error("failed to create task for el block"))
     }
@@ -106,7 +106,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
     ) -> RpcResult<Vec<ProofKey>> {
         self.operator
             .cl_stf_operator()
-            .create_task(cl_block_range, self.task_tracker.clone(), &self.db)
+            .create_task(cl_block_range, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error("failed to create task for cl block"))
     }
@@ -114,7 +114,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
     async fn prove_l1_batch(&self, l1_range: (L1BlockId, L1BlockId)) -> RpcResult<Vec<ProofKey>> {
         self.operator
             .l1_batch_operator()
-            .create_task(l1_range, self.task_tracker.clone(), &self.db)
+            .create_task(l1_range, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error("failed to create task for l1 batch"))
     }
@@ -125,7 +125,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
     ) -> RpcResult<Vec<ProofKey>> {
         self.operator
             .cl_agg_operator()
-            .create_task(l2_range, self.task_tracker.clone(), &self.db)
+            .create_task(l2_range, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error("failed to create task for l2 batch"))
     }
@@ -133,7 +133,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
     async fn prove_checkpoint(&self, ckp_idx: u64) -> RpcResult<Vec<ProofKey>> {
         self.operator
             .checkpoint_operator()
-            .create_task(ckp_idx, self.task_tracker.clone(), &self.db)
+            .create_task(ckp_idx, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error(
                 "failed to create task for given checkpoint",
@@ -150,7 +150,7 @@ impl StrataProverClientApiServer for ProverClientRpc {
         info!(%latest_ckp_idx);
         self.operator
             .checkpoint_operator()
-            .create_task(latest_ckp_idx, self.task_tracker.clone(), &self.db)
+            .create_task(latest_ckp_idx, self.task_tracker.clone())
             .await
             .map_err(to_jsonrpsee_error(
                 "failed to create task for latest checkpoint",
@@ -201,4 +201,31 @@ impl StrataProverClientApiServer for ProverClientRpc {
         let task_tracker = self.task_tracker.lock().await;
         Ok(task_tracker.generate_report())
     }
+
+    async fn retry_task(&self, key: ProofKey) -> RpcResult<()> {
+        self.task_tracker
+            .lock()
+            .await
+            .retry_task(key)
+            .map_err(to_jsonrpsee_error("could not retry task"))
+    }
+
+    async fn cancel_task(&self, key: ProofKey) -> RpcResult<()> {
+        self.task_tracker
+            .lock()
+            .await
+            .cancel_task(key)
+            .map_err(to_jsonrpsee_error("could not cancel task"))
+    }
+
+    async fn list_tasks(&self, filter: RpcProvingTaskFilter) -> RpcResult<Vec<RpcProvingTask>> {
+        let task_tracker = self.task_tracker.lock().await;
+        let tasks = task_tracker
+            .get_task_entries(|key, task| filter.matches(key, &task.status))
+            .into_iter()
+            .map(|(key, task)| RpcProvingTask::new(key, task))
+            .collect();
+
+        Ok(tasks)
+    }
 }
@@ -1,34 +1,19 @@
-use crate::errors::ProvingTaskError;
+pub use strata_db::types::ProvingTaskStatus;
 
-/// Represents the status of a proving task.
-///
-/// ## State Transitions
-///
-/// - `WaitingForDependencies` -> `Pending`: When all dependencies are resolved.
-/// - `Pending` -> `ProvingInProgress`: When the proving task starts.
-/// - `ProvingInProgress` -> `Completed`: When the proving task completes successfully.
-/// - Any state -> `Failed`: If the task fails at any point.
-#[derive(Debug, Clone, PartialEq)]
-pub enum ProvingTaskStatus {
-    /// Waiting for dependencies to be resolved.
-    WaitingForDependencies,
-    /// Ready to be started
-    Pending,
-    /// Task is currently being executed.
-    ProvingInProgress,
-    /// Task has been completed successfully.
-    Completed,
-    /// Task has failed.
-    Failed,
-}
+use crate::errors::ProvingTaskError;
 
-impl ProvingTaskStatus {
+/// Enforces the state transitions of a [`ProvingTaskStatus`].
+pub trait ProvingTaskStatusExt {
     /// Attempts to transition the current task status to a new status.
     ///
     /// # Returns
     /// * `Ok(())` if the transition is valid
     /// * `Err(ProvingTaskError::InvalidStatusTransition)` if the transition is not allowed
-    pub fn transition(&mut self, target_status: ProvingTaskStatus) -> Result<(), ProvingTaskError> {
+    fn transition(&mut self, target_status: ProvingTaskStatus) -> Result<(), ProvingTaskError>;
+}
+
+impl ProvingTaskStatusExt for ProvingTaskStatus {
+    fn transition(&mut self, target_status: ProvingTaskStatus) -> Result<(), ProvingTaskError> {
         let is_transition_valid = match (self.clone(), &target_status) {
             // Always allow transitioning to Failed
             (_, &ProvingTaskStatus::Failed) => true,
@@ -38,6 +23,21 @@ impl ProvingTaskStatus {
             (ProvingTaskStatus::ProvingInProgress, &ProvingTaskStatus::Completed) => true,
             (ProvingTaskStatus::WaitingForDependencies, &ProvingTaskStatus::Pending) => true,
 
+            // Retrying a failed or cancelled task
+            (
+                ProvingTaskStatus::Failed | ProvingTaskStatus::Cancelled,
+                &ProvingTaskStatus::Pending | &ProvingTaskStatus::WaitingForDependencies,
+            ) => true,
+
+            // Cancelling an unfinished task
+            (
+                ProvingTaskStatus::WaitingForDependencies
+                | ProvingTaskStatus::Pending
+                | ProvingTaskStatus::ProvingInProgress
+                | ProvingTaskStatus::Failed,
+                &ProvingTaskStatus::Cancelled,
+            ) => true,
+
             // All other transitions are invalid
             _ => false,
         };
@@ -150,6 +150,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cancel_and_retry() {
+        let mut status = ProvingTaskStatus::ProvingInProgress;
+        assert!(status.transition(ProvingTaskStatus::Cancelled).is_ok());
+        assert_eq!(status, ProvingTaskStatus::Cancelled);
+
+        assert!(status.transition(ProvingTaskStatus::Pending).is_ok());
+        assert_eq!(status, ProvingTaskStatus::Pending);
+
+        let mut status = ProvingTaskStatus::Failed;
+        assert!(status
+            .transition(ProvingTaskStatus::WaitingForDependencies)
+            .is_ok());
+        assert_eq!(status, ProvingTaskStatus::WaitingForDependencies);
+
+        let mut status = ProvingTaskStatus::Completed;
+        assert!(
+            status.transition(ProvingTaskStatus::Cancelled).is_err(),
+            "Completed task should not be cancellable"
+        );
+    }
+
     #[test]
     fn test_error_details() {
         let mut status = ProvingTaskStatus::Pending;
@@ -1,28 +1,47 @@
-use std::collections::{HashMap, HashSet};
-
-use strata_db::traits::ProofDatabase;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use metrics::gauge;
+use strata_common::metrics::{PROVER_TASKS, STATUS_LABEL, ZKVM_LABEL};
+use strata_db::{traits::ProofDatabase, types::ProvingTaskEntry};
 use strata_primitives::proof::{ProofContext, ProofKey, ProofZkVm};
 use strata_rocksdb::prover::db::ProofDb;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::{errors::ProvingTaskError, status::ProvingTaskStatus};
+use crate::{
+    errors::ProvingTaskError,
+    retry::RetryConfig,
+    status::{ProvingTaskStatus, ProvingTaskStatusExt},
+};
 
 /// Manages tasks and their states for proving operations.
+///
+/// Every change to a task is written through to the [`ProofDb`] so that the tasks survive a
+/// restart of the prover client.
 #[derive(Debug, Clone)]
 pub struct TaskTracker {
-    /// A map of task IDs to their statuses.
-    tasks: HashMap<ProofKey, ProvingTaskStatus>,
-    /// A map of task IDs to their dependencies that have not yet been proven.
-    pending_dependencies: HashMap<ProofKey, HashSet<ProofKey>>,
+    /// A map of task IDs to their entries, including their statuses and the dependencies that
+    /// have not yet been proven.
+    tasks: HashMap<ProofKey, ProvingTaskEntry>,
     /// Count of the tasks that are in progress
     in_progress_tasks: HashMap<ProofZkVm, usize>,
     /// List of ZkVm for which the task is created
     vms: Vec<ProofZkVm>,
+    /// Policies for retrying the failed tasks.
+    retry_config: RetryConfig,
+    /// Database where the tasks are persisted.
+    db: Arc<ProofDb>,
 }
 
 impl TaskTracker {
-    /// Creates a new `TaskTracker` instance.
-    pub fn new() -> Self {
+    /// Creates a new `TaskTracker` instance, rehydrating the tasks persisted in the database.
+    ///
+    /// Tasks that were `ProvingInProgress` when the prover client stopped are re-queued as
+    /// `Pending` since their proving was interrupted.
+    pub fn new(db: Arc<ProofDb>, retry_config: RetryConfig) -> Result<Self, ProvingTaskError> {
         let mut vms = vec![];
 
         #[cfg(feature = "sp1")]
@@ -40,25 +59,55 @@ impl TaskTracker {
             vms.push(ProofZkVm::Native);
         }
 
-        TaskTracker {
-            tasks: HashMap::new(),
-            pending_dependencies: HashMap::new(),
+        let mut tasks = HashMap::new();
+        for (proof_key, mut task) in db
+            .get_all_tasks()
+            .map_err(ProvingTaskError::DatabaseError)?
+        {
+            if task.status == ProvingTaskStatus::ProvingInProgress {
+                warn!(?proof_key, "re-queueing interrupted task");
+                task.status = ProvingTaskStatus::Pending;
+                task.updated_at = now_millis();
+                db.put_task(proof_key, task.clone())
+                    .map_err(ProvingTaskError::DatabaseError)?;
+            }
+
+            tasks.insert(proof_key, task);
+        }
+        info!(num_tasks = %tasks.len(), "loaded proving tasks");
+
+        let tracker = TaskTracker {
+            tasks,
             in_progress_tasks: HashMap::new(),
             vms,
-        }
+            retry_config,
+            db,
+        };
+        tracker.record_metrics();
+
+        Ok(tracker)
     }
 
     pub fn get_in_progress_tasks(&self) -> &HashMap<ProofZkVm, usize> {
         &self.in_progress_tasks
     }
 
+    /// Creates a task for the proof context on each configured zkVM.
+    ///
+    /// The dependencies of the proof context are persisted first, so that its input can be
+    /// fetched from their proofs once they are completed.
     pub fn create_tasks(
         &mut self,
         proof_id: ProofContext,
         deps: Vec<ProofContext>,
-        db: &ProofDb,
     ) -> Result<Vec<ProofKey>, ProvingTaskError> {
         info!(?proof_id, "Creating task for");
+        if !deps.is_empty() {
+            self.db
+                .put_proof_deps(proof_id, deps.clone())
+                .map_err(ProvingTaskError::DatabaseError)?;
+        }
+
         let mut tasks = Vec::with_capacity(self.vms.len());
         // Insert tasks for each configured host
         let vms = &self.vms.clone();
@@ -66,7 +115,7 @@ impl TaskTracker {
             let task = ProofKey::new(proof_id, *host);
             tasks.push(task);
             let dep_tasks: Vec<_> = deps.iter().map(|&dep| ProofKey::new(dep, *host)).collect();
-            self.insert_task(task, &dep_tasks, db)?;
+            self.insert_task(task, &dep_tasks)?;
         }
 
         Ok(tasks)
@@ -78,12 +127,7 @@ impl TaskTracker {
     /// - If dependencies are provided, the task is marked as `WaitingForDependencies`.
     ///
     /// Returns an error if the task already exists.
-    pub fn insert_task(
-        &mut self,
-        id: ProofKey,
-        deps: &[ProofKey],
-        db: &ProofDb,
-    ) -> Result<(), ProvingTaskError> {
+    pub fn insert_task(&mut self, id: ProofKey, deps: &[ProofKey]) -> Result<(), ProvingTaskError> {
         if self.tasks.contains_key(&id) {
             return Err(ProvingTaskError::TaskAlreadyFound(id));
         }
@@ -91,7 +135,10 @@ impl TaskTracker {
         // Gather dependencies that are not completed
         let mut pending_deps = Vec::with_capacity(deps.len());
         for &dep in deps {
-            let proof = db.get_proof(dep).map_err(ProvingTaskError::DatabaseError)?;
+            let proof = self
+                .db
+                .get_proof(dep)
+                .map_err(ProvingTaskError::DatabaseError)?;
             match proof {
                 Some(_) => {}
                 None => {
@@ -100,14 +147,16 @@ impl TaskTracker {
             }
         }
 
-        if pending_deps.is_empty() {
-            self.tasks.insert(id, ProvingTaskStatus::Pending);
+        let status = if pending_deps.is_empty() {
+            ProvingTaskStatus::Pending
         } else {
-            self.pending_dependencies
-                .insert(id, HashSet::from_iter(pending_deps));
-            self.tasks
-                .insert(id, ProvingTaskStatus::WaitingForDependencies);
+            ProvingTaskStatus::WaitingForDependencies
         };
+        let task = ProvingTaskEntry::new(status, pending_deps, now_millis());
+
+        self.persist_task(id, &task)?;
+        self.tasks.insert(id, task);
+        self.record_metrics();
 
         Ok(())
     }
@@ -118,6 +167,7 @@ impl TaskTracker {
     pub fn get_task(&self, id: ProofKey) -> Result<&ProvingTaskStatus, ProvingTaskError> {
         self.tasks
             .get(&id)
+            .map(|task| &task.status)
             .ok_or(ProvingTaskError::TaskNotFound(id))
     }
 
@@ -132,40 +182,222 @@ impl TaskTracker {
         id: ProofKey,
         new_status: ProvingTaskStatus,
     ) -> Result<(), ProvingTaskError> {
-        if let Some(status) = self.tasks.get_mut(&id) {
-            // Check for valid status transitions
-            status.transition(new_status.clone())?;
+        self.update_task(id, new_status, |_| {})
+    }
+
+    /// Starts proving a pending task.
+    ///
+    /// Returns the attempt number of the task, which identifies this run of the proving when its
+    /// result comes back.
+    pub fn start_task(&mut self, id: ProofKey) -> Result<u32, ProvingTaskError> {
+        self.update_status(id, ProvingTaskStatus::ProvingInProgress)?;
+
+        Ok(self.tasks[&id].attempts)
+    }
+
+    /// Checks if the given attempt is still the one proving the task.
+    ///
+    /// This is not the case if the task was cancelled, and possibly retried, while the attempt
+    /// was in flight, in which case its result must be discarded.
+    pub fn is_current_attempt(&self, id: ProofKey, attempt: u32) -> bool {
+        self.tasks.get(&id).is_some_and(|task| {
+            task.status == ProvingTaskStatus::ProvingInProgress && task.attempts == attempt
+        })
+    }
+
+    /// Marks a task as failed for the given reason.
+    ///
+    /// A retry is scheduled if the [`RetryPolicy`](crate::retry::RetryPolicy) for the task's proof
+    /// context allows for it.
+    pub fn fail_task(&mut self, id: ProofKey, reason: String) -> Result<(), ProvingTaskError> {
+        let policy = *self.retry_config.policy(id.context().kind());
+
+        self.update_task(id, ProvingTaskStatus::Failed, |task| {
+            task.retry_at = policy
+                .retry_delay(task.attempts)
+                .map(|delay| task.updated_at.saturating_add(delay));
+            task.failure_reason = Some(reason);
+        })
+    }
+
+    /// Cancels an unfinished task so that it is neither proven nor retried automatically.
+    ///
+    /// The tasks that depend on it, directly or not, could never be proven and are cancelled as
+    /// well. Cancelling a task that is being proven does not abort the proving, but its result is
+    /// discarded.
+    pub fn cancel_task(&mut self, id: ProofKey) -> Result<(), ProvingTaskError> {
+        self.update_task(id, ProvingTaskStatus::Cancelled, |task| {
+            task.retry_at = None;
+        })?;
+
+        for dependent_id in
+            self.get_dependents(id, |status| *status != ProvingTaskStatus::Cancelled)
+        {
+            info!(?dependent_id, cancelled_dep = ?id, "cancelling dependent task");
+            self.update_task(dependent_id, ProvingTaskStatus::Cancelled, |task| {
+                task.retry_at = None;
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Re-queues a failed or cancelled task.
+    ///
+    /// The cancelled tasks that depend on it, directly or not, are re-queued as well since they
+    /// are cancelled along with it.
+    pub fn retry_task(&mut self, id: ProofKey) -> Result<(), ProvingTaskError> {
+        self.requeue_task(id)?;
+
+        for dependent_id in
+            self.get_dependents(id, |status| *status == ProvingTaskStatus::Cancelled)
+        {
+            info!(?dependent_id, retried_dep = ?id, "retrying dependent task");
+            self.requeue_task(dependent_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves a failed or cancelled task back to `Pending`, or to `WaitingForDependencies` if some
+    /// of its dependencies are not proven yet.
+    fn requeue_task(&mut self, id: ProofKey) -> Result<(), ProvingTaskError> {
+        let task = self
+            .tasks
+            .get(&id)
+            .ok_or(ProvingTaskError::TaskNotFound(id))?;
+
+        let new_status = if task.pending_deps.is_empty() {
+            ProvingTaskStatus::Pending
+        } else {
+            ProvingTaskStatus::WaitingForDependencies
+        };
+
+        self.update_task(id, new_status, |task| {
+            task.retry_at = None;
+        })
+    }
 
-            if new_status == ProvingTaskStatus::ProvingInProgress {
-                // Increment value if key exists, or insert with a default value of 1
-                *self.in_progress_tasks.entry(*id.host()).or_insert(0) += 1;
+    /// Collects the tasks that depend on the given one, directly or through other tasks, and
+    /// whose status matches the given filter function.
+    fn get_dependents<F>(&self, id: ProofKey, filter_fn: F) -> Vec<ProofKey>
+    where
+        F: Fn(&ProvingTaskStatus) -> bool,
+    {
+        let mut dependents = vec![];
+        let mut queue = vec![id];
+        while let Some(dep) = queue.pop() {
+            for (dependent_id, dependent_task) in &self.tasks {
+                if dependent_task.pending_deps.contains(&dep)
+                    && filter_fn(&dependent_task.status)
+                    && !dependents.contains(dependent_id)
+                {
+                    dependents.push(*dependent_id);
+                    queue.push(*dependent_id);
+                }
             }
+        }
+
+        dependents
+    }
+
+    /// Re-queues the failed tasks whose scheduled retry is due.
+    ///
+    /// Returns the IDs of the re-queued tasks.
+    pub fn retry_due_tasks(&mut self) -> Result<Vec<ProofKey>, ProvingTaskError> {
+        let now = now_millis();
+        let due_tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| {
+                task.status == ProvingTaskStatus::Failed
+                    && task.retry_at.is_some_and(|retry_at| retry_at <= now)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in &due_tasks {
+            info!(?id, "retrying failed task");
+            self.requeue_task(*id)?;
+        }
+
+        Ok(due_tasks)
+    }
+
+    /// Transitions a task to the given status, applies `update` to it and persists it.
+    ///
+    /// The changes are written to the database before they are applied in memory, so a failed
+    /// write leaves the tracker unchanged.
+    fn update_task(
+        &mut self,
+        id: ProofKey,
+        new_status: ProvingTaskStatus,
+        update: impl FnOnce(&mut ProvingTaskEntry),
+    ) -> Result<(), ProvingTaskError> {
+        let Some(task) = self.tasks.get(&id) else {
+            return Err(ProvingTaskError::TaskNotFound(id));
+        };
+        let mut task = task.clone();
 
-            if new_status == ProvingTaskStatus::Completed {
-                // Decrement value if key exists, or insert with a default value of 1
-                *self.in_progress_tasks.entry(*id.host()).or_insert(0) -= 1;
+        // Check for valid status transitions
+        let prev_status = task.status.clone();
+        task.status.transition(new_status.clone())?;
+        task.updated_at = now_millis();
+        update(&mut task);
 
-                // Resolve dependencies for other tasks
-                let mut tasks_to_update = vec![];
-                for (dependent_task, deps) in self.pending_dependencies.iter_mut() {
-                    if deps.remove(&id) && deps.is_empty() {
-                        tasks_to_update.push(*dependent_task);
-                    }
+        if new_status == ProvingTaskStatus::ProvingInProgress {
+            task.attempts += 1;
+        }
+
+        if new_status == ProvingTaskStatus::Completed {
+            // Resolve dependencies for other tasks
+            let mut resolved_tasks = vec![];
+            for (dependent_id, dependent_task) in &self.tasks {
+                if !dependent_task.pending_deps.contains(&id) {
+                    continue;
                 }
 
-                for task in tasks_to_update {
-                    self.pending_dependencies.remove(&task);
-                    if let Some(task_status) = self.tasks.get_mut(&task) {
-                        task_status.transition(ProvingTaskStatus::Pending)?;
-                    }
+                let mut dependent_task = dependent_task.clone();
+                dependent_task.pending_deps.retain(|dep| *dep != id);
+                if dependent_task.pending_deps.is_empty()
+                    && dependent_task.status == ProvingTaskStatus::WaitingForDependencies
+                {
+                    dependent_task
+                        .status
+                        .transition(ProvingTaskStatus::Pending)?;
                 }
+                dependent_task.updated_at = now_millis();
+                resolved_tasks.push((*dependent_id, dependent_task));
+            }
 
-                self.tasks.remove(&id);
+            for (dependent_id, dependent_task) in &resolved_tasks {
+                self.persist_task(*dependent_id, dependent_task)?;
             }
-            Ok(())
+            self.db
+                .del_task(id)
+                .map_err(ProvingTaskError::DatabaseError)?;
+
+            self.tasks.extend(resolved_tasks);
+            self.tasks.remove(&id);
         } else {
-            Err(ProvingTaskError::TaskNotFound(id))
+            self.persist_task(id, &task)?;
+            self.tasks.insert(id, task);
+        }
+
+        if prev_status == ProvingTaskStatus::ProvingInProgress {
+            // The task is not being proven anymore, regardless of the outcome
+            if let Some(count) = self.in_progress_tasks.get_mut(id.host()) {
+                *count = count.saturating_sub(1);
+            }
+        }
+
+        if new_status == ProvingTaskStatus::ProvingInProgress {
+            // Increment value if key exists, or insert with a default value of 1
+            *self.in_progress_tasks.entry(*id.host()).or_insert(0) += 1;
         }
+        self.record_metrics();
+
+        Ok(())
     }
 
     /// Filters and retrieves a list of `ProofKey` references for tasks whose status
@@ -174,7 +406,7 @@ impl TaskTracker {
     /// # Example
     ///
     /// ```rust
-    /// let task_tracker = TaskTracker::new();
+    /// let task_tracker = TaskTracker::new(db, RetryConfig::default())?;
     /// let pending_tasks =
     ///     task_tracker.get_tasks_by_status(|status| matches!(status, ProvingTaskStatus::Pending));
     /// ```
@@ -185,7 +417,7 @@ impl TaskTracker {
         self.tasks
             .iter()
             .filter_map(|(proof_key, task)| {
-                if filter_fn(task) {
+                if filter_fn(&task.status) {
                     Some(*proof_key) // Only return the `proof_key` if the task matches the filter
                 } else {
                     None
@@ -194,16 +426,87 @@ impl TaskTracker {
             .collect()
     }
 
+    /// Retrieves the tasks, along with their entries, that match the given filter function.
+    pub fn get_task_entries<F>(&self, filter_fn: F) -> Vec<(ProofKey, ProvingTaskEntry)>
+    where
+        F: Fn(&ProofKey, &ProvingTaskEntry) -> bool,
+    {
+        self.tasks
+            .iter()
+            .filter(|(proof_key, task)| filter_fn(proof_key, task))
+            .map(|(proof_key, task)| (*proof_key, task.clone()))
+            .collect()
+    }
+
     /// Generates a report of task statuses and their counts across all tasks.
     pub fn generate_report(&self) -> HashMap<String, usize> {
         let mut report: HashMap<String, usize> = HashMap::new();
 
-        for status in self.tasks.values() {
-            *report.entry(format!("{:?}", status)).or_insert(0) += 1;
+        for task in self.tasks.values() {
+            *report.entry(format!("{:?}", task.status)).or_insert(0) += 1;
         }
 
         report
     }
+
+    /// Writes the task through to the database.
+    fn persist_task(&self, id: ProofKey, task: &ProvingTaskEntry) -> Result<(), ProvingTaskError> {
+        self.db
+            .put_task(id, task.clone())
+            .map_err(ProvingTaskError::DatabaseError)
+    }
+
+    /// Records the number of tracked tasks per zkVM and status.
+    ///
+    /// Every combination is recorded so that the gauges drop back to zero once the tasks move on.
+    fn record_metrics(&self) {
+        let mut counts: HashMap<(ProofZkVm, &'static str), usize> = HashMap::new();
+        for (proof_key, task) in &self.tasks {
+            *counts
+                .entry((*proof_key.host(), status_label(&task.status)))
+                .or_insert(0) += 1;
+        }
+
+        for vm in &self.vms {
+            for status in TRACKED_STATUSES {
+                let label = status_label(&status);
+                let count = counts.get(&(*vm, label)).copied().unwrap_or(0);
+                gauge!(PROVER_TASKS, ZKVM_LABEL => format!("{:?}", vm), STATUS_LABEL => label)
+                    .set(count as f64);
+            }
+        }
+    }
+}
+
+/// Statuses of the tasks that stay in the [`TaskTracker`].
+///
+/// Completed tasks are removed from the tracker, so they are not reported.
+const TRACKED_STATUSES: [ProvingTaskStatus; 5] = [
+    ProvingTaskStatus::WaitingForDependencies,
+    ProvingTaskStatus::Pending,
+    ProvingTaskStatus::ProvingInProgress,
+    ProvingTaskStatus::Failed,
+    ProvingTaskStatus::Cancelled,
+];
+
+/// Returns the metrics label for the given [`ProvingTaskStatus`].
+fn status_label(status: &ProvingTaskStatus) -> &'static str {
+    match status {
+        ProvingTaskStatus::WaitingForDependencies => "waiting_for_dependencies",
+        ProvingTaskStatus::Pending => "pending",
+        ProvingTaskStatus::ProvingInProgress => "proving_in_progress",
+        ProvingTaskStatus::Completed => "completed",
+        ProvingTaskStatus::Failed => "failed",
+        ProvingTaskStatus::Cancelled => "cancelled",
+    }
+}
+
+/// Returns the current time in milliseconds since the UNIX epoch.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis() as u64
 }
 
 #[cfg(test)]
@@ -214,6 +517,7 @@ mod tests {
     use strata_test_utils::ArbitraryGenerator;
 
     use super::*;
+    use crate::retry::RetryPolicy;
 
     // Helper function to generate test L1 block IDs
     fn gen_task_with_deps(n: u64) -> (ProofKey, Vec<ProofKey>) {
@@ -236,18 +540,17 @@ mod tests {
         (key, deps)
     }
 
-    fn setup_db() -> ProofDb {
+    fn setup_db() -> Arc<ProofDb> {
         let (db, db_ops) = get_rocksdb_tmp_instance_for_prover().unwrap();
-        ProofDb::new(db, db_ops)
+        Arc::new(ProofDb::new(db, db_ops))
     }
 
     #[test]
     fn test_insert_task_no_dependencies() {
-        let mut tracker = TaskTracker::new();
+        let mut tracker = TaskTracker::new(setup_db(), RetryConfig::default()).unwrap();
         let (id, _) = gen_task_with_deps(0);
-        let db = setup_db();
 
-        tracker.insert_task(id, &[], &db).unwrap();
+        tracker.insert_task(id, &[]).unwrap();
         assert!(
             matches!(tracker.get_task(id), Ok(&ProvingTaskStatus::Pending)),
             "Task with no dependencies should be Pending"
@@ -256,14 +559,13 @@ mod tests {
 
     #[test]
     fn test_insert_task_with_dependencies() {
-        let mut tracker = TaskTracker::new();
+        let mut tracker = TaskTracker::new(setup_db(), RetryConfig::default()).unwrap();
         let (id, deps) = gen_task_with_deps(2);
-        let db = setup_db();
 
         for dep in &deps {
-            tracker.insert_task(*dep, &[], &db).unwrap();
+            tracker.insert_task(*dep, &[]).unwrap();
         }
-        tracker.insert_task(id, &deps.clone(), &db).unwrap();
+        tracker.insert_task(id, &deps.clone()).unwrap();
         assert!(
             matches!(
                 tracker.get_task(id),
@@ -273,9 +575,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_tasks_with_dependencies() {
+        let db = setup_db();
+        let mut tracker = TaskTracker::new(db.clone(), RetryConfig::default()).unwrap();
+        let (id, deps) = gen_task_with_deps(2);
+        let dep_contexts: Vec<_> = deps.iter().map(|dep| *dep.context()).collect();
+
+        let tasks = tracker
+            .create_tasks(*id.context(), dep_contexts.clone())
+            .unwrap();
+        assert!(tasks.iter().all(|task| task.context() == id.context()));
+        assert_eq!(
+            db.get_proof_deps(*id.context()).unwrap(),
+            Some(dep_contexts.clone()),
+            "Dependencies should be persisted along with the tasks"
+        );
+
+        assert!(
+            tracker.create_tasks(*id.context(), dep_contexts).is_err(),
+            "Tasks should not be created twice"
+        );
+        assert!(
+            matches!(
+                tracker.get_task(tasks[0]),
+                Ok(&ProvingTaskStatus::WaitingForDependencies)
+            ),
+            "Existing task should be left untouched"
+        );
+    }
+
     #[test]
     fn test_task_not_found_error() {
-        let mut tracker = TaskTracker::new();
+        let mut tracker = TaskTracker::new(setup_db(), RetryConfig::default()).unwrap();
         let (id, _) = gen_task_with_deps(0);
 
         let result = tracker.update_status(id, ProvingTaskStatus::Pending);
@@ -284,14 +616,13 @@ mod tests {
 
     #[test]
     fn test_dependency_resolution() {
-        let mut tracker = TaskTracker::new();
+        let mut tracker = TaskTracker::new(setup_db(), RetryConfig::default()).unwrap();
         let (id, deps) = gen_task_with_deps(2);
-        let db = setup_db();
 
         for dep in &deps {
-            tracker.insert_task(*dep, &[], &db).unwrap();
+            tracker.insert_task(*dep, &[]).unwrap();
         }
-        tracker.insert_task(id, &deps, &db).unwrap();
+        tracker.insert_task(id, &deps).unwrap();
 
         for dep in &deps {
             tracker
@@ -304,4 +635,154 @@ mod tests {
             "Task should become Pending after all dependencies are resolved"
         );
     }
+
+    #[test]
+    fn test_rehydrate_tasks() {
+        let db = setup_db();
+        let (id, deps) = gen_task_with_deps(1);
+        let dep = deps[0];
+
+        let mut tracker = TaskTracker::new(db.clone(), RetryConfig::default()).unwrap();
+        tracker.insert_task(dep, &[]).unwrap();
+        tracker.insert_task(id, &deps).unwrap();
+        tracker
+            .update_status(dep, ProvingTaskStatus::ProvingInProgress)
+            .unwrap();
+
+        // Simulate a restart while the dependency is being proven.
+        drop(tracker);
+        let mut tracker = TaskTracker::new(db, RetryConfig::default()).unwrap();
+
+        assert!(
+            matches!(tracker.get_task(dep), Ok(&ProvingTaskStatus::Pending)),
+            "Interrupted task should be re-queued as Pending"
+        );
+        assert!(
+            matches!(
+                tracker.get_task(id),
+                Ok(&ProvingTaskStatus::WaitingForDependencies)
+            ),
+            "Task should still be waiting for its dependency"
+        );
+
+        tracker
+            .update_status(dep, ProvingTaskStatus::ProvingInProgress)
+            .and_then(|_| tracker.update_status(dep, ProvingTaskStatus::Completed))
+            .unwrap();
+        assert!(
+            matches!(tracker.get_task(id), Ok(&ProvingTaskStatus::Pending)),
+            "Dependencies should be resolved after rehydration"
+        );
+    }
+
+    #[test]
+    fn test_fail_and_retry_task() {
+        let retry_config = RetryConfig {
+            default: RetryPolicy {
+                max_retries: 1,
+                base_delay_ms: 0,
+                max_delay_ms: 0,
+            },
+            ..Default::default()
+        };
+        let mut tracker = TaskTracker::new(setup_db(), retry_config).unwrap();
+        let (id, _) = gen_task_with_deps(0);
+        tracker.insert_task(id, &[]).unwrap();
+
+        tracker
+            .update_status(id, ProvingTaskStatus::ProvingInProgress)
+            .unwrap();
+        tracker.fail_task(id, "first failure".to_string()).unwrap();
+        assert_eq!(tracker.get_in_progress_tasks().get(id.host()), Some(&0));
+
+        assert_eq!(tracker.retry_due_tasks().unwrap(), vec![id]);
+        assert!(
+            matches!(tracker.get_task(id), Ok(&ProvingTaskStatus::Pending)),
+            "Failed task should be retried"
+        );
+
+        tracker
+            .update_status(id, ProvingTaskStatus::ProvingInProgress)
+            .unwrap();
+        tracker.fail_task(id, "second failure".to_string()).unwrap();
+        assert!(
+            tracker.retry_due_tasks().unwrap().is_empty(),
+            "Task should not be retried once out of retries"
+        );
+
+        let entries = tracker.get_task_entries(|key, _| *key == id);
+        assert_eq!(entries[0].1.attempts, 2);
+        assert_eq!(
+            entries[0].1.failure_reason.as_deref(),
+            Some("second failure")
+        );
+
+        tracker.cancel_task(id).unwrap();
+        tracker.retry_task(id).unwrap();
+        assert!(
+            matches!(tracker.get_task(id), Ok(&ProvingTaskStatus::Pending)),
+            "Cancelled task should be retried manually"
+        );
+    }
+
+    #[test]
+    fn test_discard_stale_attempt() {
+        let mut tracker = TaskTracker::new(setup_db(), RetryConfig::default()).unwrap();
+        let (id, _) = gen_task_with_deps(0);
+        tracker.insert_task(id, &[]).unwrap();
+
+        let attempt = tracker.start_task(id).unwrap();
+        assert!(tracker.is_current_attempt(id, attempt));
+
+        // Cancel and retry the task while the first attempt is still in flight.
+        tracker.cancel_task(id).unwrap();
+        assert!(
+            !tracker.is_current_attempt(id, attempt),
+            "Attempt of a cancelled task should be stale"
+        );
+
+        tracker.retry_task(id).unwrap();
+        let retried_attempt = tracker.start_task(id).unwrap();
+        assert!(
+            !tracker.is_current_attempt(id, attempt),
+            "Attempt should be stale once the task is retried"
+        );
+        assert!(tracker.is_current_attempt(id, retried_attempt));
+        assert_eq!(tracker.get_in_progress_tasks().get(id.host()), Some(&1));
+    }
+
+    #[test]
+    fn test_cancel_and_retry_dependents() {
+        let mut tracker = TaskTracker::new(setup_db(), RetryConfig::default()).unwrap();
+        let (id, deps) = gen_task_with_deps(1);
+        let dep = deps[0];
+        let (other_id, _) = gen_task_with_deps(0);
+
+        tracker.insert_task(dep, &[]).unwrap();
+        tracker.insert_task(id, &deps).unwrap();
+        tracker.insert_task(other_id, &[id]).unwrap();
+
+        tracker.cancel_task(dep).unwrap();
+        for task in [id, other_id] {
+            assert!(
+                matches!(tracker.get_task(task), Ok(&ProvingTaskStatus::Cancelled)),
+                "Tasks depending on a cancelled task should be cancelled"
+            );
+        }
+
+        tracker.retry_task(dep).unwrap();
+        assert!(matches!(
+            tracker.get_task(dep),
+            Ok(&ProvingTaskStatus::Pending)
+        ));
+        for task in [id, other_id] {
+            assert!(
+                matches!(
+                    tracker.get_task(task),
+                    Ok(&ProvingTaskStatus::WaitingForDependencies)
+                ),
+                "Dependents should be re-queued along with the retried task"
+            );
+        }
+    }
 }
@@ -0,0 +1,173 @@
+use std::{fmt, fs, path::Path, str::FromStr};
+
+use argh::FromArgs;
+use bdk_wallet::{
+    bitcoin::{psbt::ExtractTxError, secp256k1::Secp256k1, Psbt, Transaction},
+    miniscript::psbt::PsbtExt,
+    Wallet,
+};
+use strata_primitives::l1::BitcoinPsbt;
+
+use crate::{
+    keys::Keys,
+    link::{OnchainObject, PrettyPrint},
+    settings::Settings,
+    signet::SignetWallet,
+};
+
+/// Finalizes a PSBT that was signed offline and broadcasts its transaction to signet
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "broadcast-psbt")]
+pub struct BroadcastPsbtArgs {
+    /// the signed PSBT, either base64 encoded or a path to a file containing it
+    #[argh(positional)]
+    psbt: String,
+}
+
+pub async fn broadcast_psbt(args: BroadcastPsbtArgs, keys: Keys, settings: Settings) {
+    let encoded = if Path::new(&args.psbt).is_file() {
+        fs::read_to_string(&args.psbt).expect("readable PSBT file")
+    } else {
+        args.psbt
+    };
+    let psbt = BitcoinPsbt::from(Psbt::from_str(encoded.trim()).expect("valid base64 PSBT"));
+    println!("Finalizing transaction {}", psbt.compute_txid());
+
+    let l1w = SignetWallet::from_keys(&keys, settings.network, settings.signet_backend.clone())
+        .expect("valid wallet");
+    let tx = match finalize_psbt(psbt.into(), &l1w) {
+        Ok(tx) => tx,
+        Err(e) => {
+            println!("{e}");
+            std::process::exit(1)
+        }
+    };
+
+    settings
+        .signet_backend
+        .broadcast_tx(&tx)
+        .await
+        .expect("successful broadcast");
+    let txid = tx.compute_txid();
+    println!(
+        "{}",
+        OnchainObject::from(&txid)
+            .with_maybe_explorer(settings.mempool_space_endpoint.as_deref())
+            .pretty(),
+    );
+}
+
+/// Why a signed PSBT could not be turned into a transaction.
+#[derive(Debug)]
+enum FinalizePsbtError {
+    /// The input does not spend an output of this wallet, so the PSBT was built for another one.
+    ForeignInput(usize),
+    /// The input could not be finalized, usually because it is missing signatures.
+    Incomplete(usize, String),
+    /// The finalized transaction could not be extracted.
+    Extract(ExtractTxError),
+}
+
+impl fmt::Display for FinalizePsbtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FinalizePsbtError::ForeignInput(index) => {
+                write!(f, "Input {index} does not spend from this wallet")
+            }
+            FinalizePsbtError::Incomplete(index, e) => {
+                write!(f, "Failed to finalize input {index}: {e}")
+            }
+            FinalizePsbtError::Extract(e) => write!(f, "Failed to extract transaction: {e}"),
+        }
+    }
+}
+
+/// Checks that every input of the PSBT spends from the wallet, finalizes the inputs that the
+/// signer did not finalize and extracts the signed transaction.
+fn finalize_psbt(mut psbt: Psbt, wallet: &Wallet) -> Result<Transaction, FinalizePsbtError> {
+    let secp = Secp256k1::verification_only();
+    for index in 0..psbt.inputs.len() {
+        let is_mine = psbt
+            .spend_utxo(index)
+            .is_ok_and(|prevout| wallet.is_mine(prevout.script_pubkey.clone()));
+        if !is_mine {
+            return Err(FinalizePsbtError::ForeignInput(index));
+        }
+
+        let input = &psbt.inputs[index];
+        // the signer may have finalized the input already
+        if input.final_script_witness.is_some() || input.final_script_sig.is_some() {
+            continue;
+        }
+        psbt.finalize_inp_mut(&secp, index)
+            .map_err(|e| FinalizePsbtError::Incomplete(index, e.to_string()))?;
+    }
+
+    psbt.extract_tx().map_err(FinalizePsbtError::Extract)
+}
+
+#[cfg(test)]
+mod tests {
+    use bdk_wallet::SignOptions;
+
+    use super::*;
+    use crate::keys::tests::{funded_watch_only_wallet, signer_wallet, unsigned_psbt};
+
+    /// Options of an offline signer that leaves finalizing to the cli.
+    fn sign_options() -> SignOptions {
+        SignOptions {
+            try_finalize: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_finalize_externally_signed_psbt() {
+        let mut wallet = funded_watch_only_wallet(1);
+        let mut psbt = unsigned_psbt(&mut wallet);
+
+        let finalized = signer_wallet(1)
+            .sign(&mut psbt, sign_options())
+            .expect("signable psbt");
+        assert!(!finalized, "signer must leave finalizing to the cli");
+
+        let tx = finalize_psbt(psbt, &wallet).expect("signed psbt must finalize");
+        assert!(
+            tx.input.iter().all(|input| !input.witness.is_empty()),
+            "every input must be signed"
+        );
+    }
+
+    #[test]
+    fn test_reject_incomplete_psbt() {
+        let mut wallet = funded_watch_only_wallet(1);
+        let psbt = unsigned_psbt(&mut wallet);
+
+        assert!(
+            matches!(
+                finalize_psbt(psbt, &wallet),
+                Err(FinalizePsbtError::Incomplete(0, _))
+            ),
+            "unsigned input must not finalize"
+        );
+    }
+
+    #[test]
+    fn test_reject_psbt_of_other_wallet() {
+        let wallet = funded_watch_only_wallet(1);
+
+        let mut other_wallet = funded_watch_only_wallet(2);
+        let mut psbt = unsigned_psbt(&mut other_wallet);
+        signer_wallet(2)
+            .sign(&mut psbt, sign_options())
+            .expect("signable psbt");
+
+        assert!(
+            matches!(
+                finalize_psbt(psbt, &wallet),
+                Err(FinalizePsbtError::ForeignInput(0))
+            ),
+            "psbt built for another wallet must be rejected"
+        );
+    }
+}
@@ -17,17 +17,18 @@ use strata_primitives::constants::UNSPENDABLE_PUBLIC_KEY;
 
 use crate::{
     constants::{BRIDGE_IN_AMOUNT, RECOVER_AT_DELAY, RECOVER_DELAY, SIGNET_BLOCK_TIME},
+    keys::Keys,
     link::{OnchainObject, PrettyPrint},
     recovery::DescriptorRecovery,
-    seed::Seed,
     settings::Settings,
-    signet::{get_fee_rate, log_fee_rate, SignetWallet},
+    signet::{get_fee_rate, log_fee_rate, print_unsigned_psbt, SignetWallet},
     strata::StrataWallet,
     taproot::{ExtractP2trPubkey, NotTaprootAddress},
 };
 
 /// Deposit 10 BTC from signet to Strata. If an address is not provided, the wallet's internal
-/// Strata address will be used.
+/// Strata address will be used. In watch-only mode, an address must be provided and an unsigned
+/// PSBT is printed instead of broadcasting the deposit.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "deposit")]
 pub struct DepositArgs {
@@ -44,20 +45,28 @@ pub async fn deposit(
         strata_address,
         fee_rate,
     }: DepositArgs,
-    seed: Seed,
+    keys: Keys,
     settings: Settings,
 ) {
     let requested_strata_address =
         strata_address.map(|a| StrataAddress::from_str(&a).expect("bad strata address"));
+    let strata_address = match (requested_strata_address, &keys) {
+        (Some(address), _) => address,
+        (None, Keys::Seed(seed)) => StrataWallet::new(seed, &settings.strata_endpoint)
+            .unwrap()
+            .default_signer_address(),
+        (None, Keys::WatchOnly(_)) => {
+            println!("A Strata address must be provided in watch-only mode");
+            std::process::exit(1)
+        }
+    };
     let mut l1w =
-        SignetWallet::new(&seed, settings.network, settings.signet_backend.clone()).unwrap();
-    let l2w = StrataWallet::new(&seed, &settings.strata_endpoint).unwrap();
+        SignetWallet::from_keys(&keys, settings.network, settings.signet_backend.clone()).unwrap();
 
     l1w.sync().await.unwrap();
     let recovery_address = l1w.reveal_next_address(KeychainKind::External).address;
     l1w.persist().unwrap();
 
-    let strata_address = requested_strata_address.unwrap_or(l2w.default_signer_address());
     println!(
         "Bridging {} to Strata address {}",
         BRIDGE_IN_AMOUNT.to_string().green(),
@@ -120,15 +129,11 @@ pub async fn deposit(
         builder.fee_rate(fee_rate);
         builder.finish().expect("valid psbt")
     };
-    l1w.sign(&mut psbt, Default::default()).unwrap();
-    println!("Built transaction");
-
-    let tx = psbt.extract_tx().expect("valid tx");
 
     let pb = ProgressBar::new_spinner().with_message("Saving output descriptor");
     pb.enable_steady_tick(Duration::from_millis(100));
 
-    let mut desc_file = DescriptorRecovery::open(&seed, &settings.descriptor_db)
+    let mut desc_file = DescriptorRecovery::open(&keys, &settings.descriptor_db)
         .await
         .unwrap();
     desc_file
@@ -137,6 +142,16 @@ pub async fn deposit(
         .unwrap();
     pb.finish_with_message("Saved output descriptor");
 
+    if keys.is_watch_only() {
+        print_unsigned_psbt(psbt);
+        return;
+    }
+
+    l1w.sign(&mut psbt, Default::default()).unwrap();
+    println!("Built transaction");
+
+    let tx = psbt.extract_tx().expect("valid tx");
+
     let pb = ProgressBar::new_spinner().with_message("Broadcasting transaction");
     pb.enable_steady_tick(Duration::from_millis(100));
     settings
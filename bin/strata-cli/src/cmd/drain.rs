@@ -10,15 +10,16 @@ use colored::Colorize;
 
 use crate::{
     constants::SATS_TO_WEI,
+    keys::Keys,
     link::{OnchainObject, PrettyPrint},
-    seed::Seed,
     settings::Settings,
-    signet::{get_fee_rate, log_fee_rate, SignetWallet},
+    signet::{get_fee_rate, log_fee_rate, print_unsigned_psbt, SignetWallet},
     strata::StrataWallet,
 };
 
 /// Drains the internal wallet to the provided
-/// signet and Strata addresses
+/// signet and Strata addresses. In watch-only mode, an unsigned PSBT is printed for the signet
+/// drain instead of broadcasting it
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "drain")]
 pub struct DrainArgs {
@@ -41,7 +42,7 @@ pub async fn drain(
         strata_address,
         fee_rate,
     }: DrainArgs,
-    seed: Seed,
+    keys: Keys,
     settings: Settings,
 ) {
     if strata_address.is_none() && signet_address.is_none() {
@@ -59,7 +60,8 @@ pub async fn drain(
 
     if let Some(address) = signet_address {
         let mut l1w =
-            SignetWallet::new(&seed, settings.network, settings.signet_backend.clone()).unwrap();
+            SignetWallet::from_keys(&keys, settings.network, settings.signet_backend.clone())
+                .unwrap();
         l1w.sync().await.unwrap();
         let balance = l1w.balance();
         if balance.untrusted_pending > Amount::ZERO {
@@ -78,20 +80,25 @@ pub async fn drain(
             builder.fee_rate(fee_rate);
             builder.finish().expect("valid transaction")
         };
-        l1w.sign(&mut psbt, Default::default()).unwrap();
-        let tx = psbt.extract_tx().expect("fully signed tx");
-        settings.signet_backend.broadcast_tx(&tx).await.unwrap();
-        let txid = tx.compute_txid();
-        println!(
-            "{}",
-            OnchainObject::from(&txid)
-                .with_maybe_explorer(settings.mempool_space_endpoint.as_deref())
-                .pretty()
-        );
-        println!("Drained signet wallet to {}", address,);
+        if keys.is_watch_only() {
+            print_unsigned_psbt(psbt);
+        } else {
+            l1w.sign(&mut psbt, Default::default()).unwrap();
+            let tx = psbt.extract_tx().expect("fully signed tx");
+            settings.signet_backend.broadcast_tx(&tx).await.unwrap();
+            let txid = tx.compute_txid();
+            println!(
+                "{}",
+                OnchainObject::from(&txid)
+                    .with_maybe_explorer(settings.mempool_space_endpoint.as_deref())
+                    .pretty()
+            );
+            println!("Drained signet wallet to {}", address,);
+        }
     }
 
     if let Some(address) = strata_address {
+        let seed = keys.seed_or_exit();
         let l2w = StrataWallet::new(&seed, &settings.strata_endpoint).unwrap();
         let balance = l2w.get_balance(l2w.default_signer_address()).await.unwrap();
         if balance == U256::ZERO {
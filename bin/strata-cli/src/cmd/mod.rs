@@ -1,6 +1,7 @@
 use argh::FromArgs;
 use backup::BackupArgs;
 use balance::BalanceArgs;
+use broadcast_psbt::BroadcastPsbtArgs;
 use change_pwd::ChangePwdArgs;
 use config::ConfigArgs;
 use deposit::DepositArgs;
@@ -15,6 +16,7 @@ use withdraw::WithdrawArgs;
 
 pub mod backup;
 pub mod balance;
+pub mod broadcast_psbt;
 pub mod change_pwd;
 pub mod config;
 pub mod deposit;
@@ -50,4 +52,5 @@ pub enum Commands {
     Reset(ResetArgs),
     Scan(ScanArgs),
     Config(ConfigArgs),
+    BroadcastPsbt(BroadcastPsbtArgs),
 }
@@ -7,13 +7,14 @@ use colored::Colorize;
 
 use crate::{
     constants::RECOVERY_DESC_CLEANUP_DELAY,
+    keys::Keys,
     recovery::DescriptorRecovery,
-    seed::Seed,
     settings::Settings,
-    signet::{get_fee_rate, log_fee_rate, sync_wallet, SignetWallet},
+    signet::{get_fee_rate, log_fee_rate, print_unsigned_psbt, sync_wallet, SignetWallet},
 };
 
-/// Attempt recovery of old deposit transactions
+/// Attempt recovery of old deposit transactions. In watch-only mode, an unsigned PSBT is printed
+/// for every recovery instead of broadcasting it
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "recover")]
 pub struct RecoverArgs {
@@ -22,13 +23,13 @@ pub struct RecoverArgs {
     fee_rate: Option<u64>,
 }
 
-pub async fn recover(args: RecoverArgs, seed: Seed, settings: Settings) {
+pub async fn recover(args: RecoverArgs, keys: Keys, settings: Settings) {
     let mut l1w =
-        SignetWallet::new(&seed, settings.network, settings.signet_backend.clone()).unwrap();
+        SignetWallet::from_keys(&keys, settings.network, settings.signet_backend.clone()).unwrap();
     l1w.sync().await.unwrap();
 
     println!("Opening descriptor recovery");
-    let mut descriptor_file = DescriptorRecovery::open(&seed, &settings.descriptor_db)
+    let mut descriptor_file = DescriptorRecovery::open(&keys, &settings.descriptor_db)
         .await
         .unwrap();
     let current_height = l1w.local_chain().get_chain_tip().unwrap().height;
@@ -95,6 +96,11 @@ pub async fn recover(args: RecoverArgs, seed: Seed, settings: Settings) {
             builder.finish().expect("valid tx")
         };
 
+        if keys.is_watch_only() {
+            print_unsigned_psbt(psbt);
+            continue;
+        }
+
         recovery_wallet
             .sign(&mut psbt, Default::default())
             .expect("valid sign op");
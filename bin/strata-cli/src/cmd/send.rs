@@ -11,15 +11,16 @@ use bdk_wallet::bitcoin::{Address, Amount};
 
 use crate::{
     constants::SATS_TO_WEI,
+    keys::Keys,
     link::{OnchainObject, PrettyPrint},
     net_type::{net_type_or_exit, NetworkType},
-    seed::Seed,
     settings::Settings,
-    signet::{get_fee_rate, log_fee_rate, SignetWallet},
+    signet::{get_fee_rate, log_fee_rate, print_unsigned_psbt, SignetWallet},
     strata::StrataWallet,
 };
 
-/// Send some bitcoin from the internal wallet.
+/// Send some bitcoin from the internal wallet. In watch-only mode, an unsigned PSBT is printed
+/// instead of broadcasting a signet transaction.
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "send")]
 pub struct SendArgs {
@@ -40,7 +41,7 @@ pub struct SendArgs {
     fee_rate: Option<u64>,
 }
 
-pub async fn send(args: SendArgs, seed: Seed, settings: Settings) {
+pub async fn send(args: SendArgs, keys: Keys, settings: Settings) {
     let network_type = net_type_or_exit(&args.network_type);
 
     match network_type {
@@ -51,7 +52,7 @@ pub async fn send(args: SendArgs, seed: Seed, settings: Settings) {
                 .require_network(settings.network)
                 .expect("correct network");
             let mut l1w =
-                SignetWallet::new(&seed, settings.network, settings.signet_backend.clone())
+                SignetWallet::from_keys(&keys, settings.network, settings.signet_backend.clone())
                     .expect("valid wallet");
             l1w.sync().await.unwrap();
             let fee_rate = get_fee_rate(args.fee_rate, settings.signet_backend.as_ref()).await;
@@ -62,6 +63,10 @@ pub async fn send(args: SendArgs, seed: Seed, settings: Settings) {
                 builder.fee_rate(fee_rate);
                 builder.finish().expect("valid psbt")
             };
+            if keys.is_watch_only() {
+                print_unsigned_psbt(psbt);
+                return;
+            }
             l1w.sign(&mut psbt, Default::default())
                 .expect("signable psbt");
             let tx = psbt.extract_tx().expect("signed tx");
@@ -79,6 +84,7 @@ pub async fn send(args: SendArgs, seed: Seed, settings: Settings) {
             );
         }
         NetworkType::Strata => {
+            let seed = keys.seed_or_exit();
             let l2w = StrataWallet::new(&seed, &settings.strata_endpoint).expect("valid wallet");
             let address = StrataAddress::from_str(&args.address).expect("valid address");
             let tx = TransactionRequest::default()
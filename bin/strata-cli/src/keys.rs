@@ -0,0 +1,209 @@
+use std::str::FromStr;
+
+use bdk_wallet::{keys::DescriptorPublicKey, miniscript::Descriptor, KeychainKind, Wallet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::seed::{BaseWallet, Seed};
+
+/// Public descriptors of a signet wallet whose keys are held elsewhere.
+///
+/// Configured through the `[watch_only]` table of the config file. The descriptors should include
+/// key origins (i.e. `tr([fingerprint/86h/1h/0h]tpub.../0/*)`) so that the offline signer can
+/// find the keys for the PSBTs emitted by the CLI.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WatchOnly {
+    /// Descriptor of the external (receive) keychain.
+    pub external_descriptor: String,
+    /// Descriptor of the internal (change) keychain.
+    pub internal_descriptor: String,
+}
+
+impl WatchOnly {
+    pub fn signet_wallet(&self) -> BaseWallet {
+        let external_desc = parse_public_descriptor(&self.external_descriptor);
+        let internal_desc = parse_public_descriptor(&self.internal_descriptor);
+        BaseWallet(
+            Wallet::load()
+                .descriptor(KeychainKind::External, Some(external_desc.clone()))
+                .descriptor(KeychainKind::Internal, Some(internal_desc.clone())),
+            Wallet::create(external_desc, internal_desc),
+        )
+    }
+
+    /// Key for the descriptor recovery file of the watch-only wallet.
+    ///
+    /// The file only holds bridge-in descriptors with public keys, so this just ties the file to
+    /// the configured descriptors rather than keeping anything secret.
+    pub fn descriptor_recovery_key(&self) -> [u8; 32] {
+        let mut hasher = <Sha256 as Digest>::new(); // this is to appease the analyzer
+        hasher.update(b"alpen labs strata watch-only descriptor recovery file 2024");
+        hasher.update(self.external_descriptor.as_bytes());
+        hasher.update(self.internal_descriptor.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+fn parse_public_descriptor(desc: &str) -> Descriptor<DescriptorPublicKey> {
+    Descriptor::<DescriptorPublicKey>::from_str(desc)
+        .expect("watch-only descriptor should be valid and only contain public keys")
+}
+
+/// Where the CLI gets the keys of its signet wallet from.
+pub enum Keys {
+    /// Keys are derived from the [`Seed`] and transactions get signed right away.
+    Seed(Seed),
+    /// Only public descriptors are known and transactions are emitted as unsigned PSBTs.
+    WatchOnly(WatchOnly),
+}
+
+impl Keys {
+    pub fn is_watch_only(&self) -> bool {
+        matches!(self, Keys::WatchOnly(_))
+    }
+
+    pub fn signet_wallet(&self) -> BaseWallet {
+        match self {
+            Keys::Seed(seed) => seed.signet_wallet(),
+            Keys::WatchOnly(watch_only) => watch_only.signet_wallet(),
+        }
+    }
+
+    pub fn descriptor_recovery_key(&self) -> [u8; 32] {
+        match self {
+            Keys::Seed(seed) => seed.descriptor_recovery_key(),
+            Keys::WatchOnly(watch_only) => watch_only.descriptor_recovery_key(),
+        }
+    }
+
+    /// Returns the [`Seed`]. Prints error message and exits if the CLI is in watch-only mode.
+    pub fn seed_or_exit(self) -> Seed {
+        match self {
+            Keys::Seed(seed) => seed,
+            Keys::WatchOnly(_) => {
+                println!("This command requires the seed and is not available in watch-only mode");
+                std::process::exit(1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use bdk_wallet::{
+        bitcoin::{
+            absolute::LockTime,
+            bip32::{DerivationPath, Xpriv, Xpub},
+            hashes::Hash,
+            secp256k1::Secp256k1,
+            transaction::Version,
+            Amount, Network, OutPoint, Psbt, Transaction, TxIn, TxOut, Txid,
+        },
+        SignOptions,
+    };
+
+    use super::*;
+
+    const ACCOUNT_PATH: &str = "86h/1h/0h";
+
+    fn master_key(seed: u8) -> Xpriv {
+        Xpriv::new_master(Network::Signet, &[seed; 32]).expect("valid xpriv")
+    }
+
+    /// Public descriptors of the account of the master key derived from `seed`, with key origins.
+    pub(crate) fn watch_only(seed: u8) -> WatchOnly {
+        let secp = Secp256k1::new();
+        let master = master_key(seed);
+        let path = DerivationPath::from_str(&format!("m/{ACCOUNT_PATH}")).expect("valid path");
+        let account = master.derive_priv(&secp, &path).expect("derivable path");
+        let origin = format!(
+            "[{}/{ACCOUNT_PATH}]{}",
+            master.fingerprint(&secp),
+            Xpub::from_priv(&secp, &account)
+        );
+
+        WatchOnly {
+            external_descriptor: format!("tr({origin}/0/*)"),
+            internal_descriptor: format!("tr({origin}/1/*)"),
+        }
+    }
+
+    /// Wallet holding the private keys behind [`watch_only`], like an offline signer would.
+    pub(crate) fn signer_wallet(seed: u8) -> Wallet {
+        let master = master_key(seed);
+        Wallet::create(
+            format!("tr({master}/{ACCOUNT_PATH}/0/*)"),
+            format!("tr({master}/{ACCOUNT_PATH}/1/*)"),
+        )
+        .network(Network::Signet)
+        .create_wallet_no_persist()
+        .expect("valid wallet")
+    }
+
+    /// Watch-only wallet of `seed` that has received an unconfirmed output.
+    pub(crate) fn funded_watch_only_wallet(seed: u8) -> Wallet {
+        let (_, create) = watch_only(seed).signet_wallet().split();
+        let mut wallet = create
+            .network(Network::Signet)
+            .create_wallet_no_persist()
+            .expect("valid wallet");
+
+        let address = wallet.reveal_next_address(KeychainKind::External).address;
+        let funding_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::from_byte_array([seed; 32]), 0),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+        wallet.apply_unconfirmed_txs([(funding_tx, 1)]);
+
+        wallet
+    }
+
+    /// Builds a PSBT that sends part of the funds of the wallet to an external address.
+    pub(crate) fn unsigned_psbt(wallet: &mut Wallet) -> Psbt {
+        let recipient = signer_wallet(u8::MAX)
+            .peek_address(KeychainKind::External, 0)
+            .address;
+
+        let mut builder = wallet.build_tx();
+        builder.add_recipient(recipient.script_pubkey(), Amount::from_sat(50_000));
+        builder.finish().expect("valid psbt")
+    }
+
+    #[test]
+    fn test_watch_only_emits_unsigned_psbt() {
+        let mut wallet = funded_watch_only_wallet(1);
+        let mut psbt = unsigned_psbt(&mut wallet);
+
+        let fingerprint = master_key(1).fingerprint(&Secp256k1::new());
+        for input in &psbt.inputs {
+            assert!(
+                input.tap_key_sig.is_none() && input.final_script_witness.is_none(),
+                "watch-only psbt must not be signed"
+            );
+            assert!(
+                input.witness_utxo.is_some(),
+                "offline signer needs the spent output"
+            );
+            assert!(
+                input
+                    .tap_key_origins
+                    .values()
+                    .any(|(_, (origin, _))| *origin == fingerprint),
+                "offline signer needs the key origin to find its key"
+            );
+        }
+
+        let finalized = wallet
+            .sign(&mut psbt, SignOptions::default())
+            .expect("signable psbt");
+        assert!(!finalized, "watch-only wallet must not be able to sign");
+    }
+}
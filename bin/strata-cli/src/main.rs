@@ -1,5 +1,6 @@
 pub mod cmd;
 pub mod constants;
+pub mod keys;
 mod link;
 pub mod net_type;
 pub mod recovery;
@@ -10,10 +11,11 @@ pub mod strata;
 pub mod taproot;
 
 use cmd::{
-    backup::backup, balance::balance, change_pwd::change_pwd, config::config, deposit::deposit,
-    drain::drain, faucet::faucet, receive::receive, recover::recover, reset::reset, scan::scan,
-    send::send, withdraw::withdraw, Commands, TopLevel,
+    backup::backup, balance::balance, broadcast_psbt::broadcast_psbt, change_pwd::change_pwd,
+    config::config, deposit::deposit, drain::drain, faucet::faucet, receive::receive,
+    recover::recover, reset::reset, scan::scan, send::send, withdraw::withdraw, Commands, TopLevel,
 };
+use keys::Keys;
 #[cfg(target_os = "linux")]
 use seed::FilePersister;
 #[cfg(not(target_os = "linux"))]
@@ -44,20 +46,24 @@ async fn main() {
 
     assert!(set_data_dir(settings.data_dir.clone()));
 
-    let seed = seed::load_or_create(&persister).unwrap();
+    let keys = match settings.watch_only.clone() {
+        Some(watch_only) => Keys::WatchOnly(watch_only),
+        None => Keys::Seed(seed::load_or_create(&persister).unwrap()),
+    };
 
     match cmd {
-        Commands::Recover(args) => recover(args, seed, settings).await,
-        Commands::Drain(args) => drain(args, seed, settings).await,
-        Commands::Balance(args) => balance(args, seed, settings).await,
-        Commands::Backup(args) => backup(args, seed).await,
-        Commands::Deposit(args) => deposit(args, seed, settings).await,
-        Commands::Withdraw(args) => withdraw(args, seed, settings).await,
-        Commands::Faucet(args) => faucet(args, seed, settings).await,
-        Commands::Send(args) => send(args, seed, settings).await,
-        Commands::Receive(args) => receive(args, seed, settings).await,
-        Commands::ChangePwd(args) => change_pwd(args, seed, persister).await,
-        Commands::Scan(args) => scan(args, seed, settings).await,
+        Commands::Recover(args) => recover(args, keys, settings).await,
+        Commands::Drain(args) => drain(args, keys, settings).await,
+        Commands::Balance(args) => balance(args, keys.seed_or_exit(), settings).await,
+        Commands::Backup(args) => backup(args, keys.seed_or_exit()).await,
+        Commands::Deposit(args) => deposit(args, keys, settings).await,
+        Commands::Withdraw(args) => withdraw(args, keys.seed_or_exit(), settings).await,
+        Commands::Faucet(args) => faucet(args, keys.seed_or_exit(), settings).await,
+        Commands::Send(args) => send(args, keys, settings).await,
+        Commands::Receive(args) => receive(args, keys.seed_or_exit(), settings).await,
+        Commands::ChangePwd(args) => change_pwd(args, keys.seed_or_exit(), persister).await,
+        Commands::Scan(args) => scan(args, keys.seed_or_exit(), settings).await,
+        Commands::BroadcastPsbt(args) => broadcast_psbt(args, keys, settings).await,
         _ => {}
     }
 }
@@ -19,7 +19,7 @@ use sled::IVec;
 use terrors::OneOf;
 use tokio::io::AsyncReadExt;
 
-use crate::seed::Seed;
+use crate::keys::Keys;
 
 pub struct DescriptorRecovery {
     db: sled::Db,
@@ -117,8 +117,8 @@ impl DescriptorRecovery {
         Ok(())
     }
 
-    pub async fn open(seed: &Seed, descriptor_db: &Path) -> io::Result<Self> {
-        let key = seed.descriptor_recovery_key();
+    pub async fn open(keys: &Keys, descriptor_db: &Path) -> io::Result<Self> {
+        let key = keys.descriptor_recovery_key();
         let cipher = Aes256GcmSiv::new(&key.into());
         Ok(Self {
             db: sled::open(descriptor_db)?,
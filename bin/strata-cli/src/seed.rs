@@ -18,7 +18,7 @@ use zeroize::Zeroizing;
 
 use crate::constants::{AES_NONCE_LEN, AES_TAG_LEN, PW_SALT_LEN, SEED_LEN};
 
-pub struct BaseWallet(LoadParams, CreateParams);
+pub struct BaseWallet(pub(crate) LoadParams, pub(crate) CreateParams);
 
 impl BaseWallet {
     pub fn split(self) -> (LoadParams, CreateParams) {
@@ -17,6 +17,7 @@ use terrors::OneOf;
 
 use crate::{
     constants::{BRIDGE_MUSIG2_PUBKEY, BRIDGE_STRATA_ADDRESS, DEFAULT_NETWORK},
+    keys::WatchOnly,
     signet::{backend::SignetBackend, EsploraClient},
 };
 
@@ -33,6 +34,7 @@ pub struct SettingsFromFile {
     pub blockscout_endpoint: Option<String>,
     pub bridge_pubkey: Option<Hex<[u8; 32]>>,
     pub network: Option<Network>,
+    pub watch_only: Option<WatchOnly>,
 }
 
 /// Settings struct filled with either config values or
@@ -52,6 +54,9 @@ pub struct Settings {
     pub network: Network,
     pub config_file: PathBuf,
     pub signet_backend: Arc<dyn SignetBackend>,
+    /// Set if the CLI should only use public descriptors for its signet wallet and emit unsigned
+    /// PSBTs instead of signing.
+    pub watch_only: Option<WatchOnly>,
 }
 
 pub static PROJ_DIRS: LazyLock<ProjectDirs> = LazyLock::new(|| {
@@ -68,7 +73,6 @@ impl Settings {
     pub fn load() -> Result<Self, OneOf<(io::Error, config::ConfigError)>> {
         let proj_dirs = &PROJ_DIRS;
         let config_file = CONFIG_FILE.as_path();
-        let descriptor_file = proj_dirs.data_dir().to_owned().join("descriptors");
         let linux_seed_file = proj_dirs.data_dir().to_owned().join("seed");
 
         create_dir_all(proj_dirs.config_dir()).map_err(OneOf::new)?;
@@ -103,10 +107,18 @@ impl Settings {
             _ => panic!("invalid config for signet - configure for esplora or bitcoind"),
         };
 
+        // keep the watch-only wallet apart from the seed's wallet
+        let data_dir = match from_file.watch_only {
+            Some(_) => proj_dirs.data_dir().to_owned().join("watch-only"),
+            None => proj_dirs.data_dir().to_owned(),
+        };
+        create_dir_all(&data_dir).map_err(OneOf::new)?;
+        let descriptor_file = data_dir.join("descriptors");
+
         Ok(Settings {
             esplora: from_file.esplora,
             strata_endpoint: from_file.strata_endpoint,
-            data_dir: proj_dirs.data_dir().to_owned(),
+            data_dir,
             faucet_endpoint: from_file.faucet_endpoint,
             bridge_musig2_pubkey: XOnlyPublicKey::from_slice(&match from_file.bridge_pubkey {
                 Some(key) => key.0,
@@ -126,6 +138,7 @@ impl Settings {
             network: from_file.network.unwrap_or(DEFAULT_NETWORK),
             config_file: CONFIG_FILE.clone(),
             signet_backend: sync_backend,
+            watch_only: from_file.watch_only,
         })
     }
 }
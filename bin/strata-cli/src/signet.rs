@@ -13,15 +13,19 @@ use std::{
 use backend::{ScanError, SignetBackend, SyncError, WalletUpdate};
 use bdk_esplora::esplora_client::{self, AsyncClient};
 use bdk_wallet::{
-    bitcoin::{FeeRate, Network},
+    bitcoin::{FeeRate, Network, Psbt},
     rusqlite::{self, Connection},
     PersistedWallet, Wallet,
 };
 use persist::Persister;
+use strata_primitives::l1::BitcoinPsbt;
 use terrors::OneOf;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
-use crate::seed::Seed;
+use crate::{
+    keys::Keys,
+    seed::{BaseWallet, Seed},
+};
 
 pub fn log_fee_rate(fr: &FeeRate) {
     println!(
@@ -44,6 +48,18 @@ pub async fn get_fee_rate(
     }
 }
 
+/// Prints an unsigned PSBT so it can be signed offline and later be broadcast with the
+/// `broadcast-psbt` command.
+pub fn print_unsigned_psbt(psbt: Psbt) {
+    let psbt = BitcoinPsbt::from(psbt);
+    println!(
+        "Unsigned PSBT for transaction {}:",
+        psbt.compute_txid().to_string().yellow()
+    );
+    println!("{}", psbt.inner());
+    println!("Sign it offline, then broadcast it with `strata broadcast-psbt <psbt>`");
+}
+
 #[derive(Clone, Debug)]
 pub struct EsploraClient(AsyncClient);
 
@@ -90,7 +106,25 @@ impl SignetWallet {
         network: Network,
         sync_backend: Arc<dyn SignetBackend>,
     ) -> io::Result<Self> {
-        let (load, create) = seed.signet_wallet().split();
+        Self::from_base(seed.signet_wallet(), network, sync_backend)
+    }
+
+    /// Loads the signet wallet for the given [`Keys`], which will be watch-only if there's no
+    /// seed.
+    pub fn from_keys(
+        keys: &Keys,
+        network: Network,
+        sync_backend: Arc<dyn SignetBackend>,
+    ) -> io::Result<Self> {
+        Self::from_base(keys.signet_wallet(), network, sync_backend)
+    }
+
+    fn from_base(
+        base: BaseWallet,
+        network: Network,
+        sync_backend: Arc<dyn SignetBackend>,
+    ) -> io::Result<Self> {
+        let (load, create) = base.split();
         Ok(Self {
             wallet: load
                 .check_network(network)
@@ -3,8 +3,8 @@ use std::path::PathBuf;
 use argh::FromArgs;
 use bitcoin::Network;
 use strata_config::{
-    BitcoindConfig, ClientConfig, ClientMode, Config, ExecConfig, FullNodeConfig, RelayerConfig,
-    RethELConfig, SequencerConfig, SyncConfig,
+    BitcoindConfig, ClientConfig, ClientMode, Config, ExecConfig, FullNodeConfig, L1BackendConfig,
+    MetricsConfig, RelayerConfig, RethELConfig, SequencerConfig, SyncConfig,
 };
 
 #[derive(Debug, Clone, FromArgs)]
@@ -42,6 +42,12 @@ pub struct Args {
     #[argh(option, short = 'k', description = "path to sequencer root key")]
     pub sequencer_key: Option<PathBuf>,
 
+    #[argh(
+        option,
+        description = "rotation of the sequencer key to derive from the root key"
+    )]
+    pub sequencer_key_rotation: Option<u32>,
+
     #[argh(option, description = "sequencer rpc host:port")]
     pub sequencer_rpc: Option<String>,
 
@@ -60,8 +66,34 @@ pub struct Args {
 
     #[argh(option, description = "database retry count")]
     pub db_retry_count: Option<u16>,
+
+    #[argh(option, description = "metrics host, defaults to 0.0.0.0")]
+    pub metrics_host: Option<String>,
+
+    #[argh(option, description = "metrics port, metrics are disabled if not set")]
+    pub metrics_port: Option<u16>,
+
+    #[argh(
+        option,
+        description = "esplora API url to follow L1 from instead of bitcoind"
+    )]
+    pub esplora_url: Option<String>,
+
+    #[argh(
+        option,
+        description = "bitcoind ZMQ endpoint publishing new blocks, e.g. tcp://127.0.0.1:28332"
+    )]
+    pub bitcoind_zmq_url: Option<String>,
+
+    #[argh(
+        switch,
+        description = "start a new full node from the latest finalized checkpoint"
+    )]
+    pub checkpoint_sync: bool,
 }
 
+const DEFAULT_METRICS_HOST: &str = "0.0.0.0";
+
 impl Args {
     pub fn derive_config(&self) -> Result<Config, String> {
         let args = self.clone();
@@ -74,6 +106,7 @@ impl Args {
                     "args: no bitcoin --rpc-password provided",
                 )?,
                 network: require(args.network, "args: no bitcoin --network provided")?,
+                zmq_block_url: args.bitcoind_zmq_url,
             },
             client: ClientConfig {
                 rpc_host: require(args.rpc_host, "args: no client --rpc-host provided")?,
@@ -83,10 +116,14 @@ impl Args {
                     if let Some(sequencer_key) = args.sequencer_key {
                         ClientMode::Sequencer(SequencerConfig {
                             sequencer_key,
+                            sequencer_key_rotation: args.sequencer_key_rotation,
                             sequencer_bitcoin_address: args.sequencer_bitcoin_address,
                         })
                     } else if let Some(sequencer_rpc) = args.sequencer_rpc {
-                        ClientMode::FullNode(FullNodeConfig { sequencer_rpc })
+                        ClientMode::FullNode(FullNodeConfig {
+                            sequencer_rpc,
+                            checkpoint_sync: args.checkpoint_sync,
+                        })
                     } else {
                         return Err(
                             "args: no client --sequencer-key or --sequencer-bitcion-address provided or --sequencer-rpc provided"
@@ -113,7 +150,21 @@ impl Args {
                 refresh_interval: 10,
                 stale_duration: 120,
                 relay_misc: true,
+                limits: Default::default(),
             },
+            metrics: args.metrics_port.map(|port| MetricsConfig {
+                host: args
+                    .metrics_host
+                    .unwrap_or_else(|| DEFAULT_METRICS_HOST.to_string()),
+                port,
+            }),
+            broadcaster: None,
+            writer: Default::default(),
+            l1_backend: args.esplora_url.map(|url| L1BackendConfig::Esplora { url }),
+            pruning: Default::default(),
+            p2p: None,
+            admin_rpc: None,
+            public_rpc: Default::default(),
         })
     }
 
@@ -130,6 +181,9 @@ impl Args {
         if let Some(rpc_password) = args.bitcoind_password {
             config.bitcoind_rpc.rpc_password = rpc_password;
         }
+        if let Some(zmq_url) = args.bitcoind_zmq_url {
+            config.bitcoind_rpc.zmq_block_url = Some(zmq_url);
+        }
         if let Some(rpc_host) = args.rpc_host {
             config.client.rpc_host = rpc_host;
         }
@@ -144,10 +198,16 @@ impl Args {
         if let Some(sequencer_key) = args.sequencer_key {
             config.client.client_mode = ClientMode::Sequencer(SequencerConfig {
                 sequencer_key,
+                sequencer_key_rotation: args.sequencer_key_rotation,
                 sequencer_bitcoin_address: args.sequencer_bitcoin_address,
             });
         } else if let Some(sequencer_rpc) = args.sequencer_rpc {
-            config.client.client_mode = ClientMode::FullNode(FullNodeConfig { sequencer_rpc });
+            config.client.client_mode = ClientMode::FullNode(FullNodeConfig {
+                sequencer_rpc,
+                checkpoint_sync: args.checkpoint_sync,
+            });
+        } else if let ClientMode::FullNode(fullnode_config) = &mut config.client.client_mode {
+            fullnode_config.checkpoint_sync |= args.checkpoint_sync;
         }
         if let Some(rpc_url) = args.reth_authrpc {
             config.exec.reth.rpc_url = rpc_url;
@@ -155,6 +215,18 @@ impl Args {
         if let Some(db_retry_count) = args.db_retry_count {
             config.client.db_retry_count = db_retry_count;
         }
+        if let Some(port) = args.metrics_port {
+            let host = args
+                .metrics_host
+                .or(config.metrics.take().map(|metrics| metrics.host))
+                .unwrap_or_else(|| DEFAULT_METRICS_HOST.to_string());
+            config.metrics = Some(MetricsConfig { host, port });
+        } else if let (Some(host), Some(metrics)) = (args.metrics_host, config.metrics.as_mut()) {
+            metrics.host = host;
+        }
+        if let Some(url) = args.esplora_url {
+            config.l1_backend = Some(L1BackendConfig::Esplora { url });
+        }
     }
 }
 
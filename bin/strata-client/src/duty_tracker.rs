@@ -0,0 +1,184 @@
+//! Tracks the txids of the transactions that the bridge operators are expected to sign, so that the
+//! relayer can hold back nonces and signatures for anything else until it's known.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use bitcoin::{key::Parity, secp256k1::XOnlyPublicKey, Network, Txid};
+use strata_bridge_relay::duties::PendingDutyTxids;
+use strata_bridge_tx_builder::{
+    prelude::{DepositInfo, TxBuildContext},
+    TxKind,
+};
+use strata_db::traits::Database;
+use strata_primitives::bridge::PublickeyTable;
+use strata_state::{
+    bridge_state::{OperatorEntry, OperatorTable},
+    chain_state::Chainstate,
+    id::L2BlockId,
+};
+use strata_status::StatusChannel;
+use tokio::sync::Mutex;
+use tracing::*;
+
+use crate::extractor::{extract_deposit_requests, extract_reimbursement_infos};
+
+/// Builds the [`PublickeyTable`] of the operators' wallet pubkeys.
+pub(crate) fn operator_pubkey_table(operator_table: &OperatorTable) -> PublickeyTable {
+    pubkey_table_of(operator_table.operators())
+}
+
+/// Builds the [`PublickeyTable`] of the wallet pubkeys of the removed operators that may still be
+/// notaries of deposits.
+pub(crate) fn retired_operator_pubkey_table(operator_table: &OperatorTable) -> PublickeyTable {
+    pubkey_table_of(operator_table.retired_operators())
+}
+
+fn pubkey_table_of(entries: &[OperatorEntry]) -> PublickeyTable {
+    entries
+        .iter()
+        .map(|entry| {
+            let pubkey = XOnlyPublicKey::try_from(*entry.wallet_pk())
+                .expect("something has gone horribly wrong");
+
+            // This is a taproot pubkey so its parity has to be even.
+            (entry.idx(), pubkey.public_key(Parity::Even))
+        })
+        .collect::<BTreeMap<_, _>>()
+        .into()
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    /// The L1 index to continue extracting the deposit requests from.
+    next_l1_idx: u64,
+
+    /// The deposit requests extracted so far.
+    deposit_infos: Vec<DepositInfo>,
+
+    /// The operator pubkeys the txids were computed with.
+    pubkey_table: Option<PublickeyTable>,
+
+    /// The txids of the deposit duties.
+    deposit_txids: HashSet<Txid>,
+
+    /// The txids of the reimbursement duties as of the last refresh.
+    reimbursement_txids: HashSet<Txid>,
+
+    /// The chain tip of the chainstate the txids were last refreshed at.
+    refreshed_tip: Option<L2BlockId>,
+}
+
+impl TrackerState {
+    fn contains(&self, txid: &Txid) -> bool {
+        self.deposit_txids.contains(txid) || self.reimbursement_txids.contains(txid)
+    }
+}
+
+/// Computes the txids of the deposit and reimbursement duties the same way the bridge clients do,
+/// refreshing them when asked about a txid it doesn't know yet.
+///
+/// The duties only change with the chainstate, so they're refreshed at most once per chainstate
+/// however many unknown txids it's asked about.
+pub(crate) struct DutyTxidTracker<D> {
+    database: Arc<D>,
+    status_channel: StatusChannel,
+    network: Network,
+    state: Mutex<TrackerState>,
+}
+
+impl<D: Database + Sync + Send + 'static> DutyTxidTracker<D> {
+    pub(crate) fn new(database: Arc<D>, status_channel: StatusChannel, network: Network) -> Self {
+        Self {
+            database,
+            status_channel,
+            network,
+            state: Mutex::new(TrackerState::default()),
+        }
+    }
+
+    /// Returns the chain tip of the current chainstate, if there's one.
+    fn chain_tip(&self) -> Option<L2BlockId> {
+        self.status_channel
+            .subscribe_chain_state()
+            .borrow()
+            .as_ref()
+            .map(Chainstate::chain_tip_blockid)
+    }
+
+    async fn refresh(&self, state: &mut TrackerState) -> anyhow::Result<()> {
+        // Without a chainstate nothing is known yet, and the relayer retries later.
+        let (Some(operator_table), Some(deposits_table)) = (
+            self.status_channel.operator_table(),
+            self.status_channel.deposits_table(),
+        ) else {
+            return Ok(());
+        };
+
+        let (deposit_infos, next_l1_idx) =
+            extract_deposit_requests(self.database.l1_db(), state.next_l1_idx, self.network)
+                .await?;
+        state.next_l1_idx = next_l1_idx;
+
+        // The own index does not affect the txids, so any index works here.
+        let pubkey_table = operator_pubkey_table(&operator_table);
+        let build_context = TxBuildContext::new(self.network, pubkey_table.clone(), 0)
+            .with_retired_pubkeys(retired_operator_pubkey_table(&operator_table));
+
+        // The deposit txids change with the operator set, so recompute them all when it does.
+        let new_start = if state.pubkey_table.as_ref() == Some(&pubkey_table) {
+            state.deposit_infos.len()
+        } else {
+            state.deposit_txids.clear();
+            state.pubkey_table = Some(pubkey_table);
+            0
+        };
+        state.deposit_infos.extend(deposit_infos);
+
+        let deposit_txids: Vec<_> = state.deposit_infos[new_start..]
+            .iter()
+            .filter_map(|info| txid_of(info, &build_context))
+            .collect();
+        state.deposit_txids.extend(deposit_txids);
+
+        // Reimbursements stop being pending once executed, so these are replaced every time.
+        state.reimbursement_txids = extract_reimbursement_infos(&deposits_table)
+            .filter_map(|info| txid_of(&info, &build_context))
+            .collect();
+
+        Ok(())
+    }
+}
+
+fn txid_of(info: &impl TxKind, build_context: &TxBuildContext) -> Option<Txid> {
+    match info.construct_signing_data(build_context) {
+        Ok(signing_data) => Some(signing_data.psbt.compute_txid()),
+        Err(err) => {
+            debug!(%err, "could not build duty tx, skipping");
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl<D: Database + Sync + Send + 'static> PendingDutyTxids for DutyTxidTracker<D> {
+    async fn is_pending_duty_txid(&self, txid: &Txid) -> anyhow::Result<bool> {
+        let mut state = self.state.lock().await;
+        if state.contains(txid) {
+            return Ok(true);
+        }
+
+        // Nothing can have changed since the last refresh if the chainstate hasn't.
+        let chain_tip = self.chain_tip();
+        if chain_tip.is_none() || chain_tip == state.refreshed_tip {
+            return Ok(false);
+        }
+
+        self.refresh(&mut state).await?;
+        state.refreshed_tip = chain_tip;
+        Ok(state.contains(txid))
+    }
+}
@@ -14,7 +14,9 @@ use bitcoin::{
     TapNodeHash, Transaction,
 };
 use jsonrpsee::core::RpcResult;
-use strata_bridge_tx_builder::prelude::{CooperativeWithdrawalInfo, DepositInfo};
+use strata_bridge_tx_builder::prelude::{
+    CooperativeWithdrawalInfo, DepositInfo, ReimbursementInfo,
+};
 use strata_db::traits::L1Database;
 use strata_primitives::l1::BitcoinAddress;
 use strata_rpc_types::RpcServerError;
@@ -152,18 +154,15 @@ pub(super) fn extract_withdrawal_infos(
     let withdrawal_infos = deposits.filter_map(|deposit| {
         if let DepositState::Dispatched(dispatched_state) = deposit.deposit_state() {
             let deposit_outpoint = deposit.output().outpoint();
-            let user_pk = dispatched_state
-                .cmd()
-                .withdraw_outputs()
-                .first()
-                .expect("there should be a withdraw output in a dispatched deposit")
-                .dest_addr();
+            let withdraw_outputs = dispatched_state.cmd().withdraw_outputs().to_vec();
             let assigned_operator_idx = dispatched_state.assignee();
             let exec_deadline = dispatched_state.exec_deadline();
 
             let withdrawal_info = CooperativeWithdrawalInfo::new(
                 *deposit_outpoint,
-                *user_pk,
+                deposit.amt().into(),
+                deposit.notary_operators().to_vec(),
+                withdraw_outputs,
                 assigned_operator_idx,
                 exec_deadline,
             );
@@ -177,6 +176,29 @@ pub(super) fn extract_withdrawal_infos(
     withdrawal_infos
 }
 
+/// Extract the reimbursement duties from the chain state.
+///
+/// These are the deposits whose withdrawal has been paid out by the assigned operator from its own
+/// wallet, and which are to be swept to that operator by all the operators together.
+pub(super) fn extract_reimbursement_infos(
+    deposits_table: &DepositsTable,
+) -> impl Iterator<Item = ReimbursementInfo> + '_ {
+    deposits_table.deposits().filter_map(|deposit| {
+        let DepositState::Fulfilled(fulfilled_state) = deposit.deposit_state() else {
+            return None;
+        };
+
+        Some(ReimbursementInfo::new(
+            *deposit.output().outpoint(),
+            deposit.amt().into(),
+            deposit.notary_operators().to_vec(),
+            fulfilled_state.withdrawal_total().into(),
+            fulfilled_state.assignee(),
+            fulfilled_state.fulfillment_txid().inner(),
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Not;
@@ -199,20 +221,20 @@ mod tests {
     use strata_primitives::{
         bridge::OperatorIdx,
         buf::Buf32,
-        l1::{BitcoinAmount, L1BlockManifest, OutputRef, XOnlyPk},
+        l1::{BitcoinAmount, BitcoinTxid, L1BlockManifest, OutputRef, XOnlyPk},
     };
     use strata_rocksdb::{test_utils::get_rocksdb_tmp_instance, L1Db};
     use strata_state::{
         bridge_state::{
-            DepositEntry, DepositsTable, DispatchCommand, DispatchedState, OperatorTable,
-            WithdrawOutput,
+            DepositEntry, DepositsTable, DispatchCommand, DispatchedState, FulfilledState,
+            OperatorTable, WithdrawOutput,
         },
         chain_state::Chainstate,
         exec_env::ExecEnvState,
         exec_update::UpdateInput,
         genesis::GenesisStateData,
         id::L2BlockId,
-        l1::{L1BlockId, L1HeaderRecord, L1Tx, L1TxProof, L1ViewState},
+        l1::{HeaderVerificationState, L1BlockId, L1HeaderRecord, L1Tx, L1TxProof, L1ViewState},
         tx::DepositRequestInfo,
     };
     use strata_test_utils::{bridge::generate_mock_unsigned_tx, ArbitraryGenerator};
@@ -302,16 +324,11 @@ mod tests {
 
         let deposit_state = needle.deposit_state();
         if let DepositState::Dispatched(dispatched_state) = deposit_state {
-            let withdraw_output = dispatched_state
-                .cmd()
-                .withdraw_outputs()
-                .first()
-                .expect("should have at least one `WithdrawOutput");
-            let user_pk = withdraw_output.dest_addr();
-
             let expected_info = CooperativeWithdrawalInfo::new(
                 *needle.output().outpoint(),
-                *user_pk,
+                needle.amt().into(),
+                needle.notary_operators().to_vec(),
+                dispatched_state.cmd().withdraw_outputs().to_vec(),
                 dispatched_state.assignee(),
                 dispatched_state.exec_deadline(),
             );
@@ -327,6 +344,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_extract_reimbursement_infos() {
+        let num_deposits = 10;
+        let (chain_state, _, needle) = generate_empty_chain_state_with_deposits(num_deposits);
+        let mut deposits_table = chain_state.deposits_table().clone();
+
+        let DepositState::Dispatched(dispatched_state) = needle.deposit_state() else {
+            unreachable!("needle must be in dispatched state");
+        };
+        let fulfillment_txid: BitcoinTxid = ArbitraryGenerator::new().generate();
+        let fulfilled_state = FulfilledState::new(
+            dispatched_state.assignee(),
+            fulfillment_txid.clone(),
+            dispatched_state.cmd().get_total_value(),
+        );
+        deposits_table
+            .get_deposit_mut(needle.idx())
+            .unwrap()
+            .set_state(DepositState::Fulfilled(fulfilled_state));
+
+        let reimbursement_infos = extract_reimbursement_infos(&deposits_table).collect::<Vec<_>>();
+
+        let expected_info = ReimbursementInfo::new(
+            *needle.output().outpoint(),
+            needle.amt().into(),
+            needle.notary_operators().to_vec(),
+            dispatched_state.cmd().get_total_value().into(),
+            dispatched_state.assignee(),
+            fulfillment_txid.inner(),
+        );
+        assert_eq!(
+            reimbursement_infos,
+            vec![expected_info],
+            "only the fulfilled deposit should be reimbursed"
+        );
+    }
+
     /// Populates the db with block data.
     ///
     /// This data includes the `needle` at some random block within the provided range.
@@ -553,7 +607,8 @@ mod tests {
     ) -> (Chainstate, usize, DepositEntry) {
         let l1_block_id = L1BlockId::from(Buf32::zero());
         let safe_block = L1HeaderRecord::new(l1_block_id, vec![], Buf32::zero());
-        let l1_state = L1ViewState::new_at_horizon(0, safe_block);
+        let l1_state =
+            L1ViewState::new_at_horizon(0, safe_block, HeaderVerificationState::default());
 
         let operator_table = OperatorTable::new_empty();
 
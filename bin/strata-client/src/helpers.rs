@@ -121,8 +121,9 @@ pub fn create_bitcoin_rpc_client(config: &Config) -> anyhow::Result<Arc<BitcoinC
     Ok(btc_rpc.into())
 }
 
-/// Loads sequencer identity data from the root key at the specified path.
-pub fn load_seqkey(path: &Path) -> anyhow::Result<IdentityData> {
+/// Loads sequencer identity data for the given key rotation from the root key at the specified
+/// path.
+pub fn load_seqkey(path: &Path, rotation: u32) -> anyhow::Result<IdentityData> {
     let raw_buf = fs::read(path)?;
     let str_buf = std::str::from_utf8(&raw_buf)?;
     debug!(?path, "loading sequencer root key");
@@ -130,7 +131,7 @@ pub fn load_seqkey(path: &Path) -> anyhow::Result<IdentityData> {
     let master_xpriv = ZeroizableXpriv::new(Xpriv::decode(&buf)?);
 
     // Actually do the key derivation from the root key and then derive the pubkey from that.
-    let seq_keys = SequencerKeys::new(&master_xpriv)?;
+    let seq_keys = SequencerKeys::new_rotated(&master_xpriv, rotation)?;
     let seq_xpriv = seq_keys.derived_xpriv();
     let mut seq_sk = Buf32::from(seq_xpriv.private_key.secret_bytes());
     let seq_xpub = seq_keys.derived_xpub();
@@ -143,7 +144,7 @@ pub fn load_seqkey(path: &Path) -> anyhow::Result<IdentityData> {
     seq_sk.zeroize();
 
     // Changed this to the pubkey so that we don't just log our privkey.
-    debug!(?ident, "ready to sign as sequencer");
+    debug!(?ident, %rotation, "ready to sign as sequencer");
 
     let idata = IdentityData::new(ident, ik);
     Ok(idata)
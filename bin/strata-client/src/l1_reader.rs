@@ -1,10 +1,15 @@
 use std::sync::Arc;
 
+use async_trait::async_trait;
+use bitcoin::{Block, BlockHash, Network, Txid};
 use strata_btcio::{
-    reader::{config::ReaderConfig, query::bitcoin_data_reader_task},
-    rpc::traits::Reader,
+    reader::{config::ReaderConfig, notify::block_notifications, query::bitcoin_data_reader_task},
+    rpc::{
+        esplora::EsploraClient, traits::Reader, types::GetBlockchainInfo, BitcoinClient,
+        ClientResult,
+    },
 };
-use strata_config::Config;
+use strata_config::{Config, L1BackendConfig};
 use strata_consensus_logic::{csm::ctl::CsmController, l1_handler::bitcoin_data_handler_task};
 use strata_db::traits::{Database, L1Database};
 use strata_primitives::params::Params;
@@ -38,6 +43,17 @@ where
         params.clone(),
     ));
 
+    // Block notifications only make sense when following L1 through bitcoind
+    let follows_bitcoind = !matches!(config.l1_backend, Some(L1BackendConfig::Esplora { .. }));
+    let notifications = match &config.bitcoind_rpc.zmq_block_url {
+        Some(url) if follows_bitcoind => {
+            let (notifications, listener) = block_notifications(url.clone());
+            executor.spawn_critical_async("l1_block_notification_listener", listener);
+            Some(notifications)
+        }
+        _ => None,
+    };
+
     executor.spawn_critical_async(
         "bitcoin_data_reader_task",
         bitcoin_data_reader_task(
@@ -46,6 +62,7 @@ where
             target_next_block,
             reader_config,
             status_channel,
+            notifications,
         ),
     );
 
@@ -57,3 +74,86 @@ where
     });
     Ok(())
 }
+
+/// The client the L1 reader follows L1 through, as selected by the `[l1_backend]` config.
+#[derive(Debug)]
+pub enum L1ReaderClient {
+    Bitcoind(Arc<BitcoinClient>),
+    Esplora(EsploraClient),
+}
+
+impl L1ReaderClient {
+    /// Creates the client for the configured backend, reusing the `bitcoind` client if that's the
+    /// one to follow L1 through.
+    pub fn from_config(
+        config: &Config,
+        bitcoin_client: Arc<BitcoinClient>,
+    ) -> anyhow::Result<Self> {
+        match &config.l1_backend {
+            None | Some(L1BackendConfig::Bitcoind) => Ok(Self::Bitcoind(bitcoin_client)),
+            Some(L1BackendConfig::Esplora { url }) => {
+                let client = EsploraClient::new(url, config.bitcoind_rpc.network)?;
+                Ok(Self::Esplora(client))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Reader for L1ReaderClient {
+    async fn estimate_smart_fee(&self, conf_target: u16) -> ClientResult<u64> {
+        match self {
+            Self::Bitcoind(client) => client.estimate_smart_fee(conf_target).await,
+            Self::Esplora(client) => client.estimate_smart_fee(conf_target).await,
+        }
+    }
+
+    async fn get_block(&self, hash: &BlockHash) -> ClientResult<Block> {
+        match self {
+            Self::Bitcoind(client) => client.get_block(hash).await,
+            Self::Esplora(client) => client.get_block(hash).await,
+        }
+    }
+
+    async fn get_block_at(&self, height: u64) -> ClientResult<Block> {
+        match self {
+            Self::Bitcoind(client) => client.get_block_at(height).await,
+            Self::Esplora(client) => client.get_block_at(height).await,
+        }
+    }
+
+    async fn get_block_count(&self) -> ClientResult<u64> {
+        match self {
+            Self::Bitcoind(client) => client.get_block_count().await,
+            Self::Esplora(client) => client.get_block_count().await,
+        }
+    }
+
+    async fn get_block_hash(&self, height: u64) -> ClientResult<BlockHash> {
+        match self {
+            Self::Bitcoind(client) => client.get_block_hash(height).await,
+            Self::Esplora(client) => client.get_block_hash(height).await,
+        }
+    }
+
+    async fn get_blockchain_info(&self) -> ClientResult<GetBlockchainInfo> {
+        match self {
+            Self::Bitcoind(client) => client.get_blockchain_info().await,
+            Self::Esplora(client) => client.get_blockchain_info().await,
+        }
+    }
+
+    async fn get_raw_mempool(&self) -> ClientResult<Vec<Txid>> {
+        match self {
+            Self::Bitcoind(client) => client.get_raw_mempool().await,
+            Self::Esplora(client) => client.get_raw_mempool().await,
+        }
+    }
+
+    async fn network(&self) -> ClientResult<Network> {
+        match self {
+            Self::Bitcoind(client) => client.network().await,
+            Self::Esplora(client) => client.network().await,
+        }
+    }
+}
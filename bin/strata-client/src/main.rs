@@ -1,7 +1,14 @@
 use std::{str::FromStr, sync::Arc, time::Duration};
 
 use bitcoin::{hashes::Hash, Address, BlockHash};
-use jsonrpsee::Methods;
+use jsonrpsee::{
+    server::{
+        middleware::rpc::RpcServiceBuilder, serve_with_graceful_shutdown, stop_channel,
+        ConnectionGuard, ServerHandle,
+    },
+    Methods,
+};
+use reth_rpc_layer::{AuthLayer, JwtAuthValidator};
 use rpc_client::sync_client;
 use strata_bridge_relay::relayer::RelayerHandle;
 use strata_btcio::{
@@ -9,8 +16,10 @@ use strata_btcio::{
     rpc::{traits::Reader, BitcoinClient},
     writer::{config::WriterConfig, start_inscription_task},
 };
-use strata_common::logging;
-use strata_config::{ClientMode, Config, SequencerConfig};
+use strata_common::{logging, metrics};
+use strata_config::{
+    AdminRpcConfig, BroadcasterConfig, ClientMode, Config, P2pConfig, SequencerConfig,
+};
 use strata_consensus_logic::{
     checkpoint::CheckpointHandle,
     duty::{types::DutyBatch, worker as duty_worker},
@@ -22,7 +31,8 @@ use strata_db::{
     DbError,
 };
 use strata_eectl::engine::ExecEngineCtl;
-use strata_evmexec::{engine::RpcExecEngineCtl, EngineRpcClient};
+use strata_evmexec::{engine::RpcExecEngineCtl, validate_block_accessory, EngineRpcClient};
+use strata_p2p::{P2pHandle, P2pSyncClient};
 use strata_primitives::params::Params;
 use strata_rocksdb::{
     broadcaster::db::BroadcastDb, init_broadcaster_database, init_core_dbs,
@@ -34,31 +44,46 @@ use strata_rpc_api::{
 };
 use strata_status::StatusChannel;
 use strata_storage::{
-    create_node_storage, ops::bridge_relay::BridgeMsgOps, L2BlockManager, NodeStorage,
+    create_node_storage, ops::bridge_relay::BridgeMsgOps, L2BlockManager, NodeStorage, PruneHorizon,
 };
-use strata_sync::{self, L2SyncContext, RpcSyncPeer};
+use strata_sync::{self, FallbackSyncPeer, L2SyncContext, RpcSyncPeer, SyncClient};
 use strata_tasks::{ShutdownSignal, TaskExecutor, TaskManager};
 use tokio::{
+    net::TcpListener,
     runtime::Handle,
     sync::{broadcast, oneshot},
 };
 use tracing::*;
 
-use crate::{args::Args, helpers::*};
+use crate::{
+    args::Args,
+    duty_tracker::DutyTxidTracker,
+    helpers::*,
+    l1_reader::L1ReaderClient,
+    rpc_middleware::{
+        is_admin_method, is_loopback_host, public_method_predicate, ClientRateLimiters,
+        MethodFilter, MethodPredicate, RateLimit,
+    },
+};
 
 mod args;
+mod duty_tracker;
 mod errors;
 mod extractor;
 mod helpers;
 mod l1_reader;
 mod network;
 mod rpc_client;
+mod rpc_middleware;
 mod rpc_server;
 
 // TODO: this might need to come from config.
 const BITCOIN_POLL_INTERVAL: u64 = 200; // millis
 const SEQ_ADDR_GENERATION_TIMEOUT: u64 = 10; // seconds
 
+/// Maximum number of open connections to the public RPC listener, like jsonrpsee's default.
+const MAX_PUBLIC_RPC_CONNECTIONS: usize = 100;
+
 fn main() -> anyhow::Result<()> {
     let args: Args = argh::from_env();
     if let Err(e) = main_inner(args) {
@@ -90,6 +115,14 @@ fn main_inner(args: Args) -> anyhow::Result<()> {
 
     init_logging(executor.handle());
 
+    // Install the metrics recorder before any of the tasks start recording.
+    if let Some(metrics_config) = &config.metrics {
+        let metrics_server = metrics::init(metrics_config)?;
+        executor.spawn_critical_async("metrics-server", async move {
+            metrics_server.await.map_err(Into::into)
+        });
+    }
+
     // Init thread pool for batch jobs.
     // TODO switch to num_cpus
     let pool = threadpool::ThreadPool::with_name("strata-pool".to_owned(), 8);
@@ -110,11 +143,35 @@ fn main_inner(args: Args) -> anyhow::Result<()> {
 
     let checkpoint_handle: Arc<_> = CheckpointHandle::new(manager.checkpoint().clone()).into();
     let bitcoin_client = create_bitcoin_rpc_client(&config)?;
+    let l1_client = Arc::new(L1ReaderClient::from_config(
+        &config,
+        bitcoin_client.clone(),
+    )?);
 
-    // Check if we have to do genesis.
+    // Check if we have to do genesis, unless we can start from a checkpoint instead.
     if genesis::check_needs_client_init(database.as_ref())? {
-        info!("need to init client state!");
-        genesis::init_client_state(&params, database.as_ref())?;
+        let bootstrapped = match &config.client.client_mode {
+            ClientMode::FullNode(fullnode_config) if fullnode_config.checkpoint_sync => {
+                info!("trying to bootstrap client state from checkpoint");
+                let rpc_client = runtime.block_on(sync_client(&fullnode_config.sequencer_rpc));
+                let sync_peer = RpcSyncPeer::new(rpc_client, 10);
+                runtime
+                    .block_on(strata_sync::bootstrap_from_checkpoint(
+                        &sync_peer,
+                        l1_client.as_ref(),
+                        database.as_ref(),
+                        manager.l2(),
+                        &params,
+                    ))?
+                    .is_some()
+            }
+            _ => false,
+        };
+
+        if !bootstrapped {
+            info!("need to init client state!");
+            genesis::init_client_state(&params, database.as_ref())?;
+        }
     }
 
     info!("init finished, starting main tasks");
@@ -128,8 +185,19 @@ fn main_inner(args: Args) -> anyhow::Result<()> {
         &manager,
         bridge_msg_ops,
         bitcoin_client,
+        l1_client,
     )?;
 
+    let p2p_handle = match &config.p2p {
+        Some(p2p_config) => Some(start_p2p_tasks(
+            &ctx,
+            &executor,
+            runtime.handle(),
+            p2p_config.clone(),
+        )?),
+        None => None,
+    };
+
     let mut methods = jsonrpsee::Methods::new();
 
     match &config.client.client_mode {
@@ -142,6 +210,7 @@ fn main_inner(args: Args) -> anyhow::Result<()> {
                 &executor,
                 ctx.bitcoin_client.clone(),
                 params.clone(),
+                config.broadcaster.clone(),
             );
             let seq_db = init_sequencer_database(rbdb.clone(), ops_config);
 
@@ -161,22 +230,19 @@ fn main_inner(args: Args) -> anyhow::Result<()> {
             info!(?sequencer_rpc, "initing fullnode task");
 
             let rpc_client = runtime.block_on(sync_client(sequencer_rpc));
-            let sync_peer = RpcSyncPeer::new(rpc_client, 10);
-            let l2_sync_context = L2SyncContext::new(
-                sync_peer,
-                ctx.l2_block_manager.clone(),
-                ctx.sync_manager.clone(),
-            );
-            // NOTE: this might block for some time during first run with empty db until genesis
-            // block is generated
-            let mut l2_sync_state =
-                strata_sync::block_until_csm_ready_and_init_sync_state(&l2_sync_context)?;
-
-            executor.spawn_critical_async("l2-sync-manager", async move {
-                strata_sync::sync_worker(&mut l2_sync_state, &l2_sync_context)
-                    .await
-                    .map_err(Into::into)
-            });
+            let rpc_peer = RpcSyncPeer::new(rpc_client, 10);
+            match (&p2p_handle, &config.p2p) {
+                (Some(p2p_handle), Some(p2p_config)) => {
+                    let p2p_peer = P2pSyncClient::new(p2p_handle.clone(), 10);
+                    if p2p_config.sync_from_sequencer {
+                        let sync_peer = FallbackSyncPeer::new(p2p_peer, rpc_peer);
+                        start_l2_sync_task(&ctx, &executor, sync_peer)?;
+                    } else {
+                        start_l2_sync_task(&ctx, &executor, p2p_peer)?;
+                    }
+                }
+                _ => start_l2_sync_task(&ctx, &executor, rpc_peer)?,
+            }
         }
     }
 
@@ -198,6 +264,55 @@ fn main_inner(args: Args) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Starts the P2P node along with the task that announces our chain tip to the peers.
+fn start_p2p_tasks(
+    ctx: &CoreContext,
+    executor: &TaskExecutor,
+    handle: &Handle,
+    p2p_config: P2pConfig,
+) -> anyhow::Result<P2pHandle> {
+    let (p2p_handle, p2p_task) = handle.block_on(strata_p2p::start_p2p(
+        p2p_config,
+        ctx.params.clone(),
+        ctx.l2_block_manager.clone(),
+        validate_block_accessory,
+    ))?;
+    executor.spawn_critical_async("p2p", p2p_task);
+    executor.spawn_critical_async(
+        "p2p-announcer",
+        strata_p2p::announce_tip_task(
+            p2p_handle.clone(),
+            ctx.status_channel.clone(),
+            ctx.l2_block_manager.clone(),
+        ),
+    );
+    Ok(p2p_handle)
+}
+
+/// Starts syncing L2 blocks from `sync_peer`, as full nodes do.
+fn start_l2_sync_task<T: SyncClient + Send + Sync + 'static>(
+    ctx: &CoreContext,
+    executor: &TaskExecutor,
+    sync_peer: T,
+) -> anyhow::Result<()> {
+    let l2_sync_context = L2SyncContext::new(
+        sync_peer,
+        ctx.l2_block_manager.clone(),
+        ctx.sync_manager.clone(),
+    );
+    // NOTE: this might block for some time during first run with empty db until genesis
+    // block is generated
+    let mut l2_sync_state =
+        strata_sync::block_until_csm_ready_and_init_sync_state(&l2_sync_context)?;
+
+    executor.spawn_critical_async("l2-sync-manager", async move {
+        strata_sync::sync_worker(&mut l2_sync_state, &l2_sync_context)
+            .await
+            .map_err(Into::into)
+    });
+    Ok(())
+}
+
 /// Sets up the logging system given a handle to a runtime context to possibly
 /// start the OTLP output on.
 fn init_logging(rt: &Handle) {
@@ -232,6 +347,7 @@ pub struct CoreContext {
     pub engine: Arc<RpcExecEngineCtl<EngineRpcClient>>,
     pub relayer_handle: Arc<RelayerHandle>,
     pub bitcoin_client: Arc<BitcoinClient>,
+    pub prune_horizon: Arc<PruneHorizon>,
 }
 
 fn do_startup_checks(
@@ -303,6 +419,7 @@ fn start_core_tasks(
     storage: &NodeStorage,
     bridge_msg_ops: Arc<BridgeMsgOps>,
     bitcoin_client: Arc<BitcoinClient>,
+    l1_client: Arc<L1ReaderClient>,
 ) -> anyhow::Result<CoreContext> {
     // init status tasks
     let status_channel = init_status_channel(database.as_ref())?;
@@ -319,7 +436,7 @@ fn start_core_tasks(
     do_startup_checks(
         database.as_ref(),
         engine.as_ref(),
-        bitcoin_client.as_ref(),
+        l1_client.as_ref(),
         executor.handle(),
     )?;
 
@@ -340,20 +457,42 @@ fn start_core_tasks(
         executor,
         sync_manager.get_params(),
         config,
-        bitcoin_client.clone(),
+        l1_client,
         database.clone(),
         sync_manager.get_csm_ctl(),
         status_channel.clone(),
     )?;
 
     // Start relayer task.
+    let duty_txids = Arc::new(DutyTxidTracker::new(
+        database.clone(),
+        status_channel.clone(),
+        params.rollup().network,
+    ));
     let relayer_handle = strata_bridge_relay::relayer::start_bridge_relayer_task(
         bridge_msg_ops,
         status_channel.clone(),
+        duty_txids,
         config.relayer,
         executor,
     );
 
+    // Start pruning the data outside of the retention window.
+    let prune_horizon = Arc::new(PruneHorizon::from_db(database.as_ref())?);
+    let pruning_db = database.clone();
+    let pruning_l2_block_manager = storage.l2().clone();
+    let pruning_config = config.pruning;
+    let pruning_horizon = prune_horizon.clone();
+    executor.spawn_critical("pruning_task", move |shutdown| {
+        strata_storage::pruning_task(
+            shutdown,
+            pruning_db,
+            pruning_l2_block_manager,
+            pruning_config,
+            pruning_horizon,
+        )
+    });
+
     Ok(CoreContext {
         database,
         pool,
@@ -364,6 +503,7 @@ fn start_core_tasks(
         engine,
         relayer_handle,
         bitcoin_client,
+        prune_horizon,
     })
 }
 
@@ -391,7 +531,10 @@ fn start_sequencer_tasks(
     } = ctx;
 
     info!(seqkey_path = ?sequencer_config.sequencer_key, "initing sequencer duties task");
-    let idata = load_seqkey(&sequencer_config.sequencer_key)?;
+    let idata = load_seqkey(
+        &sequencer_config.sequencer_key,
+        sequencer_config.sequencer_key_rotation.unwrap_or_default(),
+    )?;
 
     // Set up channel and clone some things.
     let (duties_tx, duties_rx) = broadcast::channel::<DutyBatch>(8);
@@ -412,6 +555,7 @@ fn start_sequencer_tasks(
     let writer_config = WriterConfig::new(
         sequencer_bitcoin_address,
         params.rollup().rollup_name.clone(),
+        &config.writer,
     )?;
 
     // Start inscription tasks
@@ -477,6 +621,7 @@ fn start_broadcaster_tasks(
     executor: &TaskExecutor,
     bitcoin_client: Arc<BitcoinClient>,
     params: Arc<Params>,
+    broadcaster_config: Option<BroadcasterConfig>,
 ) -> Arc<L1BroadcastHandle> {
     // Set up L1 broadcaster.
     let broadcast_ctx = strata_storage::ops::l1tx_broadcast::Context::new(
@@ -484,8 +629,13 @@ fn start_broadcaster_tasks(
     );
     let broadcast_ops = Arc::new(broadcast_ctx.into_ops(pool));
     // start broadcast task
-    let broadcast_handle =
-        spawn_broadcaster_task(executor, bitcoin_client.clone(), broadcast_ops, params);
+    let broadcast_handle = spawn_broadcaster_task(
+        executor,
+        bitcoin_client.clone(),
+        broadcast_ops,
+        params,
+        broadcaster_config,
+    );
     Arc::new(broadcast_handle)
 }
 
@@ -502,6 +652,7 @@ async fn start_rpc(
         l2_block_manager,
         status_channel,
         relayer_handle,
+        prune_horizon,
         ..
     } = ctx;
 
@@ -515,24 +666,37 @@ async fn start_rpc(
         l2_block_manager.clone(),
         checkpoint_handle,
         relayer_handle,
+        prune_horizon.clone(),
     );
     methods.merge(strata_rpc.into_rpc())?;
 
     let admin_rpc = rpc_server::AdminServerImpl::new(stop_tx);
     methods.merge(admin_rpc.into_rpc())?;
 
-    let debug_rpc = rpc_server::StrataDebugRpcImpl::new(l2_block_manager, database);
+    let debug_rpc = rpc_server::StrataDebugRpcImpl::new(l2_block_manager, database, prune_horizon);
     methods.merge(debug_rpc.into_rpc())?;
 
     let rpc_host = config.client.rpc_host;
     let rpc_port = config.client.rpc_port;
 
-    let rpc_server = jsonrpsee::server::ServerBuilder::new()
-        .build(format!("{rpc_host}:{rpc_port}"))
+    let listener = TcpListener::bind(format!("{rpc_host}:{rpc_port}"))
         .await
         .expect("init: build rpc server");
 
-    let rpc_handle = rpc_server.start(methods);
+    let admin_rpc_handle = match &config.admin_rpc {
+        Some(admin_config) => Some(start_admin_rpc(admin_config, methods.clone()).await?),
+        None => {
+            warn!("serving admin RPC methods on the public listener");
+            None
+        }
+    };
+
+    let is_allowed = public_method_predicate(&config.public_rpc, config.admin_rpc.is_none());
+    let limiters = config
+        .public_rpc
+        .max_requests_per_sec
+        .map(|per_sec| Arc::new(ClientRateLimiters::new(per_sec)));
+    let rpc_handle = start_public_rpc(listener, methods, is_allowed, limiters);
 
     // start a Btcio event handler
     info!("started RPC server");
@@ -551,5 +715,115 @@ async fn start_rpc(
     // wait for rpc to stop
     rpc_handle.stopped().await;
 
+    if let Some(admin_rpc_handle) = admin_rpc_handle {
+        if admin_rpc_handle.stop().is_err() {
+            warn!("admin RPC server already stopped");
+        }
+        admin_rpc_handle.stopped().await;
+    }
+
     Ok(())
 }
+
+/// Starts serving the allowed methods on the public listener.
+///
+/// The connections are accepted here rather than by the server, so that the requests of each
+/// client are rate limited by its IP address.
+fn start_public_rpc(
+    listener: TcpListener,
+    methods: Methods,
+    is_allowed: MethodPredicate,
+    limiters: Option<Arc<ClientRateLimiters>>,
+) -> ServerHandle {
+    let (stop_handle, server_handle) = stop_channel();
+    let svc_builder = jsonrpsee::server::Server::builder().to_service_builder();
+    let conn_guard = ConnectionGuard::new(MAX_PUBLIC_RPC_CONNECTIONS);
+
+    tokio::spawn(async move {
+        loop {
+            let (sock, remote_addr) = tokio::select! {
+                res = listener.accept() => match res {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        warn!(err = %e, "failed to accept RPC connection");
+                        continue;
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+
+            let Some(conn_permit) = conn_guard.try_acquire() else {
+                debug!(%remote_addr, "too many RPC connections, dropping");
+                continue;
+            };
+
+            let limiter = limiters
+                .as_ref()
+                .map(|limiters| limiters.for_client(remote_addr.ip()));
+            let is_allowed = is_allowed.clone();
+            let rpc_middleware = RpcServiceBuilder::new()
+                .layer_fn(move |service| RateLimit::new(service, limiter.clone()))
+                .layer_fn(move |service| MethodFilter::new(service, is_allowed.clone()));
+            let svc = svc_builder
+                .clone()
+                .set_rpc_middleware(rpc_middleware)
+                .build(methods.clone(), stop_handle.clone());
+
+            let stopped = stop_handle.clone().shutdown();
+            tokio::spawn(async move {
+                if let Err(e) = serve_with_graceful_shutdown(sock, svc, stopped).await {
+                    debug!(%remote_addr, err = %e, "RPC connection failed");
+                }
+                drop(conn_permit);
+            });
+        }
+    });
+
+    server_handle
+}
+
+/// Starts serving the admin methods on their own listener, requiring the requests to be signed
+/// with the JWT secret if there's one.
+///
+/// Unauthenticated admin requests are only accepted from the local machine.
+async fn start_admin_rpc(
+    config: &AdminRpcConfig,
+    methods: Methods,
+) -> anyhow::Result<ServerHandle> {
+    let addr = format!("{}:{}", config.host, config.port);
+    let is_allowed: MethodPredicate = Arc::new(is_admin_method);
+    let rpc_middleware = RpcServiceBuilder::new()
+        .layer_fn(move |service| MethodFilter::new(service, is_allowed.clone()));
+
+    let handle = match &config.jwt_secret {
+        Some(path) => {
+            let secret = load_jwtsecret(path)?;
+            let http_middleware =
+                tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+            jsonrpsee::server::ServerBuilder::new()
+                .set_http_middleware(http_middleware)
+                .set_rpc_middleware(rpc_middleware)
+                .build(&addr)
+                .await?
+                .start(methods)
+        }
+        None => {
+            if !is_loopback_host(&config.host) {
+                anyhow::bail!(
+                    "admin RPC must have a jwt_secret to listen on non-loopback host {}",
+                    config.host
+                );
+            }
+
+            warn!("admin RPC requests are not authenticated");
+            jsonrpsee::server::ServerBuilder::new()
+                .set_rpc_middleware(rpc_middleware)
+                .build(&addr)
+                .await?
+                .start(methods)
+        }
+    };
+
+    info!(%addr, "started admin RPC server");
+    Ok(handle)
+}
@@ -66,6 +66,8 @@ fn get_deprecated_fallback() -> RollupParams {
         dispatch_assignment_dur: 64,
         proof_publish_mode: ProofPublishMode::Timeout(5),
         max_deposits_in_block: 16,
+        forced_inclusion_delay: 6,
+        max_forced_incls_in_block: 16,
         network: bitcoin::Network::Regtest,
     }
 }
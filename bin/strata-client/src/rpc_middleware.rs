@@ -0,0 +1,295 @@
+//! RPC middlewares restricting what gets served on the RPC listeners.
+
+use std::{
+    collections::HashMap,
+    future::{self, Ready},
+    net::IpAddr,
+    sync::Arc,
+    time::Instant,
+};
+
+use futures::future::Either;
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorCode, ErrorObject, Request},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use strata_config::PublicRpcConfig;
+
+/// Prefix of the methods in the `strataadmin` namespace.
+const ADMIN_METHOD_PREFIX: &str = "strataadmin_";
+
+/// Prefix of the debugging methods, which expose internal state and are served like the admin
+/// ones.
+const DEBUG_METHOD_PREFIX: &str = "debug_";
+
+/// Error code returned for requests over the rate limit.
+const RATE_LIMITED_CODE: i32 = -32005;
+
+/// Decides whether a method is served, by name.
+pub type MethodPredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Checks if the method is only served on the admin listener, if there's one.
+pub fn is_admin_method(name: &str) -> bool {
+    name.starts_with(ADMIN_METHOD_PREFIX) || name.starts_with(DEBUG_METHOD_PREFIX)
+}
+
+/// Checks if the host only accepts connections from the local machine.
+pub fn is_loopback_host(host: &str) -> bool {
+    host == "localhost" || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Builds the predicate for the methods served on the public listener, which only serves the
+/// admin methods if there's no separate admin listener.
+pub fn public_method_predicate(config: &PublicRpcConfig, serve_admin: bool) -> MethodPredicate {
+    let allowed_methods = config.allowed_methods.clone();
+    Arc::new(move |name| {
+        if !serve_admin && is_admin_method(name) {
+            return false;
+        }
+
+        allowed_methods
+            .as_ref()
+            .map_or(true, |allowed| allowed.iter().any(|m| m == name))
+    })
+}
+
+/// Rejects the calls to methods the predicate doesn't allow as if they didn't exist.
+pub struct MethodFilter<S> {
+    service: S,
+    is_allowed: MethodPredicate,
+}
+
+impl<S> MethodFilter<S> {
+    pub fn new(service: S, is_allowed: MethodPredicate) -> Self {
+        Self {
+            service,
+            is_allowed,
+        }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for MethodFilter<S>
+where
+    S: RpcServiceT<'a> + Send + Sync,
+{
+    type Future = Either<S::Future, Ready<MethodResponse>>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        if (self.is_allowed)(req.method_name()) {
+            Either::Left(self.service.call(req))
+        } else {
+            Either::Right(future::ready(MethodResponse::error(
+                req.id,
+                ErrorObject::from(ErrorCode::MethodNotFound),
+            )))
+        }
+    }
+}
+
+/// Token bucket that refills at a fixed rate, shared by every connection of a client.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_sec: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allows `per_sec` requests per second, in bursts of up to a second's worth.
+    pub fn new(per_sec: u32) -> Self {
+        Self {
+            per_sec: per_sec as f64,
+            tokens: per_sec as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Checks if the bucket is refilled at `now`, in which case it's the same as a new one.
+    fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens + elapsed.as_secs_f64() * self.per_sec >= self.per_sec
+    }
+
+    /// Takes a token if there's one left at `now`.
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.per_sec).min(self.per_sec);
+        self.last_refill = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// The [`RateLimiter`]s of the clients of a listener, keyed by their IP address so that a client
+/// can't get around its limit by opening more connections.
+#[derive(Debug)]
+pub struct ClientRateLimiters {
+    per_sec: u32,
+    limiters: Mutex<HashMap<IpAddr, Arc<Mutex<RateLimiter>>>>,
+}
+
+impl ClientRateLimiters {
+    /// Allows each client `per_sec` requests per second, see [`RateLimiter::new`].
+    pub fn new(per_sec: u32) -> Self {
+        Self {
+            per_sec,
+            limiters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the limiter of the client, for a new connection of it.
+    pub fn for_client(&self, ip: IpAddr) -> Arc<Mutex<RateLimiter>> {
+        let now = Instant::now();
+        let mut limiters = self.limiters.lock();
+
+        // forget the clients without connections whose limit doesn't matter anymore
+        limiters
+            .retain(|_, limiter| Arc::strong_count(limiter) > 1 || !limiter.lock().is_full(now));
+
+        limiters
+            .entry(ip)
+            .or_insert_with(|| Arc::new(Mutex::new(RateLimiter::new(self.per_sec))))
+            .clone()
+    }
+}
+
+/// Rejects the calls over the rate limit, if there's one.
+pub struct RateLimit<S> {
+    service: S,
+    limiter: Option<Arc<Mutex<RateLimiter>>>,
+}
+
+impl<S> RateLimit<S> {
+    pub fn new(service: S, limiter: Option<Arc<Mutex<RateLimiter>>>) -> Self {
+        Self { service, limiter }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for RateLimit<S>
+where
+    S: RpcServiceT<'a> + Send + Sync,
+{
+    type Future = Either<S::Future, Ready<MethodResponse>>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let allowed = self
+            .limiter
+            .as_ref()
+            .map_or(true, |limiter| limiter.lock().try_acquire(Instant::now()));
+
+        if allowed {
+            Either::Left(self.service.call(req))
+        } else {
+            Either::Right(future::ready(MethodResponse::error(
+                req.id,
+                ErrorObject::borrowed(RATE_LIMITED_CODE, "rate limit exceeded", None),
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_public_method_predicate() {
+        let config = PublicRpcConfig::default();
+        let serve_all = public_method_predicate(&config, true);
+        assert!(serve_all("strata_syncStatus"));
+        assert!(serve_all("strataadmin_stop"));
+
+        let no_admin = public_method_predicate(&config, false);
+        assert!(no_admin("strata_syncStatus"));
+        assert!(!no_admin("strataadmin_stop"));
+        assert!(!no_admin("strataadmin_submitDABlob"));
+        assert!(
+            !no_admin("debug_getChainstateAtIdx"),
+            "debug methods should only be served with the admin ones"
+        );
+
+        let config = PublicRpcConfig {
+            allowed_methods: Some(vec![
+                "strata_syncStatus".to_string(),
+                "strataadmin_stop".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let allow_list = public_method_predicate(&config, false);
+        assert!(allow_list("strata_syncStatus"));
+        assert!(!allow_list("strata_getRawBundles"));
+        assert!(
+            !allow_list("strataadmin_stop"),
+            "admin methods should not be served on the public listener"
+        );
+    }
+
+    #[test]
+    fn test_is_loopback_host() {
+        assert!(is_loopback_host("127.0.0.1"));
+        assert!(is_loopback_host("::1"));
+        assert!(is_loopback_host("localhost"));
+        assert!(!is_loopback_host("0.0.0.0"));
+        assert!(!is_loopback_host("192.168.1.10"));
+        assert!(!is_loopback_host("example.com"));
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(2);
+        let start = limiter.last_refill;
+
+        assert!(limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start));
+        assert!(!limiter.try_acquire(start), "burst should be used up");
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.try_acquire(later), "should refill over time");
+        assert!(!limiter.try_acquire(later));
+
+        let much_later = start + Duration::from_secs(60);
+        assert!(limiter.try_acquire(much_later));
+        assert!(limiter.try_acquire(much_later));
+        assert!(
+            !limiter.try_acquire(much_later),
+            "should not refill past the burst size"
+        );
+    }
+
+    #[test]
+    fn test_client_rate_limiters() {
+        let limiters = ClientRateLimiters::new(1);
+        let client: IpAddr = "10.0.0.1".parse().unwrap();
+        let other_client: IpAddr = "10.0.0.2".parse().unwrap();
+
+        let conn = limiters.for_client(client);
+        let now = Instant::now();
+        assert!(conn.lock().try_acquire(now));
+
+        let other_conn = limiters.for_client(client);
+        assert!(
+            !other_conn.lock().try_acquire(now),
+            "connections of a client should share its limit"
+        );
+        assert!(
+            limiters.for_client(other_client).lock().try_acquire(now),
+            "clients should not share their limits"
+        );
+
+        drop(conn);
+        drop(other_conn);
+        let conn = limiters.for_client(client);
+        assert!(
+            !conn.lock().try_acquire(now),
+            "limit should outlive the connections of the client"
+        );
+    }
+}
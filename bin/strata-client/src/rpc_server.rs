@@ -1,16 +1,14 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::sync::Arc;
 
 use async_trait::async_trait;
-use bitcoin::{
-    consensus::deserialize,
-    hashes::Hash,
-    key::Parity,
-    secp256k1::{PublicKey, XOnlyPublicKey},
-    Transaction as BTransaction, Txid,
-};
+use bitcoin::{consensus::deserialize, hashes::Hash, Transaction as BTransaction, Txid};
 use futures::TryFutureExt;
-use jsonrpsee::core::RpcResult;
-use strata_bridge_relay::relayer::RelayerHandle;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use serde::Serialize;
+use strata_bridge_relay::{relayer::RelayerHandle, stats::OperatorRelayStats};
 use strata_btcio::{broadcaster::L1BroadcastHandle, writer::InscriptionHandle};
 use strata_consensus_logic::{
     checkpoint::CheckpointHandle, csm::state_tracker::reconstruct_state, l1_handler::verify_proof,
@@ -31,8 +29,9 @@ use strata_rpc_api::{
 };
 use strata_rpc_types::{
     errors::RpcServerError as Error, DaBlob, HexBytes, HexBytes32, L2BlockStatus, RpcBlockHeader,
-    RpcBridgeDuties, RpcChainState, RpcCheckpointInfo, RpcClientStatus, RpcDepositEntry,
-    RpcExecUpdate, RpcL1Status, RpcSyncStatus,
+    RpcBridgeDuties, RpcBridgeRelayStats, RpcChainState, RpcCheckpointInfo, RpcClientStatus,
+    RpcDepositEntry, RpcExecUpdate, RpcL1Status, RpcOperatorRelayStats, RpcRelayCounters,
+    RpcSyncStatus,
 };
 use strata_rpc_utils::to_jsonrpsee_error;
 use strata_state::{
@@ -40,8 +39,9 @@ use strata_state::{
     block::{L2Block, L2BlockBundle},
     bridge_duties::BridgeDuty,
     bridge_ops::WithdrawalIntent,
-    client_state::ClientState,
+    client_state::{ClientState, LocalL1State, SyncState},
     da_blob::{BlobDest, BlobIntent},
+    exec_update::Op,
     header::L2Header,
     id::L2BlockId,
     l1::L1BlockId,
@@ -49,12 +49,15 @@ use strata_state::{
     sync_event::SyncEvent,
 };
 use strata_status::StatusChannel;
-use strata_storage::L2BlockManager;
+use strata_storage::{L2BlockManager, PruneHorizon};
 use strata_zkvm::ProofReceipt;
-use tokio::sync::{oneshot, Mutex};
+use tokio::sync::{broadcast::error::RecvError, oneshot, Mutex};
 use tracing::*;
 
-use crate::extractor::{extract_deposit_requests, extract_withdrawal_infos};
+use crate::{
+    duty_tracker::{operator_pubkey_table, retired_operator_pubkey_table},
+    extractor::{extract_deposit_requests, extract_reimbursement_infos, extract_withdrawal_infos},
+};
 
 fn fetch_l2blk<D: Database + Sync + Send + 'static>(
     l2_db: &Arc<<D as Database>::L2DB>,
@@ -73,6 +76,7 @@ pub struct StrataRpcImpl<D> {
     l2_block_manager: Arc<L2BlockManager>,
     checkpoint_handle: Arc<CheckpointHandle>,
     relayer_handle: Arc<RelayerHandle>,
+    prune_horizon: Arc<PruneHorizon>,
 }
 
 impl<D: Database + Sync + Send + 'static> StrataRpcImpl<D> {
@@ -84,6 +88,7 @@ impl<D: Database + Sync + Send + 'static> StrataRpcImpl<D> {
         l2_block_manager: Arc<L2BlockManager>,
         checkpoint_handle: Arc<CheckpointHandle>,
         relayer_handle: Arc<RelayerHandle>,
+        prune_horizon: Arc<PruneHorizon>,
     ) -> Self {
         Self {
             status_channel,
@@ -92,8 +97,64 @@ impl<D: Database + Sync + Send + 'static> StrataRpcImpl<D> {
             l2_block_manager,
             checkpoint_handle,
             relayer_handle,
+            prune_horizon,
         }
     }
+
+    /// Errors out if the L2 data at `height` has been pruned.
+    fn check_l2_not_pruned(&self, height: u64) -> Result<(), Error> {
+        if self.prune_horizon.is_l2_pruned(height) {
+            return Err(Error::Pruned("L2 block", height));
+        }
+        Ok(())
+    }
+
+    /// Errors out if the L1 txs at `height` have been pruned.
+    fn check_l1_not_pruned(&self, height: u64) -> Result<(), Error> {
+        if self.prune_horizon.is_l1_pruned(height) {
+            return Err(Error::Pruned("L1 block", height));
+        }
+        Ok(())
+    }
+
+    /// Builds the [`RpcClientStatus`] from the sync state and the L1 view of a client state.
+    async fn build_client_status(
+        &self,
+        sync_state: Option<SyncState>,
+        l1_view: &LocalL1State,
+    ) -> Result<RpcClientStatus, Error> {
+        let last_l1 = l1_view.tip_blkid().cloned().unwrap_or_else(|| {
+            // TODO figure out a better way to do this
+            warn!("last L1 block not set in client state, returning zero");
+            L1BlockId::from(Buf32::zero())
+        });
+
+        // Copy these out of the sync state, if they're there.
+        let (chain_tip, finalized_blkid) = sync_state
+            .map(|ss| (*ss.chain_tip_blkid(), *ss.finalized_blkid()))
+            .unwrap_or_default();
+
+        // FIXME make this load from cache, and put the data we actually want
+        // here in the client state
+        // FIXME error handling
+        let db = self.database.clone();
+        let slot: u64 = wait_blocking("load_cur_block", move || {
+            let l2_db = db.l2_db();
+            l2_db
+                .get_block_data(chain_tip)
+                .map(|b| b.map(|b| b.header().blockidx()).unwrap_or(u64::MAX))
+                .map_err(Error::from)
+        })
+        .await?;
+
+        Ok(RpcClientStatus {
+            chain_tip: *chain_tip.as_ref(),
+            chain_tip_slot: slot,
+            finalized_blkid: *finalized_blkid.as_ref(),
+            last_l1_block: *last_l1.as_ref(),
+            buried_l1_height: l1_view.buried_l1_height(),
+        })
+    }
 }
 
 fn conv_blk_header_to_rpc(blk_header: &impl L2Header) -> RpcBlockHeader {
@@ -111,6 +172,7 @@ fn conv_blk_header_to_rpc(blk_header: &impl L2Header) -> RpcBlockHeader {
 #[async_trait]
 impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
     async fn get_blocks_at_idx(&self, idx: u64) -> RpcResult<Vec<HexBytes32>> {
+        self.check_l2_not_pruned(idx)?;
         let l2_block_manager = self.l2_block_manager.clone();
         let l2_blocks = l2_block_manager
             .get_blocks_at_height_async(idx)
@@ -162,37 +224,7 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
         let sync_state = self.status_channel.sync_state();
         let l1_view = self.status_channel.l1_view();
 
-        let last_l1 = l1_view.tip_blkid().cloned().unwrap_or_else(|| {
-            // TODO figure out a better way to do this
-            warn!("last L1 block not set in client state, returning zero");
-            L1BlockId::from(Buf32::zero())
-        });
-
-        // Copy these out of the sync state, if they're there.
-        let (chain_tip, finalized_blkid) = sync_state
-            .map(|ss| (*ss.chain_tip_blkid(), *ss.finalized_blkid()))
-            .unwrap_or_default();
-
-        // FIXME make this load from cache, and put the data we actually want
-        // here in the client state
-        // FIXME error handling
-        let db = self.database.clone();
-        let slot: u64 = wait_blocking("load_cur_block", move || {
-            let l2_db = db.l2_db();
-            l2_db
-                .get_block_data(chain_tip)
-                .map(|b| b.map(|b| b.header().blockidx()).unwrap_or(u64::MAX))
-                .map_err(Error::from)
-        })
-        .await?;
-
-        Ok(RpcClientStatus {
-            chain_tip: *chain_tip.as_ref(),
-            chain_tip_slot: slot,
-            finalized_blkid: *finalized_blkid.as_ref(),
-            last_l1_block: *last_l1.as_ref(),
-            buried_l1_height: l1_view.buried_l1_height(),
-        })
+        Ok(self.build_client_status(sync_state, &l1_view).await?)
     }
 
     async fn get_recent_block_headers(&self, count: u64) -> RpcResult<Vec<RpcBlockHeader>> {
@@ -228,6 +260,7 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
     }
 
     async fn get_headers_at_idx(&self, idx: u64) -> RpcResult<Option<Vec<RpcBlockHeader>>> {
+        self.check_l2_not_pruned(idx)?;
         let sync_state = self.status_channel.sync_state();
         let tip_blkid = *sync_state.ok_or(Error::ClientNotStarted)?.chain_tip_blkid();
         let db = self.database.clone();
@@ -303,6 +336,16 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
                     })
                     .collect();
 
+                let forced_inclusions = exec_update
+                    .input()
+                    .applied_ops()
+                    .iter()
+                    .filter_map(|op| match op {
+                        Op::ForcedInclusion(forced_incl) => Some(forced_incl.clone()),
+                        _ => None,
+                    })
+                    .collect();
+
                 Ok(Some(RpcExecUpdate {
                     update_idx: exec_update.input().update_idx(),
                     entries_root: *exec_update.input().entries_root().as_ref(),
@@ -310,6 +353,7 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
                     new_state: *exec_update.output().new_state().as_ref(),
                     withdrawals,
                     da_blobs,
+                    forced_inclusions,
                 }))
             }
             None => Ok(None),
@@ -325,6 +369,9 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
         .await?;
 
         let prev_slot = l2_blk_bundle.block().header().header().blockidx() - 1;
+        if self.prune_horizon.is_l2_pruned(prev_slot) {
+            return Err(Error::Pruned("chainstate", prev_slot).into());
+        }
 
         let chain_state_db = self.database.clone();
         let chain_state = wait_blocking("l2_chain_state", move || {
@@ -376,6 +423,8 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
     }
 
     async fn get_raw_bundles(&self, start_height: u64, end_height: u64) -> RpcResult<HexBytes> {
+        self.check_l2_not_pruned(start_height)?;
+
         let block_ids = futures::future::join_all(
             (start_height..=end_height)
                 .map(|height| self.l2_block_manager.get_blocks_at_height_async(height)),
@@ -400,6 +449,10 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
             .filter_map(|blk| blk.ok().flatten())
             .collect::<Vec<_>>();
 
+        // The horizon only moves up and is moved before the blocks below it get deleted, so if it
+        // is still at or below the start of the range, none of the blocks read were pruned.
+        self.check_l2_not_pruned(start_height)?;
+
         borsh::to_vec(&blocks)
             .map(HexBytes)
             .map_err(to_jsonrpsee_error("failed to serialize"))
@@ -420,6 +473,35 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
         Ok(block)
     }
 
+    async fn get_chainstate_snapshot(&self, epoch: u64) -> RpcResult<Option<HexBytes>> {
+        let Some(entry) = self
+            .checkpoint_handle
+            .get_checkpoint(epoch)
+            .await
+            .map_err(|e| Error::Other(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let checkpoint: BatchCheckpoint = entry.into();
+        let last_l2_height = checkpoint.batch_info().l2_range.1;
+        if self.prune_horizon.is_l2_pruned(last_l2_height) {
+            return Err(Error::Pruned("chainstate", last_l2_height).into());
+        }
+
+        let db = self.database.clone();
+        let chainstate = wait_blocking("chainstate_snapshot", move || {
+            db.chain_state_db()
+                .get_toplevel_state(last_l2_height)
+                .map_err(Error::Db)
+        })
+        .await?
+        .ok_or(Error::MissingChainstate(last_l2_height))?;
+
+        let snapshot =
+            borsh::to_vec(&chainstate).map_err(to_jsonrpsee_error("failed to serialize"))?;
+        Ok(Some(HexBytes(snapshot)))
+    }
+
     async fn get_msgs_by_scope(&self, scope: HexBytes) -> RpcResult<Vec<HexBytes>> {
         let msgs = self
             .relayer_handle
@@ -448,6 +530,33 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
         Ok(())
     }
 
+    async fn get_bridge_relay_stats(&self) -> RpcResult<RpcBridgeRelayStats> {
+        let stats = self.relayer_handle.stats();
+
+        let to_counters = |op_stats: OperatorRelayStats| RpcRelayCounters {
+            relayed: op_stats.relayed,
+            over_quota: op_stats.over_quota,
+            oversized: op_stats.oversized,
+            unknown_duty: op_stats.unknown_duty,
+        };
+
+        let operators = stats
+            .operators
+            .into_iter()
+            .map(|(operator_idx, op_stats)| RpcOperatorRelayStats {
+                operator_idx,
+                counters: to_counters(op_stats),
+            })
+            .collect();
+
+        Ok(RpcBridgeRelayStats {
+            operators,
+            unauthenticated: to_counters(stats.unauthenticated),
+            invalid: stats.invalid,
+            unverified: stats.unverified,
+        })
+    }
+
     // FIXME: find a way to handle reorgs if that becomes a problem
     async fn get_bridge_duties(
         &self,
@@ -455,6 +564,7 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
         start_index: u64,
     ) -> RpcResult<RpcBridgeDuties> {
         info!(%operator_idx, %start_index, "received request for bridge duties");
+        self.check_l1_not_pruned(start_index)?;
 
         // OPTIMIZE: the extraction of deposit and withdrawal duties can happen in parallel as they
         // depend on independent sources of information. This optimization can be done if this RPC
@@ -475,10 +585,12 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
             .ok_or(Error::BeforeGenesis)?;
 
         let withdrawal_duties = extract_withdrawal_infos(&deps_table).map(BridgeDuty::from);
+        let reimbursement_duties = extract_reimbursement_infos(&deps_table).map(BridgeDuty::from);
 
         let mut duties = vec![];
         duties.extend(deposit_duties);
         duties.extend(withdrawal_duties);
+        duties.extend(reimbursement_duties);
 
         info!(%operator_idx, %start_index, "dispatching duties");
         Ok(RpcBridgeDuties {
@@ -493,21 +605,15 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
             .status_channel
             .operator_table()
             .ok_or(Error::BeforeGenesis)?;
-        let operator_map: BTreeMap<OperatorIdx, PublicKey> = operator_table
-            .operators()
-            .iter()
-            .fold(BTreeMap::new(), |mut map, entry| {
-                let pubkey = XOnlyPublicKey::try_from(*entry.wallet_pk())
-                    .expect("something has gone horribly wrong");
-
-                // This is a taproot pubkey so its parity has to be even.
-                let pubkey = pubkey.public_key(Parity::Even);
-
-                map.insert(entry.idx(), pubkey);
-                map
-            });
+        Ok(operator_pubkey_table(&operator_table))
+    }
 
-        Ok(operator_map.into())
+    async fn get_retired_operator_chain_pubkey_set(&self) -> RpcResult<PublickeyTable> {
+        let operator_table = self
+            .status_channel
+            .operator_table()
+            .ok_or(Error::BeforeGenesis)?;
+        Ok(retired_operator_pubkey_table(&operator_table))
     }
 
     async fn get_checkpoint_info(&self, idx: u64) -> RpcResult<Option<RpcCheckpointInfo>> {
@@ -553,6 +659,10 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
 
     // FIXME: possibly create a separate rpc type corresponding to SyncEvent
     async fn get_sync_event(&self, idx: u64) -> RpcResult<Option<SyncEvent>> {
+        if self.prune_horizon.is_sync_event_pruned(idx) {
+            return Err(Error::Pruned("sync event", idx).into());
+        }
+
         let db = self.database.clone();
 
         let ev: Option<SyncEvent> = wait_blocking("fetch_sync_event", move || {
@@ -596,6 +706,255 @@ impl<D: Database + Send + Sync + 'static> StrataApiServer for StrataRpcImpl<D> {
 
         Ok(res)
     }
+
+    async fn subscribe_new_headers(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let fetch_limit = self.sync_manager.params().run().l2_blocks_fetch_limit;
+        let mut chs_rx = self.status_channel.subscribe_chain_state();
+        let mut last_tip_blkid = None;
+
+        loop {
+            let tip_blkid = chs_rx
+                .borrow_and_update()
+                .as_ref()
+                .map(|chs| chs.chain_tip_blockid());
+
+            if let Some(tip_blkid) = tip_blkid.filter(|blkid| Some(*blkid) != last_tip_blkid) {
+                let db = self.database.clone();
+                let headers = wait_blocking("new_block_headers", move || {
+                    fetch_headers_since::<D>(db.l2_db(), tip_blkid, last_tip_blkid, fetch_limit)
+                })
+                .await?;
+
+                for header in headers.iter().rev() {
+                    send_subscription_item(&sink, header).await?;
+                }
+
+                last_tip_blkid = Some(tip_blkid);
+            }
+
+            tokio::select! {
+                _ = sink.closed() => break,
+                res = chs_rx.changed() => if res.is_err() { break },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe_client_status(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let mut cupdate_rx = self.sync_manager.create_cstate_subscription();
+
+        loop {
+            let notif = tokio::select! {
+                _ = sink.closed() => break,
+                notif = cupdate_rx.recv() => notif,
+            };
+
+            let notif = match notif {
+                Ok(notif) => notif,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(%skipped, "client status subscription lagged behind");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let state = notif.new_state();
+            let status = self
+                .build_client_status(state.sync().cloned(), state.l1_view())
+                .await?;
+
+            send_subscription_item(&sink, &status).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe_checkpoints(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let mut cupdate_rx = self.sync_manager.create_cstate_subscription();
+
+        // Only the checkpoints verified after the subscription has started are sent.
+        let mut last_idx = self
+            .status_channel
+            .l1_view()
+            .verified_checkpoints()
+            .last()
+            .map(|ckpt| ckpt.batch_info.idx());
+
+        loop {
+            let notif = tokio::select! {
+                _ = sink.closed() => break,
+                notif = cupdate_rx.recv() => notif,
+            };
+
+            let notif = match notif {
+                Ok(notif) => notif,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(%skipped, "checkpoints subscription lagged behind");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            let prev_idx = last_idx;
+            let new_checkpoints = notif
+                .new_state()
+                .l1_view()
+                .verified_checkpoints()
+                .iter()
+                .filter(|ckpt| prev_idx.map_or(true, |idx| ckpt.batch_info.idx() > idx));
+
+            for ckpt in new_checkpoints {
+                let info = RpcCheckpointInfo::from(ckpt.batch_info.clone());
+                send_subscription_item(&sink, &info).await?;
+                last_idx = Some(ckpt.batch_info.idx());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe_bridge_duties(
+        &self,
+        pending: PendingSubscriptionSink,
+        operator_idx: OperatorIdx,
+        start_index: u64,
+    ) -> SubscriptionResult {
+        if let Err(e) = self.check_l1_not_pruned(start_index) {
+            pending.reject(e).await;
+            return Ok(());
+        }
+        let sink = pending.accept().await?;
+        info!(%operator_idx, %start_index, "received subscription for bridge duties");
+
+        let l1_db = self.database.l1_db();
+        let network = self.sync_manager.params().rollup().network;
+        let mut chs_rx = self.status_channel.subscribe_chain_state();
+        let mut start_index = start_index;
+        let mut last_chain_duties = None;
+
+        loop {
+            let deps_table = chs_rx
+                .borrow_and_update()
+                .as_ref()
+                .map(|chs| chs.deposits_table().clone());
+
+            if let Some(deps_table) = deps_table {
+                let (deposit_duties, stop_index) =
+                    extract_deposit_requests(l1_db, start_index, network).await?;
+
+                let mut duties: Vec<BridgeDuty> = deposit_duties.map(BridgeDuty::from).collect();
+                let chain_duties: Vec<BridgeDuty> = extract_withdrawal_infos(&deps_table)
+                    .map(BridgeDuty::from)
+                    .chain(extract_reimbursement_infos(&deps_table).map(BridgeDuty::from))
+                    .collect();
+
+                if !duties.is_empty() || last_chain_duties.as_ref() != Some(&chain_duties) {
+                    duties.extend(chain_duties.iter().cloned());
+
+                    let bridge_duties = RpcBridgeDuties {
+                        duties,
+                        start_index,
+                        stop_index,
+                    };
+                    send_subscription_item(&sink, &bridge_duties).await?;
+
+                    last_chain_duties = Some(chain_duties);
+                }
+
+                start_index = stop_index;
+            }
+
+            tokio::select! {
+                _ = sink.closed() => break,
+                res = chs_rx.changed() => if res.is_err() { break },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe_bridge_msgs(
+        &self,
+        pending: PendingSubscriptionSink,
+        scope: HexBytes,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let mut msg_rx = self.relayer_handle.subscribe_messages();
+
+        loop {
+            let msg = tokio::select! {
+                _ = sink.closed() => break,
+                msg = msg_rx.recv() => msg,
+            };
+
+            let msg = match msg {
+                Ok(msg) => msg,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(%skipped, "bridge msgs subscription lagged behind");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            if msg.scope() != scope.0.as_slice() {
+                continue;
+            }
+
+            match borsh::to_vec(&msg) {
+                Ok(raw_msg) => send_subscription_item(&sink, &HexBytes(raw_msg)).await?,
+                Err(_) => {
+                    let msg_id = msg.compute_id();
+                    warn!(%msg_id, "failed to serialize bridge msg");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the headers of the blocks from `tip_blkid` back to, but excluding, `last_blkid`,
+/// starting from the tip.
+///
+/// At most `limit` headers are collected, which bounds the walk back if `last_blkid` is not an
+/// ancestor of the tip, as is the case after a reorg.
+fn fetch_headers_since<D: Database + Sync + Send + 'static>(
+    l2_db: &Arc<<D as Database>::L2DB>,
+    tip_blkid: L2BlockId,
+    last_blkid: Option<L2BlockId>,
+    limit: u64,
+) -> Result<Vec<RpcBlockHeader>, Error> {
+    let mut output = Vec::new();
+    let mut cur_blkid = tip_blkid;
+
+    while output.len() < limit as usize && Some(cur_blkid) != last_blkid {
+        let l2_blk = fetch_l2blk::<D>(l2_db, cur_blkid)?;
+        output.push(conv_blk_header_to_rpc(l2_blk.header()));
+        cur_blkid = *l2_blk.header().parent();
+        if l2_blk.header().blockidx() == 0 || Buf32::from(cur_blkid).is_zero() {
+            break;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Serializes the item and sends it to the subscriber.
+async fn send_subscription_item<T: Serialize>(
+    sink: &SubscriptionSink,
+    item: &T,
+) -> SubscriptionResult {
+    let msg = SubscriptionMessage::from_json(item)?;
+    sink.send(msg).await?;
+
+    Ok(())
 }
 
 /// Wrapper around [``tokio::task::spawn_blocking``] that handles errors in
@@ -758,13 +1117,19 @@ impl StrataSequencerApiServer for SequencerServerImpl {
 pub struct StrataDebugRpcImpl<D> {
     l2_block_manager: Arc<L2BlockManager>,
     database: Arc<D>,
+    prune_horizon: Arc<PruneHorizon>,
 }
 
 impl<D: Database + Sync + Send + 'static> StrataDebugRpcImpl<D> {
-    pub fn new(l2_block_manager: Arc<L2BlockManager>, database: Arc<D>) -> Self {
+    pub fn new(
+        l2_block_manager: Arc<L2BlockManager>,
+        database: Arc<D>,
+        prune_horizon: Arc<PruneHorizon>,
+    ) -> Self {
         Self {
             l2_block_manager,
             database,
+            prune_horizon,
         }
     }
 }
@@ -781,6 +1146,10 @@ impl<D: Database + Sync + Send + 'static> StrataDebugApiServer for StrataDebugRp
         Ok(l2_block)
     }
     async fn get_chainstate_at_idx(&self, idx: u64) -> RpcResult<Option<RpcChainState>> {
+        if self.prune_horizon.is_l2_pruned(idx) {
+            return Err(Error::Pruned("chainstate", idx).into());
+        }
+
         let db = self.database.clone();
         let chain_state = wait_blocking("chain_state_at_idx", move || {
             db.chain_state_db()
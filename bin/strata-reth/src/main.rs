@@ -6,6 +6,7 @@ use std::{fs, future::Future, path::PathBuf, sync::Arc};
 use alloy_genesis::Genesis;
 use clap::Parser;
 use reth::{
+    api::FullNodeComponents,
     args::LogArgs,
     builder::{NodeBuilder, WithLaunchContext},
     CliRunner,
@@ -16,7 +17,9 @@ use reth_cli_commands::node::NodeCommand;
 use strata_reth_db::rocksdb::WitnessDB;
 use strata_reth_exex::ProverWitnessGenerator;
 use strata_reth_node::StrataEthereumNode;
-use strata_reth_rpc::{SequencerClient, StrataRPC, StrataRpcApiServer};
+use strata_reth_rpc::{
+    SequencerClient, StrataEngineApiServer, StrataEngineRPC, StrataRPC, StrataRpcApiServer,
+};
 use tracing::info;
 
 const DEFAULT_CHAIN_SPEC: &str = include_str!("../res/devnet-chain.json");
@@ -59,6 +62,11 @@ fn main() {
 
         // Note: can only add single hook
         node_builder = node_builder.extend_rpc_modules(|ctx| {
+            // The CL checks the forced inclusions of new payloads through the auth endpoint.
+            let engine_rpc =
+                StrataEngineRPC::new(ctx.provider().clone(), ctx.node().evm_config().clone());
+            ctx.auth_module.merge_auth_methods(engine_rpc.into_rpc())?;
+
             if let Some(rpc) = extend_rpc {
                 ctx.modules.merge_configured(rpc.into_rpc())?;
             }
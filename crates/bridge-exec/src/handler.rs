@@ -1,19 +1,23 @@
 //! Deposit/withdrawal transaction handling module
 
-use std::{fmt::Debug, time::Duration};
+use std::{
+    fmt::Debug,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
-use bitcoin::{key::Keypair, Transaction, Txid};
+use bitcoin::{Transaction, Txid};
 use borsh::{BorshDeserialize, BorshSerialize};
-use jsonrpsee::tokio::time::sleep;
-use strata_bridge_sig_manager::manager::SignatureManager;
+use jsonrpsee::{
+    core::client::Subscription,
+    tokio::time::{sleep, timeout},
+};
+use strata_bridge_sig_manager::{manager::SignatureManager, signer::BridgeSigner};
 use strata_bridge_tx_builder::{context::BuildContext, TxKind};
 use strata_primitives::{
     bridge::{Musig2PartialSig, Musig2PubNonce, OperatorIdx, OperatorPartialSig},
     l1::BitcoinTxid,
-    relay::{
-        types::{BridgeMessage, Scope},
-        util::MessageSigner,
-    },
+    relay::types::{BridgeMessage, Scope},
 };
 use strata_rpc_api::StrataApiClient;
 use strata_rpc_types::HexBytes;
@@ -22,13 +26,15 @@ use tracing::{debug, info, warn};
 use crate::errors::{ExecError, ExecResult};
 
 /// The execution context for handling bridge-related signing activities.
-#[derive(Clone)]
 pub struct ExecHandler<
     L2Client: StrataApiClient + Sync + Send,
     TxBuildContext: BuildContext + Sync + Send,
 > {
     /// The build context required to create transaction data needed for signing.
-    pub tx_build_ctx: TxBuildContext,
+    ///
+    /// The operator set changes between epochs, so this is replaced whenever the pubkeys on the
+    /// rollup change.
+    pub tx_build_ctx: RwLock<TxBuildContext>,
 
     /// The signature manager that handles nonce and signature aggregation.
     pub sig_manager: SignatureManager,
@@ -36,21 +42,40 @@ pub struct ExecHandler<
     /// The RPC client to connect to the RPC full node.
     pub l2_rpc_client: L2Client,
 
-    /// The keypair for this client used to sign bridge-related messages.
-    pub keypair: Keypair,
+    /// The signer holding this client's key, used to sign bridge-related messages.
+    pub signer: Arc<dyn BridgeSigner>,
 
     /// This client's position in the MuSig2 signing ceremony.
     pub own_index: OperatorIdx,
 
-    /// The interval for polling bridge messages.
+    /// The interval for polling bridge messages when they aren't pushed through a subscription.
+    ///
+    /// The messages are still polled at this interval while subscribed in case some were missed.
     pub msg_polling_interval: Duration,
 }
 
 impl<L2Client, TxBuildContext> ExecHandler<L2Client, TxBuildContext>
 where
     L2Client: StrataApiClient + Sync + Send,
-    TxBuildContext: BuildContext + Sync + Send,
+    TxBuildContext: BuildContext + Clone + Sync + Send,
 {
+    /// Get a copy of the current build context, so that a transaction is built and signed against
+    /// the same operator set even if it's replaced in the meantime.
+    pub fn build_context(&self) -> TxBuildContext {
+        self.tx_build_ctx
+            .read()
+            .expect("build context lock poisoned")
+            .clone()
+    }
+
+    /// Replace the build context, for example after the operator set changed.
+    pub fn set_build_context(&self, tx_build_ctx: TxBuildContext) {
+        *self
+            .tx_build_ctx
+            .write()
+            .expect("build context lock poisoned") = tx_build_ctx;
+    }
+
     /// Construct and sign a transaction based on the provided `TxInfo`.
     ///
     /// # Returns
@@ -62,21 +87,24 @@ where
     {
         info!("starting transaction signing");
 
-        let operator_pubkeys = self.tx_build_ctx.pubkey_table();
-
         info!(?tx_info, "received transaction details");
 
+        // the operators that sign may be a subset of the current ones, for example when spending a
+        // deposit locked to the operators that notarized it
+        let tx_build_ctx = self.build_context();
+        let operator_pubkeys = tx_info.signers(&tx_build_ctx)?;
+
         // sign the transaction with MuSig2 and put inside the OperatorPartialSig
 
         // construct the transaction data
-        let tx_signing_data = tx_info.construct_signing_data(&self.tx_build_ctx)?;
+        let tx_signing_data = tx_info.construct_signing_data(&tx_build_ctx)?;
 
         debug!(?tx_signing_data, "got the signing data");
 
         // add the tx_state to the sig_manager in order to generate a sec_nonce and pub_nonce
         let txid = self
             .sig_manager
-            .add_tx_state(tx_signing_data, operator_pubkeys.clone())
+            .add_tx_state(tx_signing_data, operator_pubkeys)
             .await
             .map_err(|e| ExecError::Signing(e.to_string()))?;
 
@@ -114,8 +142,15 @@ where
         payload: S,
         txid: &Txid,
     ) -> Result<BridgeMessage, ExecError> {
-        let signed_message = MessageSigner::new(self.own_index, self.keypair.secret_key().into())
-            .sign_scope(scope, &payload)
+        let raw_scope = borsh::to_vec(scope).expect("should be able to borsh serialize scope");
+        let raw_payload =
+            borsh::to_vec(&payload).expect("should be able to borsh serialize payload");
+        let unsigned_message = BridgeMessage::new_unsigned(self.own_index, raw_scope, raw_payload);
+
+        let signed_message = self
+            .signer
+            .sign_bridge_msg(unsigned_message)
+            .await
             .map_err(|e| ExecError::Signing(e.to_string()))?;
         debug!(?signed_message, "created the message");
 
@@ -131,15 +166,21 @@ where
     }
 
     /// Poll for nonces until all nonces have been collected.
-    // TODO: use long-polling here instead.
     async fn poll_for_nonces(&self, scope: &[u8], txid: &Txid) -> Result<(), ExecError> {
         debug!(%txid, "polling for other operators' nonces");
 
-        loop {
-            let received_nonces = self.parse_messages::<Musig2PubNonce>(scope).await?;
+        let mut subscription = self.subscribe_messages(scope).await;
+        let mut received_nonces = self.get_messages::<Musig2PubNonce>(scope).await?;
 
+        loop {
             let mut all_done = false;
             for (sender_idx, pub_nonce) in received_nonces {
+                // Our own nonce is added along with the state. Any nonce of ours still held by
+                // the relay may be from a session that the signer has since lost.
+                if sender_idx == self.own_index {
+                    continue;
+                }
+
                 all_done = self
                     .sig_manager
                     .add_nonce(txid, sender_idx, &pub_nonce)
@@ -156,7 +197,9 @@ where
                 break;
             }
 
-            sleep(self.msg_polling_interval).await;
+            received_nonces = self
+                .wait_for_messages::<Musig2PubNonce>(scope, &mut subscription)
+                .await?;
         }
 
         Ok(())
@@ -244,13 +287,13 @@ where
         Ok(tx)
     }
 
-    // TODO: use long-polling here instead.
     async fn poll_for_signatures(&self, scope: &[u8], txid: &Txid) -> Result<(), ExecError> {
         debug!("waiting for other operators' signatures");
 
-        loop {
-            let signatures = self.parse_messages::<Musig2PartialSig>(scope).await?;
+        let mut subscription = self.subscribe_messages(scope).await;
+        let mut signatures = self.get_messages::<Musig2PartialSig>(scope).await?;
 
+        loop {
             let mut all_signed = false;
             for (signer_index, partial_sig) in signatures {
                 let signature_info = OperatorPartialSig::new(partial_sig, signer_index);
@@ -272,16 +315,65 @@ where
                 break;
             }
 
-            sleep(self.msg_polling_interval).await;
+            signatures = self
+                .wait_for_messages::<Musig2PartialSig>(scope, &mut subscription)
+                .await?;
         }
 
         Ok(())
     }
 
-    async fn parse_messages<'parser, Payload>(
-        &'parser self,
-        scope: &'parser [u8],
-    ) -> Result<impl Iterator<Item = (OperatorIdx, Payload)> + 'parser, ExecError>
+    /// Subscribes to the messages with the given `scope`, returning `None` if the full node
+    /// doesn't support it, in which case we can only poll for them.
+    async fn subscribe_messages(&self, scope: &[u8]) -> Option<Subscription<HexBytes>> {
+        match self.l2_rpc_client.subscribe_bridge_msgs(scope.into()).await {
+            Ok(subscription) => Some(subscription),
+            Err(err) => {
+                warn!(%err, "could not subscribe to bridge messages, falling back to polling");
+                None
+            }
+        }
+    }
+
+    /// Waits for the next message pushed through the `subscription`, or for the polling interval
+    /// to pass and then gets all the messages with the given `scope`.
+    ///
+    /// If the subscription gets closed, it is dropped and only polling is used from then on.
+    async fn wait_for_messages<Payload>(
+        &self,
+        scope: &[u8],
+        subscription: &mut Option<Subscription<HexBytes>>,
+    ) -> Result<Vec<(OperatorIdx, Payload)>, ExecError>
+    where
+        Payload: BorshDeserialize + Debug,
+    {
+        match subscription {
+            Some(sub) => match timeout(self.msg_polling_interval, sub.next()).await {
+                Ok(Some(Ok(raw_msg))) => {
+                    return Ok(parse_message(&raw_msg.0).into_iter().collect())
+                }
+                Ok(Some(Err(err))) => {
+                    warn!(%err, "bridge message subscription failed, falling back to polling");
+                    *subscription = None;
+                }
+                Ok(None) => {
+                    warn!("bridge message subscription closed, falling back to polling");
+                    *subscription = None;
+                }
+                // Nothing was pushed for a while, poll in case we missed something.
+                Err(_) => {}
+            },
+            None => sleep(self.msg_polling_interval).await,
+        }
+
+        self.get_messages(scope).await
+    }
+
+    /// Gets all the messages with the given `scope` from the full node.
+    async fn get_messages<Payload>(
+        &self,
+        scope: &[u8],
+    ) -> Result<Vec<(OperatorIdx, Payload)>, ExecError>
     where
         Payload: BorshDeserialize + Debug,
     {
@@ -292,32 +384,38 @@ where
             .get_msgs_by_scope(raw_scope)
             .await?
             .into_iter()
-            .filter_map(move |msg| {
-                let msg = borsh::from_slice::<BridgeMessage>(&msg.0);
-                if let Ok(msg) = msg {
-                    let raw_payload = msg.payload();
-                    let payload = borsh::from_slice::<Payload>(raw_payload);
-                    let raw_scope = msg.scope();
-                    let scope = borsh::from_slice::<Scope>(raw_scope);
-                    debug!(?msg, ?payload, ?scope, "got message from the L2 Client");
-
-                    match payload {
-                        Ok(payload) => Some((msg.source_id(), payload)),
-                        Err(ref error) => {
-                            warn!(?scope, ?payload, ?error, "skipping faulty message payload");
-                            None
-                        }
-                    }
-                } else {
-                    warn!(?scope, "skipping faulty message");
-                    None
-                }
-            });
+            .filter_map(|msg| parse_message(&msg.0))
+            .collect();
 
         Ok(received_payloads)
     }
 }
 
+/// Parses a raw [`BridgeMessage`] into its sender and payload, skipping faulty ones.
+fn parse_message<Payload>(raw_msg: &[u8]) -> Option<(OperatorIdx, Payload)>
+where
+    Payload: BorshDeserialize + Debug,
+{
+    let Ok(msg) = borsh::from_slice::<BridgeMessage>(raw_msg) else {
+        warn!("skipping faulty message");
+        return None;
+    };
+
+    let raw_payload = msg.payload();
+    let payload = borsh::from_slice::<Payload>(raw_payload);
+    let raw_scope = msg.scope();
+    let scope = borsh::from_slice::<Scope>(raw_scope);
+    debug!(?msg, ?payload, ?scope, "got message from the L2 Client");
+
+    match payload {
+        Ok(payload) => Some((msg.source_id(), payload)),
+        Err(ref error) => {
+            warn!(?scope, ?payload, ?error, "skipping faulty message payload");
+            None
+        }
+    }
+}
+
 impl<L2Client, TxBuildContext> Debug for ExecHandler<L2Client, TxBuildContext>
 where
     L2Client: StrataApiClient + Sync + Send,
@@ -328,7 +426,7 @@ where
             f,
             "Handler Context index: {}, pubkey: {}",
             self.own_index,
-            self.keypair.public_key()
+            self.signer.pubkey()
         )
     }
 }
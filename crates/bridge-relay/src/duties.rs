@@ -0,0 +1,183 @@
+//! Lookup of the transactions the operators are expected to be signing, so that the relayer can
+//! hold back the nonces and signatures for anything else.
+
+use std::collections::{HashMap, VecDeque};
+
+use async_trait::async_trait;
+use bitcoin::Txid;
+use strata_primitives::{
+    bridge::OperatorIdx,
+    relay::types::{BridgeMessage, BridgeMsgId, Scope},
+};
+
+/// Knows the txids of the transactions built for the pending bridge duties.
+#[async_trait]
+pub trait PendingDutyTxids: Send + Sync + 'static {
+    /// Returns if the transaction with the given txid is the one built for a pending duty.
+    ///
+    /// A txid that isn't known may still become known once the duties catch up with the chain, so
+    /// this means "not yet" rather than "never".
+    async fn is_pending_duty_txid(&self, txid: &Txid) -> anyhow::Result<bool>;
+}
+
+/// Accepts every txid, for when there's no way to know the pending duties.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnyDutyTxid;
+
+#[async_trait]
+impl PendingDutyTxids for AnyDutyTxid {
+    async fn is_pending_duty_txid(&self, _txid: &Txid) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Returns if the message is for a pending duty, or isn't for any duty at all.
+pub async fn is_for_pending_duty(
+    duty_txids: &dyn PendingDutyTxids,
+    message: &BridgeMessage,
+) -> anyhow::Result<bool> {
+    match message.try_parse_scope() {
+        Some(Scope::V0Sig(txid) | Scope::V0PubNonce(txid)) => {
+            duty_txids.is_pending_duty_txid(&txid.inner()).await
+        }
+        _ => Ok(true),
+    }
+}
+
+/// Messages held back because their duty isn't known yet, to be retried later.
+///
+/// Only a limited number of messages is kept per operator, the oldest ones are dropped first.
+#[derive(Debug)]
+pub struct DeferredMessages {
+    /// The maximum number of messages kept per operator.
+    max_per_operator: usize,
+
+    /// The messages of each operator with the time they were first received, oldest first.
+    msgs: HashMap<OperatorIdx, VecDeque<(u128, BridgeMsgId, BridgeMessage)>>,
+}
+
+impl DeferredMessages {
+    /// Creates an empty instance that keeps up to `max_per_operator` messages per operator.
+    pub fn new(max_per_operator: usize) -> Self {
+        Self {
+            max_per_operator,
+            msgs: HashMap::new(),
+        }
+    }
+
+    /// Holds back a message received at the given time, unless it already is.
+    pub fn push(&mut self, timestamp: u128, message: BridgeMessage) {
+        let message_id = message.compute_id();
+        let queue = self.msgs.entry(message.source_id()).or_default();
+        if queue.iter().any(|(_, id, _)| *id == message_id) {
+            return;
+        }
+
+        queue.push_back((timestamp, message_id, message));
+        if queue.len() > self.max_per_operator {
+            queue.pop_front();
+        }
+    }
+
+    /// Takes out all the messages received after `after_ts`, dropping the older ones.
+    pub fn take_since(&mut self, after_ts: u128) -> Vec<(u128, BridgeMessage)> {
+        self.msgs
+            .drain()
+            .flat_map(|(_, queue)| queue)
+            .filter(|(timestamp, _, _)| *timestamp > after_ts)
+            .map(|(timestamp, _, message)| (timestamp, message))
+            .collect()
+    }
+
+    /// Returns the number of messages held back.
+    pub fn len(&self) -> usize {
+        self.msgs.values().map(VecDeque::len).sum()
+    }
+
+    /// Returns if no message is held back.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use strata_primitives::l1::BitcoinTxid;
+    use strata_test_utils::ArbitraryGenerator;
+
+    use super::*;
+
+    struct KnownTxids(HashSet<Txid>);
+
+    #[async_trait]
+    impl PendingDutyTxids for KnownTxids {
+        async fn is_pending_duty_txid(&self, txid: &Txid) -> anyhow::Result<bool> {
+            Ok(self.0.contains(txid))
+        }
+    }
+
+    fn message(source_id: OperatorIdx, scope: Scope, payload: Vec<u8>) -> BridgeMessage {
+        let scope: Vec<u8> = scope.try_into().expect("scope must serialize");
+        BridgeMessage::new_unsigned(source_id, scope, payload)
+    }
+
+    #[tokio::test]
+    async fn test_is_for_pending_duty() {
+        let mut generator = ArbitraryGenerator::new();
+        let known: BitcoinTxid = generator.generate();
+        let unknown: BitcoinTxid = generator.generate();
+        let duty_txids = KnownTxids(HashSet::from([known.inner()]));
+
+        for scope in [Scope::V0Sig(known.clone()), Scope::V0PubNonce(known)] {
+            let msg = message(0, scope, vec![]);
+            assert!(is_for_pending_duty(&duty_txids, &msg).await.unwrap());
+        }
+
+        for scope in [Scope::V0Sig(unknown.clone()), Scope::V0PubNonce(unknown)] {
+            let msg = message(0, scope, vec![]);
+            assert!(
+                !is_for_pending_duty(&duty_txids, &msg).await.unwrap(),
+                "messages for unknown duties must be held back"
+            );
+        }
+
+        let msg = message(0, Scope::Misc, vec![]);
+        assert!(
+            is_for_pending_duty(&duty_txids, &msg).await.unwrap(),
+            "messages without a duty must pass"
+        );
+    }
+
+    #[test]
+    fn test_deferred_messages() {
+        let mut deferred = DeferredMessages::new(2);
+
+        deferred.push(1, message(0, Scope::Misc, vec![1]));
+        deferred.push(1, message(0, Scope::Misc, vec![1]));
+        assert_eq!(deferred.len(), 1, "duplicates must only be kept once");
+
+        deferred.push(2, message(0, Scope::Misc, vec![2]));
+        deferred.push(3, message(0, Scope::Misc, vec![3]));
+        deferred.push(3, message(1, Scope::Misc, vec![3]));
+        assert_eq!(
+            deferred.len(),
+            3,
+            "only the latest messages of an operator must be kept"
+        );
+
+        let mut retried: Vec<_> = deferred
+            .take_since(2)
+            .into_iter()
+            .map(|(ts, msg)| (ts, msg.source_id(), msg.payload().to_vec()))
+            .collect();
+        retried.sort();
+        assert_eq!(
+            retried,
+            vec![(3, 0, vec![3]), (3, 1, vec![3])],
+            "stale messages must be dropped"
+        );
+        assert!(deferred.is_empty());
+    }
+}
@@ -1,2 +1,5 @@
+pub mod duties;
+pub mod quotas;
 mod recent_msg_tracker;
 pub mod relayer;
+pub mod stats;
@@ -0,0 +1,139 @@
+//! Bookkeeping of the messages relayed in the current refresh interval, to enforce the quotas of
+//! the operators and scopes.
+
+use std::collections::HashMap;
+
+use strata_config::RelayLimitsConfig;
+use strata_primitives::bridge::OperatorIdx;
+
+/// Counts the messages relayed in the current refresh interval.
+///
+/// Messages without a verified source are all counted towards a single quota instead of that of
+/// their operator and scope, since both their source and their scope can be made up.
+#[derive(Debug)]
+pub struct QuotaTracker {
+    /// Limits on the number of messages.
+    limits: RelayLimitsConfig,
+
+    /// Number of messages relayed from each operator.
+    operator_msg_counts: HashMap<OperatorIdx, u32>,
+
+    /// Number of messages relayed with each scope.
+    scope_msg_counts: HashMap<Vec<u8>, u32>,
+
+    /// Number of messages relayed whose source isn't verified.
+    unverified_msg_count: u32,
+}
+
+impl QuotaTracker {
+    /// Creates a new instance with all the quotas available.
+    pub fn new(limits: RelayLimitsConfig) -> Self {
+        Self {
+            limits,
+            operator_msg_counts: HashMap::new(),
+            scope_msg_counts: HashMap::new(),
+            unverified_msg_count: 0,
+        }
+    }
+
+    /// Returns if a message from the given source with the given scope is within the quotas.
+    pub fn has_room(&self, source: Option<OperatorIdx>, scope: &[u8]) -> bool {
+        let Some(source_id) = source else {
+            return self.unverified_msg_count < self.limits.max_unverified_msgs;
+        };
+
+        let operator_count = self.operator_msg_counts.get(&source_id).copied();
+        let scope_count = self.scope_msg_counts.get(scope).copied();
+        operator_count.unwrap_or_default() < self.limits.max_msgs_per_operator
+            && scope_count.unwrap_or_default() < self.limits.max_msgs_per_scope
+    }
+
+    /// Counts a relayed message from the given source with the given scope towards the quotas.
+    pub fn charge(&mut self, source: Option<OperatorIdx>, scope: &[u8]) {
+        let Some(source_id) = source else {
+            self.unverified_msg_count += 1;
+            return;
+        };
+
+        *self.operator_msg_counts.entry(source_id).or_default() += 1;
+        *self.scope_msg_counts.entry(scope.to_vec()).or_default() += 1;
+    }
+
+    /// Starts a new refresh interval, so that all the quotas are available again.
+    pub fn reset(&mut self) {
+        self.operator_msg_counts.clear();
+        self.scope_msg_counts.clear();
+        self.unverified_msg_count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> RelayLimitsConfig {
+        RelayLimitsConfig {
+            max_msgs_per_operator: 2,
+            max_msgs_per_scope: 3,
+            max_unverified_msgs: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_operator_quota_reset() {
+        let mut quotas = QuotaTracker::new(limits());
+
+        for scope in [&[0][..], &[1]] {
+            assert!(quotas.has_room(Some(1), scope));
+            quotas.charge(Some(1), scope);
+        }
+        assert!(
+            !quotas.has_room(Some(1), &[2]),
+            "operator must be over its quota"
+        );
+        assert!(
+            quotas.has_room(Some(2), &[2]),
+            "other operators must have their own quota"
+        );
+
+        quotas.reset();
+        assert!(
+            quotas.has_room(Some(1), &[2]),
+            "quota must be available again after a reset"
+        );
+    }
+
+    #[test]
+    fn test_scope_quota() {
+        let mut quotas = QuotaTracker::new(limits());
+
+        for source in [1, 2, 3] {
+            assert!(quotas.has_room(Some(source), &[0]));
+            quotas.charge(Some(source), &[0]);
+        }
+        assert!(
+            !quotas.has_room(Some(4), &[0]),
+            "scope must be over its quota"
+        );
+        assert!(quotas.has_room(Some(4), &[1]));
+    }
+
+    #[test]
+    fn test_unverified_quota() {
+        let mut quotas = QuotaTracker::new(limits());
+
+        quotas.charge(None, &[0]);
+        assert!(
+            !quotas.has_room(None, &[1]),
+            "unverified messages must share a single quota"
+        );
+        assert!(
+            quotas.has_room(Some(1), &[0]),
+            "unverified messages must not use up the quota of an operator or scope"
+        );
+
+        quotas.reset();
+        assert!(quotas.has_room(None, &[1]));
+    }
+}
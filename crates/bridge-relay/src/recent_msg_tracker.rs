@@ -14,15 +14,14 @@ impl RecentMessageTracker {
         }
     }
 
-    /// Checks if we should relay the message.
-    pub async fn check_should_relay(&self, cur_timestamp: u128, message_id: BridgeMsgId) -> bool {
-        let mut msgs = self.messages.lock().await;
-        if msgs.contains_key(&message_id) {
-            return false;
-        }
+    /// Checks if we've already relayed the message.
+    pub async fn is_seen(&self, message_id: &BridgeMsgId) -> bool {
+        self.messages.lock().await.contains_key(message_id)
+    }
 
-        msgs.insert(message_id, cur_timestamp);
-        true
+    /// Remembers that we've relayed the message, so that we don't relay it again.
+    pub async fn mark_seen(&self, cur_timestamp: u128, message_id: BridgeMsgId) {
+        self.messages.lock().await.insert(message_id, cur_timestamp);
     }
 
     /// Clears messages that should be forgotten by now.
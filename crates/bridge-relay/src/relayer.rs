@@ -1,6 +1,6 @@
 //! For message routing, deduplication, enforcing operator bandwidth, processing and validation,
 use std::{
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -9,10 +9,22 @@ use strata_primitives::relay::types::{BridgeMessage, Scope};
 use strata_status::StatusChannel;
 use strata_storage::ops::bridge_relay::BridgeMsgOps;
 use strata_tasks::TaskExecutor;
-use tokio::{select, sync::mpsc, time::interval};
+use tokio::{
+    select,
+    sync::{broadcast, mpsc},
+    time::interval,
+};
 use tracing::*;
 
-use crate::recent_msg_tracker::RecentMessageTracker;
+use crate::{
+    duties::{is_for_pending_duty, DeferredMessages, PendingDutyTxids},
+    quotas::QuotaTracker,
+    recent_msg_tracker::RecentMessageTracker,
+    stats::RelayStats,
+};
+
+/// Number of accepted messages buffered for each subscriber before it starts lagging behind.
+const MSG_SUBSCRIPTION_CAPACITY: usize = 1024;
 
 /// Contains bookkeeping for deduplicating messages and persisting them to disk.
 pub struct RelayerState {
@@ -27,6 +39,21 @@ pub struct RelayerState {
 
     /// Tracker to avoid duplicating messages.
     processed_msgs: RecentMessageTracker,
+
+    /// Notifies subscribers of the messages we accept.
+    msg_tx: broadcast::Sender<BridgeMessage>,
+
+    /// To check that the nonces and signatures are for transactions we expect.
+    duty_txids: Arc<dyn PendingDutyTxids>,
+
+    /// Messages held back until the duty they're for is known.
+    deferred_msgs: DeferredMessages,
+
+    /// Number of messages relayed in the current refresh interval.
+    quotas: QuotaTracker,
+
+    /// Counters shared with the [`RelayerHandle`].
+    stats: Arc<Mutex<RelayStats>>,
 }
 
 impl RelayerState {
@@ -35,31 +62,45 @@ impl RelayerState {
         config: RelayerConfig,
         brmsg_ops: Arc<BridgeMsgOps>,
         status_channel: StatusChannel,
+        msg_tx: broadcast::Sender<BridgeMessage>,
+        duty_txids: Arc<dyn PendingDutyTxids>,
+        stats: Arc<Mutex<RelayStats>>,
     ) -> Self {
         Self {
             config,
             brmsg_ops,
             status_channel,
             processed_msgs: RecentMessageTracker::new(),
+            msg_tx,
+            duty_txids,
+            deferred_msgs: DeferredMessages::new(
+                config.limits.max_deferred_msgs_per_operator as usize,
+            ),
+            quotas: QuotaTracker::new(config.limits),
+            stats,
         }
     }
 
+    fn update_stats(&self, f: impl FnOnce(&mut RelayStats)) {
+        f(&mut self.stats.lock().expect("relayer: stats lock poisoned"));
+    }
+
     /// Handles new incoming messages by validating, enforcing bandwidth limits, checking
     /// duplicates, updating the chain state, and storing the message in the database.
     ///
     /// # Arguments
     ///
+    /// * `timestamp` - When the message was first received.
     /// * `message` - The incoming [`BridgeMessage`] to be processed.
-    async fn handle_new_message(&mut self, message: BridgeMessage) -> anyhow::Result<()> {
+    async fn handle_new_message(
+        &mut self,
+        timestamp: u128,
+        message: BridgeMessage,
+    ) -> anyhow::Result<()> {
         let message_id = message.compute_id();
 
         // Check for duplicates
-        let timestamp = get_now_micros();
-        if !self
-            .processed_msgs
-            .check_should_relay(timestamp, message_id)
-            .await
-        {
+        if self.processed_msgs.is_seen(&message_id).await {
             trace!(%message_id, "dropping message we've already seen");
             return Ok(());
         }
@@ -77,26 +118,80 @@ impl RelayerState {
             false
         };
 
+        let limits = self.config.limits;
+
         // If it's not a misc message, then we want to actually do deeper
         // validation on it.
+        let mut verified = false;
         if !is_misc {
-            // We only perform the deeper validation if we're properly synced.
-            // Otherwise it's better for network health to relay them
-            // unconditionally.
-            // TODO make it configurable if we relay or not without chainstate?
-            if let Some(op_table) = self.status_channel.operator_table() {
-                let sig_res =
-                    strata_primitives::relay::util::verify_bridge_msg_sig(&message, &op_table);
-
-                if let Err(e) = sig_res {
-                    trace!(err = %e, "dropping invalid message");
+            // We can only perform the deeper validation if we're properly synced.
+            // Otherwise we only relay them if we've been set to in the config.
+            match self.status_channel.operator_table() {
+                Some(op_table) => {
+                    let sig_res =
+                        strata_primitives::relay::util::verify_bridge_msg_sig(&message, &op_table);
+
+                    if let Err(e) = sig_res {
+                        trace!(err = %e, "dropping invalid message");
+                        self.update_stats(|stats| stats.invalid += 1);
+                        return Ok(());
+                    }
+
+                    verified = true;
+                }
+                None if !limits.relay_unverified => {
+                    trace!(%message_id, "dropping message we can't verify yet");
+                    self.update_stats(|stats| stats.unverified += 1);
                     return Ok(());
                 }
+                None => {}
             }
         }
 
+        let source_id = message.source_id();
+
+        // Only verified messages are charged to the operator they claim to be from.
+        let source = verified.then_some(source_id);
+
+        let size = message.payload().len();
+        if size > limits.max_payload_size {
+            debug!(%message_id, %source_id, %size, "dropping oversized message");
+            self.update_stats(|stats| stats.source_mut(source).oversized += 1);
+            return Ok(());
+        }
+
+        // Check the quotas before anything expensive, but only charge them for the messages we
+        // relay.
+        if !self.quotas.has_room(source, message.scope()) {
+            debug!(%message_id, %source_id, "dropping message over quota");
+            self.update_stats(|stats| stats.source_mut(source).over_quota += 1);
+            return Ok(());
+        }
+
+        // Nonces and signatures are only useful for the transactions of pending duties, but we may
+        // not know about the duty yet, so hold them back to retry later.  Holding a message back
+        // costs the sender its quota just like relaying it, so that made up txids can't be used to
+        // make us look up the duties for free.
+        self.quotas.charge(source, message.scope());
+        if verified && !is_for_pending_duty(self.duty_txids.as_ref(), &message).await? {
+            debug!(%message_id, %source_id, "deferring message for unknown duty");
+            self.update_stats(|stats| stats.source_mut(source).unknown_duty += 1);
+            self.deferred_msgs.push(timestamp, message);
+            return Ok(());
+        }
+
+        // Only now that it's accepted, so that we can still take it if it's resent after being
+        // dropped.
+        self.processed_msgs.mark_seen(timestamp, message_id).await;
+
         // Store it in database.
-        self.brmsg_ops.write_msg_async(timestamp, message).await?;
+        self.brmsg_ops
+            .write_msg_async(timestamp, message.clone())
+            .await?;
+        self.update_stats(|stats| stats.source_mut(source).relayed += 1);
+
+        // Then notify the subscribers, if there are any.
+        let _ = self.msg_tx.send(message);
 
         Ok(())
     }
@@ -117,11 +212,34 @@ impl RelayerState {
         self.processed_msgs.clear_stale_messages(before_ts).await;
         Ok(())
     }
+
+    /// Starts a new refresh interval, so that operators can send messages up to their quota
+    /// again.
+    fn reset_quotas(&mut self) {
+        self.quotas.reset();
+    }
+
+    /// Retries the messages held back because their duty wasn't known, dropping those received
+    /// before `before_ts`.
+    async fn retry_deferred_msgs(&mut self, before_ts: u128) {
+        let deferred = self.deferred_msgs.take_since(before_ts);
+        if !deferred.is_empty() {
+            debug!(num_msgs = %deferred.len(), "retrying deferred messages");
+        }
+
+        for (timestamp, message) in deferred {
+            if let Err(e) = self.handle_new_message(timestamp, message).await {
+                warn!(err = %e, "failed to handle deferred message");
+            }
+        }
+    }
 }
 
 pub struct RelayerHandle {
     brmsg_tx: mpsc::Sender<BridgeMessage>,
+    msg_tx: broadcast::Sender<BridgeMessage>,
     ops: Arc<BridgeMsgOps>,
+    stats: Arc<Mutex<RelayStats>>,
 }
 
 impl RelayerHandle {
@@ -139,6 +257,20 @@ impl RelayerHandle {
         }
     }
 
+    /// Subscribes to the messages accepted by the relayer from now on, after they've been
+    /// stored.
+    pub fn subscribe_messages(&self) -> broadcast::Receiver<BridgeMessage> {
+        self.msg_tx.subscribe()
+    }
+
+    /// Returns the counters of the messages relayed and dropped so far.
+    pub fn stats(&self) -> RelayStats {
+        self.stats
+            .lock()
+            .expect("relayer: stats lock poisoned")
+            .clone()
+    }
+
     // TODO refactor this to not require vec
     pub async fn get_message_by_scope_async(
         &self,
@@ -163,6 +295,7 @@ impl RelayerHandle {
 pub fn start_bridge_relayer_task(
     ops: Arc<BridgeMsgOps>,
     status_channel: StatusChannel,
+    duty_txids: Arc<dyn PendingDutyTxids>,
     config: RelayerConfig,
     task_exec: &TaskExecutor,
 ) -> Arc<RelayerHandle> {
@@ -170,10 +303,26 @@ pub fn start_bridge_relayer_task(
     // the peer that sent them to us
     let (brmsg_tx, brmsg_rx) = mpsc::channel::<BridgeMessage>(100);
 
-    let state = RelayerState::new(config, ops.clone(), status_channel);
+    let (msg_tx, _) = broadcast::channel::<BridgeMessage>(MSG_SUBSCRIPTION_CAPACITY);
+
+    let stats = Arc::new(Mutex::new(RelayStats::default()));
+
+    let state = RelayerState::new(
+        config,
+        ops.clone(),
+        status_channel,
+        msg_tx.clone(),
+        duty_txids,
+        stats.clone(),
+    );
     task_exec.spawn_critical_async("bridge-msg-relayer", relayer_task(state, brmsg_rx));
 
-    let h = RelayerHandle { brmsg_tx, ops };
+    let h = RelayerHandle {
+        brmsg_tx,
+        msg_tx,
+        ops,
+        stats,
+    };
     Arc::new(h)
 }
 
@@ -189,17 +338,21 @@ async fn relayer_task(
                 let bmsg_id = new_message.compute_id();
                 trace!(%bmsg_id, "new bridge msg");
 
-                if let Err(e) = state.handle_new_message(new_message).await {
+                if let Err(e) = state.handle_new_message(get_now_micros(), new_message).await {
                     error!(err = %e, "failed to handle new message");
                 }
             }
 
             _ = refresh_interval.tick() => {
+                state.reset_quotas();
+
                 // prune old messages that cross the threshold duration
                 let duration = get_now_micros() - state.config.stale_duration as u128 * 1_000_000;
                 if let Err(e) = state.prune_old_msg_before(duration).await {
                     warn!(err = %e, "failed to purge stale messages");
                 }
+
+                state.retry_deferred_msgs(duration).await;
             }
         }
     }
@@ -232,10 +385,11 @@ mod tests {
             .as_micros();
 
         // Initially, the message should not be marked as duplicate
-        assert!(processed_msgs.check_should_relay(ts, message_id).await);
+        assert!(!processed_msgs.is_seen(&message_id).await);
+        processed_msgs.mark_seen(ts, message_id).await;
 
         // Now it should be marked as duplicate
-        assert!(!processed_msgs.check_should_relay(ts, message_id).await);
+        assert!(processed_msgs.is_seen(&message_id).await);
     }
 
     #[tokio::test]
@@ -254,33 +408,19 @@ mod tests {
         let old_ts = cur_ts - Duration::from_secs(200);
         let old_ts_us = old_ts.as_micros();
 
-        // Both messages should be considered processed initially
-        assert!(
-            processed_msgs
-                .check_should_relay(cur_ts_us, cur_message_id)
-                .await
-        );
-        assert!(
-            processed_msgs
-                .check_should_relay(old_ts_us, old_message_id)
-                .await
-        );
+        // Both messages should be considered processed once marked
+        processed_msgs.mark_seen(cur_ts_us, cur_message_id).await;
+        processed_msgs.mark_seen(old_ts_us, old_message_id).await;
+        assert!(processed_msgs.is_seen(&cur_message_id).await);
+        assert!(processed_msgs.is_seen(&old_message_id).await);
 
         // Clear old messages
         processed_msgs.clear_stale_messages(cur_ts_us - 1).await;
 
         // The old message should no longer be considered processed
-        assert!(
-            processed_msgs
-                .check_should_relay(old_ts_us, old_message_id)
-                .await
-        );
+        assert!(!processed_msgs.is_seen(&old_message_id).await);
 
         // The current message should still be considered processed
-        assert!(
-            !processed_msgs
-                .check_should_relay(cur_ts_us, cur_message_id)
-                .await
-        );
+        assert!(processed_msgs.is_seen(&cur_message_id).await);
     }
 }
@@ -0,0 +1,51 @@
+//! Counters of the messages the relayer accepted and dropped, per operator.
+//!
+//! Only messages with a verified signature are counted under the operator they're from, so there
+//! are never more entries than operators.  The rest are counted together.
+
+use std::collections::BTreeMap;
+
+use strata_primitives::bridge::OperatorIdx;
+
+/// Counters of the messages from a single operator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperatorRelayStats {
+    /// Messages relayed.
+    pub relayed: u64,
+
+    /// Messages dropped because the operator or the scope was over its quota.
+    pub over_quota: u64,
+
+    /// Messages dropped because their payload was too large.
+    pub oversized: u64,
+
+    /// Times a message was held back because its txid wasn't part of any pending duty yet.
+    pub unknown_duty: u64,
+}
+
+/// Counters of the messages seen by the relayer since it started.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelayStats {
+    /// Counters per operator.
+    pub operators: BTreeMap<OperatorIdx, OperatorRelayStats>,
+
+    /// Counters of the messages whose source isn't verified, such as misc messages.
+    pub unauthenticated: OperatorRelayStats,
+
+    /// Messages dropped because their signature was invalid.
+    pub invalid: u64,
+
+    /// Messages dropped because we couldn't check their signature.
+    pub unverified: u64,
+}
+
+impl RelayStats {
+    /// Returns the counters for the given verified operator, creating them if needed, or the
+    /// counters of unauthenticated messages if there is none.
+    pub fn source_mut(&mut self, source: Option<OperatorIdx>) -> &mut OperatorRelayStats {
+        match source {
+            Some(idx) => self.operators.entry(idx).or_default(),
+            None => &mut self.unauthenticated,
+        }
+    }
+}
@@ -82,6 +82,19 @@ pub enum BridgeSigError {
     /// Could not apply tweak.
     #[error("could not apply tweak due to {0}")]
     Tweak(#[from] TweakError),
+
+    /// The signer has no open signing session for the transaction, either because it has already
+    /// signed it or because the signer restarted.
+    #[error("no open signing session for the transaction")]
+    SessionNotFound,
+
+    /// The signer already has as many signing sessions open as it allows.
+    #[error("too many open signing sessions")]
+    TooManySessions,
+
+    /// Failed to get a response from a remote signer.
+    #[error("remote signer: {0}")]
+    Remote(String),
 }
 
 /// Result type alias for the signature manager with [`BridgeSigError`] as the Error variant.
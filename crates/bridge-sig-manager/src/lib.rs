@@ -1,9 +1,13 @@
 //! Handles signing and storing of signatures.
 //!
 //! Provides APIs to sign the given transaction based on the configured `Reserved Address`
-//! or private key, store the signatures and look them up when necessary.
+//! or private key, store the signatures and look them up when necessary. The operator's key is
+//! only ever used through a [`BridgeSigner`](signer::BridgeSigner), which may either hold it
+//! in-process or forward the requests to a separate signing service.
 
 pub mod errors;
 pub mod manager;
 pub mod operations;
 pub mod prelude;
+pub mod remote;
+pub mod signer;
@@ -4,19 +4,13 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use bitcoin::{
-    hashes::Hash,
-    key::{
-        rand::{rngs::OsRng, RngCore},
-        Keypair, TapTweak,
-    },
-    secp256k1::{schnorr::Signature, PublicKey, SecretKey, XOnlyPublicKey},
+    key::TapTweak,
+    secp256k1::{schnorr::Signature, PublicKey, XOnlyPublicKey},
     sighash::SighashCache,
     witness::Witness,
     Transaction, Txid,
 };
-use musig2::{
-    aggregate_partial_signatures, secp256k1::SECP256K1, AggNonce, KeyAggContext, SecNonce,
-};
+use musig2::{aggregate_partial_signatures, secp256k1::SECP256K1, AggNonce, KeyAggContext};
 use strata_db::entities::bridge_tx_state::BridgeTxState;
 use strata_primitives::{
     bridge::{
@@ -27,10 +21,13 @@ use strata_primitives::{
     l1::TaprootSpendPath,
 };
 use strata_storage::ops::bridge::BridgeTxStateOps;
-use tracing::info;
+use tracing::{info, warn};
 
 use super::errors::{BridgeSigError, BridgeSigResult};
-use crate::operations::{create_message_hash, sign_state_partial, verify_partial_sig};
+use crate::{
+    operations::{create_message_hash, verify_partial_sig},
+    signer::BridgeSigner,
+};
 
 /// Handle creation, collection and aggregation of signatures for a [`BridgeTxState`] with the help
 /// of a persistence layer.
@@ -39,8 +36,8 @@ pub struct SignatureManager {
     /// Abstraction over the persistence layer for the signatures.
     db_ops: Arc<BridgeTxStateOps>,
 
-    /// This bridge client's keypair
-    keypair: Keypair,
+    /// Signer holding this bridge client's key.
+    signer: Arc<dyn BridgeSigner>,
 
     /// This bridge client's Operator index.
     index: OperatorIdx,
@@ -54,10 +51,14 @@ impl std::fmt::Debug for SignatureManager {
 
 impl SignatureManager {
     /// Create a new [`SignatureManager`].
-    pub fn new(db_ops: Arc<BridgeTxStateOps>, index: OperatorIdx, keypair: Keypair) -> Self {
+    pub fn new(
+        db_ops: Arc<BridgeTxStateOps>,
+        index: OperatorIdx,
+        signer: Arc<dyn BridgeSigner>,
+    ) -> Self {
         Self {
             db_ops,
-            keypair,
+            signer,
             index,
         }
     }
@@ -65,7 +66,8 @@ impl SignatureManager {
     /// Adds a [`BridgeTxState`] to the [`SignatureManager`]
     ///
     /// If the computed [`Txid`] is already present in the database, it is not updated but simply
-    /// returned.
+    /// returned, unless the signer has lost the session for the nonce that was shared and not yet
+    /// signed with, in which case the state is replaced with one that starts a new nonce round.
     pub async fn add_tx_state(
         &self,
         tx_signing_data: TxSigningData,
@@ -73,21 +75,6 @@ impl SignatureManager {
     ) -> BridgeSigResult<Txid> {
         let txid = tx_signing_data.psbt.compute_txid();
 
-        // if the state is already present, then there is no need to generate a new secnonce,
-        // the pubnonces and signatures can all be deterministically generated once the secnonce is
-        // fixed.
-        //
-        // A collision between `Txid`'s is practically impossible.
-        if self
-            .db_ops
-            .get_tx_state_async(Buf32::from(txid))
-            .await?
-            .is_some()
-        {
-            info!(%txid, "not replacing tx_state that is already present in the tx_db");
-            return Ok(txid);
-        }
-
         let key_agg_ctx = KeyAggContext::new(pubkey_table.0.values().copied())?;
 
         let keypath_spend_only = matches!(tx_signing_data.spend_path, TaprootSpendPath::Key);
@@ -97,13 +84,35 @@ impl SignatureManager {
         } else {
             key_agg_ctx
         };
+        let aggregated_pubkey = key_agg_ctx.aggregated_pubkey();
 
-        let sec_nonce = self.generate_sec_nonce(&txid, &key_agg_ctx);
-        let pub_nonce = sec_nonce.public_nonce();
+        // A collision between `Txid`'s is practically impossible.
+        let existing = self.db_ops.get_tx_state_async(Buf32::from(txid)).await?;
 
-        let mut tx_state = BridgeTxState::new(tx_signing_data, pubkey_table, sec_nonce.into())?;
+        // Once signed, the session is closed and there is nothing left to redo.
+        if existing
+            .as_ref()
+            .is_some_and(|state| state.collected_sigs().contains_key(&self.index))
+        {
+            info!(%txid, "not replacing signed tx_state that is already present in the tx_db");
+            return Ok(txid);
+        }
+
+        // If the signer still holds the session, this returns the pubnonce that was shared.
+        let pub_nonce = self.signer.open_session(&txid, &aggregated_pubkey).await?;
+
+        if let Some(tx_state) = existing {
+            if tx_state.collected_nonces().get(&self.index) == Some(&pub_nonce) {
+                info!(%txid, "not replacing tx_state that is already present in the tx_db");
+                return Ok(txid);
+            }
+
+            warn!(%txid, "signer lost the signing session, restarting the nonce round");
+        }
 
-        tx_state.add_nonce(&self.index, pub_nonce.into())?;
+        let mut tx_state = BridgeTxState::new(tx_signing_data, pubkey_table)?;
+
+        tx_state.add_nonce(&self.index, pub_nonce)?;
 
         self.db_ops
             .put_tx_state_async(Buf32::from(txid), tx_state)
@@ -126,30 +135,6 @@ impl SignatureManager {
         entry.ok_or(BridgeSigError::TransactionNotFound)
     }
 
-    /// Generate a random secret nonce.
-    ///
-    /// Please refer to MuSig2 nonce generation section in
-    /// [BIP 327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki).
-    ///
-    /// # Notes
-    ///
-    /// The entropy is pooled using the underlying operating system's
-    /// cryptographic-safe pseudo-random number generator with [`OsRng`].
-    fn generate_sec_nonce(&self, txid: &Txid, key_agg_ctx: &KeyAggContext) -> SecNonce {
-        let aggregated_pubkey: PublicKey = key_agg_ctx.aggregated_pubkey();
-
-        let mut nonce_seed = [0u8; 32];
-        OsRng.fill_bytes(&mut nonce_seed);
-
-        let seckey = SecretKey::from_keypair(&self.keypair);
-
-        SecNonce::build(nonce_seed)
-            .with_seckey(seckey)
-            .with_message(txid.as_byte_array())
-            .with_aggregated_pubkey(aggregated_pubkey)
-            .build()
-    }
-
     /// Get one's own public nonce for the given [`Txid`].
     ///
     /// Please refer to MuSig2 nonce generation section in
@@ -232,23 +217,23 @@ impl SignatureManager {
 
         let message = create_message_hash(&mut sighash_cache, &prevouts, spend_info)?;
 
-        let message = message.as_ref();
-
-        let signature = sign_state_partial(
-            tx_state.pubkeys(),
-            tx_state.secnonce(),
-            &self.keypair,
-            &aggregated_nonce,
-            message,
-            keypath_spend_only,
-        )?;
+        let signature = self
+            .signer
+            .sign_partial(
+                txid,
+                tx_state.pubkeys(),
+                &aggregated_nonce,
+                &message,
+                keypath_spend_only,
+            )
+            .await?;
 
-        let own_signature_info = OperatorPartialSig::new(signature.into(), self.index);
+        let own_signature_info = OperatorPartialSig::new(signature, self.index);
         verify_partial_sig(
             &tx_state,
             &own_signature_info,
             &aggregated_nonce,
-            message,
+            message.as_ref(),
             keypath_spend_only,
         )?;
 
@@ -445,10 +430,11 @@ mod tests {
 
     use arbitrary::{Arbitrary, Unstructured};
     use bitcoin::{
-        hashes::sha256,
-        secp256k1::{Message, PublicKey, SECP256K1},
+        hashes::{sha256, Hash},
+        key::Keypair,
+        secp256k1::{Message, SecretKey},
     };
-    use musig2::PubNonce;
+    use musig2::{PubNonce, SecNonce};
     use strata_primitives::bridge::Musig2PartialSig;
     use strata_test_utils::bridge::{
         generate_keypairs, generate_mock_tx_signing_data, generate_mock_tx_state_ops,
@@ -456,6 +442,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::{operations::sign_state_partial, signer::KeypairSigner};
 
     #[tokio::test]
     async fn test_add_tx_state_keyspend() {
@@ -515,33 +502,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_generate_sec_nonce() {
-        let (pks, sks) = generate_keypairs(5);
-        let pubkey_table = generate_pubkey_table(&pks);
-
-        let key_agg_ctx = KeyAggContext::new(pubkey_table.0.values().copied())
-            .expect("should be able to create key aggregation context");
-
-        let self_index = 0;
-        let keypair = Keypair::from_secret_key(SECP256K1, &sks[0]);
-
-        let sig_manager = generate_mock_manager(self_index, keypair);
-
-        let keypath_spend_only = false;
-        let txid = generate_mock_tx_signing_data(keypath_spend_only)
-            .psbt
-            .compute_txid();
-
-        let result1 = sig_manager.generate_sec_nonce(&txid, &key_agg_ctx);
-        let result2 = sig_manager.generate_sec_nonce(&txid, &key_agg_ctx);
-
-        assert_ne!(
-            result1, result2,
-            "should generate different sec nonces even for the same context"
-        );
-    }
-
     #[tokio::test]
     async fn test_get_own_nonce_keyspend() {
         test_get_own_nonce(true).await;
@@ -600,6 +560,74 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_add_tx_state_after_session_lost() {
+        let own_index = 0;
+        let (pks, sks) = generate_keypairs(2);
+        let pubkey_table = generate_pubkey_table(&pks);
+
+        let keypair = Keypair::from_secret_key(SECP256K1, &sks[own_index]);
+        let tx_signing_data = generate_mock_tx_signing_data(false);
+
+        let sig_manager = generate_mock_manager(own_index as OperatorIdx, keypair);
+        let txid = sig_manager
+            .add_tx_state(tx_signing_data.clone(), pubkey_table.clone())
+            .await
+            .unwrap();
+        let own_pubnonce = sig_manager.get_own_nonce(&txid).await.unwrap();
+
+        let other_sec_nonce = generate_sec_nonce(&txid, pks.clone(), sks[1], false);
+        sig_manager
+            .add_nonce(&txid, 1, &other_sec_nonce.public_nonce().into())
+            .await
+            .unwrap();
+
+        sig_manager
+            .add_tx_state(tx_signing_data.clone(), pubkey_table.clone())
+            .await
+            .unwrap();
+        let tx_state = sig_manager.get_tx_state(&txid).await.unwrap();
+        assert!(
+            tx_state.has_all_nonces(),
+            "should keep the state while the signer holds the session"
+        );
+
+        // restart the signer without its sessions
+        let sig_manager = SignatureManager::new(
+            sig_manager.db_ops.clone(),
+            own_index as OperatorIdx,
+            Arc::new(KeypairSigner::new(keypair)),
+        );
+
+        sig_manager
+            .add_tx_state(tx_signing_data, pubkey_table)
+            .await
+            .unwrap();
+        let tx_state = sig_manager.get_tx_state(&txid).await.unwrap();
+        let new_pubnonce = tx_state
+            .collected_nonces()
+            .get(&(own_index as OperatorIdx))
+            .expect("own nonce should be present");
+        assert_ne!(
+            *new_pubnonce, own_pubnonce,
+            "should share the nonce of a new session"
+        );
+        assert_eq!(
+            tx_state.collected_nonces().len(),
+            1,
+            "should restart the nonce round"
+        );
+
+        sig_manager
+            .add_nonce(&txid, 1, &other_sec_nonce.public_nonce().into())
+            .await
+            .unwrap();
+        sig_manager
+            .add_own_partial_sig(&txid)
+            .await
+            .expect("should sign with the new session");
+    }
+
     #[tokio::test]
     async fn test_get_aggregated_nonce_keyspend() {
         test_get_aggregated_nonce(true).await;
@@ -862,7 +890,7 @@ mod tests {
         let external_keypair = Keypair::from_secret_key(SECP256K1, &sks[external_index]);
         let external_signature = sign_state_partial(
             tx_state.pubkeys(),
-            tx_state.secnonce(),
+            &sec_nonces[own_index].clone().into(),
             &external_keypair,
             &agg_nonce,
             message.as_ref(),
@@ -938,7 +966,7 @@ mod tests {
         let random_message = Message::from_digest_slice(&random_message).unwrap();
         let invalid_external_signature = sign_state_partial(
             tx_state.pubkeys(),
-            tx_state.secnonce(),
+            &sec_nonces[external_index].clone().into(),
             &external_keypair,
             &agg_nonce,
             random_message.as_ref(),
@@ -1098,7 +1126,11 @@ mod tests {
     fn generate_mock_manager(self_index: u32, keypair: Keypair) -> SignatureManager {
         let db_ops = generate_mock_tx_state_ops(1);
 
-        SignatureManager::new(db_ops.into(), self_index, keypair)
+        SignatureManager::new(
+            db_ops.into(),
+            self_index,
+            Arc::new(KeypairSigner::new(keypair)),
+        )
     }
 
     async fn collect_nonces(
@@ -288,7 +288,8 @@ mod tests {
 
         let num_operators = 3;
         let own_index = 1;
-        let (sks, aggregated_nonce, tx_state) = setup(num_operators, own_index, keypath_spend_only);
+        let (sks, sec_nonce, aggregated_nonce, tx_state) =
+            setup(num_operators, own_index, keypath_spend_only);
         let txid = tx_state.unsigned_tx().compute_txid();
 
         // Step 1: Generate a partial signature
@@ -296,7 +297,7 @@ mod tests {
         let keypair = Keypair::from_secret_key(SECP256K1, &sks[own_index]);
         let partial_sig_result = sign_state_partial(
             tx_state.pubkeys(),
-            tx_state.secnonce(),
+            &sec_nonce.into(),
             &keypair,
             &aggregated_nonce,
             txid.as_byte_array(),
@@ -395,7 +396,7 @@ mod tests {
         num_operators: usize,
         own_index: usize,
         keypath_spend_only: bool,
-    ) -> (Vec<SecretKey>, AggNonce, BridgeTxState) {
+    ) -> (Vec<SecretKey>, SecNonce, AggNonce, BridgeTxState) {
         assert!(own_index.lt(&num_operators), "invalid own index set");
 
         let (pks, sks) = generate_keypairs(num_operators);
@@ -437,12 +438,8 @@ mod tests {
 
         let aggregated_nonce = pub_nonces.iter().sum();
 
-        let mut tx_state = BridgeTxState::new(
-            tx_output,
-            pubkey_table,
-            sec_nonces[own_index].clone().into(),
-        )
-        .expect("Failed to create TxState");
+        let mut tx_state =
+            BridgeTxState::new(tx_output, pubkey_table).expect("Failed to create TxState");
 
         let mut nonces_complete = false;
         let mut permuted_pub_nonces = pub_nonces.clone();
@@ -459,6 +456,11 @@ mod tests {
             "adding the final nonce should complete the collection"
         );
 
-        (sks, aggregated_nonce, tx_state)
+        (
+            sks,
+            sec_nonces[own_index].clone(),
+            aggregated_nonce,
+            tx_state,
+        )
     }
 }
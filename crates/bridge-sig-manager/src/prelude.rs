@@ -1,3 +1,3 @@
 //! Re-exports types and traits for convenience.
 
-pub use crate::{errors::*, manager::*};
+pub use crate::{errors::*, manager::*, signer::*};
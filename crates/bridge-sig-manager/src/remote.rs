@@ -0,0 +1,325 @@
+//! A [`BridgeSigner`] backed by a signing service listening on a Unix socket, and the server side
+//! to serve any [`BridgeSigner`] as such a service.
+
+use std::{
+    fmt, fs,
+    os::unix::fs::{DirBuilderExt, PermissionsExt},
+    path::Path,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use bitcoin::{
+    secp256k1::{Message, PublicKey},
+    Txid,
+};
+use jsonrpsee::{
+    core::{client::Client, RpcResult},
+    server::ServerHandle,
+    types::ErrorObjectOwned,
+};
+use musig2::AggNonce;
+use reth_ipc::{client::IpcClientBuilder, server::Builder as IpcServerBuilder};
+use strata_bridge_rpc_api::{StrataBridgeSignerApiClient, StrataBridgeSignerApiServer};
+use strata_primitives::{
+    bridge::{Musig2PartialSig, Musig2PubNonce, PublickeyTable},
+    buf::Buf32,
+    relay::types::BridgeMessage,
+};
+
+use crate::{
+    errors::{BridgeSigError, BridgeSigResult},
+    signer::BridgeSigner,
+};
+
+/// Error code returned by the signing service when the wrapped signer fails.
+const SIGNER_ERROR_CODE: i32 = -32000;
+
+/// Error code returned by the signing service when the wrapped signer has no open session for the
+/// transaction, so that the client can tell it apart from other failures.
+const SESSION_NOT_FOUND_CODE: i32 = -32001;
+
+/// A [`BridgeSigner`] that forwards every request to a signing service over a Unix socket.
+pub struct RemoteSigner {
+    client: Client,
+
+    /// The public key, fetched once on connecting since it does not change.
+    pubkey: PublicKey,
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "remote signer: {}", self.pubkey)
+    }
+}
+
+impl RemoteSigner {
+    /// Connects to the signing service listening on the socket at `path`.
+    pub async fn connect(path: &str) -> BridgeSigResult<Self> {
+        let client = IpcClientBuilder::default()
+            .build(path)
+            .await
+            .map_err(|e| BridgeSigError::Remote(e.to_string()))?;
+
+        let pubkey = client.get_pubkey().await.map_err(remote_err)?;
+
+        Ok(Self { client, pubkey })
+    }
+}
+
+#[async_trait]
+impl BridgeSigner for RemoteSigner {
+    fn pubkey(&self) -> PublicKey {
+        self.pubkey
+    }
+
+    async fn open_session(
+        &self,
+        txid: &Txid,
+        aggregated_pubkey: &PublicKey,
+    ) -> BridgeSigResult<Musig2PubNonce> {
+        self.client
+            .open_session(*txid, *aggregated_pubkey)
+            .await
+            .map_err(remote_err)
+    }
+
+    async fn sign_partial(
+        &self,
+        txid: &Txid,
+        pubkey_table: &PublickeyTable,
+        aggregated_nonce: &AggNonce,
+        message: &Message,
+        keypath_spend_only: bool,
+    ) -> BridgeSigResult<Musig2PartialSig> {
+        self.client
+            .sign_partial(
+                *txid,
+                pubkey_table.clone(),
+                aggregated_nonce.clone(),
+                Buf32::from(*message.as_ref()),
+                keypath_spend_only,
+            )
+            .await
+            .map_err(remote_err)
+    }
+
+    async fn sign_bridge_msg(&self, message: BridgeMessage) -> BridgeSigResult<BridgeMessage> {
+        self.client
+            .sign_bridge_msg(message)
+            .await
+            .map_err(remote_err)
+    }
+}
+
+fn remote_err(err: jsonrpsee::core::ClientError) -> BridgeSigError {
+    match err {
+        jsonrpsee::core::ClientError::Call(e) if e.code() == SESSION_NOT_FOUND_CODE => {
+            BridgeSigError::SessionNotFound
+        }
+        err => BridgeSigError::Remote(err.to_string()),
+    }
+}
+
+/// Serves a [`BridgeSigner`] through the [`StrataBridgeSignerApiServer`] RPCs.
+#[derive(Debug)]
+pub struct SignerRpc<S> {
+    signer: Arc<S>,
+}
+
+impl<S: BridgeSigner> SignerRpc<S> {
+    /// Creates a new [`SignerRpc`].
+    pub fn new(signer: Arc<S>) -> Self {
+        Self { signer }
+    }
+}
+
+#[async_trait]
+impl<S: BridgeSigner + 'static> StrataBridgeSignerApiServer for SignerRpc<S> {
+    async fn get_pubkey(&self) -> RpcResult<PublicKey> {
+        Ok(self.signer.pubkey())
+    }
+
+    async fn open_session(
+        &self,
+        txid: Txid,
+        aggregated_pubkey: PublicKey,
+    ) -> RpcResult<Musig2PubNonce> {
+        self.signer
+            .open_session(&txid, &aggregated_pubkey)
+            .await
+            .map_err(signer_err)
+    }
+
+    async fn sign_partial(
+        &self,
+        txid: Txid,
+        pubkey_table: PublickeyTable,
+        aggregated_nonce: AggNonce,
+        message: Buf32,
+        keypath_spend_only: bool,
+    ) -> RpcResult<Musig2PartialSig> {
+        let message = Message::from_digest(message.0);
+
+        self.signer
+            .sign_partial(
+                &txid,
+                &pubkey_table,
+                &aggregated_nonce,
+                &message,
+                keypath_spend_only,
+            )
+            .await
+            .map_err(signer_err)
+    }
+
+    async fn sign_bridge_msg(&self, message: BridgeMessage) -> RpcResult<BridgeMessage> {
+        self.signer
+            .sign_bridge_msg(message)
+            .await
+            .map_err(signer_err)
+    }
+}
+
+fn signer_err(err: BridgeSigError) -> ErrorObjectOwned {
+    let code = match err {
+        BridgeSigError::SessionNotFound => SESSION_NOT_FOUND_CODE,
+        _ => SIGNER_ERROR_CODE,
+    };
+
+    ErrorObjectOwned::owned::<()>(code, err.to_string(), None)
+}
+
+/// Starts serving the `signer` on a Unix socket at `path` that only the current user can connect
+/// to, since anyone able to connect can get the operator's signatures.
+pub async fn start_signer_server<S: BridgeSigner + 'static>(
+    path: &str,
+    signer: Arc<S>,
+) -> BridgeSigResult<ServerHandle> {
+    let rpc = SignerRpc::new(signer).into_rpc();
+
+    // The socket is created in a directory that only we can access and only moved into place once
+    // its permissions are restricted, so that nobody can connect to it in between.
+    let staging_dir = format!("{path}.staging");
+    let staging_path = Path::new(&staging_dir).join("signer.ipc");
+    let _ = fs::remove_dir_all(&staging_dir);
+    fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging_dir)
+        .map_err(io_err)?;
+
+    let handle = IpcServerBuilder::default()
+        .build(staging_path.to_string_lossy().into_owned())
+        .start(rpc)
+        .await
+        .map_err(|e| BridgeSigError::Remote(e.to_string()))?;
+
+    fs::set_permissions(&staging_path, fs::Permissions::from_mode(0o600)).map_err(io_err)?;
+    fs::rename(&staging_path, path).map_err(io_err)?;
+    fs::remove_dir(&staging_dir).map_err(io_err)?;
+
+    Ok(handle)
+}
+
+fn io_err(err: std::io::Error) -> BridgeSigError {
+    BridgeSigError::Remote(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::key::Keypair;
+    use musig2::{secp256k1::SECP256K1, KeyAggContext};
+    use strata_primitives::relay::util::verify_sig;
+    use strata_test_utils::bridge::{
+        generate_keypairs, generate_mock_tx_signing_data, generate_pubkey_table,
+    };
+
+    use super::*;
+    use crate::signer::KeypairSigner;
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let (pks, sks) = generate_keypairs(3);
+        let pubkey_table = generate_pubkey_table(&pks);
+        let key_agg_ctx = KeyAggContext::new(pks.clone()).unwrap();
+
+        let socket_dir = tempfile::tempdir().unwrap();
+        let socket_path = socket_dir.path().join("signer.ipc");
+        let socket_path = socket_path.to_str().unwrap();
+
+        let local = Arc::new(KeypairSigner::new(Keypair::from_secret_key(
+            SECP256K1, &sks[0],
+        )));
+        let server = start_signer_server(socket_path, local.clone())
+            .await
+            .expect("should start the signer server");
+
+        let remote = RemoteSigner::connect(socket_path)
+            .await
+            .expect("should connect to the signer server");
+        assert_eq!(remote.pubkey(), pks[0], "pubkeys should match");
+
+        let mode = fs::metadata(socket_path).unwrap().permissions().mode();
+        assert_eq!(
+            mode & 0o777,
+            0o600,
+            "socket should only be accessible to the owner"
+        );
+
+        let unsigned = BridgeMessage::new_unsigned(0, vec![1], vec![2]);
+        let signed = remote.sign_bridge_msg(unsigned).await.unwrap();
+        let x_only_pk = Buf32::from(pks[0].x_only_public_key().0.serialize());
+        assert!(verify_sig(
+            &x_only_pk,
+            signed.compute_id().inner(),
+            signed.signature()
+        ));
+
+        let txid = generate_mock_tx_signing_data(false).psbt.compute_txid();
+        let aggregated_pubkey: PublicKey = key_agg_ctx.aggregated_pubkey();
+        let pub_nonce = remote
+            .open_session(&txid, &aggregated_pubkey)
+            .await
+            .unwrap();
+        assert_eq!(
+            local.open_session(&txid, &aggregated_pubkey).await.unwrap(),
+            pub_nonce,
+            "session should be kept by the signer"
+        );
+
+        let aggregated_nonce: AggNonce = [pub_nonce.inner().clone()]
+            .into_iter()
+            .chain(
+                sks[1..]
+                    .iter()
+                    .map(|sk| musig2::SecNonce::build([2; 32]).with_seckey(*sk).build())
+                    .map(|sn| sn.public_nonce()),
+            )
+            .sum();
+
+        let message = Message::from_digest([3; 32]);
+        let remote_sig = remote
+            .sign_partial(&txid, &pubkey_table, &aggregated_nonce, &message, false)
+            .await
+            .unwrap();
+        musig2::verify_partial(
+            &key_agg_ctx,
+            *remote_sig.inner(),
+            &aggregated_nonce,
+            pks[0],
+            pub_nonce.inner(),
+            message.as_ref(),
+        )
+        .expect("remote signature should be valid");
+
+        let result = remote
+            .sign_partial(&txid, &pubkey_table, &aggregated_nonce, &message, false)
+            .await;
+        assert!(
+            result.is_err_and(|e| matches!(e, BridgeSigError::SessionNotFound)),
+            "session should be closed after signing"
+        );
+
+        server.stop().unwrap();
+    }
+}
@@ -0,0 +1,554 @@
+//! Defines the [`BridgeSigner`] that holds the operator's key, so that the rest of the bridge
+//! client never has to.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use bitcoin::{
+    bip32::Xpriv,
+    hashes::Hash,
+    key::{
+        rand::{rngs::OsRng, RngCore},
+        Keypair, Parity,
+    },
+    secp256k1::{Message, PublicKey, SecretKey, SECP256K1},
+    Txid,
+};
+use musig2::{AggNonce, SecNonce};
+use strata_db::entities::signer_session::SignerSession;
+use strata_primitives::{
+    bridge::{Musig2PartialSig, Musig2PubNonce, PublickeyTable},
+    buf::Buf32,
+    relay::{types::BridgeMessage, util::sign_msg_hash},
+};
+use strata_storage::ops::signer_session::SignerSessionOps;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::{
+    errors::{BridgeSigError, BridgeSigResult},
+    operations::sign_state_partial,
+};
+
+/// The maximum number of signing sessions that a [`KeypairSigner`] keeps open at a time.
+pub const MAX_OPEN_SESSIONS: usize = 1024;
+
+/// The time after which an open signing session of a [`KeypairSigner`] is dropped.
+pub const SESSION_EXPIRY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Produces the nonces and signatures that require the operator's key.
+///
+/// The secret nonces never leave the signer. Each one is kept in a signing session for the
+/// transaction it was generated for and is dropped as soon as it has been used, since signing
+/// twice with the same secret nonce leaks the key. A session may still be lost, for example if it
+/// expires or the signer restarts without persisting it, in which case signing fails with
+/// [`BridgeSigError::SessionNotFound`] and a new session has to be opened.
+#[async_trait]
+pub trait BridgeSigner: fmt::Debug + Send + Sync {
+    /// The public key of the operator, as registered in the operator table.
+    fn pubkey(&self) -> PublicKey;
+
+    /// Opens the MuSig2 session that signs the transaction with the given [`Txid`] under the
+    /// `aggregated_pubkey`, returning the public nonce of the session.
+    ///
+    /// If a session is already open for the transaction, its public nonce is returned again.
+    ///
+    /// Please refer to MuSig2 nonce generation section in
+    /// [BIP 327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki).
+    async fn open_session(
+        &self,
+        txid: &Txid,
+        aggregated_pubkey: &PublicKey,
+    ) -> BridgeSigResult<Musig2PubNonce>;
+
+    /// Produces the operator's MuSig2 partial signature of the sighash `message`, closing the
+    /// session of the transaction with the given [`Txid`].
+    ///
+    /// Please refer to MuSig2 signing section in
+    /// [BIP 327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki).
+    async fn sign_partial(
+        &self,
+        txid: &Txid,
+        pubkey_table: &PublickeyTable,
+        aggregated_nonce: &AggNonce,
+        message: &Message,
+        keypath_spend_only: bool,
+    ) -> BridgeSigResult<Musig2PartialSig>;
+
+    /// Signs the id of an unsigned [`BridgeMessage`], returning the signed message.
+    async fn sign_bridge_msg(&self, message: BridgeMessage) -> BridgeSigResult<BridgeMessage>;
+}
+
+/// A [`BridgeSigner`] that signs in-process with a [`Keypair`].
+///
+/// At most [`MAX_OPEN_SESSIONS`] sessions are kept open, each for at most [`SESSION_EXPIRY`].
+pub struct KeypairSigner {
+    keypair: Keypair,
+
+    /// The open signing sessions.
+    sessions: Mutex<HashMap<Txid, SignerSession>>,
+
+    /// Persists the open signing sessions so that they survive a restart, if set.
+    session_ops: Option<Arc<SignerSessionOps>>,
+}
+
+impl fmt::Debug for KeypairSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "keypair signer: {}", self.keypair.public_key())
+    }
+}
+
+impl KeypairSigner {
+    /// Creates a new [`KeypairSigner`] whose sessions are only kept in memory.
+    pub fn new(keypair: Keypair) -> Self {
+        Self {
+            keypair,
+            sessions: Mutex::new(HashMap::new()),
+            session_ops: None,
+        }
+    }
+
+    /// Creates a new [`KeypairSigner`] from the operator's wallet [`Xpriv`], negating the key if
+    /// needed so that its public key has an even parity.
+    pub fn from_wallet_xpriv(wallet_xpriv: &Xpriv) -> Self {
+        let mut keypair = wallet_xpriv.to_keypair(SECP256K1);
+
+        let (_, parity) = keypair.x_only_public_key();
+        if matches!(parity, Parity::Odd) {
+            let sk = SecretKey::from_keypair(&keypair).negate();
+            keypair = Keypair::from_secret_key(SECP256K1, &sk);
+        }
+
+        Self::new(keypair)
+    }
+
+    /// Persists the sessions of the signer with the given `session_ops`, restoring the sessions
+    /// that were left open before the last restart.
+    ///
+    /// Each session is persisted before its public nonce is handed out and deleted before its
+    /// secret nonce is used, so that a secret nonce can never be used twice.
+    pub async fn with_session_ops(
+        self,
+        session_ops: Arc<SignerSessionOps>,
+    ) -> BridgeSigResult<Self> {
+        let sessions: HashMap<Txid, SignerSession> = session_ops
+            .get_all_sessions_async()
+            .await?
+            .into_iter()
+            .map(|(txid, session)| (txid.into(), session))
+            .collect();
+
+        debug!(count = sessions.len(), "restored open signing sessions");
+
+        Ok(Self {
+            sessions: Mutex::new(sessions),
+            session_ops: Some(session_ops),
+            ..self
+        })
+    }
+
+    /// Drops the sessions that have been open for longer than [`SESSION_EXPIRY`].
+    async fn prune_expired_sessions(
+        &self,
+        sessions: &mut HashMap<Txid, SignerSession>,
+        now: u64,
+    ) -> BridgeSigResult<()> {
+        let expired = sessions
+            .iter()
+            .filter(|(_, session)| {
+                now.saturating_sub(session.opened_at()) >= SESSION_EXPIRY.as_secs()
+            })
+            .map(|(txid, _)| *txid)
+            .collect::<Vec<_>>();
+
+        for txid in expired {
+            warn!(%txid, "dropping expired signing session");
+
+            if let Some(session_ops) = &self.session_ops {
+                session_ops.delete_session_async(Buf32::from(txid)).await?;
+            }
+            sessions.remove(&txid);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BridgeSigner for KeypairSigner {
+    fn pubkey(&self) -> PublicKey {
+        self.keypair.public_key()
+    }
+
+    /// Opens a session with a random secret nonce.
+    ///
+    /// # Notes
+    ///
+    /// The entropy is pooled using the underlying operating system's
+    /// cryptographic-safe pseudo-random number generator with [`OsRng`].
+    ///
+    /// # Errors
+    ///
+    /// If [`MAX_OPEN_SESSIONS`] sessions are already open
+    /// ([`BridgeSigError::TooManySessions`]).
+    async fn open_session(
+        &self,
+        txid: &Txid,
+        aggregated_pubkey: &PublicKey,
+    ) -> BridgeSigResult<Musig2PubNonce> {
+        let mut sessions = self.sessions.lock().await;
+
+        let now = unix_now();
+        self.prune_expired_sessions(&mut sessions, now).await?;
+
+        if let Some(session) = sessions.get(txid) {
+            return Ok(session.pubnonce());
+        }
+
+        if sessions.len() >= MAX_OPEN_SESSIONS {
+            return Err(BridgeSigError::TooManySessions);
+        }
+
+        let mut nonce_seed = [0u8; 32];
+        OsRng.fill_bytes(&mut nonce_seed);
+
+        let seckey = SecretKey::from_keypair(&self.keypair);
+
+        let sec_nonce = SecNonce::build(nonce_seed)
+            .with_seckey(seckey)
+            .with_message(txid.as_byte_array())
+            .with_aggregated_pubkey(*aggregated_pubkey)
+            .build();
+        let session = SignerSession::new(sec_nonce.into(), now);
+
+        // The session is persisted before its nonce is shared so that it can still be signed
+        // with after a restart.
+        if let Some(session_ops) = &self.session_ops {
+            session_ops
+                .put_session_async(Buf32::from(txid), session.clone())
+                .await?;
+        }
+
+        let pub_nonce = session.pubnonce();
+        sessions.insert(*txid, session);
+
+        Ok(pub_nonce)
+    }
+
+    async fn sign_partial(
+        &self,
+        txid: &Txid,
+        pubkey_table: &PublickeyTable,
+        aggregated_nonce: &AggNonce,
+        message: &Message,
+        keypath_spend_only: bool,
+    ) -> BridgeSigResult<Musig2PartialSig> {
+        // The session is closed before signing so that its nonce can never be used twice, even
+        // if signing fails or the signer restarts.
+        let session = {
+            let mut sessions = self.sessions.lock().await;
+            let session = sessions
+                .remove(txid)
+                .ok_or(BridgeSigError::SessionNotFound)?;
+
+            if let Some(session_ops) = &self.session_ops {
+                session_ops.delete_session_async(Buf32::from(txid)).await?;
+            }
+
+            session
+        };
+
+        let signature = sign_state_partial(
+            pubkey_table,
+            session.secnonce(),
+            &self.keypair,
+            aggregated_nonce,
+            message.as_ref(),
+            keypath_spend_only,
+        )?;
+
+        Ok(signature.into())
+    }
+
+    async fn sign_bridge_msg(&self, message: BridgeMessage) -> BridgeSigResult<BridgeMessage> {
+        let msg_id = message.compute_id();
+        let sig = sign_msg_hash(&self.keypair.secret_key().into(), msg_id.inner());
+
+        Ok(message.with_signature(sig))
+    }
+}
+
+/// Get the current time in seconds since the UNIX epoch.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should be after the UNIX epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::Network;
+    use musig2::KeyAggContext;
+    use strata_primitives::relay::util::verify_sig;
+    use strata_test_utils::bridge::{
+        generate_keypairs, generate_mock_signer_session_ops, generate_mock_tx_signing_data,
+        generate_pubkey_table, generate_sec_nonce,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_signing_session() {
+        let (pks, sks) = generate_keypairs(2);
+        let pubkey_table = generate_pubkey_table(&pks);
+
+        let key_agg_ctx = KeyAggContext::new(pubkey_table.0.values().copied())
+            .expect("should be able to create key aggregation context");
+        let aggregated_pubkey: PublicKey = key_agg_ctx.aggregated_pubkey();
+
+        let signer = KeypairSigner::new(Keypair::from_secret_key(SECP256K1, &sks[0]));
+
+        let keypath_spend_only = false;
+        let txid = generate_mock_tx_signing_data(keypath_spend_only)
+            .psbt
+            .compute_txid();
+
+        let pub_nonce = signer
+            .open_session(&txid, &aggregated_pubkey)
+            .await
+            .unwrap();
+        assert_eq!(
+            signer
+                .open_session(&txid, &aggregated_pubkey)
+                .await
+                .unwrap(),
+            pub_nonce,
+            "should return the nonce of the session that is already open"
+        );
+
+        let other_sec_nonce = generate_sec_nonce(&txid, pks.clone(), sks[1], keypath_spend_only);
+        let aggregated_nonce: AggNonce =
+            [pub_nonce.inner().clone(), other_sec_nonce.public_nonce()]
+                .into_iter()
+                .sum();
+        let message = Message::from_digest([1; 32]);
+
+        signer
+            .sign_partial(
+                &txid,
+                &pubkey_table,
+                &aggregated_nonce,
+                &message,
+                keypath_spend_only,
+            )
+            .await
+            .expect("should sign with the open session");
+
+        let result = signer
+            .sign_partial(
+                &txid,
+                &pubkey_table,
+                &aggregated_nonce,
+                &Message::from_digest([2; 32]),
+                keypath_spend_only,
+            )
+            .await;
+        assert!(
+            result.is_err_and(|e| matches!(e, BridgeSigError::SessionNotFound)),
+            "should not sign twice with the same nonce"
+        );
+
+        assert_ne!(
+            signer
+                .open_session(&txid, &aggregated_pubkey)
+                .await
+                .unwrap(),
+            pub_nonce,
+            "should generate a fresh nonce once the session is closed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_sessions() {
+        let (pks, sks) = generate_keypairs(2);
+        let pubkey_table = generate_pubkey_table(&pks);
+        let keypair = Keypair::from_secret_key(SECP256K1, &sks[0]);
+
+        let key_agg_ctx = KeyAggContext::new(pubkey_table.0.values().copied()).unwrap();
+        let aggregated_pubkey: PublicKey = key_agg_ctx.aggregated_pubkey();
+
+        let session_ops = Arc::new(generate_mock_signer_session_ops(1));
+        let signer = KeypairSigner::new(keypair)
+            .with_session_ops(session_ops.clone())
+            .await
+            .unwrap();
+
+        let txid = generate_mock_tx_signing_data(false).psbt.compute_txid();
+        let pub_nonce = signer
+            .open_session(&txid, &aggregated_pubkey)
+            .await
+            .unwrap();
+
+        // restart the signer
+        let signer = KeypairSigner::new(keypair)
+            .with_session_ops(session_ops.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            signer
+                .open_session(&txid, &aggregated_pubkey)
+                .await
+                .unwrap(),
+            pub_nonce,
+            "should restore the session that was open before the restart"
+        );
+
+        let other_sec_nonce = generate_sec_nonce(&txid, pks.clone(), sks[1], false);
+        let aggregated_nonce: AggNonce =
+            [pub_nonce.inner().clone(), other_sec_nonce.public_nonce()]
+                .into_iter()
+                .sum();
+        let message = Message::from_digest([1; 32]);
+
+        let signature = signer
+            .sign_partial(&txid, &pubkey_table, &aggregated_nonce, &message, false)
+            .await
+            .expect("should sign with the restored session");
+        musig2::verify_partial(
+            &key_agg_ctx,
+            *signature.inner(),
+            &aggregated_nonce,
+            pks[0],
+            pub_nonce.inner(),
+            message.as_ref(),
+        )
+        .expect("signature should be valid");
+
+        // restart the signer again
+        let signer = KeypairSigner::new(keypair)
+            .with_session_ops(session_ops)
+            .await
+            .unwrap();
+        let result = signer
+            .sign_partial(&txid, &pubkey_table, &aggregated_nonce, &message, false)
+            .await;
+        assert!(
+            result.is_err_and(|e| matches!(e, BridgeSigError::SessionNotFound)),
+            "should not restore a session that has been signed with"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_limit_and_expiry() {
+        let (pks, sks) = generate_keypairs(1);
+        let aggregated_pubkey = pks[0];
+
+        let session_ops = Arc::new(generate_mock_signer_session_ops(1));
+        let signer = KeypairSigner::new(Keypair::from_secret_key(SECP256K1, &sks[0]))
+            .with_session_ops(session_ops.clone())
+            .await
+            .unwrap();
+
+        let txids = (0..=MAX_OPEN_SESSIONS)
+            .map(|i| {
+                let mut txid = [0u8; 32];
+                txid[..8].copy_from_slice(&(i as u64).to_le_bytes());
+
+                Txid::from_byte_array(txid)
+            })
+            .collect::<Vec<_>>();
+
+        for txid in &txids[..MAX_OPEN_SESSIONS] {
+            signer
+                .open_session(txid, &aggregated_pubkey)
+                .await
+                .expect("should open session below the limit");
+        }
+
+        let result = signer
+            .open_session(&txids[MAX_OPEN_SESSIONS], &aggregated_pubkey)
+            .await;
+        assert!(
+            result.is_err_and(|e| matches!(e, BridgeSigError::TooManySessions)),
+            "should not open more sessions than the limit"
+        );
+
+        // expire the first session
+        let expired = SignerSession::new(
+            generate_sec_nonce(&txids[0], pks.clone(), sks[0], false).into(),
+            unix_now() - SESSION_EXPIRY.as_secs(),
+        );
+        signer
+            .sessions
+            .lock()
+            .await
+            .insert(txids[0], expired.clone());
+        session_ops
+            .put_session_async(Buf32::from(txids[0]), expired)
+            .await
+            .unwrap();
+
+        signer
+            .open_session(&txids[MAX_OPEN_SESSIONS], &aggregated_pubkey)
+            .await
+            .expect("should open session once an expired one is dropped");
+
+        let stored = session_ops.get_all_sessions_async().await.unwrap();
+        assert_eq!(
+            stored.len(),
+            MAX_OPEN_SESSIONS,
+            "expired session should be deleted from storage"
+        );
+        assert!(
+            stored
+                .iter()
+                .all(|(txid, _)| *txid != Buf32::from(txids[0])),
+            "expired session should not be stored"
+        );
+    }
+
+    #[test]
+    fn test_from_wallet_xpriv_even_parity() {
+        for seed in 0..8u8 {
+            let xpriv = Xpriv::new_master(Network::Regtest, &[seed; 32]).unwrap();
+            let signer = KeypairSigner::from_wallet_xpriv(&xpriv);
+
+            let (_, parity) = signer.pubkey().x_only_public_key();
+            assert_eq!(parity, Parity::Even, "pubkey should have an even parity");
+
+            let (xonly, _) = xpriv.to_keypair(SECP256K1).x_only_public_key();
+            assert_eq!(
+                signer.pubkey().x_only_public_key().0,
+                xonly,
+                "should have the same x-only pubkey as the xpriv"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_bridge_msg() {
+        let (_, sks) = generate_keypairs(1);
+        let signer = KeypairSigner::new(Keypair::from_secret_key(SECP256K1, &sks[0]));
+
+        let unsigned = BridgeMessage::new_unsigned(0, vec![1], vec![2, 3]);
+        let signed = signer.sign_bridge_msg(unsigned.clone()).await.unwrap();
+        assert_eq!(
+            signed.compute_id().inner(),
+            unsigned.compute_id().inner(),
+            "signing should not change the message"
+        );
+
+        let pubkey = Buf32::from(signer.pubkey().x_only_public_key().0.serialize());
+        assert!(verify_sig(
+            &pubkey,
+            signed.compute_id().inner(),
+            signed.signature()
+        ));
+    }
+}
@@ -1,6 +1,5 @@
 //! Constants related to bridge transactions.
 
-use bitcoin::Amount;
 use strata_primitives::l1::BitcoinAmount;
 
 /// The value of each UTXO in the Bridge Multisig Address.
@@ -14,10 +13,19 @@ pub const BRIDGE_DENOMINATION: BitcoinAmount = BitcoinAmount::from_int_btc(10);
 /// transaction computes to ~5.5 sats/vB (run integration tests with `RUST_LOG=warn` to verify).
 pub const MIN_RELAY_FEE: BitcoinAmount = BitcoinAmount::from_sat(10);
 
-/// The fee charged by the operator to process a withdrawal.
+/// The fee charged by the operator to process a withdrawal, in basis points of the withdrawn
+/// amount.
 ///
-/// This has the type [`Amount`] for convenience.
-pub const OPERATOR_FEE: Amount = Amount::from_sat(BRIDGE_DENOMINATION.to_sat() / 20); // 5%
+/// The operator fee also pays for the fees of the withdrawal transaction.
+pub const OPERATOR_FEE_BPS: u64 = 500; // 5%
 
 /// Magic bytes to add to the metadata output in transactions to help identify them.
 pub const MAGIC_BYTES: &[u8; 11] = b"alpenstrata";
+
+/// Tag following the [`MAGIC_BYTES`] in the metadata output of the transaction with which an
+/// operator pays out a withdrawal from its own wallet.
+pub const WITHDRAWAL_FULFILLMENT_TAG: u8 = 0x01;
+
+/// Tag following the [`MAGIC_BYTES`] in the metadata output of the transaction that reimburses an
+/// operator from the deposit UTXO for fulfilling a withdrawal.
+pub const DEPOSIT_REIMBURSEMENT_TAG: u8 = 0x02;
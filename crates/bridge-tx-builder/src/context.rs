@@ -21,6 +21,13 @@ pub trait BuildContext {
     /// the withdrawal request of the user and pays some fees to the assigned operator.
     fn pubkey_table(&self) -> &PublickeyTable;
 
+    /// Get the pubkey table of the operators that were removed from the active set but may still
+    /// be notaries of deposits.
+    ///
+    /// These are only needed to spend the deposits locked to them, so they aren't part of the
+    /// aggregated pubkey.
+    fn retired_pubkey_table(&self) -> &PublickeyTable;
+
     /// Get the aggregated MuSig2 x-only pubkey used in the spending condition of the multisig.
     fn aggregated_pubkey(&self) -> XOnlyPublicKey;
 
@@ -40,6 +47,10 @@ pub struct TxBuildContext {
     /// A table that maps bridge operator indexes to their respective x-only Schnorr pubkeys.
     pubkey_table: PublickeyTable,
 
+    /// The same as `pubkey_table` for the retired operators that may still be notaries of
+    /// deposits.
+    retired_pubkey_table: PublickeyTable,
+
     /// The aggregated pubkey computed for the [`PublickeyTable`].
     ///
     /// This is fixed for the given [`PublickeyTable`] and so we compute it only once.
@@ -58,10 +69,17 @@ impl TxBuildContext {
         Self {
             network,
             pubkey_table: operator_pubkeys,
+            retired_pubkey_table: PublickeyTable::default(),
             aggregated_pubkey,
             own_index,
         }
     }
+
+    /// Sets the pubkeys of the retired operators that may still be notaries of deposits.
+    pub fn with_retired_pubkeys(mut self, retired_pubkeys: PublickeyTable) -> Self {
+        self.retired_pubkey_table = retired_pubkeys;
+        self
+    }
 }
 
 impl BuildContext for TxBuildContext {
@@ -70,6 +88,11 @@ impl BuildContext for TxBuildContext {
         &self.pubkey_table
     }
 
+    /// Get the retired operators' pubkey table.
+    fn retired_pubkey_table(&self) -> &PublickeyTable {
+        &self.retired_pubkey_table
+    }
+
     /// Get the aggregated operator pubkeys.
     fn aggregated_pubkey(&self) -> XOnlyPublicKey {
         self.aggregated_pubkey
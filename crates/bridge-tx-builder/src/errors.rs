@@ -3,6 +3,7 @@
 use bitcoin::{
     psbt,
     taproot::{TaprootBuilder, TaprootBuilderError},
+    Amount,
 };
 use strata_primitives::{bridge::OperatorIdx, errors::ParseError};
 use thiserror::Error;
@@ -18,6 +19,10 @@ pub enum BridgeTxBuilderError {
     #[error("could not build cooperative withdrawal transaction: {0}")]
     CooperativeWithdrawalTransaction(#[from] CooperativeWithdrawalError),
 
+    /// Error building the Reimbursement Transaction.
+    #[error("could not build deposit reimbursement transaction: {0}")]
+    DepositReimbursementTransaction(#[from] DepositReimbursementError),
+
     /// Error due to there being no script provided to create a taproot address.
     #[error("noscript taproot address for only script path spend is not possible")]
     EmptyTapscript,
@@ -92,4 +97,40 @@ pub enum CooperativeWithdrawalError {
     /// The supplied assigned operator id is not part of the federation
     #[error("operator idx {0} is not part of federation")]
     Unauthorized(OperatorIdx),
+
+    /// An operator that notarized the deposit is missing from the pubkey table.
+    #[error("notary operator idx {0} is missing from the pubkey table")]
+    MissingNotary(OperatorIdx),
+
+    /// The withdrawal has no outputs to pay out to.
+    #[error("withdrawal has no outputs")]
+    NoOutputs,
+
+    /// The total value of the withdrawal outputs exceeds the deposit it spends.
+    #[error("withdrawal total {0} exceeds the deposit amount {1}")]
+    ExceedsDeposit(Amount, Amount),
+
+    /// The operator fees do not cover the fees of the withdrawal transaction.
+    #[error("operator fee {0} does not cover the transaction fees {1}")]
+    InsufficientFees(Amount, Amount),
+}
+
+/// Error while creating the transaction that reimburses an operator from the deposit.
+#[derive(Debug, Clone, Error)]
+pub enum DepositReimbursementError {
+    /// The operator to be reimbursed is not part of the federation.
+    #[error("operator idx {0} is not part of federation")]
+    Unauthorized(OperatorIdx),
+
+    /// An operator that notarized the deposit is missing from the pubkey table.
+    #[error("notary operator idx {0} is missing from the pubkey table")]
+    MissingNotary(OperatorIdx),
+
+    /// The total value of the fulfilled withdrawal exceeds the deposit it is reimbursed from.
+    #[error("withdrawal total {0} exceeds the deposit amount {1}")]
+    ExceedsDeposit(Amount, Amount),
+
+    /// The operator fees do not cover the fees of the reimbursement transaction.
+    #[error("operator fee {0} does not cover the transaction fees {1}")]
+    InsufficientFees(Amount, Amount),
 }
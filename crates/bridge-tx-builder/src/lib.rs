@@ -9,11 +9,12 @@ pub mod deposit;
 pub mod errors;
 pub mod operations;
 pub mod prelude;
+pub mod reimbursement;
 pub mod withdrawal;
 
 use context::BuildContext;
 use errors::BridgeTxBuilderResult;
-use strata_primitives::bridge::TxSigningData;
+use strata_primitives::bridge::{PublickeyTable, TxSigningData};
 
 /// Defines a method that any bridge transaction must implement in order to create a
 /// structure that can be signed.
@@ -26,4 +27,12 @@ pub trait TxKind {
         &self,
         build_context: &C,
     ) -> BridgeTxBuilderResult<TxSigningData>;
+
+    /// Get the pubkeys of the operators that must sign the transaction.
+    ///
+    /// These are all the operators in the build context unless the transaction spends a UTXO
+    /// locked to some other set of operators.
+    fn signers<C: BuildContext>(&self, build_context: &C) -> BridgeTxBuilderResult<PublickeyTable> {
+        Ok(build_context.pubkey_table().clone())
+    }
 }
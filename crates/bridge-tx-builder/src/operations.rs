@@ -3,6 +3,7 @@
 
 use bitcoin::{
     absolute::LockTime,
+    hashes::Hash,
     key::UntweakedPublicKey,
     opcodes::{
         all::{OP_CHECKSIG, OP_RETURN},
@@ -12,12 +13,18 @@ use bitcoin::{
     secp256k1::{PublicKey, XOnlyPublicKey, SECP256K1},
     taproot::{TaprootBuilder, TaprootSpendInfo},
     transaction, Address, Amount, Network, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
-    Witness,
+    Txid, Witness,
 };
 use musig2::KeyAggContext;
-use strata_primitives::{bridge::PublickeyTable, constants::UNSPENDABLE_PUBLIC_KEY};
+use strata_primitives::{
+    bridge::{OperatorIdx, PublickeyTable},
+    constants::UNSPENDABLE_PUBLIC_KEY,
+};
 
-use super::{constants::MAGIC_BYTES, errors::BridgeTxBuilderError};
+use super::{
+    constants::{DEPOSIT_REIMBURSEMENT_TAG, MAGIC_BYTES, WITHDRAWAL_FULFILLMENT_TAG},
+    errors::BridgeTxBuilderError,
+};
 use crate::errors::BridgeTxBuilderResult;
 
 /// Create a script with the spending condition that a MuSig2 aggregated signature corresponding to
@@ -56,6 +63,61 @@ pub fn metadata_script(el_address: &[u8; 20]) -> ScriptBuf {
         .into_script()
 }
 
+/// Create the metadata script that links a withdrawal fulfillment transaction to the deposit whose
+/// withdrawal it pays out and to the operator that pays it out, who is the one to be reimbursed.
+pub fn withdrawal_fulfillment_script(
+    deposit_outpoint: &OutPoint,
+    operator_idx: OperatorIdx,
+) -> ScriptBuf {
+    cooperative_withdrawal_script(deposit_outpoint, operator_idx, None)
+}
+
+/// Create the metadata script for a withdrawal paid out of the deposit itself, which additionally
+/// commits to the output that returns the rest of the deposit to the bridge, if there is one.
+pub fn cooperative_withdrawal_script(
+    deposit_outpoint: &OutPoint,
+    operator_idx: OperatorIdx,
+    change_vout: Option<u32>,
+) -> ScriptBuf {
+    let mut data = PushBytesBuf::new();
+    data.extend_from_slice(MAGIC_BYTES)
+        .expect("MAGIC_BYTES should be within the limit");
+    data.push(WITHDRAWAL_FULFILLMENT_TAG)
+        .expect("tag should be within the limit");
+    data.extend_from_slice(&deposit_outpoint.txid.to_byte_array())
+        .expect("txid should be within the limit");
+    data.extend_from_slice(&deposit_outpoint.vout.to_be_bytes())
+        .expect("vout should be within the limit");
+    data.extend_from_slice(&operator_idx.to_be_bytes())
+        .expect("operator idx should be within the limit");
+    if let Some(change_vout) = change_vout {
+        data.extend_from_slice(&change_vout.to_be_bytes())
+            .expect("change vout should be within the limit");
+    }
+
+    Builder::new()
+        .push_opcode(OP_RETURN)
+        .push_slice(data)
+        .into_script()
+}
+
+/// Create the metadata script that links a reimbursement transaction to the withdrawal fulfillment
+/// transaction it reimburses.
+pub fn deposit_reimbursement_script(fulfillment_txid: &Txid) -> ScriptBuf {
+    let mut data = PushBytesBuf::new();
+    data.extend_from_slice(MAGIC_BYTES)
+        .expect("MAGIC_BYTES should be within the limit");
+    data.push(DEPOSIT_REIMBURSEMENT_TAG)
+        .expect("tag should be within the limit");
+    data.extend_from_slice(&fulfillment_txid.to_byte_array())
+        .expect("txid should be within the limit");
+
+    Builder::new()
+        .push_opcode(OP_RETURN)
+        .push_slice(data)
+        .into_script()
+}
+
 /// Different spending paths for a taproot.
 ///
 /// It can be a key path spend, a script path spend or both.
@@ -1,3 +1,5 @@
 //! Re-exports types and traits for convenience.
 
-pub use crate::{constants::*, context::*, deposit::*, errors::*, operations::*, withdrawal::*};
+pub use crate::{
+    constants::*, context::*, deposit::*, errors::*, operations::*, reimbursement::*, withdrawal::*,
+};
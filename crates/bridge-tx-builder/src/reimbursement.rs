@@ -0,0 +1,509 @@
+//! Provides types/traits associated with reimbursing operators for the withdrawals they fulfill.
+//!
+//! The operator assigned a withdrawal pays out the users from its own wallet first, with the
+//! fulfillment transaction from [`crate::withdrawal`]. Once the payout is seen on chain, the
+//! federation sweeps the deposit UTXO to the operator with the transaction built here.
+//!
+//! The deposit UTXO is locked to the operators that notarized the deposit, which need not be the
+//! current operator set, so the transaction is built and signed in the context of those operators.
+//! Any change goes back to the same operators as the second output of the transaction.
+
+use std::collections::BTreeMap;
+
+use bitcoin::{Amount, FeeRate, OutPoint, Psbt, Transaction, TxOut, Txid};
+use serde::{Deserialize, Serialize};
+use strata_primitives::{
+    bridge::{OperatorIdx, PublickeyTable, TxSigningData},
+    l1::{BitcoinPsbt, TaprootSpendPath},
+};
+
+use crate::{
+    context::{BuildContext, TxBuildContext},
+    errors::{BridgeTxBuilderResult, DepositReimbursementError},
+    prelude::{
+        anyone_can_spend_txout, create_taproot_addr, create_tx, create_tx_ins, create_tx_outs,
+        deposit_reimbursement_script, SpendPath, MIN_RELAY_FEE,
+    },
+    withdrawal::{bridge_script_pubkey, operator_fee},
+    TxKind,
+};
+
+/// Details required to reimburse an operator from a deposit for a withdrawal it has fulfilled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReimbursementInfo {
+    /// The [`OutPoint`] of the UTXO in the Bridge Address that the withdrawal was assigned
+    /// against.
+    deposit_outpoint: OutPoint,
+
+    /// The amount locked in the deposit UTXO.
+    deposit_amount: Amount,
+
+    /// The indexes of the operators that notarized the deposit, to whose aggregated key the
+    /// deposit UTXO is locked.
+    notary_operators: Vec<OperatorIdx>,
+
+    /// The total value of the withdrawal, including the operator fee.
+    withdrawal_total: Amount,
+
+    /// The index of the operator that fulfilled the withdrawal.
+    assigned_operator_idx: OperatorIdx,
+
+    /// The ID of the transaction with which the operator paid out the withdrawal.
+    fulfillment_txid: Txid,
+}
+
+impl TxKind for ReimbursementInfo {
+    fn construct_signing_data<C: BuildContext>(
+        &self,
+        build_context: &C,
+    ) -> BridgeTxBuilderResult<TxSigningData> {
+        let notary_context = TxBuildContext::new(
+            *build_context.network(),
+            self.signers(build_context)?,
+            build_context.own_index(),
+        );
+
+        let prevout = self.create_prevout(&notary_context)?;
+        let unsigned_tx = self.create_unsigned_tx(&notary_context, prevout.value)?;
+
+        let mut psbt = Psbt::from_unsigned_tx(unsigned_tx)?;
+
+        psbt.inputs
+            .get_mut(0)
+            .expect("reimbursement tx is guaranteed to have one UTXO -- the deposit")
+            .witness_utxo = Some(prevout);
+
+        let psbt = BitcoinPsbt::from(psbt);
+
+        Ok(TxSigningData {
+            psbt,
+            spend_path: TaprootSpendPath::Key,
+        })
+    }
+
+    fn signers<C: BuildContext>(&self, build_context: &C) -> BridgeTxBuilderResult<PublickeyTable> {
+        let pubkey_table = build_context.pubkey_table();
+        let retired_pubkey_table = build_context.retired_pubkey_table();
+
+        // The notaries may have been removed from the active set since the deposit was made.
+        let notary_pubkeys = self
+            .notary_operators
+            .iter()
+            .map(
+                |idx| match pubkey_table.0.get(idx).or(retired_pubkey_table.0.get(idx)) {
+                    Some(pubkey) => Ok((*idx, *pubkey)),
+                    None => Err(DepositReimbursementError::MissingNotary(*idx)),
+                },
+            )
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        Ok(notary_pubkeys.into())
+    }
+}
+
+impl ReimbursementInfo {
+    /// Create a new reimbursement request.
+    pub fn new(
+        deposit_outpoint: OutPoint,
+        deposit_amount: Amount,
+        notary_operators: Vec<OperatorIdx>,
+        withdrawal_total: Amount,
+        assigned_operator_idx: OperatorIdx,
+        fulfillment_txid: Txid,
+    ) -> Self {
+        Self {
+            deposit_outpoint,
+            deposit_amount,
+            notary_operators,
+            withdrawal_total,
+            assigned_operator_idx,
+            fulfillment_txid,
+        }
+    }
+
+    /// Get the outpoint of the deposit UTXO that the reimbursement spends.
+    pub fn deposit_outpoint(&self) -> OutPoint {
+        self.deposit_outpoint
+    }
+
+    /// Get the amount locked in the deposit UTXO.
+    pub fn deposit_amount(&self) -> Amount {
+        self.deposit_amount
+    }
+
+    /// Get the indexes of the operators that notarized the deposit.
+    pub fn notary_operators(&self) -> &[OperatorIdx] {
+        &self.notary_operators
+    }
+
+    /// Get the total value of the fulfilled withdrawal.
+    pub fn withdrawal_total(&self) -> Amount {
+        self.withdrawal_total
+    }
+
+    /// Get the index of the operator that is to be reimbursed.
+    pub fn assigned_operator_idx(&self) -> OperatorIdx {
+        self.assigned_operator_idx
+    }
+
+    /// Get the ID of the transaction with which the operator paid out the withdrawal.
+    pub fn fulfillment_txid(&self) -> Txid {
+        self.fulfillment_txid
+    }
+
+    fn create_prevout<T: BuildContext>(&self, build_context: &T) -> BridgeTxBuilderResult<TxOut> {
+        Ok(TxOut {
+            value: self.deposit_amount,
+            script_pubkey: bridge_script_pubkey(build_context)?,
+        })
+    }
+
+    fn create_unsigned_tx<T: BuildContext>(
+        &self,
+        build_context: &T,
+        total_amount: Amount,
+    ) -> BridgeTxBuilderResult<Transaction> {
+        if self.withdrawal_total > total_amount {
+            return Err(DepositReimbursementError::ExceedsDeposit(
+                self.withdrawal_total,
+                total_amount,
+            ))?;
+        }
+
+        let tx_ins = create_tx_ins([self.deposit_outpoint]);
+
+        // create the output that reimburses the operator
+        let pubkey_table = build_context.pubkey_table();
+        let Some(operator_pubkey) = pubkey_table.0.get(&self.assigned_operator_idx) else {
+            return Err(DepositReimbursementError::Unauthorized(
+                self.assigned_operator_idx,
+            ))?;
+        };
+
+        let spend_path = SpendPath::KeySpend {
+            internal_key: operator_pubkey.x_only_public_key().0,
+        };
+
+        let (operator_addr, _) = create_taproot_addr(build_context.network(), spend_path)?;
+        let operator_script_pubkey = operator_addr.script_pubkey();
+
+        // create the `anyone can spend` output for CPFP
+        let anyone_can_spend_out = anyone_can_spend_txout();
+
+        // The operator gets back what it paid out to the users along with its fee.
+        let mut reimbursement = self.withdrawal_total;
+
+        // Whatever is left of the deposit goes back to the bridge address as change, unless it is
+        // dust in which case it goes to the operator.
+        let bridge_script_pubkey = bridge_script_pubkey(build_context)?;
+        let change_amount = total_amount - self.withdrawal_total;
+        let change_out = if change_amount >= bridge_script_pubkey.minimal_non_dust() {
+            Some((bridge_script_pubkey, change_amount))
+        } else {
+            reimbursement += change_amount;
+            None
+        };
+
+        // The operator fee pays for the entire transaction, same as in the cooperative withdrawal.
+        let fee_rate = FeeRate::from_sat_per_vb(MIN_RELAY_FEE.to_sat())
+            .expect("MIN_RELAY_FEE should be set correctly");
+        let tx_fee = operator_script_pubkey.minimal_non_dust_custom(fee_rate);
+
+        let total_fees = anyone_can_spend_out.value + tx_fee;
+        let operator_fee = operator_fee(self.withdrawal_total);
+        if operator_fee < total_fees {
+            return Err(DepositReimbursementError::InsufficientFees(
+                operator_fee,
+                total_fees,
+            ))?;
+        }
+
+        let mut outputs = Vec::with_capacity(4);
+        outputs.push((operator_script_pubkey, reimbursement - total_fees)); // reimbursement
+        outputs.extend(change_out); // change back to the bridge
+        outputs.push((
+            deposit_reimbursement_script(&self.fulfillment_txid),
+            Amount::ZERO,
+        ));
+        outputs.push((
+            anyone_can_spend_out.script_pubkey,
+            anyone_can_spend_out.value,
+        )); // anyone can spend for CPFP
+
+        let tx_outs = create_tx_outs(outputs);
+
+        let unsigned_tx = create_tx(tx_ins, tx_outs);
+
+        Ok(unsigned_tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bitcoin::{
+        hashes::{sha256d, Hash},
+        Amount, Network, OutPoint, Txid,
+    };
+    use strata_primitives::{bridge::OperatorIdx, l1::TaprootSpendPath};
+    use strata_test_utils::bridge::{generate_keypairs, generate_pubkey_table};
+
+    use crate::{
+        context::TxBuildContext,
+        errors::{BridgeTxBuilderError, DepositReimbursementError},
+        prelude::{deposit_reimbursement_script, ReimbursementInfo, BRIDGE_DENOMINATION},
+        TxKind,
+    };
+
+    fn fulfillment_txid() -> Txid {
+        Txid::from_raw_hash(sha256d::Hash::hash(&[0xffu8; 32]))
+    }
+
+    #[test]
+    fn test_construct_signing_data_success() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(3);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[1u8; 32])), 1);
+
+        let assigned_operator_idx = 2 as OperatorIdx;
+        let reimbursement_info = ReimbursementInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1, 2],
+            BRIDGE_DENOMINATION.into(),
+            assigned_operator_idx,
+            fulfillment_txid(),
+        );
+
+        let build_context = TxBuildContext::new(Network::Regtest, pubkey_table, 0);
+
+        // Act
+        let signing_data = reimbursement_info
+            .construct_signing_data(&build_context)
+            .expect("should be able to construct TxSigningData");
+
+        // Assert
+        let psbt = signing_data.psbt.inner();
+        assert_eq!(
+            psbt.inputs.len(),
+            1,
+            "reimbursement psbt should have 1 input (the deposit)"
+        );
+        assert_eq!(
+            psbt.unsigned_tx.input[0].previous_output, deposit_outpoint,
+            "reimbursement should spend the deposit"
+        );
+        assert_eq!(
+            psbt.outputs.len(),
+            3,
+            "reimbursement psbt should have 3 outputs -- operator, metadata and anybody takes"
+        );
+        assert_eq!(
+            psbt.unsigned_tx.output[1].script_pubkey,
+            deposit_reimbursement_script(&fulfillment_txid()),
+            "reimbursement should commit to the fulfillment"
+        );
+
+        assert!(
+            matches!(signing_data.spend_path, TaprootSpendPath::Key),
+            "signing data should have a keypath spend"
+        );
+    }
+
+    #[test]
+    fn test_create_unsigned_tx_with_change() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(3);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[2u8; 32])), 2);
+
+        let assigned_operator_idx = 1 as OperatorIdx;
+        let reimbursement_info = ReimbursementInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1, 2],
+            Amount::from_int_btc(9),
+            assigned_operator_idx,
+            fulfillment_txid(),
+        );
+
+        let build_context = TxBuildContext::new(Network::Regtest, pubkey_table, 0);
+
+        // Act
+        let unsigned_tx = reimbursement_info
+            .create_unsigned_tx(&build_context, Amount::from(BRIDGE_DENOMINATION))
+            .expect("should be able to create the reimbursement tx");
+
+        // Assert
+        assert_eq!(
+            unsigned_tx.output.len(),
+            4,
+            "reimbursement tx should have 4 outputs -- operator, change, metadata and anybody takes"
+        );
+
+        let bridge_script_pubkey = reimbursement_info
+            .create_prevout(&build_context)
+            .unwrap()
+            .script_pubkey;
+        let change = &unsigned_tx.output[1];
+        assert_eq!(change.script_pubkey, bridge_script_pubkey);
+        assert_eq!(
+            change.value,
+            Amount::from_int_btc(1),
+            "change should go back to the bridge"
+        );
+
+        let reimbursement = unsigned_tx.output[0].value;
+        assert!(
+            reimbursement < Amount::from_int_btc(9),
+            "reimbursement should pay for the transaction fee"
+        );
+        assert!(
+            reimbursement > Amount::from_int_btc(9) - super::operator_fee(Amount::from_int_btc(9)),
+            "reimbursement should cover what the operator paid out"
+        );
+    }
+
+    #[test]
+    fn test_create_unsigned_tx_unauthorized() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(2);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[3u8; 32])), 3);
+
+        let reimbursement_info = ReimbursementInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1],
+            BRIDGE_DENOMINATION.into(),
+            5,
+            fulfillment_txid(),
+        );
+
+        let build_context = TxBuildContext::new(Network::Regtest, pubkey_table, 0);
+
+        // Act
+        let unsigned_tx_result =
+            reimbursement_info.create_unsigned_tx(&build_context, BRIDGE_DENOMINATION.into());
+
+        // Assert
+        assert!(unsigned_tx_result.is_err_and(|e| matches!(
+            e,
+            BridgeTxBuilderError::DepositReimbursementTransaction(
+                DepositReimbursementError::Unauthorized(5),
+            ),
+        )));
+    }
+
+    #[test]
+    fn test_create_unsigned_tx_exceeds_deposit() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(2);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[4u8; 32])), 4);
+
+        let reimbursement_info = ReimbursementInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1],
+            Amount::from_int_btc(12),
+            0,
+            fulfillment_txid(),
+        );
+
+        let build_context = TxBuildContext::new(Network::Regtest, pubkey_table, 0);
+
+        // Act
+        let unsigned_tx_result =
+            reimbursement_info.create_unsigned_tx(&build_context, BRIDGE_DENOMINATION.into());
+
+        // Assert
+        assert!(unsigned_tx_result.is_err_and(|e| matches!(
+            e,
+            BridgeTxBuilderError::DepositReimbursementTransaction(
+                DepositReimbursementError::ExceedsDeposit(..),
+            ),
+        )));
+    }
+
+    #[test]
+    fn test_construct_signing_data_notary_subset() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(3);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[5u8; 32])), 5);
+
+        let reimbursement_info = ReimbursementInfo::new(
+            deposit_outpoint,
+            Amount::from_int_btc(5),
+            vec![0, 2],
+            Amount::from_int_btc(5),
+            2,
+            fulfillment_txid(),
+        );
+
+        let build_context = TxBuildContext::new(Network::Regtest, pubkey_table.clone(), 0);
+
+        // Act
+        let signers = reimbursement_info
+            .signers(&build_context)
+            .expect("notaries should be in the pubkey table");
+        let signing_data = reimbursement_info
+            .construct_signing_data(&build_context)
+            .expect("should be able to construct TxSigningData");
+
+        // Assert
+        assert_eq!(
+            signers.0.keys().copied().collect::<Vec<_>>(),
+            vec![0, 2],
+            "only the notaries should sign"
+        );
+
+        let notary_context = TxBuildContext::new(Network::Regtest, signers, 0);
+        let prevout = signing_data.psbt.inner().inputs[0]
+            .witness_utxo
+            .clone()
+            .expect("reimbursement should have a witness utxo");
+        assert_eq!(
+            prevout,
+            reimbursement_info.create_prevout(&notary_context).unwrap(),
+            "the deposit should be locked to the notaries"
+        );
+        assert_eq!(prevout.value, Amount::from_int_btc(5));
+
+        let unknown_notary = ReimbursementInfo::new(
+            deposit_outpoint,
+            Amount::from_int_btc(5),
+            vec![0, 7],
+            Amount::from_int_btc(5),
+            0,
+            fulfillment_txid(),
+        );
+        assert!(unknown_notary
+            .construct_signing_data(&build_context)
+            .is_err_and(|e| matches!(
+                e,
+                BridgeTxBuilderError::DepositReimbursementTransaction(
+                    DepositReimbursementError::MissingNotary(7),
+                ),
+            )));
+
+        // A notary removed from the active set is looked up among the retired operators.
+        let mut active_pubkeys = pubkey_table;
+        let retired_pubkey = active_pubkeys.0.remove(&0).unwrap();
+        let retired_context = TxBuildContext::new(Network::Regtest, active_pubkeys, 2)
+            .with_retired_pubkeys(BTreeMap::from([(0, retired_pubkey)]).into());
+        assert_eq!(
+            reimbursement_info.signers(&retired_context).unwrap(),
+            reimbursement_info.signers(&build_context).unwrap(),
+            "retired notaries should still sign"
+        );
+    }
+}
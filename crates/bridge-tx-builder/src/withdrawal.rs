@@ -1,35 +1,48 @@
 //! Provides types/traits associated with the withdrawal process.
+//!
+//! Like the reimbursement in [`crate::reimbursement`], the withdrawal spends a deposit UTXO locked
+//! to the operators that notarized the deposit, so it is built and signed in their context.
 
-use bitcoin::{Amount, FeeRate, OutPoint, Psbt, Transaction, TxOut};
+use std::collections::BTreeMap;
+
+use bitcoin::{Amount, FeeRate, Network, OutPoint, Psbt, ScriptBuf, Transaction, TxOut};
 use serde::{Deserialize, Serialize};
 use strata_primitives::{
-    bridge::{BitcoinBlockHeight, OperatorIdx, TxSigningData},
-    l1::{BitcoinPsbt, TaprootSpendPath, XOnlyPk},
+    bridge::{BitcoinBlockHeight, OperatorIdx, PublickeyTable, TxSigningData, WithdrawOutput},
+    l1::{BitcoinPsbt, TaprootSpendPath},
 };
 
 use crate::{
-    context::BuildContext,
+    context::{BuildContext, TxBuildContext},
     errors::{BridgeTxBuilderResult, CooperativeWithdrawalError},
     prelude::{
-        anyone_can_spend_txout, create_taproot_addr, create_tx, create_tx_ins, create_tx_outs,
-        SpendPath, BRIDGE_DENOMINATION, MIN_RELAY_FEE, OPERATOR_FEE,
+        anyone_can_spend_txout, cooperative_withdrawal_script, create_taproot_addr, create_tx,
+        create_tx_ins, create_tx_outs, withdrawal_fulfillment_script, SpendPath, MIN_RELAY_FEE,
+        OPERATOR_FEE_BPS,
     },
     TxKind,
 };
 
 /// Details for a withdrawal info assigned to an operator.
 ///
-/// It has all the information required to create a transaction for fulfilling a user's withdrawal
-/// request and pay operator fees.
+/// It has all the information required to create a transaction for fulfilling a batch of user
+/// withdrawal requests, pay operator fees and return the rest of the deposit to the bridge address.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CooperativeWithdrawalInfo {
     /// The [`OutPoint`] of the UTXO in the Bridge Address that is to be used to service the
     /// withdrawal request.
     deposit_outpoint: OutPoint,
 
-    /// The x-only public key of the user used to create the taproot address that the user can
-    /// spend from.
-    user_pk: XOnlyPk,
+    /// The amount locked in the deposit UTXO.
+    deposit_amount: Amount,
+
+    /// The indexes of the operators that notarized the deposit, to whose aggregated key the
+    /// deposit UTXO is locked.
+    notary_operators: Vec<OperatorIdx>,
+
+    /// The outputs paying out to the users, each to a taproot address created from the user's
+    /// x-only public key.
+    withdraw_outputs: Vec<WithdrawOutput>,
 
     /// The index of the operator that is assigned the withdrawal.
     assigned_operator_idx: OperatorIdx,
@@ -46,8 +59,14 @@ impl TxKind for CooperativeWithdrawalInfo {
         &self,
         build_context: &C,
     ) -> BridgeTxBuilderResult<TxSigningData> {
-        let prevout = self.create_prevout(build_context)?;
-        let unsigned_tx = self.create_unsigned_tx(build_context, prevout.value)?;
+        let notary_context = TxBuildContext::new(
+            *build_context.network(),
+            self.signers(build_context)?,
+            build_context.own_index(),
+        );
+
+        let prevout = self.create_prevout(&notary_context)?;
+        let unsigned_tx = self.create_unsigned_tx(&notary_context, prevout.value)?;
 
         let mut psbt = Psbt::from_unsigned_tx(unsigned_tx)?;
 
@@ -63,19 +82,42 @@ impl TxKind for CooperativeWithdrawalInfo {
             spend_path: TaprootSpendPath::Key,
         })
     }
+
+    fn signers<C: BuildContext>(&self, build_context: &C) -> BridgeTxBuilderResult<PublickeyTable> {
+        let pubkey_table = build_context.pubkey_table();
+        let retired_pubkey_table = build_context.retired_pubkey_table();
+
+        // The notaries may have been removed from the active set since the deposit was made.
+        let notary_pubkeys = self
+            .notary_operators
+            .iter()
+            .map(
+                |idx| match pubkey_table.0.get(idx).or(retired_pubkey_table.0.get(idx)) {
+                    Some(pubkey) => Ok((*idx, *pubkey)),
+                    None => Err(CooperativeWithdrawalError::MissingNotary(*idx)),
+                },
+            )
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        Ok(notary_pubkeys.into())
+    }
 }
 
 impl CooperativeWithdrawalInfo {
     /// Create a new withdrawal request.
     pub fn new(
         deposit_outpoint: OutPoint,
-        user_pk: XOnlyPk,
+        deposit_amount: Amount,
+        notary_operators: Vec<OperatorIdx>,
+        withdraw_outputs: Vec<WithdrawOutput>,
         assigned_operator_idx: OperatorIdx,
         exec_deadline: BitcoinBlockHeight,
     ) -> Self {
         Self {
             deposit_outpoint,
-            user_pk,
+            deposit_amount,
+            notary_operators,
+            withdraw_outputs,
             assigned_operator_idx,
             exec_deadline,
         }
@@ -86,23 +128,96 @@ impl CooperativeWithdrawalInfo {
         self.deposit_outpoint
     }
 
+    /// Get the amount locked in the deposit UTXO.
+    pub fn deposit_amount(&self) -> Amount {
+        self.deposit_amount
+    }
+
+    /// Get the indexes of the operators that notarized the deposit.
+    pub fn notary_operators(&self) -> &[OperatorIdx] {
+        &self.notary_operators
+    }
+
+    /// Get the outputs that the withdrawal pays out to.
+    pub fn withdraw_outputs(&self) -> &[WithdrawOutput] {
+        &self.withdraw_outputs
+    }
+
+    /// Get the total value of the outputs that the withdrawal pays out to.
+    pub fn total_value(&self) -> Amount {
+        self.withdraw_outputs
+            .iter()
+            .map(|output| Amount::from(output.amt()))
+            .sum()
+    }
+
+    /// Get the total fee the assigned operator keeps out of the outputs of the withdrawal.
+    pub fn total_operator_fee(&self) -> Amount {
+        self.withdraw_outputs
+            .iter()
+            .map(|output| operator_fee(Amount::from(output.amt())))
+            .sum()
+    }
+
+    /// Get the index of the operator that is assigned the withdrawal.
+    pub fn assigned_operator_idx(&self) -> OperatorIdx {
+        self.assigned_operator_idx
+    }
+
+    /// Get the bitcoin block height before which the withdrawal has to be processed.
+    pub fn exec_deadline(&self) -> BitcoinBlockHeight {
+        self.exec_deadline
+    }
+
     /// Check if the passed bitcoin block height is greater than the deadline for the withdrawal.
     pub fn is_expired_at(&self, block_height: BitcoinBlockHeight) -> bool {
         self.exec_deadline < block_height
     }
 
-    fn create_prevout<T: BuildContext>(&self, build_context: &T) -> BridgeTxBuilderResult<TxOut> {
-        // We are not committing to any script path as the internal key should already be
-        // randomized due to MuSig2 aggregation. See: <https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#cite_note-23>
-        let spend_path = SpendPath::KeySpend {
-            internal_key: build_context.aggregated_pubkey(),
-        };
+    /// Create the transaction with which the assigned operator pays out the withdrawal from its
+    /// own wallet, to be reimbursed from the deposit afterwards.
+    ///
+    /// The transaction has no inputs, these and the change are to be added by the operator's
+    /// wallet. Besides the payouts to the users, it has a metadata output committing to the
+    /// deposit so that the fulfillment can be matched to it on chain.
+    pub fn create_fulfillment_tx(&self, network: Network) -> BridgeTxBuilderResult<Transaction> {
+        if self.withdraw_outputs.is_empty() {
+            return Err(CooperativeWithdrawalError::NoOutputs)?;
+        }
+
+        let mut tx_outs = create_tx_outs(self.create_payouts(network)?);
+        tx_outs.push(TxOut {
+            script_pubkey: withdrawal_fulfillment_script(
+                &self.deposit_outpoint,
+                self.assigned_operator_idx,
+            ),
+            value: Amount::ZERO,
+        });
 
-        let (bridge_addr, _) = create_taproot_addr(build_context.network(), spend_path)?;
+        Ok(create_tx(vec![], tx_outs))
+    }
+
+    /// Create the outputs that pay out the withdrawal to the users, net of the operator fee.
+    fn create_payouts(&self, network: Network) -> BridgeTxBuilderResult<Vec<(ScriptBuf, Amount)>> {
+        self.withdraw_outputs
+            .iter()
+            .map(|output| {
+                let user_addr = output
+                    .dest_addr()
+                    .to_p2tr_address(network)
+                    .map_err(CooperativeWithdrawalError::InvalidUserPk)?;
+
+                let amount = Amount::from(output.amt());
+
+                Ok((user_addr.script_pubkey(), amount - operator_fee(amount)))
+            })
+            .collect()
+    }
 
+    fn create_prevout<T: BuildContext>(&self, build_context: &T) -> BridgeTxBuilderResult<TxOut> {
         Ok(TxOut {
-            value: BRIDGE_DENOMINATION.into(),
-            script_pubkey: bridge_addr.script_pubkey(),
+            value: self.deposit_amount,
+            script_pubkey: bridge_script_pubkey(build_context)?,
         })
     }
 
@@ -111,56 +226,86 @@ impl CooperativeWithdrawalInfo {
         build_context: &T,
         total_amount: Amount,
     ) -> BridgeTxBuilderResult<Transaction> {
+        if self.withdraw_outputs.is_empty() {
+            return Err(CooperativeWithdrawalError::NoOutputs)?;
+        }
+
+        let withdrawal_total = self.total_value();
+        if withdrawal_total > total_amount {
+            return Err(CooperativeWithdrawalError::ExceedsDeposit(
+                withdrawal_total,
+                total_amount,
+            ))?;
+        }
+
         let tx_ins = create_tx_ins([self.deposit_outpoint]);
 
-        // create the output for the operator fees
+        // create the output for the operator fees, the assigned operator has to be a notary of the
+        // deposit
         let pubkey_table = build_context.pubkey_table();
-        let assigned_operator_pubkey = pubkey_table.0.get(&self.assigned_operator_idx);
-
-        if assigned_operator_pubkey.is_none() {
+        let Some(operator_pubkey) = pubkey_table.0.get(&self.assigned_operator_idx) else {
             return Err(CooperativeWithdrawalError::Unauthorized(
                 self.assigned_operator_idx,
             ))?;
-        }
+        };
 
-        let x_only_pubkey = assigned_operator_pubkey
-            .expect("should be present")
-            .x_only_public_key()
-            .0;
         let spend_path = SpendPath::KeySpend {
-            internal_key: x_only_pubkey,
+            internal_key: operator_pubkey.x_only_public_key().0,
         };
 
         let (operator_addr, _) = create_taproot_addr(build_context.network(), spend_path)?;
+        let operator_script_pubkey = operator_addr.script_pubkey();
 
         // create the `anyone can spend` output for CPFP
         let anyone_can_spend_out = anyone_can_spend_txout();
 
-        // create the output that pays to the user
-        let user_addr = self
-            .user_pk
-            .to_p2tr_address(*build_context.network())
-            .map_err(CooperativeWithdrawalError::InvalidUserPk)?;
-        let user_script_pubkey = user_addr.script_pubkey();
+        // create the outputs that pay to the users, net of the operator fee
+        let mut outputs = Vec::with_capacity(self.withdraw_outputs.len() + 3);
+        outputs.extend(self.create_payouts(*build_context.network())?);
+        let mut operator_fee = self.total_operator_fee();
+
+        // Whatever is left of the deposit goes back to the bridge address as change, unless it is
+        // dust in which case it goes to the operator.
+        let bridge_script_pubkey = bridge_script_pubkey(build_context)?;
+        let change_amount = total_amount - withdrawal_total;
+        let change_out = if change_amount >= bridge_script_pubkey.minimal_non_dust() {
+            Some((bridge_script_pubkey, change_amount))
+        } else {
+            operator_fee += change_amount;
+            None
+        };
 
-        // This fee pays for the entire transaction.
+        // The operator fee pays for the entire transaction.
         // In the current configuration of `10` for `MIN_RELAY_FEE`, the total transaction fee
         // computes to ~5.5 SAT (run integration tests with `RUST_LOG=warn` to verify).
         let fee_rate = FeeRate::from_sat_per_vb(MIN_RELAY_FEE.to_sat())
             .expect("MIN_RELAY_FEE should be set correctly");
-        let tx_fee = user_script_pubkey.minimal_non_dust_custom(fee_rate);
+        let tx_fee = operator_script_pubkey.minimal_non_dust_custom(fee_rate);
+
+        let total_fees = anyone_can_spend_out.value + tx_fee;
+        let net_operator_fee = operator_fee.checked_sub(total_fees).ok_or(
+            CooperativeWithdrawalError::InsufficientFees(operator_fee, total_fees),
+        )?;
+
+        outputs.push((operator_script_pubkey, net_operator_fee)); // operator fees
+
+        // The metadata lets the rollup match the withdrawal to the deposit on chain, and pick up
+        // the change as a new deposit.
+        let change_vout = change_out.as_ref().map(|_| outputs.len() as u32);
+        let metadata_script = cooperative_withdrawal_script(
+            &self.deposit_outpoint,
+            self.assigned_operator_idx,
+            change_vout,
+        );
 
-        let net_amount = total_amount - OPERATOR_FEE - anyone_can_spend_out.value - tx_fee;
+        outputs.extend(change_out); // change back to the bridge
+        outputs.push((
+            anyone_can_spend_out.script_pubkey,
+            anyone_can_spend_out.value,
+        )); // anyone can spend for CPFP
+        outputs.push((metadata_script, Amount::ZERO)); // metadata
 
-        let tx_outs = create_tx_outs([
-            (user_script_pubkey, net_amount),              // payout to the user
-            (operator_addr.script_pubkey(), OPERATOR_FEE), // operator fees
-            // anyone can spend for CPFP
-            (
-                anyone_can_spend_out.script_pubkey,
-                anyone_can_spend_out.value,
-            ),
-        ]);
+        let tx_outs = create_tx_outs(outputs);
 
         let unsigned_tx = create_tx(tx_ins, tx_outs);
 
@@ -168,6 +313,26 @@ impl CooperativeWithdrawalInfo {
     }
 }
 
+/// Computes the fee the operator keeps out of a withdrawal of the given amount.
+pub fn operator_fee(amount: Amount) -> Amount {
+    amount * OPERATOR_FEE_BPS / 10_000
+}
+
+/// Creates the script pubkey of the bridge address that holds the deposits.
+pub(crate) fn bridge_script_pubkey<T: BuildContext>(
+    build_context: &T,
+) -> BridgeTxBuilderResult<ScriptBuf> {
+    // We are not committing to any script path as the internal key should already be
+    // randomized due to MuSig2 aggregation. See: <https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#cite_note-23>
+    let spend_path = SpendPath::KeySpend {
+        internal_key: build_context.aggregated_pubkey(),
+    };
+
+    let (bridge_addr, _) = create_taproot_addr(build_context.network(), spend_path)?;
+
+    Ok(bridge_addr.script_pubkey())
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Not;
@@ -177,17 +342,20 @@ mod tests {
         Amount, Network, OutPoint, Txid,
     };
     use strata_primitives::{
-        bridge::OperatorIdx,
+        bridge::{OperatorIdx, WithdrawOutput},
         buf::Buf32,
         errors::ParseError,
-        l1::{TaprootSpendPath, XOnlyPk},
+        l1::{BitcoinAmount, TaprootSpendPath, XOnlyPk},
     };
     use strata_test_utils::bridge::{generate_keypairs, generate_pubkey_table};
 
     use crate::{
         context::TxBuildContext,
         errors::{BridgeTxBuilderError, CooperativeWithdrawalError},
-        prelude::{CooperativeWithdrawalInfo, BRIDGE_DENOMINATION},
+        prelude::{
+            cooperative_withdrawal_script, operator_fee, withdrawal_fulfillment_script,
+            CooperativeWithdrawalInfo, BRIDGE_DENOMINATION,
+        },
         TxKind,
     };
 
@@ -210,8 +378,15 @@ mod tests {
 
         let assigned_operator_idx = assigned_operator_idx as OperatorIdx;
 
-        let withdrawal_info =
-            CooperativeWithdrawalInfo::new(deposit_outpoint, user_pk, assigned_operator_idx, 0);
+        let withdraw_outputs = vec![WithdrawOutput::new(user_pk, BRIDGE_DENOMINATION)];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1, 2],
+            withdraw_outputs,
+            assigned_operator_idx,
+            0,
+        );
 
         let build_context = TxBuildContext::new(
             Network::Regtest,
@@ -238,8 +413,9 @@ mod tests {
         );
         assert_eq!(
             psbt.outputs.len(),
-            3,
-            "withdrawal psbt should have 3 outputs -- payout, operator fee, and anybody takes"
+            4,
+            "withdrawal psbt should have 4 outputs -- payout, operator fee, anybody takes and \
+             metadata"
         );
 
         assert!(
@@ -267,9 +443,12 @@ mod tests {
         let invalid_user_pk = XOnlyPk::new(Buf32::zero());
         let assigned_operator_idx = assigned_operator_idx as OperatorIdx;
 
+        let withdraw_outputs = vec![WithdrawOutput::new(invalid_user_pk, BRIDGE_DENOMINATION)];
         let withdrawal_info = CooperativeWithdrawalInfo::new(
             deposit_outpoint,
-            invalid_user_pk,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1],
+            withdraw_outputs,
             assigned_operator_idx,
             0,
         );
@@ -289,6 +468,68 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_create_fulfillment_tx_success() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(3);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[7u8; 32])), 7);
+
+        let user_pks = pubkeys[1..]
+            .iter()
+            .map(|pk| XOnlyPk::new(Buf32(pk.x_only_public_key().0.serialize())))
+            .collect::<Vec<_>>();
+
+        let withdraw_outputs = vec![
+            WithdrawOutput::new(user_pks[0], BitcoinAmount::from_int_btc(4)),
+            WithdrawOutput::new(user_pks[1], BitcoinAmount::from_int_btc(5)),
+        ];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1, 2],
+            withdraw_outputs,
+            2,
+            0,
+        );
+
+        // Act
+        let fulfillment_tx = withdrawal_info
+            .create_fulfillment_tx(Network::Regtest)
+            .expect("should be able to create the fulfillment tx");
+
+        // Assert
+        assert!(
+            fulfillment_tx.input.is_empty(),
+            "inputs are left to the operator's wallet"
+        );
+        assert_eq!(
+            fulfillment_tx.output.len(),
+            3,
+            "fulfillment tx should have 3 outputs -- 2 payouts and metadata"
+        );
+
+        let payout = &fulfillment_tx.output[0];
+        assert_eq!(
+            payout.script_pubkey,
+            user_pks[0]
+                .to_p2tr_address(Network::Regtest)
+                .unwrap()
+                .script_pubkey()
+        );
+        assert_eq!(
+            payout.value,
+            Amount::from_int_btc(4) - operator_fee(Amount::from_int_btc(4)),
+            "payout should be net of the operator fee"
+        );
+
+        assert_eq!(
+            fulfillment_tx.output[2].script_pubkey,
+            withdrawal_fulfillment_script(&deposit_outpoint, 2),
+            "fulfillment should commit to the deposit and the assignee"
+        );
+    }
+
     #[test]
     fn test_create_prevout_success() {
         // Arrange
@@ -307,8 +548,15 @@ mod tests {
         let user_pk = XOnlyPk::new(Buf32(pubkeys[user_index].x_only_public_key().0.serialize()));
         let assigned_operator_idx = assigned_operator_idx as OperatorIdx;
 
-        let withdrawal_info =
-            CooperativeWithdrawalInfo::new(deposit_outpoint, user_pk, assigned_operator_idx, 0);
+        let withdraw_outputs = vec![WithdrawOutput::new(user_pk, BRIDGE_DENOMINATION)];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            Amount::from_int_btc(12),
+            vec![0, 1, 2],
+            withdraw_outputs,
+            assigned_operator_idx,
+            0,
+        );
 
         let build_context =
             TxBuildContext::new(Network::Regtest, pubkey_table, assigned_operator_idx);
@@ -322,9 +570,10 @@ mod tests {
 
         assert!(prevout.script_pubkey.is_empty().not());
 
-        assert!(
-            prevout.value.eq(&BRIDGE_DENOMINATION.into()),
-            "output amount equal to the bridge denomination"
+        assert_eq!(
+            prevout.value,
+            Amount::from_int_btc(12),
+            "output amount equal to the deposit amount"
         );
     }
 
@@ -346,8 +595,15 @@ mod tests {
         let user_pk = XOnlyPk::new(Buf32(pubkeys[user_index].x_only_public_key().0.serialize()));
         let assigned_operator_idx = assigned_operator_idx as OperatorIdx;
 
-        let withdrawal_info =
-            CooperativeWithdrawalInfo::new(deposit_outpoint, user_pk, assigned_operator_idx, 0);
+        let withdraw_outputs = vec![WithdrawOutput::new(user_pk, BRIDGE_DENOMINATION)];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1, 2, 3],
+            withdraw_outputs,
+            assigned_operator_idx,
+            0,
+        );
 
         let build_context =
             TxBuildContext::new(Network::Regtest, pubkey_table, assigned_operator_idx);
@@ -362,6 +618,193 @@ mod tests {
 
         // Verify that the transaction has the correct number of inputs and outputs
         assert_eq!(unsigned_tx.input.len(), 1);
-        assert_eq!(unsigned_tx.output.len(), 3);
+        assert_eq!(unsigned_tx.output.len(), 4);
+        assert_eq!(
+            unsigned_tx.output[3].script_pubkey,
+            cooperative_withdrawal_script(&deposit_outpoint, assigned_operator_idx, None),
+            "withdrawal without change should commit to the deposit and the assignee"
+        );
+    }
+
+    #[test]
+    fn test_create_unsigned_tx_batch_with_change() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(4);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[5u8; 32])), 5);
+
+        let assigned_operator_idx = 0 as OperatorIdx;
+        let user_pks = pubkeys[2..]
+            .iter()
+            .map(|pk| XOnlyPk::new(Buf32(pk.x_only_public_key().0.serialize())))
+            .collect::<Vec<_>>();
+
+        let withdraw_outputs = vec![
+            WithdrawOutput::new(user_pks[0], BitcoinAmount::from_int_btc(4)),
+            WithdrawOutput::new(user_pks[1], BitcoinAmount::from_int_btc(5)),
+        ];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1, 2, 3],
+            withdraw_outputs,
+            assigned_operator_idx,
+            0,
+        );
+
+        let build_context =
+            TxBuildContext::new(Network::Regtest, pubkey_table, assigned_operator_idx);
+
+        // Act
+        let unsigned_tx = withdrawal_info
+            .create_unsigned_tx(&build_context, Amount::from(BRIDGE_DENOMINATION))
+            .expect("should be able to create the withdrawal tx");
+
+        // Assert
+        assert_eq!(
+            unsigned_tx.output.len(),
+            6,
+            "withdrawal tx should have 6 outputs -- 2 payouts, operator fee, change, anybody takes \
+             and metadata"
+        );
+
+        let bridge_script_pubkey = withdrawal_info
+            .create_prevout(&build_context)
+            .unwrap()
+            .script_pubkey;
+        let change = &unsigned_tx.output[3];
+        assert_eq!(change.script_pubkey, bridge_script_pubkey);
+        assert_eq!(
+            change.value,
+            Amount::from_int_btc(1),
+            "change should go back to the bridge"
+        );
+        assert_eq!(
+            unsigned_tx.output[5].script_pubkey,
+            cooperative_withdrawal_script(&deposit_outpoint, assigned_operator_idx, Some(3)),
+            "metadata should point at the change"
+        );
+
+        let total_out: Amount = unsigned_tx.output.iter().map(|out| out.value).sum();
+        assert!(
+            total_out < Amount::from(BRIDGE_DENOMINATION),
+            "outputs should leave room for the transaction fee"
+        );
+    }
+
+    #[test]
+    fn test_create_unsigned_tx_exceeds_deposit() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(2);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[6u8; 32])), 6);
+
+        let assigned_operator_idx = 0 as OperatorIdx;
+        let user_pk = XOnlyPk::new(Buf32(pubkeys[1].x_only_public_key().0.serialize()));
+
+        let withdraw_outputs = vec![
+            WithdrawOutput::new(user_pk, BitcoinAmount::from_int_btc(6)),
+            WithdrawOutput::new(user_pk, BitcoinAmount::from_int_btc(6)),
+        ];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            BRIDGE_DENOMINATION.into(),
+            vec![0, 1],
+            withdraw_outputs,
+            assigned_operator_idx,
+            0,
+        );
+
+        let build_context =
+            TxBuildContext::new(Network::Regtest, pubkey_table, assigned_operator_idx);
+
+        // Act
+        let unsigned_tx_result =
+            withdrawal_info.create_unsigned_tx(&build_context, Amount::from(BRIDGE_DENOMINATION));
+
+        // Assert
+        assert!(unsigned_tx_result.is_err_and(|e| matches!(
+            e,
+            BridgeTxBuilderError::CooperativeWithdrawalTransaction(
+                CooperativeWithdrawalError::ExceedsDeposit(..),
+            ),
+        )));
+    }
+
+    #[test]
+    fn test_construct_signing_data_notary_subset() {
+        // Arrange
+        let (pubkeys, _seckeys) = generate_keypairs(3);
+        let pubkey_table = generate_pubkey_table(&pubkeys[..]);
+        let deposit_outpoint =
+            OutPoint::new(Txid::from_raw_hash(sha256d::Hash::hash(&[8u8; 32])), 8);
+
+        let user_pk = XOnlyPk::new(Buf32(pubkeys[1].x_only_public_key().0.serialize()));
+        let withdraw_outputs = vec![WithdrawOutput::new(user_pk, BitcoinAmount::from_int_btc(4))];
+        let withdrawal_info = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            Amount::from_int_btc(5),
+            vec![0, 2],
+            withdraw_outputs.clone(),
+            2,
+            0,
+        );
+
+        let build_context = TxBuildContext::new(Network::Regtest, pubkey_table, 0);
+
+        // Act
+        let signers = withdrawal_info
+            .signers(&build_context)
+            .expect("notaries should be in the pubkey table");
+        let signing_data = withdrawal_info
+            .construct_signing_data(&build_context)
+            .expect("should be able to construct TxSigningData");
+
+        // Assert
+        assert_eq!(
+            signers.0.keys().copied().collect::<Vec<_>>(),
+            vec![0, 2],
+            "only the notaries should sign"
+        );
+
+        let notary_context = TxBuildContext::new(Network::Regtest, signers, 0);
+        let prevout = signing_data.psbt.inner().inputs[0]
+            .witness_utxo
+            .clone()
+            .expect("withdrawal should have a witness utxo");
+        assert_eq!(
+            prevout,
+            withdrawal_info.create_prevout(&notary_context).unwrap(),
+            "the deposit should be locked to the notaries"
+        );
+
+        let unsigned_tx = &signing_data.psbt.inner().unsigned_tx;
+        let change = &unsigned_tx.output[2];
+        assert_eq!(change.script_pubkey, prevout.script_pubkey);
+        assert_eq!(
+            change.value,
+            Amount::from_int_btc(1),
+            "change should be taken from the deposit amount"
+        );
+
+        // The assigned operator has to be one of the notaries.
+        let not_a_notary = CooperativeWithdrawalInfo::new(
+            deposit_outpoint,
+            Amount::from_int_btc(5),
+            vec![0, 2],
+            withdraw_outputs,
+            1,
+            0,
+        );
+        assert!(not_a_notary
+            .construct_signing_data(&build_context)
+            .is_err_and(|e| matches!(
+                e,
+                BridgeTxBuilderError::CooperativeWithdrawalTransaction(
+                    CooperativeWithdrawalError::Unauthorized(1),
+                ),
+            )));
     }
 }
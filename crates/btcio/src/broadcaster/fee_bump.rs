@@ -0,0 +1,524 @@
+//! Fee bumping of transactions that are stuck in the mempool.
+
+use std::collections::HashSet;
+
+use bitcoin::{
+    absolute::LockTime, consensus, transaction::Version, Amount, OutPoint, ScriptBuf, Sequence,
+    Transaction, TxIn, TxOut, Txid, Witness,
+};
+use strata_config::BroadcasterConfig;
+use strata_db::types::{FeeBumpMethod, L1TxEntry, L1TxStatus};
+use tracing::*;
+
+use super::error::{BroadcasterError, BroadcasterResult};
+use crate::rpc::{
+    traits::{Broadcaster, Reader, Signer, Wallet},
+    types::GetTransactionDetailCategory,
+};
+
+const BITCOIN_DUST_LIMIT: u64 = 546;
+
+/// Fee rate that a replacement has to pay on top of the replaced tx's fee, in sat/vB (BIP125).
+const INCREMENTAL_RELAY_FEE_RATE: u64 = 1;
+
+/// Checks if the entry has been waiting in the mempool long enough at the given L1 height to get
+/// its fee bumped.
+pub(crate) fn needs_fee_bump(txentry: &L1TxEntry, height: u64, config: &BroadcasterConfig) -> bool {
+    txentry.status == L1TxStatus::Published
+        && txentry.fee_bump_method != FeeBumpMethod::Disabled
+        && txentry.fee_bumps().len() < config.max_fee_bumps as usize
+        && txentry
+            .published_height
+            .is_some_and(|published| height >= published + config.fee_bump_after_blocks)
+}
+
+/// Returns the fee rate of the next bump, or `None` if it can't be higher than the last one.
+///
+/// Like the writer, we target twice the estimate, and every bump at least doubles the previous
+/// one so that fee spikes are caught up with quickly.
+pub(crate) fn next_fee_rate(
+    estimate: u64,
+    last_fee_rate: Option<u64>,
+    config: &BroadcasterConfig,
+) -> Option<u64> {
+    let fee_rate = estimate
+        .saturating_mul(2)
+        .max(last_fee_rate.unwrap_or_default().saturating_mul(2))
+        .min(config.max_fee_rate);
+    (fee_rate > last_fee_rate.unwrap_or_default()).then_some(fee_rate)
+}
+
+/// Returns how the fee of the entry's tx gets bumped.
+///
+/// A replacement of a tx that doesn't signal replaceability (BIP125) would be rejected, so those
+/// are bumped with a child instead.
+pub(crate) fn fee_bump_method(txentry: &L1TxEntry, tx: &Transaction) -> FeeBumpMethod {
+    match txentry.fee_bump_method {
+        FeeBumpMethod::Rbf if !tx.is_explicitly_rbf() => FeeBumpMethod::Cpfp,
+        method => method,
+    }
+}
+
+/// Bumps the fee of a published tx that has been waiting in the mempool for too long, and
+/// publishes the bump.
+///
+/// `spent_txids` are the txids spent by the other txs being tracked. Such txs aren't replaced
+/// since that would invalidate the txs spending them, whose CPFP bumps pay for them instead.
+///
+/// Returns the updated entry if the fee was bumped.
+pub(crate) async fn bump_fee(
+    rpc_client: &(impl Reader + Broadcaster + Wallet + Signer),
+    txentry: &L1TxEntry,
+    spent_txids: &HashSet<Txid>,
+    height: u64,
+    config: &BroadcasterConfig,
+) -> BroadcasterResult<Option<L1TxEntry>> {
+    let tx = txentry
+        .try_to_tx()
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+    let txid = tx.compute_txid();
+
+    let mut new_entry = txentry.clone();
+    let method = fee_bump_method(txentry, &tx);
+    if method != txentry.fee_bump_method {
+        debug!(%txid, "tx does not signal replaceability, bumping its fee with a child instead");
+        new_entry = new_entry.with_fee_bump_method(method);
+    }
+
+    if method == FeeBumpMethod::Rbf && spent_txids.contains(&txid) {
+        debug!(%txid, "tx has tracked descendants, leaving the fee bump to them");
+        return Ok(None);
+    }
+
+    let estimate = rpc_client
+        .estimate_smart_fee(1)
+        .await
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+    let last_fee_rate = txentry.fee_bumps().last().map(|bump| bump.fee_rate);
+    let Some(fee_rate) = next_fee_rate(estimate, last_fee_rate, config) else {
+        debug!(%txid, ?last_fee_rate, "fee rate already at maximum, not bumping");
+        return Ok(None);
+    };
+
+    // both ways of bumping take the fee from an output that belongs to the wallet
+    let info = rpc_client
+        .get_transaction(&txid)
+        .await
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+    let Some(vout) = info
+        .details
+        .iter()
+        .find(|detail| detail.category == GetTransactionDetailCategory::Receive)
+        .map(|detail| detail.vout)
+    else {
+        warn!(%txid, "tx does not pay to the wallet, cannot bump its fee");
+        return Ok(None);
+    };
+
+    match method {
+        FeeBumpMethod::Rbf => {
+            let Some(replacement) = replace_by_fee(rpc_client, &tx, vout, fee_rate).await? else {
+                return Ok(None);
+            };
+            publish(rpc_client, &replacement).await?;
+            let replacement_txid = replacement.compute_txid();
+            info!(%txid, %replacement_txid, %fee_rate, "replaced stuck tx");
+            new_entry.replace_tx(&replacement, fee_rate);
+        }
+        FeeBumpMethod::Cpfp => {
+            let Some(child) = pay_with_child(rpc_client, &tx, vout, fee_rate).await? else {
+                return Ok(None);
+            };
+            publish(rpc_client, &child).await?;
+            let child_txid = child.compute_txid();
+            info!(%txid, %child_txid, %fee_rate, "published child of stuck tx");
+            new_entry.add_cpfp_child(&child, fee_rate);
+        }
+        FeeBumpMethod::Disabled => return Ok(None),
+    }
+
+    new_entry.published_height = Some(height);
+    Ok(Some(new_entry))
+}
+
+/// Republishes the child of the latest CPFP bump of the entry, which gets dropped from the mempool
+/// along with its parent.
+pub(crate) async fn republish_cpfp_child(
+    rpc_client: &impl Broadcaster,
+    txentry: &L1TxEntry,
+) -> BroadcasterResult<()> {
+    let Some(child) = txentry.try_to_cpfp_child() else {
+        return Ok(());
+    };
+    let child = child.map_err(|e| BroadcasterError::Other(e.to_string()))?;
+    publish(rpc_client, &child).await?;
+    let child_txid = child.compute_txid();
+    info!(%child_txid, "republished child of tx");
+    Ok(())
+}
+
+/// Creates a signed replacement of `tx` that pays for the given fee rate, taking the extra fee from
+/// the wallet's output at `vout`.
+async fn replace_by_fee(
+    rpc_client: &(impl Wallet + Signer),
+    tx: &Transaction,
+    vout: u32,
+    fee_rate: u64,
+) -> BroadcasterResult<Option<Transaction>> {
+    let fee = tx_fee(rpc_client, tx).await?;
+    let vsize = tx.vsize() as u64;
+    let fee_increase = Amount::from_sat(
+        (fee_rate * vsize)
+            .saturating_sub(fee.to_sat())
+            .max(INCREMENTAL_RELAY_FEE_RATE * vsize),
+    );
+
+    let Some(replacement) = build_rbf_replacement(tx, vout, fee_increase) else {
+        let txid = tx.compute_txid();
+        warn!(%txid, %fee_increase, "wallet output too small to pay for a replacement");
+        return Ok(None);
+    };
+    sign_with_wallet(rpc_client, &replacement).await.map(Some)
+}
+
+/// Returns the fee paid by `tx`, looking its prevouts up in the wallet.
+async fn tx_fee(rpc_client: &impl Wallet, tx: &Transaction) -> BroadcasterResult<Amount> {
+    let mut input_value = Amount::ZERO;
+    for input in &tx.input {
+        let prev_tx = rpc_client
+            .get_transaction(&input.previous_output.txid)
+            .await
+            .map_err(|e| BroadcasterError::Other(e.to_string()))?
+            .hex;
+        let prevout = prev_tx
+            .output
+            .get(input.previous_output.vout as usize)
+            .ok_or_else(|| {
+                BroadcasterError::Other(format!("missing prevout {}", input.previous_output))
+            })?;
+        input_value += prevout.value;
+    }
+    let output_value = tx.output.iter().map(|output| output.value).sum::<Amount>();
+    input_value
+        .checked_sub(output_value)
+        .ok_or_else(|| BroadcasterError::Other("tx spends more than its inputs".to_string()))
+}
+
+/// Returns the total vsize and fee of `tx` along with its parents that are still unconfirmed,
+/// which a child of `tx` has to pay for too.
+///
+/// Only direct parents are looked at, which covers the commit tx of a reveal.
+async fn unconfirmed_package(
+    rpc_client: &impl Wallet,
+    tx: &Transaction,
+) -> BroadcasterResult<(u64, Amount)> {
+    let mut vsize = tx.vsize() as u64;
+    let mut fee = tx_fee(rpc_client, tx).await?;
+
+    let parent_txids: HashSet<Txid> = tx
+        .input
+        .iter()
+        .map(|input| input.previous_output.txid)
+        .collect();
+    for parent_txid in parent_txids {
+        let parent = rpc_client
+            .get_transaction(&parent_txid)
+            .await
+            .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+        if parent.confirmations == 0 {
+            vsize += parent.hex.vsize() as u64;
+            fee += tx_fee(rpc_client, &parent.hex).await?;
+        }
+    }
+
+    Ok((vsize, fee))
+}
+
+/// Creates a signed child of `tx` that spends the wallet's output at `vout` back to the wallet and
+/// brings `tx` and its unconfirmed parents up to the given fee rate.
+async fn pay_with_child(
+    rpc_client: &(impl Wallet + Signer),
+    tx: &Transaction,
+    vout: u32,
+    fee_rate: u64,
+) -> BroadcasterResult<Option<Transaction>> {
+    let parent = OutPoint {
+        txid: tx.compute_txid(),
+        vout,
+    };
+    let value = tx
+        .output
+        .get(vout as usize)
+        .ok_or_else(|| BroadcasterError::Other(format!("missing output {parent}")))?
+        .value;
+    let script_pubkey = rpc_client
+        .get_new_address()
+        .await
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?
+        .script_pubkey();
+
+    let (package_vsize, paid_fee) = unconfirmed_package(rpc_client, tx).await?;
+    let package_fee =
+        |child_vsize: u64| package_fee(fee_rate, package_vsize, paid_fee, child_vsize);
+
+    // Sign a draft first to find out the size of the child, then sign it again with the final fee.
+    let Some(draft) = build_cpfp_child(parent, value, script_pubkey.clone(), package_fee(0)) else {
+        warn!(%parent, "wallet output too small to pay for a child");
+        return Ok(None);
+    };
+    let draft = sign_with_wallet(rpc_client, &draft).await?;
+
+    let fee = package_fee(draft.vsize() as u64);
+    let Some(child) = build_cpfp_child(parent, value, script_pubkey, fee) else {
+        warn!(%parent, "wallet output too small to pay for a child");
+        return Ok(None);
+    };
+    sign_with_wallet(rpc_client, &child).await.map(Some)
+}
+
+/// Returns the fee a child of `child_vsize` has to pay to bring a package of unconfirmed txs of
+/// `package_vsize` that already pay `paid_fee` up to `fee_rate`.
+///
+/// The child pays at least `fee_rate` for itself, even if the package doesn't need it.
+pub(crate) fn package_fee(
+    fee_rate: u64,
+    package_vsize: u64,
+    paid_fee: Amount,
+    child_vsize: u64,
+) -> Amount {
+    Amount::from_sat(
+        (fee_rate * (package_vsize + child_vsize))
+            .saturating_sub(paid_fee.to_sat())
+            .max(fee_rate * child_vsize),
+    )
+}
+
+/// Builds an unsigned replacement of `tx` that pays `fee_increase` more in fees by taking it from
+/// the output at `vout`.
+///
+/// Returns `None` if the output can't pay for it without becoming dust.
+pub(crate) fn build_rbf_replacement(
+    tx: &Transaction,
+    vout: u32,
+    fee_increase: Amount,
+) -> Option<Transaction> {
+    let mut replacement = tx.clone();
+    for input in replacement.input.iter_mut() {
+        input.script_sig = ScriptBuf::new();
+        input.witness = Witness::new();
+    }
+
+    let output = replacement.output.get_mut(vout as usize)?;
+    output.value = output
+        .value
+        .checked_sub(fee_increase)
+        .filter(|value| value.to_sat() >= BITCOIN_DUST_LIMIT)?;
+
+    Some(replacement)
+}
+
+/// Builds an unsigned tx spending `parent` worth `value` to `script_pubkey`, paying `fee`.
+///
+/// Returns `None` if the output would be dust.
+pub(crate) fn build_cpfp_child(
+    parent: OutPoint,
+    value: Amount,
+    script_pubkey: ScriptBuf,
+    fee: Amount,
+) -> Option<Transaction> {
+    let value = value
+        .checked_sub(fee)
+        .filter(|value| value.to_sat() >= BITCOIN_DUST_LIMIT)?;
+
+    Some(Transaction {
+        version: Version(2),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: parent,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value,
+            script_pubkey,
+        }],
+    })
+}
+
+async fn sign_with_wallet(
+    rpc_client: &impl Signer,
+    tx: &Transaction,
+) -> BroadcasterResult<Transaction> {
+    let signed = rpc_client
+        .sign_raw_transaction_with_wallet(tx)
+        .await
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+    if !signed.complete {
+        return Err(BroadcasterError::Other(format!(
+            "wallet could not fully sign fee bump {}",
+            tx.compute_txid()
+        )));
+    }
+    consensus::encode::deserialize_hex(&signed.hex)
+        .map_err(|e| BroadcasterError::Other(e.to_string()))
+}
+
+async fn publish(rpc_client: &impl Broadcaster, tx: &Transaction) -> BroadcasterResult<()> {
+    rpc_client
+        .send_raw_transaction(tx)
+        .await
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use bitcoin::hashes::Hash;
+
+    use super::*;
+    use crate::test_utils::SOME_TX;
+
+    fn get_config() -> BroadcasterConfig {
+        BroadcasterConfig {
+            fee_bump_after_blocks: 3,
+            max_fee_bumps: 2,
+            max_fee_rate: 100,
+        }
+    }
+
+    fn some_tx() -> Transaction {
+        consensus::encode::deserialize_hex(SOME_TX).unwrap()
+    }
+
+    #[test]
+    fn test_needs_fee_bump() {
+        let config = get_config();
+        let mut entry = L1TxEntry::from_tx(&some_tx());
+        entry.status = L1TxStatus::Published;
+        entry.published_height = Some(100);
+
+        assert!(
+            !needs_fee_bump(&entry, 102, &config),
+            "should wait for the threshold"
+        );
+        assert!(needs_fee_bump(&entry, 103, &config));
+
+        let disabled = entry.clone().with_fee_bump_method(FeeBumpMethod::Disabled);
+        assert!(!needs_fee_bump(&disabled, 103, &config));
+
+        let mut confirmed = entry.clone();
+        confirmed.status = L1TxStatus::Confirmed { confirmations: 1 };
+        assert!(!needs_fee_bump(&confirmed, 103, &config));
+
+        entry.add_cpfp_child(&some_tx(), 10);
+        entry.add_cpfp_child(&some_tx(), 20);
+        assert!(
+            !needs_fee_bump(&entry, 103, &config),
+            "should stop after the maximum number of bumps"
+        );
+    }
+
+    #[test]
+    fn test_fee_bump_method() {
+        let mut tx = some_tx();
+        for input in tx.input.iter_mut() {
+            input.sequence = Sequence::MAX;
+        }
+        let entry = L1TxEntry::from_tx(&tx).with_fee_bump_method(FeeBumpMethod::Rbf);
+        assert_eq!(
+            fee_bump_method(&entry, &tx),
+            FeeBumpMethod::Cpfp,
+            "tx not signaling replaceability should be bumped with a child"
+        );
+
+        tx.input[0].sequence = Sequence::ENABLE_RBF_NO_LOCKTIME;
+        assert_eq!(fee_bump_method(&entry, &tx), FeeBumpMethod::Rbf);
+
+        let entry = entry.with_fee_bump_method(FeeBumpMethod::Disabled);
+        assert_eq!(fee_bump_method(&entry, &tx), FeeBumpMethod::Disabled);
+    }
+
+    #[test]
+    fn test_next_fee_rate() {
+        let config = get_config();
+
+        assert_eq!(next_fee_rate(5, None, &config), Some(10));
+        assert_eq!(
+            next_fee_rate(5, Some(10), &config),
+            Some(20),
+            "should at least double the last bump"
+        );
+        assert_eq!(next_fee_rate(30, Some(10), &config), Some(60));
+        assert_eq!(
+            next_fee_rate(80, Some(10), &config),
+            Some(100),
+            "should be capped at the maximum"
+        );
+        assert_eq!(next_fee_rate(80, Some(100), &config), None);
+    }
+
+    #[test]
+    fn test_package_fee() {
+        assert_eq!(
+            package_fee(10, 200, Amount::from_sat(1_000), 100),
+            Amount::from_sat(2_000),
+            "child should make up for what the package doesn't pay"
+        );
+        assert_eq!(
+            package_fee(10, 200, Amount::from_sat(5_000), 100),
+            Amount::from_sat(1_000),
+            "child should at least pay for itself"
+        );
+    }
+
+    #[test]
+    fn test_build_rbf_replacement() {
+        let tx = some_tx();
+        let value = tx.output[0].value;
+
+        let replacement = build_rbf_replacement(&tx, 0, Amount::from_sat(1_000)).unwrap();
+        assert_eq!(replacement.output[0].value, value - Amount::from_sat(1_000));
+        assert_eq!(
+            replacement.input[0].previous_output,
+            tx.input[0].previous_output
+        );
+        assert!(
+            replacement.input[0].script_sig.is_empty(),
+            "old signatures should be stripped"
+        );
+
+        assert!(
+            build_rbf_replacement(&tx, 0, value).is_none(),
+            "should not create dust"
+        );
+        assert!(build_rbf_replacement(&tx, 1, Amount::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_build_cpfp_child() {
+        let parent = OutPoint {
+            txid: Txid::from_slice(&[1; 32]).unwrap(),
+            vout: 1,
+        };
+        let script_pubkey = some_tx().output[0].script_pubkey.clone();
+
+        let child = build_cpfp_child(
+            parent,
+            Amount::from_sat(10_000),
+            script_pubkey.clone(),
+            Amount::from_sat(3_000),
+        )
+        .unwrap();
+        assert_eq!(child.input[0].previous_output, parent);
+        assert_eq!(child.output[0].value, Amount::from_sat(7_000));
+
+        assert!(build_cpfp_child(
+            parent,
+            Amount::from_sat(3_500),
+            script_pubkey,
+            Amount::from_sat(3_000)
+        )
+        .is_none());
+    }
+}
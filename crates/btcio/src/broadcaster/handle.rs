@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use strata_config::BroadcasterConfig;
 use strata_db::{
     types::{L1TxEntry, L1TxStatus},
     DbResult,
@@ -23,6 +24,9 @@ impl L1BroadcastHandle {
         Self { ops, sender }
     }
 
+    /// Returns the status of the tx that was submitted with the given txid.
+    ///
+    /// If its fee was bumped with RBF, this is the status of the latest replacement.
     pub async fn get_tx_status(&self, txid: Buf32) -> DbResult<Option<L1TxStatus>> {
         Ok(self
             .ops
@@ -70,6 +74,7 @@ pub fn spawn_broadcaster_task<T>(
     l1_rpc_client: Arc<T>,
     broadcast_ops: Arc<BroadcastDbOps>,
     params: Arc<Params>,
+    config: Option<BroadcasterConfig>,
 ) -> L1BroadcastHandle
 where
     T: Reader + Broadcaster + Wallet + Signer + Send + Sync + 'static,
@@ -77,7 +82,7 @@ where
     let (broadcast_entry_tx, broadcast_entry_rx) = mpsc::channel::<(u64, L1TxEntry)>(64);
     let ops = broadcast_ops.clone();
     executor.spawn_critical_async("l1_broadcaster_task", async move {
-        broadcaster_task(l1_rpc_client, ops, broadcast_entry_rx, params)
+        broadcaster_task(l1_rpc_client, ops, broadcast_entry_rx, params, config)
             .await
             .map_err(Into::into)
     });
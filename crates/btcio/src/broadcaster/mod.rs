@@ -1,4 +1,5 @@
 pub mod error;
+mod fee_bump;
 mod handle;
 mod state;
 pub mod task;
@@ -1,6 +1,15 @@
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use bitcoin::{hashes::Hash, Txid};
+use metrics::{counter, gauge};
+use strata_common::metrics::{
+    BROADCASTER_FEE_BUMPS, BROADCASTER_QUEUE_DEPTH, BROADCASTER_TX_STATUS, STATUS_LABEL,
+};
+use strata_config::BroadcasterConfig;
 use strata_db::types::{L1TxEntry, L1TxStatus};
 use strata_primitives::params::Params;
 use strata_storage::{ops::l1tx_broadcast, BroadcastDbOps};
@@ -10,19 +19,23 @@ use tracing::*;
 use crate::{
     broadcaster::{
         error::{BroadcasterError, BroadcasterResult},
+        fee_bump::{bump_fee, needs_fee_bump, republish_cpfp_child},
         state::BroadcasterState,
     },
-    rpc::traits::{Broadcaster, Wallet},
+    rpc::traits::{Broadcaster, Reader, Signer, Wallet},
 };
 
 const BROADCAST_POLL_INTERVAL: u64 = 1_000; // millis
 
 /// Broadcasts the next blob to be sent
+///
+/// The fees of txs stuck in the mempool only get bumped if `config` is set.
 pub async fn broadcaster_task(
-    rpc_client: Arc<impl Broadcaster + Wallet>,
+    rpc_client: Arc<impl Reader + Broadcaster + Wallet + Signer>,
     ops: Arc<l1tx_broadcast::BroadcastDbOps>,
     mut entry_receiver: Receiver<(u64, L1TxEntry)>,
     params: Arc<Params>,
+    config: Option<BroadcasterConfig>,
 ) -> BroadcasterResult<()> {
     info!("Starting Broadcaster task");
     let interval = tokio::time::interval(Duration::from_millis(BROADCAST_POLL_INTERVAL));
@@ -50,6 +63,7 @@ pub async fn broadcaster_task(
             ops.clone(),
             rpc_client.as_ref(),
             params.as_ref(),
+            config.as_ref(),
         )
         .await
         .map_err(|e| {
@@ -62,31 +76,67 @@ pub async fn broadcaster_task(
         }
 
         state.next(updated_entries, &ops).await?;
+        gauge!(BROADCASTER_QUEUE_DEPTH).set(state.unfinalized_entries.len() as f64);
     }
 }
 
 /// Processes unfinalized entries and returns entries idxs that are finalized
+///
+/// Also bumps the fees of the entries that have been waiting in the mempool for too long if
+/// `config` is set.
 async fn process_unfinalized_entries(
     unfinalized_entries: &BTreeMap<u64, L1TxEntry>,
     ops: Arc<BroadcastDbOps>,
-    rpc_client: &(impl Broadcaster + Wallet),
+    rpc_client: &(impl Reader + Broadcaster + Wallet + Signer),
     params: &Params,
+    config: Option<&BroadcasterConfig>,
 ) -> BroadcasterResult<(BTreeMap<u64, L1TxEntry>, Vec<u64>)> {
     let mut to_remove = Vec::new();
     let mut updated_entries = BTreeMap::new();
 
+    if unfinalized_entries.is_empty() {
+        return Ok((updated_entries, to_remove));
+    }
+
+    let height = rpc_client
+        .get_block_count()
+        .await
+        .map_err(|e| BroadcasterError::Other(e.to_string()))?;
+
+    // Txs spent by other tracked txs, which must not be replaced.
+    let spent_txids: HashSet<Txid> = unfinalized_entries
+        .values()
+        .filter_map(|txentry| txentry.try_to_tx().ok())
+        .flat_map(|tx| tx.input.into_iter().map(|input| input.previous_output.txid))
+        .collect();
+
     for (idx, txentry) in unfinalized_entries.iter() {
         debug!(?txentry.status, %idx, "processing txentry");
         let updated_status = handle_entry(rpc_client, txentry, *idx, ops.as_ref(), params).await?;
         debug!(?updated_status, %idx, "updated status handled");
 
+        let mut new_txentry = txentry.clone();
+        let mut is_updated = false;
+
         if let Some(status) = updated_status {
-            let mut new_txentry = txentry.clone();
-            new_txentry.status = status.clone();
+            let label = status_label(&status);
+            if label != status_label(&txentry.status) {
+                counter!(BROADCASTER_TX_STATUS, STATUS_LABEL => label).increment(1);
+            }
 
-            // update in db, maybe this should be moved out of this fn to separate concerns??
-            ops.put_tx_entry_by_idx_async(*idx, new_txentry.clone())
-                .await?;
+            // Start tracking the time in mempool whenever the tx gets (re)published.
+            if status == L1TxStatus::Published
+                && (txentry.status != L1TxStatus::Published || txentry.published_height.is_none())
+            {
+                new_txentry.published_height = Some(height);
+            }
+
+            // The child paying for the tx went away with it, so it has to be published again.
+            if status == L1TxStatus::Published && txentry.status == L1TxStatus::Unpublished {
+                if let Err(e) = republish_cpfp_child(rpc_client, txentry).await {
+                    warn!(%idx, %e, "failed to republish child of tx");
+                }
+            }
 
             // Remove if finalized or has invalid inputs
             if matches!(status, L1TxStatus::Finalized { confirmations: _ })
@@ -95,14 +145,44 @@ async fn process_unfinalized_entries(
                 to_remove.push(*idx);
             }
 
-            updated_entries.insert(*idx, new_txentry);
-        } else {
-            updated_entries.insert(*idx, txentry.clone());
+            new_txentry.status = status;
+            is_updated = true;
+        }
+
+        if let Some(config) = config.filter(|config| needs_fee_bump(&new_txentry, height, config)) {
+            match bump_fee(rpc_client, &new_txentry, &spent_txids, height, config).await {
+                Ok(Some(bumped_txentry)) => {
+                    counter!(BROADCASTER_FEE_BUMPS).increment(1);
+                    new_txentry = bumped_txentry;
+                    is_updated = true;
+                }
+                Ok(None) => {}
+                Err(e) => warn!(%idx, %e, "failed to bump fee of stuck tx"),
+            }
         }
+
+        if is_updated {
+            // update in db, maybe this should be moved out of this fn to separate concerns??
+            ops.put_tx_entry_by_idx_async(*idx, new_txentry.clone())
+                .await?;
+        }
+
+        updated_entries.insert(*idx, new_txentry);
     }
     Ok((updated_entries, to_remove))
 }
 
+/// Returns the metrics label for the given [`L1TxStatus`], ignoring the confirmations.
+fn status_label(status: &L1TxStatus) -> &'static str {
+    match status {
+        L1TxStatus::Unpublished => "unpublished",
+        L1TxStatus::Published => "published",
+        L1TxStatus::Confirmed { .. } => "confirmed",
+        L1TxStatus::Finalized { .. } => "finalized",
+        L1TxStatus::InvalidInputs => "invalid_inputs",
+    }
+}
+
 /// Takes in `[L1TxEntry]`, checks status and then either publishes or checks for confirmations and
 /// returns its updated status. Returns None if status is not changed
 async fn handle_entry(
@@ -116,6 +196,8 @@ async fn handle_entry(
         .get_txid_async(idx)
         .await?
         .ok_or(BroadcasterError::TxNotFound(idx))?;
+    // Watch the latest replacement if the fee was bumped with RBF.
+    let txid = txentry.replacement_txid().unwrap_or(txid);
     match txentry.status {
         L1TxStatus::Unpublished => {
             // Try to publish
@@ -454,6 +536,7 @@ mod test {
             ops,
             cl.as_ref(),
             params.as_ref(),
+            None,
         )
         .await
         .unwrap();
@@ -0,0 +1,185 @@
+//! Push-based notifications of new L1 blocks through bitcoind's ZMQ interface.
+//!
+//! The notifications are only used as a hint to poll the client right away, so the reader keeps
+//! handling reorgs and missed blocks the same way it does when polling. ZMQ may drop messages, so
+//! the reader still polls every [`NOTIFIED_POLL_DUR`] while the subscription is up, and falls back
+//! to the regular poll interval whenever it is down.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::sync::mpsc;
+use tracing::*;
+use zeromq::{Socket, SocketRecv, SubSocket};
+
+/// Topics published by bitcoind when a new block is connected.
+const BLOCK_TOPICS: [&str; 2] = ["hashblock", "rawblock"];
+
+/// Time between polls while notifications are being received, in case one got lost.
+pub const NOTIFIED_POLL_DUR: Duration = Duration::from_secs(30);
+
+/// Time to wait before reconnecting after the subscription failed.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Receiving end of the block notifications, used by the reader to decide when to poll.
+#[derive(Debug)]
+pub struct BlockNotifications {
+    rx: mpsc::Receiver<()>,
+    connected: Arc<AtomicBool>,
+}
+
+impl BlockNotifications {
+    /// Whether the subscription is currently up.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Waits until the reader should poll the client again.
+    ///
+    /// This returns early when a new block is announced while the subscription is up, and after
+    /// `poll_dur` otherwise.
+    pub async fn wait(&mut self, poll_dur: Duration) {
+        if !self.is_connected() {
+            tokio::time::sleep(poll_dur).await;
+            return;
+        }
+
+        tokio::select! {
+            _ = self.rx.recv() => {
+                trace!("got new block notification");
+            }
+            _ = tokio::time::sleep(NOTIFIED_POLL_DUR) => {}
+        }
+    }
+}
+
+/// Creates the [`BlockNotifications`] for the bitcoind ZMQ endpoint at `url` (e.g.
+/// `tcp://127.0.0.1:28332`) along with the task that keeps the subscription alive.
+///
+/// bitcoind has to publish either `hashblock` or `rawblock` on the endpoint.
+pub fn block_notifications(
+    url: String,
+) -> (
+    BlockNotifications,
+    impl std::future::Future<Output = anyhow::Result<()>>,
+) {
+    // A single pending notification is enough since the reader catches up on every poll
+    let (tx, rx) = mpsc::channel(1);
+    let connected = Arc::new(AtomicBool::new(false));
+
+    let notifications = BlockNotifications {
+        rx,
+        connected: connected.clone(),
+    };
+    (notifications, zmq_listener_task(url, tx, connected))
+}
+
+/// Subscribes to the block topics and forwards every message as a notification, reconnecting
+/// whenever the subscription fails.
+async fn zmq_listener_task(
+    url: String,
+    tx: mpsc::Sender<()>,
+    connected: Arc<AtomicBool>,
+) -> anyhow::Result<()> {
+    info!(%url, "starting L1 block notification listener");
+    loop {
+        let err = match subscribe(&url).await {
+            Ok(mut socket) => {
+                info!(%url, "subscribed to L1 block notifications");
+                connected.store(true, Ordering::Relaxed);
+                listen(&mut socket, &tx).await
+            }
+            Err(err) => err,
+        };
+
+        if tx.is_closed() {
+            // The reader is gone, so there's nobody to notify
+            return Ok(());
+        }
+
+        connected.store(false, Ordering::Relaxed);
+        warn!(%url, %err, "L1 block notifications unavailable, falling back to polling");
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn subscribe(url: &str) -> anyhow::Result<SubSocket> {
+    let mut socket = SubSocket::new();
+    socket.connect(url).await?;
+    for topic in BLOCK_TOPICS {
+        socket.subscribe(topic).await?;
+    }
+    Ok(socket)
+}
+
+/// Forwards notifications until receiving fails, returning the error.
+async fn listen(socket: &mut SubSocket, tx: &mpsc::Sender<()>) -> anyhow::Error {
+    loop {
+        match socket.recv().await {
+            Ok(msg) => {
+                let topic = msg
+                    .get(0)
+                    .map(|topic| String::from_utf8_lossy(topic).into_owned());
+                trace!(?topic, "received ZMQ message");
+
+                // A full channel already has a notification pending
+                if let Err(mpsc::error::TrySendError::Closed(_)) = tx.try_send(()) {
+                    return anyhow::anyhow!("reader stopped listening");
+                }
+            }
+            Err(err) => return err.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::TcpListener;
+
+    use corepc_node::{BitcoinD, Conf};
+
+    use super::*;
+    use crate::test_utils::corepc_node_helpers::mine_blocks;
+
+    #[tokio::test]
+    async fn test_block_notifications() {
+        // Find a free port for bitcoind to publish on
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url = format!("tcp://127.0.0.1:{port}");
+        let zmq_arg = format!("-zmqpubhashblock={url}");
+
+        let mut conf = Conf::default();
+        conf.args.push(&zmq_arg);
+        let bitcoind = BitcoinD::from_downloaded_with_conf(&conf).unwrap();
+
+        let (mut notifications, listener) = block_notifications(url);
+        tokio::spawn(listener);
+
+        // Wait for the subscription to be up
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !notifications.is_connected() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("should subscribe");
+
+        // ZMQ subscriptions take a moment to propagate to the publisher
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        mine_blocks(&bitcoind, 1, None).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), notifications.rx.recv())
+            .await
+            .expect("should be notified of the new block")
+            .expect("listener should be running");
+    }
+}
@@ -6,9 +6,15 @@ use std::{
 
 use anyhow::bail;
 use bitcoin::{Block, BlockHash};
-use strata_state::l1::{
-    get_btc_params, get_difficulty_adjustment_height, BtcParams, HeaderVerificationState,
-    L1BlockId, TimestampStore,
+use metrics::{counter, gauge};
+use strata_common::metrics::{L1_READER_HEIGHT, L1_READER_REORGS};
+use strata_primitives::params::RollupParams;
+use strata_state::{
+    bridge_state::OperatorTable,
+    l1::{
+        get_btc_params, get_difficulty_adjustment_height, BtcParams, HeaderVerificationState,
+        L1BlockId, TimestampStore,
+    },
 };
 use strata_status::StatusChannel;
 use strata_tx_parser::{
@@ -20,7 +26,7 @@ use tokio::sync::mpsc;
 use tracing::*;
 
 use crate::{
-    reader::{config::ReaderConfig, state::ReaderState},
+    reader::{config::ReaderConfig, notify::BlockNotifications, state::ReaderState},
     rpc::traits::Reader,
     status::{apply_status_updates, L1StatusUpdate},
 };
@@ -38,12 +44,16 @@ struct ReaderContext<R: Reader> {
 }
 
 /// The main task that initializes the reader state and starts reading from bitcoin.
+///
+/// If `notifications` are provided, the client gets polled as soon as a new block is announced
+/// instead of waiting for the poll interval.
 pub async fn bitcoin_data_reader_task(
     client: Arc<impl Reader>,
     event_tx: mpsc::Sender<L1Event>,
     target_next_block: u64,
     config: Arc<ReaderConfig>,
     status_channel: StatusChannel,
+    notifications: Option<BlockNotifications>,
 ) -> anyhow::Result<()> {
     let ctx = ReaderContext {
         client,
@@ -51,13 +61,14 @@ pub async fn bitcoin_data_reader_task(
         config,
         status_channel,
     };
-    do_reader_task(ctx, target_next_block).await
+    do_reader_task(ctx, target_next_block, notifications).await
 }
 
 /// Inner function that actually does the reading task.
 async fn do_reader_task<R: Reader>(
     ctx: ReaderContext<R>,
     target_next_block: u64,
+    mut notifications: Option<BlockNotifications>,
 ) -> anyhow::Result<()> {
     info!(%target_next_block, "started L1 reader task!");
 
@@ -99,7 +110,10 @@ async fn do_reader_task<R: Reader>(
             }
         }
 
-        tokio::time::sleep(poll_dur).await;
+        match notifications.as_mut() {
+            Some(notifications) => notifications.wait(poll_dur).await,
+            None => tokio::time::sleep(poll_dur).await,
+        }
 
         status_updates.push(L1StatusUpdate::LastUpdate(
             SystemTime::now()
@@ -146,11 +160,18 @@ async fn update_epoch_and_filter_config<R: Reader>(
     // TODO: check if new_epoch < current epoch. should panic if so?
     let curr_epoch = state.epoch();
 
-    // if new epoch
-    if curr_epoch != new_epoch {
+    // The operator set only changes at epoch boundaries, but we also check for it separately in
+    // case we missed the boundary.
+    let operator_table = ctx.status_channel.operator_table();
+    let new_seqno = operator_table
+        .as_ref()
+        .map_or(0, OperatorTable::next_update_seqno);
+
+    // if new epoch or operator set
+    if curr_epoch != new_epoch || state.operator_update_seqno() != new_seqno {
         state.set_epoch(new_epoch);
-        // TODO: pass in chainstate to `derive_from`
-        let new_config = TxFilterConfig::derive_from(ctx.config.params.rollup())?;
+        state.set_operator_update_seqno(new_seqno);
+        let new_config = derive_filter_config(ctx.config.params.rollup(), operator_table.as_ref())?;
         let curr_filter_config = state.filter_config().clone();
 
         if new_config != curr_filter_config {
@@ -161,6 +182,20 @@ async fn update_epoch_and_filter_config<R: Reader>(
     Ok(None)
 }
 
+/// Derives the filter config, expecting deposits to the bridge address of the given operator
+/// table if it has been updated since genesis.
+fn derive_filter_config(
+    rollup_params: &RollupParams,
+    operator_table: Option<&OperatorTable>,
+) -> anyhow::Result<TxFilterConfig> {
+    match operator_table {
+        Some(table) if table.next_update_seqno() > 0 => {
+            TxFilterConfig::derive_from_operator_table(rollup_params, table)
+        }
+        _ => TxFilterConfig::derive_from(rollup_params),
+    }
+}
+
 /// Inits the reader state by trying to backfill blocks up to a target height.
 async fn init_reader_state<R: Reader>(
     ctx: &ReaderContext<R>,
@@ -197,15 +232,19 @@ async fn init_reader_state<R: Reader>(
     }
 
     let params = ctx.config.params.clone();
-    let filter_config = TxFilterConfig::derive_from(params.rollup())?;
+    let operator_table = ctx.status_channel.operator_table();
+    let filter_config = derive_filter_config(params.rollup(), operator_table.as_ref())?;
     let epoch = ctx.status_channel.epoch().unwrap_or(0);
-    let state = ReaderState::new(
+    let mut state = ReaderState::new(
         real_cur_height + 1,
         lookback,
         init_queue,
         filter_config,
         epoch,
     );
+    if let Some(table) = operator_table {
+        state.set_operator_update_seqno(table.next_update_seqno());
+    }
     Ok(state)
 }
 
@@ -233,6 +272,7 @@ async fn poll_for_new_blocks<R: Reader>(
     if let Some((pivot_height, pivot_blkid)) = find_pivot_block(ctx.client.as_ref(), state).await? {
         if pivot_height < state.best_block_idx() {
             info!(%pivot_height, %pivot_blkid, "found apparent reorg");
+            counter!(L1_READER_REORGS).increment(1);
             state.rollback_to_height(pivot_height);
             let revert_ev = L1Event::RevertTo(pivot_height);
             if ctx.event_tx.send(revert_ev).await.is_err() {
@@ -259,6 +299,7 @@ async fn poll_for_new_blocks<R: Reader>(
             }
         };
         info!(%fetch_height, %l1blkid, "accepted new block");
+        gauge!(L1_READER_HEIGHT).set(fetch_height as f64);
     }
 
     Ok(())
@@ -465,6 +506,7 @@ mod test {
                 )
                 .unwrap(),
             },
+            sequencer_pubkeys: None,
         }
     }
 
@@ -20,6 +20,10 @@ pub struct ReaderState {
 
     /// Current epoch
     epoch: u64,
+
+    /// Sequence number of the next operator update, as of the last time we
+    /// derived the filter config.
+    operator_update_seqno: u64,
 }
 
 impl ReaderState {
@@ -39,6 +43,7 @@ impl ReaderState {
             recent_blocks,
             filter_config,
             epoch,
+            operator_update_seqno: 0,
         }
     }
 
@@ -58,6 +63,14 @@ impl ReaderState {
         self.epoch = epoch;
     }
 
+    pub fn operator_update_seqno(&self) -> u64 {
+        self.operator_update_seqno
+    }
+
+    pub(crate) fn set_operator_update_seqno(&mut self, seqno: u64) {
+        self.operator_update_seqno = seqno;
+    }
+
     pub fn best_block(&self) -> &BlockHash {
         self.recent_blocks.back().unwrap()
     }
@@ -27,9 +27,9 @@ use crate::rpc::{
     error::{BitcoinRpcError, ClientError},
     traits::{Broadcaster, Reader, Signer, Wallet},
     types::{
-        CreateWallet, GetBlockVerbosityZero, GetBlockchainInfo, GetNewAddress, GetTransaction,
-        ImportDescriptor, ImportDescriptorResult, ListDescriptors, ListTransactions, ListUnspent,
-        SignRawTransactionWithWallet,
+        BumpFee, CreateWallet, FundRawTransaction, GetBlockVerbosityZero, GetBlockchainInfo,
+        GetNewAddress, GetTransaction, ImportDescriptor, ImportDescriptorResult, ListDescriptors,
+        ListTransactions, ListUnspent, SignRawTransactionWithWallet,
     },
 };
 
@@ -307,6 +307,18 @@ impl Wallet for BitcoinClient {
     async fn list_wallets(&self) -> ClientResult<Vec<String>> {
         self.call::<Vec<String>>("listwallets", &[]).await
     }
+
+    async fn fund_raw_transaction(&self, tx: &Transaction) -> ClientResult<FundRawTransaction> {
+        let tx_hex = serialize_hex(tx);
+        trace!(tx_hex = %tx_hex, "Funding transaction");
+        self.call::<FundRawTransaction>("fundrawtransaction", &[to_value(tx_hex)?])
+            .await
+    }
+
+    async fn bump_fee(&self, txid: &Txid) -> ClientResult<BumpFee> {
+        self.call::<BumpFee>("bumpfee", &[to_value(txid.to_string())?])
+            .await
+    }
 }
 
 #[async_trait]
@@ -39,6 +39,10 @@ pub enum ClientError {
     #[error("{0}")]
     Body(String),
 
+    /// The requested resource does not exist, such as an unknown block or tx
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     /// HTTP status error, not retryable
     #[error("Obtained failure status({0}): {1}")]
     Status(String, String),
@@ -94,16 +98,24 @@ pub enum ClientError {
 
 impl ClientError {
     pub fn is_tx_not_found(&self) -> bool {
-        matches!(self, Self::Server(-5, _))
+        matches!(self, Self::Server(-5, _) | Self::NotFound(_))
     }
 
     pub fn is_block_not_found(&self) -> bool {
-        matches!(self, Self::Server(-5, _))
+        matches!(self, Self::Server(-5, _) | Self::NotFound(_))
     }
 
     pub fn is_missing_or_invalid_input(&self) -> bool {
         matches!(self, Self::Server(-26, _)) || matches!(self, Self::Server(-25, _))
     }
+
+    pub fn is_insufficient_funds(&self) -> bool {
+        match self {
+            Self::Server(-6, _) => true,
+            Self::Server(-4, msg) => msg.contains("Insufficient funds"),
+            _ => false,
+        }
+    }
 }
 
 impl From<SerdeJsonError> for ClientError {
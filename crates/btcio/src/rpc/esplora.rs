@@ -0,0 +1,458 @@
+//! An Esplora HTTP API backed implementation of the [`Reader`] and [`Broadcaster`] traits.
+//!
+//! This allows following L1 without running a `bitcoind` next to the client. Esplora does not
+//! expose everything that `bitcoind` does, so the parts of [`GetBlockchainInfo`] that it can't
+//! provide are filled with defaults.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use async_trait::async_trait;
+use bitcoin::{
+    consensus::{self, encode::serialize_hex},
+    Block, BlockHash, Network, Transaction, Txid,
+};
+use reqwest::{Client, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::*;
+
+use crate::rpc::{
+    client::ClientResult,
+    error::ClientError,
+    traits::{Broadcaster, Reader},
+    types::GetBlockchainInfo,
+};
+
+/// Fee rate used if the Esplora instance does not have any estimates, in sat/vB.
+const FALLBACK_FEE_RATE: u64 = 1;
+
+/// An `async` client for interacting with an Esplora HTTP API.
+#[derive(Debug)]
+pub struct EsploraClient {
+    /// The base URL of the Esplora API, without a trailing slash.
+    url: String,
+    /// The underlying `async` HTTP client.
+    client: Client,
+    /// The network the Esplora instance follows, since it can't be queried.
+    network: Network,
+}
+
+/// Confirmation status of a transaction as reported by Esplora.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EsploraTxStatus {
+    /// Whether the transaction is included in a block.
+    pub confirmed: bool,
+    /// Height of the block including the transaction, if confirmed.
+    pub block_height: Option<u64>,
+    /// Hash of the block including the transaction, if confirmed.
+    pub block_hash: Option<BlockHash>,
+}
+
+/// Summary of a block as returned by the `/block/:hash` endpoint.
+#[derive(Clone, Debug, Deserialize)]
+struct EsploraBlock {
+    height: u64,
+    mediantime: u64,
+    difficulty: f64,
+}
+
+impl EsploraClient {
+    /// Creates a new [`EsploraClient`] for the Esplora API at the given URL.
+    pub fn new(url: &str, network: Network) -> ClientResult<Self> {
+        let client = Client::builder()
+            .build()
+            .map_err(|e| ClientError::Other(format!("Could not create client: {e}")))?;
+
+        let url = url.trim_end_matches('/').to_string();
+        trace!(%url, "Created esplora client");
+
+        Ok(Self {
+            url,
+            client,
+            network,
+        })
+    }
+
+    /// Gets the confirmation status of a transaction.
+    pub async fn get_tx_status(&self, txid: &Txid) -> ClientResult<EsploraTxStatus> {
+        self.get_json(&format!("/tx/{txid}/status")).await
+    }
+
+    async fn get(&self, path: &str) -> ClientResult<Response> {
+        trace!(%path, "Calling esplora");
+        let response = self
+            .client
+            .get(format!("{}{path}", self.url))
+            .send()
+            .await
+            .map_err(map_reqwest_error)?;
+        check_status(response).await
+    }
+
+    async fn get_text<T>(&self, path: &str) -> ClientResult<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let text = self
+            .get(path)
+            .await?
+            .text()
+            .await
+            .map_err(|e| ClientError::Body(e.to_string()))?;
+        text.trim()
+            .parse()
+            .map_err(|e: T::Err| ClientError::Parse(e.to_string()))
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> ClientResult<T> {
+        self.get(path)
+            .await?
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse(e.to_string()))
+    }
+}
+
+/// Turns non-success HTTP statuses into errors, keeping the body as the message.
+async fn check_status(response: Response) -> ClientResult<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let message = response.text().await.unwrap_or_default();
+    Err(match status {
+        StatusCode::NOT_FOUND => ClientError::NotFound(message),
+        _ => ClientError::Status(status.to_string(), message),
+    })
+}
+
+fn map_reqwest_error(err: reqwest::Error) -> ClientError {
+    if err.is_connect() {
+        ClientError::Connection(err.to_string())
+    } else if err.is_timeout() {
+        ClientError::Timeout
+    } else if err.is_builder() {
+        ClientError::ReqBuilder(err.to_string())
+    } else {
+        ClientError::Request(err.to_string())
+    }
+}
+
+/// Picks the estimate for the lowest confirmation target that is at least `conf_target`, or the
+/// one for the highest target if there's none.
+fn pick_fee_estimate(estimates: &HashMap<String, f64>, conf_target: u16) -> Option<f64> {
+    let mut estimates: Vec<(u16, f64)> = estimates
+        .iter()
+        .filter_map(|(target, rate)| Some((target.parse().ok()?, *rate)))
+        .collect();
+    estimates.sort_by_key(|(target, _)| *target);
+
+    estimates
+        .iter()
+        .find(|(target, _)| *target >= conf_target)
+        .or(estimates.last())
+        .map(|(_, rate)| *rate)
+}
+
+#[async_trait]
+impl Reader for EsploraClient {
+    async fn estimate_smart_fee(&self, conf_target: u16) -> ClientResult<u64> {
+        let estimates = self
+            .get_json::<HashMap<String, f64>>("/fee-estimates")
+            .await?;
+
+        // round up to sat/vB
+        Ok(pick_fee_estimate(&estimates, conf_target)
+            .map(|rate| rate.ceil() as u64)
+            .unwrap_or(FALLBACK_FEE_RATE))
+    }
+
+    async fn get_block(&self, hash: &BlockHash) -> ClientResult<Block> {
+        let raw = self
+            .get(&format!("/block/{hash}/raw"))
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| ClientError::Body(e.to_string()))?;
+        consensus::deserialize(&raw).map_err(|e| ClientError::Other(format!("block decode: {e}")))
+    }
+
+    async fn get_block_at(&self, height: u64) -> ClientResult<Block> {
+        let hash = self.get_block_hash(height).await?;
+        self.get_block(&hash).await
+    }
+
+    async fn get_block_count(&self) -> ClientResult<u64> {
+        self.get_text("/blocks/tip/height").await
+    }
+
+    async fn get_block_hash(&self, height: u64) -> ClientResult<BlockHash> {
+        self.get_text(&format!("/block-height/{height}")).await
+    }
+
+    async fn get_blockchain_info(&self) -> ClientResult<GetBlockchainInfo> {
+        let tip_hash: BlockHash = self.get_text("/blocks/tip/hash").await?;
+        let tip: EsploraBlock = self.get_json(&format!("/block/{tip_hash}")).await?;
+
+        Ok(GetBlockchainInfo {
+            chain: self.network.to_core_arg().to_string(),
+            blocks: tip.height,
+            headers: tip.height,
+            best_block_hash: tip_hash.to_string(),
+            difficulty: tip.difficulty,
+            median_time: tip.mediantime,
+            verification_progress: 1.0,
+            initial_block_download: false,
+            chain_work: String::new(),
+            size_on_disk: 0,
+            pruned: false,
+            prune_height: None,
+            automatic_pruning: None,
+            prune_target_size: None,
+        })
+    }
+
+    async fn get_raw_mempool(&self) -> ClientResult<Vec<Txid>> {
+        self.get_json("/mempool/txids").await
+    }
+
+    async fn network(&self) -> ClientResult<Network> {
+        Ok(self.network)
+    }
+}
+
+#[async_trait]
+impl Broadcaster for EsploraClient {
+    async fn send_raw_transaction(&self, tx: &Transaction) -> ClientResult<Txid> {
+        let txstr = serialize_hex(tx);
+        trace!(txstr = %txstr, "Sending raw transaction");
+        let response = self
+            .client
+            .post(format!("{}/tx", self.url))
+            .body(txstr)
+            .send()
+            .await
+            .map_err(map_reqwest_error)?;
+        let txid = check_status(response)
+            .await?
+            .text()
+            .await
+            .map_err(|e| ClientError::Body(e.to_string()))?
+            .trim()
+            .parse::<Txid>()
+            .map_err(|e| ClientError::Parse(e.to_string()))?;
+        trace!(?txid, "Transaction sent");
+        Ok(txid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+    use bitcoin::{
+        absolute::LockTime, block::Header, hashes::Hash, transaction::Version, Amount,
+        CompactTarget, OutPoint, ScriptBuf, TxIn, TxMerkleNode, TxOut,
+    };
+    use hyper::{
+        body::to_bytes,
+        service::{make_service_fn, service_fn},
+        Body, Method, Request, Server,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    /// Blocks served by the mock Esplora server, indexed by height.
+    struct MockEsplora {
+        blocks: Vec<Block>,
+    }
+
+    impl MockEsplora {
+        fn new(count: u32) -> Self {
+            let mut blocks: Vec<Block> = vec![];
+            for nonce in 0..count {
+                let prev_blockhash = blocks
+                    .last()
+                    .map(|b| b.block_hash())
+                    .unwrap_or(BlockHash::all_zeros());
+                blocks.push(Block {
+                    header: Header {
+                        version: bitcoin::block::Version::ONE,
+                        prev_blockhash,
+                        merkle_root: TxMerkleNode::all_zeros(),
+                        time: 1_700_000_000 + nonce,
+                        bits: CompactTarget::from_consensus(0x207fffff),
+                        nonce,
+                    },
+                    txdata: vec![],
+                });
+            }
+            Self { blocks }
+        }
+
+        fn find(&self, hash: &str) -> Option<(usize, &Block)> {
+            self.blocks
+                .iter()
+                .enumerate()
+                .find(|(_, b)| b.block_hash().to_string() == hash)
+        }
+
+        fn respond(&self, method: &Method, path: &str, body: &[u8]) -> (u16, Vec<u8>) {
+            let tip = self.blocks.len() - 1;
+            let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+            match (method, segments.as_slice()) {
+                (&Method::GET, ["blocks", "tip", "height"]) => (200, tip.to_string().into()),
+                (&Method::GET, ["blocks", "tip", "hash"]) => {
+                    (200, self.blocks[tip].block_hash().to_string().into())
+                }
+                (&Method::GET, ["block-height", height]) => {
+                    match height
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|h| self.blocks.get(h))
+                    {
+                        Some(block) => (200, block.block_hash().to_string().into()),
+                        None => (404, b"Block not found".to_vec()),
+                    }
+                }
+                (&Method::GET, ["block", hash, "raw"]) => match self.find(hash) {
+                    Some((_, block)) => (200, consensus::serialize(block)),
+                    None => (404, b"Block not found".to_vec()),
+                },
+                (&Method::GET, ["block", hash]) => match self.find(hash) {
+                    Some((height, _)) => {
+                        let body = json!({
+                            "id": hash,
+                            "height": height,
+                            "mediantime": 1_700_000_000,
+                            "difficulty": 1.0,
+                        });
+                        (200, body.to_string().into())
+                    }
+                    None => (404, b"Block not found".to_vec()),
+                },
+                (&Method::GET, ["tx", _, "status"]) => {
+                    let body = json!({
+                        "confirmed": true,
+                        "block_height": tip,
+                        "block_hash": self.blocks[tip].block_hash(),
+                        "block_time": 1_700_000_000,
+                    });
+                    (200, body.to_string().into())
+                }
+                (&Method::GET, ["fee-estimates"]) => {
+                    let body = json!({ "1": 20.5, "6": 10.2, "144": 1.0 });
+                    (200, body.to_string().into())
+                }
+                (&Method::GET, ["mempool", "txids"]) => (200, b"[]".to_vec()),
+                (&Method::POST, ["tx"]) => {
+                    let tx: Transaction =
+                        consensus::encode::deserialize_hex(std::str::from_utf8(body).unwrap())
+                            .unwrap();
+                    (200, tx.compute_txid().to_string().into())
+                }
+                _ => (404, b"Not found".to_vec()),
+            }
+        }
+    }
+
+    /// Starts serving the mock Esplora API on a random local port and returns its URL.
+    async fn start_mock_server(mock: MockEsplora) -> String {
+        let mock = Arc::new(mock);
+        let make_svc = make_service_fn(move |_conn| {
+            let mock = mock.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let mock = mock.clone();
+                    async move {
+                        let method = req.method().clone();
+                        let path = req.uri().path().to_string();
+                        let body = to_bytes(req.into_body()).await.unwrap();
+                        let (status, body) = mock.respond(&method, &path, &body);
+                        Ok::<_, Infallible>(
+                            hyper::Response::builder()
+                                .status(status)
+                                .body(Body::from(body))
+                                .unwrap(),
+                        )
+                    }
+                }))
+            }
+        });
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+        let server = Server::bind(&addr).serve(make_svc);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    #[tokio::test]
+    async fn test_esplora_reader() {
+        let mock = MockEsplora::new(5);
+        let blocks = mock.blocks.clone();
+        let url = start_mock_server(mock).await;
+        let client = EsploraClient::new(&format!("{url}/"), Network::Regtest).unwrap();
+
+        assert_eq!(client.network().await.unwrap(), Network::Regtest);
+        assert_eq!(client.get_block_count().await.unwrap(), 4);
+        assert_eq!(
+            client.get_block_hash(2).await.unwrap(),
+            blocks[2].block_hash()
+        );
+        assert_eq!(client.get_block_at(3).await.unwrap(), blocks[3]);
+        assert_eq!(
+            client.get_block(&blocks[1].block_hash()).await.unwrap(),
+            blocks[1]
+        );
+
+        let info = client.get_blockchain_info().await.unwrap();
+        assert_eq!(info.blocks, 4);
+        assert_eq!(info.best_block_hash, blocks[4].block_hash().to_string());
+
+        assert_eq!(client.estimate_smart_fee(1).await.unwrap(), 21);
+        assert_eq!(client.estimate_smart_fee(3).await.unwrap(), 11);
+        assert_eq!(client.estimate_smart_fee(1000).await.unwrap(), 1);
+        assert!(client.get_raw_mempool().await.unwrap().is_empty());
+
+        let status = client.get_tx_status(&Txid::all_zeros()).await.unwrap();
+        assert!(status.confirmed);
+        assert_eq!(status.block_height, Some(4));
+        assert_eq!(status.block_hash, Some(blocks[4].block_hash()));
+    }
+
+    #[tokio::test]
+    async fn test_esplora_not_found() {
+        let url = start_mock_server(MockEsplora::new(1)).await;
+        let client = EsploraClient::new(&url, Network::Regtest).unwrap();
+
+        let err = client.get_block_at(10).await.unwrap_err();
+        assert!(err.is_block_not_found(), "unexpected error {err:?}");
+
+        let err = client.get_block(&BlockHash::all_zeros()).await.unwrap_err();
+        assert!(err.is_block_not_found(), "unexpected error {err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_esplora_broadcast() {
+        let url = start_mock_server(MockEsplora::new(1)).await;
+        let client = EsploraClient::new(&url, Network::Regtest).unwrap();
+
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::all_zeros(), 0),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(1_000),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        };
+        let txid = client.send_raw_transaction(&tx).await.unwrap();
+        assert_eq!(txid, tx.compute_txid());
+    }
+}
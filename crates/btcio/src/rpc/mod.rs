@@ -1,5 +1,6 @@
 pub mod client;
 pub mod error;
+pub mod esplora;
 pub mod traits;
 pub mod types;
 
@@ -4,8 +4,8 @@ use bitcoin::{bip32::Xpriv, Address, Block, BlockHash, Network, Transaction, Txi
 use crate::rpc::{
     client::ClientResult,
     types::{
-        GetBlockchainInfo, GetTransaction, ImportDescriptor, ImportDescriptorResult,
-        ListTransactions, ListUnspent, SignRawTransactionWithWallet,
+        BumpFee, FundRawTransaction, GetBlockchainInfo, GetTransaction, ImportDescriptor,
+        ImportDescriptorResult, ListTransactions, ListUnspent, SignRawTransactionWithWallet,
     },
 };
 
@@ -128,6 +128,24 @@ pub trait Wallet {
 
     /// Lists all wallets in the underlying Bitcoin client.
     async fn list_wallets(&self) -> ClientResult<Vec<String>>;
+
+    /// Adds inputs from the underlying Bitcoin client's wallet to the
+    /// transaction until it covers its outputs and the fee, adding a change
+    /// output if needed.
+    ///
+    /// # Note
+    ///
+    /// The added inputs are not signed, see
+    /// [`Signer::sign_raw_transaction_with_wallet`].
+    async fn fund_raw_transaction(&self, tx: &Transaction) -> ClientResult<FundRawTransaction>;
+
+    /// Replaces an unconfirmed transaction of the underlying Bitcoin client's
+    /// wallet with one paying a higher fee, which is signed and broadcasted.
+    ///
+    /// # Note
+    ///
+    /// The transaction must signal replaceability as per BIP 125.
+    async fn bump_fee(&self, txid: &Txid) -> ClientResult<BumpFee>;
 }
 
 /// Signing functionality that any Bitcoin client **with private keys** that
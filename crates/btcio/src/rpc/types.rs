@@ -294,9 +294,11 @@ pub struct ListUnspent {
 /// for all other categories.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct ListTransactions {
-    /// The Bitcoin address.
-    #[serde(deserialize_with = "deserialize_address")]
-    pub address: Address<NetworkUnchecked>,
+    /// The Bitcoin address, if the output has one.
+    ///
+    /// Outputs without an address, such as `OP_RETURN` outputs, are listed without it.
+    #[serde(default, deserialize_with = "deserialize_address_option")]
+    pub address: Option<Address<NetworkUnchecked>>,
     /// Category of the transaction.
     category: TransactionCategory,
     /// The signed amount in BTC.
@@ -317,6 +319,40 @@ pub struct ListTransactions {
     pub txid: Txid,
 }
 
+/// Models the result of JSON-RPC method `fundrawtransaction`.
+///
+/// # Note
+///
+/// The inputs are added from the underlying Bitcoin client's wallet and are
+/// not signed yet.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct FundRawTransaction {
+    /// The funded transaction.
+    #[serde(deserialize_with = "deserialize_tx")]
+    pub hex: Transaction,
+    /// The fee added to the transaction in BTC.
+    #[serde(deserialize_with = "deserialize_bitcoin")]
+    pub fee: Amount,
+    /// The position of the added change output, or -1 if none was added.
+    pub changepos: i32,
+}
+
+/// Models the result of JSON-RPC method `bumpfee`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct BumpFee {
+    /// The id of the replacement transaction.
+    #[serde(deserialize_with = "deserialize_txid")]
+    pub txid: Txid,
+    /// The fee of the replaced transaction in BTC.
+    #[serde(deserialize_with = "deserialize_bitcoin")]
+    pub origfee: Amount,
+    /// The fee of the replacement transaction in BTC.
+    #[serde(deserialize_with = "deserialize_bitcoin")]
+    pub fee: Amount,
+    /// Errors encountered during processing, if any.
+    pub errors: Vec<String>,
+}
+
 /// Models the result of JSON-RPC method `signrawtransactionwithwallet`.
 ///
 /// # Note
@@ -532,6 +568,20 @@ where
     deserializer.deserialize_any(AddressVisitor)
 }
 
+/// Deserializes an optional address string into a proper [`Address`].
+fn deserialize_address_option<'d, D>(
+    deserializer: D,
+) -> Result<Option<Address<NetworkUnchecked>>, D::Error>
+where
+    D: Deserializer<'d>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(v) => deserialize_address(v.into_deserializer()).map(Some),
+        None => Ok(None),
+    }
+}
+
 /// Deserializes the blockhash string into proper [`BlockHash`]s.
 fn deserialize_blockhash<'d, D>(deserializer: D) -> Result<BlockHash, D::Error>
 where
@@ -10,6 +10,7 @@ pub enum L1StatusUpdate {
     CurTip(String),
     LastPublishedTxid(Txid),
     IncrementInscriptionCount,
+    WriterError(Option<String>),
 }
 
 pub async fn apply_status_updates(st_updates: &[L1StatusUpdate], st_chan: &StatusChannel) {
@@ -27,6 +28,7 @@ pub async fn apply_status_updates(st_updates: &[L1StatusUpdate], st_chan: &Statu
                 l1_status.last_published_txid = Some(Into::into(*txid))
             }
             L1StatusUpdate::IncrementInscriptionCount => l1_status.published_inscription_count += 1,
+            L1StatusUpdate::WriterError(err) => l1_status.writer_error = err.clone(),
         }
     }
 
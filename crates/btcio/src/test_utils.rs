@@ -12,12 +12,16 @@ use crate::{
     rpc::{
         traits::{Broadcaster, Reader, Signer, Wallet},
         types::{
-            GetBlockchainInfo, GetTransaction, ImportDescriptor, ImportDescriptorResult,
-            ListTransactions, ListUnspent, SignRawTransactionWithWallet,
+            BumpFee, FundRawTransaction, GetBlockchainInfo, GetTransaction, ImportDescriptor,
+            ImportDescriptorResult, ListTransactions, ListUnspent, SignRawTransactionWithWallet,
         },
         ClientResult,
     },
-    writer::builder::{build_reveal_transaction, generate_inscription_script, InscriptionError},
+    writer::builder::{
+        build_reveal_transaction, generate_batched_inscription_script,
+        generate_forced_inclusion_script, generate_inscription_script,
+        generate_operator_update_script, InscriptionError,
+    },
 };
 
 /// A test implementation of a Bitcoin client.
@@ -180,6 +184,23 @@ impl Wallet for TestBitcoinClient {
     async fn list_wallets(&self) -> ClientResult<Vec<String>> {
         Ok(vec![])
     }
+
+    async fn fund_raw_transaction(&self, tx: &Transaction) -> ClientResult<FundRawTransaction> {
+        Ok(FundRawTransaction {
+            hex: tx.clone(),
+            fee: Amount::ZERO,
+            changepos: -1,
+        })
+    }
+
+    async fn bump_fee(&self, txid: &Txid) -> ClientResult<BumpFee> {
+        Ok(BumpFee {
+            txid: *txid,
+            origfee: Amount::ZERO,
+            fee: Amount::ZERO,
+            errors: vec![],
+        })
+    }
 }
 
 #[async_trait]
@@ -219,6 +240,30 @@ pub fn generate_inscription_script_test(
     generate_inscription_script(inscription_data, rollup_name, version)
 }
 
+pub fn generate_batched_inscription_script_test(
+    inscriptions: &[InscriptionData],
+    rollup_name: &str,
+    version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    generate_batched_inscription_script(inscriptions, rollup_name, version)
+}
+
+pub fn generate_forced_inclusion_script_test(
+    payload: &[u8],
+    rollup_name: &str,
+    version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    generate_forced_inclusion_script(payload, rollup_name, version)
+}
+
+pub fn generate_operator_update_script_test(
+    update: &[u8],
+    rollup_name: &str,
+    version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    generate_operator_update_script(update, rollup_name, version)
+}
+
 pub fn build_reveal_transaction_test(
     input_transaction: Transaction,
     recipient: Address,
@@ -26,7 +26,9 @@ use bitcoin::{
 };
 use rand::{rngs::OsRng, RngCore};
 use strata_state::tx::InscriptionData;
-use strata_tx_parser::inscription::{BATCH_DATA_TAG, ROLLUP_NAME_TAG, VERSION_TAG};
+use strata_tx_parser::inscription::{
+    BATCH_DATA_TAG, FORCED_INCLUSION_TAG, OPERATOR_UPDATE_TAG, ROLLUP_NAME_TAG, VERSION_TAG,
+};
 use thiserror::Error;
 use tracing::trace;
 
@@ -47,6 +49,9 @@ pub enum InscriptionError {
     #[error("insufficient funds for tx (need {0} sats, have {1} sats)")]
     NotEnoughUtxos(u64, u64),
 
+    #[error("inscription fee exceeds the fee budget (need {0} sats, have {1} sats)")]
+    FeeBudgetExceeded(u64, u64),
+
     #[error("Error building taproot")]
     Taproot(#[from] TaprootBuilderError),
 
@@ -58,34 +63,87 @@ pub enum InscriptionError {
 // Btcio depends on `tx-parser`. So this file is behind a feature flag 'test-utils' and on dev
 // dependencies on `tx-parser`, we include {btcio, feature="strata_test_utils"} , so cyclic
 // dependency doesn't happen
+/// Builds the commit and reveal transactions for the payloads and returns them along with the total
+/// fee they pay.
+///
+/// Fails with [`InscriptionError::FeeBudgetExceeded`] if the fee is above `fee_budget`.
 pub async fn build_inscription_txs(
-    payload: &[u8],
+    payloads: &[Vec<u8>],
     rpc_client: &Arc<impl Reader + Wallet + Signer>,
     config: &WriterConfig,
-) -> anyhow::Result<(Transaction, Transaction)> {
-    let network = rpc_client.network().await?;
-    let utxos = rpc_client.get_utxos().await?;
+    fee_budget: Option<u64>,
+) -> Result<(Transaction, Transaction, u64), InscriptionError> {
+    let network = rpc_client.network().await.map_err(anyhow::Error::from)?;
+    let utxos = rpc_client.get_utxos().await.map_err(anyhow::Error::from)?;
 
     let fee_rate = match config.inscription_fee_policy {
-        InscriptionFeePolicy::Smart => rpc_client.estimate_smart_fee(1).await? * 2,
+        InscriptionFeePolicy::Smart { conf_target } => {
+            rpc_client
+                .estimate_smart_fee(conf_target)
+                .await
+                .map_err(anyhow::Error::from)?
+                * 2
+        }
         InscriptionFeePolicy::Fixed(val) => val,
     };
-    create_inscription_transactions(
+    let fee_rate = fee_rate.clamp(config.min_fee_rate, config.max_fee_rate);
+
+    let (commit, reveal) = create_inscription_transactions(
         &config.rollup_name,
-        payload,
-        utxos,
+        payloads,
+        utxos.clone(),
         config.sequencer_address.clone(),
         config.amount_for_reveal_txn,
         fee_rate,
         network,
-    )
-    .map_err(|e| anyhow::anyhow!(e.to_string()))
+    )?;
+
+    let fee = calculate_inscription_fee(&commit, &reveal, &utxos);
+    if let Some(budget) = fee_budget {
+        if fee > budget {
+            return Err(InscriptionError::FeeBudgetExceeded(fee, budget));
+        }
+    }
+
+    Ok((commit, reveal, fee))
 }
 
+/// Calculates the fees paid by the commit and reveal transactions together, given the wallet
+/// `utxos` that the commit spends.
+fn calculate_inscription_fee(
+    commit: &Transaction,
+    reveal: &Transaction,
+    utxos: &[ListUnspent],
+) -> u64 {
+    let input_total: u64 = commit
+        .input
+        .iter()
+        .filter_map(|txin| {
+            utxos.iter().find(|utxo| {
+                utxo.txid == txin.previous_output.txid && utxo.vout == txin.previous_output.vout
+            })
+        })
+        .map(|utxo| utxo.amount.to_sat())
+        .sum();
+
+    // The reveal output goes back to the sequencer, as does the change of the commit
+    let change: u64 = commit.output[1..]
+        .iter()
+        .map(|out| out.value.to_sat())
+        .sum();
+    let reveal_output: u64 = reveal.output.iter().map(|out| out.value.to_sat()).sum();
+
+    input_total.saturating_sub(change + reveal_output)
+}
+
+/// Creates the commit and reveal transactions inscribing the given payloads.
+///
+/// Each payload gets its own envelope in the reveal script, so that multiple blobs share the L1
+/// overhead of a single commit/reveal pair.
 #[allow(clippy::too_many_arguments)]
 pub fn create_inscription_transactions(
     rollup_name: &str,
-    write_intent: &[u8],
+    write_intents: &[Vec<u8>],
     utxos: Vec<ListUnspent>,
     recipient: Address,
     reveal_value: u64,
@@ -96,11 +154,14 @@ pub fn create_inscription_transactions(
     let key_pair = generate_key_pair()?;
     let public_key = XOnlyPublicKey::from_keypair(&key_pair).0;
 
-    let insc_data = InscriptionData::new(write_intent.to_vec());
+    let insc_data: Vec<InscriptionData> = write_intents
+        .iter()
+        .map(|intent| InscriptionData::new(intent.clone()))
+        .collect();
 
     // Start creating inscription content
     let reveal_script =
-        build_reveal_script(rollup_name, &public_key, insc_data, INSCRIPTION_VERSION)?;
+        build_reveal_script(rollup_name, &public_key, &insc_data, INSCRIPTION_VERSION)?;
 
     // Create spend info for tapscript
     let taproot_spend_info = TaprootBuilder::new()
@@ -397,11 +458,11 @@ pub fn generate_key_pair() -> Result<UntweakedKeypair, anyhow::Error> {
 }
 
 /// Builds reveal script such that it contains opcodes for verifying the internal key as well as the
-/// inscription block
+/// inscription blocks
 fn build_reveal_script(
     rollup_name: &str,
     taproot_public_key: &XOnlyPublicKey,
-    insc_data: InscriptionData,
+    insc_data: &[InscriptionData],
     version: u8,
 ) -> Result<ScriptBuf, anyhow::Error> {
     let mut script_bytes = script::Builder::new()
@@ -409,7 +470,7 @@ fn build_reveal_script(
         .push_opcode(OP_CHECKSIG)
         .into_script()
         .into_bytes();
-    let script = generate_inscription_script(insc_data, rollup_name, version)?;
+    let script = generate_batched_inscription_script(insc_data, rollup_name, version)?;
     script_bytes.extend(script.into_bytes());
     Ok(ScriptBuf::from(script_bytes))
 }
@@ -497,6 +558,58 @@ pub fn generate_inscription_script(
     inscription_data: InscriptionData,
     rollup_name: &str,
     version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    trace!(batchdata_size = %inscription_data.batch_data().len(), "Inserting batch data");
+    generate_envelope_script(
+        BATCH_DATA_TAG,
+        inscription_data.batch_data(),
+        rollup_name,
+        version,
+    )
+}
+
+// Generates a [`ScriptBuf`] that consists of one `OP_IF .. OP_ENDIF` block per inscription, one
+// after another
+pub fn generate_batched_inscription_script(
+    inscriptions: &[InscriptionData],
+    rollup_name: &str,
+    version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    let mut script_bytes = vec![];
+    for inscription_data in inscriptions {
+        let script = generate_inscription_script(inscription_data.clone(), rollup_name, version)?;
+        script_bytes.extend(script.into_bytes());
+    }
+    Ok(ScriptBuf::from(script_bytes))
+}
+
+// Generates a [`ScriptBuf`] that consists of `OP_IF .. OP_ENDIF` block carrying a raw EE
+// transaction to be force included
+pub fn generate_forced_inclusion_script(
+    payload: &[u8],
+    rollup_name: &str,
+    version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    trace!(payload_size = %payload.len(), "Inserting forced inclusion payload");
+    generate_envelope_script(FORCED_INCLUSION_TAG, payload, rollup_name, version)
+}
+
+// Generates a [`ScriptBuf`] that consists of `OP_IF .. OP_ENDIF` block carrying a borsh-encoded
+// sequencer-signed operator set update
+pub fn generate_operator_update_script(
+    update: &[u8],
+    rollup_name: &str,
+    version: u8,
+) -> anyhow::Result<ScriptBuf> {
+    trace!(update_size = %update.len(), "Inserting operator update");
+    generate_envelope_script(OPERATOR_UPDATE_TAG, update, rollup_name, version)
+}
+
+fn generate_envelope_script(
+    data_tag: &[u8],
+    data: &[u8],
+    rollup_name: &str,
+    version: u8,
 ) -> anyhow::Result<ScriptBuf> {
     let mut builder = script::Builder::new()
         .push_opcode(OP_FALSE)
@@ -505,11 +618,10 @@ pub fn generate_inscription_script(
         .push_slice(PushBytesBuf::try_from(rollup_name.as_bytes().to_vec())?)
         .push_slice(PushBytesBuf::try_from(VERSION_TAG.to_vec())?)
         .push_slice(PushBytesBuf::from([version]))
-        .push_slice(PushBytesBuf::try_from(BATCH_DATA_TAG.to_vec())?)
-        .push_int(inscription_data.batch_data().len() as i64);
+        .push_slice(PushBytesBuf::try_from(data_tag.to_vec())?)
+        .push_int(data.len() as i64);
 
-    trace!(batchdata_size = %inscription_data.batch_data().len(), "Inserting batch data");
-    for chunk in inscription_data.batch_data().chunks(520) {
+    for chunk in data.chunks(520) {
         trace!(size=%chunk.len(), "inserting chunk");
         builder = builder.push_slice(PushBytesBuf::try_from(chunk.to_vec())?);
     }
@@ -726,7 +838,7 @@ mod tests {
         let write_intent = vec![0u8; 100];
         let (commit, reveal) = super::create_inscription_transactions(
             rollup_name,
-            &write_intent,
+            &[write_intent],
             utxos.to_vec(),
             address.clone(),
             REVEAL_OUTPUT_AMOUNT,
@@ -766,5 +878,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_batched_inscription_transactions() {
+        let (rollup_name, _, _, _, address, utxos) = get_mock_data();
+
+        let write_intents = vec![vec![1u8; 100], vec![2u8; 1000], vec![3u8; 10]];
+        let (commit, reveal) = super::create_inscription_transactions(
+            rollup_name,
+            &write_intents,
+            utxos.to_vec(),
+            address.clone(),
+            REVEAL_OUTPUT_AMOUNT,
+            10,
+            bitcoin::Network::Bitcoin,
+        )
+        .unwrap();
+
+        assert_eq!(reveal.input.len(), 1, "reveal tx should have 1 input");
+        assert_eq!(
+            reveal.input[0].previous_output.txid,
+            commit.compute_txid(),
+            "reveal should use commit as input"
+        );
+
+        // The reveal script holds one envelope per intent
+        let reveal_script = reveal.input[0].witness.tapscript().unwrap();
+        let envelopes = reveal_script
+            .instructions()
+            .filter(|ins| matches!(ins, Ok(script::Instruction::Op(op)) if *op == OP_ENDIF))
+            .count();
+        assert_eq!(envelopes, write_intents.len());
+
+        // The commit output has to cover the larger reveal tx
+        let (single_commit, _) = super::create_inscription_transactions(
+            rollup_name,
+            &write_intents[..1],
+            utxos.to_vec(),
+            address,
+            REVEAL_OUTPUT_AMOUNT,
+            10,
+            bitcoin::Network::Bitcoin,
+        )
+        .unwrap();
+        assert!(commit.output[0].value > single_commit.output[0].value);
+    }
+
+    #[test]
+    fn test_calculate_inscription_fee() {
+        let (rollup_name, _, _, _, address, utxos) = get_mock_data();
+
+        let fee_rate = 10;
+        let (commit, reveal) = super::create_inscription_transactions(
+            rollup_name,
+            &[vec![0u8; 100]],
+            utxos.to_vec(),
+            address,
+            REVEAL_OUTPUT_AMOUNT,
+            fee_rate,
+            bitcoin::Network::Bitcoin,
+        )
+        .unwrap();
+
+        let fee = super::calculate_inscription_fee(&commit, &reveal, &utxos);
+        let commit_fee = utxos[2].amount.to_sat()
+            - commit
+                .output
+                .iter()
+                .map(|out| out.value.to_sat())
+                .sum::<u64>();
+        let reveal_fee = commit.output[0].value.to_sat() - reveal.output[0].value.to_sat();
+        assert_eq!(fee, commit_fee + reveal_fee);
+        assert!(fee >= (reveal.vsize() as u64) * fee_rate);
+    }
+
     // TODO: make the tests more comprehensive
 }
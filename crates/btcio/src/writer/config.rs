@@ -1,4 +1,5 @@
 use bitcoin::Address;
+pub use strata_config::InscriptionFeePolicy;
 
 #[derive(Debug, Clone)]
 pub struct WriterConfig {
@@ -14,28 +15,55 @@ pub struct WriterConfig {
     /// How should the inscription fee be determined
     pub(super) inscription_fee_policy: InscriptionFeePolicy,
 
+    /// Lower bound of the inscription fee rate, in sat/vB
+    pub(super) min_fee_rate: u64,
+
+    /// Upper bound of the inscription fee rate, in sat/vB
+    pub(super) max_fee_rate: u64,
+
+    /// Maximum amount(in sats) to spend on inscription fees per checkpoint epoch
+    pub(super) fee_budget_per_epoch: Option<u64>,
+
     /// How much amount(in sats) to send to reveal address
     pub(super) amount_for_reveal_txn: u64,
+
+    /// Maximum number of blobs that get inscribed in a single commit/reveal pair
+    pub(super) max_blobs_per_inscription: usize,
+
+    /// Maximum total size(in bytes) of the blobs that get inscribed in a single commit/reveal
+    /// pair. A single blob larger than this is still inscribed on its own.
+    pub(super) max_inscription_size: usize,
+
+    /// How long to wait for more blobs to fill up a batch before inscribing it, in millis
+    pub(super) batch_window_ms: u64,
 }
 
 impl WriterConfig {
-    pub fn new(sequencer_address: Address, rollup_name: String) -> anyhow::Result<Self> {
+    pub fn new(
+        sequencer_address: Address,
+        rollup_name: String,
+        config: &strata_config::WriterConfig,
+    ) -> anyhow::Result<Self> {
+        if config.min_fee_rate > config.max_fee_rate {
+            anyhow::bail!(
+                "writer min_fee_rate ({}) is above max_fee_rate ({})",
+                config.min_fee_rate,
+                config.max_fee_rate
+            );
+        }
+
         Ok(Self {
             sequencer_address,
             rollup_name,
-            // TODO: get these from config as well
-            inscription_fee_policy: InscriptionFeePolicy::Smart,
-            poll_duration_ms: 1_000,
-            amount_for_reveal_txn: 1_000,
+            inscription_fee_policy: config.fee_policy.clone(),
+            min_fee_rate: config.min_fee_rate,
+            max_fee_rate: config.max_fee_rate,
+            fee_budget_per_epoch: config.fee_budget_per_epoch,
+            poll_duration_ms: config.poll_duration_ms,
+            amount_for_reveal_txn: config.amount_for_reveal_txn,
+            max_blobs_per_inscription: config.max_blobs_per_inscription,
+            max_inscription_size: config.max_inscription_size,
+            batch_window_ms: config.batch_window_ms,
         })
     }
 }
-
-#[derive(Debug, Clone)]
-pub enum InscriptionFeePolicy {
-    /// Use estimatesmartfee.
-    Smart,
-
-    /// Fixed fee in sat/vB.
-    Fixed(u64),
-}
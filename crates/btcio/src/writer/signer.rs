@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use bitcoin::{consensus, Transaction};
-use strata_db::types::{BlobEntry, L1TxEntry};
+use strata_db::types::{BlobEntry, FeeBumpMethod, L1TxEntry};
 use strata_primitives::buf::Buf32;
 use tracing::*;
 
@@ -16,20 +16,28 @@ use crate::{
 
 type BlobIdx = u64;
 
-/// Create inscription transactions corresponding to a [`BlobEntry`].
+/// Create inscription transactions corresponding to a batch of [`BlobEntry`]s.
+///
+/// All the blobs are inscribed in the same reveal transaction, so the entries end up sharing a
+/// single commit/reveal pair.
 ///
 /// This is used during one of the cases:
 /// 1. A new blob intent needs to be signed
 /// 2. A signed intent needs to be resigned because somehow its inputs were spent/missing
 /// 3. A confirmed block that includes the tx gets reorged
+///
+/// Returns the txids of the commit and reveal transactions along with the fee they pay, which
+/// can't be more than `fee_budget`.
 pub async fn create_and_sign_blob_inscriptions(
-    blobentry: &BlobEntry,
+    blobentries: &[BlobEntry],
     broadcast_handle: &L1BroadcastHandle,
     client: Arc<impl Reader + Wallet + Signer>,
     config: &WriterConfig,
-) -> Result<(Buf32, Buf32), InscriptionError> {
+    fee_budget: Option<u64>,
+) -> Result<(Buf32, Buf32, u64), InscriptionError> {
     trace!("Creating and signing blob inscriptions");
-    let (commit, reveal) = build_inscription_txs(&blobentry.blob, &client, config).await?;
+    let blobs: Vec<Vec<u8>> = blobentries.iter().map(|e| e.blob.clone()).collect();
+    let (commit, reveal, fee) = build_inscription_txs(&blobs, &client, config, fee_budget).await?;
 
     let ctxid = commit.compute_txid();
     debug!(commit_txid = ?ctxid, "Signing commit transaction");
@@ -44,7 +52,9 @@ pub async fn create_and_sign_blob_inscriptions(
     let cid: Buf32 = signed_commit.compute_txid().into();
     let rid: Buf32 = reveal.compute_txid().into();
 
-    let centry = L1TxEntry::from_tx(&signed_commit);
+    // Replacing the commit tx would invalidate the signed reveal, so both are bumped by a child
+    // spending the reveal's output to the sequencer address.
+    let centry = L1TxEntry::from_tx(&signed_commit).with_fee_bump_method(FeeBumpMethod::Disabled);
     let rentry = L1TxEntry::from_tx(&reveal);
 
     // These don't need to be atomic. It will be handled by writer task if it does not find both
@@ -57,7 +67,7 @@ pub async fn create_and_sign_blob_inscriptions(
         .put_tx_entry(rid, rentry)
         .await
         .map_err(|e| InscriptionError::Other(e.into()))?;
-    Ok((cid, rid))
+    Ok((cid, rid, fee))
 }
 
 #[cfg(test)]
@@ -92,10 +102,15 @@ mod test {
             .await
             .unwrap();
 
-        let (cid, rid) =
-            create_and_sign_blob_inscriptions(&entry, bcast_handle.as_ref(), client, &config)
-                .await
-                .unwrap();
+        let (cid, rid, _) = create_and_sign_blob_inscriptions(
+            &[entry],
+            bcast_handle.as_ref(),
+            client,
+            &config,
+            None,
+        )
+        .await
+        .unwrap();
 
         // Check if corresponding txs exist in db
         let ctx = bcast_handle.get_tx_entry_by_id_async(cid).await.unwrap();
@@ -103,4 +118,26 @@ mod test {
         assert!(ctx.is_some());
         assert!(rtx.is_some());
     }
+
+    #[tokio::test]
+    async fn test_create_and_sign_blob_inscriptions_over_budget() {
+        let bcast_handle = get_broadcast_handle();
+        let client = Arc::new(TestBitcoinClient::new(1));
+        let config = get_config();
+
+        let entry = BlobEntry::new_unsigned([1; 100].to_vec());
+        let res = create_and_sign_blob_inscriptions(
+            &[entry],
+            bcast_handle.as_ref(),
+            client,
+            &config,
+            Some(1),
+        )
+        .await;
+
+        assert!(matches!(
+            res,
+            Err(InscriptionError::FeeBudgetExceeded(_, 1))
+        ));
+    }
 }
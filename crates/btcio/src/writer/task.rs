@@ -1,9 +1,13 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use strata_db::{
     traits::SequencerDatabase,
     types::{BlobEntry, BlobL1Status, L1TxStatus},
 };
+use strata_primitives::buf::Buf32;
 use strata_state::da_blob::{BlobDest, BlobIntent};
 use strata_status::StatusChannel;
 use strata_storage::ops::inscription::{Context, InscriptionDataOps};
@@ -150,37 +154,77 @@ pub async fn watcher_task(
     tokio::pin!(interval);
 
     let mut curr_blobidx = next_blbidx_to_watch;
+    // Index of the unsigned entry that is waiting for its batch to fill up, and since when
+    let mut pending_since: Option<(u64, Instant)> = None;
+    let mut epoch_fees = EpochFees::default();
     loop {
         interval.as_mut().tick().await;
 
         if let Some(blobentry) = insc_ops.get_blob_entry_by_idx_async(curr_blobidx).await? {
             match blobentry.status {
-                // If unsigned or needs resign, create new signed commit/reveal txs and update the
-                // entry
+                // If unsigned or needs resign, create new signed commit/reveal txs for the batch of
+                // entries starting at this one and update the entries
                 BlobL1Status::Unsigned | BlobL1Status::NeedsResign => {
                     debug!(?blobentry.status, %curr_blobidx, "Processing unsigned blobentry");
+                    let batch =
+                        collect_blob_batch(curr_blobidx, &blobentry, &insc_ops, &config).await?;
+
+                    // Give more intents the chance to join a new batch if there's room for them
+                    let since = match pending_since {
+                        Some((idx, since)) if idx == curr_blobidx => since,
+                        _ => {
+                            let now = Instant::now();
+                            pending_since = Some((curr_blobidx, now));
+                            now
+                        }
+                    };
+                    if blobentry.status == BlobL1Status::Unsigned
+                        && !batch.is_full
+                        && since.elapsed() < Duration::from_millis(config.batch_window_ms)
+                    {
+                        continue;
+                    }
+
+                    let entries: Vec<BlobEntry> =
+                        batch.entries.iter().map(|(_, e)| e.clone()).collect();
+                    let epoch = status_channel.epoch().unwrap_or_default();
+                    let fee_budget = epoch_fees
+                        .remaining(epoch, config.fee_budget_per_epoch, &insc_ops)
+                        .await?;
                     match create_and_sign_blob_inscriptions(
-                        &blobentry,
+                        &entries,
                         &broadcast_handle,
                         bitcoin_client.clone(),
                         &config,
+                        fee_budget,
                     )
                     .await
                     {
-                        Ok((cid, rid)) => {
-                            let mut updated_entry = blobentry.clone();
-                            updated_entry.status = BlobL1Status::Unpublished;
-                            updated_entry.commit_txid = cid;
-                            updated_entry.reveal_txid = rid;
-                            update_existing_entry(curr_blobidx, updated_entry, &insc_ops).await?;
+                        Ok((cid, rid, fee)) => {
+                            epoch_fees.record(fee, &insc_ops).await?;
+                            set_writer_error(None, &status_channel).await;
+
+                            for (idx, entry) in batch.entries {
+                                let mut updated_entry = entry;
+                                updated_entry.status = BlobL1Status::Unpublished;
+                                updated_entry.commit_txid = cid;
+                                updated_entry.reveal_txid = rid;
+                                update_existing_entry(idx, updated_entry, &insc_ops).await?;
+                            }
 
-                            debug!(%curr_blobidx, "Signed blob");
+                            debug!(%curr_blobidx, blobs = %entries.len(), "Signed blob batch");
                         }
-                        Err(InscriptionError::NotEnoughUtxos(required, available)) => {
+                        Err(e @ InscriptionError::NotEnoughUtxos(required, available)) => {
                             // Just wait till we have enough utxos and let the status be `Unsigned`
                             // or `NeedsResign`
                             // Maybe send an alert
                             error!(%required, %available, "Not enough utxos available to create commit/reveal transaction");
+                            set_writer_error(Some(e.to_string()), &status_channel).await;
+                        }
+                        Err(e @ InscriptionError::FeeBudgetExceeded(fee, budget)) => {
+                            // Wait for the next epoch to replenish the budget
+                            warn!(%fee, %budget, %epoch, "Inscription fee budget of epoch exhausted");
+                            set_writer_error(Some(e.to_string()), &status_channel).await;
                         }
                         e => {
                             e?;
@@ -233,6 +277,98 @@ pub async fn watcher_task(
     }
 }
 
+/// Fees spent on inscriptions during the checkpoint epoch the chain is in.
+///
+/// The chain state moves on to the next epoch with the last block of each checkpoint, so the
+/// budget is replenished as checkpoints are produced.  The spent fees are persisted so that
+/// restarting doesn't replenish the budget of the current epoch.
+#[derive(Debug, Default)]
+struct EpochFees {
+    /// The epoch the fees were spent in, `None` until loaded.
+    epoch: Option<u64>,
+    spent: u64,
+}
+
+impl EpochFees {
+    /// Returns what is left of the budget in the given epoch, loading what was already spent if
+    /// it's a new one.
+    async fn remaining(
+        &mut self,
+        epoch: u64,
+        budget: Option<u64>,
+        insc_ops: &InscriptionDataOps,
+    ) -> anyhow::Result<Option<u64>> {
+        if self.epoch != Some(epoch) {
+            self.spent = insc_ops
+                .get_epoch_fees_async(epoch)
+                .await?
+                .unwrap_or_default();
+            self.epoch = Some(epoch);
+        }
+        Ok(budget.map(|budget| budget.saturating_sub(self.spent)))
+    }
+
+    /// Records a fee spent in the epoch of the last [`Self::remaining`] call.
+    async fn record(&mut self, fee: u64, insc_ops: &InscriptionDataOps) -> anyhow::Result<()> {
+        self.spent += fee;
+        if let Some(epoch) = self.epoch {
+            insc_ops.put_epoch_fees_async(epoch, self.spent).await?;
+        }
+        Ok(())
+    }
+}
+
+/// A batch of [`BlobEntry`]s that get inscribed together, along with their indices.
+struct BlobBatch {
+    entries: Vec<(u64, BlobEntry)>,
+
+    /// Whether the batch can't take any more entries, either because of the size budget or because
+    /// the next entry can't be part of it.
+    is_full: bool,
+}
+
+/// Collects the entries that can be inscribed together with the entry at `start_idx`.
+///
+/// These are the consecutive entries that are waiting to be signed as well as the ones that shared
+/// the commit/reveal pair of the first entry if it needs to be resigned, within the size budget of
+/// the [`WriterConfig`]. The first entry is always part of the batch.
+async fn collect_blob_batch(
+    start_idx: u64,
+    first: &BlobEntry,
+    insc_ops: &InscriptionDataOps,
+    config: &WriterConfig,
+) -> anyhow::Result<BlobBatch> {
+    let mut size = first.blob.len();
+    let mut entries = vec![(start_idx, first.clone())];
+
+    let is_full = loop {
+        if entries.len() >= config.max_blobs_per_inscription {
+            break true;
+        }
+
+        let idx = start_idx + entries.len() as u64;
+        let Some(entry) = insc_ops.get_blob_entry_by_idx_async(idx).await? else {
+            break false;
+        };
+
+        let shares_txs = first.commit_txid != Buf32::zero()
+            && entry.commit_txid == first.commit_txid
+            && entry.reveal_txid == first.reveal_txid;
+        let needs_signing = matches!(
+            entry.status,
+            BlobL1Status::Unsigned | BlobL1Status::NeedsResign
+        );
+        if !(needs_signing || shares_txs) || size + entry.blob.len() > config.max_inscription_size {
+            break true;
+        }
+
+        size += entry.blob.len();
+        entries.push((idx, entry));
+    };
+
+    Ok(BlobBatch { entries, is_full })
+}
+
 async fn update_l1_status(
     blobentry: &BlobEntry,
     new_status: &BlobL1Status,
@@ -252,6 +388,13 @@ async fn update_l1_status(
     }
 }
 
+/// Sets or clears the reason for the writer to not inscribe blobs in the L1 status.
+async fn set_writer_error(err: Option<String>, status_channel: &StatusChannel) {
+    if status_channel.l1_status().writer_error != err {
+        apply_status_updates(&[L1StatusUpdate::WriterError(err)], status_channel).await;
+    }
+}
+
 async fn update_existing_entry(
     idx: u64,
     updated_entry: BlobEntry,
@@ -293,7 +436,7 @@ mod test {
     use strata_test_utils::ArbitraryGenerator;
 
     use super::*;
-    use crate::writer::test_utils::get_inscription_ops;
+    use crate::writer::test_utils::{get_config, get_inscription_ops};
 
     #[test]
     fn test_initialize_writer_state_no_last_blob_idx() {
@@ -337,6 +480,84 @@ mod test {
         assert_eq!(idx, expected_idx);
     }
 
+    #[tokio::test]
+    async fn test_collect_blob_batch() {
+        let iops = get_inscription_ops();
+        let config = get_config();
+
+        let signed = |blob: Vec<u8>, cid: u8| {
+            BlobEntry::new(
+                blob,
+                [cid; 32].into(),
+                [cid; 32].into(),
+                BlobL1Status::Published,
+            )
+        };
+        let entries = [
+            BlobEntry::new_unsigned(vec![1; 400]),
+            BlobEntry::new_unsigned(vec![2; 400]),
+            // Exceeds the size budget together with the ones above
+            BlobEntry::new_unsigned(vec![3; 400]),
+            BlobEntry::new_unsigned(vec![4; 10]),
+            signed(vec![5; 10], 1),
+        ];
+        for (i, entry) in entries.iter().enumerate() {
+            iops.put_blob_entry_async([i as u8; 32].into(), entry.clone())
+                .await
+                .unwrap();
+        }
+
+        let batch = collect_blob_batch(0, &entries[0], &iops, &config)
+            .await
+            .unwrap();
+        let idxs: Vec<_> = batch.entries.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(idxs, vec![0, 1]);
+        assert!(batch.is_full);
+
+        // Stops at the entry that's already signed
+        let batch = collect_blob_batch(2, &entries[2], &iops, &config)
+            .await
+            .unwrap();
+        let idxs: Vec<_> = batch.entries.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(idxs, vec![2, 3]);
+        assert!(batch.is_full);
+
+        // A resign takes along the entries that shared the commit/reveal pair
+        let mut resign = entries[4].clone();
+        resign.status = BlobL1Status::NeedsResign;
+        iops.put_blob_entry_async([5; 32].into(), signed(vec![7; 10], 1))
+            .await
+            .unwrap();
+        let batch = collect_blob_batch(4, &resign, &iops, &config)
+            .await
+            .unwrap();
+        let idxs: Vec<_> = batch.entries.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(idxs, vec![4, 5]);
+        assert!(!batch.is_full, "more entries could join the batch");
+    }
+
+    #[tokio::test]
+    async fn test_epoch_fees() {
+        let iops = get_inscription_ops();
+        let budget = Some(1_000);
+
+        let mut fees = EpochFees::default();
+        assert_eq!(fees.remaining(1, None, &iops).await.unwrap(), None);
+        assert_eq!(fees.remaining(1, budget, &iops).await.unwrap(), Some(1_000));
+
+        fees.record(600, &iops).await.unwrap();
+        assert_eq!(fees.remaining(1, budget, &iops).await.unwrap(), Some(400));
+        fees.record(600, &iops).await.unwrap();
+        assert_eq!(fees.remaining(1, budget, &iops).await.unwrap(), Some(0));
+
+        // The budget is replenished in the next epoch
+        assert_eq!(fees.remaining(2, budget, &iops).await.unwrap(), Some(1_000));
+
+        // But not by restarting
+        let mut fees = EpochFees::default();
+        assert_eq!(fees.remaining(1, budget, &iops).await.unwrap(), Some(0));
+    }
+
     #[test]
     fn test_determine_blob_next_status() {
         // When both are unpublished
@@ -59,7 +59,13 @@ pub fn get_config() -> WriterConfig {
         sequencer_address: addr,
         rollup_name: "strata".to_string(),
         inscription_fee_policy: InscriptionFeePolicy::Fixed(100),
+        min_fee_rate: 1,
+        max_fee_rate: 1000,
+        fee_budget_per_epoch: None,
         poll_duration_ms: 1000,
         amount_for_reveal_txn: 1000,
+        max_blobs_per_inscription: 3,
+        max_inscription_size: 1000,
+        batch_window_ms: 0,
     }
 }
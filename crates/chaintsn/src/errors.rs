@@ -19,6 +19,21 @@ pub enum TsnError {
     #[error("L1 segment block did not extend the chain tip")]
     L1SegNotExtend,
 
+    #[error("malformed header for L1 block {0}")]
+    MalformedL1Header(u64),
+
+    #[error("L1 block {0} has target {1:#x}, expected {2:#x}")]
+    L1TargetMismatch(u64, u32, u32),
+
+    #[error("L1 block {0} ({1}) does not meet its PoW target")]
+    L1PowNotMet(u64, L1BlockId),
+
+    #[error("L1 block {0} has timestamp {1} not above the median time {2}")]
+    L1TimestampTooOld(u64, u32, u32),
+
+    #[error("L1 segment has less accumulated work than the current chain (new {0}, current {1})")]
+    L1SegInsufficientWork(u128, u128),
+
     #[error("ran out of deposits to assign withdrawals to")]
     InsufficientDepositsForIntents,
 
@@ -27,4 +42,7 @@ pub enum TsnError {
 
     #[error("applied el ops and el ops from chain state doesn't match")]
     ElOpsMismatch,
+
+    #[error("forced inclusion {0} from L1 block {1} is overdue at L1 height {2}")]
+    ForcedInclusionOverdue(u64, u64, u64),
 }
@@ -4,19 +4,24 @@
 
 use std::{cmp::max, collections::HashMap};
 
-use bitcoin::{OutPoint, Transaction};
+use bitcoin::{block::Header, OutPoint, Transaction, XOnlyPublicKey};
 use rand_core::{RngCore, SeedableRng};
 use strata_primitives::{
+    bridge::OperatorIdx,
     l1::{BitcoinAmount, L1TxRef, OutputRef},
     params::RollupParams,
 };
 use strata_state::{
     block::L1Segment,
-    bridge_ops::{DepositIntent, WithdrawalIntent},
-    bridge_state::{DepositState, DispatchCommand, WithdrawOutput},
+    bridge_ops::{DepositIntent, WithdrawalBatch, WithdrawalIntent},
+    bridge_state::{DepositState, DispatchCommand, OperatorTable},
     exec_env::ExecEnvState,
     exec_update::{self, construct_ops_from_deposit_intents, ELDepositData, Op},
-    l1::{self, L1MaturationEntry},
+    l1::{
+        self, BtcParams, HeaderVerificationState, L1HeaderRecord, L1MaturationEntry,
+        L1VerificationError,
+    },
+    operator_update::{OperatorUpdateOp, SignedOperatorUpdate},
     prelude::*,
     state_op::StateCache,
     state_queue,
@@ -53,10 +58,20 @@ pub fn process_block(
     // Update basic bookkeeping.
     state.set_cur_header(header);
 
+    // The sequencer can only process the forced inclusions that were pending before this block.
+    let includable_end = state
+        .state()
+        .exec_env_state()
+        .pending_forced_incls()
+        .next_idx();
+
     // Go through each stage and play out the operations it has.
+    let exec_update = body.exec_segment().update();
     process_l1_view_update(state, body.l1_segment(), params)?;
-    let ready_withdrawals = process_execution_update(state, body.exec_segment().update())?;
+    let ready_withdrawals = process_execution_update(state, exec_update)?;
+    check_forced_inclusion_deadline(state, exec_update, includable_end, params)?;
     process_deposit_updates(state, ready_withdrawals, &mut rng, params)?;
+    process_epoch_end(state, params);
 
     Ok(())
 }
@@ -79,14 +94,12 @@ fn process_l1_view_update(
     params: &RollupParams,
 ) -> Result<(), TsnError> {
     let l1v = state.state().l1_view();
-    // Accept new blocks, comparing the tip against the current to figure out if
-    // we need to do a reorg.
-    // FIXME this should actually check PoW, it just does it based on block heights
+    // Accept new blocks, comparing the accumulated work of the new chain against the current one
+    // to figure out if we need to do a reorg.
     if !l1seg.new_payloads().is_empty() {
         let l1v = state.state().l1_view();
+        let btc_params = BtcParams::from(params.network);
 
-        // Validate the new blocks actually extend the tip.  This is what we have to tweak to make
-        // more complicated to check the PoW.
         let new_tip_block = l1seg.new_payloads().last().unwrap();
         let new_tip_height = new_tip_block.idx();
         let first_new_block_height = new_tip_height - l1seg.new_payloads().len() as u64 + 1;
@@ -94,12 +107,6 @@ fn process_l1_view_update(
         let cur_tip_height = l1v.tip_height();
         let cur_safe_height = l1v.safe_height();
 
-        // Check that the new chain is actually longer, if it's shorter then we didn't do anything.
-        // TODO This probably needs to be adjusted for PoW.
-        if new_tip_height < cur_tip_height {
-            return Err(TsnError::L1SegNotExtend);
-        }
-
         // Now make sure that the block hashes all connect up sensibly.
         let pivot_idx = implied_pivot_height;
         let pivot_blkid = l1v
@@ -109,26 +116,69 @@ fn process_l1_view_update(
             .unwrap_or_else(|| l1v.safe_block().blkid());
         check_chain_integrity(pivot_idx, pivot_blkid, l1seg.new_payloads())?;
 
+        // Verify the headers of the blocks we keep and of the new blocks, starting from the safe
+        // block.  The blocks we'd drop were verified when they were accepted, we only replay them
+        // to know how much work the current chain has.
+        let (kept_blocks, dropped_blocks): (Vec<_>, Vec<_>) = l1v
+            .maturation_queue()
+            .iter_entries()
+            .map(|(h, e)| (h, e.record()))
+            .partition(|(h, _)| *h <= pivot_idx);
+        let new_blocks = l1seg
+            .new_payloads()
+            .iter()
+            .map(|e| (e.idx(), e.record()))
+            .collect::<Vec<_>>();
+
+        let mut pivot_vs = l1v.safe_verification_state().clone();
+        verify_l1_headers(&mut pivot_vs, &kept_blocks, &btc_params)?;
+
+        let mut cur_tip_vs = pivot_vs.clone();
+        verify_l1_headers(&mut cur_tip_vs, &dropped_blocks, &btc_params)?;
+
+        let mut new_tip_vs = pivot_vs;
+        verify_l1_headers(&mut new_tip_vs, &new_blocks, &btc_params)?;
+
+        // Check that the new chain has at least as much work as the current one, if it has less
+        // then we didn't do anything.
+        let new_work = new_tip_vs.total_accumulated_pow;
+        let cur_work = cur_tip_vs.total_accumulated_pow;
+        if new_work < cur_work {
+            return Err(TsnError::L1SegInsufficientWork(new_work, cur_work));
+        }
+
+        let maturation_threshold = params.l1_reorg_safe_depth as u64;
+        let new_matured_l1_height = max(
+            new_tip_height.saturating_sub(maturation_threshold),
+            cur_safe_height,
+        );
+
+        // Figure out the verification state of the new safe block before we start modifying
+        // the state.
+        let matured_blocks = kept_blocks
+            .iter()
+            .chain(new_blocks.iter())
+            .filter(|(h, _)| *h <= new_matured_l1_height)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut new_safe_vs = l1v.safe_verification_state().clone();
+        verify_l1_headers(&mut new_safe_vs, &matured_blocks, &btc_params)?;
+
         // Okay now that we've figured that out, let's actually how to actually do the reorg.
         if pivot_idx > params.horizon_l1_height && pivot_idx < cur_tip_height {
             state.revert_l1_view_to(pivot_idx);
         }
 
-        let maturation_threshold = params.l1_reorg_safe_depth as u64;
-
         for e in l1seg.new_payloads() {
             let ment = L1MaturationEntry::from(e.clone());
             state.apply_l1_block_entry(ment.clone());
         }
 
-        let new_matured_l1_height = max(
-            new_tip_height.saturating_sub(maturation_threshold),
-            cur_safe_height,
-        );
-
         for idx in (cur_safe_height..=new_matured_l1_height) {
             state.mature_l1_block(idx);
         }
+
+        state.set_safe_l1_verification_state(new_safe_vs);
     }
 
     Ok(())
@@ -153,20 +203,44 @@ fn check_chain_integrity(
             return Err(TsnError::L1BlockIdMismatch(h, *attested_id, computed_id));
         }
 
-        // Make sure matches parent.
-        // TODO FIXME I think my impl for parent_blkid is incorrect, fix this later
-        /*let blk_parent = e.record().parent_blkid();
-        if i == 0 {
-            if blk_parent != *pivot_blkid {
-                return Err(TsnError::L1BlockParentMismatch(h, blk_parent, *pivot_blkid));
-            }
-        } else {
-            let parent_payload = &new_blocks[i - 1];
-            let parent_id = parent_payload.record().blkid();
-            if blk_parent != *parent_id {
-                return Err(TsnError::L1BlockParentMismatch(h, blk_parent, *parent_id));
-            }
-        }*/
+        // The parent links are checked along with the PoW in `verify_l1_headers`.
+    }
+
+    Ok(())
+}
+
+/// Verifies the PoW, difficulty retargeting and parent links of consecutive L1 block headers,
+/// updating the verification state as it goes.
+///
+/// Blocks at or below the last verified block of the state are skipped, these are the blocks
+/// before genesis that were accepted without verification.
+fn verify_l1_headers(
+    vs: &mut HeaderVerificationState,
+    blocks: &[(u64, &L1HeaderRecord)],
+    btc_params: &BtcParams,
+) -> Result<(), TsnError> {
+    for (h, rec) in blocks {
+        let h = *h;
+        if h <= vs.last_verified_block_num as u64 {
+            continue;
+        }
+
+        let header: Header = bitcoin::consensus::deserialize(rec.buf())
+            .map_err(|_| TsnError::MalformedL1Header(h))?;
+
+        vs.check_and_update_full(&header, btc_params)
+            .map_err(|e| match e {
+                L1VerificationError::ContinuityError(parent, last_blkid) => {
+                    TsnError::L1BlockParentMismatch(h, parent, last_blkid)
+                }
+                L1VerificationError::TargetMismatch(target, expected) => {
+                    TsnError::L1TargetMismatch(h, target, expected)
+                }
+                L1VerificationError::PowNotMet(blkid) => TsnError::L1PowNotMet(h, blkid),
+                L1VerificationError::TimestampTooOld(time, median) => {
+                    TsnError::L1TimestampTooOld(h, time, median)
+                }
+            })?;
     }
 
     Ok(())
@@ -205,9 +279,209 @@ fn process_execution_update<'u>(
         state.consume_deposit_intent(intent_idx);
     }
 
+    // The forced inclusions have to be processed in the order they were queued in, so the ones
+    // applied must match the front of the queue exactly.  Whether the EL actually included the
+    // txs is checked the same way as the rest of the EL payload.
+    let applied_forced_incls = applied_ops
+        .iter()
+        .filter_map(|op| match op {
+            Op::ForcedInclusion(forced_incl) => Some(forced_incl),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let pending_forced_incls = state.state().exec_env_state().pending_forced_incls();
+    if applied_forced_incls.len() > pending_forced_incls.len() {
+        return Err(TsnError::ElOpsMismatch);
+    }
+
+    for (applied, (idx, pending)) in applied_forced_incls
+        .iter()
+        .zip(pending_forced_incls.iter_entries())
+    {
+        if applied.incl_idx() != idx || applied.payload() != pending.payload() {
+            return Err(TsnError::ElOpsMismatch);
+        }
+    }
+
+    if let Some(last_forced_incl) = applied_forced_incls.last() {
+        state.consume_forced_inclusion(last_forced_incl.incl_idx());
+    }
+
     Ok(update.output().withdrawals())
 }
 
+/// Checks that the sequencer hasn't left any forced inclusion pending for longer than it's allowed
+/// to, which is [`RollupParams::forced_inclusion_delay`] L1 blocks after the one it matured in.
+///
+/// Only the forced inclusions queued before `includable_end` count, and a block that processes
+/// as many of them as it's allowed to is always valid, so a backlog of overdue forced inclusions
+/// is worked through instead of halting the chain.
+///
+/// Note: This only bounds the delay relative to our view of L1, so it relies on the L1 view being
+/// kept up to date.
+fn check_forced_inclusion_deadline(
+    state: &StateCache,
+    update: &exec_update::ExecUpdate,
+    includable_end: u64,
+    params: &RollupParams,
+) -> Result<(), TsnError> {
+    let n_applied = update
+        .input()
+        .applied_ops()
+        .iter()
+        .filter(|op| matches!(op, Op::ForcedInclusion(_)))
+        .count();
+    if n_applied >= params.max_forced_incls_in_block as usize {
+        return Ok(());
+    }
+
+    let safe_height = state.state().l1_view().safe_height();
+    let pending_forced_incls = state.state().exec_env_state().pending_forced_incls();
+
+    // The queue is ordered by L1 height, so we only have to check the oldest entry.  The ones that
+    // matured in this block weren't known to the sequencer when it built the payload.
+    if let Some((idx, oldest)) = pending_forced_incls.iter_entries().next() {
+        if idx < includable_end && safe_height > oldest.l1_height() + params.forced_inclusion_delay
+        {
+            return Err(TsnError::ForcedInclusionOverdue(
+                idx,
+                oldest.l1_height(),
+                safe_height,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Ends the current epoch if the block is its last one, applying the pending
+/// operator updates so that the operator set only ever changes between epochs.
+///
+/// The epoch's checkpoint covers the blocks up to and including this one, so
+/// the next epoch starts out with the new operator set.
+fn process_epoch_end(state: &mut StateCache, params: &RollupParams) {
+    let epoch = state.state().epoch();
+    // A chain that was running before it tracked its epoch catches up one
+    // epoch per block.
+    if state.state().chain_tip_slot() < params.epoch_terminal_slot(epoch) {
+        return;
+    }
+
+    process_operator_updates(state, params);
+    state.set_epoch(epoch + 1);
+}
+
+/// Applies the pending operator updates.
+///
+/// Updates are applied in order of their sequence number.  An update is
+/// skipped as a whole if it doesn't have the expected sequence number, isn't
+/// signed by the sequencer, or would leave the table empty or some deposit
+/// without any of its notary operators in the table, since nobody would be
+/// able to front the withdrawals from it anymore.  Updates with a later
+/// sequence number than the ones applied are kept for the next epoch.
+fn process_operator_updates(state: &mut StateCache, params: &RollupParams) {
+    let slot = state.state().chain_tip_slot();
+    let table = state.state().operator_table();
+    if table.pending_updates().is_empty() {
+        return;
+    }
+
+    // The sequencer may have posted them out of order.
+    let mut pending_updates = table.pending_updates().iter().collect::<Vec<_>>();
+    pending_updates.sort_by_key(|update| update.update().seqno());
+
+    let mut next_seqno = table.next_update_seqno();
+    let mut valid_ops = Vec::new();
+    let mut new_table = table.clone();
+    for update in pending_updates {
+        if update.update().seqno() != next_seqno {
+            continue;
+        }
+
+        if !params
+            .cred_rule
+            .verify_signer_at(slot, |pk| update.verify_sig(pk))
+        {
+            continue;
+        }
+
+        let Some(updated_table) = try_apply_operator_update(state, &new_table, update) else {
+            continue;
+        };
+
+        new_table = updated_table;
+        valid_ops.extend_from_slice(update.update().ops());
+        next_seqno += 1;
+    }
+
+    // Deposits in L1 blocks up to our current tip were made to the current set, even if those
+    // blocks only mature after the set changes.
+    if !valid_ops.is_empty() {
+        let tip_height = state.state().l1_view().tip_height();
+        state.record_notary_set(tip_height);
+    }
+
+    for op in valid_ops {
+        match op {
+            OperatorUpdateOp::Add {
+                signing_pk,
+                wallet_pk,
+            } => state.insert_operator(signing_pk, wallet_pk),
+            OperatorUpdateOp::Remove(idx) => state.remove_operator(idx),
+        }
+    }
+
+    state.clear_operator_updates(next_seqno);
+}
+
+/// Applies an operator update to a copy of the table, returning `None` if it
+/// isn't valid.
+fn try_apply_operator_update(
+    state: &StateCache,
+    table: &OperatorTable,
+    update: &SignedOperatorUpdate,
+) -> Option<OperatorTable> {
+    let mut table = table.clone();
+
+    for op in update.update().ops() {
+        match op {
+            OperatorUpdateOp::Add {
+                signing_pk,
+                wallet_pk,
+            } => {
+                // The wallet key goes into the bridge address so it has to be a valid key.
+                XOnlyPublicKey::from_slice(wallet_pk.as_ref()).ok()?;
+                if table.operators().iter().any(|e| e.wallet_pk() == wallet_pk) {
+                    return None;
+                }
+
+                table.insert(*signing_pk, *wallet_pk);
+            }
+            OperatorUpdateOp::Remove(idx) => {
+                table.remove(*idx)?;
+            }
+        }
+    }
+
+    if table.is_empty() {
+        return None;
+    }
+
+    let orphans_deposit = state.state().deposits_table().deposits().any(|ent| {
+        !matches!(ent.deposit_state(), DepositState::Executed)
+            && !ent
+                .notary_operators()
+                .iter()
+                .any(|idx| table.get_operator(*idx).is_some())
+    });
+    if orphans_deposit {
+        return None;
+    }
+
+    Some(table)
+}
+
 /// Iterates over the deposits table, making updates where needed.
 ///
 /// Includes:
@@ -220,9 +494,6 @@ fn process_deposit_updates(
     rng: &mut SlotRng,
     params: &RollupParams,
 ) -> Result<(), TsnError> {
-    // TODO make this capable of handling multiple denominations, have to decide
-    // how those get represented first though
-
     let num_deposit_ents = state.state().deposits_table().len();
 
     // This determines how long we'll keep trying to service a withdrawal before
@@ -275,13 +546,26 @@ fn process_deposit_updates(
             }
 
             DepositState::Accepted => {
-                // If we have an intent to assign, we can dispatch it to this deposit.
+                // If we have intents to assign, we can dispatch as many of them as fit in this
+                // deposit as a batch.
                 if have_ready_intent {
-                    let intent = &ready_withdrawals[next_intent_to_assign];
-                    let op_idx = ops_seq[next_intent_to_assign % ops_seq.len()];
-
-                    let outp = WithdrawOutput::new(*intent.dest_pk(), *intent.amt());
-                    let cmd = DispatchCommand::new(vec![outp]);
+                    let batch = WithdrawalBatch::pack(
+                        &ready_withdrawals[next_intent_to_assign..],
+                        ent.amt(),
+                    );
+                    if batch.is_empty() {
+                        continue;
+                    }
+
+                    let op_pos = ops_seq[next_intent_to_assign % ops_seq.len()];
+                    let op_idx = pick_deposit_operator(
+                        state.state().operator_table(),
+                        ent.notary_operators(),
+                        op_pos,
+                    )
+                    .ok_or(TsnError::NoOperators)?;
+
+                    let cmd = DispatchCommand::from(&batch);
                     state.assign_withdrawal_command(
                         deposit_idx,
                         op_idx,
@@ -289,37 +573,48 @@ fn process_deposit_updates(
                         new_exec_height as u64,
                     );
 
-                    next_intent_to_assign += 1;
+                    next_intent_to_assign += batch.len();
                 }
             }
 
             DepositState::Dispatched(dstate) => {
                 // Check if the deposit is past the threshold.
                 if cur_block_height >= dstate.exec_deadline() {
+                    let operator_table = state.state().operator_table();
+
+                    // The assignee may have been removed since, in which case we just start
+                    // from the front of the table.
+                    let cur_op_pos = operator_table
+                        .indices()
+                        .position(|idx| idx == dstate.assignee())
+                        .unwrap_or_default() as u32;
+
                     // Pick the next assignee, if there are any.
                     let new_op_pos = if num_operators > 1 {
                         // Compute a random offset from 1 to (num_operators - 1),
                         // ensuring we pick a different operator than the current one.
                         let offset = 1 + (rng.next_u32() % (num_operators - 1));
-                        (dstate.assignee() + offset) % num_operators
+                        (cur_op_pos + offset) % num_operators
                     } else {
                         // If there is only a single operator, we remain with the current assignee.
-                        dstate.assignee()
+                        cur_op_pos
                     };
 
-                    // Convert their position in the table to their global index.
-                    let op_idx = state
-                        .state()
-                        .operator_table()
-                        .get_entry_at_pos(new_op_pos)
-                        .expect("chaintsn: inconsistent state")
-                        .idx();
+                    let op_idx =
+                        pick_deposit_operator(operator_table, ent.notary_operators(), new_op_pos)
+                            .ok_or(TsnError::NoOperators)?;
 
                     state.reset_deposit_assignee(deposit_idx, op_idx, new_exec_height as u64);
                 }
             }
 
+            DepositState::Fulfilled(_) => {
+                // Waiting on the notaries to reimburse the assignee, nothing to do here.
+            }
+
             DepositState::Executed => {
+                // Any change of the deposit was registered as a new deposit when its spend was
+                // seen on L1, so this one can go.
                 deposit_idxs_to_remove.push(deposit_idx);
             }
         }
@@ -327,7 +622,8 @@ fn process_deposit_updates(
 
     // Sanity check.  For devnet this should never fail since we should never be
     // able to withdraw more than was deposited, so we should never run out of
-    // deposits to assign withdrawals to.
+    // deposits to assign withdrawals to.  This can also fail if an intent is
+    // larger than any deposit left.
     if next_intent_to_assign != ready_withdrawals.len() {
         return Err(TsnError::InsufficientDepositsForIntents);
     }
@@ -337,6 +633,35 @@ fn process_deposit_updates(
     Ok(())
 }
 
+/// Converts the position of an operator in the table to the global index of an
+/// operator that can front withdrawals from a deposit with the given notary
+/// operators.
+///
+/// This is the operator at that position if it's one of the notaries.
+/// Otherwise, since the operator set may have changed since the deposit was
+/// made, it falls back to picking among the notaries still in the table.
+fn pick_deposit_operator(
+    operator_table: &OperatorTable,
+    notary_operators: &[OperatorIdx],
+    pos: u32,
+) -> Option<OperatorIdx> {
+    let op_idx = operator_table.get_entry_at_pos(pos)?.idx();
+    if notary_operators.contains(&op_idx) {
+        return Some(op_idx);
+    }
+
+    let eligible = notary_operators
+        .iter()
+        .copied()
+        .filter(|idx| operator_table.get_operator(*idx).is_some())
+        .collect::<Vec<_>>();
+    if eligible.is_empty() {
+        return None;
+    }
+
+    Some(eligible[pos as usize % eligible.len()])
+}
+
 /// Wrapper to safely select a random operator index using wide reduction
 /// This will return a deterministically-random index in the range `[0, num)`
 fn next_rand_op_pos(rng: &mut SlotRng, num: u32) -> u32 {
@@ -347,24 +672,86 @@ fn next_rand_op_pos(rng: &mut SlotRng, num: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use rand_core::SeedableRng;
-    use strata_primitives::{buf::Buf32, l1::BitcoinAmount, params::OperatorConfig};
+    use strata_primitives::{
+        block_credential::CredRule,
+        buf::Buf32,
+        l1::{BitcoinAmount, XOnlyPk},
+        params::{OperatorConfig, RollupParams},
+        relay::util::{compute_pubkey_for_privkey, sign_msg_hash},
+    };
     use strata_state::{
         block::{ExecSegment, L1Segment, L2BlockBody},
-        bridge_state::OperatorTable,
+        bridge_ops::WithdrawalIntent,
+        bridge_state::{DepositState, OperatorTable},
         chain_state::Chainstate,
         exec_env::ExecEnvState,
-        exec_update::{ExecUpdate, UpdateInput, UpdateOutput},
+        exec_update::{ELForcedInclusionData, ExecUpdate, Op, UpdateInput, UpdateOutput},
+        forced_inclusion::ForcedInclusionTx,
         genesis::GenesisStateData,
         header::{L2BlockHeader, L2Header},
         id::L2BlockId,
-        l1::{DepositUpdateTx, L1HeaderPayload, L1HeaderRecord, L1Tx, L1ViewState},
+        l1::{BtcParams, DepositUpdateTx, L1HeaderPayload, L1HeaderRecord, L1Tx, L1ViewState},
+        operator_update::{
+            OperatorUpdate, OperatorUpdateOp, OperatorUpdateTx, SignedOperatorUpdate,
+        },
         state_op::StateCache,
-        tx::{DepositInfo, ProtocolOperation},
+        tx::{DepositInfo, ForcedInclusionInfo, ProtocolOperation},
+    };
+    use strata_test_utils::{
+        bitcoin::{get_btc_chain, BtcChainSegment},
+        l2::{gen_params, make_dummy_operator_pubkeys_with_seed},
+        ArbitraryGenerator,
     };
-    use strata_test_utils::{l2::gen_params, ArbitraryGenerator};
 
-    use super::{next_rand_op_pos, process_block};
-    use crate::{slot_rng::SlotRng, transition::process_l1_view_update};
+    use super::{
+        check_forced_inclusion_deadline, next_rand_op_pos, process_block, process_deposit_updates,
+        process_execution_update, process_operator_updates,
+    };
+    use crate::{errors::TsnError, slot_rng::SlotRng, transition::process_l1_view_update};
+
+    /// Height of the safe L1 block in the test chainstates.
+    const SAFE_L1_HEIGHT: u32 = 40400;
+
+    /// Creates rollup params with the horizon at [`SAFE_L1_HEIGHT`], so that extending the safe
+    /// block isn't treated as a reorg, on mainnet where the test chain is from.
+    fn rollup_params() -> RollupParams {
+        let mut params = gen_params().rollup;
+        params.horizon_l1_height = SAFE_L1_HEIGHT as u64;
+        params.network = bitcoin::Network::Bitcoin;
+        params
+    }
+
+    /// Creates a chainstate whose safe L1 block is the block at `height` of the test chain.
+    fn chainstate_at_l1_height(chain: &BtcChainSegment, height: u32) -> Chainstate {
+        let safe_block = L1HeaderRecord::from(&chain.get_block_manifest(height));
+        let btc_params = BtcParams::from(rollup_params().network);
+        let safe_vs = chain.get_verification_state(height + 1, &btc_params);
+        let l1_state = L1ViewState::new_at_horizon(height as u64 + 1, safe_block, safe_vs);
+
+        let base_input = UpdateInput::new(0, vec![], Buf32::zero(), vec![]);
+        let exec_state = ExecEnvState::from_base_input(base_input, Buf32::zero());
+
+        let gdata = GenesisStateData::new(
+            L2BlockId::from(Buf32::zero()),
+            l1_state,
+            OperatorTable::new_empty(),
+            exec_state,
+        );
+        Chainstate::from_genesis(&gdata)
+    }
+
+    /// Creates payloads for the blocks of the test chain in the given height range.
+    fn l1_payloads(
+        chain: &BtcChainSegment,
+        heights: impl Iterator<Item = u64>,
+    ) -> Vec<L1HeaderPayload> {
+        heights
+            .map(|h| {
+                let record = L1HeaderRecord::from(&chain.get_block_manifest(h as u32));
+                L1HeaderPayload::new(h, record).build()
+            })
+            .collect()
+    }
 
     #[test]
     // Confirm that operator index sampling is deterministic and in bounds
@@ -382,25 +769,24 @@ mod tests {
 
     #[test]
     fn test_process_l1_view_update_with_deposit_update_tx() {
-        let mut chs: Chainstate = ArbitraryGenerator::new().generate();
-        // get the l1 view state of the chain state
-        let params = gen_params();
-        let header_record = chs.l1_view();
-
-        let tip_height = header_record.tip_height();
-        let maturation_queue = header_record.maturation_queue();
+        let chain = get_btc_chain();
+        let chs = chainstate_at_l1_height(&chain, SAFE_L1_HEIGHT);
+        let params = rollup_params();
+        let tip_height = chs.l1_view().tip_height();
 
         let mut state_cache = StateCache::new(chs);
         let amt: BitcoinAmount = ArbitraryGenerator::new().generate();
 
+        let heights = tip_height..=tip_height + params.l1_reorg_safe_depth as u64;
         let new_payloads_with_deposit_update_tx: Vec<L1HeaderPayload> =
-            (1..=params.rollup().l1_reorg_safe_depth + 1)
-                .map(|idx| {
-                    let record = ArbitraryGenerator::new_with_size(1 << 15).generate();
+            l1_payloads(&chain, heights)
+                .into_iter()
+                .enumerate()
+                .map(|(idx, payload)| {
                     let proof = ArbitraryGenerator::new_with_size(1 << 12).generate();
                     let tx = ArbitraryGenerator::new_with_size(1 << 8).generate();
 
-                    let l1tx = if idx == 1 {
+                    let l1tx = if idx == 0 {
                         let protocol_op = ProtocolOperation::Deposit(DepositInfo {
                             amt,
                             outpoint: ArbitraryGenerator::new().generate(),
@@ -411,16 +797,15 @@ mod tests {
                         ArbitraryGenerator::new_with_size(1 << 15).generate()
                     };
 
-                    let deposit_update_tx = DepositUpdateTx::new(l1tx, idx);
-                    L1HeaderPayload::new(tip_height + idx as u64, record)
-                        .with_deposit_update_txs(vec![deposit_update_tx])
-                        .build()
+                    let deposit_update_tx = DepositUpdateTx::new(l1tx, idx as u32);
+                    payload.with_deposit_update_txs(vec![deposit_update_tx])
                 })
                 .collect();
 
-        let mut l1_segment = L1Segment::new(new_payloads_with_deposit_update_tx);
+        let l1_segment = L1Segment::new(new_payloads_with_deposit_update_tx);
 
-        let view_update = process_l1_view_update(&mut state_cache, &l1_segment, params.rollup());
+        let view_update = process_l1_view_update(&mut state_cache, &l1_segment, &params);
+        assert!(view_update.is_ok());
         assert_eq!(
             state_cache
                 .state()
@@ -451,36 +836,328 @@ mod tests {
 
     #[test]
     fn test_process_l1_view_update_maturation_check() {
-        let mut chs: Chainstate = ArbitraryGenerator::new().generate();
-        let params = gen_params();
-        let header_record = chs.l1_view();
-        let old_safe_height = header_record.safe_height();
-        let to_mature_blk_num = 10;
+        let chain = get_btc_chain();
+        let chs = chainstate_at_l1_height(&chain, SAFE_L1_HEIGHT);
+        let params = rollup_params();
+        let old_safe_height = chs.l1_view().safe_height();
+        let to_mature_blk_num: u64 = 10;
 
         let mut state_cache = StateCache::new(chs);
-        let maturation_queue_len = state_cache.state().l1_view().maturation_queue().len() as u64;
 
         // Simulate L1 payloads that have matured
-        let new_payloads_matured: Vec<L1HeaderPayload> = (1..params.rollup().l1_reorg_safe_depth
-            + to_mature_blk_num)
-            .map(|idx| {
-                let record = ArbitraryGenerator::new_with_size(1 << 15).generate();
-                L1HeaderPayload::new(old_safe_height + idx as u64, record)
-                    .with_deposit_update_txs(vec![])
-                    .build()
-            })
-            .collect();
-
-        let mut l1_segment = L1Segment::new(new_payloads_matured.clone());
+        let heights = old_safe_height
+            ..old_safe_height + params.l1_reorg_safe_depth as u64 + to_mature_blk_num;
+        let l1_segment = L1Segment::new(l1_payloads(&chain, heights));
 
         // Process the L1 view update for matured blocks
-        let result = process_l1_view_update(&mut state_cache, &l1_segment, params.rollup());
+        let result = process_l1_view_update(&mut state_cache, &l1_segment, &params);
         assert!(result.is_ok());
 
         // Check that blocks were matured
+        let new_safe_height = old_safe_height + to_mature_blk_num;
+        let l1_view = state_cache.state().l1_view();
+        assert_eq!(l1_view.safe_height(), new_safe_height);
+        assert_eq!(
+            l1_view.safe_verification_state().last_verified_block_num as u64,
+            new_safe_height - 1,
+            "verification state must follow the safe block"
+        );
+    }
+
+    #[test]
+    fn test_process_l1_view_update_invalid_pow() {
+        let chain = get_btc_chain();
+        let chs = chainstate_at_l1_height(&chain, SAFE_L1_HEIGHT);
+        let params = rollup_params();
+        let tip_height = chs.l1_view().tip_height();
+
+        let mut state_cache = StateCache::new(chs.clone());
+
+        // Tamper with the nonce of the second block so it no longer meets its target.
+        let mut header = chain.get_header(tip_height as u32 + 1);
+        header.nonce = header.nonce.wrapping_add(1);
+        let record = L1HeaderRecord::create_from_serialized_header(
+            bitcoin::consensus::serialize(&header),
+            Buf32::zero(),
+        );
+
+        let mut payloads = l1_payloads(&chain, tip_height..=tip_height);
+        payloads.push(L1HeaderPayload::new(tip_height + 1, record).build());
+        let l1_segment = L1Segment::new(payloads);
+
+        let result = process_l1_view_update(&mut state_cache, &l1_segment, &params);
+        assert!(matches!(result, Err(TsnError::L1PowNotMet(h, _)) if h == tip_height + 1));
+        assert_eq!(
+            state_cache.state(),
+            &chs,
+            "state must be unchanged on error"
+        );
+    }
+
+    #[test]
+    fn test_process_l1_view_update_insufficient_work() {
+        let chain = get_btc_chain();
+        let chs = chainstate_at_l1_height(&chain, SAFE_L1_HEIGHT);
+        let params = rollup_params();
+        let tip_height = chs.l1_view().tip_height();
+
+        let mut state_cache = StateCache::new(chs);
+
+        let heights = tip_height..tip_height + params.l1_reorg_safe_depth as u64 + 2;
+        let l1_segment = L1Segment::new(l1_payloads(&chain, heights));
+        let result = process_l1_view_update(&mut state_cache, &l1_segment, &params);
+        assert!(result.is_ok());
+
+        // Reorging to a chain that is one block shorter must be rejected.
+        let l1v = state_cache.state().l1_view();
+        let first_height = l1v.safe_height();
+        let shorter_tip = l1v.maturation_queue().back_idx().unwrap() - 1;
+        let l1_segment = L1Segment::new(l1_payloads(&chain, first_height..=shorter_tip));
+        let result = process_l1_view_update(&mut state_cache, &l1_segment, &params);
+        assert!(matches!(result, Err(TsnError::L1SegInsufficientWork(..))));
+    }
+
+    #[test]
+    fn test_forced_inclusion_deadline() {
+        let chain = get_btc_chain();
+        let chs = chainstate_at_l1_height(&chain, SAFE_L1_HEIGHT);
+        let params = rollup_params();
+        let tip_height = chs.l1_view().tip_height();
+
+        let mut state_cache = StateCache::new(chs);
+
+        // Post a forced inclusion in the first new block and let it mature.
+        let payload = vec![0xf8; 120];
+        let heights = tip_height..=tip_height + params.l1_reorg_safe_depth as u64;
+        let mut payloads = l1_payloads(&chain, heights);
+        let proof = ArbitraryGenerator::new_with_size(1 << 12).generate();
+        let protocol_op = ProtocolOperation::ForcedInclusion(ForcedInclusionInfo {
+            payload: payload.clone(),
+        });
+        let l1tx = L1Tx::new(proof, vec![], protocol_op);
+        payloads[0] = payloads[0]
+            .clone()
+            .with_forced_inclusion_txs(vec![ForcedInclusionTx::new(l1tx)]);
+        let l1_segment = L1Segment::new(payloads);
+        let result = process_l1_view_update(&mut state_cache, &l1_segment, &params);
+        assert!(result.is_ok());
+
+        let pending = state_cache.state().exec_env_state().pending_forced_incls();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending.entries()[0].l1_height(), tip_height);
+
+        let make_update = |payloads: Vec<Vec<u8>>| {
+            let ops = payloads
+                .into_iter()
+                .map(|payload| Op::ForcedInclusion(ELForcedInclusionData::new(0, payload)))
+                .collect();
+            let input = UpdateInput::new(1, ops, Buf32::zero(), vec![]);
+            ExecUpdate::new(input, UpdateOutput::new_from_state(Buf32::zero()))
+        };
+        let empty_update = make_update(vec![]);
+        let includable_end = pending.next_idx();
+        let result =
+            check_forced_inclusion_deadline(&state_cache, &empty_update, includable_end, &params);
+        assert!(result.is_ok());
+
+        // Applying a different tx than the one that was forced must be rejected.
+        let mut bad_state_cache = StateCache::new(state_cache.state().clone());
+        let bad_update = make_update(vec![vec![0; 120]]);
+        let result = process_execution_update(&mut bad_state_cache, &bad_update);
+        assert!(matches!(result, Err(TsnError::ElOpsMismatch)));
+
+        // If the sequencer keeps extending the L1 view without processing it, it becomes overdue.
+        let mut overdue_state_cache = StateCache::new(state_cache.state().clone());
+        let next_height = tip_height + params.l1_reorg_safe_depth as u64 + 1;
+        let heights = next_height..next_height + params.forced_inclusion_delay;
+        let l1_segment = L1Segment::new(l1_payloads(&chain, heights));
+        let result = process_l1_view_update(&mut overdue_state_cache, &l1_segment, &params);
+        assert!(result.is_ok());
+        let result = check_forced_inclusion_deadline(
+            &overdue_state_cache,
+            &empty_update,
+            includable_end,
+            &params,
+        );
+        assert!(matches!(
+            result,
+            Err(TsnError::ForcedInclusionOverdue(0, ..))
+        ));
+
+        // It's not held against a block that couldn't have known about it.
+        let result =
+            check_forced_inclusion_deadline(&overdue_state_cache, &empty_update, 0, &params);
+        assert!(result.is_ok());
+
+        // Nor against a block that processes as many forced inclusions as it can.
+        let busy_params = RollupParams {
+            max_forced_incls_in_block: 1,
+            ..params.clone()
+        };
+        let busy_update = make_update(vec![vec![0; 120]]);
+        let result = check_forced_inclusion_deadline(
+            &overdue_state_cache,
+            &busy_update,
+            includable_end,
+            &busy_params,
+        );
+        assert!(result.is_ok());
+
+        // Processing it clears the queue.
+        let update = make_update(vec![payload]);
+        let result = process_execution_update(&mut state_cache, &update);
+        assert!(result.is_ok());
+        let pending = state_cache.state().exec_env_state().pending_forced_incls();
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_process_deposit_updates_batches_withdrawals() {
+        let params = gen_params();
+        let operators = [make_dummy_operator_pubkeys_with_seed(0)];
+
+        let base_input = UpdateInput::new(0, vec![], Buf32::zero(), vec![]);
+        let gdata = GenesisStateData::new(
+            L2BlockId::from(Buf32::zero()),
+            ArbitraryGenerator::new().generate(),
+            OperatorTable::from_operator_list(&operators),
+            ExecEnvState::from_base_input(base_input, Buf32::zero()),
+        );
+        let mut chs = Chainstate::from_genesis(&gdata);
+
+        let deposit_amt = BitcoinAmount::from_int_btc(10);
+        for _ in 0..2 {
+            let outpoint = ArbitraryGenerator::new().generate();
+            chs.deposits_table_mut()
+                .add_deposits(&outpoint, &[0], deposit_amt);
+        }
+
+        let dest_pk = XOnlyPk::new(Buf32::zero());
+        let ready_withdrawals =
+            [4, 5, 3].map(|btc| WithdrawalIntent::new(BitcoinAmount::from_int_btc(btc), dest_pk));
+
+        let mut state_cache = StateCache::new(chs);
+        let mut rng = SlotRng::from_seed([1u8; 32]);
+        let result = process_deposit_updates(
+            &mut state_cache,
+            &ready_withdrawals,
+            &mut rng,
+            params.rollup(),
+        );
+        assert!(result.is_ok());
+
+        let num_outputs = |idx| match state_cache
+            .state()
+            .deposits_table()
+            .get_deposit(idx)
+            .unwrap()
+            .deposit_state()
+        {
+            DepositState::Dispatched(dstate) => dstate.cmd().withdraw_outputs().len(),
+            _ => panic!("deposit {idx} must be dispatched"),
+        };
+        assert_eq!(num_outputs(0), 2, "first two intents must be batched");
+        assert_eq!(num_outputs(1), 1);
+    }
+
+    #[test]
+    fn test_process_operator_updates() {
+        let chain = get_btc_chain();
+        let mut chs = chainstate_at_l1_height(&chain, SAFE_L1_HEIGHT);
+        let tip_height = chs.l1_view().tip_height();
+
+        let seq_sk = Buf32::from([3; 32]);
+        let mut params = rollup_params();
+        params.cred_rule = CredRule::SchnorrKey(compute_pubkey_for_privkey(&seq_sk));
+
+        // A deposit that only operator 0 can front withdrawals from.
+        let outpoint = ArbitraryGenerator::new().generate();
+        chs.deposits_table_mut()
+            .add_deposits(&outpoint, &[0], BitcoinAmount::from_int_btc(10));
+
+        let mut state_cache = StateCache::new(chs);
+        for seed in 0..2 {
+            let opkeys = make_dummy_operator_pubkeys_with_seed(seed);
+            state_cache.insert_operator(*opkeys.signing_pk(), *opkeys.wallet_pk());
+        }
+
+        let sign = |seqno, ops| {
+            let update = OperatorUpdate::new(seqno, ops);
+            let sig = sign_msg_hash(&seq_sk, &update.get_sighash());
+            SignedOperatorUpdate::new(update, sig)
+        };
+        let new_opkeys = make_dummy_operator_pubkeys_with_seed(2);
+        let updates = [
+            // Posted ahead of time, kept for a later epoch.
+            sign(3, vec![OperatorUpdateOp::Remove(2)]),
+            sign(
+                0,
+                vec![
+                    OperatorUpdateOp::Add {
+                        signing_pk: *new_opkeys.signing_pk(),
+                        wallet_pk: *new_opkeys.wallet_pk(),
+                    },
+                    OperatorUpdateOp::Remove(1),
+                ],
+            ),
+            // Would leave the deposit without any of its notaries.
+            sign(1, vec![OperatorUpdateOp::Remove(0)]),
+            // Not signed by the sequencer.
+            SignedOperatorUpdate::new(
+                OperatorUpdate::new(1, vec![OperatorUpdateOp::Remove(2)]),
+                ArbitraryGenerator::new().generate(),
+            ),
+        ];
+
+        // Post the updates in the first new block and let it mature.
+        let heights = tip_height..=tip_height + params.l1_reorg_safe_depth as u64;
+        let mut payloads = l1_payloads(&chain, heights);
+        let update_txs = updates
+            .into_iter()
+            .map(|update| {
+                let proof = ArbitraryGenerator::new_with_size(1 << 12).generate();
+                let protocol_op = ProtocolOperation::OperatorUpdate(update);
+                OperatorUpdateTx::new(L1Tx::new(proof, vec![], protocol_op))
+            })
+            .collect();
+        payloads[0] = payloads[0].clone().with_operator_update_txs(update_txs);
+        let l1_segment = L1Segment::new(payloads);
+        let result = process_l1_view_update(&mut state_cache, &l1_segment, &params);
+        assert!(result.is_ok());
+
+        let operator_table = state_cache.state().operator_table();
+        assert_eq!(operator_table.pending_updates().len(), 4);
+        assert_eq!(operator_table.indices().collect::<Vec<_>>(), vec![0, 1]);
+
+        // Nothing changes in the middle of an epoch.
+        assert!(state_cache.state().chain_tip_slot() < params.epoch_terminal_slot(0));
+        process_epoch_end(&mut state_cache, &params);
+        let operator_table = state_cache.state().operator_table();
+        assert_eq!(operator_table.pending_updates().len(), 4);
+        assert_eq!(state_cache.state().epoch(), 0);
+
+        process_operator_updates(&mut state_cache, &params);
+
+        let operator_table = state_cache.state().operator_table();
+        assert_eq!(operator_table.indices().collect::<Vec<_>>(), vec![0, 2]);
         assert_eq!(
-            state_cache.state().l1_view().safe_height(),
-            old_safe_height + to_mature_blk_num as u64
+            operator_table.get_operator(2).unwrap().wallet_pk(),
+            new_opkeys.wallet_pk()
         );
+        let pending_seqnos = operator_table
+            .pending_updates()
+            .iter()
+            .map(|update| update.update().seqno())
+            .collect::<Vec<_>>();
+        assert_eq!(pending_seqnos, vec![3]);
+        assert_eq!(operator_table.next_update_seqno(), 1);
+
+        // Deposits in blocks we'd already seen were made to the old set.
+        let tip_height = state_cache.state().l1_view().tip_height();
+        assert_eq!(operator_table.notary_set_at(tip_height), vec![0, 1]);
+        assert_eq!(operator_table.notary_set_at(tip_height + 1), vec![0, 2]);
+
+        let deposit = state_cache.state().deposits_table().get_deposit(0).unwrap();
+        assert_eq!(deposit.notary_operators(), &[0]);
     }
 }
@@ -0,0 +1,149 @@
+//! Prometheus metrics shared by the strata services.
+//!
+//! Metrics are recorded through the [`metrics`] facade, so library crates only need to emit them
+//! using the names defined here. Recording is a no-op until a binary calls [`init`], which
+//! installs the global Prometheus recorder and returns a future that serves the collected metrics
+//! over HTTP at [`METRICS_PATH`].
+
+use std::{convert::Infallible, future::Future, net::SocketAddr};
+
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
+use strata_config::MetricsConfig;
+use thiserror::Error;
+use tracing::*;
+
+/// The path at which the metrics are served.
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Label holding the status of the entity being counted.
+pub const STATUS_LABEL: &str = "status";
+
+/// Label holding the zkVM that a proving task runs on.
+pub const ZKVM_LABEL: &str = "zkvm";
+
+/// Height of the last L1 block processed by the reader.
+pub const L1_READER_HEIGHT: &str = "strata_l1_reader_height";
+
+/// Number of L1 reorgs detected by the reader.
+pub const L1_READER_REORGS: &str = "strata_l1_reader_reorgs_total";
+
+/// Number of L1 transactions that the broadcaster is still tracking.
+pub const BROADCASTER_QUEUE_DEPTH: &str = "strata_broadcaster_queue_depth";
+
+/// Number of L1 transaction status updates made by the broadcaster, labeled by [`STATUS_LABEL`].
+pub const BROADCASTER_TX_STATUS: &str = "strata_broadcaster_tx_status_total";
+
+/// Number of fee bumps of stuck L1 transactions made by the broadcaster.
+pub const BROADCASTER_FEE_BUMPS: &str = "strata_broadcaster_fee_bumps_total";
+
+/// Height of the current L2 chain tip picked by the fork choice manager.
+pub const FORK_CHOICE_TIP_HEIGHT: &str = "strata_fork_choice_tip_height";
+
+/// Height of the finalized L2 block known to the fork choice manager.
+pub const FORK_CHOICE_FINALIZED_HEIGHT: &str = "strata_fork_choice_finalized_height";
+
+/// Index of the last sync event processed by the client state machine.
+pub const CSM_EVENT_INDEX: &str = "strata_csm_event_index";
+
+/// Number of proving tasks, labeled by [`ZKVM_LABEL`] and [`STATUS_LABEL`].
+pub const PROVER_TASKS: &str = "strata_prover_tasks";
+
+/// Number of bridge duties executed by an operator, labeled by [`STATUS_LABEL`].
+pub const BRIDGE_DUTY_STATUS: &str = "strata_bridge_duty_status_total";
+
+/// Errors that can occur while setting up the metrics server.
+#[derive(Debug, Error)]
+pub enum MetricsError {
+    /// The Prometheus recorder could not be installed.
+    #[error("could not install recorder: {0}")]
+    Recorder(#[from] BuildError),
+
+    /// The configured address is not a valid socket address.
+    #[error("invalid metrics address: {0}")]
+    InvalidAddress(#[from] std::net::AddrParseError),
+
+    /// The HTTP server failed.
+    #[error("metrics server: {0}")]
+    Server(#[from] hyper::Error),
+}
+
+/// Installs the global Prometheus recorder and returns a future that serves the metrics at
+/// [`METRICS_PATH`] on the configured address.
+///
+/// This must be called at most once per process.
+pub fn init(
+    config: &MetricsConfig,
+) -> Result<impl Future<Output = Result<(), MetricsError>>, MetricsError> {
+    let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+    let handle = PrometheusBuilder::new().install_recorder()?;
+
+    Ok(serve(handle, addr))
+}
+
+async fn serve(handle: PrometheusHandle, addr: SocketAddr) -> Result<(), MetricsError> {
+    let make_svc = make_service_fn(move |_conn| {
+        let handle = handle.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let res = handle_request(&handle, &req);
+                async move { Ok::<_, Infallible>(res) }
+            }))
+        }
+    });
+
+    let server = Server::try_bind(&addr)?.serve(make_svc);
+    info!(%addr, "metrics server started");
+
+    server.await?;
+
+    Ok(())
+}
+
+fn handle_request(handle: &PrometheusHandle, req: &Request<Body>) -> Response<Body> {
+    let res = if req.method() == Method::GET && req.uri().path() == METRICS_PATH {
+        Response::builder()
+            .header(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)
+            .body(Body::from(handle.render()))
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+    };
+
+    res.expect("metrics: build response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_request() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+
+        let req = Request::get(METRICS_PATH).body(Body::empty()).unwrap();
+        let res = handle_request(&handle, &req);
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            PROMETHEUS_CONTENT_TYPE
+        );
+
+        let req = Request::get("/other").body(Body::empty()).unwrap();
+        let res = handle_request(&handle, &req);
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let req = Request::post(METRICS_PATH).body(Body::empty()).unwrap();
+        let res = handle_request(&handle, &req);
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -11,4 +11,50 @@ pub struct RelayerConfig {
 
     /// Relay misc messages that don't check signatures.
     pub relay_misc: bool,
+
+    /// Limits on the messages relayed.  The defaults are used for anything that is not set.
+    #[serde(default)]
+    pub limits: RelayLimitsConfig,
+}
+
+/// Limits on the messages the bridge relayer accepts, to keep a single operator from flooding the
+/// network.
+#[derive(Copy, Clone, Deserialize, Debug)]
+#[serde(default)]
+pub struct RelayLimitsConfig {
+    /// Maximum number of messages relayed from a single operator per refresh interval.
+    pub max_msgs_per_operator: u32,
+
+    /// Maximum number of messages relayed with a single scope per refresh interval.
+    pub max_msgs_per_scope: u32,
+
+    /// Maximum number of messages relayed per refresh interval whose source can't be verified,
+    /// which are misc messages and, if relayed, unverified ones.  These share a single quota so
+    /// that they can't use up the quota of the operator they claim to be from.
+    pub max_unverified_msgs: u32,
+
+    /// Maximum size of a message payload, in bytes.
+    pub max_payload_size: usize,
+
+    /// Maximum number of messages held back from a single operator because the duty they're for
+    /// isn't known yet.
+    pub max_deferred_msgs_per_operator: u32,
+
+    /// Relay the messages we can't check the signatures of because we don't have a chainstate
+    /// yet, instead of dropping them.
+    pub relay_unverified: bool,
+}
+
+impl Default for RelayLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_msgs_per_operator: 1_000,
+            max_msgs_per_scope: 256,
+            max_unverified_msgs: 256,
+            // Nonces and partial signatures are well below this.
+            max_payload_size: 4_096,
+            max_deferred_msgs_per_operator: 64,
+            relay_unverified: false,
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{net::SocketAddr, path::PathBuf};
 
 use bitcoin::Network;
 use serde::Deserialize;
@@ -9,6 +9,8 @@ use crate::RelayerConfig;
 pub struct SequencerConfig {
     /// path to sequencer root key
     pub sequencer_key: PathBuf,
+    /// rotation of the sequencer key derived from the root key, defaults to the original key
+    pub sequencer_key_rotation: Option<u32>,
     /// address with funds for sequencer transactions
     pub sequencer_bitcoin_address: Option<String>,
 }
@@ -17,6 +19,10 @@ pub struct SequencerConfig {
 pub struct FullNodeConfig {
     /// host:port of sequencer rpc
     pub sequencer_rpc: String,
+    /// start a new node from the latest checkpoint finalized on L1 instead of from genesis, the
+    /// execution client is expected to already have the checkpoint's block
+    #[serde(default)]
+    pub checkpoint_sync: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -51,6 +57,64 @@ pub struct BitcoindConfig {
     pub rpc_user: String,
     pub rpc_password: String,
     pub network: Network,
+    /// ZMQ endpoint bitcoind publishes `hashblock` or `rawblock` on, e.g.
+    /// `tcp://127.0.0.1:28332`. If set, the L1 reader polls as soon as a new block is announced.
+    pub zmq_block_url: Option<String>,
+}
+
+/// Where the client follows L1 from.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum L1BackendConfig {
+    /// The `bitcoind` configured in `[bitcoind_rpc]`.
+    Bitcoind,
+    /// An Esplora HTTP API. The network is still taken from `[bitcoind_rpc]`.
+    Esplora {
+        /// Base URL of the API, e.g. `https://mempool.space/signet/api`.
+        url: String,
+    },
+}
+
+/// How much history the node keeps around.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PruningConfig {
+    /// Everything is kept.
+    #[default]
+    Archive,
+    /// Only what's needed to follow the chain since the last finalized checkpoint is kept.
+    Full,
+    /// Like `Full`, but the data of the given number of epochs before the last finalized
+    /// checkpoint is kept as well.
+    Custom { epochs: u64 },
+}
+
+/// Configuration of the P2P network L2 blocks get gossiped over.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct P2pConfig {
+    /// Address to accept connections from other nodes on.
+    pub listen_addr: SocketAddr,
+    /// Addresses of the nodes to connect to on startup, reconnecting whenever the connection
+    /// drops.
+    pub peers: Vec<SocketAddr>,
+    /// Maximum number of connected peers, inbound and outbound.  Slots for the configured peers
+    /// are always kept free, so inbound connections only get what is left.
+    pub max_peers: usize,
+    /// Whether a full node keeps syncing from the sequencer RPC whenever the peers can't serve
+    /// it, instead of only syncing from peers.
+    pub sync_from_sequencer: bool,
+}
+
+impl Default for P2pConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: SocketAddr::from(([0, 0, 0, 0], 8439)),
+            peers: Vec::new(),
+            max_peers: 32,
+            sync_from_sequencer: true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -64,18 +128,138 @@ pub struct ExecConfig {
     pub reth: RethELConfig,
 }
 
+/// Configuration of the Prometheus metrics endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricsConfig {
+    /// IP address to serve the metrics on.
+    pub host: String,
+    /// Port to serve the metrics on.
+    pub port: u16,
+}
+
+/// Configuration of the listener for the `strataadmin` RPC namespace.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdminRpcConfig {
+    /// IP address to serve the admin methods on.
+    pub host: String,
+    /// Port to serve the admin methods on.
+    pub port: u16,
+    /// Path to the hex-encoded secret the admin requests have to be signed with as JWTs, like the
+    /// engine API's.  Requests are not authenticated if this is not set, which is only allowed
+    /// if `host` is a loopback address.
+    pub jwt_secret: Option<PathBuf>,
+}
+
+/// Restrictions on the methods served on the public RPC listener.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PublicRpcConfig {
+    /// Names of the methods that are served, e.g. `strata_syncStatus`.  Every method is served if
+    /// this is not set.
+    pub allowed_methods: Option<Vec<String>>,
+    /// Maximum number of requests served per second to each client IP address.  There's no
+    /// limit if this is not set.
+    pub max_requests_per_sec: Option<u32>,
+}
+
+/// Configuration of the fee bumping of L1 txs that are stuck in the mempool.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BroadcasterConfig {
+    /// Number of L1 blocks a published tx may wait in the mempool before its fee gets bumped.
+    pub fee_bump_after_blocks: u64,
+    /// Maximum number of times the fee of a single tx gets bumped.
+    pub max_fee_bumps: u32,
+    /// Upper bound of the fee rate used for bumps, in sat/vB.
+    pub max_fee_rate: u64,
+}
+
+/// Configuration of the L1 writer that inscribes the sequencer's blobs.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WriterConfig {
+    /// Time between each processing of the blob queue, in millis.
+    pub poll_duration_ms: u64,
+    /// Amount (in sats) sent to the sequencer address by the reveal tx.
+    pub amount_for_reveal_txn: u64,
+    /// How the fee rate of the inscriptions gets determined.
+    pub fee_policy: InscriptionFeePolicy,
+    /// Lower bound of the fee rate of the inscriptions, in sat/vB.
+    pub min_fee_rate: u64,
+    /// Upper bound of the fee rate of the inscriptions, in sat/vB.
+    pub max_fee_rate: u64,
+    /// Maximum amount (in sats) spent on inscription fees in a single checkpoint epoch. There's no
+    /// limit if this is not set.
+    pub fee_budget_per_epoch: Option<u64>,
+    /// Maximum number of blobs inscribed in a single commit/reveal pair.
+    pub max_blobs_per_inscription: usize,
+    /// Maximum total size (in bytes) of the blobs inscribed in a single commit/reveal pair.
+    pub max_inscription_size: usize,
+    /// How long to wait for more blobs to fill up a batch before inscribing it, in millis.
+    pub batch_window_ms: u64,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            poll_duration_ms: 1_000,
+            amount_for_reveal_txn: 1_000,
+            fee_policy: InscriptionFeePolicy::Smart { conf_target: 1 },
+            min_fee_rate: 1,
+            max_fee_rate: 1_000,
+            fee_budget_per_epoch: None,
+            max_blobs_per_inscription: 16,
+            // Leaves enough room below the standard tx weight limit for the rest of the reveal tx
+            max_inscription_size: 300_000,
+            batch_window_ms: 5_000,
+        }
+    }
+}
+
+/// How the fee rate of the inscriptions gets determined.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InscriptionFeePolicy {
+    /// Twice the rate returned by `estimatesmartfee` for the confirmation target, in blocks.
+    Smart { conf_target: u16 },
+
+    /// Fixed fee in sat/vB.
+    Fixed(u64),
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub client: ClientConfig,
     pub bitcoind_rpc: BitcoindConfig,
+    /// L1 is followed through `bitcoind` if this is not set.
+    pub l1_backend: Option<L1BackendConfig>,
     pub sync: SyncConfig,
     pub exec: ExecConfig,
     pub relayer: RelayerConfig,
+    /// The metrics endpoint is disabled if this is not set.
+    pub metrics: Option<MetricsConfig>,
+    /// Stuck L1 txs don't get their fees bumped if this is not set.
+    pub broadcaster: Option<BroadcasterConfig>,
+    /// The defaults are used for anything that is not set.
+    #[serde(default)]
+    pub writer: WriterConfig,
+    /// Everything is kept if this is not set.
+    #[serde(default)]
+    pub pruning: PruningConfig,
+    /// P2P networking is disabled if this is not set.
+    pub p2p: Option<P2pConfig>,
+    /// The admin methods are served on the public listener if this is not set.
+    pub admin_rpc: Option<AdminRpcConfig>,
+    /// The defaults are used for anything that is not set.
+    #[serde(default)]
+    pub public_rpc: PublicRpcConfig,
 }
 
 #[cfg(test)]
 mod test {
-    use crate::config::Config;
+    use crate::config::{
+        AdminRpcConfig, ClientMode, Config, FullNodeConfig, InscriptionFeePolicy, L1BackendConfig,
+        P2pConfig, PruningConfig,
+    };
 
     #[test]
     fn test_config_load() {
@@ -110,6 +294,29 @@ mod test {
             refresh_interval = 10
             stale_duration = 120
             relay_misc = true
+
+            [metrics]
+            host = "0.0.0.0"
+            port = 9090
+
+            [broadcaster]
+            fee_bump_after_blocks = 3
+            max_fee_bumps = 5
+            max_fee_rate = 500
+
+            [writer]
+            fee_policy = { smart = { conf_target = 3 } }
+            max_fee_rate = 200
+            fee_budget_per_epoch = 1000000
+
+            [admin_rpc]
+            host = "127.0.0.1"
+            port = 8433
+            jwt_secret = "/path/to/jwt.hex"
+
+            [public_rpc]
+            allowed_methods = ["strata_syncStatus", "strata_getRawBundles"]
+            max_requests_per_sec = 100
         "#;
 
         let config = toml::from_str::<Config>(config_string_sequencer);
@@ -118,6 +325,40 @@ mod test {
             "should be able to load sequencer TOML config but got: {:?}",
             config.err()
         );
+        let config = config.unwrap();
+        assert!(config.metrics.is_some(), "metrics config should be loaded");
+        assert!(
+            config.broadcaster.is_some(),
+            "broadcaster config should be loaded"
+        );
+        assert!(matches!(
+            config.writer.fee_policy,
+            InscriptionFeePolicy::Smart { conf_target: 3 }
+        ));
+        assert_eq!(config.writer.max_fee_rate, 200);
+        assert_eq!(
+            config.writer.poll_duration_ms, 1_000,
+            "unset writer options should have defaults"
+        );
+        assert_eq!(
+            config.pruning,
+            PruningConfig::Archive,
+            "nothing should be pruned by default"
+        );
+        assert!(config.p2p.is_none(), "p2p config should be optional");
+        assert!(matches!(
+            config.admin_rpc,
+            Some(AdminRpcConfig {
+                port: 8433,
+                jwt_secret: Some(_),
+                ..
+            })
+        ));
+        assert_eq!(
+            config.public_rpc.allowed_methods.as_ref().map(Vec::len),
+            Some(2)
+        );
+        assert_eq!(config.public_rpc.max_requests_per_sec, Some(100));
 
         let config_string_fullnode = r#"
             [bitcoind_rpc]
@@ -125,6 +366,7 @@ mod test {
             rpc_user = "alpen"
             rpc_password = "alpen"
             network = "regtest"
+            zmq_block_url = "tcp://127.0.0.1:28332"
 
             [client]
             rpc_host = "0.0.0.0"
@@ -133,6 +375,7 @@ mod test {
             datadir = "/path/to/data/directory"
             sequencer_bitcoin_address = "some_addr"
             sequencer_rpc = "9.9.9.9:8432"
+            checkpoint_sync = true
             seq_pubkey = "123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0"
             db_retry_count = 5
 
@@ -150,6 +393,21 @@ mod test {
             refresh_interval = 10
             stale_duration = 120
             relay_misc = true
+
+            [relayer.limits]
+            max_msgs_per_operator = 100
+
+            [l1_backend]
+            type = "esplora"
+            url = "http://localhost:3002"
+
+            [pruning]
+            mode = "custom"
+            epochs = 4
+
+            [p2p]
+            listen_addr = "0.0.0.0:9000"
+            peers = ["10.0.0.1:9000", "10.0.0.2:9000"]
         "#;
 
         let config = toml::from_str::<Config>(config_string_fullnode);
@@ -158,5 +416,47 @@ mod test {
             "should be able to load full-node TOML config but got: {:?}",
             config.err()
         );
+        let config = config.unwrap();
+        assert!(
+            config.metrics.is_none(),
+            "metrics config should be optional"
+        );
+        assert!(matches!(
+            config.l1_backend,
+            Some(L1BackendConfig::Esplora { .. })
+        ));
+        assert!(matches!(
+            config.client.client_mode,
+            ClientMode::FullNode(FullNodeConfig {
+                checkpoint_sync: true,
+                ..
+            })
+        ));
+        assert_eq!(
+            config.bitcoind_rpc.zmq_block_url.as_deref(),
+            Some("tcp://127.0.0.1:28332")
+        );
+        assert_eq!(config.pruning, PruningConfig::Custom { epochs: 4 });
+        assert_eq!(config.relayer.limits.max_msgs_per_operator, 100);
+        assert_eq!(
+            config.relayer.limits.max_payload_size, 4_096,
+            "unset relay limits should have defaults"
+        );
+        assert!(
+            config.admin_rpc.is_none(),
+            "admin rpc config should be optional"
+        );
+        assert!(
+            config.public_rpc.allowed_methods.is_none(),
+            "every method should be served by default"
+        );
+        assert!(matches!(
+            config.p2p,
+            Some(P2pConfig {
+                max_peers: 32,
+                sync_from_sequencer: true,
+                ..
+            })
+        ));
     }
 }
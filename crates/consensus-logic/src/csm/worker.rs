@@ -4,6 +4,8 @@
 
 use std::{sync::Arc, thread};
 
+use metrics::gauge;
+use strata_common::metrics::CSM_EVENT_INDEX;
 use strata_db::{
     traits::*,
     types::{CheckpointConfStatus, CheckpointEntry, CheckpointProvingStatus},
@@ -239,6 +241,7 @@ fn handle_sync_event<D: Database>(
 
     // Make sure that the new state index is set as expected.
     assert_eq!(state.state_tracker.cur_state_idx(), ev_idx);
+    gauge!(CSM_EVENT_INDEX).set(ev_idx as f64);
 
     // Write the client state checkpoint periodically based on the event idx..
     if ev_idx % state.params.run.client_checkpoint_interval as u64 == 0 {
@@ -299,11 +302,26 @@ fn apply_action<D: Database>(
 
             // TODO: use l1blkid during chain state genesis ?
 
-            let chstate = genesis::init_genesis_chainstate(&state.params, state.database.as_ref())
-                .map_err(|err| {
-                    error!(err = %err, "failed to compute chain genesis");
-                    Error::GenesisFailed(err.to_string())
+            // The client state is updated with the genesis verification state along with
+            // emitting this action.
+            let genesis_l1_vs = state
+                .cur_state()
+                .l1_view()
+                .tip_verification_state()
+                .cloned()
+                .ok_or_else(|| {
+                    Error::GenesisFailed("missing L1 genesis verification state".to_owned())
                 })?;
+
+            let chstate = genesis::init_genesis_chainstate(
+                &state.params,
+                state.database.as_ref(),
+                genesis_l1_vs,
+            )
+            .map_err(|err| {
+                error!(err = %err, "failed to compute chain genesis");
+                Error::GenesisFailed(err.to_string())
+            })?;
             status_channel.update_chainstate(chstate);
         }
 
@@ -23,13 +23,18 @@ use strata_state::{
     chain_state::Chainstate,
     client_state::{ClientState, LocalL1State},
     exec_update::{
-        construct_ops_from_deposit_intents, ELDepositData, ExecUpdate, Op, UpdateOutput,
+        construct_ops_from_deposit_intents, construct_ops_from_forced_inclusions, ELDepositData,
+        ExecUpdate, Op, UpdateOutput,
     },
+    forced_inclusion::ForcedInclusionTx,
     header::L2BlockHeader,
     l1::{DepositUpdateTx, L1HeaderPayload, L1HeaderRecord},
+    operator_update::OperatorUpdateTx,
     prelude::*,
     state_op::*,
-    tx::ProtocolOperation::Deposit,
+    tx::ProtocolOperation::{
+        Deposit, DepositReimbursement, ForcedInclusion, OperatorUpdate, WithdrawalFulfillment,
+    },
 };
 use tracing::*;
 
@@ -172,13 +177,7 @@ fn prepare_l1_segment(
     if maturation_queue_size == 0 {
         let mut payloads = Vec::new();
         for (h, _b) in unacc_blocks.iter().take(max_l1_entries) {
-            let rec = load_header_record(*h, l1_db)?;
-            let deposit_update_tx = fetch_deposit_update_txs(*h, l1_db)?;
-            payloads.push(
-                L1HeaderPayload::new(*h, rec)
-                    .with_deposit_update_txs(deposit_update_tx)
-                    .build(),
-            );
+            payloads.push(load_header_payload(*h, l1_db)?);
         }
 
         debug!(n = %payloads.len(), "filling in empty queue with fresh L1 payloads");
@@ -216,13 +215,7 @@ fn prepare_l1_segment(
     // Load the blocks.
     let mut payloads = Vec::new();
     for (h, _b) in fresh_blocks {
-        let rec = load_header_record(*h, l1_db)?;
-        let deposit_update_tx = fetch_deposit_update_txs(*h, l1_db)?;
-        payloads.push(
-            L1HeaderPayload::new(*h, rec)
-                .with_deposit_update_txs(deposit_update_tx)
-                .build(),
-        );
+        payloads.push(load_header_payload(*h, l1_db)?);
     }
 
     if !payloads.is_empty() {
@@ -243,24 +236,54 @@ fn load_header_record(h: u64, l1_db: &impl L1Database) -> Result<L1HeaderRecord,
     ))
 }
 
-fn fetch_deposit_update_txs(
+/// Loads the payload for the L1 block at the given height, with the txs relevant to the rollup.
+fn load_header_payload(h: u64, l1_db: &impl L1Database) -> Result<L1HeaderPayload, Error> {
+    let rec = load_header_record(h, l1_db)?;
+    let (deposit_update_txs, forced_inclusion_txs, operator_update_txs) =
+        fetch_protocol_txs(h, l1_db)?;
+    Ok(L1HeaderPayload::new(h, rec)
+        .with_deposit_update_txs(deposit_update_txs)
+        .with_forced_inclusion_txs(forced_inclusion_txs)
+        .with_operator_update_txs(operator_update_txs)
+        .build())
+}
+
+fn fetch_protocol_txs(
     h: u64,
     l1_db: &impl L1Database,
-) -> Result<Vec<DepositUpdateTx>, Error> {
+) -> Result<
+    (
+        Vec<DepositUpdateTx>,
+        Vec<ForcedInclusionTx>,
+        Vec<OperatorUpdateTx>,
+    ),
+    Error,
+> {
     let relevant_tx_ref = l1_db
         .get_block_txs(h)?
         .ok_or(Error::MissingL1BlockHeight(h))?;
 
     let mut deposit_update_txs = Vec::new();
+    let mut forced_inclusion_txs = Vec::new();
+    let mut operator_update_txs = Vec::new();
     for tx_ref in relevant_tx_ref {
         let tx = l1_db.get_tx(tx_ref)?.ok_or(Error::MissingL1Tx)?;
 
-        if let Deposit(dep) = tx.protocol_operation() {
-            deposit_update_txs.push(DepositUpdateTx::new(tx, tx_ref.position()));
+        match tx.protocol_operation() {
+            Deposit(_) | WithdrawalFulfillment(_) | DepositReimbursement(_) => {
+                deposit_update_txs.push(DepositUpdateTx::new(tx, tx_ref.position()))
+            }
+            ForcedInclusion(_) => forced_inclusion_txs.push(ForcedInclusionTx::new(tx)),
+            OperatorUpdate(_) => operator_update_txs.push(OperatorUpdateTx::new(tx)),
+            _ => {}
         }
     }
 
-    Ok(deposit_update_txs)
+    Ok((
+        deposit_update_txs,
+        forced_inclusion_txs,
+        operator_update_txs,
+    ))
 }
 
 /// Takes two partially-overlapping lists of block indexes and IDs and returns a
@@ -349,7 +372,15 @@ fn prepare_exec_data<E: ExecEngineCtl>(
 
     // construct el_ops by looking at chainstate
     let pending_deposits = prev_chstate.exec_env_state().pending_deposits();
-    let el_ops = construct_ops_from_deposit_intents(pending_deposits, params.max_deposits_in_block);
+    let mut el_ops =
+        construct_ops_from_deposit_intents(pending_deposits, params.max_deposits_in_block);
+
+    // also process the pending forced inclusions, they have to be processed oldest first
+    let pending_forced_incls = prev_chstate.exec_env_state().pending_forced_incls();
+    el_ops.extend(construct_ops_from_forced_inclusions(
+        pending_forced_incls,
+        params.max_forced_incls_in_block,
+    ));
     let payload_env = PayloadEnv::new(timestamp, prev_l2_blkid, safe_l1_block, el_ops);
 
     let key = engine.prepare_payload(payload_env)?;
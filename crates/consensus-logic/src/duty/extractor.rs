@@ -1,6 +1,9 @@
 use strata_db::traits::ChainstateDatabase;
-use strata_primitives::{buf::Buf32, params::Params};
-use strata_state::{batch::BatchInfo, client_state::ClientState, id::L2BlockId};
+use strata_primitives::{
+    buf::Buf32,
+    params::{Params, RollupParams},
+};
+use strata_state::{batch::BatchInfo, client_state::ClientState};
 use tracing::*;
 
 use super::types::{BlockSigningDuty, Duty, Identity};
@@ -9,8 +12,8 @@ use crate::{duty::types::BatchCheckpointDuty, errors::Error};
 /// Extracts new duties given a consensus state and a identity.
 pub fn extract_duties(
     state: &ClientState,
-    _ident: &Identity,
-    _params: &Params,
+    ident: &Identity,
+    params: &Params,
     chs_db: &impl ChainstateDatabase,
     rollup_params_commitment: Buf32,
 ) -> Result<Vec<Duty>, Error> {
@@ -23,27 +26,43 @@ pub fn extract_duties(
     let tip_height = ss.chain_tip_height();
     let tip_blkid = *ss.chain_tip_blkid();
 
-    // Since we're not rotating sequencers, for now we just *always* produce a
-    // new block.
-    let duty_data = BlockSigningDuty::new_simple(tip_height + 1, tip_blkid);
-    let mut duties = vec![Duty::SignBlock(duty_data)];
+    // We only sign the blocks and checkpoints that the credential rule makes us responsible for,
+    // which is always the case if there is a single sequencer.
+    let Identity::Sequencer(pubkey) = ident;
+    let cred_rule = &params.rollup().cred_rule;
 
-    duties.extend(extract_batch_duties(
+    let mut duties = Vec::new();
+
+    let target_slot = tip_height + 1;
+    if cred_rule.is_signer_at(target_slot, pubkey) {
+        let duty_data = BlockSigningDuty::new_simple(target_slot, tip_blkid);
+        duties.push(Duty::SignBlock(duty_data));
+    } else {
+        trace!(%target_slot, "not the signer for the next block");
+    }
+
+    let batch_duties = extract_batch_duties(
         state,
         tip_height,
-        tip_blkid,
         chs_db,
+        params.rollup(),
         rollup_params_commitment,
-    )?);
+    )?;
+    duties.extend(batch_duties.into_iter().filter(|duty| match duty {
+        Duty::CommitBatch(data) => cred_rule.is_signer_at(data.batch_info().l2_range.1, pubkey),
+        _ => true,
+    }));
 
     Ok(duties)
 }
 
+/// Extracts the duty to commit the checkpoint for the next epoch, once our tip has reached its
+/// last block.
 fn extract_batch_duties(
     state: &ClientState,
     tip_height: u64,
-    tip_id: L2BlockId,
     chs_db: &impl ChainstateDatabase,
+    rollup_params: &RollupParams,
     rollup_params_commitment: Buf32,
 ) -> Result<Vec<Duty>, Error> {
     if !state.is_chain_active() {
@@ -57,16 +76,15 @@ fn extract_batch_duties(
         None => {
             debug!(
                 ?tip_height,
-                ?tip_id,
                 "No finalized checkpoint, creating new checkpiont"
             );
-            // But wait until we've move past genesis, perhaps this can be
-            // configurable. Right now this is not ideal because we will be wasting proving resource
-            // just for a couple of initial blocks in the first batch
-            if tip_height == 0 {
+            let first_checkpoint_idx = 0;
+
+            // The checkpoint covers the whole epoch, so wait until it has ended.
+            let epoch_end = rollup_params.epoch_terminal_slot(first_checkpoint_idx);
+            if tip_height < epoch_end {
                 return Ok(vec![]);
             }
-            let first_checkpoint_idx = 0;
 
             let current_l1_state = state
                 .l1_view()
@@ -86,7 +104,7 @@ fn extract_batch_duties(
             let l1_transition = (genesis_l1_state_hash, current_l1_state_hash);
 
             // Start from first non-genesis l2 block height
-            let l2_range = (1, tip_height);
+            let l2_range = (1, epoch_end);
 
             let initial_chain_state = chs_db
                 .get_toplevel_state(0)?
@@ -94,10 +112,11 @@ fn extract_batch_duties(
             let initial_chain_state_root = initial_chain_state.compute_state_root();
 
             let current_chain_state = chs_db
-                .get_toplevel_state(tip_height)?
-                .ok_or(Error::MissingIdxChainstate(0))?;
+                .get_toplevel_state(epoch_end)?
+                .ok_or(Error::MissingIdxChainstate(epoch_end))?;
             let current_chain_state_root = current_chain_state.compute_state_root();
             let l2_transition = (initial_chain_state_root, current_chain_state_root);
+            let epoch_end_id = current_chain_state.chain_tip_blockid();
 
             let new_batch = BatchInfo::new(
                 first_checkpoint_idx,
@@ -105,7 +124,7 @@ fn extract_batch_duties(
                 l2_range,
                 l1_transition,
                 l2_transition,
-                tip_id,
+                epoch_end_id,
                 (0, current_l1_state.total_accumulated_pow),
                 rollup_params_commitment,
             );
@@ -117,6 +136,12 @@ fn extract_batch_duties(
         Some(prev_checkpoint) => {
             let checkpoint = prev_checkpoint.batch_info.clone();
 
+            // The checkpoint covers the whole epoch, so wait until it has ended.
+            let epoch_end = rollup_params.epoch_terminal_slot(checkpoint.idx + 1);
+            if tip_height < epoch_end {
+                return Ok(vec![]);
+            }
+
             let current_l1_state = state
                 .l1_view()
                 .tip_verification_state()
@@ -128,14 +153,13 @@ fn extract_batch_duties(
             let current_l1_state_hash = current_l1_state.compute_hash().unwrap();
             let l1_transition = (checkpoint.l1_transition.1, current_l1_state_hash);
 
-            // Also, rather than tip heights, we might need to limit the max range a prover will be
-            // proving
-            let l2_range = (checkpoint.l2_range.1 + 1, tip_height);
+            let l2_range = (checkpoint.l2_range.1 + 1, epoch_end);
             let current_chain_state = chs_db
-                .get_toplevel_state(tip_height)?
-                .ok_or(Error::MissingIdxChainstate(0))?;
+                .get_toplevel_state(epoch_end)?
+                .ok_or(Error::MissingIdxChainstate(epoch_end))?;
             let current_chain_state_root = current_chain_state.compute_state_root();
             let l2_transition = (checkpoint.l2_transition.1, current_chain_state_root);
+            let epoch_end_id = current_chain_state.chain_tip_blockid();
 
             let new_batch = BatchInfo::new(
                 checkpoint.idx + 1,
@@ -143,7 +167,7 @@ fn extract_batch_duties(
                 l2_range,
                 l1_transition,
                 l2_transition,
-                tip_id,
+                epoch_end_id,
                 (
                     checkpoint.l1_pow_transition.1,
                     current_l1_state.total_accumulated_pow,
@@ -2,7 +2,9 @@
 
 use std::sync::Arc;
 
+use metrics::gauge;
 use strata_chaintsn::transition::process_block;
+use strata_common::metrics::{FORK_CHOICE_FINALIZED_HEIGHT, FORK_CHOICE_TIP_HEIGHT};
 use strata_db::{
     errors::DbError,
     traits::{BlockStatus, ChainstateDatabase, Database},
@@ -435,6 +437,14 @@ fn handle_new_state<D: Database>(
     let blkid = sync.finalized_blkid();
     let fin_report = fcm_state.chain_tracker.update_finalized_tip(blkid)?;
     info!(?blkid, "updated finalized tip");
+
+    // the height is only needed for the metrics, so failing to get it must not abort the FCM
+    match fcm_state.get_block_index(blkid) {
+        Ok(finalized_height) => {
+            gauge!(FORK_CHOICE_FINALIZED_HEIGHT).set(finalized_height as f64);
+        }
+        Err(e) => warn!(?blkid, err = ?e, "failed to get finalized block height"),
+    }
     trace!(?fin_report, "finalization report");
     // TODO do something with the finalization report
 
@@ -580,5 +590,7 @@ fn apply_tip_update<D: Database>(
         fc_manager.cur_index = idx;
     }
 
+    gauge!(FORK_CHOICE_TIP_HEIGHT).set(fc_manager.cur_index as f64);
+
     Ok(pre_state)
 }
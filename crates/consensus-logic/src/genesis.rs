@@ -14,7 +14,7 @@ use strata_state::{
     exec_update::{ExecUpdate, UpdateInput, UpdateOutput},
     genesis::GenesisStateData,
     header::L2BlockHeader,
-    l1::{L1HeaderRecord, L1ViewState},
+    l1::{HeaderVerificationState, L1HeaderRecord, L1ViewState},
     prelude::*,
 };
 use tracing::*;
@@ -42,12 +42,17 @@ pub fn init_client_state(params: &Params, database: &impl Database) -> anyhow::R
 /// the rollup chain.  Requires that the L1 blocks between the horizon and the
 /// L2 genesis are already in the datatabase.
 ///
+/// The header verification state must have the L1 genesis block as its last
+/// verified block, the headers of the L1 blocks after it are verified starting
+/// from it.
+///
 /// This does not update the client state to include the new sync state data
 /// that it should have now.  That is introduced by writing a new sync event for
 /// that.
 pub fn init_genesis_chainstate(
     params: &Params,
     database: &impl Database,
+    genesis_l1_vs: HeaderVerificationState,
 ) -> anyhow::Result<Chainstate> {
     debug!("preparing database genesis chainstate!");
 
@@ -61,7 +66,7 @@ pub fn init_genesis_chainstate(
 
     // Build the genesis block and genesis consensus states.
     let gblock = make_genesis_block(params);
-    let gchstate = make_genesis_chainstate(&gblock, pregenesis_mfs, params);
+    let gchstate = make_genesis_chainstate(&gblock, pregenesis_mfs, genesis_l1_vs, params);
 
     // Now insert things into the database.
     let chs_db = database.chain_state_db();
@@ -142,6 +147,7 @@ pub fn make_genesis_block(params: &Params) -> L2BlockBundle {
 pub fn make_genesis_chainstate(
     gblock: &L2BlockBundle,
     pregenesis_mfs: Vec<L1BlockRecord>,
+    genesis_l1_vs: HeaderVerificationState,
     params: &Params,
 ) -> Chainstate {
     let genesis_blkid = gblock.header().get_blockid();
@@ -152,7 +158,7 @@ pub fn make_genesis_chainstate(
 
     let horizon_blk_height = params.rollup.horizon_l1_height;
     let genesis_blk_rec = L1HeaderRecord::from(pregenesis_mfs.last().unwrap());
-    let l1vs = L1ViewState::new_at_horizon(horizon_blk_height, genesis_blk_rec);
+    let l1vs = L1ViewState::new_at_horizon(horizon_blk_height, genesis_blk_rec, genesis_l1_vs);
 
     let optbl = construct_operator_table(&params.rollup().operator_config);
     let gdata = GenesisStateData::new(genesis_blkid, l1vs, optbl, gees);
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
 use bitcoin::{consensus::serialize, hashes::Hash, Block};
-use secp256k1::XOnlyPublicKey;
-use strata_db::traits::{Database, L1Database};
+use strata_db::{
+    traits::{Database, L1Database},
+    DbResult,
+};
 use strata_primitives::{
     block_credential::CredRule,
     buf::Buf32,
@@ -31,21 +33,8 @@ pub fn bitcoin_data_handler_task<D: Database + Send + Sync + 'static>(
     mut event_rx: mpsc::Receiver<L1Event>,
     params: Arc<Params>,
 ) -> anyhow::Result<()> {
-    // Parse the sequencer pubkey once here as this involves and FFI call that we don't want to be
-    // calling per event although it can be generated from the params passed to the relevant event
-    // handler.
-    let seq_pubkey = match params.rollup.cred_rule {
-        CredRule::Unchecked => None,
-        CredRule::SchnorrKey(buf32) => Some(
-            XOnlyPublicKey::try_from(buf32)
-                .expect("the sequencer pubkey must be valid in the params"),
-        ),
-    };
-
     while let Some(event) = event_rx.blocking_recv() {
-        if let Err(e) =
-            handle_bitcoin_event(event, l1db.as_ref(), csm_ctl.as_ref(), &params, seq_pubkey)
-        {
+        if let Err(e) = handle_bitcoin_event(event, l1db.as_ref(), csm_ctl.as_ref(), &params) {
             error!(err = %e, "failed to handle L1 event");
         }
     }
@@ -59,7 +48,6 @@ fn handle_bitcoin_event<L1D>(
     l1db: &L1D,
     csm_ctl: &CsmController,
     params: &Arc<Params>,
-    seq_pubkey: Option<XOnlyPublicKey>,
 ) -> anyhow::Result<()>
 where
     L1D: L1Database + Sync + Send + 'static,
@@ -88,13 +76,7 @@ where
                 return Ok(());
             }
 
-            let l1blkid = blockdata.block().block_hash();
-
-            let manifest = generate_block_manifest(blockdata.block(), epoch);
-            let l1txs: Vec<_> = generate_l1txs(&blockdata);
-            let num_txs = l1txs.len();
-            l1db.put_block_data(blockdata.block_num(), manifest, l1txs.clone())?;
-            info!(%height, %l1blkid, txs = %num_txs, "wrote L1 block manifest");
+            store_l1_block_data(l1db, &blockdata, epoch)?;
 
             // Write to sync event db if it's something we care about.
             let blkid: Buf32 = blockdata.block().block_hash().into();
@@ -103,7 +85,7 @@ where
 
             // Check for da batch and send event accordingly
             debug!(?height, "Checking for da batch");
-            let checkpoints = check_for_da_batch(&blockdata, seq_pubkey);
+            let checkpoints = check_for_da_batch(&blockdata, &params.rollup().cred_rule);
             debug!(?checkpoints, "Received checkpoints");
             if !checkpoints.is_empty() {
                 let ev = SyncEvent::L1DABatch(height, checkpoints);
@@ -123,11 +105,25 @@ where
     }
 }
 
-/// Parses inscriptions and checks for batch data in the transactions
-fn check_for_da_batch(
+/// Writes the manifest and the relevant transactions of an L1 block to the database.
+pub fn store_l1_block_data(
+    l1db: &impl L1Database,
     blockdata: &BlockData,
-    seq_pubkey: Option<XOnlyPublicKey>,
-) -> Vec<BatchCheckpoint> {
+    epoch: u64,
+) -> DbResult<()> {
+    let height = blockdata.block_num();
+    let l1blkid = blockdata.block().block_hash();
+
+    let manifest = generate_block_manifest(blockdata.block(), epoch);
+    let l1txs: Vec<_> = generate_l1txs(blockdata);
+    let num_txs = l1txs.len();
+    l1db.put_block_data(height, manifest, l1txs)?;
+    info!(%height, %l1blkid, txs = %num_txs, "wrote L1 block manifest");
+    Ok(())
+}
+
+/// Parses inscriptions and checks for batch data in the transactions
+pub fn check_for_da_batch(blockdata: &BlockData, cred_rule: &CredRule) -> Vec<BatchCheckpoint> {
     let protocol_ops_txs = blockdata.protocol_ops_txs();
 
     let signed_checkpts = protocol_ops_txs
@@ -141,15 +137,15 @@ fn check_for_da_batch(
         });
 
     let sig_verified_checkpoints = signed_checkpts.filter_map(|(signed_checkpoint, tx)| {
-        if let Some(seq_pubkey) = seq_pubkey {
-            if !signed_checkpoint.verify_sig(&seq_pubkey.into()) {
-                error!(
-                    ?tx,
-                    ?signed_checkpoint,
-                    "signature verification failed on checkpoint"
-                );
-                return None;
-            }
+        // The checkpoint is signed by the sequencer responsible for the last block in the batch.
+        let last_slot = signed_checkpoint.checkpoint().batch_info().l2_range.1;
+        if !cred_rule.verify_signer_at(last_slot, |pubkey| signed_checkpoint.verify_sig(pubkey)) {
+            error!(
+                ?tx,
+                ?signed_checkpoint,
+                "signature verification failed on checkpoint"
+            );
+            return None;
         }
         let checkpoint: BatchCheckpoint = signed_checkpoint.clone().into();
         Some(checkpoint)
@@ -1,7 +1,7 @@
 //! Defines the [`BridgeTxState`] type that tracks the state of signature collection for a
 //! particular [`Psbt`](bitcoin::Psbt).
 
-use std::{collections::BTreeMap, ops::Not};
+use std::{collections::BTreeMap, io, ops::Not};
 
 use arbitrary::Arbitrary;
 use bitcoin::{Transaction, TxOut, Txid};
@@ -19,7 +19,7 @@ use super::errors::{BridgeTxStateError, EntityResult};
 
 /// The state a transaction is in with respect to the number of signatures that have been collected
 /// from the bridge federation signatories.
-#[derive(Debug, Clone, PartialEq, Arbitrary, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, Arbitrary, BorshSerialize)]
 pub struct BridgeTxState {
     /// The partially signed bitcoin transaction that this state tracks.
     psbt: BitcoinPsbt,
@@ -31,12 +31,6 @@ pub struct BridgeTxState {
     /// This table maps the [`OperatorIdx`] to their corresponding pubkeys.
     pubkey_table: PublickeyTable,
 
-    /// The private nonce unique to the transaction being tracked by this state.
-    // FIXME: storing the secret nonce in the db is not a good practice but since it needs to be
-    // generated uniquely per transaction that is to be signed, we store it here.
-    // For more on nonce security, see [this](https://docs.rs/musig2/latest/musig2/#security).
-    secnonce: Musig2SecNonce,
-
     /// The (public) nonces shared for the particular [`Psbt`](bitcoin::Psbt)
     /// that this state tracks under MuSig2.
     collected_nonces: BTreeMap<OperatorIdx, Musig2PubNonce>,
@@ -46,14 +40,46 @@ pub struct BridgeTxState {
     collected_sigs: BTreeMap<OperatorIdx, Musig2PartialSig>,
 }
 
+/// The nonces and signatures collected for a [`BridgeTxState`].
+type Collected = (
+    BTreeMap<OperatorIdx, Musig2PubNonce>,
+    BTreeMap<OperatorIdx, Musig2PartialSig>,
+);
+
+impl BorshDeserialize for BridgeTxState {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let psbt = BitcoinPsbt::deserialize_reader(reader)?;
+        let spend_path = TaprootSpendPath::deserialize_reader(reader)?;
+        let pubkey_table = PublickeyTable::deserialize_reader(reader)?;
+
+        // Entries stored before the secret nonce moved into the signer hold it right after the
+        // pubkey table. The random bytes of a secret nonce practically never decode as the rest of
+        // the current layout, so the legacy layout is only tried if the current one fails. The
+        // secret nonce is dropped and the signer starts a new nonce round for such entries.
+        //
+        // NOTE: this consumes the whole reader, so the state can only be stored on its own.
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest)?;
+
+        let (collected_nonces, collected_sigs) = match borsh::from_slice::<Collected>(&rest) {
+            Ok(collected) => collected,
+            Err(_) => borsh::from_slice::<(Musig2SecNonce, Collected)>(&rest)?.1,
+        };
+
+        Ok(Self {
+            psbt,
+            spend_path,
+            pubkey_table,
+            collected_nonces,
+            collected_sigs,
+        })
+    }
+}
+
 impl BridgeTxState {
     /// Create a new [`BridgeTxState`] for the given [`Psbt`](bitcoin::Psbt)
     /// and list of [`bitcoin::secp256k1::PublicKey`].
-    pub fn new(
-        tx_signing_data: TxSigningData,
-        pubkey_table: PublickeyTable,
-        sec_nonce: Musig2SecNonce,
-    ) -> EntityResult<Self> {
+    pub fn new(tx_signing_data: TxSigningData, pubkey_table: PublickeyTable) -> EntityResult<Self> {
         let collected_sigs = BTreeMap::new();
 
         let collected_nonces: BTreeMap<OperatorIdx, Musig2PubNonce> = BTreeMap::new();
@@ -62,7 +88,6 @@ impl BridgeTxState {
             psbt: tx_signing_data.psbt,
             spend_path: tx_signing_data.spend_path,
             pubkey_table,
-            secnonce: sec_nonce,
             collected_nonces,
             collected_sigs,
         })
@@ -100,11 +125,6 @@ impl BridgeTxState {
         self.pubkey_table.0.keys().len()
     }
 
-    /// Get the private nonce for the transaction being tracked in this state.
-    pub fn secnonce(&self) -> &Musig2SecNonce {
-        &self.secnonce
-    }
-
     /// Get the [`PublickeyTable`] that maps [`OperatorIdx`] to the corresponding `PublicKey`
     /// correspondng to the multisig.
     pub fn pubkeys(&self) -> &PublickeyTable {
@@ -218,8 +238,7 @@ mod tests {
 
     use arbitrary::Unstructured;
     use strata_test_utils::bridge::{
-        generate_keypairs, generate_mock_tx_signing_data, generate_pubkey_table,
-        generate_sec_nonce, permute,
+        generate_keypairs, generate_mock_tx_signing_data, generate_pubkey_table, permute,
     };
 
     use super::*;
@@ -227,9 +246,8 @@ mod tests {
 
     #[test]
     fn test_has_all_nonces() {
-        let own_index = 0;
         let num_operators = 2;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         assert!(
             !tx_state.has_all_nonces(),
@@ -257,9 +275,8 @@ mod tests {
 
     #[test]
     fn test_add_nonce() {
-        let own_index = 0;
         let num_operators = 2;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         let data = vec![0u8; 1024];
         let mut unstructured = Unstructured::new(&data[..]);
@@ -290,9 +307,8 @@ mod tests {
 
     #[test]
     fn test_ordered_nonces() {
-        let own_index = 0;
         let num_operators = 10;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         let data = vec![0u8; 1024];
         let mut unstructured = Unstructured::new(&data[..]);
@@ -340,9 +356,8 @@ mod tests {
 
     #[test]
     fn test_is_fully_signed_all_signatures_present() {
-        let own_index = 0;
         let num_operators = 2;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         for i in 0..num_operators {
             let data = vec![0u8; 32];
@@ -365,7 +380,7 @@ mod tests {
     fn test_is_fully_signed_missing_signature() {
         let own_index = 0;
         let num_operators = 1;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         let data = vec![0u8; 32];
         let mut unstructured = Unstructured::new(&data);
@@ -393,7 +408,7 @@ mod tests {
     fn test_add_signature_success() {
         let own_index = 0;
         let num_operators = 1;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         let data = vec![0u8; 32];
         let mut unstructured = Unstructured::new(&data);
@@ -412,9 +427,8 @@ mod tests {
 
     #[test]
     fn test_add_signature_invalid_pubkey() {
-        let own_index = 0;
         let num_operators = 1;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         let data = vec![0u8; 32];
         let mut unstructured = Unstructured::new(&data);
@@ -436,9 +450,8 @@ mod tests {
 
     #[test]
     fn test_ordered_sigs() {
-        let own_index = 0;
         let num_operators = 1;
-        let mut tx_state = create_mock_tx_state(own_index, num_operators);
+        let mut tx_state = create_mock_tx_state(num_operators);
 
         let mut operator_ids = (0..num_operators).collect::<Vec<usize>>();
         permute(&mut operator_ids);
@@ -468,20 +481,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_borsh_roundtrip() {
+        let data = vec![1u8; 1024];
+        let mut unstructured = Unstructured::new(&data[..]);
+
+        let mut tx_state = create_mock_tx_state(2);
+        let nonce = Musig2PubNonce::arbitrary(&mut unstructured).unwrap();
+        tx_state.add_nonce(&0, nonce).unwrap();
+        let sig = Musig2PartialSig::arbitrary(&mut unstructured).unwrap();
+        tx_state
+            .add_signature(OperatorPartialSig::new(sig, 1))
+            .unwrap();
+
+        let bytes = borsh::to_vec(&tx_state).unwrap();
+        let decoded: BridgeTxState = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(
+            decoded, tx_state,
+            "decoded state should match the encoded one"
+        );
+    }
+
+    #[test]
+    fn test_borsh_decode_legacy() {
+        let data = vec![2u8; 1024];
+        let mut unstructured = Unstructured::new(&data[..]);
+
+        let mut tx_state = create_mock_tx_state(2);
+        let nonce = Musig2PubNonce::arbitrary(&mut unstructured).unwrap();
+        tx_state.add_nonce(&1, nonce).unwrap();
+        let sig = Musig2PartialSig::arbitrary(&mut unstructured).unwrap();
+        tx_state
+            .add_signature(OperatorPartialSig::new(sig, 0))
+            .unwrap();
+
+        let secnonce = Musig2SecNonce::arbitrary(&mut unstructured).unwrap();
+        let legacy_bytes = borsh::to_vec(&(
+            &tx_state.psbt,
+            &tx_state.spend_path,
+            &tx_state.pubkey_table,
+            &secnonce,
+            &tx_state.collected_nonces,
+            &tx_state.collected_sigs,
+        ))
+        .unwrap();
+
+        let decoded: BridgeTxState = borsh::from_slice(&legacy_bytes).unwrap();
+        assert_eq!(
+            decoded, tx_state,
+            "legacy state should decode without the secnonce"
+        );
+    }
+
     /// Creates a mock [`BridgeTxState`] for the given params. We do this manually here instead of
     /// leveraging [`arbitrary::Arbitrary`] since we want more fine-grained control over the created
     /// structure.
-    fn create_mock_tx_state(own_index: usize, num_operators: usize) -> BridgeTxState {
-        let (pks, sks) = generate_keypairs(num_operators);
+    fn create_mock_tx_state(num_operators: usize) -> BridgeTxState {
+        let (pks, _) = generate_keypairs(num_operators);
 
         let tx_output = generate_mock_tx_signing_data(false);
 
         let pubkey_table = generate_pubkey_table(&pks);
 
-        let sec_nonce =
-            generate_sec_nonce(&tx_output.psbt.compute_txid(), pks, sks[own_index], false);
-
-        BridgeTxState::new(tx_output, pubkey_table, sec_nonce.into())
-            .expect("Failed to create TxState")
+        BridgeTxState::new(tx_output, pubkey_table).expect("Failed to create TxState")
     }
 }
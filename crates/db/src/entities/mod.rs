@@ -8,3 +8,4 @@
 
 pub mod bridge_tx_state;
 pub mod errors;
+pub mod signer_session;
@@ -0,0 +1,44 @@
+//! Defines the [`SignerSession`] type that persists an open MuSig2 signing session of a bridge
+//! signer.
+
+use arbitrary::Arbitrary;
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_primitives::bridge::{Musig2PubNonce, Musig2SecNonce};
+
+/// An open MuSig2 signing session, kept so that a signer can still sign the transactions whose
+/// nonces it has already shared after it restarts.
+#[derive(Debug, Clone, PartialEq, Arbitrary, BorshSerialize, BorshDeserialize)]
+pub struct SignerSession {
+    /// The secret nonce of the session.
+    ///
+    /// This is as sensitive as the key itself, since signing twice with it leaks the key.
+    secnonce: Musig2SecNonce,
+
+    /// The time at which the session was opened, in seconds since the UNIX epoch.
+    opened_at: u64,
+}
+
+impl SignerSession {
+    /// Creates a new [`SignerSession`].
+    pub fn new(secnonce: Musig2SecNonce, opened_at: u64) -> Self {
+        Self {
+            secnonce,
+            opened_at,
+        }
+    }
+
+    /// Get the secret nonce of the session.
+    pub fn secnonce(&self) -> &Musig2SecNonce {
+        &self.secnonce
+    }
+
+    /// Get the public nonce of the session.
+    pub fn pubnonce(&self) -> Musig2PubNonce {
+        self.secnonce.inner().public_nonce().into()
+    }
+
+    /// Get the time at which the session was opened, in seconds since the UNIX epoch.
+    pub fn opened_at(&self) -> u64 {
+        self.opened_at
+    }
+}
@@ -2,7 +2,11 @@ use std::{collections::HashMap, sync::RwLock};
 
 use strata_primitives::buf::Buf32;
 
-use crate::{entities::bridge_tx_state::BridgeTxState, traits::BridgeTxDatabase, DbResult};
+use crate::{
+    entities::{bridge_tx_state::BridgeTxState, signer_session::SignerSession},
+    traits::{BridgeTxDatabase, SignerSessionDatabase},
+    DbResult,
+};
 
 #[derive(Debug, Default)]
 pub struct StubTxStateDb(RwLock<HashMap<Buf32, BridgeTxState>>);
@@ -28,3 +32,27 @@ impl BridgeTxDatabase for StubTxStateDb {
         Ok(tx_state)
     }
 }
+
+#[derive(Debug, Default)]
+pub struct StubSignerSessionDb(RwLock<HashMap<Buf32, SignerSession>>);
+
+impl SignerSessionDatabase for StubSignerSessionDb {
+    fn put_session(&self, txid: Buf32, session: SignerSession) -> DbResult<()> {
+        let mut db = self.0.write().unwrap();
+        db.insert(txid, session);
+
+        Ok(())
+    }
+
+    fn delete_session(&self, txid: Buf32) -> DbResult<Option<SignerSession>> {
+        let mut db = self.0.write().unwrap();
+
+        Ok(db.remove(&txid))
+    }
+
+    fn get_all_sessions(&self) -> DbResult<Vec<(Buf32, SignerSession)>> {
+        let db = self.0.read().unwrap();
+
+        Ok(db.iter().map(|(k, v)| (*k, v.clone())).collect())
+    }
+}
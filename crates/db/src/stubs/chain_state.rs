@@ -52,6 +52,15 @@ impl ChainstateDatabase for StubChainstateDb {
         Ok(())
     }
 
+    fn write_bootstrap_state(&self, idx: u64, toplevel: &Chainstate) -> DbResult<()> {
+        let mut st = self.state.lock();
+        if !st.toplevels.is_empty() {
+            return Err(DbError::OverwriteStateUpdate(idx));
+        }
+        st.toplevels.insert(idx, toplevel.clone());
+        Ok(())
+    }
+
     fn write_state_update(&self, idx: u64, batch: &WriteBatch) -> DbResult<()> {
         let mut st = self.state.lock();
 
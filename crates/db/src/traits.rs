@@ -11,15 +11,21 @@ use strata_primitives::{
     proof::{ProofContext, ProofKey},
 };
 use strata_state::{
-    block::L2BlockBundle, bridge_duties::BridgeDutyStatus, chain_state::Chainstate,
-    client_state::ClientState, l1::L1Tx, operation::*, prelude::*, state_op::WriteBatch,
+    block::L2BlockBundle,
+    bridge_duties::{BridgeDutyStatus, ChallengeReport},
+    chain_state::Chainstate,
+    client_state::ClientState,
+    l1::L1Tx,
+    operation::*,
+    prelude::*,
+    state_op::WriteBatch,
     sync_event::SyncEvent,
 };
 use strata_zkvm::ProofReceipt;
 
 use crate::{
-    entities::bridge_tx_state::BridgeTxState,
-    types::{BlobEntry, CheckpointEntry, L1TxEntry},
+    entities::{bridge_tx_state::BridgeTxState, signer_session::SignerSession},
+    types::{BlobEntry, CheckpointEntry, L1TxEntry, ProvingTaskEntry},
     DbResult,
 };
 
@@ -58,6 +64,12 @@ pub trait L1Database {
     /// index will be the new chain tip that we store.
     fn revert_to_height(&self, idx: u64) -> DbResult<()>;
 
+    /// Deletes the relevant txs stored for the blocks in a range, defined as a
+    /// half-open interval.  The block manifests are kept so the chain can still
+    /// be traversed.  This should only be used for blocks deeply buried under
+    /// a finalized checkpoint.
+    fn purge_block_txs(&self, start_idx: u64, end_idx: u64) -> DbResult<()>;
+
     // TODO DA scraping storage
 
     /// Gets the current chain tip index.
@@ -71,6 +83,10 @@ pub trait L1Database {
     /// present.  Otherwise, returns error.
     fn get_blockid_range(&self, start_idx: u64, end_idx: u64) -> DbResult<Vec<Buf32>>;
 
+    /// Gets the index of the earliest block whose relevant txs haven't been
+    /// purged, or `None` if no blocks are stored.
+    fn get_earliest_txs_idx(&self) -> DbResult<Option<u64>>;
+
     /// Gets the relevant txs we stored in a block.
     fn get_block_txs(&self, idx: u64) -> DbResult<Option<Vec<L1TxRef>>>;
 
@@ -107,9 +123,16 @@ pub trait SyncEventDatabase {
     /// never need to look at them again.
     fn clear_sync_event(&self, start_idx: u64, end_idx: u64) -> DbResult<()>;
 
+    /// Deletes the sync events after the specified index, which becomes the
+    /// last one stored.
+    fn revert_to_idx(&self, idx: u64) -> DbResult<()>;
+
     /// Returns the index of the most recently written sync event.
     fn get_last_idx(&self) -> DbResult<Option<u64>>;
 
+    /// Returns the index of the earliest sync event that hasn't been cleared.
+    fn get_first_idx(&self) -> DbResult<Option<u64>>;
+
     /// Gets the sync event with some index, if it exists.
     fn get_sync_event(&self, idx: u64) -> DbResult<Option<SyncEvent>>;
 
@@ -130,6 +153,10 @@ pub trait ClientStateDatabase {
     /// error if trying to overwrite a state.
     fn write_client_state_checkpoint(&self, idx: u64, state: ClientState) -> DbResult<()>;
 
+    /// Deletes the consensus outputs and client state checkpoints after the
+    /// specified index, so that the index becomes the last one written.
+    fn revert_to_idx(&self, idx: u64) -> DbResult<()>;
+
     /// Gets the idx of the last written state.  Or returns error if a bootstrap
     /// state has not been written yet.
     fn get_last_write_idx(&self) -> DbResult<u64>;
@@ -204,6 +231,11 @@ pub trait ChainstateDatabase {
     /// Writes the genesis chainstate at index 0.
     fn write_genesis_state(&self, toplevel: &Chainstate) -> DbResult<()>;
 
+    /// Writes a chainstate snapshot at index `idx` to start from instead of
+    /// genesis.  Like the genesis state, this can only be written to an empty
+    /// database.
+    fn write_bootstrap_state(&self, idx: u64, toplevel: &Chainstate) -> DbResult<()>;
+
     /// Stores a write batch in the database, possibly computing that state
     /// under the hood from the writes.  Will not overwrite existing data,
     /// previous writes must be purged first in order to be replaced.
@@ -267,6 +299,12 @@ pub trait BlobDatabase {
 
     /// Get the last blob index
     fn get_last_blob_idx(&self) -> DbResult<Option<u64>>;
+
+    /// Store the fees spent on inscriptions during the given checkpoint epoch.
+    fn put_epoch_fees(&self, epoch: u64, fees: u64) -> DbResult<()>;
+
+    /// Get the fees spent on inscriptions during the given checkpoint epoch.
+    fn get_epoch_fees(&self, epoch: u64) -> DbResult<Option<u64>>;
 }
 
 pub trait ProofDatabase {
@@ -301,6 +339,25 @@ pub trait ProofDatabase {
     /// Tries to delete dependencies of by its context, returning if it really
     /// existed or not.
     fn del_proof_deps(&self, proof_context: ProofContext) -> DbResult<bool>;
+
+    /// Inserts or updates the proving task with the given [`ProofKey`].
+    ///
+    /// Returns `Ok(())` on success, or an error on failure.
+    fn put_task(&self, proof_key: ProofKey, task: ProvingTaskEntry) -> DbResult<()>;
+
+    /// Retrieves a proving task by its key.
+    ///
+    /// Returns `Some(task)` if found, or `None` if not.
+    fn get_task(&self, proof_key: ProofKey) -> DbResult<Option<ProvingTaskEntry>>;
+
+    /// Deletes a proving task by its key.
+    ///
+    /// Tries to delete a proving task by its key, returning if it really
+    /// existed or not.
+    fn del_task(&self, proof_key: ProofKey) -> DbResult<bool>;
+
+    /// Retrieves all the proving tasks in the database.
+    fn get_all_tasks(&self) -> DbResult<Vec<(ProofKey, ProvingTaskEntry)>>;
 }
 
 // TODO remove this trait, just like the high level `Database` trait
@@ -357,6 +414,22 @@ pub trait BridgeTxDatabase {
     fn get_tx_state(&self, txid: Buf32) -> DbResult<Option<BridgeTxState>>;
 }
 
+/// Provides methods to persist the open MuSig2 signing sessions of a bridge signer so that they
+/// survive a restart.
+///
+/// Each session is identified by the [`Txid`](bitcoin::Txid) (represented as a [`Buf32`]) of the
+/// transaction that it signs.
+pub trait SignerSessionDatabase {
+    /// Adds a [`SignerSession`], replacing the existing one if present.
+    fn put_session(&self, txid: Buf32, session: SignerSession) -> DbResult<()>;
+
+    /// Deletes the [`SignerSession`] and returns it if it exists.
+    fn delete_session(&self, txid: Buf32) -> DbResult<Option<SignerSession>>;
+
+    /// Get all the stored [`SignerSession`]s.
+    fn get_all_sessions(&self) -> DbResult<Vec<(Buf32, SignerSession)>>;
+}
+
 /// Provides methods to manage the status of a deposit or withdrawal duty that a bridge client
 /// executes.
 ///
@@ -376,6 +449,17 @@ pub trait BridgeDutyDatabase {
     ///
     /// If a duty for the given `txid` is not present
     fn put_duty_status(&self, txid: Buf32, status: BridgeDutyStatus) -> DbResult<()>;
+
+    /// Stores the raw signed transaction that fulfills the withdrawal duty identified by the given
+    /// `txid`, replacing the existing one if present.
+    ///
+    /// This is stored before the fulfillment is broadcasted so that a withdrawal is never paid out
+    /// twice.
+    fn put_fulfillment(&self, txid: Buf32, fulfillment_raw: Vec<u8>) -> DbResult<()>;
+
+    /// Get the raw transaction that fulfills the withdrawal duty identified by the given `txid` if
+    /// it exists.
+    fn get_fulfillment(&self, txid: Buf32) -> DbResult<Option<Vec<u8>>>;
 }
 
 /// Provides methods to manage the duty index for the deposit duties.
@@ -390,3 +474,36 @@ pub trait BridgeDutyIndexDatabase {
     /// This is done in response to the response received from the full node's RPC.
     fn set_index(&self, index: u64) -> DbResult<()>;
 }
+
+/// Provides methods to persist the [`ChallengeReport`]s produced by a bridge client running in
+/// challenger mode.
+///
+/// Each report is identified by its [`ChallengeReport::id`], so that every fault observed for a
+/// deposit UTXO is kept as a separate report. Reports are also assigned a monotonically
+/// increasing index in the order in which they were first inserted so that they can be paged
+/// through.
+pub trait ChallengeReportDatabase {
+    /// Adds a report with the given `id`, replacing the existing one if present.
+    ///
+    /// Returns `Some(idx)` if the report is newly inserted, otherwise `None`.
+    fn put_report(&self, id: Buf32, report: ChallengeReport) -> DbResult<Option<u64>>;
+
+    /// Get the report with the given `id` if it exists.
+    fn get_report(&self, id: Buf32) -> DbResult<Option<ChallengeReport>>;
+
+    /// Get the report at a given index if it exists.
+    fn get_report_by_idx(&self, idx: u64) -> DbResult<Option<ChallengeReport>>;
+
+    /// Get the index that the next new report will be inserted at.
+    fn get_next_report_idx(&self) -> DbResult<u64>;
+
+    /// Records the hash of the bitcoin block at the given height once it has been scanned for
+    /// faults, replacing the one scanned before at that height if any.
+    fn put_scanned_block(&self, height: u64, block_hash: Buf32) -> DbResult<()>;
+
+    /// Get the hash of the bitcoin block scanned at the given height if it exists.
+    fn get_scanned_block(&self, height: u64) -> DbResult<Option<Buf32>>;
+
+    /// Get the height of the highest bitcoin block scanned so far, if any.
+    fn get_last_scanned_height(&self) -> DbResult<Option<u64>>;
+}
@@ -1,5 +1,7 @@
 //! Module for database local types
 
+use std::io;
+
 use arbitrary::Arbitrary;
 use bitcoin::{
     consensus::{self, deserialize, serialize},
@@ -7,7 +9,7 @@ use bitcoin::{
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use strata_primitives::buf::Buf32;
+use strata_primitives::{buf::Buf32, proof::ProofKey};
 use strata_state::batch::{BatchCheckpoint, BatchInfo, BootstrapState};
 use strata_zkvm::ProofReceipt;
 
@@ -16,7 +18,9 @@ use strata_zkvm::ProofReceipt;
 #[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Arbitrary)]
 pub struct BlobEntry {
     pub blob: Vec<u8>,
+    /// Txid of the commit tx. Blobs that are inscribed in the same batch share it.
     pub commit_txid: Buf32,
+    /// Txid of the reveal tx. Blobs that are inscribed in the same batch share it.
     pub reveal_txid: Buf32,
     pub status: BlobL1Status,
 }
@@ -73,26 +77,80 @@ pub enum BlobL1Status {
 
 /// This is the entry that gets saved to the database corresponding to a bitcoin transaction that
 /// the broadcaster will publish and watches for until finalization
-#[derive(
-    Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Arbitrary, Serialize, Deserialize,
-)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, Arbitrary, Serialize, Deserialize)]
 pub struct L1TxEntry {
     /// Raw serialized transaction. This is basically `consensus::serialize()` of [`Transaction`]
     tx_raw: Vec<u8>,
 
     /// The status of the transaction in bitcoin
     pub status: L1TxStatus,
+
+    /// How the broadcaster bumps the fee of the transaction if it gets stuck in the mempool
+    pub fee_bump_method: FeeBumpMethod,
+
+    /// L1 height at which the transaction was last published or had its fee bumped, used to
+    /// track how long it has been waiting in the mempool
+    pub published_height: Option<u64>,
+
+    /// Fee bumps done so far, oldest first
+    fee_bumps: Vec<FeeBump>,
+
+    /// Raw serialized child transaction of the latest CPFP bump, which has to be republished
+    /// along with the transaction
+    cpfp_child_raw: Option<Vec<u8>>,
+}
+
+impl BorshDeserialize for L1TxEntry {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let tx_raw = Vec::<u8>::deserialize_reader(reader)?;
+        let status = L1TxStatus::deserialize_reader(reader)?;
+
+        // Entries stored before fee bumping was added end here, their fees are never bumped.
+        let mut method_tag = [0u8; 1];
+        if reader.read(&mut method_tag)? == 0 {
+            return Ok(Self {
+                tx_raw,
+                status,
+                fee_bump_method: FeeBumpMethod::Disabled,
+                published_height: None,
+                fee_bumps: Vec::new(),
+                cpfp_child_raw: None,
+            });
+        }
+
+        let fee_bump_method = FeeBumpMethod::deserialize_reader(&mut method_tag.as_slice())?;
+        Ok(Self {
+            tx_raw,
+            status,
+            fee_bump_method,
+            published_height: Option::<u64>::deserialize_reader(reader)?,
+            fee_bumps: Vec::<FeeBump>::deserialize_reader(reader)?,
+            cpfp_child_raw: Option::<Vec<u8>>::deserialize_reader(reader)?,
+        })
+    }
 }
 
 impl L1TxEntry {
     /// Create a new [`L1TxEntry`] from a [`Transaction`].
+    ///
+    /// The fee of the transaction gets bumped with CPFP if it pays to the broadcaster's wallet.
     pub fn from_tx(tx: &Transaction) -> Self {
         Self {
             tx_raw: serialize(tx),
             status: L1TxStatus::Unpublished,
+            fee_bump_method: FeeBumpMethod::Cpfp,
+            published_height: None,
+            fee_bumps: Vec::new(),
+            cpfp_child_raw: None,
         }
     }
 
+    /// Sets how the fee of the transaction gets bumped.
+    pub fn with_fee_bump_method(mut self, method: FeeBumpMethod) -> Self {
+        self.fee_bump_method = method;
+        self
+    }
+
     /// Returns the raw serialized transaction.
     ///
     /// # Note
@@ -115,6 +173,83 @@ impl L1TxEntry {
     pub fn is_finalized(&self) -> bool {
         matches!(self.status, L1TxStatus::Finalized { .. })
     }
+
+    /// Returns the fee bumps done so far, oldest first.
+    pub fn fee_bumps(&self) -> &[FeeBump] {
+        &self.fee_bumps
+    }
+
+    /// Returns the txid of the latest replacement of the transaction, if it has been replaced.
+    ///
+    /// This is the txid of the transaction in the entry, which has to be watched instead of the
+    /// one the entry was created with.
+    pub fn replacement_txid(&self) -> Option<Buf32> {
+        match self.fee_bump_method {
+            FeeBumpMethod::Rbf => self.fee_bumps.last().map(|bump| bump.txid),
+            _ => None,
+        }
+    }
+
+    /// Replaces the transaction with a version paying a higher fee.
+    pub fn replace_tx(&mut self, tx: &Transaction, fee_rate: u64) {
+        self.tx_raw = serialize(tx);
+        self.fee_bumps.push(FeeBump {
+            txid: tx.compute_txid().into(),
+            fee_rate,
+        });
+    }
+
+    /// Records a child transaction that was published to pay for the transaction's fee, replacing
+    /// the previous one.
+    pub fn add_cpfp_child(&mut self, child: &Transaction, fee_rate: u64) {
+        self.cpfp_child_raw = Some(serialize(child));
+        self.fee_bumps.push(FeeBump {
+            txid: child.compute_txid().into(),
+            fee_rate,
+        });
+    }
+
+    /// Deserializes the child transaction of the latest CPFP bump, if any.
+    pub fn try_to_cpfp_child(&self) -> Option<Result<Transaction, consensus::encode::Error>> {
+        self.cpfp_child_raw.as_deref().map(deserialize)
+    }
+}
+
+/// The ways the fee of a transaction stuck in the mempool can be bumped
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Arbitrary,
+    Serialize,
+    Deserialize,
+)]
+pub enum FeeBumpMethod {
+    /// The fee is never bumped
+    Disabled,
+
+    /// The transaction is re-signed by the wallet with less change, replacing it (BIP125). If
+    /// the transaction doesn't signal replaceability, it gets switched to [`Self::Cpfp`].
+    Rbf,
+
+    /// A wallet transaction spending an output of the transaction pays for both (child pays for
+    /// parent)
+    Cpfp,
+}
+
+/// A fee bump of a transaction
+#[derive(
+    Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Arbitrary, Serialize, Deserialize,
+)]
+pub struct FeeBump {
+    /// Txid of the replacement for RBF, or of the child for CPFP
+    pub txid: Buf32,
+
+    /// Fee rate targeted by the bump, in sat/vB
+    pub fee_rate: u64,
 }
 
 /// The possible statuses of a publishable transaction
@@ -227,6 +362,77 @@ pub enum CheckpointConfStatus {
     Finalized,
 }
 
+/// Represents the status of a proving task.
+///
+/// ## State Transitions
+///
+/// - `WaitingForDependencies` -> `Pending`: When all dependencies are resolved.
+/// - `Pending` -> `ProvingInProgress`: When the proving task starts.
+/// - `ProvingInProgress` -> `Completed`: When the proving task completes successfully.
+/// - Any state -> `Failed`: If the task fails at any point.
+/// - `Failed` -> `Pending`: When the failed task is retried.
+/// - Any unfinished state -> `Cancelled`: When the task is cancelled.
+/// - `Cancelled` -> `Pending`: When the cancelled task is retried.
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Arbitrary, Serialize, Deserialize,
+)]
+pub enum ProvingTaskStatus {
+    /// Waiting for dependencies to be resolved.
+    WaitingForDependencies,
+    /// Ready to be started
+    Pending,
+    /// Task is currently being executed.
+    ProvingInProgress,
+    /// Task has been completed successfully.
+    Completed,
+    /// Task has failed.
+    Failed,
+    /// Task has been cancelled and will not be retried automatically.
+    Cancelled,
+}
+
+/// Entry corresponding to a proving task tracked by the prover client.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct ProvingTaskEntry {
+    /// Current status of the task.
+    pub status: ProvingTaskStatus,
+
+    /// Dependencies of the task that have not been proven yet.
+    pub pending_deps: Vec<ProofKey>,
+
+    /// Number of times the proving of this task has been started.
+    pub attempts: u32,
+
+    /// Time at which the task was created, in milliseconds since the UNIX epoch.
+    pub created_at: u64,
+
+    /// Time at which the task was last updated, in milliseconds since the UNIX epoch.
+    pub updated_at: u64,
+
+    /// Reason for the last failure of the task, if any.
+    pub failure_reason: Option<String>,
+
+    /// Time at which a failed task is to be retried, in milliseconds since the UNIX epoch.
+    ///
+    /// `None` if the task is not scheduled to be retried.
+    pub retry_at: Option<u64>,
+}
+
+impl ProvingTaskEntry {
+    /// Creates a new task entry with no attempts made so far.
+    pub fn new(status: ProvingTaskStatus, pending_deps: Vec<ProofKey>, created_at: u64) -> Self {
+        Self {
+            status,
+            pending_deps,
+            attempts: 0,
+            created_at,
+            updated_at: created_at,
+            failure_reason: None,
+            retry_at: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json;
@@ -258,4 +464,27 @@ mod tests {
             assert_eq!(actual, l1_tx_status);
         }
     }
+
+    #[test]
+    fn check_borsh_of_legacy_l1txentry() {
+        let tx_raw = vec![1, 2, 3];
+        let status = L1TxStatus::Confirmed { confirmations: 3 };
+        let legacy = borsh::to_vec(&(tx_raw.clone(), status.clone())).unwrap();
+
+        let entry = L1TxEntry::try_from_slice(&legacy).unwrap();
+        assert_eq!(entry.tx_raw(), tx_raw.as_slice());
+        assert_eq!(entry.status, status);
+        assert_eq!(entry.fee_bump_method, FeeBumpMethod::Disabled);
+        assert!(entry.fee_bumps().is_empty());
+
+        let mut entry = entry.with_fee_bump_method(FeeBumpMethod::Cpfp);
+        entry.published_height = Some(10);
+        entry.fee_bumps.push(FeeBump {
+            txid: Buf32::zero(),
+            fee_rate: 10,
+        });
+        entry.cpfp_child_raw = Some(vec![4, 5]);
+        let decoded = L1TxEntry::try_from_slice(&borsh::to_vec(&entry).unwrap()).unwrap();
+        assert_eq!(decoded, entry);
+    }
 }
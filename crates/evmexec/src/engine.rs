@@ -2,12 +2,14 @@ use std::sync::Arc;
 
 use alloy_rpc_types::{
     engine::{
-        ExecutionPayloadInputV2, ForkchoiceState, PayloadAttributes, PayloadId, PayloadStatusEnum,
+        ExecutionPayloadInputV2, ExecutionPayloadV2, ForkchoiceState, PayloadAttributes, PayloadId,
+        PayloadStatusEnum,
     },
     Withdrawal,
 };
 use futures::future::TryFutureExt;
-use reth_primitives::revm_primitives::{Address, B256};
+use reth_primitives::revm_primitives::{Address, Bytes, B256};
+use reth_rpc_types_compat::engine::try_payload_v2_to_block;
 use strata_eectl::{
     engine::{BlockStatus, ExecEngineCtl, PayloadStatus},
     errors::{EngineError, EngineResult},
@@ -19,12 +21,13 @@ use strata_primitives::{
 };
 use strata_reth_evm::constants::COINBASE_ADDRESS;
 use strata_reth_node::{
-    ExecutionPayloadFieldV2, StrataExecutionPayloadEnvelopeV2, StrataPayloadAttributes,
+    ExecutionPayloadFieldV2, ForcedInclusion, StrataExecutionPayloadEnvelopeV2,
+    StrataPayloadAttributes,
 };
 use strata_state::{
     block::L2BlockBundle,
     bridge_ops,
-    exec_update::{ELDepositData, ExecUpdate, Op, UpdateOutput},
+    exec_update::{ELDepositData, ELForcedInclusionData, ExecUpdate, Op, UpdateOutput},
     id::L2BlockId,
 };
 use strata_storage::L2BlockManager;
@@ -51,6 +54,61 @@ const fn gwei_to_sats(gwei: u64) -> u64 {
     gwei / 10
 }
 
+/// Converts the deposits among the ops to the withdrawals the EL credits them with.
+fn withdrawals_from_ops(ops: &[Op]) -> Vec<Withdrawal> {
+    ops.iter()
+        .filter_map(|op| match op {
+            Op::Deposit(deposit_data) => Some(Withdrawal {
+                index: deposit_data.intent_idx(),
+                address: address_from_slice(deposit_data.dest_addr())?,
+                amount: sats_to_gwei(deposit_data.amt())?,
+                validator_index: 0,
+            }),
+            // forced inclusions are part of the payload's transactions
+            Op::ForcedInclusion(_) => None,
+        })
+        .collect()
+}
+
+/// Collects the raw forced txs among the ops.
+fn forced_txs_from_ops(ops: &[Op]) -> Vec<Bytes> {
+    ops.iter()
+        .filter_map(|op| match op {
+            Op::ForcedInclusion(forced_incl) => Some(Bytes::copy_from_slice(forced_incl.payload())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Checks that the EL payload in the accessory of a block is the one its exec segment commits to.
+///
+/// The block header only commits to the exec segment, so without this a block could be passed
+/// around with a valid header and a different payload.
+pub fn validate_block_accessory(bundle: &L2BlockBundle) -> bool {
+    let Ok(el_payload) = borsh::from_slice::<ElPayload>(bundle.accessory().exec_payload()) else {
+        return false;
+    };
+
+    let update = bundle.exec_segment().update();
+    if el_payload.state_root != *update.output().new_state() {
+        return false;
+    }
+
+    // The exec segment commits to the block hash, which covers the rest of the payload.
+    let ops = update.input().applied_ops();
+    let block_hash = B256::from(el_payload.block_hash.0);
+    let v2_payload = ExecutionPayloadV2 {
+        payload_inner: el_payload.clone().into(),
+        withdrawals: withdrawals_from_ops(ops),
+    };
+    let hash_matches = try_payload_v2_to_block(v2_payload)
+        .is_ok_and(|block| block.header.hash_slow() == block_hash);
+
+    hash_matches
+        && make_update_input_from_payload_and_ops(el_payload, ops)
+            .is_ok_and(|update_input| &update_input == update.input())
+}
+
 struct RpcExecEngineInner<T: EngineRpc> {
     pub client: T,
     pub fork_choice_state: Mutex<ForkchoiceState>,
@@ -111,8 +169,12 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
         let withdrawals = payload_env
             .el_ops()
             .iter()
-            .map(|op| match op {
-                Op::Deposit(deposit_data) => Ok(Withdrawal {
+            .filter_map(|op| match op {
+                Op::Deposit(deposit_data) => Some(deposit_data),
+                _ => None,
+            })
+            .map(|deposit_data| {
+                Ok(Withdrawal {
                     index: deposit_data.intent_idx(),
                     address: address_from_slice(deposit_data.dest_addr()).ok_or_else(|| {
                         EngineError::InvalidAddress(deposit_data.dest_addr().to_vec())
@@ -120,10 +182,22 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
                     amount: sats_to_gwei(deposit_data.amt())
                         .ok_or(EngineError::AmountConversion(deposit_data.amt()))?,
                     ..Default::default()
-                }),
+                })
             })
             .collect::<Result<_, _>>()?;
 
+        let forced_inclusions = payload_env
+            .el_ops()
+            .iter()
+            .filter_map(|op| match op {
+                Op::ForcedInclusion(forced_incl) => Some(ForcedInclusion {
+                    index: forced_incl.incl_idx(),
+                    tx: Bytes::copy_from_slice(forced_incl.payload()),
+                }),
+                _ => None,
+            })
+            .collect();
+
         let payload_attributes = StrataPayloadAttributes::new_from_eth(PayloadAttributes {
             // evm expects timestamp in seconds
             timestamp: payload_env.timestamp() / 1000,
@@ -131,7 +205,8 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
             withdrawals: Some(withdrawals),
             parent_beacon_block_root: None,
             suggested_fee_recipient: COINBASE_ADDRESS,
-        });
+        })
+        .with_forced_inclusions(forced_inclusions);
 
         let mut fcs = *self.fork_choice_state.lock().await;
         fcs.head_block_hash = prev_block.block_hash();
@@ -164,9 +239,10 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
         let StrataExecutionPayloadEnvelopeV2 {
             inner: execution_payload_v2,
             withdrawal_intents: rpc_withdrawal_intents,
+            forced_inclusions: rpc_forced_inclusions,
         } = payload;
 
-        let (el_payload, ops) = match execution_payload_v2.execution_payload {
+        let (el_payload, mut ops) = match execution_payload_v2.execution_payload {
             ExecutionPayloadFieldV2::V1(payload) => {
                 let el_payload: ElPayload = payload.into();
 
@@ -191,6 +267,13 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
             }
         };
 
+        ops.extend(rpc_forced_inclusions.into_iter().map(|forced_incl| {
+            Op::ForcedInclusion(ELForcedInclusionData::new(
+                forced_incl.index,
+                forced_incl.tx.to_vec(),
+            ))
+        }));
+
         let el_state_root = el_payload.state_root;
         let accessory_data = borsh::to_vec(&el_payload).unwrap();
         let update_input = make_update_input_from_payload_and_ops(el_payload, &ops)
@@ -217,19 +300,10 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
         let el_payload = borsh::from_slice::<ElPayload>(payload.accessory_data())
             .map_err(|_| EngineError::Other("Invalid payload".to_string()))?;
 
+        let block_hash = el_payload.block_hash.0.into();
+
         // actually bridge-in deposits
-        let withdrawals: Vec<Withdrawal> = payload
-            .ops()
-            .iter()
-            .filter_map(|op| match op {
-                Op::Deposit(deposit_data) => Some(Withdrawal {
-                    index: deposit_data.intent_idx(),
-                    address: address_from_slice(deposit_data.dest_addr())?,
-                    amount: sats_to_gwei(deposit_data.amt())?,
-                    validator_index: 0,
-                }),
-            })
-            .collect();
+        let withdrawals = withdrawals_from_ops(payload.ops());
 
         let v2_payload = ExecutionPayloadInputV2 {
             execution_payload: el_payload.into(),
@@ -241,6 +315,21 @@ impl<T: EngineRpc> RpcExecEngineInner<T> {
         let payload_status =
             payload_status_result.map_err(|err| EngineError::Other(err.to_string()))?;
 
+        if matches!(payload_status.status, PayloadStatusEnum::Valid) {
+            // The EL executes the block without knowing which forced txs it consumed, so it's
+            // asked separately whether the ones left out of it are invalid.
+            let forced_txs = forced_txs_from_ops(payload.ops());
+            if !forced_txs.is_empty()
+                && !self
+                    .client
+                    .check_forced_inclusions(block_hash, forced_txs)
+                    .await
+                    .map_err(|err| EngineError::Other(err.to_string()))?
+            {
+                return EngineResult::Ok(BlockStatus::Invalid);
+            }
+        }
+
         match payload_status.status {
             PayloadStatusEnum::Valid => EngineResult::Ok(BlockStatus::Valid),
             PayloadStatusEnum::Syncing => EngineResult::Ok(BlockStatus::Syncing),
@@ -407,9 +496,12 @@ mod tests {
     use rand::{rngs::OsRng, Rng};
     use reth_primitives::revm_primitives::{alloy_primitives::Bloom, Bytes, FixedBytes, U256};
     use strata_eectl::{errors::EngineResult, messages::PayloadEnv};
-    use strata_primitives::buf::Buf32;
+    use strata_primitives::buf::{Buf32, Buf64};
     use strata_reth_node::{ExecutionPayloadEnvelopeV2, ExecutionPayloadFieldV2};
-    use strata_state::block::{L2Block, L2BlockAccessory};
+    use strata_state::{
+        block::{ExecSegment, L1Segment, L2Block, L2BlockAccessory, L2BlockBody},
+        header::{L2BlockHeader, SignedL2BlockHeader},
+    };
 
     use super::*;
     use crate::http_client::MockEngineRpc;
@@ -557,6 +649,7 @@ mod tests {
                     block_value: U256::from(100),
                 },
                 withdrawal_intents: vec![],
+                forced_inclusions: vec![],
             })
         });
 
@@ -612,4 +705,96 @@ mod tests {
 
         assert!(matches!(result, EngineResult::Ok(BlockStatus::Valid)));
     }
+
+    #[tokio::test]
+    async fn test_submit_new_payload_skipped_valid_forced_inclusion() {
+        let mut mock_client = MockEngineRpc::new();
+        let fcs = ForkchoiceState::default();
+
+        let el_payload = ElPayload {
+            base_fee_per_gas: FixedBytes::<32>::from(U256::from(10)).into(),
+            parent_hash: Default::default(),
+            fee_recipient: Default::default(),
+            state_root: Default::default(),
+            receipts_root: Default::default(),
+            logs_bloom: [0u8; 256],
+            prev_randao: Default::default(),
+            block_number: Default::default(),
+            gas_limit: Default::default(),
+            gas_used: Default::default(),
+            timestamp: Default::default(),
+            extra_data: Default::default(),
+            block_hash: Default::default(),
+            transactions: Default::default(),
+        };
+        let accessory_data = borsh::to_vec(&el_payload).unwrap();
+
+        let ops = vec![Op::ForcedInclusion(ELForcedInclusionData::new(
+            0,
+            vec![0xde, 0xad],
+        ))];
+        let update_input = make_update_input_from_payload_and_ops(el_payload, &ops).unwrap();
+        let update_output = UpdateOutput::new_from_state(Buf32::zero());
+
+        let payload_data = ExecPayloadData::new(
+            ExecUpdate::new(update_input, update_output),
+            accessory_data,
+            ops,
+        );
+
+        mock_client.expect_new_payload_v2().returning(move |_| {
+            Ok(alloy_rpc_types::engine::PayloadStatus {
+                status: PayloadStatusEnum::Valid,
+                latest_valid_hash: None,
+            })
+        });
+        // the EL finds that a forced tx left out of the block was valid
+        mock_client
+            .expect_check_forced_inclusions()
+            .returning(|_, _| Ok(false));
+
+        let rpc_exec_engine_inner = RpcExecEngineInner::new(mock_client, fcs);
+
+        let result = rpc_exec_engine_inner.submit_new_payload(payload_data).await;
+
+        assert!(matches!(result, EngineResult::Ok(BlockStatus::Invalid)));
+    }
+
+    #[test]
+    fn test_validate_block_accessory() {
+        let mut v1_payload = random_execution_payload_v1();
+        let evm_block = try_payload_v2_to_block(ExecutionPayloadV2 {
+            payload_inner: v1_payload.clone(),
+            withdrawals: vec![],
+        })
+        .unwrap();
+        v1_payload.block_hash = evm_block.header.hash_slow();
+        let el_payload: ElPayload = v1_payload.into();
+
+        let update_input = make_update_input_from_payload_and_ops(el_payload.clone(), &[]).unwrap();
+        let update_output = UpdateOutput::new_from_state(el_payload.state_root);
+        let exec_seg = ExecSegment::new(ExecUpdate::new(update_input, update_output));
+        let body = L2BlockBody::new(L1Segment::new_empty(), exec_seg);
+        let header = L2BlockHeader::new(1, 0, L2BlockId::default(), &body, Buf32::zero());
+        let block = L2Block::new(SignedL2BlockHeader::new(header, Buf64::zero()), body);
+
+        let accessory = L2BlockAccessory::new(borsh::to_vec(&el_payload).unwrap());
+        let bundle = L2BlockBundle::new(block.clone(), accessory);
+        assert!(validate_block_accessory(&bundle));
+
+        let mut tampered_payload = el_payload;
+        tampered_payload.gas_used += 1;
+        let accessory = L2BlockAccessory::new(borsh::to_vec(&tampered_payload).unwrap());
+        let bundle = L2BlockBundle::new(block.clone(), accessory);
+        assert!(
+            !validate_block_accessory(&bundle),
+            "payload not matching the block hash should be rejected"
+        );
+
+        let bundle = L2BlockBundle::new(block, L2BlockAccessory::new(vec![]));
+        assert!(
+            !validate_block_accessory(&bundle),
+            "undecodable payload should be rejected"
+        );
+    }
 }
@@ -7,10 +7,17 @@ use alloy_rpc_types::{
     },
     serde_helpers::WithOtherFields,
 };
-use jsonrpsee::http_client::{transport::HttpBackend, HttpClient, HttpClientBuilder};
+use jsonrpsee::{
+    core::client::ClientT,
+    http_client::{transport::HttpBackend, HttpClient, HttpClientBuilder},
+    rpc_params,
+};
 #[cfg(test)]
 use mockall::automock;
-use reth_primitives::{revm_primitives::alloy_primitives::BlockHash, Block};
+use reth_primitives::{
+    revm_primitives::alloy_primitives::{BlockHash, Bytes},
+    Block,
+};
 use reth_rpc_api::{EngineApiClient, EthApiClient};
 use reth_rpc_layer::{AuthClientLayer, AuthClientService};
 use strata_reth_node::{
@@ -53,6 +60,12 @@ pub trait EngineRpc {
     ) -> RpcResult<ExecutionPayloadBodiesV1>;
 
     async fn block_by_hash(&self, block_hash: BlockHash) -> RpcResult<Option<Block>>;
+
+    async fn check_forced_inclusions(
+        &self,
+        block_hash: BlockHash,
+        forced_txs: Vec<Bytes>,
+    ) -> RpcResult<bool>;
 }
 
 #[derive(Debug, Clone)]
@@ -112,4 +125,17 @@ impl EngineRpc for EngineRpcClient {
 
         Ok(block.map(|b| b.try_into().unwrap()))
     }
+
+    async fn check_forced_inclusions(
+        &self,
+        block_hash: BlockHash,
+        forced_txs: Vec<Bytes>,
+    ) -> RpcResult<bool> {
+        self.client
+            .request(
+                "strataee_checkForcedInclusions",
+                rpc_params![block_hash, forced_txs],
+            )
+            .await
+    }
 }
@@ -6,5 +6,6 @@ mod http_client;
 pub mod engine;
 pub mod preloaded_storage;
 
+pub use engine::validate_block_accessory;
 pub use fork_choice_state::fork_choice_state_initial;
 pub use http_client::EngineRpcClient;
@@ -29,6 +29,27 @@ impl SequencerKeys {
         })
     }
 
+    /// Creates a new [`SequencerKeys`] for a rotation of the sequencer key from a master
+    /// [`Xpriv`].
+    ///
+    /// Rotation `0` is the original sequencer key, the same as [`SequencerKeys::new`]. Any later
+    /// rotation `n` is derived at the hardened child `n` of the original sequencer key, i.e. at
+    /// the path `m/56'/10'/n'`.
+    pub fn new_rotated(master: &Xpriv, rotation: u32) -> Result<Self, KeyError> {
+        if rotation == 0 {
+            return Self::new(master);
+        }
+
+        let path =
+            STRATA_SEQUENCER_DERIVATION_PATH.extend([ChildNumber::from_hardened_idx(rotation)?]);
+        let derived = master.derive_priv(SECP256K1, &path)?;
+
+        Ok(Self {
+            master: *master,
+            derived,
+        })
+    }
+
     /// Sequencer's master [`Xpriv`].
     pub fn master_xpriv(&self) -> &Xpriv {
         &self.master
@@ -146,6 +167,29 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_new_rotated() {
+        let master = Xpriv::new_master(Network::Regtest, &[2u8; 32]).unwrap();
+
+        let original = SequencerKeys::new(&master).unwrap();
+        let rotation_0 = SequencerKeys::new_rotated(&master, 0).unwrap();
+        assert_eq!(
+            original.derived_xpub(),
+            rotation_0.derived_xpub(),
+            "rotation 0 must be the original key"
+        );
+
+        let rotation_1 = SequencerKeys::new_rotated(&master, 1).unwrap();
+        let rotation_2 = SequencerKeys::new_rotated(&master, 2).unwrap();
+        assert_ne!(original.derived_xpub(), rotation_1.derived_xpub());
+        assert_ne!(rotation_1.derived_xpub(), rotation_2.derived_xpub());
+
+        assert!(
+            SequencerKeys::new_rotated(&master, 1 << 31).is_err(),
+            "rotation must fit in a hardened child number"
+        );
+    }
+
     #[test]
     #[cfg(feature = "zeroize")]
     fn test_zeroize() {
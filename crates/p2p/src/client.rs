@@ -0,0 +1,85 @@
+use std::cmp::min;
+
+use futures::stream::{self, Stream, StreamExt};
+use strata_rpc_types::RpcSyncStatus;
+use strata_state::{block::L2BlockBundle, chain_state::Chainstate, id::L2BlockId};
+use strata_sync::{ClientError, SyncClient};
+use tracing::*;
+
+use crate::node::P2pHandle;
+
+/// Syncs from the peer with the highest chain tip.
+pub struct P2pSyncClient {
+    handle: P2pHandle,
+    download_batch_size: usize,
+}
+
+impl P2pSyncClient {
+    pub fn new(handle: P2pHandle, download_batch_size: usize) -> Self {
+        Self {
+            handle,
+            download_batch_size,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SyncClient for P2pSyncClient {
+    async fn get_sync_status(&self) -> Result<RpcSyncStatus, ClientError> {
+        let (_, status) = self
+            .handle
+            .best_peer()
+            .ok_or_else(|| ClientError::Network("no peer with a known status".to_string()))?;
+
+        Ok(RpcSyncStatus {
+            tip_height: status.tip_height,
+            tip_block_id: status.tip_blkid,
+            finalized_block_id: status.finalized_blkid,
+        })
+    }
+
+    fn get_blocks_range(
+        &self,
+        start_height: u64,
+        end_height: u64,
+    ) -> impl Stream<Item = L2BlockBundle> {
+        let block_ranges = (start_height..=end_height)
+            .step_by(self.download_batch_size)
+            .map(move |s| (s, min(self.download_batch_size as u64 + s - 1, end_height)));
+
+        stream::unfold(block_ranges, |mut block_ranges| async {
+            let (start_height, end_height) = block_ranges.next()?;
+            let (peer, _) = self.handle.best_peer()?;
+            match self
+                .handle
+                .request_blocks_range(peer, start_height, end_height)
+                .await
+            {
+                Ok(blocks) => Some((stream::iter(blocks), block_ranges)),
+                Err(err) => {
+                    error!(%peer, "failed to get blocks: {err}");
+                    None
+                }
+            }
+        })
+        .flatten()
+    }
+
+    async fn get_block_by_id(
+        &self,
+        block_id: &L2BlockId,
+    ) -> Result<Option<L2BlockBundle>, ClientError> {
+        self.handle
+            .request_block_by_id(block_id)
+            .await
+            .map_err(|e| ClientError::Network(e.to_string()))
+    }
+
+    /// Chainstate snapshots are only served over RPC.
+    async fn get_chainstate_snapshot(
+        &self,
+        _epoch: u64,
+    ) -> Result<Option<Chainstate>, ClientError> {
+        Ok(None)
+    }
+}
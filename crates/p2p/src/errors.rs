@@ -0,0 +1,31 @@
+use strata_state::id::L2BlockId;
+use thiserror::Error;
+
+use crate::node::PeerId;
+
+#[derive(Debug, Error)]
+pub enum P2pError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("message of {0} bytes is too large")]
+    MessageTooLarge(usize),
+
+    #[error("failed to decode message: {0}")]
+    Decode(String),
+
+    #[error("too many peers")]
+    TooManyPeers,
+
+    #[error("peer {0} is not connected")]
+    PeerUnavailable(PeerId),
+
+    #[error("peer {0} did not respond in time")]
+    Timeout(PeerId),
+
+    #[error("peer {0} sent invalid block {1}")]
+    InvalidBlock(PeerId, L2BlockId),
+
+    #[error("peer did not send a valid hello")]
+    Handshake,
+}
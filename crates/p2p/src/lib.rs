@@ -0,0 +1,18 @@
+//! Peer-to-peer network L2 blocks get gossiped over, so that full nodes don't all have to sync
+//! from the sequencer.
+//!
+//! Nodes keep plain TCP connections to the peers they are configured with and to the ones that
+//! connect to them, once both sides have shown they are on the same rollup.  New blocks are
+//! relayed to every peer after their credential, body and accessory are checked, and peers can be
+//! asked for the blocks they have by height or by id, which [`P2pSyncClient`] uses to sync from
+//! the peer with the highest tip it has proven.
+
+mod client;
+mod errors;
+mod message;
+mod node;
+
+pub use client::P2pSyncClient;
+pub use errors::P2pError;
+pub use message::{Message, PeerStatus};
+pub use node::{announce_tip_task, start_p2p, AccessoryCheck, P2pHandle, PeerId};
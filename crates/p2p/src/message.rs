@@ -0,0 +1,125 @@
+//! Messages exchanged between peers and how they are framed on the wire.
+//!
+//! Every message is borsh-encoded and prefixed with its length as a big-endian `u32`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use strata_primitives::buf::Buf32;
+use strata_state::{block::L2BlockBundle, id::L2BlockId};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::errors::P2pError;
+
+/// Upper bound of the size of a single message, which has to fit a full response to a range
+/// request.
+pub const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Chain tip a node advertises to its peers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct PeerStatus {
+    pub tip_height: u64,
+    pub tip_blkid: L2BlockId,
+    pub finalized_blkid: L2BlockId,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum Message {
+    /// The first message on a connection, so that nodes of different rollups don't peer.
+    Hello { rollup_params_hash: Buf32 },
+
+    /// The sender's chain tip, sent on connect and whenever it changes.
+    Status(PeerStatus),
+
+    /// A block that's new to the sender, relayed to every peer that may not have it yet.
+    NewBlock(L2BlockBundle),
+
+    /// Asks for the blocks at the heights from `start` to `end`, inclusive.
+    GetBlocksRange { req_id: u64, start: u64, end: u64 },
+
+    /// Asks for a single block.
+    GetBlockById { req_id: u64, blkid: L2BlockId },
+
+    /// Response to either request, with the blocks ordered by height.
+    Blocks {
+        req_id: u64,
+        blocks: Vec<L2BlockBundle>,
+    },
+}
+
+/// Reads the next message from the stream.
+pub(crate) async fn read_message<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Message, P2pError> {
+    let len = reader.read_u32().await? as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(P2pError::MessageTooLarge(len));
+    }
+
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf).await?;
+    borsh::from_slice(&buf).map_err(|e| P2pError::Decode(e.to_string()))
+}
+
+/// Writes a message to the stream, flushing it right away.
+pub(crate) async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    msg: &Message,
+) -> Result<(), P2pError> {
+    let buf = borsh::to_vec(msg)?;
+    if buf.len() > MAX_MESSAGE_SIZE {
+        return Err(P2pError::MessageTooLarge(buf.len()));
+    }
+
+    writer.write_u32(buf.len() as u32).await?;
+    writer.write_all(&buf).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_test_utils::l2::gen_l2_chain;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_message_roundtrip() {
+        let blocks = gen_l2_chain(None, 2);
+        let msgs = vec![
+            Message::Hello {
+                rollup_params_hash: Buf32::zero(),
+            },
+            Message::NewBlock(blocks[0].clone()),
+            Message::GetBlocksRange {
+                req_id: 1,
+                start: 0,
+                end: 2,
+            },
+            Message::Blocks { req_id: 1, blocks },
+        ];
+
+        let mut buf = Vec::new();
+        for msg in &msgs {
+            write_message(&mut buf, msg).await.unwrap();
+        }
+
+        let mut reader = buf.as_slice();
+        for msg in &msgs {
+            assert_eq!(&read_message(&mut reader).await.unwrap(), msg);
+        }
+        assert!(
+            read_message(&mut reader).await.is_err(),
+            "should fail at the end of the stream"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_message() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(MAX_MESSAGE_SIZE as u32 + 1).to_be_bytes());
+
+        assert!(matches!(
+            read_message(&mut buf.as_slice()).await,
+            Err(P2pError::MessageTooLarge(_))
+        ));
+    }
+}
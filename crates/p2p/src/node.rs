@@ -0,0 +1,898 @@
+//! Connections to the peers and the handling of the messages they send.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use strata_config::P2pConfig;
+use strata_primitives::{buf::Buf32, params::Params};
+use strata_state::{
+    block::L2BlockBundle,
+    block_validation::{check_block_credential, validate_block_segments},
+    header::L2Header,
+    id::L2BlockId,
+};
+use strata_status::StatusChannel;
+use strata_storage::L2BlockManager;
+use tokio::{
+    io::BufWriter,
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    sync::{mpsc, oneshot},
+};
+use tracing::*;
+
+use crate::{
+    errors::P2pError,
+    message::{read_message, write_message, Message, PeerStatus},
+};
+
+/// Identifies a connection to a peer for as long as it is up.
+pub type PeerId = u64;
+
+/// Checks the parts of a block that its header doesn't commit to, i.e. the accessory.
+pub type AccessoryCheck = fn(&L2BlockBundle) -> bool;
+
+/// Maximum number of blocks served for a single range request.
+const MAX_BLOCKS_PER_REQUEST: u64 = 100;
+
+/// Number of messages queued up for a peer before new ones get dropped.
+const PEER_QUEUE_SIZE: usize = 256;
+
+/// Number of block ids remembered to avoid relaying a block more than once.
+const SEEN_CACHE_SIZE: usize = 4096;
+
+/// Number of gossiped blocks kept around to serve requests for blocks that aren't stored yet.
+const RECENT_BLOCKS_CACHE_SIZE: usize = 512;
+
+/// Time to wait for a peer to respond to a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time to wait before redialing a configured peer.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Time to wait for a peer to say hello after connecting.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Peer {
+    addr: SocketAddr,
+    inbound: bool,
+    tx: mpsc::Sender<Message>,
+
+    /// The last status the peer sent.
+    claimed_status: Option<PeerStatus>,
+
+    /// The last status the peer proved by serving the block at its tip, which is the only one
+    /// peers are ranked by.
+    status: Option<PeerStatus>,
+}
+
+/// State shared between the connections and the handles.
+struct Shared {
+    params: Arc<Params>,
+    rollup_params_hash: Buf32,
+    l2_block_manager: Arc<L2BlockManager>,
+    check_accessory: AccessoryCheck,
+    /// Inbound connections are capped so that they can't take the slots of the configured peers.
+    max_inbound_peers: usize,
+    peers: Mutex<HashMap<PeerId, Peer>>,
+    next_peer_id: AtomicU64,
+    next_req_id: AtomicU64,
+    local_status: Mutex<Option<PeerStatus>>,
+    seen: Mutex<LruCache<L2BlockId, ()>>,
+    recent_blocks: Mutex<LruCache<L2BlockId, L2BlockBundle>>,
+    /// Requests waiting for a response, along with the peer they were sent to.
+    pending: Mutex<HashMap<u64, (PeerId, oneshot::Sender<Vec<L2BlockBundle>>)>>,
+}
+
+impl Shared {
+    fn has_inbound_slot(&self) -> bool {
+        let num_inbound = self.peers.lock().values().filter(|p| p.inbound).count();
+        num_inbound < self.max_inbound_peers
+    }
+
+    fn add_peer(
+        &self,
+        addr: SocketAddr,
+        tx: mpsc::Sender<Message>,
+        inbound: bool,
+    ) -> Result<PeerId, P2pError> {
+        let mut peers = self.peers.lock();
+        let num_inbound = peers.values().filter(|p| p.inbound).count();
+        if inbound && num_inbound >= self.max_inbound_peers {
+            return Err(P2pError::TooManyPeers);
+        }
+
+        let id = self.next_peer_id.fetch_add(1, Ordering::Relaxed);
+        peers.insert(
+            id,
+            Peer {
+                addr,
+                inbound,
+                tx,
+                claimed_status: None,
+                status: None,
+            },
+        );
+        Ok(id)
+    }
+
+    fn remove_peer(&self, id: PeerId) {
+        self.peers.lock().remove(&id);
+    }
+
+    /// Queues a message for a peer, dropping it if the peer is gone or too far behind.
+    fn send_to(&self, id: PeerId, msg: Message) -> bool {
+        let peers = self.peers.lock();
+        let Some(peer) = peers.get(&id) else {
+            return false;
+        };
+        if let Err(e) = peer.tx.try_send(msg) {
+            warn!(%id, addr = %peer.addr, %e, "failed to queue message for peer");
+            return false;
+        }
+        true
+    }
+
+    /// Queues a message for every peer but `except`.
+    fn broadcast(&self, msg: &Message, except: Option<PeerId>) {
+        for (id, peer) in self.peers.lock().iter() {
+            if Some(*id) == except {
+                continue;
+            }
+            if let Err(e) = peer.tx.try_send(msg.clone()) {
+                warn!(%id, addr = %peer.addr, %e, "failed to queue message for peer");
+            }
+        }
+    }
+
+    /// Remembers a block as seen, returning whether it was new.
+    fn mark_seen(&self, blkid: L2BlockId) -> bool {
+        self.seen.lock().put(blkid, ()).is_none()
+    }
+
+    /// Checks everything about a block from a peer that can be checked without executing it.
+    ///
+    /// The block id only covers the header, so the body and the accessory have to be checked
+    /// against it before the block gets relayed or cached under that id.
+    fn check_block(&self, block: &L2BlockBundle) -> bool {
+        check_block_credential(block.header(), self.params.rollup())
+            && validate_block_segments(block)
+            && (self.check_accessory)(block)
+    }
+
+    fn get_recent_block(&self, blkid: &L2BlockId) -> Option<L2BlockBundle> {
+        self.recent_blocks.lock().get(blkid).cloned()
+    }
+
+    /// Looks a block up among the recent ones first, then in the database.
+    async fn get_block(&self, blkid: &L2BlockId) -> Option<L2BlockBundle> {
+        if let Some(block) = self.get_recent_block(blkid) {
+            return Some(block);
+        }
+
+        match self.l2_block_manager.get_block_data_async(blkid).await {
+            Ok(block) => block,
+            Err(e) => {
+                warn!(%blkid, %e, "failed to load block");
+                None
+            }
+        }
+    }
+
+    /// Sends a request to a peer and waits for its response, checking every block in it.
+    async fn request(
+        &self,
+        peer: PeerId,
+        make_msg: impl FnOnce(u64) -> Message,
+    ) -> Result<Vec<L2BlockBundle>, P2pError> {
+        let req_id = self.next_req_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(req_id, (peer, tx));
+
+        if !self.send_to(peer, make_msg(req_id)) {
+            self.pending.lock().remove(&req_id);
+            return Err(P2pError::PeerUnavailable(peer));
+        }
+
+        let blocks = match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(blocks)) => blocks,
+            Ok(Err(_)) => return Err(P2pError::PeerUnavailable(peer)),
+            Err(_) => {
+                self.pending.lock().remove(&req_id);
+                return Err(P2pError::Timeout(peer));
+            }
+        };
+
+        if let Some(block) = blocks.iter().find(|block| !self.check_block(block)) {
+            return Err(P2pError::InvalidBlock(peer, block.header().get_blockid()));
+        }
+
+        Ok(blocks)
+    }
+
+    /// Collects the blocks we have in the range, capped to [`MAX_BLOCKS_PER_REQUEST`] heights.
+    async fn get_blocks_range(&self, start: u64, end: u64) -> Vec<L2BlockBundle> {
+        let end = end.min(start.saturating_add(MAX_BLOCKS_PER_REQUEST - 1));
+        let mut blocks = Vec::new();
+
+        for height in start..=end {
+            let mut blkids = match self
+                .l2_block_manager
+                .get_blocks_at_height_async(height)
+                .await
+            {
+                Ok(blkids) => blkids,
+                Err(e) => {
+                    warn!(%height, %e, "failed to load block ids");
+                    Vec::new()
+                }
+            };
+
+            // Blocks that were gossiped to us may not be stored yet.
+            for (blkid, block) in self.recent_blocks.lock().iter() {
+                if block.header().blockidx() == height && !blkids.contains(blkid) {
+                    blkids.push(*blkid);
+                }
+            }
+
+            for blkid in blkids {
+                if let Some(block) = self.get_block(&blkid).await {
+                    blocks.push(block);
+                }
+            }
+        }
+
+        blocks
+    }
+}
+
+/// Handle to the P2P node, used to publish blocks and to request them from peers.
+#[derive(Clone)]
+pub struct P2pHandle {
+    shared: Arc<Shared>,
+    local_addr: SocketAddr,
+}
+
+impl P2pHandle {
+    /// Address the node accepts connections on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn num_peers(&self) -> usize {
+        self.shared.peers.lock().len()
+    }
+
+    /// Gossips a block to every peer, unless it was already seen.
+    pub fn publish_block(&self, block: L2BlockBundle) {
+        let blkid = block.header().get_blockid();
+        if !self.shared.mark_seen(blkid) {
+            return;
+        }
+
+        trace!(%blkid, "publishing block");
+        self.shared.recent_blocks.lock().put(blkid, block.clone());
+        self.shared.broadcast(&Message::NewBlock(block), None);
+    }
+
+    /// Sets the chain tip advertised to the peers.
+    pub fn set_local_status(&self, status: PeerStatus) {
+        let mut local_status = self.shared.local_status.lock();
+        if *local_status == Some(status) {
+            return;
+        }
+
+        *local_status = Some(status);
+        self.shared.broadcast(&Message::Status(status), None);
+    }
+
+    /// The peer with the highest proven chain tip, along with its status.
+    pub fn best_peer(&self) -> Option<(PeerId, PeerStatus)> {
+        self.shared
+            .peers
+            .lock()
+            .iter()
+            .filter_map(|(id, peer)| peer.status.map(|status| (*id, status)))
+            .max_by_key(|(_, status)| status.tip_height)
+    }
+
+    /// Ids of the connected peers, with the highest proven chain tips first.
+    fn peers_by_tip(&self) -> Vec<PeerId> {
+        let mut peers: Vec<_> = self
+            .shared
+            .peers
+            .lock()
+            .iter()
+            .map(|(id, peer)| (*id, peer.status.map(|status| status.tip_height)))
+            .collect();
+        peers.sort_by(|a, b| b.1.cmp(&a.1));
+        peers.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// A block that was gossiped to us recently.
+    pub fn get_recent_block(&self, blkid: &L2BlockId) -> Option<L2BlockBundle> {
+        self.shared.get_recent_block(blkid)
+    }
+
+    /// Asks a peer for the blocks at the heights from `start` to `end`, inclusive.  Peers serve
+    /// at most 100 heights at once, so the response may stop short of `end`.
+    pub async fn request_blocks_range(
+        &self,
+        peer: PeerId,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<L2BlockBundle>, P2pError> {
+        self.shared
+            .request(peer, |req_id| Message::GetBlocksRange {
+                req_id,
+                start,
+                end,
+            })
+            .await
+    }
+
+    /// Asks the peers for a block, best peer first, until one of them has it.
+    pub async fn request_block_by_id(
+        &self,
+        blkid: &L2BlockId,
+    ) -> Result<Option<L2BlockBundle>, P2pError> {
+        if let Some(block) = self.get_recent_block(blkid) {
+            return Ok(Some(block));
+        }
+
+        let blkid = *blkid;
+        for peer in self.peers_by_tip() {
+            let res = self
+                .shared
+                .request(peer, |req_id| Message::GetBlockById { req_id, blkid })
+                .await;
+            match res {
+                Ok(blocks) => {
+                    if let Some(block) = blocks
+                        .into_iter()
+                        .find(|block| block.header().get_blockid() == blkid)
+                    {
+                        return Ok(Some(block));
+                    }
+                }
+                Err(e) => debug!(%peer, %blkid, %e, "failed to request block"),
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Starts listening on the configured address and creates the [`P2pHandle`] along with the task
+/// that accepts connections and keeps dialing the configured peers.
+///
+/// Blocks from peers are only relayed or served once their accessory passes `check_accessory`.
+pub async fn start_p2p(
+    config: P2pConfig,
+    params: Arc<Params>,
+    l2_block_manager: Arc<L2BlockManager>,
+    check_accessory: AccessoryCheck,
+) -> anyhow::Result<(P2pHandle, impl Future<Output = anyhow::Result<()>>)> {
+    let listener = TcpListener::bind(config.listen_addr).await?;
+    let local_addr = listener.local_addr()?;
+    info!(%local_addr, "listening for p2p connections");
+
+    let rollup_params_hash = params.rollup().compute_hash();
+    let shared = Arc::new(Shared {
+        params,
+        rollup_params_hash,
+        l2_block_manager,
+        check_accessory,
+        max_inbound_peers: config.max_peers.saturating_sub(config.peers.len()),
+        peers: Mutex::new(HashMap::new()),
+        next_peer_id: AtomicU64::new(0),
+        next_req_id: AtomicU64::new(0),
+        local_status: Mutex::new(None),
+        seen: Mutex::new(LruCache::new(
+            NonZeroUsize::new(SEEN_CACHE_SIZE).expect("p2p: nonzero cache size"),
+        )),
+        recent_blocks: Mutex::new(LruCache::new(
+            NonZeroUsize::new(RECENT_BLOCKS_CACHE_SIZE).expect("p2p: nonzero cache size"),
+        )),
+        pending: Mutex::new(HashMap::new()),
+    });
+
+    let handle = P2pHandle {
+        shared: shared.clone(),
+        local_addr,
+    };
+    Ok((handle, p2p_task(shared, listener, config.peers)))
+}
+
+async fn p2p_task(
+    shared: Arc<Shared>,
+    listener: TcpListener,
+    peers: Vec<SocketAddr>,
+) -> anyhow::Result<()> {
+    for addr in peers {
+        tokio::spawn(dial_task(shared.clone(), addr));
+    }
+
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        debug!(%addr, "accepted p2p connection");
+        let shared = shared.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_connection(&shared, stream, addr, true).await {
+                debug!(%addr, %e, "p2p connection closed");
+            }
+        });
+    }
+}
+
+/// Keeps a connection to a configured peer up, redialing it whenever it drops.
+async fn dial_task(shared: Arc<Shared>, addr: SocketAddr) {
+    loop {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => {
+                debug!(%addr, "connected to peer");
+                if let Err(e) = run_connection(&shared, stream, addr, false).await {
+                    debug!(%addr, %e, "p2p connection closed");
+                }
+            }
+            Err(e) => debug!(%addr, %e, "failed to connect to peer"),
+        }
+
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Runs a connection until either side closes it.
+async fn run_connection(
+    shared: &Arc<Shared>,
+    stream: TcpStream,
+    addr: SocketAddr,
+    inbound: bool,
+) -> Result<(), P2pError> {
+    if inbound && !shared.has_inbound_slot() {
+        return Err(P2pError::TooManyPeers);
+    }
+
+    stream.set_nodelay(true)?;
+    let (mut reader, mut writer) = stream.into_split();
+    handshake(shared, &mut reader, &mut writer).await?;
+
+    let (tx, rx) = mpsc::channel(PEER_QUEUE_SIZE);
+    let id = shared.add_peer(addr, tx, inbound)?;
+    info!(%id, %addr, "peer connected");
+
+    let local_status = *shared.local_status.lock();
+    if let Some(status) = local_status {
+        shared.send_to(id, Message::Status(status));
+    }
+
+    let writer_task = tokio::spawn(write_loop(writer, rx));
+    let res = read_loop(shared, id, reader).await;
+
+    shared.remove_peer(id);
+    writer_task.abort();
+    info!(%id, %addr, "peer disconnected");
+    res
+}
+
+/// Exchanges hellos with the peer, failing if it isn't on the same rollup.
+async fn handshake(
+    shared: &Shared,
+    reader: &mut OwnedReadHalf,
+    writer: &mut OwnedWriteHalf,
+) -> Result<(), P2pError> {
+    let hello = Message::Hello {
+        rollup_params_hash: shared.rollup_params_hash,
+    };
+    write_message(writer, &hello).await?;
+
+    match tokio::time::timeout(HANDSHAKE_TIMEOUT, read_message(reader)).await {
+        Ok(Ok(msg)) if msg == hello => Ok(()),
+        Ok(Err(e)) => Err(e),
+        _ => Err(P2pError::Handshake),
+    }
+}
+
+async fn write_loop(writer: OwnedWriteHalf, mut rx: mpsc::Receiver<Message>) {
+    let mut writer = BufWriter::new(writer);
+    while let Some(msg) = rx.recv().await {
+        if let Err(e) = write_message(&mut writer, &msg).await {
+            debug!(%e, "failed to write p2p message");
+            return;
+        }
+    }
+}
+
+async fn read_loop(
+    shared: &Arc<Shared>,
+    id: PeerId,
+    mut reader: OwnedReadHalf,
+) -> Result<(), P2pError> {
+    loop {
+        let msg = read_message(&mut reader).await?;
+        handle_message(shared, id, msg).await;
+    }
+}
+
+async fn handle_message(shared: &Arc<Shared>, id: PeerId, msg: Message) {
+    match msg {
+        Message::Hello { .. } => debug!(%id, "got hello after handshake"),
+
+        Message::Status(status) => {
+            trace!(%id, ?status, "got peer status");
+            let is_new = shared
+                .peers
+                .lock()
+                .get_mut(&id)
+                .is_some_and(|peer| peer.claimed_status.replace(status) != Some(status));
+            if is_new {
+                tokio::spawn(verify_status(shared.clone(), id, status));
+            }
+        }
+
+        Message::NewBlock(block) => handle_new_block(shared, id, block),
+
+        Message::GetBlocksRange { req_id, start, end } => {
+            let blocks = shared.get_blocks_range(start, end).await;
+            shared.send_to(id, Message::Blocks { req_id, blocks });
+        }
+
+        Message::GetBlockById { req_id, blkid } => {
+            let blocks = shared.get_block(&blkid).await.into_iter().collect();
+            shared.send_to(id, Message::Blocks { req_id, blocks });
+        }
+
+        Message::Blocks { req_id, blocks } => {
+            let tx = {
+                let mut pending = shared.pending.lock();
+                match pending.get(&req_id) {
+                    Some((peer, _)) if *peer == id => pending.remove(&req_id).map(|(_, tx)| tx),
+                    Some(_) => {
+                        warn!(%id, %req_id, "got response to request sent to another peer");
+                        None
+                    }
+                    None => {
+                        debug!(%id, %req_id, "got response to unknown request");
+                        None
+                    }
+                }
+            };
+
+            if let Some(tx) = tx {
+                // The requester may have timed out already
+                let _ = tx.send(blocks);
+            }
+        }
+    }
+}
+
+/// Records the status of a peer once it proves it by serving the block at its tip, so that a peer
+/// can't get picked to sync from by advertising a made up tip.
+async fn verify_status(shared: Arc<Shared>, id: PeerId, status: PeerStatus) {
+    let blkid = status.tip_blkid;
+    let tip_block = match shared.get_block(&blkid).await {
+        Some(block) => Some(block),
+        None => match shared
+            .request(id, |req_id| Message::GetBlockById { req_id, blkid })
+            .await
+        {
+            Ok(blocks) => blocks
+                .into_iter()
+                .find(|block| block.header().get_blockid() == blkid),
+            Err(e) => {
+                debug!(%id, %blkid, %e, "failed to request tip block");
+                None
+            }
+        },
+    };
+
+    if !tip_block.is_some_and(|block| block.header().blockidx() == status.tip_height) {
+        warn!(%id, ?status, "peer did not prove its chain tip");
+        return;
+    }
+
+    if let Some(peer) = shared.peers.lock().get_mut(&id) {
+        // A newer status may have come in meanwhile.
+        if peer.claimed_status == Some(status) {
+            peer.status = Some(status);
+        }
+    }
+}
+
+/// Relays a gossiped block to the other peers if it is new and valid.
+fn handle_new_block(shared: &Shared, id: PeerId, block: L2BlockBundle) {
+    let blkid = block.header().get_blockid();
+    if shared.seen.lock().contains(&blkid) {
+        return;
+    }
+
+    // Only valid blocks are marked as seen, so an invalid copy can't shadow the real one.
+    if !shared.check_block(&block) {
+        warn!(%id, %blkid, "peer gossiped invalid block");
+        return;
+    }
+
+    if !shared.mark_seen(blkid) {
+        return;
+    }
+
+    let height = block.header().blockidx();
+    trace!(%id, %blkid, %height, "got new block");
+    shared.recent_blocks.lock().put(blkid, block.clone());
+    shared.broadcast(&Message::NewBlock(block), Some(id));
+}
+
+/// Advertises our chain tip and gossips the block at the tip whenever it changes.
+pub async fn announce_tip_task(
+    handle: P2pHandle,
+    status_channel: StatusChannel,
+    l2_block_manager: Arc<L2BlockManager>,
+) -> anyhow::Result<()> {
+    let mut chs_rx = status_channel.subscribe_chain_state();
+    loop {
+        let tip = chs_rx
+            .borrow_and_update()
+            .as_ref()
+            .map(|chs| (chs.chain_tip_slot(), chs.chain_tip_blockid()));
+
+        if let Some((tip_height, tip_blkid)) = tip {
+            let finalized_blkid = status_channel
+                .sync_state()
+                .map(|sync| *sync.finalized_blkid())
+                .unwrap_or_default();
+            handle.set_local_status(PeerStatus {
+                tip_height,
+                tip_blkid,
+                finalized_blkid,
+            });
+
+            match l2_block_manager.get_block_data_async(&tip_blkid).await {
+                Ok(Some(block)) => handle.publish_block(block),
+                Ok(None) => warn!(%tip_blkid, "missing tip block"),
+                Err(e) => warn!(%tip_blkid, %e, "failed to load tip block"),
+            }
+        }
+
+        if chs_rx.changed().await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_primitives::block_credential::CredRule;
+    use strata_rocksdb::test_utils::get_common_db;
+    use strata_state::block::L2Block;
+    use strata_test_utils::l2::{gen_l2_chain, gen_params};
+
+    use super::*;
+
+    async fn start_node(params: &Params, peers: Vec<SocketAddr>) -> P2pHandle {
+        let config = P2pConfig {
+            listen_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            peers,
+            ..Default::default()
+        };
+        start_node_with_config(params, config).await
+    }
+
+    async fn start_node_with_config(params: &Params, config: P2pConfig) -> P2pHandle {
+        let pool = threadpool::ThreadPool::new(1);
+        let l2_block_manager = Arc::new(L2BlockManager::new(pool, get_common_db()));
+
+        let (handle, task) =
+            start_p2p(config, Arc::new(params.clone()), l2_block_manager, |_| true)
+                .await
+                .unwrap();
+        tokio::spawn(task);
+        handle
+    }
+
+    async fn wait_until(mut cond: impl FnMut() -> bool) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !cond() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("condition should be met");
+    }
+
+    #[tokio::test]
+    async fn test_gossip_and_requests() {
+        let params = gen_params();
+
+        // a <- b <- c, so everything has to go through b
+        let a = start_node(&params, vec![]).await;
+        let b = start_node(&params, vec![a.local_addr()]).await;
+        let c = start_node(&params, vec![b.local_addr()]).await;
+        wait_until(|| a.num_peers() == 1 && b.num_peers() == 2 && c.num_peers() == 1).await;
+
+        let blocks = gen_l2_chain(None, 3);
+        for block in &blocks {
+            a.publish_block(block.clone());
+        }
+
+        for block in &blocks {
+            let blkid = block.header().get_blockid();
+            wait_until(|| c.get_recent_block(&blkid).is_some()).await;
+        }
+
+        let tip = blocks.last().unwrap().header();
+        b.set_local_status(PeerStatus {
+            tip_height: tip.blockidx(),
+            tip_blkid: tip.get_blockid(),
+            finalized_blkid: L2BlockId::default(),
+        });
+        wait_until(|| c.best_peer().is_some()).await;
+
+        let (peer, status) = c.best_peer().unwrap();
+        assert_eq!(status.tip_blkid, tip.get_blockid());
+
+        let range = c
+            .request_blocks_range(peer, 0, tip.blockidx())
+            .await
+            .unwrap();
+        assert_eq!(range, blocks, "should get every block in order");
+
+        // Ask a peer rather than the local cache
+        let block = c
+            .shared
+            .request(peer, |req_id| Message::GetBlockById {
+                req_id,
+                blkid: blocks[1].header().get_blockid(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(block, vec![blocks[1].clone()]);
+
+        let block = c.request_block_by_id(&L2BlockId::default()).await.unwrap();
+        assert!(block.is_none(), "should not find unknown block");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_block_not_relayed() {
+        let mut params = gen_params();
+        params.rollup.cred_rule = CredRule::SchnorrKey(Buf32::zero());
+
+        let a = start_node(&params, vec![]).await;
+        let b = start_node(&params, vec![a.local_addr()]).await;
+        wait_until(|| a.num_peers() == 1 && b.num_peers() == 1).await;
+
+        // The generated blocks aren't signed
+        let block = gen_l2_chain(None, 0).remove(0);
+        let blkid = block.header().get_blockid();
+        a.publish_block(block);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(
+            b.get_recent_block(&blkid).is_none(),
+            "block with invalid credential should be dropped"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tampered_block_not_relayed() {
+        let params = gen_params();
+
+        let a = start_node(&params, vec![]).await;
+        let b = start_node(&params, vec![a.local_addr()]).await;
+        wait_until(|| a.num_peers() == 1 && b.num_peers() == 1).await;
+
+        // The real header with the body of another block
+        let blocks = gen_l2_chain(None, 1);
+        let blkid = blocks[1].header().get_blockid();
+        let tampered = L2BlockBundle::new(
+            L2Block::new(blocks[1].header().clone(), blocks[0].body().clone()),
+            blocks[1].accessory().clone(),
+        );
+        a.publish_block(tampered);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(
+            b.get_recent_block(&blkid).is_none(),
+            "block with tampered body should be dropped"
+        );
+
+        // The tampered copy must not keep the real one from getting through
+        a.shared.seen.lock().pop(&blkid);
+        a.publish_block(blocks[1].clone());
+        wait_until(|| b.get_recent_block(&blkid) == Some(blocks[1].clone())).await;
+    }
+
+    #[tokio::test]
+    async fn test_unproven_tip_ignored() {
+        let params = gen_params();
+
+        let a = start_node(&params, vec![]).await;
+        let b = start_node(&params, vec![a.local_addr()]).await;
+        wait_until(|| a.num_peers() == 1 && b.num_peers() == 1).await;
+
+        a.set_local_status(PeerStatus {
+            tip_height: u64::MAX,
+            tip_blkid: L2BlockId::default(),
+            finalized_blkid: L2BlockId::default(),
+        });
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(
+            b.best_peer().is_none(),
+            "peer should not be ranked by a tip it can't serve"
+        );
+
+        let blocks = gen_l2_chain(None, 1);
+        for block in &blocks {
+            a.publish_block(block.clone());
+        }
+        let tip = blocks[1].header();
+        a.set_local_status(PeerStatus {
+            tip_height: tip.blockidx(),
+            tip_blkid: tip.get_blockid(),
+            finalized_blkid: L2BlockId::default(),
+        });
+        wait_until(|| b.best_peer().is_some()).await;
+    }
+
+    #[tokio::test]
+    async fn test_other_rollup_rejected() {
+        let params = gen_params();
+        let mut other_params = gen_params();
+        other_params.rollup.rollup_name = "other".to_string();
+
+        let a = start_node(&params, vec![]).await;
+        let b = start_node(&other_params, vec![a.local_addr()]).await;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(
+            a.num_peers(),
+            0,
+            "node of another rollup should be rejected"
+        );
+        assert_eq!(
+            b.num_peers(),
+            0,
+            "node of another rollup should be rejected"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outbound_slots_reserved() {
+        let params = gen_params();
+
+        let a = start_node(&params, vec![]).await;
+        let b = start_node_with_config(
+            &params,
+            P2pConfig {
+                listen_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+                peers: vec![a.local_addr()],
+                max_peers: 1,
+                ..Default::default()
+            },
+        )
+        .await;
+        let c = start_node(&params, vec![b.local_addr()]).await;
+        wait_until(|| a.num_peers() == 1 && b.num_peers() == 1).await;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(
+            b.num_peers(),
+            1,
+            "only the configured peer should get the slot"
+        );
+        assert_eq!(c.num_peers(), 0, "inbound connection should be rejected");
+    }
+}
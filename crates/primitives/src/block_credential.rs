@@ -2,9 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::*;
+use crate::{params::ParamsError, prelude::*};
 
 /// Rule we use to decide how to identify if an L2 block is correctly signed.
+///
+/// Every rule except [`CredRule::Unchecked`] expects a single BIP340 schnorr signature from the key
+/// that is responsible for the block's slot. A threshold set of sequencers can be supported
+/// through any of these by using their aggregated key (via MuSig2 or FROST).
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CredRule {
@@ -13,4 +17,240 @@ pub enum CredRule {
 
     /// Just sign every block with a static BIP340 schnorr pubkey.
     SchnorrKey(Buf32),
+
+    /// Sign every block with the BIP340 schnorr pubkey that is scheduled for its slot.
+    ///
+    /// This allows rotating the sequencer key at predetermined slots without a hard fork.
+    KeySchedule(Vec<ScheduledKey>),
+
+    /// Sign every block with the BIP340 schnorr pubkey of the leader for its slot, where the
+    /// leadership rotates through a set of sequencers.
+    RotatingLeader(LeaderSet),
+}
+
+/// A sequencer pubkey that becomes valid at a certain slot.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ScheduledKey {
+    /// The first slot that has to be signed with this key.
+    ///
+    /// The key remains valid until the activation slot of the next key in the schedule.
+    pub activation_slot: u64,
+
+    /// The BIP340 schnorr pubkey.
+    pub pubkey: Buf32,
+}
+
+/// A set of sequencers that take turns in producing blocks.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LeaderSet {
+    /// The BIP340 schnorr pubkeys of the sequencers in the order they lead.
+    pub keys: Vec<Buf32>,
+
+    /// Number of consecutive slots that each leader produces blocks for before handing over to
+    /// the next.
+    pub slots_per_leader: u64,
+}
+
+impl LeaderSet {
+    /// Returns the pubkey of the leader for the given slot.
+    pub fn leader_at(&self, slot: u64) -> Option<&Buf32> {
+        if self.keys.is_empty() || self.slots_per_leader == 0 {
+            return None;
+        }
+
+        let turn = slot / self.slots_per_leader;
+        let idx = (turn % self.keys.len() as u64) as usize;
+        self.keys.get(idx)
+    }
+}
+
+impl CredRule {
+    /// Returns the pubkey that is expected to sign the block at the given slot.
+    ///
+    /// Returns `None` if blocks are unchecked or if no key is responsible for the slot, in which
+    /// case no block can be valid at the slot.
+    pub fn signer_at(&self, slot: u64) -> Option<&Buf32> {
+        match self {
+            CredRule::Unchecked => None,
+            CredRule::SchnorrKey(pubkey) => Some(pubkey),
+            CredRule::KeySchedule(schedule) => schedule
+                .iter()
+                .rev()
+                .find(|key| key.activation_slot <= slot)
+                .map(|key| &key.pubkey),
+            CredRule::RotatingLeader(leader_set) => leader_set.leader_at(slot),
+        }
+    }
+
+    /// Returns all the pubkeys that are expected to sign blocks at some slot.
+    ///
+    /// Returns `None` if blocks are unchecked, in which case any pubkey is allowed.
+    pub fn signers(&self) -> Option<Vec<Buf32>> {
+        match self {
+            CredRule::Unchecked => None,
+            CredRule::SchnorrKey(pubkey) => Some(vec![*pubkey]),
+            CredRule::KeySchedule(schedule) => {
+                Some(schedule.iter().map(|key| key.pubkey).collect())
+            }
+            CredRule::RotatingLeader(leader_set) => Some(leader_set.keys.clone()),
+        }
+    }
+
+    /// Checks if the given pubkey is allowed to sign the block at the given slot.
+    ///
+    /// Any pubkey is allowed if blocks are unchecked.
+    pub fn is_signer_at(&self, slot: u64, pubkey: &Buf32) -> bool {
+        match self {
+            CredRule::Unchecked => true,
+            _ => self.signer_at(slot) == Some(pubkey),
+        }
+    }
+
+    /// Checks the credential of something signed for the given slot, such as a block or a
+    /// checkpoint ending at that slot, with the provided signature verifier.
+    ///
+    /// The verifier is called with the pubkey that is expected to have signed. This always passes
+    /// if blocks are unchecked.
+    pub fn verify_signer_at(&self, slot: u64, verify: impl FnOnce(&Buf32) -> bool) -> bool {
+        match self {
+            CredRule::Unchecked => true,
+            _ => self.signer_at(slot).is_some_and(verify),
+        }
+    }
+
+    /// Sanity checks the rule.
+    pub fn check_well_formed(&self) -> Result<(), ParamsError> {
+        match self {
+            CredRule::Unchecked | CredRule::SchnorrKey(_) => {}
+            CredRule::KeySchedule(schedule) => {
+                if schedule.is_empty() {
+                    return Err(ParamsError::InvalidCredRule("empty key schedule"));
+                }
+
+                let is_sorted = schedule
+                    .windows(2)
+                    .all(|pair| pair[0].activation_slot < pair[1].activation_slot);
+                if !is_sorted {
+                    return Err(ParamsError::InvalidCredRule(
+                        "key schedule not strictly ordered by activation slot",
+                    ));
+                }
+            }
+            CredRule::RotatingLeader(leader_set) => {
+                if leader_set.keys.is_empty() {
+                    return Err(ParamsError::InvalidCredRule("empty leader set"));
+                }
+
+                if leader_set.slots_per_leader == 0 {
+                    return Err(ParamsError::ZeroProperty("slots_per_leader"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(b: u8) -> Buf32 {
+        Buf32::from([b; 32])
+    }
+
+    #[test]
+    fn test_key_schedule_signer() {
+        let rule = CredRule::KeySchedule(vec![
+            ScheduledKey {
+                activation_slot: 0,
+                pubkey: key(1),
+            },
+            ScheduledKey {
+                activation_slot: 100,
+                pubkey: key(2),
+            },
+        ]);
+        rule.check_well_formed()
+            .expect("schedule should be well formed");
+
+        assert_eq!(rule.signer_at(0), Some(&key(1)));
+        assert_eq!(rule.signer_at(99), Some(&key(1)));
+        assert_eq!(rule.signer_at(100), Some(&key(2)));
+        assert_eq!(rule.signer_at(u64::MAX), Some(&key(2)));
+
+        assert!(rule.is_signer_at(50, &key(1)));
+        assert!(
+            !rule.is_signer_at(150, &key(1)),
+            "rotated key must not sign"
+        );
+    }
+
+    #[test]
+    fn test_key_schedule_before_activation() {
+        let rule = CredRule::KeySchedule(vec![ScheduledKey {
+            activation_slot: 10,
+            pubkey: key(1),
+        }]);
+
+        assert_eq!(rule.signer_at(9), None);
+        assert!(!rule.verify_signer_at(9, |_| true));
+    }
+
+    #[test]
+    fn test_rotating_leader_signer() {
+        let rule = CredRule::RotatingLeader(LeaderSet {
+            keys: vec![key(1), key(2), key(3)],
+            slots_per_leader: 2,
+        });
+        rule.check_well_formed()
+            .expect("leader set should be well formed");
+
+        let leaders: Vec<_> = (0..8).map(|slot| *rule.signer_at(slot).unwrap()).collect();
+        assert_eq!(
+            leaders,
+            vec![
+                key(1),
+                key(1),
+                key(2),
+                key(2),
+                key(3),
+                key(3),
+                key(1),
+                key(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unchecked_signer() {
+        let rule = CredRule::Unchecked;
+
+        assert_eq!(rule.signer_at(0), None);
+        assert!(rule.is_signer_at(0, &key(1)));
+        assert!(rule.verify_signer_at(0, |_| false));
+    }
+
+    #[test]
+    fn test_check_well_formed() {
+        let unordered = CredRule::KeySchedule(vec![
+            ScheduledKey {
+                activation_slot: 10,
+                pubkey: key(1),
+            },
+            ScheduledKey {
+                activation_slot: 10,
+                pubkey: key(2),
+            },
+        ]);
+        assert!(unordered.check_well_formed().is_err());
+
+        assert!(CredRule::KeySchedule(vec![]).check_well_formed().is_err());
+
+        let no_slots = CredRule::RotatingLeader(LeaderSet {
+            keys: vec![key(1)],
+            slots_per_leader: 0,
+        });
+        assert!(no_slots.check_well_formed().is_err());
+    }
 }
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     constants::{MUSIG2_PARTIAL_SIG_SIZE, NONCE_SEED_SIZE, PUB_NONCE_SIZE, SEC_NONCE_SIZE},
-    l1::{BitcoinPsbt, TaprootSpendPath},
+    l1::{BitcoinAmount, BitcoinPsbt, TaprootSpendPath, XOnlyPk},
 };
 
 /// The ID of an operator.
@@ -29,12 +29,39 @@ pub type OperatorIdx = u32;
 /// The bitcoin block height that a withdrawal command references.
 pub type BitcoinBlockHeight = u64;
 
+/// An output of a withdrawal, paying out the amount of a withdrawal intent to the user.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct WithdrawOutput {
+    /// Taproot Schnorr XOnlyPubkey with the merkle root information.
+    dest_addr: XOnlyPk,
+
+    /// Amount in sats.
+    amt: BitcoinAmount,
+}
+
+impl WithdrawOutput {
+    pub fn new(dest_addr: XOnlyPk, amt: BitcoinAmount) -> Self {
+        Self { dest_addr, amt }
+    }
+
+    pub fn dest_addr(&self) -> &XOnlyPk {
+        &self.dest_addr
+    }
+
+    pub fn amt(&self) -> BitcoinAmount {
+        self.amt
+    }
+}
+
 /// A table that maps [`OperatorIdx`] to the corresponding [`PublicKey`].
 ///
 /// We use a [`PublicKey`] instead of an [`bitcoin::secp256k1::XOnlyPublicKey`] for convenience
 /// since the [`musig2`] crate has functions that expect a [`PublicKey`] and this table is most
 /// useful for interacting with those functions.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PublickeyTable(pub BTreeMap<OperatorIdx, PublicKey>);
 
 impl From<BTreeMap<OperatorIdx, PublicKey>> for PublickeyTable {
@@ -65,8 +65,10 @@ impl From<L1BlockId> for BlockHash {
     }
 }
 
-/// Reference to a transaction in a block.  This is the block index and the
-/// position of the transaction in the block.
+/// Reference to a transaction in a block.  This is the block index, the
+/// position of the transaction in the block and the index of the protocol
+/// operation among the ones carried by that transaction, since a batched
+/// inscription carries several of them.
 #[derive(
     Copy,
     Clone,
@@ -82,7 +84,7 @@ impl From<L1BlockId> for BlockHash {
     Serialize,
     Deserialize,
 )]
-pub struct L1TxRef(u64, u32);
+pub struct L1TxRef(u64, u32, u32);
 
 impl L1TxRef {
     pub fn blk_idx(&self) -> u64 {
@@ -91,17 +93,27 @@ impl L1TxRef {
     pub fn position(&self) -> u32 {
         self.1
     }
+    pub fn op_idx(&self) -> u32 {
+        self.2
+    }
 }
 
-impl From<L1TxRef> for (u64, u32) {
+impl From<L1TxRef> for (u64, u32, u32) {
     fn from(val: L1TxRef) -> Self {
-        (val.0, val.1)
+        (val.0, val.1, val.2)
     }
 }
 
+/// Refers to the first protocol operation of the transaction.
 impl From<(u64, u32)> for L1TxRef {
     fn from(value: (u64, u32)) -> Self {
-        Self(value.0, value.1)
+        Self(value.0, value.1, 0)
+    }
+}
+
+impl From<(u64, u32, u32)> for L1TxRef {
+    fn from(value: (u64, u32, u32)) -> Self {
+        Self(value.0, value.1, value.2)
     }
 }
 
@@ -330,6 +342,9 @@ pub struct L1Status {
 
     /// number of published transactions in current run (commit + reveal pair count as 1)
     pub published_inscription_count: u64,
+
+    /// Why the writer is currently refusing to inscribe blobs, if it is.
+    pub writer_error: Option<String>,
 }
 
 /// A wrapper around the [`bitcoin::Address<NetworkChecked>`] type created in order to implement
@@ -553,7 +568,16 @@ impl Sum for BitcoinAmount {
 
 /// A wrapper around [`Buf32`] for XOnly Schnorr taproot pubkeys.
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Arbitrary,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
 )]
 pub struct XOnlyPk(Buf32);
 
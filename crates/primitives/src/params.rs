@@ -62,6 +62,13 @@ pub struct RollupParams {
     /// max number of deposits in a block
     pub max_deposits_in_block: u8,
 
+    /// Number of L1 blocks after the one it matured in that a forced inclusion has to be
+    /// processed within.
+    pub forced_inclusion_delay: u64,
+
+    /// max number of forced inclusions in a block
+    pub max_forced_incls_in_block: u8,
+
     /// network the l1 is set on
     pub network: bitcoin::Network,
 }
@@ -79,6 +86,8 @@ impl RollupParams {
             return Err(ParamsError::EmptyRollupName);
         }
 
+        self.cred_rule.check_well_formed()?;
+
         match &self.operator_config {
             OperatorConfig::Static(optbl) => {
                 if optbl.is_empty() {
@@ -116,6 +125,14 @@ impl RollupParams {
             return Err(ParamsError::ZeroProperty("max_deposits_in_block"));
         }
 
+        if self.forced_inclusion_delay == 0 {
+            return Err(ParamsError::ZeroProperty("forced_inclusion_delay"));
+        }
+
+        if self.max_forced_incls_in_block == 0 {
+            return Err(ParamsError::ZeroProperty("max_forced_incls_in_block"));
+        }
+
         Ok(())
     }
 
@@ -156,6 +173,14 @@ impl RollupParams {
             address,
         }
     }
+
+    /// Returns the slot of the last block of the given epoch.
+    ///
+    /// The chain state moves on to the next epoch with this block, and the
+    /// checkpoint for the epoch covers the L2 blocks up to and including it.
+    pub fn epoch_terminal_slot(&self, epoch: u64) -> u64 {
+        (epoch + 1) * self.target_l2_batch_size
+    }
 }
 
 /// Describes how we decide to wait for proofs for checkpoints to generate.
@@ -234,6 +259,9 @@ pub enum ParamsError {
 
     #[error("no operators set")]
     NoOperators,
+
+    #[error("invalid cred rule: {0}")]
+    InvalidCredRule(&'static str),
 }
 
 impl OperatorConfig {
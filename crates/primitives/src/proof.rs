@@ -71,6 +71,32 @@ pub enum ProofContext {
     Checkpoint(u64),
 }
 
+impl ProofContext {
+    /// Returns the kind of this proof context.
+    pub fn kind(&self) -> ProofContextKind {
+        match self {
+            ProofContext::BtcBlockspace(..) => ProofContextKind::BtcBlockspace,
+            ProofContext::L1Batch(..) => ProofContextKind::L1Batch,
+            ProofContext::EvmEeStf(..) => ProofContextKind::EvmEeStf,
+            ProofContext::ClStf(..) => ProofContextKind::ClStf,
+            ProofContext::ClAgg(..) => ProofContextKind::ClAgg,
+            ProofContext::Checkpoint(..) => ProofContextKind::Checkpoint,
+        }
+    }
+}
+
+/// The kind of a [`ProofContext`], without the range or scope of the proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofContextKind {
+    BtcBlockspace,
+    L1Batch,
+    EvmEeStf,
+    ClStf,
+    ClAgg,
+    Checkpoint,
+}
+
 /// Represents the ZkVm host used for proof generation.
 ///
 /// This enum identifies the ZkVm environment utilized to create a proof.
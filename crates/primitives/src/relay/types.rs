@@ -56,6 +56,26 @@ impl<'a> Arbitrary<'a> for BridgeMessage {
 }
 
 impl BridgeMessage {
+    /// Creates a message that still has to be signed, for when the signing key is not held by a
+    /// [`MessageSigner`](super::util::MessageSigner).
+    ///
+    /// The signature of the [`compute_id`](Self::compute_id) gets attached with
+    /// [`with_signature`](Self::with_signature).
+    pub fn new_unsigned(source_id: OperatorIdx, scope: Vec<u8>, payload: Vec<u8>) -> Self {
+        Self {
+            source_id,
+            sig: Buf64::zero(),
+            scope,
+            payload,
+        }
+    }
+
+    /// Attaches the signature of the message id.
+    pub fn with_signature(mut self, sig: Buf64) -> Self {
+        self.sig = sig;
+        self
+    }
+
     /// Source ID.
     pub fn source_id(&self) -> u32 {
         self.source_id
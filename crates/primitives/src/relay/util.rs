@@ -47,19 +47,13 @@ impl MessageSigner {
 
     /// Signs a message using a raw scope and payload.
     pub fn sign_raw(&self, scope: Vec<u8>, payload: Vec<u8>) -> Result<BridgeMessage, io::Error> {
-        let mut tmp_m = BridgeMessage {
-            source_id: self.operator_idx,
-            sig: Buf64::zero(),
-            scope,
-            payload,
-        };
+        let tmp_m = BridgeMessage::new_unsigned(self.operator_idx, scope, payload);
 
         let id: Buf32 = tmp_m.compute_id().into();
         // WARN: I don't know if a global context is safe here, maybe.
         let sig = sign_msg_hash(&self.msg_signing_sk, &id);
-        tmp_m.sig = sig;
 
-        Ok(tmp_m)
+        Ok(tmp_m.with_signature(sig))
     }
 
     /// Signs a message with some particular typed scope.
@@ -2,7 +2,7 @@
 //! deposits, forced inclusion transactions as well as state updates
 
 use bitcoin::Block;
-use strata_primitives::{block_credential::CredRule, params::RollupParams};
+use strata_primitives::params::RollupParams;
 use strata_state::{
     batch::BatchCheckpoint,
     tx::{DepositInfo, ProtocolOperation},
@@ -27,10 +27,11 @@ pub fn extract_relevant_info(
                 deposits.push(deposit_info.clone());
             }
             ProtocolOperation::Checkpoint(signed_batch) => {
-                if let CredRule::SchnorrKey(pub_key) = rollup_params.cred_rule {
-                    assert!(signed_batch.verify_sig(&pub_key));
-                }
                 let batch: BatchCheckpoint = signed_batch.clone().into();
+                let last_slot = batch.batch_info().l2_range.1;
+                assert!(rollup_params
+                    .cred_rule
+                    .verify_signer_at(last_slot, |pub_key| signed_batch.verify_sig(pub_key)));
                 // Note: This assumes we will have one proper update
                 prev_checkpoint = prev_checkpoint.or(Some(batch));
             }
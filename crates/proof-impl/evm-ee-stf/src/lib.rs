@@ -25,9 +25,10 @@ use db::InMemoryDBHelper;
 use mpt::keccak;
 pub use primitives::{EvmBlockStfInput, EvmBlockStfOutput};
 use processor::{EvmConfig, EvmProcessor};
-use reth_primitives::revm_primitives::alloy_primitives::B256;
+use reth_primitives::{revm_primitives::alloy_primitives::B256, TransactionSignedNoHash};
 use revm::{primitives::SpecId, InMemoryDB};
-use strata_reth_evm::collect_withdrawal_intents;
+use strata_reth_evm::{collect_withdrawal_intents, find_skipped_forced_txs, SkippedForcedTx};
+use strata_state::exec_update::ELForcedInclusionData;
 use strata_zkvm::ZkVmEnv;
 use utils::generate_exec_update;
 
@@ -47,6 +48,10 @@ pub fn process_block_transaction(
     // Deposit requests are processed and forwarded as public parameters for verification on the CL
     let deposit_requests = input.withdrawals.clone();
 
+    // Forced inclusions are forwarded as well so the CL can take them off its queue
+    let skipped_forced_txs = check_forced_inclusions(&input.forced_inclusions, &input.transactions);
+    let forced_inclusions = input.forced_inclusions.clone();
+
     // Initialize the in-memory database
     let db = match InMemoryDB::initialize(&mut input) {
         Ok(database) => database,
@@ -59,6 +64,7 @@ pub fn process_block_transaction(
         db: Some(db),
         header: None,
         evm_config,
+        skipped_forced_txs,
     };
 
     evm_processor.initialize();
@@ -84,9 +90,29 @@ pub fn process_block_transaction(
         txn_root: block_header.transactions_root,
         deposit_requests,
         withdrawal_intents,
+        forced_inclusions,
     }
 }
 
+/// Checks that the forced inclusions the block included lead its transactions, in the order they
+/// were queued in.
+///
+/// Returns the ones that were skipped, which the processor checks are invalid at the point they
+/// were due.
+fn check_forced_inclusions(
+    forced_inclusions: &[ELForcedInclusionData],
+    transactions: &[TransactionSignedNoHash],
+) -> Vec<SkippedForcedTx> {
+    let tx_hashes = transactions.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+    find_skipped_forced_txs(
+        forced_inclusions
+            .iter()
+            .map(|forced_incl| forced_incl.payload()),
+        &tx_hashes,
+    )
+    .expect("Forced inclusion out of order")
+}
+
 /// Processes a sequence of EL block transactions from the given `zkvm` environment, ensuring block
 /// hash continuity and committing the resulting updates.
 pub fn process_block_transaction_outer(zkvm: &impl ZkVmEnv) {
@@ -156,4 +182,54 @@ mod tests {
         let op = process_block_transaction(input, EVM_CONFIG);
         assert_eq!(op, test_data.params);
     }
+
+    fn forced_inclusion(tx: &TransactionSignedNoHash) -> ELForcedInclusionData {
+        let payload = tx.clone().with_hash().envelope_encoded();
+        ELForcedInclusionData::new(0, payload.to_vec())
+    }
+
+    #[test]
+    fn test_check_forced_inclusions() {
+        let txs = get_mock_data().witness.transactions;
+
+        // the forced txs lead the block, the malformed ones are skipped
+        let malformed = ELForcedInclusionData::new(0, vec![0xde, 0xad]);
+        check_forced_inclusions(&[malformed, forced_inclusion(&txs[0])], &txs);
+        check_forced_inclusions(&[], &txs);
+    }
+
+    #[test]
+    fn test_skipped_invalid_forced_inclusion() {
+        let test_data = get_mock_data();
+        let mut input = test_data.witness;
+        let mut params = test_data.params;
+
+        // the second copy of the tx has a stale nonce once the first one is executed
+        let forced_incl = forced_inclusion(&input.transactions[0]);
+        input.forced_inclusions = vec![forced_incl.clone(), forced_incl];
+        params.forced_inclusions = input.forced_inclusions.clone();
+
+        let op = process_block_transaction(input, EVM_CONFIG);
+        assert_eq!(op, params);
+    }
+
+    #[test]
+    #[should_panic(expected = "Valid forced inclusion skipped")]
+    fn test_skipped_valid_forced_inclusion() {
+        let mut input = get_mock_data().witness;
+
+        input.forced_inclusions = vec![forced_inclusion(&input.transactions[0])];
+        input.transactions.remove(0);
+        process_block_transaction(input, EVM_CONFIG);
+    }
+
+    #[test]
+    #[should_panic(expected = "Forced inclusion out of order")]
+    fn test_check_forced_inclusions_not_leading() {
+        let txs = get_mock_data().witness.transactions;
+
+        let forced_incls = [forced_inclusion(&txs[0])];
+        let txs = [txs[0].clone(), txs[0].clone()];
+        check_forced_inclusions(&forced_incls, &txs);
+    }
 }
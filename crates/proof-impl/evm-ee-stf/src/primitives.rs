@@ -8,7 +8,7 @@ use reth_primitives::{
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use strata_reth_primitives::WithdrawalIntent;
-use strata_state::block::ExecSegment;
+use strata_state::{block::ExecSegment, exec_update::ELForcedInclusionData};
 
 use crate::mpt::{MptNode, StorageEntry};
 
@@ -28,6 +28,8 @@ pub struct EvmBlockStfOutput {
     pub txn_root: FixedBytes<32>,
     pub withdrawal_intents: Vec<WithdrawalIntent>,
     pub deposit_requests: Vec<Withdrawal>,
+    #[serde(default)]
+    pub forced_inclusions: Vec<ELForcedInclusionData>,
 }
 
 /// Necessary information to prove the execution of a Evm block.
@@ -77,4 +79,9 @@ pub struct EvmBlockStfInput {
 
     /// A list of withdrawals to process.
     pub withdrawals: Vec<Withdrawal>,
+
+    /// The forced inclusions the block processed, the ones that were valid lead the block's
+    /// transactions.
+    #[serde(default)]
+    pub forced_inclusions: Vec<ELForcedInclusionData>,
 }
@@ -33,10 +33,10 @@ use reth_trie_common::root::ordered_trie_root_with_encoder;
 use revm::{
     db::{AccountState, InMemoryDB},
     interpreter::Host,
-    primitives::{SpecId, TransactTo, TxEnv},
+    primitives::{EVMError, SpecId, TransactTo, TxEnv},
     Database, DatabaseCommit, Evm,
 };
-use strata_reth_evm::set_evm_handles;
+use strata_reth_evm::{set_evm_handles, SkippedForcedTx};
 
 use crate::{
     mpt::{keccak, RlpBytes, StateAccount},
@@ -66,6 +66,9 @@ pub struct EvmProcessor<D> {
 
     /// Evm config
     pub evm_config: EvmConfig,
+
+    /// The forced txs the block consumed without including them.
+    pub skipped_forced_txs: Vec<SkippedForcedTx>,
 }
 
 impl<D> EvmProcessor<D> {
@@ -190,7 +193,13 @@ where
         let mut cumulative_gas_used = U256::ZERO;
         let mut receipts = Vec::new();
 
+        let mut skipped_forced_txs = take(&mut self.skipped_forced_txs).into_iter().peekable();
         for (tx_no, tx) in self.input.transactions.iter().enumerate() {
+            while let Some(skipped) = skipped_forced_txs.next_if(|skipped| skipped.due_at == tx_no)
+            {
+                check_skipped_forced_tx(&mut evm, &skipped);
+            }
+
             // Recover the sender from the transaction signature.
             let tx_from = tx.recover_signer().unwrap();
 
@@ -232,6 +241,11 @@ where
             evm.context.evm.db.commit(res.state);
         }
 
+        // The rest were due after the last tx of the block.
+        for skipped in skipped_forced_txs {
+            check_skipped_forced_tx(&mut evm, &skipped);
+        }
+
         // Process consensus layer withdrawals.
         for withdrawal in self.input.withdrawals.iter() {
             // Convert withdrawal amount (in gwei) to wei.
@@ -329,6 +343,21 @@ impl EvmProcessor<InMemoryDB> {
     }
 }
 
+/// Checks that a forced tx the block skipped is invalid against the state it was due to be
+/// executed on, i.e. that the evm rejects it.
+fn check_skipped_forced_tx<D: Database>(evm: &mut Evm<'_, (), D>, skipped: &SkippedForcedTx) {
+    fill_eth_tx_env(
+        &mut evm.context.env_mut().tx,
+        &skipped.tx.transaction,
+        skipped.tx.signer(),
+    );
+    let res = evm.transact();
+    assert!(
+        matches!(res, Err(EVMError::Transaction(_))),
+        "Valid forced inclusion skipped"
+    );
+}
+
 fn fill_eth_tx_env(tx_env: &mut TxEnv, essence: &Transaction, caller: Address) {
     match essence {
         Transaction::Legacy(tx) => {
@@ -24,17 +24,19 @@ pub fn generate_exec_update(el_proof_pp: &EvmBlockStfOutput) -> ExecSegment {
         })
         .collect::<Vec<_>>();
 
-    let applied_ops = el_proof_pp
-        .deposit_requests
+    let deposit_ops = el_proof_pp.deposit_requests.iter().map(|request| {
+        Op::Deposit(ELDepositData::new(
+            request.index,
+            gwei_to_sats(request.amount),
+            request.address.as_slice().to_vec(),
+        ))
+    });
+    let forced_inclusion_ops = el_proof_pp
+        .forced_inclusions
         .iter()
-        .map(|request| {
-            Op::Deposit(ELDepositData::new(
-                request.index,
-                gwei_to_sats(request.amount),
-                request.address.as_slice().to_vec(),
-            ))
-        })
-        .collect::<Vec<_>>();
+        .cloned()
+        .map(Op::ForcedInclusion);
+    let applied_ops = deposit_ops.chain(forced_inclusion_ops).collect::<Vec<_>>();
 
     let update_input = UpdateInput::new(
         el_proof_pp.block_idx,
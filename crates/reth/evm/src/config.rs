@@ -8,7 +8,7 @@ use revm::{
 use revm_primitives::Precompile;
 
 use crate::{
-    constants::{BASEFEE_ADDRESS, FIXED_WITHDRAWAL_WEI, SCHNORR_ADDRESS},
+    constants::{BASEFEE_ADDRESS, MAX_WITHDRAWAL_WEI, MIN_WITHDRAWAL_WEI, SCHNORR_ADDRESS},
     precompiles::{
         bridge::{BridgeoutPrecompile, BRIDGEOUT_ADDRESS},
         schnorr::verify_schnorr_precompile,
@@ -30,7 +30,8 @@ where
             (
                 BRIDGEOUT_ADDRESS,
                 ContextPrecompile::ContextStateful(Arc::new(BridgeoutPrecompile::new(
-                    FIXED_WITHDRAWAL_WEI,
+                    MIN_WITHDRAWAL_WEI,
+                    MAX_WITHDRAWAL_WEI,
                 ))),
             ),
             (
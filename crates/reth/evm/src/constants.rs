@@ -8,8 +8,17 @@ pub const BRIDGEOUT_ADDRESS: Address = address!("5400000000000000000000000000000
 /// The address for the Schnorr precompile contract.
 pub const SCHNORR_ADDRESS: Address = address!("5400000000000000000000000000000000000002");
 
-/// The fixed withdrawal amount in wei (10 BTC equivalent).
-pub const FIXED_WITHDRAWAL_WEI: U256 = u256_from(10 * WEI_PER_BTC);
+/// The minimum withdrawal amount in wei (0.001 BTC equivalent).
+///
+/// Withdrawals below this would not cover the operator fee and the fees of the withdrawal
+/// transaction, or would pay the user a dust output.
+pub const MIN_WITHDRAWAL_WEI: U256 = u256_from(WEI_PER_BTC / 1_000);
+
+/// The maximum withdrawal amount in wei (10 BTC equivalent).
+///
+/// A withdrawal has to be serviced from a single deposit UTXO, so it can't be larger than the
+/// bridge denomination.
+pub const MAX_WITHDRAWAL_WEI: U256 = u256_from(10 * WEI_PER_BTC);
 
 /// The address to send transaction basefee to instead of burning.
 pub const BASEFEE_ADDRESS: Address = address!("5400000000000000000000000000000000000010");
@@ -0,0 +1,54 @@
+use reth_primitives::{TransactionSigned, TransactionSignedEcRecovered, TxHash};
+
+/// A forced tx that a block consumed without including it.
+#[derive(Debug, Clone)]
+pub struct SkippedForcedTx {
+    /// Index of the block tx that the forced tx would have been executed before.
+    pub due_at: usize,
+
+    /// The forced tx.
+    pub tx: TransactionSignedEcRecovered,
+}
+
+/// Decodes a forced tx and recovers its sender.
+///
+/// Returns `None` if the tx can never be included: it doesn't decode, its signature doesn't
+/// recover or it's a blob tx, whose sidecar we don't have.
+pub fn decode_forced_tx(payload: &[u8]) -> Option<TransactionSignedEcRecovered> {
+    TransactionSigned::decode_enveloped(&mut &payload[..])
+        .ok()
+        .filter(|tx| !tx.is_eip4844())
+        .and_then(|tx| tx.into_ecrecovered())
+}
+
+/// Matches the forced txs a block consumed against the txs at the head of the block.
+///
+/// Each forced tx has to be the next block tx, otherwise it was skipped.  Returns the skipped
+/// ones that decode, which the caller must check are invalid against the state at the point they
+/// were due, or `None` if a skipped forced tx shows up later in the block.
+pub fn find_skipped_forced_txs<'a>(
+    forced_txs: impl IntoIterator<Item = &'a [u8]>,
+    block_tx_hashes: &[TxHash],
+) -> Option<Vec<SkippedForcedTx>> {
+    let mut n_leading = 0;
+    let mut skipped = Vec::new();
+    for payload in forced_txs {
+        let Some(tx) = decode_forced_tx(payload) else {
+            continue;
+        };
+
+        if block_tx_hashes.get(n_leading) == Some(&tx.hash()) {
+            n_leading += 1;
+        } else {
+            skipped.push(SkippedForcedTx {
+                due_at: n_leading,
+                tx,
+            });
+        }
+    }
+
+    let out_of_order = skipped
+        .iter()
+        .any(|skipped| block_tx_hashes[n_leading..].contains(&skipped.tx.hash()));
+    (!out_of_order).then_some(skipped)
+}
@@ -3,8 +3,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 mod config;
 pub mod constants;
+mod forced_inclusion;
 mod precompiles;
 mod utils;
 
 pub use config::set_evm_handles;
+pub use forced_inclusion::{decode_forced_tx, find_skipped_forced_txs, SkippedForcedTx};
 pub use utils::collect_withdrawal_intents;
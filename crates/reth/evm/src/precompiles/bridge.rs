@@ -19,13 +19,15 @@ fn try_into_pubkey(maybe_pubkey: &Bytes) -> Result<FixedBytes<32>, TryFromSliceE
 /// Bridge out intent is created during block payload generation.
 /// This precompile validates transaction and burns the bridge out amount.
 pub struct BridgeoutPrecompile {
-    fixed_withdrawal_wei: U256,
+    min_withdrawal_wei: U256,
+    max_withdrawal_wei: U256,
 }
 
 impl BridgeoutPrecompile {
-    pub fn new(fixed_withdrawal_wei: U256) -> Self {
+    pub fn new(min_withdrawal_wei: U256, max_withdrawal_wei: U256) -> Self {
         Self {
-            fixed_withdrawal_wei,
+            min_withdrawal_wei,
+            max_withdrawal_wei,
         }
     }
 }
@@ -41,17 +43,30 @@ impl<DB: Database> ContextStatefulPrecompile<DB> for BridgeoutPrecompile {
         let dest_pk = try_into_pubkey(dest_pk_bytes)
             .map_err(|_| PrecompileError::other("Invalid public key length: expected 32 bytes"))?;
 
-        // Verify that the transaction value matches the required withdrawal amount
+        // Verify that the transaction value is within the allowed withdrawal range
         let withdrawal_amount = evmctx.env.tx.value;
-        if withdrawal_amount != self.fixed_withdrawal_wei {
+        if withdrawal_amount < self.min_withdrawal_wei {
             return Err(PrecompileError::other(
-                "Invalid withdrawal value: must be exactly 10 BTC in wei",
+                "Invalid withdrawal value: below the minimum withdrawal amount",
             )
             .into());
         }
 
-        // Convert wei to satoshis
-        let (sats, _) = wei_to_sats(withdrawal_amount);
+        if withdrawal_amount > self.max_withdrawal_wei {
+            return Err(PrecompileError::other(
+                "Invalid withdrawal value: above the maximum withdrawal amount",
+            )
+            .into());
+        }
+
+        // Convert wei to satoshis, the value can't be burned partially
+        let (sats, rem) = wei_to_sats(withdrawal_amount);
+        if !rem.is_zero() {
+            return Err(PrecompileError::other(
+                "Invalid withdrawal value: must be a whole number of sats in wei",
+            )
+            .into());
+        }
 
         // Try converting sats (U256) into u64 amount
         let amount: u64 = sats.try_into().map_err(|_| PrecompileErrors::Fatal {
@@ -204,6 +204,9 @@ fn extract_zkvm_input<Node: FullNodeComponents>(
         // NOTE: using default to save prover cost.
         // Will need to revisit if BLOCKHASH opcode operation is a blocker
         ancestor_headers: Default::default(),
+        // NOTE: the EL doesn't keep track of the forced inclusions a block processed, the prover
+        // fills them in from the CL's exec update for the block.
+        forced_inclusions: Default::default(),
     };
 
     Ok(input)
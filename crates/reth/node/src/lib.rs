@@ -14,4 +14,4 @@ pub use payload::{
     ExecutionPayloadEnvelopeV2, ExecutionPayloadFieldV2, StrataExecutionPayloadEnvelopeV2,
     StrataPayloadAttributes,
 };
-pub use strata_reth_primitives::WithdrawalIntent;
+pub use strata_reth_primitives::{ForcedInclusion, WithdrawalIntent};
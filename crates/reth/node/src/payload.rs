@@ -15,23 +15,31 @@ use reth_primitives::{
     SealedBlock, Withdrawals,
 };
 use serde::{Deserialize, Serialize};
-use strata_reth_primitives::WithdrawalIntent;
+use strata_reth_primitives::{ForcedInclusion, WithdrawalIntent};
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct StrataPayloadAttributes {
     /// An inner payload type
     #[serde(flatten)]
     pub inner: EthPayloadAttributes,
-    // additional custom fields to be added
+
+    /// Transactions forced through L1 that have to be included before any other transaction.
+    #[serde(default)]
+    pub forced_inclusions: Vec<ForcedInclusion>,
 }
 
 impl StrataPayloadAttributes {
     pub fn new_from_eth(payload_attributes: EthPayloadAttributes) -> Self {
         Self {
             inner: payload_attributes,
-            // more fields here
+            forced_inclusions: Vec::new(),
         }
     }
+
+    pub fn with_forced_inclusions(mut self, forced_inclusions: Vec<ForcedInclusion>) -> Self {
+        self.forced_inclusions = forced_inclusions;
+        self
+    }
 }
 
 impl PayloadAttributes for StrataPayloadAttributes {
@@ -48,47 +56,56 @@ impl PayloadAttributes for StrataPayloadAttributes {
     }
 }
 
-/// New type around the payload builder attributes type
+/// Wrapper around the payload builder attributes type
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct StrataPayloadBuilderAttributes(pub(crate) EthPayloadBuilderAttributes);
+pub struct StrataPayloadBuilderAttributes {
+    pub(crate) inner: EthPayloadBuilderAttributes,
+    pub(crate) forced_inclusions: Vec<ForcedInclusion>,
+}
+
+impl StrataPayloadBuilderAttributes {
+    pub fn forced_inclusions(&self) -> &[ForcedInclusion] {
+        &self.forced_inclusions
+    }
+}
 
 impl PayloadBuilderAttributes for StrataPayloadBuilderAttributes {
     type RpcPayloadAttributes = StrataPayloadAttributes;
     type Error = Infallible;
 
     fn try_new(parent: B256, attributes: StrataPayloadAttributes) -> Result<Self, Infallible> {
-        Ok(Self(EthPayloadBuilderAttributes::new(
-            parent,
-            attributes.inner,
-        )))
+        Ok(Self {
+            inner: EthPayloadBuilderAttributes::new(parent, attributes.inner),
+            forced_inclusions: attributes.forced_inclusions,
+        })
     }
 
     fn payload_id(&self) -> PayloadId {
-        self.0.id
+        self.inner.id
     }
 
     fn parent(&self) -> B256 {
-        self.0.parent
+        self.inner.parent
     }
 
     fn timestamp(&self) -> u64 {
-        self.0.timestamp
+        self.inner.timestamp
     }
 
     fn parent_beacon_block_root(&self) -> Option<B256> {
-        self.0.parent_beacon_block_root
+        self.inner.parent_beacon_block_root
     }
 
     fn suggested_fee_recipient(&self) -> Address {
-        self.0.suggested_fee_recipient
+        self.inner.suggested_fee_recipient
     }
 
     fn prev_randao(&self) -> B256 {
-        self.0.prev_randao
+        self.inner.prev_randao
     }
 
     fn withdrawals(&self) -> &Withdrawals {
-        &self.0.withdrawals
+        &self.inner.withdrawals
     }
 }
 
@@ -96,13 +113,19 @@ impl PayloadBuilderAttributes for StrataPayloadBuilderAttributes {
 pub struct StrataBuiltPayload {
     pub(crate) inner: EthBuiltPayload,
     pub(crate) withdrawal_intents: Vec<WithdrawalIntent>,
+    pub(crate) forced_inclusions: Vec<ForcedInclusion>,
 }
 
 impl StrataBuiltPayload {
-    pub(crate) fn new(inner: EthBuiltPayload, withdrawal_intents: Vec<WithdrawalIntent>) -> Self {
+    pub(crate) fn new(
+        inner: EthBuiltPayload,
+        withdrawal_intents: Vec<WithdrawalIntent>,
+        forced_inclusions: Vec<ForcedInclusion>,
+    ) -> Self {
         Self {
             inner,
             withdrawal_intents,
+            forced_inclusions,
         }
     }
 }
@@ -177,6 +200,9 @@ pub struct StrataExecutionPayloadEnvelopeV2 {
     #[serde(flatten)]
     pub inner: ExecutionPayloadEnvelopeV2,
     pub withdrawal_intents: Vec<WithdrawalIntent>,
+    /// Forced inclusions that were processed by the payload, whether or not they were valid.
+    #[serde(default)]
+    pub forced_inclusions: Vec<ForcedInclusion>,
 }
 
 impl StrataExecutionPayloadEnvelopeV2 {
@@ -190,6 +216,7 @@ impl From<StrataBuiltPayload> for StrataExecutionPayloadEnvelopeV2 {
         Self {
             inner: value.inner.into(),
             withdrawal_intents: value.withdrawal_intents,
+            forced_inclusions: value.forced_inclusions,
         }
     }
 }
@@ -4,7 +4,7 @@ use reth::{
     builder::{components::PayloadServiceBuilder, BuilderContext, PayloadBuilderConfig},
     providers::{CanonStateSubscriptions, ExecutionOutcome, StateProviderFactory},
     revm::database::StateProviderDatabase,
-    transaction_pool::{BestTransactionsAttributes, TransactionPool},
+    transaction_pool::{noop::NoopTransactionPool, BestTransactionsAttributes, TransactionPool},
 };
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, BasicPayloadJobGenerator,
@@ -33,7 +33,7 @@ use revm::{
 use revm_primitives::{
     calc_excess_blob_gas, EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState, U256,
 };
-use strata_reth_evm::collect_withdrawal_intents;
+use strata_reth_evm::{collect_withdrawal_intents, decode_forced_tx};
 use tracing::{debug, trace, warn};
 
 use crate::{
@@ -69,6 +69,23 @@ where
         client: &Client,
         config: PayloadConfig<Self::Attributes>,
     ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+        // The forced inclusions have to be processed by every payload, so an empty payload is
+        // built from them alone.
+        if !config.attributes.forced_inclusions().is_empty() {
+            let args = BuildArguments {
+                client,
+                pool: NoopTransactionPool::default(),
+                cached_reads: Default::default(),
+                config,
+                cancel: Default::default(),
+                best_payload: None,
+            };
+            return match try_build_payload(self.evm_config.clone(), args)? {
+                BuildOutcome::Better { payload, .. } => Ok(payload),
+                _ => Err(PayloadBuilderError::MissingPayload),
+            };
+        }
+
         let PayloadConfig {
             parent_block,
             extra_data,
@@ -90,10 +107,14 @@ where
                 PayloadConfig {
                     parent_block,
                     extra_data,
-                    attributes: attributes.0,
+                    attributes: attributes.inner,
                 },
             )?;
-        Ok(StrataBuiltPayload::new(eth_build_payload, Vec::new()))
+        Ok(StrataBuiltPayload::new(
+            eth_build_payload,
+            Vec::new(),
+            Vec::new(),
+        ))
     }
 }
 
@@ -249,6 +270,67 @@ where
         .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
     let mut receipts = Vec::new();
+
+    // Execute the forced inclusions before any tx from the pool.  A forced tx may only be skipped
+    // if it's invalid against the state it would be executed on, the STF checks this.  One that
+    // is valid but doesn't fit in the block is left on the queue along with the ones after it.
+    let mut n_consumed_forced = 0;
+    for forced in attributes.forced_inclusions() {
+        let Some(tx) = decode_forced_tx(&forced.tx) else {
+            debug!(target: "payload_builder", index = forced.index, "skipping malformed forced inclusion");
+            n_consumed_forced += 1;
+            continue;
+        };
+
+        // A tx over the block gas limit is rejected by the evm below.
+        if tx.gas_limit() <= block_gas_limit
+            && cumulative_gas_used + tx.gas_limit() > block_gas_limit
+        {
+            debug!(target: "payload_builder", index = forced.index, ?tx, "forced inclusion doesn't fit, deferring it");
+            break;
+        }
+
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            initialized_cfg.clone(),
+            initialized_block_env.clone(),
+            evm_config.tx_env(&tx),
+        );
+        let mut evm = evm_config.evm_with_env(&mut db, env);
+
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                debug!(target: "payload_builder", index = forced.index, %err, ?tx, "skipping invalid forced inclusion");
+                n_consumed_forced += 1;
+                continue;
+            }
+            Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+        };
+        drop(evm);
+        db.commit(state);
+
+        let gas_used = result.gas_used();
+        cumulative_gas_used += gas_used;
+
+        #[allow(clippy::needless_update)] // side-effect of optimism fields
+        receipts.push(Some(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: result.into_logs().into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }));
+
+        let miner_fee = tx
+            .effective_tip_per_gas(Some(base_fee))
+            .expect("fee is always valid; execution succeeded");
+        total_fees += U256::from(miner_fee) * U256::from(gas_used);
+
+        executed_senders.push(tx.signer());
+        executed_txs.push(tx.into_signed());
+        n_consumed_forced += 1;
+    }
+
     // let mut withdrawal_intents = Vec::new();
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
@@ -539,7 +621,11 @@ where
     // extend the payload with the blob sidecars from the executed txs
     eth_payload.extend_sidecars(blob_sidecars);
 
-    let payload = StrataBuiltPayload::new(eth_payload, withdrawal_intents);
+    let payload = StrataBuiltPayload::new(
+        eth_payload,
+        withdrawal_intents,
+        attributes.forced_inclusions()[..n_consumed_forced].to_vec(),
+    );
 
     Ok(BuildOutcome::Better {
         payload,
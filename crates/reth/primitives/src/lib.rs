@@ -1,7 +1,7 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 use alloy_sol_types::sol;
-use reth_primitives::revm_primitives::alloy_primitives::B256;
+use reth_primitives::revm_primitives::alloy_primitives::{Bytes, B256};
 use serde::{Deserialize, Serialize};
 
 /// Type for withdrawal_intents in rpc.
@@ -16,6 +16,18 @@ pub struct WithdrawalIntent {
     pub dest_pk: B256,
 }
 
+/// Type for forced inclusions in rpc.
+/// Distinct from `strata_state::forced_inclusion::ForcedInclusion`
+/// as this will live in reth repo eventually
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ForcedInclusion {
+    /// Index of the forced inclusion in the CL's queue.
+    pub index: u64,
+
+    /// Raw signed transaction that was posted on L1.
+    pub tx: Bytes,
+}
+
 sol! {
     #[allow(missing_docs)]
     event WithdrawalIntentEvent(
@@ -0,0 +1,112 @@
+use jsonrpsee::core::RpcResult;
+use reth_evm::ConfigureEvm;
+use reth_primitives::{
+    revm_primitives::{
+        alloy_primitives::{Bytes, B256},
+        BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, SpecId, U256,
+    },
+    Header,
+};
+use reth_provider::{BlockReader, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use revm::{db::State, DatabaseCommit};
+use strata_reth_evm::find_skipped_forced_txs;
+use strata_rpc_utils::{to_jsonrpsee_error, to_jsonrpsee_error_object};
+
+use crate::StrataEngineApiServer;
+
+/// Engine rpc implementation, served on the authenticated endpoint.
+#[derive(Debug, Clone)]
+pub struct StrataEngineRPC<Provider, EvmConfig> {
+    provider: Provider,
+    evm_config: EvmConfig,
+}
+
+impl<Provider, EvmConfig> StrataEngineRPC<Provider, EvmConfig> {
+    /// Create new instance
+    pub fn new(provider: Provider, evm_config: EvmConfig) -> Self {
+        Self {
+            provider,
+            evm_config,
+        }
+    }
+}
+
+impl<Provider, EvmConfig> StrataEngineApiServer for StrataEngineRPC<Provider, EvmConfig>
+where
+    Provider: BlockReader + StateProviderFactory + Clone + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm<Header = Header> + Send + Sync + 'static,
+{
+    fn check_forced_inclusions(&self, block_hash: B256, forced_txs: Vec<Bytes>) -> RpcResult<bool> {
+        let block = self
+            .provider
+            .block_by_hash(block_hash)
+            .map_err(to_jsonrpsee_error("Failed fetching block"))?
+            .ok_or_else(|| to_jsonrpsee_error_object(block_hash, "Unknown block"))?;
+
+        let tx_hashes = block
+            .body
+            .transactions
+            .iter()
+            .map(|tx| tx.hash())
+            .collect::<Vec<_>>();
+        let Some(skipped) =
+            find_skipped_forced_txs(forced_txs.iter().map(|tx| tx.as_ref()), &tx_hashes)
+        else {
+            return Ok(false);
+        };
+        if skipped.is_empty() {
+            return Ok(true);
+        }
+
+        let state = self
+            .provider
+            .history_by_block_hash(block.header.parent_hash)
+            .map_err(to_jsonrpsee_error("Failed fetching parent state"))?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(state))
+            .build();
+
+        let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+        let mut block_env = BlockEnv::default();
+        self.evm_config
+            .fill_cfg_and_block_env(&mut cfg, &mut block_env, &block.header, U256::ZERO);
+
+        // Replay the block up to each skipped forced tx, which the evm must reject at that point.
+        let mut n_executed = 0;
+        for skipped in skipped {
+            for tx in &block.body.transactions[n_executed..skipped.due_at] {
+                let tx = tx
+                    .clone()
+                    .into_ecrecovered()
+                    .ok_or_else(|| to_jsonrpsee_error_object(tx.hash(), "Invalid signature"))?;
+                let env = EnvWithHandlerCfg::new_with_cfg_env(
+                    cfg.clone(),
+                    block_env.clone(),
+                    self.evm_config.tx_env(&tx),
+                );
+                let mut evm = self.evm_config.evm_with_env(&mut db, env);
+                let res = evm
+                    .transact()
+                    .map_err(to_jsonrpsee_error("Failed replaying block"))?;
+                drop(evm);
+                db.commit(res.state);
+            }
+            n_executed = skipped.due_at;
+
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                self.evm_config.tx_env(&skipped.tx),
+            );
+            let mut evm = self.evm_config.evm_with_env(&mut db, env);
+            match evm.transact() {
+                Err(EVMError::Transaction(_)) => {}
+                Err(err) => return Err(to_jsonrpsee_error_object(err, "Failed executing tx")),
+                Ok(_) => return Ok(false),
+            }
+        }
+
+        Ok(true)
+    }
+}
@@ -1,12 +1,14 @@
 //! Strata custom reth rpc
 
+mod engine;
 pub mod eth;
 mod rpc;
 pub mod sequencer;
 
+pub use engine::StrataEngineRPC;
 pub use eth::StrataEthApi;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::revm_primitives::alloy_primitives::B256;
+use reth_primitives::revm_primitives::alloy_primitives::{Bytes, B256};
 pub use rpc::StrataRPC;
 pub use sequencer::SequencerClient;
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,16 @@ pub trait StrataRpcApi {
     ) -> RpcResult<Option<BlockWitness>>;
 }
 
+#[cfg_attr(not(test), rpc(server, namespace = "strataee"))]
+#[cfg_attr(test, rpc(server, client, namespace = "strataee"))]
+pub trait StrataEngineApi {
+    /// Checks that every forced tx the block consumed without including it is invalid at the
+    /// point it was due, and that the included ones lead the block in order.
+    /// Used by the CL when validating a new payload.
+    #[method(name = "checkForcedInclusions")]
+    fn check_forced_inclusions(&self, block_hash: B256, forced_txs: Vec<Bytes>) -> RpcResult<bool>;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BlockWitness {
@@ -1,18 +1,24 @@
 use std::sync::Arc;
 
-use rockbound::{OptimisticTransactionDB as DB, SchemaDBOperationsExt, TransactionRetry};
+use rockbound::{
+    utils::get_last, OptimisticTransactionDB as DB, SchemaDBOperationsExt, TransactionRetry,
+};
 use strata_db::{
-    entities::bridge_tx_state::BridgeTxState,
+    entities::{bridge_tx_state::BridgeTxState, signer_session::SignerSession},
     errors::DbError,
-    traits::{BridgeDutyDatabase, BridgeDutyIndexDatabase, BridgeTxDatabase},
+    traits::{
+        BridgeDutyDatabase, BridgeDutyIndexDatabase, BridgeTxDatabase, ChallengeReportDatabase,
+        SignerSessionDatabase,
+    },
     DbResult,
 };
 use strata_primitives::buf::Buf32;
-use strata_state::bridge_duties::BridgeDutyStatus;
+use strata_state::bridge_duties::{BridgeDutyStatus, ChallengeReport};
 
 use super::schemas::{
-    BridgeDutyCheckpointSchema, BridgeDutyStatusSchema, BridgeDutyTxidSchema, BridgeTxStateSchema,
-    BridgeTxStateTxidSchema,
+    BridgeDutyCheckpointSchema, BridgeDutyFulfillmentSchema, BridgeDutyStatusSchema,
+    BridgeDutyTxidSchema, BridgeTxStateSchema, BridgeTxStateTxidSchema, ChallengeReportIdxSchema,
+    ChallengeReportSchema, ChallengerScannedBlockSchema, SignerSessionSchema,
 };
 use crate::{sequence::get_next_id, DbOpsConfig};
 
@@ -63,6 +69,54 @@ impl BridgeTxDatabase for BridgeTxRocksDb {
     }
 }
 
+pub struct SignerSessionRocksDb {
+    db: Arc<DB>,
+    ops: DbOpsConfig,
+}
+
+impl SignerSessionRocksDb {
+    pub fn new(db: Arc<DB>, ops: DbOpsConfig) -> Self {
+        Self { db, ops }
+    }
+}
+
+impl SignerSessionDatabase for SignerSessionRocksDb {
+    fn put_session(&self, txid: Buf32, session: SignerSession) -> DbResult<()> {
+        self.db
+            .with_optimistic_txn(TransactionRetry::Count(self.ops.retry_count), |txn| {
+                txn.put::<SignerSessionSchema>(&txid, &session)?;
+
+                Ok::<(), DbError>(())
+            })
+            .map_err(|e: rockbound::TransactionError<_>| DbError::TransactionError(e.to_string()))
+    }
+
+    fn delete_session(&self, txid: Buf32) -> DbResult<Option<SignerSession>> {
+        self.db
+            .with_optimistic_txn(TransactionRetry::Count(self.ops.retry_count), |txn| {
+                if let Some(session) = txn.get::<SignerSessionSchema>(&txid)? {
+                    txn.delete::<SignerSessionSchema>(&txid)?;
+                    return Ok::<Option<SignerSession>, DbError>(Some(session));
+                }
+
+                Ok(None)
+            })
+            .map_err(|e: rockbound::TransactionError<_>| DbError::TransactionError(e.to_string()))
+    }
+
+    fn get_all_sessions(&self) -> DbResult<Vec<(Buf32, SignerSession)>> {
+        let mut iterator = self.db.iter::<SignerSessionSchema>()?;
+        iterator.seek_to_first();
+
+        let mut sessions = Vec::new();
+        for res in iterator {
+            sessions.push(res?.into_tuple());
+        }
+
+        Ok(sessions)
+    }
+}
+
 pub struct BridgeDutyRocksDb {
     db: Arc<DB>,
     ops: DbOpsConfig,
@@ -107,6 +161,20 @@ impl BridgeDutyDatabase for BridgeDutyRocksDb {
             })
             .map_err(|e: rockbound::TransactionError<_>| DbError::TransactionError(e.to_string()))
     }
+
+    fn put_fulfillment(&self, txid: Buf32, fulfillment_raw: Vec<u8>) -> DbResult<()> {
+        self.db
+            .with_optimistic_txn(TransactionRetry::Count(self.ops.retry_count), |txn| {
+                txn.put::<BridgeDutyFulfillmentSchema>(&txid, &fulfillment_raw)?;
+
+                Ok::<(), DbError>(())
+            })
+            .map_err(|e: rockbound::TransactionError<_>| DbError::TransactionError(e.to_string()))
+    }
+
+    fn get_fulfillment(&self, txid: Buf32) -> DbResult<Option<Vec<u8>>> {
+        Ok(self.db.get::<BridgeDutyFulfillmentSchema>(&txid)?)
+    }
 }
 
 pub struct BridgeDutyIndexRocksDb {
@@ -136,6 +204,69 @@ impl BridgeDutyIndexDatabase for BridgeDutyIndexRocksDb {
     }
 }
 
+pub struct ChallengeReportRocksDb {
+    db: Arc<DB>,
+    ops: DbOpsConfig,
+}
+
+impl ChallengeReportRocksDb {
+    pub fn new(db: Arc<DB>, ops: DbOpsConfig) -> Self {
+        Self { db, ops }
+    }
+}
+
+impl ChallengeReportDatabase for ChallengeReportRocksDb {
+    fn put_report(&self, id: Buf32, report: ChallengeReport) -> DbResult<Option<u64>> {
+        self.db
+            .with_optimistic_txn(TransactionRetry::Count(self.ops.retry_count), |txn| {
+                let idx = if txn.get::<ChallengeReportSchema>(&id)?.is_none() {
+                    let idx = get_next_id::<ChallengeReportIdxSchema, DB>(txn)?;
+                    txn.put::<ChallengeReportIdxSchema>(&idx, &id)?;
+
+                    Some(idx)
+                } else {
+                    None
+                };
+
+                txn.put::<ChallengeReportSchema>(&id, &report)?;
+
+                Ok::<Option<u64>, DbError>(idx)
+            })
+            .map_err(|e: rockbound::TransactionError<_>| DbError::TransactionError(e.to_string()))
+    }
+
+    fn get_report(&self, id: Buf32) -> DbResult<Option<ChallengeReport>> {
+        Ok(self.db.get::<ChallengeReportSchema>(&id)?)
+    }
+
+    fn get_report_by_idx(&self, idx: u64) -> DbResult<Option<ChallengeReport>> {
+        match self.db.get::<ChallengeReportIdxSchema>(&idx)? {
+            Some(id) => self.get_report(id),
+            None => Ok(None),
+        }
+    }
+
+    fn get_next_report_idx(&self) -> DbResult<u64> {
+        Ok(get_last::<ChallengeReportIdxSchema>(self.db.as_ref())?
+            .map(|(k, _)| k + 1)
+            .unwrap_or_default())
+    }
+
+    fn put_scanned_block(&self, height: u64, block_hash: Buf32) -> DbResult<()> {
+        Ok(self
+            .db
+            .put::<ChallengerScannedBlockSchema>(&height, &block_hash)?)
+    }
+
+    fn get_scanned_block(&self, height: u64) -> DbResult<Option<Buf32>> {
+        Ok(self.db.get::<ChallengerScannedBlockSchema>(&height)?)
+    }
+
+    fn get_last_scanned_height(&self) -> DbResult<Option<u64>> {
+        Ok(get_last::<ChallengerScannedBlockSchema>(self.db.as_ref())?.map(|(height, _)| height))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use arbitrary::{Arbitrary, Unstructured};
@@ -229,6 +360,50 @@ mod tests {
         BridgeTxRocksDb::new(db, config)
     }
 
+    #[test]
+    fn test_signer_session_db() {
+        let db = setup_signer_session_db();
+
+        let mut arb = ArbitraryGenerator::new();
+
+        let txid: Buf32 = arb.generate();
+        let session: SignerSession = arb.generate();
+
+        assert!(
+            db.get_all_sessions().unwrap().is_empty(),
+            "there should be no sessions in an empty db"
+        );
+
+        // Test insert
+        db.put_session(txid, session.clone()).unwrap();
+        assert_eq!(
+            db.get_all_sessions().unwrap(),
+            vec![(txid, session.clone())],
+            "stored sessions should match the session being stored"
+        );
+
+        // Test delete
+        assert_eq!(
+            db.delete_session(txid).unwrap(),
+            Some(session),
+            "deleting a session should return it"
+        );
+        assert!(
+            db.delete_session(txid).unwrap().is_none(),
+            "deleting a session twice should return nothing"
+        );
+        assert!(
+            db.get_all_sessions().unwrap().is_empty(),
+            "there should be no sessions after deletion"
+        );
+    }
+
+    fn setup_signer_session_db() -> SignerSessionRocksDb {
+        let (db, config) = get_rocksdb_tmp_instance().unwrap();
+
+        SignerSessionRocksDb::new(db, config)
+    }
+
     #[test]
     fn test_bridge_duty_status_db() {
         let db = setup_duty_db();
@@ -305,6 +480,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bridge_duty_fulfillment_db() {
+        let db = setup_duty_db();
+
+        let mut arb = ArbitraryGenerator::new();
+
+        let txid: Buf32 = arb.generate();
+        assert!(
+            db.get_fulfillment(txid).unwrap().is_none(),
+            "there should be no fulfillment in an empty db"
+        );
+
+        // Test insert
+        let fulfillment_raw: Vec<u8> = arb.generate();
+        db.put_fulfillment(txid, fulfillment_raw.clone()).unwrap();
+        assert_eq!(
+            db.get_fulfillment(txid).unwrap(),
+            Some(fulfillment_raw),
+            "stored fulfillment should match the fulfillment being stored"
+        );
+
+        // Test update
+        let bumped_raw: Vec<u8> = arb.generate();
+        db.put_fulfillment(txid, bumped_raw.clone()).unwrap();
+        assert_eq!(
+            db.get_fulfillment(txid).unwrap(),
+            Some(bumped_raw),
+            "stored fulfillment should be replaced"
+        );
+    }
+
     fn setup_duty_db() -> BridgeDutyRocksDb {
         let (db, config) = get_rocksdb_tmp_instance().unwrap();
 
@@ -371,4 +577,97 @@ mod tests {
 
         BridgeDutyIndexRocksDb::new(db, config)
     }
+
+    #[test]
+    fn test_challenge_report_db() {
+        let db = setup_challenge_report_db();
+
+        let mut arb = ArbitraryGenerator::new();
+
+        let report: ChallengeReport = arb.generate();
+        let id: Buf32 = arb.generate();
+
+        assert_eq!(
+            db.get_next_report_idx().unwrap(),
+            0,
+            "next report idx should be 0 for an empty db"
+        );
+
+        // Test insert
+        let idx = db.put_report(id, report.clone()).unwrap();
+        assert_eq!(idx, Some(0), "new report should be inserted at idx 0");
+
+        // Test read
+        assert_eq!(
+            db.get_report(id).unwrap(),
+            Some(report.clone()),
+            "stored report should match the report being stored"
+        );
+        assert_eq!(
+            db.get_report_by_idx(0).unwrap(),
+            Some(report),
+            "report at idx should match the report being stored"
+        );
+
+        // Test update
+        let new_report: ChallengeReport = arb.generate();
+        let idx = db.put_report(id, new_report.clone()).unwrap();
+        assert!(
+            idx.is_none(),
+            "updating a report should not assign a new idx"
+        );
+
+        assert_eq!(
+            db.get_report_by_idx(0).unwrap(),
+            Some(new_report),
+            "report at idx should match the updated report"
+        );
+        assert_eq!(
+            db.get_next_report_idx().unwrap(),
+            1,
+            "next report idx should not change on update"
+        );
+
+        assert!(
+            db.get_report_by_idx(1).unwrap().is_none(),
+            "there should be no report at an unassigned idx"
+        );
+    }
+
+    #[test]
+    fn test_challenger_scanned_block_db() {
+        let db = setup_challenge_report_db();
+
+        let mut arb = ArbitraryGenerator::new();
+        let hash: Buf32 = arb.generate();
+        let reorged_hash: Buf32 = arb.generate();
+
+        assert!(
+            db.get_last_scanned_height().unwrap().is_none(),
+            "nothing should be scanned in an empty db"
+        );
+
+        db.put_scanned_block(256, hash).unwrap();
+        db.put_scanned_block(255, hash).unwrap();
+        assert_eq!(db.get_scanned_block(256).unwrap(), Some(hash));
+        assert_eq!(
+            db.get_last_scanned_height().unwrap(),
+            Some(256),
+            "last scanned height should be the highest, not the latest"
+        );
+
+        db.put_scanned_block(256, reorged_hash).unwrap();
+        assert_eq!(
+            db.get_scanned_block(256).unwrap(),
+            Some(reorged_hash),
+            "rescanning a height should replace its block"
+        );
+        assert!(db.get_scanned_block(257).unwrap().is_none());
+    }
+
+    fn setup_challenge_report_db() -> ChallengeReportRocksDb {
+        let (db, config) = get_rocksdb_tmp_instance().unwrap();
+
+        ChallengeReportRocksDb::new(db, config)
+    }
 }
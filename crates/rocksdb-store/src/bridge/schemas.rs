@@ -1,6 +1,6 @@
-use strata_db::entities::bridge_tx_state::BridgeTxState;
+use strata_db::entities::{bridge_tx_state::BridgeTxState, signer_session::SignerSession};
 use strata_primitives::buf::Buf32;
-use strata_state::bridge_duties::BridgeDutyStatus;
+use strata_state::bridge_duties::{BridgeDutyStatus, ChallengeReport};
 
 use crate::{
     define_table_with_default_codec, define_table_with_seek_key_codec, define_table_without_codec,
@@ -17,6 +17,11 @@ define_table_with_default_codec!(
     (BridgeTxStateSchema) Buf32 => BridgeTxState
 );
 
+define_table_with_default_codec!(
+    /// A table to map `Buf32` txids to the open [`SignerSession`] that signs them.
+    (SignerSessionSchema) Buf32 => SignerSession
+);
+
 define_table_with_seek_key_codec!(
     /// A table to store mapping of [`Txid`] to [`Buf32`].
     (BridgeDutyTxidSchema) u64 => Buf32
@@ -27,7 +32,27 @@ define_table_with_default_codec!(
     (BridgeDutyStatusSchema) Buf32 => BridgeDutyStatus
 );
 
+define_table_with_default_codec!(
+    /// A table to map `Buf32` IDs of withdrawal duties to their raw fulfillment transaction.
+    (BridgeDutyFulfillmentSchema) Buf32 => Vec<u8>
+);
+
 define_table_with_default_codec!(
     /// A table to map rocksdb indexes to checkpoints.
     (BridgeDutyCheckpointSchema) u64 => u64
 );
+
+define_table_with_seek_key_codec!(
+    /// A table to store mapping of rocksdb index to the [`Buf32`] id of a report.
+    (ChallengeReportIdxSchema) u64 => Buf32
+);
+
+define_table_with_default_codec!(
+    /// A table to map `Buf32` report ids to [`ChallengeReport`].
+    (ChallengeReportSchema) Buf32 => ChallengeReport
+);
+
+define_table_with_seek_key_codec!(
+    /// A table to map bitcoin block heights to the hashes of the blocks scanned for faults.
+    (ChallengerScannedBlockSchema) u64 => Buf32
+);
@@ -68,6 +68,18 @@ impl ChainstateDatabase for ChainstateDb {
         Ok(())
     }
 
+    fn write_bootstrap_state(
+        &self,
+        idx: u64,
+        toplevel: &strata_state::chain_state::Chainstate,
+    ) -> DbResult<()> {
+        if self.get_first_idx()?.is_some() || self.get_last_idx()?.is_some() {
+            return Err(DbError::OverwriteStateUpdate(idx));
+        }
+        self.db.put::<ChainstateSchema>(&idx, toplevel)?;
+        Ok(())
+    }
+
     fn write_state_update(
         &self,
         idx: u64,
@@ -179,6 +191,30 @@ mod tests {
         assert!(res.is_err_and(|x| matches!(x, DbError::OverwriteStateUpdate(0))));
     }
 
+    #[test]
+    fn test_write_bootstrap_state() {
+        let bootstrap_state: Chainstate = ArbitraryGenerator::new().generate();
+        let db = setup_db();
+
+        db.write_bootstrap_state(10, &bootstrap_state).unwrap();
+
+        let res = db.get_earliest_state_idx();
+        assert!(res.is_ok_and(|x| matches!(x, 10)));
+
+        let res = db.get_last_state_idx();
+        assert!(res.is_ok_and(|x| matches!(x, 10)));
+
+        let res = db.write_bootstrap_state(10, &bootstrap_state);
+        assert!(res.is_err_and(|x| matches!(x, DbError::OverwriteStateUpdate(10))));
+
+        let res = db.write_genesis_state(&bootstrap_state);
+        assert!(res.is_err_and(|x| matches!(x, DbError::OverwriteStateUpdate(0))));
+
+        // Blocks after the snapshot apply on top of it
+        let res = db.write_state_update(11, &WriteBatch::new_empty());
+        assert!(res.is_ok());
+    }
+
     #[test]
     fn test_write_state_update() {
         let db = setup_db();
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use rockbound::{OptimisticTransactionDB, Schema, SchemaDBOperationsExt};
+use rockbound::{OptimisticTransactionDB, Schema, SchemaBatch, SchemaDBOperationsExt};
 use strata_db::{errors::*, traits::*, DbResult};
 use strata_state::operation::*;
 
@@ -63,6 +63,22 @@ impl ClientStateDatabase for ClientStateDb {
         Ok(())
     }
 
+    fn revert_to_idx(&self, idx: u64) -> DbResult<()> {
+        let mut batch = SchemaBatch::new();
+        if let Some(last_idx) = self.get_last_idx::<ClientUpdateOutputSchema>()? {
+            for i in (idx + 1)..=last_idx {
+                batch.delete::<ClientUpdateOutputSchema>(&i)?;
+            }
+        }
+        if let Some(last_idx) = self.get_last_idx::<ClientStateSchema>()? {
+            for i in (idx + 1)..=last_idx {
+                batch.delete::<ClientStateSchema>(&i)?;
+            }
+        }
+        self.db.write_schemas(batch)?;
+        Ok(())
+    }
+
     fn get_last_write_idx(&self) -> DbResult<u64> {
         match self.get_last_idx::<ClientUpdateOutputSchema>()? {
             Some(idx) => Ok(idx),
@@ -253,4 +269,25 @@ mod tests {
         let res = db.get_prev_checkpoint_at(100);
         assert!(res.is_ok_and(|x| matches!(x, 5)));
     }
+
+    #[test]
+    fn test_revert_to_idx() {
+        let state: ClientState = ArbitraryGenerator::new().generate();
+        let db = setup_db();
+
+        for idx in 1..=5 {
+            let output: ClientUpdateOutput = ArbitraryGenerator::new().generate();
+            db.write_client_update_output(idx, output).unwrap();
+        }
+        db.write_client_state_checkpoint(1, state.clone()).unwrap();
+        db.write_client_state_checkpoint(4, state).unwrap();
+
+        db.revert_to_idx(3).unwrap();
+        assert_eq!(db.get_last_write_idx().unwrap(), 3);
+        assert_eq!(db.get_last_checkpoint_idx().unwrap(), 1);
+
+        // Writing carries on right after the new last index.
+        let output: ClientUpdateOutput = ArbitraryGenerator::new().generate();
+        db.write_client_update_output(4, output).unwrap();
+    }
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use rockbound::{
     rocksdb::ReadOptions, schema::KeyEncoder, OptimisticTransactionDB, SchemaBatch,
@@ -14,7 +14,7 @@ use strata_state::l1::L1Tx;
 use tracing::*;
 
 use super::schemas::{L1BlockSchema, MmrSchema, TxnSchema};
-use crate::DbOpsConfig;
+use crate::{utils::get_first_idx, DbOpsConfig};
 
 pub struct L1Db {
     db: Arc<OptimisticTransactionDB>,
@@ -106,8 +106,35 @@ impl L1Database for L1Db {
         Ok(())
     }
 
+    fn purge_block_txs(&self, start_idx: u64, end_idx: u64) -> DbResult<()> {
+        if start_idx >= end_idx {
+            return Err(DbError::Other(
+                "start_idx must be less than end_idx".to_string(),
+            ));
+        }
+
+        let mut options = ReadOptions::default();
+        options.set_iterate_lower_bound(
+            KeyEncoder::<L1BlockSchema>::encode_key(&start_idx)
+                .map_err(|err| DbError::CodecError(err.to_string()))?,
+        );
+        options.set_iterate_upper_bound(
+            KeyEncoder::<L1BlockSchema>::encode_key(&end_idx)
+                .map_err(|err| DbError::CodecError(err.to_string()))?,
+        );
+
+        let mut batch = SchemaBatch::new();
+        for item in self.db.iter_with_opts::<L1BlockSchema>(options)? {
+            let (_, mf) = item?.into_tuple();
+            batch.delete::<TxnSchema>(&mf.block_hash())?;
+        }
+
+        self.db.write_schemas(batch)?;
+        Ok(())
+    }
+
     fn get_tx(&self, tx_ref: L1TxRef) -> DbResult<Option<L1Tx>> {
-        let (block_height, txindex) = tx_ref.into();
+        let (block_height, txindex, op_idx) = tx_ref.into();
         let tx = self
             .db
             .get::<L1BlockSchema>(&block_height)
@@ -117,10 +144,11 @@ impl L1Database for L1Db {
                     // we only save subset of transaction in a block, while the txindex refers to
                     // original position in txblock.
                     // TODO: txs should be hashmap with original index
+                    // A tx carrying several protocol ops is saved once per op, in order.
                     let tx = txs_opt.and_then(|txs| {
-                        txs.iter()
-                            .find(|tx| tx.proof().position() == txindex)
-                            .cloned()
+                        txs.into_iter()
+                            .filter(|tx| tx.proof().position() == txindex)
+                            .nth(op_idx as usize)
                     });
                     Ok(tx)
                 }
@@ -133,6 +161,36 @@ impl L1Database for L1Db {
         self.get_latest_block_number()
     }
 
+    fn get_earliest_txs_idx(&self) -> DbResult<Option<u64>> {
+        let (Some(mut low), Some(tip)) = (
+            get_first_idx::<L1BlockSchema>(&self.db)?,
+            self.get_latest_block_number()?,
+        ) else {
+            return Ok(None);
+        };
+
+        // The txs are always purged from the bottom up, so the blocks that still have them form a
+        // contiguous range ending at the tip.
+        let has_txs = |idx: u64| -> DbResult<bool> {
+            match self.db.get::<L1BlockSchema>(&idx)? {
+                Some(mf) => Ok(self.db.get::<TxnSchema>(&mf.block_hash())?.is_some()),
+                None => Ok(false),
+            }
+        };
+
+        let mut high = tip + 1;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if has_txs(mid)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        Ok(Some(low))
+    }
+
     fn get_block_txs(&self, idx: u64) -> DbResult<Option<Vec<L1TxRef>>> {
         // TODO eventually change how this is stored so we keep a list of the tx
         // indexes with the smaller manifest so we don't have to load all the
@@ -147,9 +205,17 @@ impl L1Database for L1Db {
             return Err(DbError::MissingL1BlockBody(idx));
         };
 
+        // A tx carrying several protocol ops is saved once per op, each gets its own ref.
+        let mut ops_per_position: HashMap<u32, u32> = HashMap::new();
         let txs_refs = txs
             .into_iter()
-            .map(|tx| L1TxRef::from((idx, tx.proof().position())))
+            .map(|tx| {
+                let position = tx.proof().position();
+                let op_idx = ops_per_position.entry(position).or_default();
+                let tx_ref = L1TxRef::from((idx, position, *op_idx));
+                *op_idx += 1;
+                tx_ref
+            })
             .collect::<Vec<L1TxRef>>();
 
         Ok(Some(txs_refs))
@@ -440,6 +506,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_batched_txs() {
+        let db = setup_db();
+        let mut arb = ArbitraryGenerator::new();
+
+        // The tx at position 1 carries two protocol ops
+        let txs: Vec<L1Tx> = [0, 1, 1, 2]
+            .into_iter()
+            .map(|position| {
+                let proof = L1TxProof::new(position, arb.generate());
+                L1Tx::new(proof, arb.generate(), arb.generate())
+            })
+            .collect();
+        db.put_block_data(1, arb.generate(), txs.clone()).unwrap();
+
+        let block_txs = db.get_block_txs(1).unwrap().unwrap();
+        let expected: Vec<L1TxRef> = vec![
+            (1, 0).into(),
+            (1, 1, 0).into(),
+            (1, 1, 1).into(),
+            (1, 2).into(),
+        ];
+        assert_eq!(block_txs, expected);
+
+        for (tx_ref, tx) in block_txs.into_iter().zip(txs) {
+            assert_eq!(
+                db.get_tx(tx_ref).unwrap(),
+                Some(tx),
+                "{tx_ref:?} should be fetched"
+            );
+        }
+        assert_eq!(db.get_tx((1, 1, 2).into()).unwrap(), None);
+    }
+
     #[test]
     fn test_get_chain_tip() {
         let db = setup_db();
@@ -471,6 +571,46 @@ mod tests {
         assert_eq!(block_txs, expected);
     }
 
+    #[test]
+    fn test_purge_block_txs() {
+        let db = setup_db();
+
+        let num_txs = 10;
+        insert_block_data(1, &db, num_txs);
+        insert_block_data(2, &db, num_txs);
+        insert_block_data(3, &db, num_txs);
+
+        assert!(db.purge_block_txs(2, 2).is_err());
+        db.purge_block_txs(1, 3).unwrap();
+
+        // Manifests are kept but the txs are gone
+        for idx in 1..3 {
+            assert!(db.get_block_manifest(idx).unwrap().is_some());
+            assert!(matches!(
+                db.get_block_txs(idx),
+                Err(DbError::MissingL1BlockBody(i)) if i == idx
+            ));
+        }
+        assert_eq!(db.get_block_txs(3).unwrap().unwrap().len(), num_txs);
+    }
+
+    #[test]
+    fn test_get_earliest_txs_idx() {
+        let db = setup_db();
+        assert_eq!(db.get_earliest_txs_idx().unwrap(), None);
+
+        for idx in 1..=5 {
+            insert_block_data(idx, &db, 2);
+        }
+        assert_eq!(db.get_earliest_txs_idx().unwrap(), Some(1));
+
+        db.purge_block_txs(1, 3).unwrap();
+        assert_eq!(db.get_earliest_txs_idx().unwrap(), Some(3));
+
+        db.purge_block_txs(3, 4).unwrap();
+        assert_eq!(db.get_earliest_txs_idx().unwrap(), Some(4));
+    }
+
     #[test]
     fn test_get_blockid_invalid_range() {
         let db = setup_db();
@@ -38,6 +38,7 @@ pub const STORE_COLUMN_FAMILIES: &[ColumnFamilyName] = &[
     // Seqdb schemas
     SeqBlobIdSchema::COLUMN_FAMILY_NAME,
     SeqBlobSchema::COLUMN_FAMILY_NAME,
+    SeqEpochFeesSchema::COLUMN_FAMILY_NAME,
     // Bcast schemas
     BcastL1TxIdSchema::COLUMN_FAMILY_NAME,
     BcastL1TxSchema::COLUMN_FAMILY_NAME,
@@ -47,11 +48,18 @@ pub const STORE_COLUMN_FAMILIES: &[ColumnFamilyName] = &[
     // Bridge signature schemas
     BridgeTxStateTxidSchema::COLUMN_FAMILY_NAME,
     BridgeTxStateSchema::COLUMN_FAMILY_NAME,
+    // Bridge signer session schemas
+    SignerSessionSchema::COLUMN_FAMILY_NAME,
     // Bridge duty schemas
     BridgeDutyTxidSchema::COLUMN_FAMILY_NAME,
     BridgeDutyStatusSchema::COLUMN_FAMILY_NAME,
+    BridgeDutyFulfillmentSchema::COLUMN_FAMILY_NAME,
     // Bridge duty checkpoint
     BridgeDutyCheckpointSchema::COLUMN_FAMILY_NAME,
+    // Challenge report schemas
+    ChallengeReportIdxSchema::COLUMN_FAMILY_NAME,
+    ChallengeReportSchema::COLUMN_FAMILY_NAME,
+    ChallengerScannedBlockSchema::COLUMN_FAMILY_NAME,
     // Checkpoint schemas
     BatchCheckpointSchema::COLUMN_FAMILY_NAME,
     // TODO add col families for other store types
@@ -60,13 +68,15 @@ pub const STORE_COLUMN_FAMILIES: &[ColumnFamilyName] = &[
 use std::{fs, path::Path, sync::Arc};
 
 use bridge::schemas::{
-    BridgeDutyCheckpointSchema, BridgeDutyStatusSchema, BridgeDutyTxidSchema, BridgeTxStateSchema,
-    BridgeTxStateTxidSchema,
+    BridgeDutyCheckpointSchema, BridgeDutyFulfillmentSchema, BridgeDutyStatusSchema,
+    BridgeDutyTxidSchema, BridgeTxStateSchema, BridgeTxStateTxidSchema, ChallengeReportIdxSchema,
+    ChallengeReportSchema, ChallengerScannedBlockSchema, SignerSessionSchema,
 };
 pub const PROVER_COLUMN_FAMILIES: &[ColumnFamilyName] = &[
     SequenceSchema::COLUMN_FAMILY_NAME,
     prover::schemas::ProofSchema::COLUMN_FAMILY_NAME,
     prover::schemas::ProofDepsSchema::COLUMN_FAMILY_NAME,
+    prover::schemas::ProvingTaskSchema::COLUMN_FAMILY_NAME,
 ];
 
 // Re-exports
@@ -90,7 +100,7 @@ use rockbound::{schema::ColumnFamilyName, Schema};
 pub use sequencer::db::RBSeqBlobDb;
 use sequencer::{
     db::SequencerDB,
-    schemas::{SeqBlobIdSchema, SeqBlobSchema},
+    schemas::{SeqBlobIdSchema, SeqBlobSchema, SeqEpochFeesSchema},
 };
 pub use sync_event::db::SyncEventDb;
 
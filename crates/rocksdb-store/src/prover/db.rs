@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
 use rockbound::{OptimisticTransactionDB, SchemaDBOperationsExt, TransactionRetry};
-use strata_db::{errors::DbError, traits::ProofDatabase, DbResult};
+use strata_db::{errors::DbError, traits::ProofDatabase, types::ProvingTaskEntry, DbResult};
 use strata_primitives::proof::{ProofContext, ProofKey};
 use strata_zkvm::ProofReceipt;
 
-use super::schemas::{ProofDepsSchema, ProofSchema};
+use super::schemas::{ProofDepsSchema, ProofSchema, ProvingTaskSchema};
 use crate::DbOpsConfig;
 
 #[derive(Debug, Clone)]
@@ -82,10 +82,44 @@ impl ProofDatabase for ProofDb {
             })
             .map_err(|e| DbError::TransactionError(e.to_string()))
     }
+
+    fn put_task(&self, proof_key: ProofKey, task: ProvingTaskEntry) -> DbResult<()> {
+        Ok(self.db.put::<ProvingTaskSchema>(&proof_key, &task)?)
+    }
+
+    fn get_task(&self, proof_key: ProofKey) -> DbResult<Option<ProvingTaskEntry>> {
+        Ok(self.db.get::<ProvingTaskSchema>(&proof_key)?)
+    }
+
+    fn del_task(&self, proof_key: ProofKey) -> DbResult<bool> {
+        self.db
+            .with_optimistic_txn(TransactionRetry::Count(self.ops.retry_count), |tx| {
+                if tx.get::<ProvingTaskSchema>(&proof_key)?.is_none() {
+                    return Ok(false);
+                }
+                tx.delete::<ProvingTaskSchema>(&proof_key)?;
+
+                Ok::<_, anyhow::Error>(true)
+            })
+            .map_err(|e| DbError::TransactionError(e.to_string()))
+    }
+
+    fn get_all_tasks(&self) -> DbResult<Vec<(ProofKey, ProvingTaskEntry)>> {
+        let mut iterator = self.db.iter::<ProvingTaskSchema>()?;
+        iterator.seek_to_first();
+
+        let mut tasks = Vec::new();
+        for res in iterator {
+            tasks.push(res?.into_tuple());
+        }
+
+        Ok(tasks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use strata_db::types::ProvingTaskStatus;
     use strata_primitives::{
         buf::Buf32,
         proof::{ProofContext, ProofZkVm},
@@ -217,4 +251,38 @@ mod tests {
             "Nonexistent proof deps should return None"
         );
     }
+
+    #[test]
+    fn test_put_and_get_tasks() {
+        let db = setup_db();
+
+        let (proof_key, _) = generate_proof();
+        let dep_key = ProofKey::new(
+            ProofContext::BtcBlockspace(Buf32::from([1u8; 32]).into()),
+            ProofZkVm::Native,
+        );
+
+        let task =
+            ProvingTaskEntry::new(ProvingTaskStatus::WaitingForDependencies, vec![dep_key], 1);
+        db.put_task(proof_key, task.clone()).unwrap();
+        assert_eq!(db.get_task(proof_key).unwrap(), Some(task.clone()));
+
+        // Updating an existing task overwrites it.
+        let mut updated_task = task;
+        updated_task.status = ProvingTaskStatus::Pending;
+        updated_task.pending_deps.clear();
+        updated_task.updated_at = 2;
+        db.put_task(proof_key, updated_task.clone()).unwrap();
+
+        let tasks = db.get_all_tasks().unwrap();
+        assert_eq!(tasks, vec![(proof_key, updated_task)]);
+
+        let res = db.del_task(proof_key);
+        assert!(matches!(res, Ok(true)));
+
+        let res = db.del_task(proof_key);
+        assert!(matches!(res, Ok(false)));
+
+        assert!(db.get_all_tasks().unwrap().is_empty());
+    }
 }
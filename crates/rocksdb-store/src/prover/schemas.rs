@@ -1,3 +1,4 @@
+use strata_db::types::ProvingTaskEntry;
 use strata_primitives::proof::{ProofContext, ProofKey};
 use strata_zkvm::ProofReceipt;
 
@@ -12,3 +13,8 @@ define_table_with_default_codec!(
     /// A table to store dependencies of a proof context
     (ProofDepsSchema) ProofContext => Vec<ProofContext>
 );
+
+define_table_with_default_codec!(
+    /// A table to store ProofKey -> ProvingTaskEntry mapping
+    (ProvingTaskSchema) ProofKey => ProvingTaskEntry
+);
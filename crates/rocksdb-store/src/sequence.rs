@@ -1,4 +1,4 @@
-use rockbound::{Schema, TransactionCtx, TransactionDBMarker};
+use rockbound::{Schema, SchemaBatch, TransactionCtx, TransactionDBMarker};
 
 use crate::{define_table_with_default_codec, define_table_without_codec, impl_borsh_value_codec};
 
@@ -36,3 +36,18 @@ pub(crate) fn get_next_id_opts<S: Schema, DB: TransactionDBMarker>(
     txn.put::<SequenceSchema>(&index_key, &next_idx)?;
     Ok(next_idx)
 }
+
+/// Rewinds the sequence of the given `Schema` after the entries past `last_idx` have been
+/// deleted, so the ids handed out next follow `last_idx` again.  With `None` the sequence starts
+/// over from its starting index.
+pub(crate) fn rewind_id<S: Schema>(
+    batch: &mut SchemaBatch,
+    last_idx: Option<u64>,
+) -> anyhow::Result<()> {
+    let index_key = S::COLUMN_FAMILY_NAME.as_bytes().to_vec();
+    match last_idx {
+        Some(idx) => batch.put::<SequenceSchema>(&index_key, &idx)?,
+        None => batch.delete::<SequenceSchema>(&index_key)?,
+    }
+    Ok(())
+}
@@ -9,7 +9,7 @@ use strata_db::{
 };
 use strata_primitives::buf::Buf32;
 
-use super::schemas::{SeqBlobIdSchema, SeqBlobSchema};
+use super::schemas::{SeqBlobIdSchema, SeqBlobSchema, SeqEpochFeesSchema};
 use crate::{sequence::get_next_id, DbOpsConfig};
 
 pub struct RBSeqBlobDb {
@@ -59,6 +59,14 @@ impl BlobDatabase for RBSeqBlobDb {
     fn get_blob_id(&self, blobidx: u64) -> DbResult<Option<Buf32>> {
         Ok(self.db.get::<SeqBlobIdSchema>(&blobidx)?)
     }
+
+    fn put_epoch_fees(&self, epoch: u64, fees: u64) -> DbResult<()> {
+        Ok(self.db.put::<SeqEpochFeesSchema>(&epoch, &fees)?)
+    }
+
+    fn get_epoch_fees(&self, epoch: u64) -> DbResult<Option<u64>> {
+        Ok(self.db.get::<SeqEpochFeesSchema>(&epoch)?)
+    }
 }
 
 pub struct SequencerDB<D> {
@@ -183,4 +191,17 @@ mod tests {
         let last_blob_idx = seq_db.get_last_blob_idx().unwrap();
         assert_eq!(last_blob_idx, Some(1));
     }
+
+    #[test]
+    fn test_epoch_fees() {
+        let (db, db_ops) = get_rocksdb_tmp_instance().unwrap();
+        let seq_db = RBSeqBlobDb::new(db, db_ops);
+
+        assert_eq!(seq_db.get_epoch_fees(1).unwrap(), None);
+
+        seq_db.put_epoch_fees(1, 600).unwrap();
+        seq_db.put_epoch_fees(1, 1_200).unwrap();
+        assert_eq!(seq_db.get_epoch_fees(1).unwrap(), Some(1_200));
+        assert_eq!(seq_db.get_epoch_fees(2).unwrap(), None);
+    }
 }
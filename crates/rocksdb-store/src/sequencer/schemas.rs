@@ -15,3 +15,8 @@ define_table_with_default_codec!(
     /// A table to store blobid -> blob mapping
     (SeqBlobSchema) Buf32 => BlobEntry
 );
+
+define_table_with_seek_key_codec!(
+    /// A table to store epoch -> fees spent on inscriptions mapping
+    (SeqEpochFeesSchema) u64 => u64
+);
@@ -5,7 +5,11 @@ use strata_db::{errors::DbError, traits::SyncEventDatabase, DbResult};
 use strata_state::sync_event::SyncEvent;
 
 use super::schemas::{SyncEventSchema, SyncEventWithTimestamp};
-use crate::{sequence::get_next_id_opts, DbOpsConfig};
+use crate::{
+    sequence::{get_next_id_opts, rewind_id},
+    utils::get_first_idx,
+    DbOpsConfig,
+};
 
 pub struct SyncEventDb {
     db: Arc<OptimisticTransactionDB>,
@@ -90,10 +94,29 @@ impl SyncEventDatabase for SyncEventDb {
         Ok(())
     }
 
+    fn revert_to_idx(&self, idx: u64) -> DbResult<()> {
+        let Some(last_key) = self.get_last_key()? else {
+            return Ok(());
+        };
+
+        let mut batch = SchemaBatch::new();
+        for id in (idx + 1)..=last_key {
+            batch.delete::<SyncEventSchema>(&id)?;
+        }
+        // Events are numbered from 1, so there's no event left to follow when reverting to 0.
+        rewind_id::<SyncEventSchema>(&mut batch, Some(idx).filter(|idx| *idx > 0))?;
+        self.db.write_schemas(batch)?;
+        Ok(())
+    }
+
     fn get_last_idx(&self) -> DbResult<Option<u64>> {
         self.get_last_key()
     }
 
+    fn get_first_idx(&self) -> DbResult<Option<u64>> {
+        get_first_idx::<SyncEventSchema>(&self.db)
+    }
+
     fn get_sync_event(&self, idx: u64) -> DbResult<Option<SyncEvent>> {
         let event = self.db.get::<SyncEventSchema>(&idx)?;
         match event {
@@ -227,4 +250,37 @@ mod tests {
         let new_idx = db.get_last_idx().unwrap().unwrap();
         assert_eq!(new_idx, 5);
     }
+
+    #[test]
+    fn test_get_first_idx() {
+        let db = setup_db();
+        assert_eq!(db.get_first_idx().unwrap(), None);
+
+        let n = 5;
+        for _ in 1..=n {
+            let _ = insert_event(&db);
+        }
+        assert_eq!(db.get_first_idx().unwrap(), Some(1));
+
+        db.clear_sync_event(1, 3).unwrap();
+        assert_eq!(db.get_first_idx().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_revert_to_idx() {
+        let db = setup_db();
+        let n = 5;
+        for _ in 1..=n {
+            let _ = insert_event(&db);
+        }
+
+        db.revert_to_idx(3).unwrap();
+        assert_eq!(db.get_last_idx().unwrap(), Some(3));
+        assert!(db.get_sync_event(3).unwrap().is_some());
+        assert!(db.get_sync_event(4).unwrap().is_none());
+
+        // The next event takes the index right after the new last one.
+        let _ = insert_event(&db);
+        assert_eq!(db.get_last_idx().unwrap(), Some(4));
+    }
 }
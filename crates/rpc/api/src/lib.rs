@@ -1,12 +1,15 @@
 //! Macro trait def for the `strata_` RPC namespace using jsonrpsee.
 use bitcoin::Txid;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    proc_macros::rpc,
+};
 use strata_db::types::{L1TxEntry, L1TxStatus};
 use strata_primitives::bridge::{OperatorIdx, PublickeyTable};
 use strata_rpc_types::{
     types::{RpcBlockHeader, RpcClientStatus, RpcL1Status},
-    HexBytes, HexBytes32, L2BlockStatus, RpcBridgeDuties, RpcChainState, RpcCheckpointInfo,
-    RpcDepositEntry, RpcExecUpdate, RpcSyncStatus,
+    HexBytes, HexBytes32, L2BlockStatus, RpcBridgeDuties, RpcBridgeRelayStats, RpcChainState,
+    RpcCheckpointInfo, RpcDepositEntry, RpcExecUpdate, RpcSyncStatus,
 };
 use strata_state::{
     block::L2Block, client_state::ClientState, id::L2BlockId, operation::ClientUpdateOutput,
@@ -69,6 +72,11 @@ pub trait StrataApi {
     #[method(name = "getRawBundleById")]
     async fn get_raw_bundle_by_id(&self, block_id: L2BlockId) -> RpcResult<Option<HexBytes>>;
 
+    /// Get the borsh-encoded chainstate after the last L2 block of the checkpoint for `epoch`, if
+    /// the checkpoint is known. New nodes start syncing from it instead of genesis.
+    #[method(name = "getChainstateSnapshot")]
+    async fn get_chainstate_snapshot(&self, epoch: u64) -> RpcResult<Option<HexBytes>>;
+
     /// Get message by scope, Currently either Deposit or Withdrawal
     #[method(name = "getBridgeMsgsByScope")]
     async fn get_msgs_by_scope(&self, scope: HexBytes) -> RpcResult<Vec<HexBytes>>;
@@ -77,6 +85,10 @@ pub trait StrataApi {
     #[method(name = "submitBridgeMsg")]
     async fn submit_bridge_msg(&self, raw_msg: HexBytes) -> RpcResult<()>;
 
+    /// Get the counters of the bridge messages this node relayed and dropped, per operator.
+    #[method(name = "getBridgeRelayStats")]
+    async fn get_bridge_relay_stats(&self) -> RpcResult<RpcBridgeRelayStats>;
+
     /// Get the [`RpcBridgeDuties`] from a certain `start_index` for a given [`OperatorIdx`].
     ///
     /// The `start_index` is a monotonically increasing number with no gaps. So, it is safe to call
@@ -101,6 +113,11 @@ pub trait StrataApi {
     #[method(name = "getActiveOperatorChainPubkeySet")]
     async fn get_active_operator_chain_pubkey_set(&self) -> RpcResult<PublickeyTable>;
 
+    /// Get the public key table of the operators that were removed from the active set but may
+    /// still be notaries of deposits, and so still sign the transactions spending them.
+    #[method(name = "getRetiredOperatorChainPubkeySet")]
+    async fn get_retired_operator_chain_pubkey_set(&self) -> RpcResult<PublickeyTable>;
+
     /// Get latest checkpoint info
     #[method(name = "getLatestCheckpointIndex")]
     async fn get_latest_checkpoint_index(&self) -> RpcResult<Option<u64>>;
@@ -126,6 +143,61 @@ pub trait StrataApi {
     /// Gets the client update output produced as a result of the sync event idx given.
     #[method(name = "getClientUpdateOutput")]
     async fn get_client_update_output(&self, idx: u64) -> RpcResult<Option<ClientUpdateOutput>>;
+
+    /// Subscribe to the headers of the blocks added to the L2 chain.
+    ///
+    /// The header of the current chain tip is sent as soon as the subscription starts. After a
+    /// reorg, the headers of the new chain are sent from the fork point onwards.
+    #[subscription(
+        name = "subscribeNewHeaders" => "newHeaders",
+        unsubscribe = "unsubscribeNewHeaders",
+        item = RpcBlockHeader
+    )]
+    async fn subscribe_new_headers(&self) -> SubscriptionResult;
+
+    /// Subscribe to the [`RpcClientStatus`] produced by every new client state.
+    #[subscription(
+        name = "subscribeClientStatus" => "clientStatus",
+        unsubscribe = "unsubscribeClientStatus",
+        item = RpcClientStatus
+    )]
+    async fn subscribe_client_status(&self) -> SubscriptionResult;
+
+    /// Subscribe to the checkpoints as they get verified on L1.
+    #[subscription(
+        name = "subscribeCheckpoints" => "checkpoints",
+        unsubscribe = "unsubscribeCheckpoints",
+        item = RpcCheckpointInfo
+    )]
+    async fn subscribe_checkpoints(&self) -> SubscriptionResult;
+
+    /// Subscribe to the [`RpcBridgeDuties`] for a given [`OperatorIdx`] from a certain
+    /// `start_index`.
+    ///
+    /// A notification is sent whenever there are new deposit duties or the set of withdrawal duties
+    /// changes. Every notification carries the full set of withdrawal duties and the deposit
+    /// duties since the `stop_index` of the previous notification.
+    #[subscription(
+        name = "subscribeBridgeDuties" => "bridgeDuties",
+        unsubscribe = "unsubscribeBridgeDuties",
+        item = RpcBridgeDuties
+    )]
+    async fn subscribe_bridge_duties(
+        &self,
+        operator_idx: OperatorIdx,
+        start_index: u64,
+    ) -> SubscriptionResult;
+
+    /// Subscribe to the raw messages with the given scope as the relayer accepts them.
+    ///
+    /// Only the messages accepted after the subscription has started are sent, so subscribers
+    /// should query `getBridgeMsgsByScope` once subscribed to catch up.
+    #[subscription(
+        name = "subscribeBridgeMsgs" => "bridgeMsgs",
+        unsubscribe = "unsubscribeBridgeMsgs",
+        item = HexBytes
+    )]
+    async fn subscribe_bridge_msgs(&self, scope: HexBytes) -> SubscriptionResult;
 }
 
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "strataadmin"))]
@@ -164,6 +236,8 @@ pub trait StrataSequencerApi {
     #[method(name = "strataadmin_broadcastRawTx")]
     async fn broadcast_raw_tx(&self, rawtx: HexBytes) -> RpcResult<Txid>;
 
+    /// Get the status of a tx submitted to the broadcaster, following its RBF replacements if its
+    /// fee was bumped
     #[method(name = "strata_getTxStatus")]
     async fn get_tx_status(&self, txid: HexBytes32) -> RpcResult<Option<L1TxStatus>>;
 }
@@ -4,9 +4,15 @@
 //! decomposed into various groups partly based on how bitcoin RPCs are categorized into various
 //! [groups](https://developer.bitcoin.org/reference/rpc/index.html).
 
-use bitcoin::Txid;
+use bitcoin::{secp256k1::PublicKey, Txid};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use strata_state::bridge_duties::BridgeDutyStatus;
+use musig2::AggNonce;
+use strata_primitives::{
+    bridge::{Musig2PartialSig, Musig2PubNonce, PublickeyTable},
+    buf::Buf32,
+    relay::types::BridgeMessage,
+};
+use strata_state::bridge_duties::{BridgeDutyStatus, ChallengeReport};
 
 /// RPCs related to information about the client itself.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "stratabridge"))]
@@ -20,6 +26,15 @@ pub trait StrataBridgeControlApi {
     /// increasing.
     #[method(name = "uptime")]
     async fn get_uptime(&self) -> RpcResult<u64>;
+
+    /// Get the [`ChallengeReport`]s recorded by a client running in challenger mode starting from
+    /// a given `start_idx` (inclusive).
+    ///
+    /// The `start_idx` is a monotonically increasing number with no gaps. So, it is safe to call
+    /// this method with any `u64` value. If there are no reports at or after `start_idx`, an empty
+    /// list is returned. The number of reports returned in a single call is capped by the server.
+    #[method(name = "getChallengeReports")]
+    async fn get_challenge_reports(&self, start_idx: u64) -> RpcResult<Vec<ChallengeReport>>;
 }
 
 /// RPCs related to network information including healthcheck, node addresses, etc.
@@ -41,3 +56,41 @@ pub trait StrataBridgeTrackerApi {
 
     // TODO: add other duty RPCs as necessary (for example, `pendingDuties`, `executedDuties`, etc.)
 }
+
+/// RPCs of a signing service that holds an operator's key on behalf of the bridge client.
+///
+/// These are meant to be served over a Unix socket that only the bridge client can access.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "stratasigner"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "stratasigner"))]
+pub trait StrataBridgeSignerApi {
+    /// Get the public key of the operator.
+    #[method(name = "getPubkey")]
+    async fn get_pubkey(&self) -> RpcResult<PublicKey>;
+
+    /// Open the MuSig2 session that signs the transaction with the given [`Txid`] under the
+    /// `aggregated_pubkey` and get its public nonce.
+    ///
+    /// The secret nonce stays with the signer until the session is closed by signing.
+    #[method(name = "openSession")]
+    async fn open_session(
+        &self,
+        txid: Txid,
+        aggregated_pubkey: PublicKey,
+    ) -> RpcResult<Musig2PubNonce>;
+
+    /// Produce the operator's MuSig2 partial signature of the 32-byte sighash `message` and close
+    /// the session of the transaction with the given [`Txid`].
+    #[method(name = "signPartial")]
+    async fn sign_partial(
+        &self,
+        txid: Txid,
+        pubkey_table: PublickeyTable,
+        aggregated_nonce: AggNonce,
+        message: Buf32,
+        keypath_spend_only: bool,
+    ) -> RpcResult<Musig2PartialSig>;
+
+    /// Sign an unsigned [`BridgeMessage`].
+    #[method(name = "signBridgeMsg")]
+    async fn sign_bridge_msg(&self, message: BridgeMessage) -> RpcResult<BridgeMessage>;
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use strata_primitives::{buf::Buf32, l2::L2BlockId};
-use strata_rpc_types::ProofKey;
+use strata_rpc_types::{ProofKey, RpcProvingTask, RpcProvingTaskFilter};
 use strata_state::l1::L1BlockId;
 use strata_zkvm::ProofReceipt;
 
@@ -66,4 +66,16 @@ pub trait StrataProverClientApi {
     /// Get report of the current prover-client
     #[method(name = "getReport")]
     async fn get_report(&self) -> RpcResult<HashMap<String, usize>>;
+
+    /// Retry the failed or cancelled task of `key`
+    #[method(name = "retryTask")]
+    async fn retry_task(&self, key: ProofKey) -> RpcResult<()>;
+
+    /// Cancel the unfinished task of `key`
+    #[method(name = "cancelTask")]
+    async fn cancel_task(&self, key: ProofKey) -> RpcResult<()>;
+
+    /// List the tasks that match the given `filter`
+    #[method(name = "listTasks")]
+    async fn list_tasks(&self, filter: RpcProvingTaskFilter) -> RpcResult<Vec<RpcProvingTask>>;
 }
@@ -55,6 +55,10 @@ pub enum RpcServerError {
     #[error("Invalid proof for checkpoint {0}: {1}")]
     InvalidProof(u64, String),
 
+    /// The requested data was deleted by pruning.
+    #[error("{0} at index {1} has been pruned")]
+    Pruned(&'static str, u64),
+
     /// Generic internal error message.  If this is used often it should be made
     /// into its own error type.
     #[error("{0}")]
@@ -83,6 +87,7 @@ impl RpcServerError {
             Self::MissingCheckpointInDb(_) => -32610,
             Self::ProofAlreadyCreated(_) => -32611,
             Self::InvalidProof(_, _) => -32612,
+            Self::Pruned(_, _) => -32613,
             Self::BlockingAbort(_) => -32001,
             Self::Other(_) => -32000,
             Self::OtherEx(_, _) => -32000,
@@ -6,16 +6,19 @@
 
 use bitcoin::{Network, Txid};
 use serde::{Deserialize, Serialize};
+use strata_db::types::{ProvingTaskEntry, ProvingTaskStatus};
 use strata_primitives::{
     bridge::OperatorIdx,
     l1::{BitcoinAmount, L1TxRef, OutputRef},
     prelude::L1Status,
+    proof::{ProofContextKind, ProofKey, ProofZkVm},
 };
 use strata_state::{
     batch::BatchInfo,
     bridge_duties::BridgeDuty,
     bridge_ops::WithdrawalIntent,
     bridge_state::{DepositEntry, DepositState},
+    exec_update::ELForcedInclusionData,
     id::L2BlockId,
 };
 
@@ -86,6 +89,9 @@ pub struct RpcL1Status {
     /// UNIX millis time of the last time we got a new update from the L1 connector.
     pub last_update: u64,
 
+    /// Why the writer is currently refusing to inscribe blobs, if it is.
+    pub writer_error: Option<String>,
+
     /// Underlying network.
     pub network: Network,
 }
@@ -100,6 +106,7 @@ impl RpcL1Status {
             last_published_txid: l1s.last_published_txid.map(Into::into),
             published_inscription_count: l1s.published_inscription_count,
             last_update: l1s.last_update,
+            writer_error: l1s.writer_error,
             network,
         }
     }
@@ -115,6 +122,7 @@ impl Default for RpcL1Status {
             last_published_txid: Default::default(),
             published_inscription_count: Default::default(),
             last_update: Default::default(),
+            writer_error: Default::default(),
             network: Network::Regtest,
         }
     }
@@ -206,6 +214,9 @@ pub struct RpcExecUpdate {
     /// only set near the end of the range of blocks in a batch since we only
     /// assert these in a per-batch frequency.
     pub da_blobs: Vec<DaBlob>,
+
+    /// Forced inclusions processed by the update.
+    pub forced_inclusions: Vec<ELForcedInclusionData>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -268,6 +279,49 @@ pub struct RpcBridgeDuties {
     pub stop_index: u64,
 }
 
+/// Counters of the bridge messages relayed and dropped by the node's relayer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcBridgeRelayStats {
+    /// Counters for the messages from each operator, ordered by [`OperatorIdx`].
+    pub operators: Vec<RpcOperatorRelayStats>,
+
+    /// Counters for the messages whose source isn't verified, such as misc messages.
+    pub unauthenticated: RpcRelayCounters,
+
+    /// Messages dropped because their signature was invalid.
+    pub invalid: u64,
+
+    /// Messages dropped because they could not be verified before the node was synced.
+    pub unverified: u64,
+}
+
+/// Counters of the bridge messages relayed and dropped for an operator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcOperatorRelayStats {
+    /// The operator the messages came from.
+    pub operator_idx: OperatorIdx,
+
+    /// The counters of the messages from the operator.
+    #[serde(flatten)]
+    pub counters: RpcRelayCounters,
+}
+
+/// Counters of the bridge messages relayed and dropped from a single source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRelayCounters {
+    /// Messages relayed.
+    pub relayed: u64,
+
+    /// Messages dropped because the operator or the scope was over its quota.
+    pub over_quota: u64,
+
+    /// Messages dropped because their payload was too large.
+    pub oversized: u64,
+
+    /// Times a message was held back because it was for a transaction of no pending duty yet.
+    pub unknown_duty: u64,
+}
+
 /// Deposit entry for RPC corresponding to [`DepositEntry`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcDepositEntry {
@@ -306,6 +360,26 @@ impl RpcDepositEntry {
             state: ent.deposit_state().clone(),
         }
     }
+
+    pub fn deposit_idx(&self) -> u32 {
+        self.deposit_idx
+    }
+
+    pub fn output(&self) -> &OutputRef {
+        &self.output
+    }
+
+    pub fn notary_operators(&self) -> &[OperatorIdx] {
+        &self.notary_operators
+    }
+
+    pub fn amt(&self) -> BitcoinAmount {
+        self.amt
+    }
+
+    pub fn state(&self) -> &DepositState {
+        &self.state
+    }
 }
 
 /// status of L2 Block
@@ -331,3 +405,71 @@ pub struct RpcChainState {
 
     pub cur_epoch: u64,
 }
+
+/// A proving task tracked by the prover client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcProvingTask {
+    /// The key of the proof being generated.
+    pub key: ProofKey,
+
+    /// The current status of the task.
+    pub status: ProvingTaskStatus,
+
+    /// Dependencies of the task that have not been proven yet.
+    pub pending_deps: Vec<ProofKey>,
+
+    /// Number of times the proving of this task has been started.
+    pub attempts: u32,
+
+    /// Reason for the last failure of the task, if any.
+    pub failure_reason: Option<String>,
+
+    /// Time at which the task was created, in milliseconds since the UNIX epoch.
+    pub created_at: u64,
+
+    /// Time at which the task was last updated, in milliseconds since the UNIX epoch.
+    pub updated_at: u64,
+
+    /// Time at which a failed task is to be retried, in milliseconds since the UNIX epoch.
+    pub retry_at: Option<u64>,
+}
+
+impl RpcProvingTask {
+    pub fn new(key: ProofKey, task: ProvingTaskEntry) -> Self {
+        Self {
+            key,
+            status: task.status,
+            pending_deps: task.pending_deps,
+            attempts: task.attempts,
+            failure_reason: task.failure_reason,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            retry_at: task.retry_at,
+        }
+    }
+}
+
+/// Filter for the proving tasks to list. Tasks match if they match all the fields that are set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RpcProvingTaskFilter {
+    /// Only include the tasks with this status.
+    #[serde(default)]
+    pub status: Option<ProvingTaskStatus>,
+
+    /// Only include the tasks proving this kind of proof context.
+    #[serde(default)]
+    pub kind: Option<ProofContextKind>,
+
+    /// Only include the tasks running on this zkVM.
+    #[serde(default)]
+    pub host: Option<ProofZkVm>,
+}
+
+impl RpcProvingTaskFilter {
+    /// Checks whether the task with the given key and status matches the filter.
+    pub fn matches(&self, key: &ProofKey, status: &ProvingTaskStatus) -> bool {
+        self.status.as_ref().is_none_or(|s| s == status)
+            && self.kind.is_none_or(|kind| kind == key.context().kind())
+            && self.host.as_ref().is_none_or(|host| host == key.host())
+    }
+}
@@ -1,5 +1,5 @@
 use strata_crypto::verify_schnorr_sig;
-use strata_primitives::{block_credential::CredRule, hash, params::RollupParams};
+use strata_primitives::{hash, params::RollupParams};
 use tracing::warn;
 
 use crate::{
@@ -29,8 +29,9 @@ pub fn validate_block_segments(block: &L2Block) -> bool {
 
 pub fn check_block_credential(header: &SignedL2BlockHeader, rollup_params: &RollupParams) -> bool {
     let sigcom = header.header().get_sighash();
-    match &rollup_params.cred_rule {
-        CredRule::Unchecked => true,
-        CredRule::SchnorrKey(pubkey) => verify_schnorr_sig(header.sig(), &sigcom, pubkey),
-    }
+    rollup_params
+        .cred_rule
+        .verify_signer_at(header.blockidx(), |pubkey| {
+            verify_schnorr_sig(header.sig(), &sigcom, pubkey)
+        })
 }
@@ -3,11 +3,18 @@
 use arbitrary::Arbitrary;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use strata_bridge_tx_builder::prelude::{CooperativeWithdrawalInfo, DepositInfo};
-use strata_primitives::bridge::OperatorIdx;
+use strata_bridge_tx_builder::prelude::{
+    CooperativeWithdrawalInfo, DepositInfo, ReimbursementInfo,
+};
+use strata_primitives::{
+    bridge::{BitcoinBlockHeight, OperatorIdx},
+    buf::Buf32,
+    hash::compute_borsh_hash,
+    l1::{BitcoinTxid, OutputRef},
+};
 
 /// The various duties that can be assigned to an operator.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", content = "payload")]
 pub enum BridgeDuty {
     /// The duty to create and sign a Deposit Transaction so as to move funds from the user to the
@@ -22,10 +29,17 @@ pub enum BridgeDuty {
     /// and the [`crate::bridge_state::DepositState`] transitions to
     /// [`crate::bridge_state::DepositState::Dispatched`].
     ///
-    /// This kicks off the withdrawal process which involves cooperative signing by the operator
-    /// set, or a more involved unilateral withdrawal process (in the future) if not all operators
-    /// cooperate in the process.
+    /// Only the assigned operator acts on this duty, by paying out the withdrawal from its own
+    /// wallet. It is then reimbursed from the deposit via [`Self::ReimburseOperator`].
     FulfillWithdrawal(CooperativeWithdrawalInfo),
+
+    /// The duty to sign a transaction that sweeps a deposit to the operator that fulfilled the
+    /// withdrawal assigned against it.
+    ///
+    /// This duty is created when the fulfillment of a withdrawal is seen on chain and the
+    /// [`crate::bridge_state::DepositState`] transitions to
+    /// [`crate::bridge_state::DepositState::Fulfilled`], and applies to all operators.
+    ReimburseOperator(ReimbursementInfo),
 }
 
 impl From<DepositInfo> for BridgeDuty {
@@ -40,6 +54,12 @@ impl From<CooperativeWithdrawalInfo> for BridgeDuty {
     }
 }
 
+impl From<ReimbursementInfo> for BridgeDuty {
+    fn from(value: ReimbursementInfo) -> Self {
+        Self::ReimburseOperator(value)
+    }
+}
+
 /// The various states a bridge duty may be in.
 ///
 /// The full state transition looks as follows:
@@ -124,3 +144,80 @@ impl BridgeDutyStatus {
         matches!(self, BridgeDutyStatus::Executed)
     }
 }
+
+/// The kinds of operator misbehaviour that a challenger can detect.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Arbitrary, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OperatorFault {
+    /// The operator assigned to a [`BridgeDuty::FulfillWithdrawal`] did not pay out the withdrawal
+    /// to the user before the `exec_deadline` in the
+    /// [`DispatchedState`](crate::bridge_state::DispatchedState) elapsed.
+    MissedWithdrawalDeadline {
+        /// The index of the operator that was assigned the withdrawal.
+        assignee: OperatorIdx,
+
+        /// The bitcoin block height before which the withdrawal had to be fulfilled.
+        exec_deadline: BitcoinBlockHeight,
+    },
+
+    /// The deposit UTXO was spent by a transaction that does not reimburse the operator for a
+    /// withdrawal fulfilled on bitcoin.
+    UnauthorizedDepositSpend {
+        /// The transaction that spent the deposit UTXO.
+        spending_txid: BitcoinTxid,
+    },
+}
+
+/// A finding produced by a challenger when it observes an operator misbehaving with respect to a
+/// deposit UTXO.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Arbitrary, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct ChallengeReport {
+    /// The deposit UTXO that the fault relates to.
+    deposit_outpoint: OutputRef,
+
+    /// The fault that was observed.
+    fault: OperatorFault,
+
+    /// The bitcoin block height at which the fault was detected.
+    detected_at: BitcoinBlockHeight,
+}
+
+impl ChallengeReport {
+    /// Creates a new [`ChallengeReport`].
+    pub fn new(
+        deposit_outpoint: OutputRef,
+        fault: OperatorFault,
+        detected_at: BitcoinBlockHeight,
+    ) -> Self {
+        Self {
+            deposit_outpoint,
+            fault,
+            detected_at,
+        }
+    }
+
+    /// Computes the id of the report, which commits to the deposit UTXO and the fault but not to
+    /// the height at which it was detected so that the same fault is only reported once.
+    pub fn id(&self) -> Buf32 {
+        compute_borsh_hash(&(&self.deposit_outpoint, &self.fault))
+    }
+
+    /// Gets the deposit UTXO that the fault relates to.
+    pub fn deposit_outpoint(&self) -> &OutputRef {
+        &self.deposit_outpoint
+    }
+
+    /// Gets the fault that was observed.
+    pub fn fault(&self) -> &OperatorFault {
+        &self.fault
+    }
+
+    /// Gets the bitcoin block height at which the fault was detected.
+    pub fn detected_at(&self) -> BitcoinBlockHeight {
+        self.detected_at
+    }
+}
@@ -7,9 +7,6 @@ use strata_primitives::{
     l1::{BitcoinAmount, XOnlyPk},
 };
 
-// TODO make this not hardcoded!
-pub const WITHDRAWAL_DENOMINATION: BitcoinAmount = BitcoinAmount::from_int_btc(10);
-
 /// Describes an intent to withdraw that hasn't been dispatched yet.
 #[derive(Clone, Debug, Eq, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 pub struct WithdrawalIntent {
@@ -41,7 +38,8 @@ impl WithdrawalIntent {
 /// Set of withdrawals that are assigned to a deposit bridge utxo.
 #[derive(Clone, Debug, Eq, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 pub struct WithdrawalBatch {
-    /// A series of [WithdrawalIntent]'s who sum does not exceed [`WITHDRAWAL_DENOMINATION`].
+    /// A series of [WithdrawalIntent]'s whose sum does not exceed the amount of the deposit the
+    /// batch is assigned to.
     intents: Vec<WithdrawalIntent>,
 }
 
@@ -51,6 +49,32 @@ impl WithdrawalBatch {
         Self { intents }
     }
 
+    /// Packs the longest prefix of the intents whose total value does not exceed `max_value` into a
+    /// batch.
+    ///
+    /// The intents are taken in order so that earlier withdrawals are never starved by later ones.
+    pub fn pack(intents: &[WithdrawalIntent], max_value: BitcoinAmount) -> Self {
+        let mut total = 0;
+        let intents = intents
+            .iter()
+            .take_while(|wi| {
+                total += wi.amt.to_sat();
+                total <= max_value.to_sat()
+            })
+            .cloned()
+            .collect();
+
+        Self { intents }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intents.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.intents.len()
+    }
+
     /// Gets the total value of the batch.  This must be less than the size of
     /// the utxo it's assigned to.
     pub fn get_total_value(&self) -> BitcoinAmount {
@@ -89,3 +113,36 @@ impl DepositIntent {
         &self.dest_ident
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_primitives::buf::Buf32;
+
+    use super::*;
+
+    fn intent(sats: u64) -> WithdrawalIntent {
+        WithdrawalIntent::new(BitcoinAmount::from_sat(sats), XOnlyPk::new(Buf32::zero()))
+    }
+
+    #[test]
+    fn test_pack_withdrawal_batch() {
+        let intents = [intent(4), intent(5), intent(2), intent(1)];
+
+        let batch = WithdrawalBatch::pack(&intents, BitcoinAmount::from_sat(10));
+        assert_eq!(
+            batch.intents(),
+            &intents[..2],
+            "must stop at the first intent that overflows"
+        );
+        assert_eq!(batch.get_total_value(), BitcoinAmount::from_sat(9));
+
+        let batch = WithdrawalBatch::pack(&intents, BitcoinAmount::from_sat(3));
+        assert!(
+            batch.is_empty(),
+            "intents larger than the max value must not be packed"
+        );
+
+        let batch = WithdrawalBatch::pack(&intents, BitcoinAmount::from_sat(12));
+        assert_eq!(batch.len(), intents.len());
+    }
+}
@@ -3,15 +3,20 @@
 //! This just implements a very simple n-of-n multisig bridge.  It will be
 //! extended to a more sophisticated design when we have that specced out.
 
+use bitcoin::Amount;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
+use strata_bridge_tx_builder::prelude::operator_fee;
+pub use strata_primitives::bridge::WithdrawOutput;
 use strata_primitives::{
     bridge::{BitcoinBlockHeight, OperatorIdx},
     buf::Buf32,
-    l1::{self, BitcoinAmount, OutputRef, XOnlyPk},
+    l1::{self, BitcoinAmount, BitcoinTxid, OutputRef},
     operator::{OperatorKeyProvider, OperatorPubkeys},
 };
 
+use crate::{bridge_ops::WithdrawalBatch, operator_update::SignedOperatorUpdate};
+
 /// Entry for an operator.
 ///
 /// Each operator has:
@@ -67,6 +72,24 @@ pub struct OperatorTable {
     ///
     /// MUST be sorted by `idx`.
     operators: Vec<OperatorEntry>,
+
+    /// Sequence number the next operator update must have to be applied.
+    next_update_seqno: u64,
+
+    /// Operator updates from matured L1 blocks waiting for the next epoch
+    /// boundary to be applied.
+    pending_updates: Vec<SignedOperatorUpdate>,
+
+    /// Operator sets that were in force before the current one, oldest first,
+    /// each with the last L1 height it was in force at.  Deposits in L1 blocks
+    /// that haven't matured yet may still have been made to them.
+    past_notary_sets: Vec<(u64, Vec<OperatorIdx>)>,
+
+    /// Operators that were removed but may still be notaries of deposits,
+    /// whose keys are needed to spend them.
+    ///
+    /// MUST be sorted by `idx`.
+    retired_operators: Vec<OperatorEntry>,
 }
 
 impl OperatorTable {
@@ -74,6 +97,10 @@ impl OperatorTable {
         Self {
             next_idx: 0,
             operators: Vec::new(),
+            next_update_seqno: 0,
+            pending_updates: Vec::new(),
+            past_notary_sets: Vec::new(),
+            retired_operators: Vec::new(),
         }
     }
 
@@ -90,6 +117,10 @@ impl OperatorTable {
                     wallet_pk: *e.wallet_pk(),
                 })
                 .collect(),
+            next_update_seqno: 0,
+            pending_updates: Vec::new(),
+            past_notary_sets: Vec::new(),
+            retired_operators: Vec::new(),
         }
     }
 
@@ -136,6 +167,98 @@ impl OperatorTable {
         self.operators.push(entry);
     }
 
+    /// Removes the operator entry with the given idx, returning it if it was
+    /// present.
+    pub fn remove(&mut self, idx: OperatorIdx) -> Option<OperatorEntry> {
+        let pos = self.operators.binary_search_by_key(&idx, |e| e.idx).ok()?;
+        let entry = self.operators.remove(pos);
+
+        // Indexes are never reused, so this keeps the retired ones sorted.
+        self.retired_operators.push(entry.clone());
+        Some(entry)
+    }
+
+    /// Returns the removed operators that may still be notaries of deposits.
+    pub fn retired_operators(&self) -> &[OperatorEntry] {
+        &self.retired_operators
+    }
+
+    /// Drops the retired operators that aren't notaries of any deposit that
+    /// hasn't been executed yet, nor of any past operator set deposits may
+    /// still be made to.
+    pub fn prune_retired_operators(&mut self, deposits_table: &DepositsTable) {
+        let past_notary_sets = &self.past_notary_sets;
+        self.retired_operators.retain(|entry| {
+            let notarizes_deposit = deposits_table.deposits().any(|deposit| {
+                !matches!(deposit.deposit_state(), DepositState::Executed)
+                    && deposit.notary_operators().contains(&entry.idx)
+            });
+            let in_past_set = past_notary_sets
+                .iter()
+                .any(|(_, set)| set.contains(&entry.idx));
+            notarizes_deposit || in_past_set
+        });
+    }
+
+    /// Returns the sequence number the next operator update must have.
+    pub fn next_update_seqno(&self) -> u64 {
+        self.next_update_seqno
+    }
+
+    /// Returns the operator updates waiting to be applied.
+    pub fn pending_updates(&self) -> &[SignedOperatorUpdate] {
+        &self.pending_updates
+    }
+
+    /// Queues an operator update to be applied at the next epoch boundary.
+    pub fn push_pending_update(&mut self, update: SignedOperatorUpdate) {
+        self.pending_updates.push(update);
+    }
+
+    /// Drops the pending operator updates that can't be applied anymore and
+    /// sets the sequence number the next one must have.
+    ///
+    /// Updates with a later sequence number are kept for a later epoch, since
+    /// the sequencer may post an update before the previous one is applied.
+    pub fn clear_pending_updates(&mut self, next_seqno: u64) {
+        self.pending_updates
+            .retain(|update| update.update().seqno() > next_seqno);
+        self.next_update_seqno = next_seqno;
+    }
+
+    /// Returns the indexes of the operators that were in force at the given L1
+    /// height, which are the notaries of any deposit made at that height.
+    pub fn notary_set_at(&self, l1_height: u64) -> Vec<OperatorIdx> {
+        self.past_notary_sets
+            .iter()
+            .find(|(last_height, _)| *last_height >= l1_height)
+            .map(|(_, set)| set.clone())
+            .unwrap_or_else(|| self.indices().collect())
+    }
+
+    /// Records the current operator set as having been in force up to and
+    /// including the given L1 height, before it's changed.
+    ///
+    /// If the set was already changed at that height, the recorded set is the
+    /// one that was actually in force then, so it's kept.
+    pub fn record_notary_set(&mut self, last_l1_height: u64) {
+        if let Some((last_height, _)) = self.past_notary_sets.last() {
+            if *last_height >= last_l1_height {
+                return;
+            }
+        }
+
+        let set = self.indices().collect();
+        self.past_notary_sets.push((last_l1_height, set));
+    }
+
+    /// Drops the recorded operator sets that were last in force at or before
+    /// the given L1 height, once that height has matured.
+    pub fn prune_notary_sets(&mut self, matured_l1_height: u64) {
+        self.past_notary_sets
+            .retain(|(last_height, _)| *last_height > matured_l1_height);
+    }
+
     /// Gets an operator from the table by its idx.
     ///
     /// Does a binary search.
@@ -146,6 +269,17 @@ impl OperatorTable {
             .map(|i| &self.operators[i])
     }
 
+    /// Gets an operator from the table by its idx, including the retired ones
+    /// that may still be notaries of deposits.
+    pub fn get_notary(&self, idx: OperatorIdx) -> Option<&OperatorEntry> {
+        self.get_operator(idx).or_else(|| {
+            self.retired_operators
+                .binary_search_by_key(&idx, |e| e.idx)
+                .ok()
+                .map(|i| &self.retired_operators[i])
+        })
+    }
+
     /// Gets a operator entry by its internal position, *ignoring* the indexes.
     pub fn get_entry_at_pos(&self, pos: u32) -> Option<&OperatorEntry> {
         self.operators.get(pos as usize)
@@ -161,7 +295,9 @@ impl OperatorKeyProvider for OperatorTable {
     fn get_operator_signing_pk(&self, idx: OperatorIdx) -> Option<Buf32> {
         // TODO: use the `signing_pk` here if we decide to use a different signing scheme for
         // signing messages.
-        self.get_operator(idx).map(|ent| ent.wallet_pk)
+        //
+        // Retired operators still sign for the deposits they're notaries of.
+        self.get_notary(idx).map(|ent| ent.wallet_pk)
     }
 }
 
@@ -182,6 +318,10 @@ impl<'a> arbitrary::Arbitrary<'a> for OperatorTable {
         Ok(Self {
             next_idx: 2,
             operators: vec![o0, o1],
+            next_update_seqno: 0,
+            pending_updates: Vec::new(),
+            past_notary_sets: Vec::new(),
+            retired_operators: Vec::new(),
         })
     }
 }
@@ -250,6 +390,16 @@ impl DepositsTable {
             .map(|i| &mut self.deposits[i])
     }
 
+    /// Gets a mut ref to the deposit that references the given outpoint.
+    ///
+    /// Does a linear scan.
+    pub fn find_deposit_by_outpoint_mut(
+        &mut self,
+        outpoint: &OutputRef,
+    ) -> Option<&mut DepositEntry> {
+        self.deposits.iter_mut().find(|e| e.output() == outpoint)
+    }
+
     pub fn get_all_deposits_idxs_iters_iter(&self) -> impl Iterator<Item = u32> + '_ {
         self.deposits.iter().map(|e| e.deposit_idx)
     }
@@ -378,8 +528,16 @@ pub enum DepositState {
     /// Order to send out withdrawal dispatched.
     Dispatched(DispatchedState),
 
-    /// Executed state, will be cleaned up.
+    /// Deposit utxo has been swept to the operator to reimburse it, will be
+    /// cleaned up.
     Executed,
+
+    /// Withdrawal paid out by the assignee from its own funds, pending
+    /// reimbursement from the deposit utxo.
+    ///
+    /// Kept last so that the borsh encoding of the other variants is
+    /// unchanged.
+    Fulfilled(FulfilledState),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -439,6 +597,46 @@ impl DispatchedState {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct FulfilledState {
+    /// The index of the operator that fronted the funds for the withdrawal and
+    /// is to be reimbursed.
+    assignee: OperatorIdx,
+
+    /// The transaction with which the operator paid out the withdrawal.
+    fulfillment_txid: BitcoinTxid,
+
+    /// The total value of the withdrawal outputs, which the operator is
+    /// reimbursed.
+    withdrawal_total: BitcoinAmount,
+}
+
+impl FulfilledState {
+    pub fn new(
+        assignee: OperatorIdx,
+        fulfillment_txid: BitcoinTxid,
+        withdrawal_total: BitcoinAmount,
+    ) -> Self {
+        Self {
+            assignee,
+            fulfillment_txid,
+            withdrawal_total,
+        }
+    }
+
+    pub fn assignee(&self) -> OperatorIdx {
+        self.assignee
+    }
+
+    pub fn fulfillment_txid(&self) -> &BitcoinTxid {
+        &self.fulfillment_txid
+    }
+
+    pub fn withdrawal_total(&self) -> BitcoinAmount {
+        self.withdrawal_total
+    }
+}
+
 /// Command to operator(s) to initiate the withdrawal.  Describes the set of
 /// outputs we're trying to withdraw to.
 ///
@@ -446,9 +644,9 @@ impl DispatchedState {
 ///
 /// # Note
 ///
-/// This is mostly here in order to support withdrawal batching (i.e., sub-denomination withdrawal
-/// amounts that can be batched and then serviced together). At the moment, the underlying `Vec` of
-/// [`WithdrawOutput`] always has a single element.
+/// The outputs are built from a [`WithdrawalBatch`], so their total value never exceeds the amount
+/// of the deposit the command is dispatched against.  Whatever is left of the deposit is paid back
+/// to the bridge address as change.
 #[derive(Clone, Debug, Eq, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 pub struct DispatchCommand {
     /// The table of withdrawal outputs.
@@ -463,25 +661,145 @@ impl DispatchCommand {
     pub fn withdraw_outputs(&self) -> &[WithdrawOutput] {
         &self.withdraw_outputs
     }
+
+    /// Gets the total value of the withdrawal outputs.
+    pub fn get_total_value(&self) -> BitcoinAmount {
+        self.withdraw_outputs.iter().map(|o| o.amt()).sum()
+    }
+
+    /// Checks if the given outputs pay out each of the withdrawal outputs, net
+    /// of the operator fee.  An output can only pay out a single withdrawal
+    /// output.
+    pub fn is_paid_by(&self, outputs: &[WithdrawOutput]) -> bool {
+        let mut unused: Vec<_> = outputs.iter().collect();
+
+        self.withdraw_outputs.iter().all(|expected| {
+            let amt = Amount::from(expected.amt());
+            let payout = amt - operator_fee(amt);
+
+            let pos = unused.iter().position(|o| {
+                o.dest_addr() == expected.dest_addr() && Amount::from(o.amt()) >= payout
+            });
+            pos.map(|pos| unused.swap_remove(pos)).is_some()
+        })
+    }
 }
 
-/// An output constructed from [`crate::bridge_ops::WithdrawalIntent`].
-#[derive(Clone, Debug, Eq, PartialEq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub struct WithdrawOutput {
-    /// Taproot Schnorr XOnlyPubkey with the merkle root information.
-    dest_addr: XOnlyPk,
+impl From<&WithdrawalBatch> for DispatchCommand {
+    fn from(batch: &WithdrawalBatch) -> Self {
+        let withdraw_outputs = batch
+            .intents()
+            .iter()
+            .map(|wi| WithdrawOutput::new(*wi.dest_pk(), *wi.amt()))
+            .collect();
 
-    /// Amount in sats.
-    amt: BitcoinAmount,
+        Self::new(withdraw_outputs)
+    }
 }
 
-impl WithdrawOutput {
-    pub fn new(dest_addr: XOnlyPk, amt: BitcoinAmount) -> Self {
-        Self { dest_addr, amt }
+#[cfg(test)]
+mod tests {
+    use strata_primitives::{bridge::WithdrawOutput, l1::XOnlyPk};
+    use strata_test_utils::ArbitraryGenerator;
+
+    use super::*;
+
+    fn output(addr: u8, sats: u64) -> WithdrawOutput {
+        WithdrawOutput::new(
+            XOnlyPk::new(Buf32::new([addr; 32])),
+            BitcoinAmount::from_sat(sats),
+        )
+    }
+
+    fn payout(addr: u8, sats: u64) -> WithdrawOutput {
+        let amt = Amount::from_sat(sats);
+        output(addr, (amt - operator_fee(amt)).to_sat())
     }
 
-    pub fn dest_addr(&self) -> &XOnlyPk {
-        &self.dest_addr
+    #[test]
+    fn test_notary_set_at() {
+        let mut table = OperatorTable::new_empty();
+        for i in 0..3 {
+            table.insert(Buf32::new([i; 32]), Buf32::new([i; 32]));
+        }
+
+        table.record_notary_set(10);
+        table.remove(0);
+        // a second change at the same height never was in force at any height
+        table.record_notary_set(10);
+        table.remove(1);
+        table.record_notary_set(12);
+        table.insert(Buf32::new([3; 32]), Buf32::new([3; 32]));
+
+        assert_eq!(table.notary_set_at(9), vec![0, 1, 2]);
+        assert_eq!(table.notary_set_at(10), vec![0, 1, 2]);
+        assert_eq!(table.notary_set_at(11), vec![2]);
+        assert_eq!(table.notary_set_at(13), vec![2, 3]);
+
+        table.prune_notary_sets(10);
+        assert_eq!(table.notary_set_at(11), vec![2]);
+        table.prune_notary_sets(12);
+        assert_eq!(table.notary_set_at(12), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_prune_retired_operators() {
+        let mut table = OperatorTable::new_empty();
+        for i in 0..3 {
+            table.insert(Buf32::new([i; 32]), Buf32::new([i; 32]));
+        }
+
+        let mut deposits_table = DepositsTable::new_empty();
+        let outpoint: OutputRef = ArbitraryGenerator::new().generate();
+        deposits_table.add_deposits(&outpoint, &[0, 1, 2], BitcoinAmount::from_int_btc(10));
+
+        table.remove(0);
+        table.remove(1);
+        table.prune_retired_operators(&deposits_table);
+        assert_eq!(table.get_notary(0).map(|e| e.idx()), Some(0));
+        assert!(table.get_operator(0).is_none());
+
+        deposits_table
+            .get_deposit_mut(0)
+            .unwrap()
+            .set_state(DepositState::Executed);
+        table.prune_retired_operators(&deposits_table);
+        assert!(table.retired_operators().is_empty());
+        assert!(table.get_notary(1).is_none());
+    }
+
+    #[test]
+    fn test_is_paid_by() {
+        let cmd = DispatchCommand::new(vec![output(1, 100_000_000), output(2, 50_000_000)]);
+
+        assert!(
+            cmd.is_paid_by(&[payout(2, 50_000_000), payout(1, 100_000_000)]),
+            "outputs paid out net of the fee must be accepted in any order"
+        );
+        assert!(
+            cmd.is_paid_by(&[output(1, 100_000_000), output(2, 50_000_000)]),
+            "outputs paying out more than required must be accepted"
+        );
+
+        let short = payout(1, 100_000_000);
+        let short = output(1, short.amt().to_sat() - 1);
+        assert!(
+            !cmd.is_paid_by(&[short, payout(2, 50_000_000)]),
+            "an output paying out less than required must be rejected"
+        );
+        assert!(
+            !cmd.is_paid_by(&[payout(3, 100_000_000), payout(2, 50_000_000)]),
+            "an output to another address must be rejected"
+        );
+        assert!(
+            !cmd.is_paid_by(&[payout(1, 100_000_000)]),
+            "a missing output must be rejected"
+        );
+
+        let cmd = DispatchCommand::new(vec![output(1, 100_000_000), output(1, 100_000_000)]);
+        assert!(
+            !cmd.is_paid_by(&[payout(1, 100_000_000)]),
+            "a single output must not pay out two withdrawal outputs"
+        );
     }
 }
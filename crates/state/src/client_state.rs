@@ -61,6 +61,40 @@ impl ClientState {
         }
     }
 
+    /// Creates the client state of a node that starts following the chain from
+    /// a checkpoint finalized on L1 instead of from genesis.
+    ///
+    /// The L1 view starts right after the block the checkpoint was included in,
+    /// with `l1_vs` tracking the headers since the end of the checkpoint's L1
+    /// range.
+    pub fn from_finalized_checkpoint(
+        horizon_l1_height: u64,
+        genesis_l1_height: u64,
+        checkpoint: L1Checkpoint,
+        l1_vs: HeaderVerificationState,
+    ) -> Self {
+        let blkid = *checkpoint.batch_info.l2_blockid();
+        let sync_state = SyncState {
+            tip_height: checkpoint.batch_info.l2_range.1,
+            tip_blkid: blkid,
+            confirmed_checkpoint_blocks: Vec::new(),
+            finalized_blkid: blkid,
+        };
+
+        let mut local_l1_view = LocalL1State::new(checkpoint.height + 1);
+        local_l1_view.header_verification_state = Some(l1_vs);
+        local_l1_view.last_finalized_checkpoint = Some(checkpoint);
+
+        Self {
+            chain_active: true,
+            sync_state: Some(sync_state),
+            local_l1_view,
+            horizon_l1_height,
+            genesis_l1_height,
+            genesis_l1_verification_state_hash: None,
+        }
+    }
+
     /// If the chain is "active", meaning we are after genesis (although we
     /// don't necessarily know what it is, that's dictated by the `SyncState`).
     pub fn is_chain_active(&self) -> bool {
@@ -28,9 +28,8 @@ pub struct ExecEnvState {
     pub pending_deposits: StateQueue<bridge_ops::DepositIntent>,
 
     /// Forced inclusions that have been accepted by the CL but not processed by
-    /// a CL payload yet.
-    // TODO This is a stub, we don't support these yet and should assert it to
-    // be empty.
+    /// a CL payload yet.  The sequencer must process these before they're
+    /// overdue.
     pending_forced_incls: StateQueue<forced_inclusion::ForcedInclusion>,
 }
 
@@ -62,6 +61,16 @@ impl ExecEnvState {
     pub fn pending_deposits_mut(&mut self) -> &mut StateQueue<bridge_ops::DepositIntent> {
         &mut self.pending_deposits
     }
+
+    pub fn pending_forced_incls(&self) -> &StateQueue<forced_inclusion::ForcedInclusion> {
+        &self.pending_forced_incls
+    }
+
+    pub fn pending_forced_incls_mut(
+        &mut self,
+    ) -> &mut StateQueue<forced_inclusion::ForcedInclusion> {
+        &mut self.pending_forced_incls
+    }
 }
 
 impl<'a> Arbitrary<'a> for ExecEnvState {
@@ -9,6 +9,7 @@ use strata_primitives::{buf::Buf32, evm_exec::create_evm_extra_payload};
 use crate::{
     bridge_ops::{self, DepositIntent},
     da_blob,
+    forced_inclusion::ForcedInclusion,
     prelude::StateQueue,
 };
 
@@ -163,6 +164,9 @@ impl UpdateOutput {
 pub enum Op {
     /// Deposit some amount.
     Deposit(ELDepositData),
+
+    /// Include a transaction that was forced through L1.
+    ForcedInclusion(ELForcedInclusionData),
 }
 
 pub fn construct_ops_from_deposit_intents(
@@ -187,6 +191,24 @@ pub fn construct_ops_from_deposit_intents(
     el_ops
 }
 
+/// Constructs the ops for the oldest pending forced inclusions, in the order
+/// they have to be processed.
+pub fn construct_ops_from_forced_inclusions(
+    pending_forced_incls: &StateQueue<ForcedInclusion>,
+    max_forced_incls_in_block: u8,
+) -> Vec<Op> {
+    pending_forced_incls
+        .iter_entries()
+        .take(max_forced_incls_in_block as usize)
+        .map(|(idx, forced_incl)| {
+            Op::ForcedInclusion(ELForcedInclusionData::new(
+                idx,
+                forced_incl.payload().to_vec(),
+            ))
+        })
+        .collect()
+}
+
 #[derive(
     Clone, Debug, Eq, PartialEq, Arbitrary, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
 )]
@@ -224,6 +246,31 @@ impl ELDepositData {
     }
 }
 
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ELForcedInclusionData {
+    /// index of the processed forced inclusion.
+    incl_idx: u64,
+
+    /// Raw signed transaction, which the EL includes if it's valid.
+    payload: Vec<u8>,
+}
+
+impl ELForcedInclusionData {
+    pub fn new(incl_idx: u64, payload: Vec<u8>) -> Self {
+        Self { incl_idx, payload }
+    }
+
+    pub fn incl_idx(&self) -> u64 {
+        self.incl_idx
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
 impl<'a> Arbitrary<'a> for UpdateOutput {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         Ok(Self::new_from_state(Buf32::arbitrary(u)?))
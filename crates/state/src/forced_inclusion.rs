@@ -1,24 +1,60 @@
 //! Forced inclusion types.
 //!
-//! This is all stubs now so that we can define data structures later.
+//! Users can post an EE transaction inside a tagged envelope on L1 to get it
+//! included in the rollup without the sequencer's cooperation.  Once the L1
+//! block it was posted in matures, the transaction is queued in the CL state
+//! and the sequencer has to process it within a bounded number of L1 blocks.
 
 use arbitrary::Arbitrary;
 use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
 
 use crate::l1;
 
+/// A forced inclusion that has been accepted by the CL and is waiting to be
+/// processed by an exec update.
 #[derive(Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize)]
 pub struct ForcedInclusion {
+    /// Height of the L1 block the forced inclusion was posted in.
+    l1_height: u64,
+
+    /// Raw signed transaction for the EE.
     payload: Vec<u8>,
 }
 
 impl ForcedInclusion {
+    pub fn new(l1_height: u64, payload: Vec<u8>) -> Self {
+        Self { l1_height, payload }
+    }
+
+    pub fn l1_height(&self) -> u64 {
+        self.l1_height
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
     pub fn into_payload(self) -> Vec<u8> {
         self.payload
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize)]
+/// An L1 tx carrying a forced inclusion.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
 pub struct ForcedInclusionTx {
+    /// The transaction in the block.
     tx: l1::L1Tx,
 }
+
+impl ForcedInclusionTx {
+    pub fn new(tx: l1::L1Tx) -> Self {
+        Self { tx }
+    }
+
+    pub fn tx(&self) -> &l1::L1Tx {
+        &self.tx
+    }
+}
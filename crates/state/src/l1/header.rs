@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use strata_primitives::buf::Buf32;
 
 use super::{DaTx, DepositUpdateTx, L1BlockId};
+use crate::{forced_inclusion::ForcedInclusionTx, operator_update::OperatorUpdateTx};
 /// Header and the wtxs root.
 ///
 /// This is the core data we need to make proof against a L1 block.  We could
@@ -105,6 +106,16 @@ pub struct L1HeaderPayload {
     ///
     /// MUST be sorted by [`DaTx`] index within block.
     pub(crate) da_txs: Vec<DaTx>,
+
+    /// Txs carrying forced inclusions.
+    ///
+    /// MUST be sorted by [`ForcedInclusionTx`] index within block.
+    pub(crate) forced_inclusion_txs: Vec<ForcedInclusionTx>,
+
+    /// Txs carrying operator updates.
+    ///
+    /// MUST be sorted by [`OperatorUpdateTx`] index within block.
+    pub(crate) operator_update_txs: Vec<OperatorUpdateTx>,
 }
 
 impl L1HeaderPayload {
@@ -114,6 +125,8 @@ impl L1HeaderPayload {
             record,
             deposit_update_txs: Vec::new(),
             da_txs: Vec::new(),
+            forced_inclusion_txs: Vec::new(),
+            operator_update_txs: Vec::new(),
         }
     }
 
@@ -127,12 +140,24 @@ impl L1HeaderPayload {
         self
     }
 
+    pub fn with_forced_inclusion_txs(mut self, txs: Vec<ForcedInclusionTx>) -> Self {
+        self.forced_inclusion_txs = txs;
+        self
+    }
+
+    pub fn with_operator_update_txs(mut self, txs: Vec<OperatorUpdateTx>) -> Self {
+        self.operator_update_txs = txs;
+        self
+    }
+
     pub fn build(self) -> L1HeaderPayload {
         L1HeaderPayload {
             idx: self.idx,
             record: self.record,
             deposit_update_txs: self.deposit_update_txs,
             da_txs: self.da_txs,
+            forced_inclusion_txs: self.forced_inclusion_txs,
+            operator_update_txs: self.operator_update_txs,
         }
     }
 
@@ -6,6 +6,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use ethnum::U256;
 use serde::{Deserialize, Serialize};
 use strata_primitives::buf::Buf32;
+use thiserror::Error;
 
 use super::{timestamp_store::TimestampStore, L1BlockId};
 use crate::l1::{params::BtcParams, utils::compute_block_hash};
@@ -70,6 +71,27 @@ pub struct HeaderVerificationState {
     pub last_11_blocks_timestamps: TimestampStore,
 }
 
+/// Errors that can occur while verifying a Bitcoin block header against a
+/// [`HeaderVerificationState`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum L1VerificationError {
+    /// The header does not build on the last verified block.
+    #[error("header parent {0} does not match last verified block {1}")]
+    ContinuityError(L1BlockId, L1BlockId),
+
+    /// The header does not encode the target expected after the difficulty adjustment.
+    #[error("header target {0:#x} does not match expected target {1:#x}")]
+    TargetMismatch(u32, u32),
+
+    /// The header's hash does not meet the encoded target.
+    #[error("block {0} does not meet its target")]
+    PowNotMet(L1BlockId),
+
+    /// The header's timestamp is not above the median of the last eleven blocks.
+    #[error("header timestamp {0} is not above the median time {1}")]
+    TimestampTooOld(u32, u32),
+}
+
 /// Summary of the HeaderVerificationState that is propagated to the CheckpointProof as public
 /// output
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, Deserialize, Serialize)]
@@ -116,7 +138,9 @@ impl HeaderVerificationState {
     /// This has not been directly used since it is not available on the current release
     fn next_target(&mut self, timestamp: u32, params: &BtcParams) -> u32 {
         let params = params.inner();
-        if (self.last_verified_block_num + 1) % params.difficulty_adjustment_interval() as u32 != 0
+        if params.no_pow_retargeting
+            || (self.last_verified_block_num + 1) % params.difficulty_adjustment_interval() as u32
+                != 0
         {
             return self.next_block_target;
         }
@@ -155,21 +179,53 @@ impl HeaderVerificationState {
         }
     }
 
-    pub fn check_and_update_full(&mut self, header: &Header, params: &BtcParams) {
+    /// Verifies the header against the state and, if it's valid, updates the state to have it as
+    /// the last verified block.
+    ///
+    /// The state is left unchanged if the header is invalid.
+    pub fn check_and_update_full(
+        &mut self,
+        header: &Header,
+        params: &BtcParams,
+    ) -> Result<(), L1VerificationError> {
         // Check continuity
         let prev_blockhash: L1BlockId =
             Buf32::from(header.prev_blockhash.as_raw_hash().to_byte_array()).into();
-        assert_eq!(prev_blockhash, self.last_verified_block_hash,);
+        if prev_blockhash != self.last_verified_block_hash {
+            return Err(L1VerificationError::ContinuityError(
+                prev_blockhash,
+                self.last_verified_block_hash,
+            ));
+        }
 
         let block_hash_raw = compute_block_hash(header);
         let block_hash = BlockHash::from_byte_array(*block_hash_raw.as_ref());
 
-        // Check PoW
-        assert_eq!(header.bits.to_consensus(), self.next_block_target);
-        header.target().is_met_by(block_hash);
+        // Check PoW, networks like testnet allow blocks at the minimum difficulty at any time
+        let target = header.bits.to_consensus();
+        let is_min_difficulty = params.inner().allow_min_difficulty_blocks
+            && target
+                == params
+                    .inner()
+                    .max_attainable_target
+                    .to_compact_lossy()
+                    .to_consensus();
+        if target != self.next_block_target && !is_min_difficulty {
+            return Err(L1VerificationError::TargetMismatch(
+                target,
+                self.next_block_target,
+            ));
+        }
+
+        if !header.target().is_met_by(block_hash) {
+            return Err(L1VerificationError::PowNotMet(block_hash_raw.into()));
+        }
 
         // Check timestamp
-        assert!(header.time > self.last_11_blocks_timestamps.median());
+        let median = self.last_11_blocks_timestamps.median();
+        if header.time <= median {
+            return Err(L1VerificationError::TimestampTooOld(header.time, median));
+        }
 
         // Increase the last verified block number by 1
         self.last_verified_block_num += 1;
@@ -185,6 +241,8 @@ impl HeaderVerificationState {
 
         // Set the target for the next block
         self.next_block_target = self.next_target(header.time, params);
+
+        Ok(())
     }
 
     // TODO: add errors
@@ -309,9 +367,58 @@ mod tests {
         for header_idx in r1..chain.end {
             verification_state
                 .check_and_update_full(&chain.get_header(header_idx), &MAINNET.clone().into())
+                .expect("header must be valid");
         }
     }
 
+    #[test]
+    fn test_invalid_headers() {
+        let chain = get_btc_chain();
+        let params: BtcParams = MAINNET.clone().into();
+        let height = 42000;
+        let verification_state = chain.get_verification_state(height, &params);
+
+        let mut state = verification_state.clone();
+        let res = state.check_and_update_full(&chain.get_header(height + 1), &params);
+        assert!(matches!(res, Err(L1VerificationError::ContinuityError(..))));
+        assert_eq!(
+            state, verification_state,
+            "state must be unchanged on error"
+        );
+
+        let mut header = chain.get_header(height);
+        header.nonce = header.nonce.wrapping_add(1);
+        let res = state.check_and_update_full(&header, &params);
+        assert!(matches!(res, Err(L1VerificationError::PowNotMet(_))));
+        assert_eq!(
+            state, verification_state,
+            "state must be unchanged on error"
+        );
+    }
+
+    #[test]
+    fn test_next_target_without_retargeting() {
+        let chain = get_btc_chain();
+        let mainnet: BtcParams = MAINNET.clone().into();
+        let regtest = BtcParams::from(bitcoin::Network::Regtest);
+
+        // the last verified block is the one right before a difficulty adjustment
+        let height = get_difficulty_adjustment_height(1, chain.start, &mainnet);
+        let state = chain.get_verification_state(height, &mainnet);
+        let timestamp = chain.get_header(height - 1).time;
+
+        assert_eq!(
+            state.clone().next_target(timestamp, &mainnet),
+            chain.get_header(height).bits.to_consensus(),
+            "mainnet must retarget"
+        );
+        assert_eq!(
+            state.clone().next_target(timestamp, &regtest),
+            state.next_block_target,
+            "regtest must keep the same target"
+        );
+    }
+
     #[test]
     fn test_get_difficulty_adjustment_height() {
         let start = 0;
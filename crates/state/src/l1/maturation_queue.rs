@@ -2,6 +2,7 @@ use arbitrary::Arbitrary;
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use super::{DaTx, DepositUpdateTx, L1BlockId, L1HeaderPayload, L1HeaderRecord};
+use crate::{forced_inclusion::ForcedInclusionTx, operator_update::OperatorUpdateTx};
 
 /// Entry representing an L1 block that we've acknowledged seems to be on the
 /// longest chain but might still reorg.  We wait until the block is buried
@@ -22,6 +23,16 @@ pub struct L1MaturationEntry {
     ///
     /// MUST be sorted by [`DaTx`] index within block.
     da_txs: Vec<DaTx>,
+
+    /// Txs carrying forced inclusions.
+    ///
+    /// MUST be sorted by [`ForcedInclusionTx`] index within block.
+    forced_inclusion_txs: Vec<ForcedInclusionTx>,
+
+    /// Txs carrying operator updates.
+    ///
+    /// MUST be sorted by [`OperatorUpdateTx`] index within block.
+    operator_update_txs: Vec<OperatorUpdateTx>,
 }
 
 impl L1MaturationEntry {
@@ -29,11 +40,15 @@ impl L1MaturationEntry {
         record: L1HeaderRecord,
         deposit_update_txs: Vec<DepositUpdateTx>,
         da_txs: Vec<DaTx>,
+        forced_inclusion_txs: Vec<ForcedInclusionTx>,
+        operator_update_txs: Vec<OperatorUpdateTx>,
     ) -> Self {
         Self {
             record,
             deposit_update_txs,
             da_txs,
+            forced_inclusion_txs,
+            operator_update_txs,
         }
     }
 
@@ -42,8 +57,26 @@ impl L1MaturationEntry {
         self.record.blkid()
     }
 
-    pub fn into_parts(self) -> (L1HeaderRecord, Vec<DepositUpdateTx>, Vec<DaTx>) {
-        (self.record, self.deposit_update_txs, self.da_txs)
+    pub fn record(&self) -> &L1HeaderRecord {
+        &self.record
+    }
+
+    pub fn into_parts(
+        self,
+    ) -> (
+        L1HeaderRecord,
+        Vec<DepositUpdateTx>,
+        Vec<DaTx>,
+        Vec<ForcedInclusionTx>,
+        Vec<OperatorUpdateTx>,
+    ) {
+        (
+            self.record,
+            self.deposit_update_txs,
+            self.da_txs,
+            self.forced_inclusion_txs,
+            self.operator_update_txs,
+        )
     }
 }
 
@@ -53,6 +86,8 @@ impl From<L1HeaderPayload> for L1MaturationEntry {
             record: value.record,
             deposit_update_txs: value.deposit_update_txs,
             da_txs: value.da_txs,
+            forced_inclusion_txs: value.forced_inclusion_txs,
+            operator_update_txs: value.operator_update_txs,
         }
     }
 }
@@ -1,4 +1,7 @@
-use bitcoin::params::{Params, MAINNET};
+use bitcoin::{
+    params::{Params, MAINNET},
+    Network,
+};
 
 #[derive(Debug, Clone)]
 pub struct BtcParams(Params);
@@ -9,6 +12,13 @@ impl From<Params> for BtcParams {
     }
 }
 
+impl From<Network> for BtcParams {
+    /// Gets the consensus parameters of the given bitcoin network.
+    fn from(network: Network) -> Self {
+        BtcParams(Params::new(network))
+    }
+}
+
 impl BtcParams {
     pub fn into_inner(self) -> Params {
         self.0
@@ -37,7 +47,8 @@ impl BtcParams {
 /// ```
 ///
 /// # Returns
-/// Returns the default Bitcoin Parameters used in our rollup.
+/// Returns the default Bitcoin Parameters used in our rollup.  Anything that knows the network in
+/// use should get its parameters with [`BtcParams::from`] instead.
 pub fn get_btc_params() -> BtcParams {
     BtcParams(MAINNET.clone())
 }
@@ -1,7 +1,7 @@
 use arbitrary::Arbitrary;
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use super::{L1HeaderRecord, L1MaturationEntry};
+use super::{HeaderVerificationState, L1HeaderRecord, L1MaturationEntry};
 use crate::prelude::StateQueue;
 
 /// Describes state relating to the CL's view of L1.  Updated by entries in the
@@ -15,16 +15,25 @@ pub struct L1ViewState {
     /// The "safe" L1 block.  This block is the last block inserted into the L1 MMR.
     pub(crate) safe_block: L1HeaderRecord,
 
+    /// Header verification state with the safe block as the last verified block.  The headers of
+    /// blocks in the maturation queue are verified starting from this.
+    pub(crate) safe_verification_state: HeaderVerificationState,
+
     /// L1 blocks that might still be reorged.
     pub(crate) maturation_queue: StateQueue<L1MaturationEntry>,
     // TODO include L1 MMR state that we mature blocks into
 }
 
 impl L1ViewState {
-    pub fn new_at_horizon(horizon_height: u64, safe_block: L1HeaderRecord) -> Self {
+    pub fn new_at_horizon(
+        horizon_height: u64,
+        safe_block: L1HeaderRecord,
+        safe_verification_state: HeaderVerificationState,
+    ) -> Self {
         Self {
             horizon_height,
             safe_block,
+            safe_verification_state,
             maturation_queue: StateQueue::new_at_index(horizon_height),
         }
     }
@@ -33,10 +42,12 @@ impl L1ViewState {
         horizon_height: u64,
         genesis_height: u64,
         genesis_trigger_block: L1HeaderRecord,
+        genesis_verification_state: HeaderVerificationState,
     ) -> Self {
         Self {
             horizon_height,
             safe_block: genesis_trigger_block,
+            safe_verification_state: genesis_verification_state,
             maturation_queue: StateQueue::new_at_index(genesis_height),
         }
     }
@@ -45,6 +56,10 @@ impl L1ViewState {
         &self.safe_block
     }
 
+    pub fn safe_verification_state(&self) -> &HeaderVerificationState {
+        &self.safe_verification_state
+    }
+
     pub fn safe_height(&self) -> u64 {
         self.maturation_queue.base_idx()
     }
@@ -61,6 +76,7 @@ impl L1ViewState {
 impl<'a> Arbitrary<'a> for L1ViewState {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let blk = L1HeaderRecord::arbitrary(u)?;
-        Ok(Self::new_at_horizon(u64::arbitrary(u)?, blk))
+        let vs = HeaderVerificationState::arbitrary(u)?;
+        Ok(Self::new_at_horizon(u64::arbitrary(u)?, blk, vs))
     }
 }
@@ -24,6 +24,7 @@ pub mod header;
 pub mod id;
 pub mod l1;
 pub mod operation;
+pub mod operator_update;
 pub mod state_op;
 pub mod state_queue;
 pub mod sync_event;
@@ -0,0 +1,115 @@
+//! Operator set update types.
+//!
+//! The sequencer can add and remove bridge operators by posting a signed
+//! update inside a tagged envelope on L1.  Once the L1 block it was posted in
+//! matures, the update is queued in the operator table and it's applied at the
+//! next epoch boundary, so that the operator set never changes in the middle of
+//! an epoch.
+
+use arbitrary::Arbitrary;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use strata_crypto::verify_schnorr_sig;
+use strata_primitives::{
+    bridge::OperatorIdx,
+    buf::{Buf32, Buf64},
+    hash::compute_borsh_hash,
+};
+
+use crate::l1;
+
+/// A single change to the operator set.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
+pub enum OperatorUpdateOp {
+    /// Adds an operator with the given pubkeys under the next unassigned index.
+    Add {
+        /// Pubkey used to verify signed messages from the operator.
+        signing_pk: Buf32,
+
+        /// Wallet pubkey used to compute MuSig2 pubkey from a set of operators.
+        wallet_pk: Buf32,
+    },
+
+    /// Removes the operator with the given index.
+    Remove(OperatorIdx),
+}
+
+/// A set of changes to the operator set that are applied together, or not at all.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
+pub struct OperatorUpdate {
+    /// Position of the update in the sequence of updates, so that a posted update can't be
+    /// replayed.
+    seqno: u64,
+
+    /// Changes to make, in order.
+    ops: Vec<OperatorUpdateOp>,
+}
+
+impl OperatorUpdate {
+    pub fn new(seqno: u64, ops: Vec<OperatorUpdateOp>) -> Self {
+        Self { seqno, ops }
+    }
+
+    pub fn seqno(&self) -> u64 {
+        self.seqno
+    }
+
+    pub fn ops(&self) -> &[OperatorUpdateOp] {
+        &self.ops
+    }
+
+    pub fn get_sighash(&self) -> Buf32 {
+        compute_borsh_hash(self)
+    }
+}
+
+/// An [`OperatorUpdate`] signed by the sequencer.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
+pub struct SignedOperatorUpdate {
+    inner: OperatorUpdate,
+    signature: Buf64,
+}
+
+impl SignedOperatorUpdate {
+    pub fn new(inner: OperatorUpdate, signature: Buf64) -> Self {
+        Self { inner, signature }
+    }
+
+    pub fn update(&self) -> &OperatorUpdate {
+        &self.inner
+    }
+
+    pub fn signature(&self) -> Buf64 {
+        self.signature
+    }
+
+    pub fn verify_sig(&self, pub_key: &Buf32) -> bool {
+        let msg = self.inner.get_sighash();
+        verify_schnorr_sig(&self.signature, &msg, pub_key)
+    }
+}
+
+/// An L1 tx carrying an operator update.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Arbitrary, BorshDeserialize, BorshSerialize, Serialize, Deserialize,
+)]
+pub struct OperatorUpdateTx {
+    /// The transaction in the block.
+    tx: l1::L1Tx,
+}
+
+impl OperatorUpdateTx {
+    pub fn new(tx: l1::L1Tx) -> Self {
+        Self { tx }
+    }
+
+    pub fn tx(&self) -> &l1::L1Tx {
+        &self.tx
+    }
+}
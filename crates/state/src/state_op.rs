@@ -7,17 +7,23 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use strata_primitives::{
     bridge::{BitcoinBlockHeight, OperatorIdx},
     buf::Buf32,
+    l1::{BitcoinAmount, OutputRef},
 };
 use tracing::*;
 
 use crate::{
     bridge_ops::DepositIntent,
-    bridge_state::{DepositState, DispatchCommand, DispatchedState},
+    bridge_state::{DepositState, DepositsTable, DispatchCommand, DispatchedState, FulfilledState},
     chain_state::Chainstate,
+    forced_inclusion::ForcedInclusion,
     header::L2Header,
     id::L2BlockId,
-    l1::{self, L1MaturationEntry},
-    tx::ProtocolOperation::Deposit,
+    l1::{self, HeaderVerificationState, L1MaturationEntry},
+    tx::{
+        DepositReimbursementInfo,
+        ProtocolOperation::{self, Deposit, DepositReimbursement, WithdrawalFulfillment},
+        WithdrawalFulfillmentInfo,
+    },
 };
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
@@ -39,17 +45,38 @@ pub enum StateOp {
     /// as a sanity check.
     MatureL1Block(u64),
 
+    /// Sets the header verification state of the safe L1 block.
+    SetSafeL1VerificationState(HeaderVerificationState),
+
     /// Remove deposit Intent
     ConsumeDepositIntent(u64),
 
+    /// Removes pending forced inclusions up to and including the one with the
+    /// given index.
+    ConsumeForcedInclusion(u64),
+
     /// Creates an operator
     CreateOperator(Buf32, Buf32),
 
+    /// Removes an operator
+    RemoveOperator(OperatorIdx),
+
+    /// Drops the pending operator updates that can't be applied anymore and
+    /// sets the sequence number the next one must have.
+    ClearOperatorUpdates(u64),
+
     /// Assigns an assignee a deposit and withdrawal dispatch command to play out.
     DispatchWithdrawal(u32, OperatorIdx, DispatchCommand, BitcoinBlockHeight),
 
     /// Resets the assignee and block height for a deposit.
     ResetDepositAssignee(u32, OperatorIdx, BitcoinBlockHeight),
+
+    /// Records the current operator set as the notary set of deposits made up
+    /// to and including the given L1 height, before it's changed.
+    RecordNotarySet(u64),
+
+    /// Moves the chain on to the given epoch.
+    SetEpoch(u64),
 }
 
 /// Collection of writes we're making to the state.
@@ -125,7 +152,9 @@ fn apply_op_to_chainstate(op: &StateOp, state: &mut Chainstate) {
         }
 
         StateOp::MatureL1Block(maturing_idx) => {
-            let operators: Vec<_> = state.operator_table().indices().collect();
+            // Deposits are made to the operator set that was in force at their L1 height.
+            let operators = state.operator_table.notary_set_at(*maturing_idx);
+            state.operator_table.prune_notary_sets(*maturing_idx);
             let mqueue = &mut state.l1_state.maturation_queue;
             let deposits = state.exec_env_state.pending_deposits_mut();
 
@@ -138,21 +167,56 @@ fn apply_op_to_chainstate(op: &StateOp, state: &mut Chainstate) {
             let matured_block = mqueue.pop_front().unwrap();
 
             // TODO add it to the MMR so we can reference it in the future
-            let (header_record, deposit_txs, _) = matured_block.into_parts();
+            let (header_record, deposit_txs, _, forced_incl_txs, operator_update_txs) =
+                matured_block.into_parts();
             for tx in deposit_txs {
-                if let Deposit(deposit_info) = tx.tx().protocol_operation() {
-                    trace!("we got some deposit_txs");
-                    let amt = deposit_info.amt;
-                    let deposit_intent = DepositIntent::new(amt, &deposit_info.address);
-                    deposits.push_back(deposit_intent);
-                    state
-                        .deposits_table
-                        .add_deposits(&deposit_info.outpoint, &operators, amt)
+                match tx.tx().protocol_operation() {
+                    Deposit(deposit_info) => {
+                        trace!("we got some deposit_txs");
+                        let amt = deposit_info.amt;
+                        let deposit_intent = DepositIntent::new(amt, &deposit_info.address);
+                        deposits.push_back(deposit_intent);
+                        state
+                            .deposits_table
+                            .add_deposits(&deposit_info.outpoint, &operators, amt)
+                    }
+                    WithdrawalFulfillment(info) => {
+                        apply_withdrawal_fulfillment(&mut state.deposits_table, info, *maturing_idx)
+                    }
+                    DepositReimbursement(info) => {
+                        apply_deposit_reimbursement(&mut state.deposits_table, info)
+                    }
+                    _ => {}
                 }
             }
+
+            let forced_incls = state.exec_env_state.pending_forced_incls_mut();
+            for tx in forced_incl_txs {
+                if let ProtocolOperation::ForcedInclusion(info) = tx.tx().protocol_operation() {
+                    let forced_incl = ForcedInclusion::new(*maturing_idx, info.payload.clone());
+                    forced_incls.push_back(forced_incl);
+                }
+            }
+
+            for tx in operator_update_txs {
+                if let ProtocolOperation::OperatorUpdate(update) = tx.tx().protocol_operation() {
+                    state.operator_table.push_pending_update(update.clone());
+                }
+            }
+
+            // Deposits made to a past set are known by now, and some may have
+            // been executed.
+            state
+                .operator_table
+                .prune_retired_operators(&state.deposits_table);
+
             state.l1_state.safe_block = header_record;
         }
 
+        StateOp::SetSafeL1VerificationState(vs) => {
+            state.l1_state.safe_verification_state = vs.clone();
+        }
+
         StateOp::ConsumeDepositIntent(to_drop_idx) => {
             let deposits = state.exec_env_state.pending_deposits_mut();
 
@@ -171,10 +235,47 @@ fn apply_op_to_chainstate(op: &StateOp, state: &mut Chainstate) {
                 .expect("stateop: unable to consume deposit intent");
         }
 
+        StateOp::ConsumeForcedInclusion(to_drop_idx) => {
+            let forced_incls = state.exec_env_state.pending_forced_incls_mut();
+
+            let front_idx = forced_incls
+                .front_idx()
+                .expect("stateop: empty forced inclusion queue");
+
+            // forced inclusions are processed sequentially, without any gaps
+            let to_drop_count = to_drop_idx
+                .checked_sub(front_idx)
+                .expect("stateop: unable to consume forced inclusion")
+                + 1;
+
+            forced_incls
+                .pop_front_n_vec(to_drop_count as usize)
+                .expect("stateop: unable to consume forced inclusion");
+        }
+
         StateOp::CreateOperator(spk, wpk) => {
             state.operator_table.insert(*spk, *wpk);
         }
 
+        StateOp::RemoveOperator(idx) => {
+            state
+                .operator_table
+                .remove(*idx)
+                .expect("stateop: missing operator idx");
+        }
+
+        StateOp::ClearOperatorUpdates(next_seqno) => {
+            state.operator_table.clear_pending_updates(*next_seqno);
+        }
+
+        StateOp::RecordNotarySet(last_l1_height) => {
+            state.operator_table.record_notary_set(*last_l1_height);
+        }
+
+        StateOp::SetEpoch(epoch) => {
+            state.epoch = *epoch;
+        }
+
         StateOp::DispatchWithdrawal(deposit_idx, op_idx, cmd, exec_height) => {
             let deposit_ent = state
                 .deposits_table_mut()
@@ -202,6 +303,106 @@ fn apply_op_to_chainstate(op: &StateOp, state: &mut Chainstate) {
     }
 }
 
+/// Marks the withdrawal dispatched against the deposit as fulfilled by its
+/// assignee, if the assignee paid out all of its outputs before the deadline.
+///
+/// Fulfillments are applied when their L1 block matures, which happens before
+/// any reassignment based on that block.  So if the fulfillment is within the
+/// deadline of the current assignment, the current assignee is the one that was
+/// assigned when it paid.
+///
+/// If the withdrawal was paid out of the deposit itself the deposit is executed
+/// right away, and any change becomes a new deposit like with a reimbursement.
+fn apply_withdrawal_fulfillment(
+    deposits_table: &mut DepositsTable,
+    info: &WithdrawalFulfillmentInfo,
+    l1_height: u64,
+) {
+    let Some(deposit_ent) = deposits_table.find_deposit_by_outpoint_mut(&info.deposit_outpoint)
+    else {
+        warn!(outpoint = ?info.deposit_outpoint, "ignoring fulfillment for unknown deposit");
+        return;
+    };
+
+    // A withdrawal paid out of the deposit itself was signed by all of its
+    // notaries, so the deposit is gone whatever state we had it in.
+    if info.spends_deposit {
+        deposit_ent.set_state(DepositState::Executed);
+        let notary_operators = deposit_ent.notary_operators().to_vec();
+        add_change_deposit(deposits_table, &notary_operators, &info.change);
+        return;
+    }
+
+    let DepositState::Dispatched(dstate) = deposit_ent.deposit_state() else {
+        warn!(deposit_idx = %deposit_ent.idx(), "ignoring fulfillment for undispatched deposit");
+        return;
+    };
+
+    if l1_height >= dstate.exec_deadline() {
+        warn!(deposit_idx = %deposit_ent.idx(), "ignoring fulfillment past the deadline");
+        return;
+    }
+
+    if info.operator_idx != dstate.assignee() {
+        let payer = info.operator_idx;
+        warn!(deposit_idx = %deposit_ent.idx(), %payer, "ignoring fulfillment by non-assignee");
+        return;
+    }
+
+    if !dstate.cmd().is_paid_by(&info.outputs) {
+        warn!(deposit_idx = %deposit_ent.idx(), "ignoring fulfillment with wrong outputs");
+        return;
+    }
+
+    let fstate = FulfilledState::new(
+        info.operator_idx,
+        info.txid.clone(),
+        dstate.cmd().get_total_value(),
+    );
+    deposit_ent.set_state(DepositState::Fulfilled(fstate));
+}
+
+/// Marks the deposit as executed once it has been swept to the operator that
+/// fulfilled its withdrawal.
+///
+/// Any change sent back to the bridge is still locked to the notaries of the
+/// deposit, so it becomes a new deposit entry with the same notaries.
+fn apply_deposit_reimbursement(
+    deposits_table: &mut DepositsTable,
+    info: &DepositReimbursementInfo,
+) {
+    let Some(deposit_ent) = deposits_table.find_deposit_by_outpoint_mut(&info.deposit_outpoint)
+    else {
+        warn!(outpoint = ?info.deposit_outpoint, "ignoring reimbursement for unknown deposit");
+        return;
+    };
+
+    let DepositState::Fulfilled(fstate) = deposit_ent.deposit_state() else {
+        warn!(deposit_idx = %deposit_ent.idx(), "ignoring reimbursement for unfulfilled deposit");
+        return;
+    };
+
+    if fstate.fulfillment_txid() != &info.fulfillment_txid {
+        warn!(deposit_idx = %deposit_ent.idx(), "ignoring reimbursement for other fulfillment");
+        return;
+    }
+
+    deposit_ent.set_state(DepositState::Executed);
+    let notary_operators = deposit_ent.notary_operators().to_vec();
+    add_change_deposit(deposits_table, &notary_operators, &info.change);
+}
+
+/// Registers the change of a spent deposit, if any, as a new deposit entry.
+fn add_change_deposit(
+    deposits_table: &mut DepositsTable,
+    notary_operators: &[OperatorIdx],
+    change: &Option<(OutputRef, BitcoinAmount)>,
+) {
+    if let Some((change_outpoint, change_amt)) = change {
+        deposits_table.add_deposits(change_outpoint, notary_operators, *change_amt);
+    }
+}
+
 /// Cache that writes to state and remembers the series of operations made to it
 /// so they can be persisted to disk without saving the chainstate.
 ///
@@ -270,11 +471,38 @@ impl StateCache {
         self.merge_op(StateOp::ConsumeDepositIntent(idx));
     }
 
+    /// Removes the pending forced inclusions up to and including the given index.
+    pub fn consume_forced_inclusion(&mut self, idx: u64) {
+        self.merge_op(StateOp::ConsumeForcedInclusion(idx));
+    }
+
     /// Inserts a new operator with the specified pubkeys into the operator table.
     pub fn insert_operator(&mut self, signing_pk: Buf32, wallet_pk: Buf32) {
         self.merge_op(StateOp::CreateOperator(signing_pk, wallet_pk));
     }
 
+    /// Removes the operator with the specified idx from the operator table.
+    pub fn remove_operator(&mut self, idx: OperatorIdx) {
+        self.merge_op(StateOp::RemoveOperator(idx));
+    }
+
+    /// Records the current operator set as the one deposits up to and
+    /// including the given L1 height were made to, before it's changed.
+    pub fn record_notary_set(&mut self, last_l1_height: u64) {
+        self.merge_op(StateOp::RecordNotarySet(last_l1_height));
+    }
+
+    /// Drops the pending operator updates that can't be applied anymore,
+    /// setting the sequence number the next one must have.
+    pub fn clear_operator_updates(&mut self, next_seqno: u64) {
+        self.merge_op(StateOp::ClearOperatorUpdates(next_seqno));
+    }
+
+    /// Moves the chain on to the given epoch.
+    pub fn set_epoch(&mut self, epoch: u64) {
+        self.merge_op(StateOp::SetEpoch(epoch));
+    }
+
     /// L1 revert
     pub fn revert_l1_view_to(&mut self, height: u64) {
         self.merge_op(StateOp::RevertL1Height(height));
@@ -290,6 +518,11 @@ impl StateCache {
         self.merge_op(StateOp::MatureL1Block(idx));
     }
 
+    /// Sets the header verification state of the safe L1 block.
+    pub fn set_safe_l1_verification_state(&mut self, vs: HeaderVerificationState) {
+        self.merge_op(StateOp::SetSafeL1VerificationState(vs));
+    }
+
     pub fn assign_withdrawal_command(
         &mut self,
         deposit_idx: u32,
@@ -320,3 +553,157 @@ impl StateCache {
 
     // TODO add more manipulator functions
 }
+
+#[cfg(test)]
+mod tests {
+    use strata_primitives::{
+        bridge::WithdrawOutput,
+        l1::{BitcoinTxid, XOnlyPk},
+    };
+    use strata_test_utils::ArbitraryGenerator;
+
+    use super::*;
+
+    const ASSIGNEE: OperatorIdx = 1;
+    const DEADLINE: u64 = 100;
+
+    /// Creates a table with a single deposit, dispatched to [`ASSIGNEE`] unless `dispatched` is
+    /// false, and a fulfillment that pays it out in full.
+    fn setup(dispatched: bool) -> (DepositsTable, WithdrawalFulfillmentInfo) {
+        let mut generator = ArbitraryGenerator::new();
+        let deposit_outpoint: OutputRef = generator.generate();
+        let amt = BitcoinAmount::from_int_btc(10);
+
+        let mut deposits_table = DepositsTable::new_empty();
+        deposits_table.add_deposits(&deposit_outpoint, &[0, 1, 2], amt);
+
+        let withdraw_output = WithdrawOutput::new(XOnlyPk::new(Buf32::new([1; 32])), amt);
+        if dispatched {
+            let cmd = DispatchCommand::new(vec![withdraw_output.clone()]);
+            let dstate = DispatchedState::new(cmd, ASSIGNEE, DEADLINE);
+            let deposit_ent = deposits_table.get_deposit_mut(0).unwrap();
+            deposit_ent.set_state(DepositState::Dispatched(dstate));
+        }
+
+        let info = WithdrawalFulfillmentInfo {
+            deposit_outpoint,
+            operator_idx: ASSIGNEE,
+            outputs: vec![withdraw_output],
+            txid: generator.generate(),
+            spends_deposit: false,
+            change: None,
+        };
+
+        (deposits_table, info)
+    }
+
+    fn deposit_state(deposits_table: &DepositsTable) -> &DepositState {
+        deposits_table.get_deposit(0).unwrap().deposit_state()
+    }
+
+    #[test]
+    fn test_apply_withdrawal_fulfillment() {
+        let (mut deposits_table, info) = setup(true);
+        apply_withdrawal_fulfillment(&mut deposits_table, &info, DEADLINE - 1);
+
+        let expected =
+            FulfilledState::new(ASSIGNEE, info.txid.clone(), BitcoinAmount::from_int_btc(10));
+        assert_eq!(
+            deposit_state(&deposits_table),
+            &DepositState::Fulfilled(expected)
+        );
+    }
+
+    #[test]
+    fn test_apply_withdrawal_fulfillment_from_deposit() {
+        let (mut deposits_table, mut info) = setup(false);
+        let change_outpoint: OutputRef = ArbitraryGenerator::new().generate();
+        info.spends_deposit = true;
+        info.change = Some((change_outpoint.clone(), BitcoinAmount::from_int_btc(1)));
+
+        // paying out of the deposit spends it even if it wasn't dispatched
+        apply_withdrawal_fulfillment(&mut deposits_table, &info, DEADLINE + 1);
+        assert_eq!(deposit_state(&deposits_table), &DepositState::Executed);
+
+        let change_ent = deposits_table
+            .find_deposit_by_outpoint_mut(&change_outpoint)
+            .expect("change must be registered as a deposit");
+        assert_eq!(change_ent.amt(), BitcoinAmount::from_int_btc(1));
+        assert_eq!(change_ent.notary_operators(), &[0, 1, 2]);
+        assert_eq!(change_ent.deposit_state(), &DepositState::Accepted);
+    }
+
+    #[test]
+    fn test_apply_withdrawal_fulfillment_rejects_invalid() {
+        let (deposits_table, info) = setup(true);
+        let dispatched = deposit_state(&deposits_table).clone();
+
+        let mut wrong_outputs = info.clone();
+        wrong_outputs.outputs[0] = WithdrawOutput::new(
+            XOnlyPk::new(Buf32::new([2; 32])),
+            BitcoinAmount::from_int_btc(10),
+        );
+        let mut non_assignee = info.clone();
+        non_assignee.operator_idx = ASSIGNEE + 1;
+
+        let cases = [
+            (&wrong_outputs, DEADLINE - 1, "wrong outputs"),
+            (&info, DEADLINE, "past the deadline"),
+            (&non_assignee, DEADLINE - 1, "paid by non-assignee"),
+        ];
+        for (info, l1_height, case) in cases {
+            let mut deposits_table = deposits_table.clone();
+            apply_withdrawal_fulfillment(&mut deposits_table, info, l1_height);
+            assert_eq!(deposit_state(&deposits_table), &dispatched, "{case}");
+        }
+
+        let (mut deposits_table, info) = setup(false);
+        apply_withdrawal_fulfillment(&mut deposits_table, &info, DEADLINE - 1);
+        assert_eq!(
+            deposit_state(&deposits_table),
+            &DepositState::Accepted,
+            "undispatched deposit must not be fulfilled"
+        );
+    }
+
+    #[test]
+    fn test_apply_deposit_reimbursement() {
+        let (mut deposits_table, info) = setup(true);
+
+        let mut reimbursement = DepositReimbursementInfo {
+            deposit_outpoint: info.deposit_outpoint.clone(),
+            fulfillment_txid: info.txid.clone(),
+            change: None,
+        };
+        apply_deposit_reimbursement(&mut deposits_table, &reimbursement);
+        assert!(
+            matches!(deposit_state(&deposits_table), DepositState::Dispatched(_)),
+            "unfulfilled deposit must not be executed"
+        );
+
+        apply_withdrawal_fulfillment(&mut deposits_table, &info, DEADLINE - 1);
+        let fulfilled = deposit_state(&deposits_table).clone();
+
+        reimbursement.fulfillment_txid = ArbitraryGenerator::new().generate::<BitcoinTxid>();
+        apply_deposit_reimbursement(&mut deposits_table, &reimbursement);
+        assert_eq!(
+            deposit_state(&deposits_table),
+            &fulfilled,
+            "reimbursement for another fulfillment must be ignored"
+        );
+
+        let change_outpoint: OutputRef = ArbitraryGenerator::new().generate();
+        reimbursement.fulfillment_txid = info.txid.clone();
+        reimbursement.change = Some((change_outpoint.clone(), BitcoinAmount::from_int_btc(1)));
+        apply_deposit_reimbursement(&mut deposits_table, &reimbursement);
+        assert_eq!(deposit_state(&deposits_table), &DepositState::Executed);
+
+        let change_ent = deposits_table
+            .get_deposit(1)
+            .expect("change must be registered as a deposit");
+        assert_eq!(change_ent.output(), &change_outpoint);
+        assert_eq!(change_ent.amt(), BitcoinAmount::from_int_btc(1));
+        assert_eq!(change_ent.notary_operators(), &[0, 1, 2]);
+        assert_eq!(change_ent.deposit_state(), &DepositState::Accepted);
+    }
+}
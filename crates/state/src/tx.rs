@@ -1,9 +1,12 @@
 use arbitrary::Arbitrary;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use strata_primitives::l1::{BitcoinAmount, OutputRef};
+use strata_primitives::{
+    bridge::{OperatorIdx, WithdrawOutput},
+    l1::{BitcoinAmount, BitcoinTxid, OutputRef},
+};
 
-use crate::batch::SignedBatchCheckpoint;
+use crate::{batch::SignedBatchCheckpoint, operator_update::SignedOperatorUpdate};
 
 /// Information related to relevant transactions to be stored in L1Tx
 #[derive(
@@ -17,6 +20,14 @@ pub enum ProtocolOperation {
     DepositRequest(DepositRequestInfo),
     /// Checkpoint data
     Checkpoint(SignedBatchCheckpoint),
+    /// Forced inclusion of an EE transaction
+    ForcedInclusion(ForcedInclusionInfo),
+    /// Sequencer-signed change to the operator set
+    OperatorUpdate(SignedOperatorUpdate),
+    /// Payout of a withdrawal by an operator from its own wallet
+    WithdrawalFulfillment(WithdrawalFulfillmentInfo),
+    /// Spend of a deposit reimbursing the operator that fulfilled its withdrawal
+    DepositReimbursement(DepositReimbursementInfo),
     // TODO: add other kinds like Proofs and statediffs
 }
 
@@ -48,6 +59,53 @@ pub struct DepositRequestInfo {
     pub address: Vec<u8>,
 }
 
+#[derive(
+    Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, Arbitrary, Serialize, Deserialize,
+)]
+pub struct ForcedInclusionInfo {
+    /// Raw signed EE transaction
+    pub payload: Vec<u8>,
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, Arbitrary, Serialize, Deserialize,
+)]
+pub struct WithdrawalFulfillmentInfo {
+    /// outpoint of the deposit the withdrawal was dispatched against
+    pub deposit_outpoint: OutputRef,
+
+    /// idx of the operator that paid out the withdrawal and is to be reimbursed
+    pub operator_idx: OperatorIdx,
+
+    /// taproot outputs of the transaction, in order
+    pub outputs: Vec<WithdrawOutput>,
+
+    /// txid of the transaction paying out the withdrawal
+    pub txid: BitcoinTxid,
+
+    /// whether the withdrawal was paid out of the deposit itself rather than the operator's
+    /// wallet, in which case there's nothing left to reimburse
+    pub spends_deposit: bool,
+
+    /// outpoint and amount of the change sent back to the notaries of the deposit, if the
+    /// withdrawal was paid out of the deposit and there's any left
+    pub change: Option<(OutputRef, BitcoinAmount)>,
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, Arbitrary, Serialize, Deserialize,
+)]
+pub struct DepositReimbursementInfo {
+    /// outpoint of the deposit being spent
+    pub deposit_outpoint: OutputRef,
+
+    /// txid of the withdrawal fulfillment being reimbursed
+    pub fulfillment_txid: BitcoinTxid,
+
+    /// outpoint and amount of the change sent back to the notaries of the deposit, if any
+    pub change: Option<(OutputRef, BitcoinAmount)>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, Arbitrary)]
 pub struct InscriptionData {
     /// payload present in inscription transaction (either batchTx or checkpointTx)
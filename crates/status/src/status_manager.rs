@@ -136,6 +136,11 @@ impl StatusChannel {
         }
     }
 
+    /// Creates a receiver that is notified of every new [`Chainstate`].
+    pub fn subscribe_chain_state(&self) -> watch::Receiver<Option<Chainstate>> {
+        self.receiver.chs.clone()
+    }
+
     // Sender methods
 
     /// Sends the updated `Chainstate` to the chain state receiver. Logs a warning if the receiver
@@ -2,11 +2,13 @@ mod cache;
 mod exec;
 mod managers;
 pub mod ops;
+mod pruning;
 
 use std::sync::Arc;
 
 pub use managers::{checkpoint::CheckpointDbManager, l1::L1BlockManager, l2::L2BlockManager};
 pub use ops::l1tx_broadcast::BroadcastDbOps;
+pub use pruning::{pruning_task, PruneHorizon};
 use strata_db::traits::Database;
 
 /// A consolidation of database managers.
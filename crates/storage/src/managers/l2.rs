@@ -38,6 +38,13 @@ impl L2BlockManager {
         Ok(())
     }
 
+    /// Deletes a block from the database, purging cache entry.  Returns whether it existed.
+    pub fn del_block_data_blocking(&self, id: &L2BlockId) -> DbResult<bool> {
+        let deleted = self.ops.del_block_data_blocking(*id)?;
+        self.block_cache.purge(id);
+        Ok(deleted)
+    }
+
     /// Gets a block either in the cache or from the underlying database.
     pub async fn get_block_data_async(&self, id: &L2BlockId) -> DbResult<Option<L2BlockBundle>> {
         self.block_cache
@@ -26,6 +26,8 @@ inst_ops! {
         get_status(txid: Txid) => Option<BridgeDutyStatus>;
         put_duty_status(txid: Txid, status: BridgeDutyStatus) => ();
         delete_duty(txid: Txid) => Option<BridgeDutyStatus>;
+        put_fulfillment(txid: Txid, fulfillment_raw: Vec<u8>) => ();
+        get_fulfillment(txid: Txid) => Option<Vec<u8>>;
     }
 }
 
@@ -50,3 +52,18 @@ fn delete_duty<D: BridgeDutyDatabase + Sync + Send + 'static>(
 ) -> DbResult<Option<BridgeDutyStatus>> {
     context.db.delete_duty(txid.into())
 }
+
+fn put_fulfillment<D: BridgeDutyDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    txid: Txid,
+    fulfillment_raw: Vec<u8>,
+) -> DbResult<()> {
+    context.db.put_fulfillment(txid.into(), fulfillment_raw)
+}
+
+fn get_fulfillment<D: BridgeDutyDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    txid: Txid,
+) -> DbResult<Option<Vec<u8>>> {
+    context.db.get_fulfillment(txid.into())
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use strata_db::{traits::ChallengeReportDatabase, DbResult};
+use strata_primitives::buf::Buf32;
+use strata_state::bridge_duties::ChallengeReport;
+
+use crate::exec::*;
+
+/// Database context for a database operation interface.
+pub struct Context<D: ChallengeReportDatabase + Sync + Send + 'static> {
+    db: Arc<D>,
+}
+
+impl<D: ChallengeReportDatabase + Sync + Send + 'static> Context<D> {
+    pub fn new(db: Arc<D>) -> Self {
+        Self { db }
+    }
+
+    pub fn into_ops(self, pool: threadpool::ThreadPool) -> ChallengeReportOps {
+        ChallengeReportOps::new(pool, Arc::new(self))
+    }
+}
+
+inst_ops! {
+    (ChallengeReportOps, Context<D: ChallengeReportDatabase>) {
+        put_report(id: Buf32, report: ChallengeReport) => Option<u64>;
+        get_report(id: Buf32) => Option<ChallengeReport>;
+        get_report_by_idx(idx: u64) => Option<ChallengeReport>;
+        get_next_report_idx() => u64;
+        put_scanned_block(height: u64, block_hash: Buf32) => ();
+        get_scanned_block(height: u64) => Option<Buf32>;
+        get_last_scanned_height() => Option<u64>;
+    }
+}
+
+fn put_report<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    id: Buf32,
+    report: ChallengeReport,
+) -> DbResult<Option<u64>> {
+    context.db.put_report(id, report)
+}
+
+fn get_report<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    id: Buf32,
+) -> DbResult<Option<ChallengeReport>> {
+    context.db.get_report(id)
+}
+
+fn get_report_by_idx<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    idx: u64,
+) -> DbResult<Option<ChallengeReport>> {
+    context.db.get_report_by_idx(idx)
+}
+
+fn get_next_report_idx<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+) -> DbResult<u64> {
+    context.db.get_next_report_idx()
+}
+
+fn put_scanned_block<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    height: u64,
+    block_hash: Buf32,
+) -> DbResult<()> {
+    context.db.put_scanned_block(height, block_hash)
+}
+
+fn get_scanned_block<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+    height: u64,
+) -> DbResult<Option<Buf32>> {
+    context.db.get_scanned_block(height)
+}
+
+fn get_last_scanned_height<D: ChallengeReportDatabase + Sync + Send + 'static>(
+    context: &Context<D>,
+) -> DbResult<Option<u64>> {
+    context.db.get_last_scanned_height()
+}
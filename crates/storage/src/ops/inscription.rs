@@ -36,6 +36,8 @@ inst_ops! {
         get_blob_entry_id(idx: u64) => Option<Buf32>;
         get_next_blob_idx() => u64;
         put_blob_entry(id: Buf32, entry: BlobEntry) => ();
+        get_epoch_fees(epoch: u64) => Option<u64>;
+        put_epoch_fees(epoch: u64, fees: u64) => ();
     }
 }
 
@@ -79,3 +81,13 @@ fn put_blob_entry<D: SequencerDatabase>(
     let blob_db = ctx.db.blob_db();
     blob_db.put_blob_entry(id, entry)
 }
+
+fn get_epoch_fees<D: SequencerDatabase>(ctx: &Context<D>, epoch: u64) -> DbResult<Option<u64>> {
+    let blob_db = ctx.db.blob_db();
+    blob_db.get_epoch_fees(epoch)
+}
+
+fn put_epoch_fees<D: SequencerDatabase>(ctx: &Context<D>, epoch: u64, fees: u64) -> DbResult<()> {
+    let blob_db = ctx.db.blob_db();
+    blob_db.put_epoch_fees(epoch, fees)
+}
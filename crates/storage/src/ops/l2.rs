@@ -13,6 +13,7 @@ inst_ops_simple! {
         get_blocks_at_height(h: u64) => Vec<L2BlockId>;
         get_block_status(id: L2BlockId) => Option<BlockStatus>;
         put_block_data(block: L2BlockBundle) => ();
+        del_block_data(id: L2BlockId) => bool;
         set_block_status(id: L2BlockId, status: BlockStatus) => ();
     }
 }
@@ -2,8 +2,10 @@ pub mod bridge;
 pub mod bridge_duty;
 pub mod bridge_duty_index;
 pub mod bridge_relay;
+pub mod challenge_report;
 pub mod checkpoint;
 pub mod inscription;
 pub mod l1;
 pub mod l1tx_broadcast;
 pub mod l2;
+pub mod signer_session;
@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use strata_db::{entities::signer_session::SignerSession, traits::SignerSessionDatabase, DbResult};
+use strata_primitives::buf::Buf32;
+
+use crate::exec::*;
+
+inst_ops_simple! {
+    (<D: SignerSessionDatabase> => SignerSessionOps) {
+        put_session(txid: Buf32, session: SignerSession) => ();
+        delete_session(txid: Buf32) => Option<SignerSession>;
+        get_all_sessions() => Vec<(Buf32, SignerSession)>;
+    }
+}
@@ -0,0 +1,454 @@
+//! Pruning of the historical data that's no longer needed to follow the chain.
+//!
+//! Everything below the retention window configured with [`PruningConfig`] gets deleted, which is
+//! always at or below the last finalized checkpoint so none of it can be reorged out.  The
+//! [`PruneHorizon`] tracks how far the data has been pruned so that lookups of pruned data can be
+//! told apart from lookups of data that doesn't exist.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use strata_config::PruningConfig;
+use strata_db::{errors::DbError, traits::*, DbResult};
+use strata_tasks::ShutdownGuard;
+use tracing::*;
+
+use crate::L2BlockManager;
+
+/// Time between pruning passes.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Granularity of the checks for shutdown while waiting for the next pass.
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lowest indexes of the data that has been kept by pruning.
+#[derive(Debug, Default)]
+pub struct PruneHorizon {
+    l2_height: AtomicU64,
+    l1_height: AtomicU64,
+    sync_event_idx: AtomicU64,
+}
+
+impl PruneHorizon {
+    /// Loads the horizon from what's left in the database, so that it picks up where the last
+    /// pruning pass left off.
+    pub fn from_db(database: &impl Database) -> DbResult<Self> {
+        let l2_height = match database.chain_state_db().get_earliest_state_idx() {
+            Ok(idx) => idx,
+            Err(DbError::NotBootstrapped) => 0,
+            Err(e) => return Err(e),
+        };
+        let l1_height = database.l1_db().get_earliest_txs_idx()?.unwrap_or(0);
+        let sync_event_idx = database.sync_event_db().get_first_idx()?.unwrap_or(0);
+
+        Ok(Self {
+            l2_height: AtomicU64::new(l2_height),
+            l1_height: AtomicU64::new(l1_height),
+            sync_event_idx: AtomicU64::new(sync_event_idx),
+        })
+    }
+
+    /// Lowest L2 height whose block and chainstate are kept.
+    pub fn l2_height(&self) -> u64 {
+        self.l2_height.load(Ordering::Relaxed)
+    }
+
+    /// Lowest L1 height whose relevant txs are kept.
+    pub fn l1_height(&self) -> u64 {
+        self.l1_height.load(Ordering::Relaxed)
+    }
+
+    /// Lowest index of the sync events that are kept.
+    pub fn sync_event_idx(&self) -> u64 {
+        self.sync_event_idx.load(Ordering::Relaxed)
+    }
+
+    pub fn is_l2_pruned(&self, height: u64) -> bool {
+        height < self.l2_height()
+    }
+
+    pub fn is_l1_pruned(&self, height: u64) -> bool {
+        height < self.l1_height()
+    }
+
+    pub fn is_sync_event_pruned(&self, idx: u64) -> bool {
+        idx < self.sync_event_idx()
+    }
+}
+
+/// Where the data stops being pruned, as computed from the last finalized checkpoint.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct PruneTargets {
+    l2_height: u64,
+    l1_height: u64,
+    sync_event_idx: u64,
+}
+
+/// Periodically prunes the data below the retention window set in `config`.  Returns right away
+/// in archive mode.
+pub fn pruning_task<D: Database>(
+    shutdown: ShutdownGuard,
+    database: Arc<D>,
+    l2_block_manager: Arc<L2BlockManager>,
+    config: PruningConfig,
+    horizon: Arc<PruneHorizon>,
+) -> anyhow::Result<()> {
+    if config == PruningConfig::Archive {
+        info!("running in archive mode, not pruning");
+        return Ok(());
+    }
+
+    info!(?config, "starting pruning task");
+    loop {
+        if let Err(e) = prune(database.as_ref(), &l2_block_manager, config, &horizon) {
+            warn!(err = %e, "failed to prune historical data");
+        }
+
+        let mut waited = Duration::ZERO;
+        while waited < PRUNE_INTERVAL {
+            if shutdown.should_shutdown() {
+                info!("pruning task received shutdown signal");
+                return Ok(());
+            }
+            thread::sleep(SHUTDOWN_CHECK_INTERVAL);
+            waited += SHUTDOWN_CHECK_INTERVAL;
+        }
+    }
+}
+
+/// Does a single pruning pass, moving the horizon forward as the data gets deleted.
+///
+/// The horizon is moved before the data below it is deleted, so that readers that find some data
+/// missing can tell it was pruned by checking the horizon afterwards.  If a deletion fails, the
+/// data left below the horizon is only cleaned up by a pass after a restart.
+fn prune<D: Database>(
+    database: &D,
+    l2_block_manager: &L2BlockManager,
+    config: PruningConfig,
+    horizon: &PruneHorizon,
+) -> DbResult<()> {
+    let Some(targets) = find_prune_targets(database, config, horizon)? else {
+        trace!("nothing to prune yet");
+        return Ok(());
+    };
+
+    let start = horizon.l2_height();
+    if targets.l2_height > start {
+        debug!(%start, end = %targets.l2_height, "pruning L2 blocks and chainstates");
+        horizon
+            .l2_height
+            .store(targets.l2_height, Ordering::Relaxed);
+        for height in start..targets.l2_height {
+            for blkid in l2_block_manager.get_blocks_at_height_blocking(height)? {
+                l2_block_manager.del_block_data_blocking(&blkid)?;
+            }
+        }
+        database
+            .chain_state_db()
+            .purge_historical_state_before(targets.l2_height)?;
+    }
+
+    let start = horizon.l1_height();
+    if targets.l1_height > start {
+        debug!(%start, end = %targets.l1_height, "pruning L1 txs");
+        horizon
+            .l1_height
+            .store(targets.l1_height, Ordering::Relaxed);
+        database.l1_db().purge_block_txs(start, targets.l1_height)?;
+    }
+
+    let start = horizon.sync_event_idx();
+    if targets.sync_event_idx > start {
+        debug!(%start, end = %targets.sync_event_idx, "pruning sync events");
+        horizon
+            .sync_event_idx
+            .store(targets.sync_event_idx, Ordering::Relaxed);
+        database
+            .sync_event_db()
+            .clear_sync_event(start, targets.sync_event_idx)?;
+    }
+
+    Ok(())
+}
+
+/// Computes how far the data can be pruned, or `None` if nothing has been finalized yet.
+fn find_prune_targets<D: Database>(
+    database: &D,
+    config: PruningConfig,
+    horizon: &PruneHorizon,
+) -> DbResult<Option<PruneTargets>> {
+    let cs_db = database.client_state_db();
+    let cs_idx = match cs_db.get_last_checkpoint_idx() {
+        Ok(idx) => idx,
+        Err(DbError::NotBootstrapped) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let Some(client_state) = cs_db.get_state_checkpoint(cs_idx)? else {
+        return Ok(None);
+    };
+    let Some(finalized) = client_state.l1_view().last_finalized_checkpoint() else {
+        return Ok(None);
+    };
+
+    let (_, finalized_height) = finalized.batch_info.l2_range;
+    let l2_height = match config {
+        PruningConfig::Archive => return Ok(None),
+        PruningConfig::Full => finalized_height,
+        PruningConfig::Custom { epochs } => {
+            let Some(epoch) = finalized.batch_info.idx().checked_sub(epochs) else {
+                return Ok(None);
+            };
+            let Some(height) = find_epoch_end(database, epoch)? else {
+                return Ok(None);
+            };
+            height
+        }
+    };
+
+    // What's below the horizon is gone already, so we can't go back there.
+    let l2_height = l2_height.max(horizon.l2_height());
+
+    // The L1 blocks below the safe block of the retained chainstate have been fully processed.
+    let chainstate = database
+        .chain_state_db()
+        .get_toplevel_state(l2_height)?
+        .ok_or(DbError::MissingL2State(l2_height))?;
+    let l1_height = chainstate.l1_view().safe_height();
+
+    // The client state can be rebuilt from its last checkpoint without the earlier events.
+    let sync_event_idx = cs_idx;
+
+    Ok(Some(PruneTargets {
+        l2_height,
+        l1_height,
+        sync_event_idx,
+    }))
+}
+
+/// Gets the last L2 height in `epoch` from the range covered by its checkpoint, or `None` if we
+/// don't have that checkpoint.
+fn find_epoch_end<D: Database>(database: &D, epoch: u64) -> DbResult<Option<u64>> {
+    let checkpoint = database.checkpoint_db().get_batch_checkpoint(epoch)?;
+    Ok(checkpoint.map(|entry| entry.batch_info.l2_range.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use strata_db::types::CheckpointEntry;
+    use strata_primitives::l1::L1BlockManifest;
+    use strata_rocksdb::test_utils::get_common_db;
+    use strata_state::{
+        chain_state::Chainstate,
+        client_state::{ClientState, L1Checkpoint},
+        genesis::GenesisStateData,
+        l1::L1ViewState,
+        state_op::WriteBatch,
+        sync_event::SyncEvent,
+    };
+    use strata_test_utils::{l2::gen_l2_chain, ArbitraryGenerator};
+    use threadpool::ThreadPool;
+
+    use super::*;
+
+    /// Number of L2 blocks in each epoch of the test chain.
+    const EPOCH_LEN: u64 = 10;
+
+    /// Index of the client state checkpoint holding the finalized checkpoint.
+    const CLIENT_STATE_IDX: u64 = 5;
+
+    /// Makes a chainstate whose L1 view is safe up to `l1_height`.
+    fn chainstate_at(l1_height: u64) -> Chainstate {
+        let mut arb = ArbitraryGenerator::new();
+        let l1_state = L1ViewState::new_at_horizon(l1_height, arb.generate(), arb.generate());
+        let gdata = GenesisStateData::new(arb.generate(), l1_state, arb.generate(), arb.generate());
+        Chainstate::from_genesis(&gdata)
+    }
+
+    /// Stores the L2 blocks, chainstates and checkpoints of `num_epochs` epochs along with as many
+    /// L1 blocks and sync events.  The chainstate at each height is safe up to the same L1 height.
+    /// If `finalized_epoch` is set, the client state has finalized the checkpoint of that epoch.
+    fn populate(database: &impl Database, num_epochs: u64, finalized_epoch: Option<u64>) {
+        let mut arb = ArbitraryGenerator::new();
+        let last_height = num_epochs * EPOCH_LEN;
+
+        for block in gen_l2_chain(None, last_height as usize) {
+            database.l2_db().put_block_data(block).unwrap();
+        }
+
+        let chs_db = database.chain_state_db();
+        chs_db.write_genesis_state(&chainstate_at(0)).unwrap();
+        for height in 1..=last_height {
+            let batch = WriteBatch::new_replace(chainstate_at(height));
+            chs_db.write_state_update(height, &batch).unwrap();
+        }
+
+        for height in 0..=last_height {
+            let mf: L1BlockManifest = arb.generate();
+            database
+                .l1_db()
+                .put_block_data(height, mf, Vec::new())
+                .unwrap();
+        }
+
+        for _ in 0..=last_height {
+            let ev: SyncEvent = arb.generate();
+            database.sync_event_db().write_sync_event(ev).unwrap();
+        }
+
+        let mut checkpoints = Vec::new();
+        for epoch in 0..num_epochs {
+            let mut entry: CheckpointEntry = arb.generate();
+            entry.batch_info.idx = epoch;
+            entry.batch_info.l2_range = (epoch * EPOCH_LEN + 1, (epoch + 1) * EPOCH_LEN);
+            database
+                .checkpoint_db()
+                .put_batch_checkpoint(epoch, entry.clone())
+                .unwrap();
+            checkpoints.push(entry);
+        }
+
+        if let Some(epoch) = finalized_epoch {
+            let entry = &checkpoints[epoch as usize];
+            let checkpoint =
+                L1Checkpoint::new(entry.batch_info.clone(), entry.bootstrap.clone(), true, 0);
+            let state = ClientState::from_finalized_checkpoint(0, 0, checkpoint, arb.generate());
+            database
+                .client_state_db()
+                .write_client_state_checkpoint(CLIENT_STATE_IDX, state)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_epoch_end() {
+        let db = get_common_db();
+        populate(db.as_ref(), 3, None);
+
+        for epoch in 0..3 {
+            let end = find_epoch_end(db.as_ref(), epoch).unwrap();
+            assert_eq!(end, Some((epoch + 1) * EPOCH_LEN));
+        }
+        assert_eq!(
+            find_epoch_end(db.as_ref(), 3).unwrap(),
+            None,
+            "epoch without a checkpoint"
+        );
+    }
+
+    #[test]
+    fn test_find_prune_targets() {
+        let db = get_common_db();
+        let horizon = PruneHorizon::default();
+
+        let targets = find_prune_targets(db.as_ref(), PruningConfig::Full, &horizon).unwrap();
+        assert_eq!(targets, None, "nothing is finalized yet");
+
+        populate(db.as_ref(), 4, Some(3));
+
+        let targets = find_prune_targets(db.as_ref(), PruningConfig::Full, &horizon).unwrap();
+        assert_eq!(
+            targets,
+            Some(PruneTargets {
+                l2_height: 4 * EPOCH_LEN,
+                l1_height: 4 * EPOCH_LEN,
+                sync_event_idx: CLIENT_STATE_IDX,
+            })
+        );
+
+        let config = PruningConfig::Custom { epochs: 2 };
+        let targets = find_prune_targets(db.as_ref(), config, &horizon).unwrap();
+        assert_eq!(
+            targets,
+            Some(PruneTargets {
+                l2_height: 2 * EPOCH_LEN,
+                l1_height: 2 * EPOCH_LEN,
+                sync_event_idx: CLIENT_STATE_IDX,
+            }),
+            "the epochs after the end of epoch 1 are kept"
+        );
+
+        let config = PruningConfig::Custom { epochs: 4 };
+        let targets = find_prune_targets(db.as_ref(), config, &horizon).unwrap();
+        assert_eq!(targets, None, "not enough epochs finalized yet");
+
+        let targets = find_prune_targets(db.as_ref(), PruningConfig::Archive, &horizon).unwrap();
+        assert_eq!(targets, None);
+
+        horizon.l2_height.store(3 * EPOCH_LEN, Ordering::Relaxed);
+        let config = PruningConfig::Custom { epochs: 2 };
+        let targets = find_prune_targets(db.as_ref(), config, &horizon).unwrap();
+        assert_eq!(
+            targets.map(|t| t.l2_height),
+            Some(3 * EPOCH_LEN),
+            "targets never go below the horizon"
+        );
+    }
+
+    #[test]
+    fn test_prune() {
+        let db = get_common_db();
+        populate(db.as_ref(), 4, Some(3));
+        let l2_block_manager = L2BlockManager::new(ThreadPool::new(1), db.clone());
+
+        let horizon = PruneHorizon::from_db(db.as_ref()).unwrap();
+        assert_eq!(horizon.l2_height(), 0);
+        assert_eq!(horizon.l1_height(), 0);
+        assert_eq!(horizon.sync_event_idx(), 1);
+
+        let config = PruningConfig::Custom { epochs: 2 };
+        prune(db.as_ref(), &l2_block_manager, config, &horizon).unwrap();
+
+        let height = 2 * EPOCH_LEN;
+        assert_eq!(horizon.l2_height(), height);
+        assert_eq!(horizon.l1_height(), height);
+        assert_eq!(horizon.sync_event_idx(), CLIENT_STATE_IDX);
+
+        // Everything below the horizon is gone while the rest is kept.
+        let blocks_at = |h| l2_block_manager.get_blocks_at_height_blocking(h).unwrap();
+        assert!(blocks_at(height - 1).is_empty());
+        assert_eq!(blocks_at(height).len(), 1);
+
+        let chs_db = db.chain_state_db();
+        assert!(chs_db.get_toplevel_state(height - 1).unwrap().is_none());
+        assert!(chs_db.get_toplevel_state(height).unwrap().is_some());
+
+        assert!(matches!(
+            db.l1_db().get_block_txs(height - 1),
+            Err(DbError::MissingL1BlockBody(_))
+        ));
+        assert!(db.l1_db().get_block_txs(height).unwrap().is_some());
+
+        let sync_ev_db = db.sync_event_db();
+        assert!(sync_ev_db
+            .get_sync_event(CLIENT_STATE_IDX - 1)
+            .unwrap()
+            .is_none());
+        assert!(sync_ev_db
+            .get_sync_event(CLIENT_STATE_IDX)
+            .unwrap()
+            .is_some());
+
+        // The horizon is picked up again after a restart.
+        let reloaded = PruneHorizon::from_db(db.as_ref()).unwrap();
+        assert_eq!(reloaded.l2_height(), height);
+        assert_eq!(reloaded.l1_height(), height);
+        assert_eq!(reloaded.sync_event_idx(), CLIENT_STATE_IDX);
+
+        prune(
+            db.as_ref(),
+            &l2_block_manager,
+            PruningConfig::Full,
+            &reloaded,
+        )
+        .unwrap();
+        assert_eq!(reloaded.l2_height(), 4 * EPOCH_LEN);
+        assert_eq!(reloaded.l1_height(), 4 * EPOCH_LEN);
+        assert!(blocks_at(4 * EPOCH_LEN - 1).is_empty());
+    }
+}
@@ -0,0 +1,491 @@
+//! Bootstrapping a new node from the latest checkpoint finalized on L1, so that it doesn't have
+//! to replay every L2 block since genesis.
+
+use strata_btcio::{reader::query::get_verification_state, rpc::traits::Reader};
+use strata_consensus_logic::l1_handler::{check_for_da_batch, store_l1_block_data, verify_proof};
+use strata_db::traits::{ChainstateDatabase, ClientStateDatabase, Database};
+use strata_primitives::params::Params;
+use strata_state::{
+    batch::BatchCheckpoint,
+    client_state::{ClientState, L1Checkpoint},
+    header::L2Header,
+    l1::{BtcParams, HeaderVerificationState},
+};
+use strata_storage::L2BlockManager;
+use strata_tx_parser::{
+    filter::filter_protocol_op_tx_refs, filter_types::TxFilterConfig, messages::BlockData,
+};
+use tracing::*;
+
+use crate::{L2SyncError, SyncClient};
+
+/// Bootstraps an empty database from the latest proven checkpoint that is buried deep enough in
+/// L1 to be final, instead of starting from genesis.
+///
+/// The checkpoint's proof is verified with the rollup verifying key, and the chainstate snapshot
+/// and last L2 block fetched from the sync peer are checked against its final state before
+/// anything gets written.  Returns the epoch of the checkpoint, or `None` if there's no
+/// checkpoint to bootstrap from yet, in which case nothing is written.
+pub async fn bootstrap_from_checkpoint<T: SyncClient>(
+    client: &T,
+    l1_client: &impl Reader,
+    database: &impl Database,
+    l2_block_manager: &L2BlockManager,
+    params: &Params,
+) -> Result<Option<u64>, L2SyncError> {
+    let filter_config = TxFilterConfig::derive_from(params.rollup())
+        .map_err(|e| L2SyncError::FilterConfig(e.to_string()))?;
+    let Some((height, checkpoint)) =
+        find_latest_finalized_checkpoint(l1_client, &filter_config, params).await?
+    else {
+        info!("no finalized checkpoint on L1 to bootstrap from");
+        return Ok(None);
+    };
+
+    let batch_info = checkpoint.batch_info();
+    let epoch = batch_info.idx();
+    let (_, last_l1_height) = batch_info.l1_range;
+    let (_, last_l2_height) = batch_info.l2_range;
+    let blkid = *batch_info.l2_blockid();
+    let final_state = batch_info.get_final_bootstrap_state();
+    info!(%epoch, %height, %last_l2_height, %blkid, "bootstrapping from checkpoint");
+
+    // Check the snapshot and block against what the proof commits to.
+    let chainstate = client
+        .get_chainstate_snapshot(epoch)
+        .await?
+        .ok_or(L2SyncError::MissingSnapshot(epoch))?;
+    if chainstate.compute_state_root() != final_state.initial_l2_state {
+        return Err(L2SyncError::InvalidSnapshot(epoch, "state root mismatch"));
+    }
+    if chainstate.chain_tip_blockid() != blkid || chainstate.chain_tip_slot() != last_l2_height {
+        return Err(L2SyncError::InvalidSnapshot(epoch, "chain tip mismatch"));
+    }
+
+    let block = client
+        .get_block_by_id(&blkid)
+        .await?
+        .ok_or(L2SyncError::MissingBlock(blkid))?;
+    // The header's state root is computed before the block's own id is known, so it can't match
+    // the final state, which the block is tied to through the chainstate's tip instead.
+    if block.header().get_blockid() != blkid || block.header().blockidx() != last_l2_height {
+        return Err(L2SyncError::InvalidSnapshot(epoch, "block mismatch"));
+    }
+
+    // Rebuild the L1 header verification state at the end of the checkpoint's L1 range.  The
+    // CSM moves it forward from there using the blocks we store below.
+    //
+    // The PoW accumulated since genesis can't be rebuilt from the few blocks fetched here, so it
+    // is taken from the checkpoint, which is only sound because the whole state is then checked
+    // against the hash the proof commits to.
+    let btc_params = BtcParams::from(params.network());
+    let l1_vs = get_verification_state(l1_client, last_l1_height + 1, &btc_params)
+        .await
+        .map_err(|e| L2SyncError::L1Client(e.to_string()))?;
+    let l1_vs = HeaderVerificationState {
+        total_accumulated_pow: batch_info.final_acc_pow(),
+        ..l1_vs
+    };
+    if l1_vs.compute_hash().ok().as_ref() != Some(batch_info.final_l1_state_hash()) {
+        return Err(L2SyncError::InvalidL1State(epoch));
+    }
+
+    // Store the L1 blocks since the end of the checkpoint's L1 range, which the L1 reader then
+    // continues from.
+    let l1_db = database.l1_db();
+    for l1_height in (last_l1_height + 1).min(height)..=height {
+        let blockdata = fetch_block_data(l1_client, l1_height, &filter_config).await?;
+        store_l1_block_data(l1_db.as_ref(), &blockdata, epoch + 1)?;
+    }
+
+    database
+        .chain_state_db()
+        .write_bootstrap_state(last_l2_height, &chainstate)?;
+    l2_block_manager.put_block_data_async(block).await?;
+
+    // Written last since this is what marks the database as initialized.
+    let l1_checkpoint = L1Checkpoint::new(
+        batch_info.clone(),
+        checkpoint.bootstrap_state().clone(),
+        true,
+        height,
+    );
+    let client_state = ClientState::from_finalized_checkpoint(
+        params.rollup().horizon_l1_height,
+        params.rollup().genesis_l1_height,
+        l1_checkpoint,
+        l1_vs,
+    );
+    database
+        .client_state_db()
+        .write_client_state_checkpoint(0, client_state)?;
+
+    info!(%epoch, "bootstrapped from checkpoint");
+    Ok(Some(epoch))
+}
+
+/// How many L1 blocks back from the last final one we look for a checkpoint to bootstrap from.
+/// If none got posted in that window, syncing from genesis is cheaper than downloading every
+/// block back to it.
+const MAX_CHECKPOINT_SCAN_DEPTH: u64 = 1_000;
+
+/// Scans L1 backwards from the last final block, at most [`MAX_CHECKPOINT_SCAN_DEPTH`] blocks
+/// deep, for the most recent checkpoint with a valid proof, returning it along with the height of
+/// the block it was included in.
+async fn find_latest_finalized_checkpoint(
+    l1_client: &impl Reader,
+    filter_config: &TxFilterConfig,
+    params: &Params,
+) -> Result<Option<(u64, BatchCheckpoint)>, L2SyncError> {
+    let rollup = params.rollup();
+    let tip = l1_client
+        .get_block_count()
+        .await
+        .map_err(|e| L2SyncError::L1Client(e.to_string()))?;
+    let final_height = tip.saturating_sub(rollup.l1_reorg_safe_depth as u64);
+    let lowest_height = (rollup.genesis_l1_height + 1)
+        .max(final_height.saturating_sub(MAX_CHECKPOINT_SCAN_DEPTH - 1));
+
+    for height in (lowest_height..=final_height).rev() {
+        let blockdata = fetch_block_data(l1_client, height, filter_config).await?;
+        let checkpoint = check_for_da_batch(&blockdata, &rollup.cred_rule)
+            .into_iter()
+            .filter(|checkpoint| {
+                let proof_receipt = checkpoint.get_proof_receipt();
+                verify_proof(checkpoint, &proof_receipt, rollup).is_ok()
+            })
+            .max_by_key(|checkpoint| checkpoint.batch_info().idx());
+
+        if let Some(checkpoint) = checkpoint {
+            return Ok(Some((height, checkpoint)));
+        }
+    }
+
+    Ok(None)
+}
+
+async fn fetch_block_data(
+    l1_client: &impl Reader,
+    height: u64,
+    filter_config: &TxFilterConfig,
+) -> Result<BlockData, L2SyncError> {
+    let block = l1_client
+        .get_block_at(height)
+        .await
+        .map_err(|e| L2SyncError::L1Client(e.to_string()))?;
+    let txs = filter_protocol_op_tx_refs(&block, filter_config);
+    Ok(BlockData::new(height, block, txs))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use bitcoin::{
+        absolute::LockTime,
+        block::{Header, Version as BlockVersion},
+        hashes::Hash,
+        transaction::Version,
+        Block, BlockHash, CompactTarget, Network, Transaction, TxIn, TxMerkleNode, Txid, Witness,
+    };
+    use futures::stream::{self, Stream};
+    use strata_btcio::{
+        rpc::{error::ClientError as L1ClientError, types::GetBlockchainInfo, ClientResult},
+        test_utils::generate_inscription_script_test,
+    };
+    use strata_consensus_logic::genesis::make_genesis_block;
+    use strata_db::traits::L1Database;
+    use strata_primitives::{
+        buf::{Buf32, Buf64},
+        proof::RollupVerifyingKey,
+    };
+    use strata_rocksdb::test_utils::get_common_db;
+    use strata_rpc_types::RpcSyncStatus;
+    use strata_state::{
+        batch::{BatchInfo, SignedBatchCheckpoint},
+        block::L2BlockBundle,
+        chain_state::Chainstate,
+        id::L2BlockId,
+        tx::InscriptionData,
+    };
+    use strata_test_utils::l2::{gen_params, get_genesis_chainstate};
+    use strata_zkvm::Proof;
+    use threadpool::ThreadPool;
+
+    use super::*;
+    use crate::ClientError;
+
+    const L1_TIP: u64 = 20;
+    const CHECKPOINT_HEIGHT: u64 = 15;
+    const LAST_L1_HEIGHT: u64 = 8;
+    const ACC_POW: u128 = 1_000;
+
+    struct TestL1Client {
+        blocks: Vec<Block>,
+    }
+
+    impl TestL1Client {
+        fn new() -> Self {
+            let mut prev_blockhash = BlockHash::all_zeros();
+            let blocks = (0..=L1_TIP)
+                .map(|height| {
+                    let block = Block {
+                        header: Header {
+                            version: BlockVersion::TWO,
+                            prev_blockhash,
+                            merkle_root: TxMerkleNode::all_zeros(),
+                            time: 1_700_000_000 + height as u32 * 600,
+                            bits: CompactTarget::from_consensus(0x207fffff),
+                            nonce: 0,
+                        },
+                        txdata: vec![],
+                    };
+                    prev_blockhash = block.block_hash();
+                    block
+                })
+                .collect();
+            Self { blocks }
+        }
+
+        /// Includes the checkpoint in the block at [`CHECKPOINT_HEIGHT`].
+        fn include_checkpoint(&mut self, checkpoint: BatchCheckpoint, params: &Params) {
+            let signed_checkpoint = SignedBatchCheckpoint::new(checkpoint, Buf64::zero());
+            let inscription_data = InscriptionData::new(borsh::to_vec(&signed_checkpoint).unwrap());
+            let script =
+                generate_inscription_script_test(inscription_data, &params.rollup().rollup_name, 1)
+                    .unwrap();
+
+            let mut witness = Witness::new();
+            witness.push([1; 64]);
+            witness.push(script);
+            witness.push([0xc0; 33]);
+            let tx = Transaction {
+                version: Version::TWO,
+                lock_time: LockTime::ZERO,
+                input: vec![TxIn {
+                    witness,
+                    ..Default::default()
+                }],
+                output: vec![],
+            };
+            self.blocks[CHECKPOINT_HEIGHT as usize].txdata.push(tx);
+        }
+    }
+
+    #[async_trait]
+    impl Reader for TestL1Client {
+        async fn estimate_smart_fee(&self, _conf_target: u16) -> ClientResult<u64> {
+            unimplemented!()
+        }
+
+        async fn get_block(&self, hash: &BlockHash) -> ClientResult<Block> {
+            self.blocks
+                .iter()
+                .find(|block| block.block_hash() == *hash)
+                .cloned()
+                .ok_or_else(|| L1ClientError::Other(format!("missing block {hash}")))
+        }
+
+        async fn get_block_at(&self, height: u64) -> ClientResult<Block> {
+            self.blocks
+                .get(height as usize)
+                .cloned()
+                .ok_or_else(|| L1ClientError::Other(format!("missing block at {height}")))
+        }
+
+        async fn get_block_count(&self) -> ClientResult<u64> {
+            Ok(self.blocks.len() as u64 - 1)
+        }
+
+        async fn get_block_hash(&self, height: u64) -> ClientResult<BlockHash> {
+            self.get_block_at(height)
+                .await
+                .map(|block| block.block_hash())
+        }
+
+        async fn get_blockchain_info(&self) -> ClientResult<GetBlockchainInfo> {
+            unimplemented!()
+        }
+
+        async fn get_raw_mempool(&self) -> ClientResult<Vec<Txid>> {
+            Ok(vec![])
+        }
+
+        async fn network(&self) -> ClientResult<Network> {
+            Ok(Network::Regtest)
+        }
+    }
+
+    struct TestSyncClient {
+        chainstate: Chainstate,
+        block: L2BlockBundle,
+    }
+
+    #[async_trait]
+    impl SyncClient for TestSyncClient {
+        async fn get_sync_status(&self) -> Result<RpcSyncStatus, ClientError> {
+            unimplemented!()
+        }
+
+        fn get_blocks_range(
+            &self,
+            _start_height: u64,
+            _end_height: u64,
+        ) -> impl Stream<Item = L2BlockBundle> {
+            stream::empty()
+        }
+
+        async fn get_block_by_id(
+            &self,
+            block_id: &L2BlockId,
+        ) -> Result<Option<L2BlockBundle>, ClientError> {
+            Ok((self.block.header().get_blockid() == *block_id).then(|| self.block.clone()))
+        }
+
+        async fn get_chainstate_snapshot(
+            &self,
+            _epoch: u64,
+        ) -> Result<Option<Chainstate>, ClientError> {
+            Ok(Some(self.chainstate.clone()))
+        }
+    }
+
+    fn get_params() -> Params {
+        let mut params = gen_params();
+        params.rollup.horizon_l1_height = 0;
+        params.rollup.genesis_l1_height = 0;
+        params.rollup.rollup_vk = RollupVerifyingKey::NativeVerifyingKey(Buf32::zero());
+        params
+    }
+
+    fn get_sync_client() -> TestSyncClient {
+        TestSyncClient {
+            chainstate: get_genesis_chainstate(),
+            block: make_genesis_block(&gen_params()),
+        }
+    }
+
+    /// Returns the L1 verification state at the end of the checkpoint's L1 range.
+    async fn get_l1_vs(l1_client: &TestL1Client) -> HeaderVerificationState {
+        let btc_params = BtcParams::from(Network::Regtest);
+        let l1_vs = get_verification_state(l1_client, LAST_L1_HEIGHT + 1, &btc_params)
+            .await
+            .unwrap();
+        HeaderVerificationState {
+            total_accumulated_pow: ACC_POW,
+            ..l1_vs
+        }
+    }
+
+    fn gen_checkpoint(final_l1_state_hash: Buf32, chainstate: &Chainstate) -> BatchCheckpoint {
+        let batch_info = BatchInfo::new(
+            0,
+            (1, LAST_L1_HEIGHT),
+            (0, chainstate.chain_tip_slot()),
+            (Buf32::zero(), final_l1_state_hash),
+            (Buf32::zero(), chainstate.compute_state_root()),
+            chainstate.chain_tip_blockid(),
+            (0, ACC_POW),
+            Buf32::zero(),
+        );
+        let bootstrap = batch_info.get_initial_bootstrap_state();
+        BatchCheckpoint::new(batch_info, bootstrap, Proof::default())
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_from_checkpoint() {
+        let params = get_params();
+        let db = get_common_db();
+        let l2_block_manager = L2BlockManager::new(ThreadPool::new(1), db.clone());
+        let sync_client = get_sync_client();
+
+        let mut l1_client = TestL1Client::new();
+        let l1_vs = get_l1_vs(&l1_client).await;
+        let checkpoint = gen_checkpoint(l1_vs.compute_hash().unwrap(), &sync_client.chainstate);
+        l1_client.include_checkpoint(checkpoint, &params);
+
+        let epoch = bootstrap_from_checkpoint(
+            &sync_client,
+            &l1_client,
+            db.as_ref(),
+            &l2_block_manager,
+            &params,
+        )
+        .await
+        .unwrap();
+        assert_eq!(epoch, Some(0));
+
+        let client_state = db
+            .client_state_db()
+            .get_state_checkpoint(0)
+            .unwrap()
+            .expect("client state should be written");
+        assert_eq!(
+            client_state.l1_view().tip_verification_state(),
+            Some(&l1_vs),
+            "L1 verification state should be rebuilt at the end of the checkpoint"
+        );
+        assert_eq!(
+            db.chain_state_db().get_toplevel_state(0).unwrap(),
+            Some(sync_client.chainstate.clone())
+        );
+        assert_eq!(
+            db.l1_db().get_chain_tip().unwrap(),
+            Some(CHECKPOINT_HEIGHT),
+            "L1 blocks should be stored up to the checkpoint"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_from_checkpoint_l1_state_mismatch() {
+        let params = get_params();
+        let db = get_common_db();
+        let l2_block_manager = L2BlockManager::new(ThreadPool::new(1), db.clone());
+        let sync_client = get_sync_client();
+
+        let mut l1_client = TestL1Client::new();
+        let checkpoint = gen_checkpoint(Buf32::zero(), &sync_client.chainstate);
+        l1_client.include_checkpoint(checkpoint, &params);
+
+        let res = bootstrap_from_checkpoint(
+            &sync_client,
+            &l1_client,
+            db.as_ref(),
+            &l2_block_manager,
+            &params,
+        )
+        .await;
+        assert!(
+            matches!(res, Err(L2SyncError::InvalidL1State(0))),
+            "should abort on a mismatching L1 state"
+        );
+        assert!(db
+            .client_state_db()
+            .get_state_checkpoint(0)
+            .unwrap()
+            .is_none());
+        assert_eq!(db.l1_db().get_chain_tip().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_without_checkpoint() {
+        let params = get_params();
+        let db = get_common_db();
+        let l2_block_manager = L2BlockManager::new(ThreadPool::new(1), db.clone());
+
+        let epoch = bootstrap_from_checkpoint(
+            &get_sync_client(),
+            &TestL1Client::new(),
+            db.as_ref(),
+            &l2_block_manager,
+            &params,
+        )
+        .await
+        .unwrap();
+        assert_eq!(epoch, None);
+        assert!(db
+            .client_state_db()
+            .get_state_checkpoint(0)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,10 +1,21 @@
-use std::cmp::min;
+use std::{
+    cmp::min,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
-use futures::stream::{self, Stream, StreamExt};
+use futures::{
+    future,
+    stream::{self, Stream, StreamExt},
+};
 use strata_rpc_api::StrataApiClient;
 use strata_rpc_types::RpcSyncStatus;
-use strata_state::{block::L2BlockBundle, id::L2BlockId};
-use tracing::error;
+use strata_state::{
+    block::L2BlockBundle, chain_state::Chainstate, header::L2Header, id::L2BlockId,
+};
+use tracing::{debug, error};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ClientError {
@@ -30,6 +41,9 @@ pub trait SyncClient {
         &self,
         block_id: &L2BlockId,
     ) -> Result<Option<L2BlockBundle>, ClientError>;
+
+    /// Fetches the chainstate at the end of the checkpoint for `epoch`.
+    async fn get_chainstate_snapshot(&self, epoch: u64) -> Result<Option<Chainstate>, ClientError>;
 }
 
 pub struct RpcSyncPeer<RPC: StrataApiClient + Send + Sync> {
@@ -106,4 +120,103 @@ impl<RPC: StrataApiClient + Send + Sync> SyncClient for RpcSyncPeer<RPC> {
 
         borsh::from_slice(&bytes.0).map_err(|err| ClientError::Deserialization(err.to_string()))
     }
+
+    async fn get_chainstate_snapshot(&self, epoch: u64) -> Result<Option<Chainstate>, ClientError> {
+        let Some(bytes) = self
+            .rpc_client
+            .get_chainstate_snapshot(epoch)
+            .await
+            .map_err(|e| ClientError::Network(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+
+        borsh::from_slice(&bytes.0)
+            .map(Some)
+            .map_err(|err| ClientError::Deserialization(err.to_string()))
+    }
+}
+
+/// Syncs from `primary`, falling back to `fallback` for whatever `primary` fails to serve.
+pub struct FallbackSyncPeer<P, F> {
+    primary: P,
+    fallback: F,
+}
+
+impl<P, F> FallbackSyncPeer<P, F> {
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: SyncClient + Send + Sync, F: SyncClient + Send + Sync> SyncClient
+    for FallbackSyncPeer<P, F>
+{
+    /// Picks the status with the higher tip, so that a lagging primary doesn't hold back sync.
+    async fn get_sync_status(&self) -> Result<RpcSyncStatus, ClientError> {
+        let (primary, fallback) = futures::join!(
+            self.primary.get_sync_status(),
+            self.fallback.get_sync_status()
+        );
+        match (primary, fallback) {
+            (Ok(primary), Ok(fallback)) if fallback.tip_height > primary.tip_height => Ok(fallback),
+            (Ok(primary), _) => Ok(primary),
+            (Err(err), fallback) => {
+                debug!(%err, "failed to get sync status from primary");
+                fallback
+            }
+        }
+    }
+
+    fn get_blocks_range(
+        &self,
+        start_height: u64,
+        end_height: u64,
+    ) -> impl Stream<Item = L2BlockBundle> {
+        let next_height = Arc::new(AtomicU64::new(start_height));
+        let synced_height = next_height.clone();
+
+        let primary = self
+            .primary
+            .get_blocks_range(start_height, end_height)
+            .inspect(move |block| {
+                synced_height.fetch_max(block.header().blockidx() + 1, Ordering::Relaxed);
+            });
+
+        // Only gets polled once the primary is done.
+        let rest = stream::once(async move { next_height.load(Ordering::Relaxed) })
+            .filter(move |start_height| future::ready(*start_height <= end_height))
+            .flat_map(move |start_height| {
+                debug!(%start_height, %end_height, "syncing rest of range from fallback");
+                self.fallback.get_blocks_range(start_height, end_height)
+            });
+
+        primary.chain(rest)
+    }
+
+    async fn get_block_by_id(
+        &self,
+        block_id: &L2BlockId,
+    ) -> Result<Option<L2BlockBundle>, ClientError> {
+        match self.primary.get_block_by_id(block_id).await {
+            Ok(Some(block)) => Ok(Some(block)),
+            Ok(None) => self.fallback.get_block_by_id(block_id).await,
+            Err(err) => {
+                debug!(%block_id, %err, "failed to get block from primary");
+                self.fallback.get_block_by_id(block_id).await
+            }
+        }
+    }
+
+    async fn get_chainstate_snapshot(&self, epoch: u64) -> Result<Option<Chainstate>, ClientError> {
+        match self.primary.get_chainstate_snapshot(epoch).await {
+            Ok(Some(chainstate)) => Ok(Some(chainstate)),
+            Ok(None) => self.fallback.get_chainstate_snapshot(epoch).await,
+            Err(err) => {
+                debug!(%epoch, %err, "failed to get chainstate snapshot from primary");
+                self.fallback.get_chainstate_snapshot(epoch).await
+            }
+        }
+    }
 }
@@ -22,4 +22,14 @@ pub enum L2SyncError {
     Db(#[from] DbError),
     #[error("chain tip: {0}")]
     ChainTip(#[from] ChainTipError),
+    #[error("L1 client: {0}")]
+    L1Client(String),
+    #[error("failed to derive L1 tx filter config: {0}")]
+    FilterConfig(String),
+    #[error("missing chainstate snapshot for epoch {0}")]
+    MissingSnapshot(u64),
+    #[error("invalid chainstate snapshot for epoch {0}: {1}")]
+    InvalidSnapshot(u64, &'static str),
+    #[error("L1 verification state doesn't match the checkpoint for epoch {0}")]
+    InvalidL1State(u64),
 }
@@ -1,8 +1,10 @@
+mod bootstrap;
 mod client;
 mod error;
 mod state;
 mod worker;
 
-pub use client::{ClientError, RpcSyncPeer, SyncClient};
+pub use bootstrap::bootstrap_from_checkpoint;
+pub use client::{ClientError, FallbackSyncPeer, RpcSyncPeer, SyncClient};
 pub use error::L2SyncError;
 pub use worker::{block_until_csm_ready_and_init_sync_state, sync_worker, L2SyncContext};
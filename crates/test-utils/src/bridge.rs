@@ -15,12 +15,15 @@ use bitcoin::{
 };
 use musig2::{KeyAggContext, SecNonce};
 use rand::{rngs::OsRng, seq::SliceRandom, RngCore};
-use strata_db::stubs::bridge::StubTxStateDb;
+use strata_db::stubs::bridge::{StubSignerSessionDb, StubTxStateDb};
 use strata_primitives::{
     bridge::{OperatorIdx, PublickeyTable, TxSigningData},
     l1::{BitcoinPsbt, BitcoinTxOut, OutputRef, TaprootSpendPath},
 };
-use strata_storage::ops::bridge::{BridgeTxStateOps, Context};
+use strata_storage::ops::{
+    bridge::{BridgeTxStateOps, Context},
+    signer_session::{self, SignerSessionOps},
+};
 use threadpool::ThreadPool;
 
 /// Generate `count` (public key, private key) pairs as two separate [`Vec`].
@@ -164,6 +167,16 @@ pub fn generate_mock_tx_state_ops(num_threads: usize) -> BridgeTxStateOps {
     storage_ctx.into_ops(pool)
 }
 
+/// Create mock database ops to persist signer sessions in a stubbed in-memory database.
+pub fn generate_mock_signer_session_ops(num_threads: usize) -> SignerSessionOps {
+    let storage = StubSignerSessionDb::default();
+    let storage_ctx = signer_session::Context::new(Arc::new(storage));
+
+    let pool = ThreadPool::new(num_threads);
+
+    storage_ctx.into_ops(pool)
+}
+
 /// Generate a MuSig2 sec nonce.
 pub fn generate_sec_nonce(
     msg: &impl AsRef<[u8]>,
@@ -15,6 +15,7 @@ use strata_state::{
     chain_state::Chainstate,
     client_state::ClientState,
     header::{L2BlockHeader, L2Header, SignedL2BlockHeader},
+    l1::get_btc_params,
 };
 
 use crate::{bitcoin::get_btc_chain, ArbitraryGenerator};
@@ -99,6 +100,8 @@ pub fn gen_params_with_seed(seed: u64) -> Params {
             dispatch_assignment_dur: 64,
             proof_publish_mode: ProofPublishMode::Strict,
             max_deposits_in_block: 16,
+            forced_inclusion_delay: 6,
+            max_forced_incls_in_block: 16,
             network: bitcoin::Network::Regtest,
         },
         run: SyncParams {
@@ -137,7 +140,9 @@ pub fn get_genesis_chainstate() -> Chainstate {
     let params = gen_params();
     // Build the genesis block and genesis consensus states.
     let gblock = make_genesis_block(&params);
-    let pregenesis_mfs =
-        vec![get_btc_chain().get_block_manifest(params.rollup().horizon_l1_height as u32)];
-    make_genesis_chainstate(&gblock, pregenesis_mfs, &params)
+    let chain = get_btc_chain();
+    let genesis_height = params.rollup().genesis_l1_height as u32;
+    let pregenesis_mfs = vec![chain.get_block_manifest(genesis_height)];
+    let genesis_l1_vs = chain.get_verification_state(genesis_height + 1, &get_btc_params());
+    make_genesis_chainstate(&gblock, pregenesis_mfs, genesis_l1_vs, &params)
 }
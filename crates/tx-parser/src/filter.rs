@@ -1,14 +1,21 @@
 use bitcoin::{Block, Transaction};
 use strata_state::{
     batch::SignedBatchCheckpoint,
-    tx::{DepositInfo, DepositRequestInfo, ProtocolOperation},
+    operator_update::SignedOperatorUpdate,
+    tx::{
+        DepositInfo, DepositReimbursementInfo, DepositRequestInfo, ForcedInclusionInfo,
+        ProtocolOperation, WithdrawalFulfillmentInfo,
+    },
 };
 
 use super::messages::ProtocolOpTxRef;
 pub use crate::filter_types::TxFilterConfig;
 use crate::{
     deposit::{deposit_request::extract_deposit_request_info, deposit_tx::extract_deposit_info},
-    inscription::parse_inscription_data,
+    inscription::{
+        parse_forced_inclusion_data, parse_inscription_data, parse_operator_update_data,
+    },
+    withdrawal::{extract_deposit_reimbursement_info, extract_withdrawal_fulfillment_info},
 };
 
 /// Filter protocol operations as refs from relevant [`Transaction`]s in a block based on given
@@ -34,11 +41,22 @@ pub fn filter_protocol_op_tx_refs(
 //  TODO: make this function return multiple ops as a single tx can have multiple outpoints that's
 //  relevant
 fn extract_protocol_ops(tx: &Transaction, filter_conf: &TxFilterConfig) -> Vec<ProtocolOperation> {
-    // Currently all we have are inscription txs, deposits and deposit requests
+    // Currently all we have are inscription txs, deposits, deposit requests, forced inclusions,
+    // operator updates, withdrawal fulfillments and deposit reimbursements
     parse_inscription_checkpoints(tx, filter_conf)
         .map(ProtocolOperation::Checkpoint)
         .chain(parse_deposits(tx, filter_conf).map(ProtocolOperation::Deposit))
         .chain(parse_deposit_requests(tx, filter_conf).map(ProtocolOperation::DepositRequest))
+        .chain(parse_forced_inclusions(tx, filter_conf).map(ProtocolOperation::ForcedInclusion))
+        .chain(parse_operator_updates(tx, filter_conf).map(ProtocolOperation::OperatorUpdate))
+        .chain(
+            parse_withdrawal_fulfillments(tx, filter_conf)
+                .map(ProtocolOperation::WithdrawalFulfillment),
+        )
+        .chain(
+            parse_deposit_reimbursements(tx, filter_conf)
+                .map(ProtocolOperation::DepositReimbursement),
+        )
         .collect()
 }
 
@@ -58,24 +76,75 @@ fn parse_deposits(
     extract_deposit_info(tx, &filter_conf.deposit_config).into_iter()
 }
 
+fn parse_withdrawal_fulfillments(
+    tx: &Transaction,
+    filter_conf: &TxFilterConfig,
+) -> impl Iterator<Item = WithdrawalFulfillmentInfo> {
+    extract_withdrawal_fulfillment_info(tx, &filter_conf.deposit_config).into_iter()
+}
+
+fn parse_deposit_reimbursements(
+    tx: &Transaction,
+    filter_conf: &TxFilterConfig,
+) -> impl Iterator<Item = DepositReimbursementInfo> {
+    extract_deposit_reimbursement_info(tx, &filter_conf.deposit_config).into_iter()
+}
+
 /// Parses inscription from the given transaction. Currently, the only inscription recognizable is
 /// the checkpoint inscription.
+///
+/// A single inscription may carry a batch of blobs, of which only the ones that decode as
+/// checkpoints are returned.
 // TODO: we need to change inscription structure and possibly have inscriptions for checkpoints and
 // DA separately
 fn parse_inscription_checkpoints<'a>(
     tx: &'a Transaction,
     filter_conf: &'a TxFilterConfig,
 ) -> impl Iterator<Item = SignedBatchCheckpoint> + 'a {
+    tx.input
+        .iter()
+        .filter_map(|inp| {
+            inp.witness
+                .tapscript()
+                .and_then(|scr| parse_inscription_data(&scr.into(), &filter_conf.rollup_name).ok())
+        })
+        .flatten()
+        .filter_map(|data| borsh::from_slice::<SignedBatchCheckpoint>(data.batch_data()).ok())
+}
+
+/// Parses forced inclusions of EE transactions from the inscriptions in the given transaction.
+fn parse_forced_inclusions<'a>(
+    tx: &'a Transaction,
+    filter_conf: &'a TxFilterConfig,
+) -> impl Iterator<Item = ForcedInclusionInfo> + 'a {
     tx.input.iter().filter_map(|inp| {
         inp.witness
             .tapscript()
-            .and_then(|scr| {
-                parse_inscription_data(&scr.into(), &filter_conf.rollup_name.clone()).ok()
-            })
-            .and_then(|data| borsh::from_slice::<SignedBatchCheckpoint>(data.batch_data()).ok())
+            .and_then(|scr| parse_forced_inclusion_data(&scr.into(), &filter_conf.rollup_name).ok())
     })
 }
 
+/// Parses operator set updates from the inscriptions in the given transaction.
+///
+/// Updates that aren't signed by any of the sequencer's keys are dropped.  The state transition
+/// still checks that they're signed by the key for the slot they're applied at.
+fn parse_operator_updates<'a>(
+    tx: &'a Transaction,
+    filter_conf: &'a TxFilterConfig,
+) -> impl Iterator<Item = SignedOperatorUpdate> + 'a {
+    tx.input
+        .iter()
+        .filter_map(|inp| {
+            inp.witness.tapscript().and_then(|scr| {
+                parse_operator_update_data(&scr.into(), &filter_conf.rollup_name).ok()
+            })
+        })
+        .filter(|update| match &filter_conf.sequencer_pubkeys {
+            Some(pubkeys) => pubkeys.iter().any(|pk| update.verify_sig(pk)),
+            None => true,
+        })
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -93,12 +162,20 @@ mod test {
     };
     use rand::{rngs::OsRng, RngCore};
     use strata_btcio::test_utils::{
-        build_reveal_transaction_test, generate_inscription_script_test,
+        build_reveal_transaction_test, generate_batched_inscription_script_test,
+        generate_forced_inclusion_script_test, generate_inscription_script_test,
+        generate_operator_update_script_test,
+    };
+    use strata_primitives::{
+        buf::Buf32,
+        l1::BitcoinAmount,
+        relay::util::{compute_pubkey_for_privkey, sign_msg_hash},
+        sorted_vec::SortedVec,
     };
-    use strata_primitives::l1::BitcoinAmount;
     use strata_state::{
         batch::SignedBatchCheckpoint,
-        tx::{InscriptionData, ProtocolOperation},
+        operator_update::{OperatorUpdate, OperatorUpdateOp, SignedOperatorUpdate},
+        tx::{ForcedInclusionInfo, InscriptionData, ProtocolOperation},
     };
     use strata_test_utils::{l2::gen_params, ArbitraryGenerator};
 
@@ -163,12 +240,17 @@ mod test {
     // Create an inscription transaction. The focus here is to create a tapscript, rather than a
     // completely valid control block
     fn create_inscription_tx(rollup_name: String) -> Transaction {
-        let address = parse_addr(OTHER_ADDR);
-        let inp_tx = create_test_tx(vec![create_test_txout(100000000, &address)]);
         let signed_checkpoint: SignedBatchCheckpoint = ArbitraryGenerator::new().generate();
         let inscription_data = InscriptionData::new(borsh::to_vec(&signed_checkpoint).unwrap());
 
         let script = generate_inscription_script_test(inscription_data, &rollup_name, 1).unwrap();
+        create_reveal_tx(script)
+    }
+
+    // Create a transaction that reveals the given tapscript
+    fn create_reveal_tx(script: ScriptBuf) -> Transaction {
+        let address = parse_addr(OTHER_ADDR);
+        let inp_tx = create_test_tx(vec![create_test_txout(100000000, &address)]);
 
         // Create controlblock
         let mut rand_bytes = [0; 32];
@@ -215,6 +297,141 @@ mod test {
         assert!(result.is_empty(), "Should filter out invalid name");
     }
 
+    #[test]
+    fn test_filter_relevant_txs_batched_inscription() {
+        let filter_config = create_tx_filter_config();
+
+        // A batch of two checkpoints and a blob that is not a checkpoint
+        let checkpoints: Vec<SignedBatchCheckpoint> = (0..2)
+            .map(|_| ArbitraryGenerator::new().generate())
+            .collect();
+        let mut inscriptions: Vec<_> = checkpoints
+            .iter()
+            .map(|ckpt| InscriptionData::new(borsh::to_vec(ckpt).unwrap()))
+            .collect();
+        inscriptions.push(InscriptionData::new(vec![0xff; 3]));
+
+        let script =
+            generate_batched_inscription_script_test(&inscriptions, &filter_config.rollup_name, 1)
+                .unwrap();
+        let tx = create_reveal_tx(script);
+        let block = create_test_block(vec![tx]);
+
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+
+        let expected: Vec<_> = checkpoints
+            .into_iter()
+            .map(ProtocolOperation::Checkpoint)
+            .collect();
+        let parsed: Vec<_> = result
+            .iter()
+            .map(|op_ref| op_ref.proto_op().clone())
+            .collect();
+        assert_eq!(
+            parsed, expected,
+            "Should parse every checkpoint in the batch"
+        );
+        assert!(result.iter().all(|op_ref| op_ref.index() == 0));
+    }
+
+    #[test]
+    fn test_filter_relevant_txs_forced_inclusion() {
+        let filter_config = create_tx_filter_config();
+        let payload = vec![0xf8; 120]; // Example raw EVM tx
+        let script =
+            generate_forced_inclusion_script_test(&payload, &filter_config.rollup_name, 1).unwrap();
+        let tx = create_reveal_tx(script);
+        let block = create_test_block(vec![tx]);
+
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+
+        assert_eq!(result.len(), 1, "Should find one relevant transaction");
+        assert_eq!(
+            result[0].proto_op(),
+            &ProtocolOperation::ForcedInclusion(ForcedInclusionInfo { payload }),
+            "Should parse the forced inclusion payload"
+        );
+
+        // The checkpoint inscriptions must not be parsed as forced inclusions
+        let tx = create_inscription_tx(filter_config.rollup_name.clone());
+        let block = create_test_block(vec![tx]);
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+        assert!(
+            result
+                .iter()
+                .all(|op_ref| !matches!(op_ref.proto_op(), ProtocolOperation::ForcedInclusion(_))),
+            "Should not find forced inclusions"
+        );
+    }
+
+    #[test]
+    fn test_filter_relevant_txs_operator_update() {
+        let filter_config = create_tx_filter_config();
+        let update: SignedOperatorUpdate = ArbitraryGenerator::new().generate();
+        let script = generate_operator_update_script_test(
+            &borsh::to_vec(&update).unwrap(),
+            &filter_config.rollup_name,
+            1,
+        )
+        .unwrap();
+        let tx = create_reveal_tx(script.clone());
+        let block = create_test_block(vec![tx]);
+
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+
+        assert_eq!(result.len(), 1, "Should find one relevant transaction");
+        assert_eq!(
+            result[0].proto_op(),
+            &ProtocolOperation::OperatorUpdate(update),
+            "Should parse the operator update"
+        );
+
+        // Only updates signed by the sequencer are parsed if its signatures are checked
+        let seq_sk = Buf32::from([3; 32]);
+        let mut filter_config = filter_config;
+        filter_config.sequencer_pubkeys =
+            Some(SortedVec::from(vec![compute_pubkey_for_privkey(&seq_sk)]));
+        let block = create_test_block(vec![create_reveal_tx(script)]);
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+        assert!(
+            result.is_empty(),
+            "Should drop the unsigned operator update"
+        );
+
+        let update = OperatorUpdate::new(0, vec![OperatorUpdateOp::Remove(0)]);
+        let sig = sign_msg_hash(&seq_sk, &update.get_sighash());
+        let update = SignedOperatorUpdate::new(update, sig);
+        let script = generate_operator_update_script_test(
+            &borsh::to_vec(&update).unwrap(),
+            &filter_config.rollup_name,
+            1,
+        )
+        .unwrap();
+        let block = create_test_block(vec![create_reveal_tx(script)]);
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+        assert_eq!(
+            result
+                .iter()
+                .map(|op_ref| op_ref.proto_op())
+                .collect::<Vec<_>>(),
+            vec![&ProtocolOperation::OperatorUpdate(update)],
+            "Should parse the signed operator update"
+        );
+
+        // Forced inclusions must not be parsed as operator updates
+        let script =
+            generate_forced_inclusion_script_test(&[0xf8; 120], &filter_config.rollup_name, 1)
+                .unwrap();
+        let block = create_test_block(vec![create_reveal_tx(script)]);
+        let result = filter_protocol_op_tx_refs(&block, &filter_config);
+        assert!(
+            result
+                .iter()
+                .all(|op_ref| !matches!(op_ref.proto_op(), ProtocolOperation::OperatorUpdate(_))),
+            "Should not find operator updates"
+        );
+    }
+
     #[test]
     fn test_filter_relevant_txs_no_match() {
         let tx1 = create_test_tx(vec![create_test_txout(1000, &parse_addr(OTHER_ADDR))]);
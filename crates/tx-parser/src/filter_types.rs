@@ -5,6 +5,7 @@ use strata_primitives::{
     params::{DepositTxParams, RollupParams},
     sorted_vec::SortedVec,
 };
+use strata_state::bridge_state::OperatorTable;
 
 use crate::utils::{generate_taproot_address, get_operator_wallet_pks};
 
@@ -25,6 +26,10 @@ pub struct TxFilterConfig {
 
     /// Deposit config that determines how a deposit transaction can be parsed.
     pub deposit_config: DepositTxParams,
+
+    /// For operator updates, which must be signed by one of the sequencer's keys.  `None` if the
+    /// sequencer's signatures aren't checked.
+    pub sequencer_pubkeys: Option<SortedVec<Buf32>>,
 }
 
 impl TxFilterConfig {
@@ -32,12 +37,35 @@ impl TxFilterConfig {
     // TODO: this will need chainstate too in the future
     pub fn derive_from(rollup_params: &RollupParams) -> anyhow::Result<Self> {
         let operator_wallet_pks = get_operator_wallet_pks(rollup_params);
-        let address = generate_taproot_address(&operator_wallet_pks, rollup_params.network)?;
+        Self::derive_with_operators(rollup_params, &operator_wallet_pks)
+    }
+
+    /// Derive a `TxFilterConfig` from `RollupParams` and the current [`OperatorTable`], so that
+    /// new deposits are expected to the bridge address of the current operator set instead of the
+    /// genesis one.
+    pub fn derive_from_operator_table(
+        rollup_params: &RollupParams,
+        operator_table: &OperatorTable,
+    ) -> anyhow::Result<Self> {
+        let operator_wallet_pks = operator_table
+            .operators()
+            .iter()
+            .map(|op| *op.wallet_pk())
+            .collect::<Vec<_>>();
+        Self::derive_with_operators(rollup_params, &operator_wallet_pks)
+    }
+
+    fn derive_with_operators(
+        rollup_params: &RollupParams,
+        operator_wallet_pks: &[Buf32],
+    ) -> anyhow::Result<Self> {
+        let address = generate_taproot_address(operator_wallet_pks, rollup_params.network)?;
 
         let rollup_name = rollup_params.rollup_name.clone();
         let expected_blobs = SortedVec::new(); // TODO: this should come from chainstate
         let expected_addrs = SortedVec::from(vec![address.clone()]);
         let expected_outpoints = SortedVec::new();
+        let sequencer_pubkeys = rollup_params.cred_rule.signers().map(SortedVec::from);
 
         let deposit_config = DepositTxParams {
             magic_bytes: rollup_name.clone().into_bytes(),
@@ -51,6 +79,7 @@ impl TxFilterConfig {
             expected_addrs,
             expected_outpoints,
             deposit_config,
+            sequencer_pubkeys,
         })
     }
 }
@@ -3,7 +3,10 @@ use bitcoin::{
     script::{Instruction, Instructions},
     ScriptBuf,
 };
-use strata_state::tx::InscriptionData;
+use strata_state::{
+    operator_update::SignedOperatorUpdate,
+    tx::{ForcedInclusionInfo, InscriptionData},
+};
 use thiserror::Error;
 use tracing::debug;
 
@@ -12,6 +15,8 @@ use super::utils::{next_bytes, next_int, next_op};
 pub const ROLLUP_NAME_TAG: &[u8] = &[1];
 pub const VERSION_TAG: &[u8] = &[2];
 pub const BATCH_DATA_TAG: &[u8] = &[3];
+pub const FORCED_INCLUSION_TAG: &[u8] = &[4];
+pub const OPERATOR_UPDATE_TAG: &[u8] = &[5];
 
 #[derive(Debug, Error)]
 pub enum InscriptionParseError {
@@ -41,20 +46,89 @@ pub enum InscriptionParseError {
     InvalidFormat,
 }
 
-/// Parse [`InscriptionData`]
+/// Parse [`InscriptionData`]s
+///
+/// The writer may batch several blobs into a single reveal transaction, in which case the script
+/// contains one envelope per blob, one after another. The blobs are returned in the order of their
+/// envelopes.
 ///
 /// # Errors
 ///
-/// This function errors if it cannot parse the [`InscriptionData`]
+/// This function errors if it cannot parse the first [`InscriptionData`] or if any of the
+/// following envelopes is malformed.
 pub fn parse_inscription_data(
     script: &ScriptBuf,
     rollup_name: &str,
-) -> Result<InscriptionData, InscriptionParseError> {
+) -> Result<Vec<InscriptionData>, InscriptionParseError> {
     let mut instructions = script.instructions();
 
-    enter_envelope(&mut instructions)?;
+    let batch_data = parse_envelope_data(&mut instructions, rollup_name, BATCH_DATA_TAG)?;
+    let mut inscriptions = vec![InscriptionData::new(batch_data)];
+
+    // Any further envelopes carry the other blobs of the batch
+    while enter_envelope(&mut instructions).is_ok() {
+        let batch_data = parse_envelope_body(&mut instructions, rollup_name, BATCH_DATA_TAG)?;
+        inscriptions.push(InscriptionData::new(batch_data));
+    }
+
+    Ok(inscriptions)
+}
+
+/// Parse [`ForcedInclusionInfo`]
+///
+/// This uses the same envelope as the checkpoint inscriptions, but tags the data with
+/// [`FORCED_INCLUSION_TAG`] instead.
+///
+/// # Errors
+///
+/// This function errors if it cannot parse the [`ForcedInclusionInfo`]
+pub fn parse_forced_inclusion_data(
+    script: &ScriptBuf,
+    rollup_name: &str,
+) -> Result<ForcedInclusionInfo, InscriptionParseError> {
+    let payload = parse_envelope_data(
+        &mut script.instructions(),
+        rollup_name,
+        FORCED_INCLUSION_TAG,
+    )?;
+    Ok(ForcedInclusionInfo { payload })
+}
+
+/// Parse [`SignedOperatorUpdate`]
+///
+/// This uses the same envelope as the checkpoint inscriptions, but tags the data with
+/// [`OPERATOR_UPDATE_TAG`] instead.  The signature isn't checked here since the sequencer key
+/// depends on the slot the update gets applied in.
+///
+/// # Errors
+///
+/// This function errors if it cannot parse the [`SignedOperatorUpdate`]
+pub fn parse_operator_update_data(
+    script: &ScriptBuf,
+    rollup_name: &str,
+) -> Result<SignedOperatorUpdate, InscriptionParseError> {
+    let data = parse_envelope_data(&mut script.instructions(), rollup_name, OPERATOR_UPDATE_TAG)?;
+    borsh::from_slice(&data).map_err(|_| InscriptionParseError::InvalidBlob)
+}
+
+/// Parses the data tagged with `data_tag` from the next rollup's envelope in the instructions.
+fn parse_envelope_data(
+    instructions: &mut Instructions,
+    rollup_name: &str,
+    data_tag: &[u8],
+) -> Result<Vec<u8>, InscriptionParseError> {
+    enter_envelope(instructions)?;
+    parse_envelope_body(instructions, rollup_name, data_tag)
+}
+
+/// Parses the contents of an envelope that has just been entered, up to its `OP_ENDIF`.
+fn parse_envelope_body(
+    instructions: &mut Instructions,
+    rollup_name: &str,
+    data_tag: &[u8],
+) -> Result<Vec<u8>, InscriptionParseError> {
     // Parse name
-    let (tag, name) = parse_bytes_pair(&mut instructions)?;
+    let (tag, name) = parse_bytes_pair(instructions)?;
 
     let extracted_rollup_name = match (tag, name) {
         (ROLLUP_NAME_TAG, namebytes) => String::from_utf8(namebytes.to_vec())
@@ -67,7 +141,7 @@ pub fn parse_inscription_data(
     }
 
     // Parse version
-    let (tag, ver) = parse_bytes_pair(&mut instructions)?;
+    let (tag, ver) = parse_bytes_pair(instructions)?;
     let _version = match (tag, ver) {
         (VERSION_TAG, [v]) => Ok(v),
         (VERSION_TAG, _) => Err(InscriptionParseError::InvalidVersion),
@@ -75,16 +149,13 @@ pub fn parse_inscription_data(
     }?;
 
     // Parse bytes
-    let tag = next_bytes(&mut instructions).ok_or(InscriptionParseError::InvalidBlobTag)?;
-    let size = next_int(&mut instructions);
-    match (tag, size) {
-        (BATCH_DATA_TAG, Some(size)) => {
-            let batch_data = extract_n_bytes(size, &mut instructions)?;
-            Ok(InscriptionData::new(batch_data))
-        }
-        (BATCH_DATA_TAG, None) => Err(InscriptionParseError::InvalidBlob),
-        _ => Err(InscriptionParseError::InvalidBlobTag),
+    let tag = next_bytes(instructions).ok_or(InscriptionParseError::InvalidBlobTag)?;
+    if tag != data_tag {
+        return Err(InscriptionParseError::InvalidBlobTag);
     }
+
+    let size = next_int(instructions).ok_or(InscriptionParseError::InvalidBlob)?;
+    extract_n_bytes(size, instructions)
 }
 
 /// Check for consecutive `OP_FALSE` and `OP_IF` that marks the beginning of an inscription
@@ -147,7 +218,9 @@ fn extract_n_bytes(
 #[cfg(test)]
 mod tests {
 
-    use strata_btcio::test_utils::generate_inscription_script_test;
+    use strata_btcio::test_utils::{
+        generate_batched_inscription_script_test, generate_inscription_script_test,
+    };
 
     use super::*;
 
@@ -162,7 +235,7 @@ mod tests {
         let result = parse_inscription_data(&script, "TestRollup").unwrap();
 
         // Assert the rollup name was parsed correctly
-        assert_eq!(result, inscription_data);
+        assert_eq!(result, vec![inscription_data]);
 
         // Try with larger size
         let bytes = vec![1; 2000];
@@ -174,6 +247,29 @@ mod tests {
         let result = parse_inscription_data(&script, "TestRollup").unwrap();
 
         // Assert the rollup name was parsed correctly
-        assert_eq!(result, inscription_data);
+        assert_eq!(result, vec![inscription_data]);
+    }
+
+    #[test]
+    fn test_parse_batched_inscription_data() {
+        let inscriptions = vec![
+            InscriptionData::new(vec![1; 100]),
+            InscriptionData::new(vec![2; 2000]),
+            InscriptionData::new(vec![3; 600]),
+        ];
+        let script =
+            generate_batched_inscription_script_test(&inscriptions, "TestRollup", 1).unwrap();
+
+        let result = parse_inscription_data(&script, "TestRollup").unwrap();
+        assert_eq!(result, inscriptions, "all blobs should be parsed in order");
+
+        // The batch is rejected if any of its envelopes belongs to another rollup
+        let other =
+            generate_inscription_script_test(InscriptionData::new(vec![4; 10]), "OtherRollup", 1)
+                .unwrap();
+        let mut script = script.into_bytes();
+        script.extend(other.into_bytes());
+        let result = parse_inscription_data(&ScriptBuf::from(script), "TestRollup");
+        assert!(result.is_err());
     }
 }
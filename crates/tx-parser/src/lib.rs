@@ -4,3 +4,4 @@ pub mod filter_types;
 pub mod inscription;
 pub mod messages;
 pub mod utils;
+pub mod withdrawal;
@@ -0,0 +1,321 @@
+//! parser for the Withdrawal Fulfillment Tx and the Deposit Reimbursement Tx
+
+use bitcoin::{hashes::Hash, opcodes::all::OP_RETURN, OutPoint, ScriptBuf, Transaction, Txid};
+use strata_bridge_tx_builder::prelude::{DEPOSIT_REIMBURSEMENT_TAG, WITHDRAWAL_FULFILLMENT_TAG};
+use strata_primitives::{
+    bridge::WithdrawOutput,
+    buf::Buf32,
+    l1::{OutputRef, XOnlyPk},
+    prelude::DepositTxParams,
+};
+use strata_state::tx::{DepositReimbursementInfo, WithdrawalFulfillmentInfo};
+
+use crate::utils::{next_bytes, next_op};
+
+/// Extracts the WithdrawalFulfillmentInfo from the Withdrawal Fulfillment Transaction
+pub fn extract_withdrawal_fulfillment_info(
+    tx: &Transaction,
+    config: &DepositTxParams,
+) -> Option<WithdrawalFulfillmentInfo> {
+    // the metadata is `txid || vout` of the deposit followed by the idx of the paying operator,
+    // and by the vout of the change if the withdrawal is paid out of the deposit itself
+    let payload = tx.output.iter().find_map(|output| {
+        parse_metadata_script(&output.script_pubkey, config, WITHDRAWAL_FULFILLMENT_TAG)
+    })?;
+    if payload.len() != 40 && payload.len() != 44 {
+        return None;
+    }
+
+    let txid = Txid::from_slice(&payload[..32]).ok()?;
+    let vout = u32::from_be_bytes(payload[32..36].try_into().ok()?);
+    let operator_idx = u32::from_be_bytes(payload[36..40].try_into().ok()?);
+    let deposit_outpoint = OutPoint { txid, vout };
+
+    let spends_deposit = tx
+        .input
+        .iter()
+        .any(|input| input.previous_output == deposit_outpoint);
+
+    let change = payload
+        .get(40..)
+        .filter(|change_vout| spends_deposit && !change_vout.is_empty())
+        .and_then(|change_vout| {
+            let change_vout = u32::from_be_bytes(change_vout.try_into().ok()?);
+            let output = tx
+                .output
+                .get(change_vout as usize)
+                .filter(|output| output.script_pubkey.is_p2tr())?;
+            let change_outpoint = OutPoint {
+                txid: tx.compute_txid(),
+                vout: change_vout,
+            };
+
+            Some((OutputRef::from(change_outpoint), output.value.into()))
+        });
+
+    // every taproot output is a potential payout to a user
+    let outputs = tx
+        .output
+        .iter()
+        .filter(|output| output.script_pubkey.is_p2tr())
+        .map(|output| {
+            // p2tr script is `OP_1 OP_PUSHBYTES_32 <output key>`
+            let output_key = Buf32::try_from(&output.script_pubkey.as_bytes()[2..34])
+                .expect("p2tr script must contain a 32 byte output key");
+
+            WithdrawOutput::new(XOnlyPk::new(output_key), output.value.into())
+        })
+        .collect();
+
+    Some(WithdrawalFulfillmentInfo {
+        deposit_outpoint: OutputRef::from(deposit_outpoint),
+        operator_idx,
+        outputs,
+        txid: tx.compute_txid().into(),
+        spends_deposit,
+        change,
+    })
+}
+
+/// Extracts the DepositReimbursementInfo from the Deposit Reimbursement Transaction
+pub fn extract_deposit_reimbursement_info(
+    tx: &Transaction,
+    config: &DepositTxParams,
+) -> Option<DepositReimbursementInfo> {
+    // the metadata is the txid of the withdrawal fulfillment
+    let payload = tx.output.iter().find_map(|output| {
+        parse_metadata_script(&output.script_pubkey, config, DEPOSIT_REIMBURSEMENT_TAG)
+    })?;
+    let fulfillment_txid = Txid::from_slice(payload).ok()?;
+
+    // the deposit is always spent by the first input
+    let deposit_outpoint = tx.input.first()?.previous_output;
+
+    // the change, if any, follows the output reimbursing the operator
+    let change = tx
+        .output
+        .get(1)
+        .filter(|output| output.script_pubkey.is_p2tr())
+        .map(|output| {
+            let change_outpoint = OutPoint {
+                txid: tx.compute_txid(),
+                vout: 1,
+            };
+
+            (OutputRef::from(change_outpoint), output.value.into())
+        });
+
+    Some(DepositReimbursementInfo {
+        deposit_outpoint: OutputRef::from(deposit_outpoint),
+        fulfillment_txid: fulfillment_txid.into(),
+        change,
+    })
+}
+
+/// extracts the payload following the Magic Bytes and the given tag given that the script is
+/// OP_RETURN type
+fn parse_metadata_script<'a>(
+    script: &'a ScriptBuf,
+    config: &DepositTxParams,
+    tag: u8,
+) -> Option<&'a [u8]> {
+    let mut instructions = script.instructions();
+
+    if next_op(&mut instructions) != Some(OP_RETURN) {
+        return None;
+    }
+
+    let data = next_bytes(&mut instructions)?;
+
+    let magic_bytes = &config.magic_bytes;
+    let magic_len = magic_bytes.len();
+
+    if data.len() <= magic_len || &data[..magic_len] != magic_bytes || data[magic_len] != tag {
+        return None;
+    }
+
+    Some(&data[magic_len + 1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::{
+        absolute::LockTime,
+        key::Keypair,
+        secp256k1::{SecretKey, SECP256K1},
+        transaction::Version,
+        Amount, Network, TxIn, TxOut,
+    };
+    use strata_bridge_tx_builder::prelude::{
+        cooperative_withdrawal_script, deposit_reimbursement_script, operator_fee,
+        CooperativeWithdrawalInfo, MAGIC_BYTES,
+    };
+    use strata_primitives::l1::BitcoinAmount;
+    use strata_test_utils::ArbitraryGenerator;
+
+    use super::*;
+    use crate::deposit::test_utils::get_deposit_tx_config;
+
+    fn get_config() -> DepositTxParams {
+        DepositTxParams {
+            magic_bytes: MAGIC_BYTES.to_vec(),
+            ..get_deposit_tx_config()
+        }
+    }
+
+    #[test]
+    fn check_withdrawal_fulfillment_parser() {
+        let mut generator = ArbitraryGenerator::new();
+        let deposit_outpoint: OutputRef = generator.generate();
+        let sk = SecretKey::from_slice(&[1u8; 32]).expect("must be a valid secret key");
+        let (x_only_pk, _) = Keypair::from_secret_key(SECP256K1, &sk).x_only_public_key();
+        let dest_addr = XOnlyPk::new(x_only_pk.into());
+        let amt = BitcoinAmount::from_int_btc(10);
+
+        let withdrawal = CooperativeWithdrawalInfo::new(
+            *deposit_outpoint.outpoint(),
+            amt.into(),
+            vec![3],
+            vec![WithdrawOutput::new(dest_addr, amt)],
+            3,
+            100,
+        );
+        let tx = withdrawal
+            .create_fulfillment_tx(Network::Regtest)
+            .expect("fulfillment tx must be created");
+
+        let info = extract_withdrawal_fulfillment_info(&tx, &get_config())
+            .expect("fulfillment must be parsed");
+
+        let amt = Amount::from(amt);
+        assert_eq!(info.deposit_outpoint, deposit_outpoint);
+        assert_eq!(info.operator_idx, 3);
+        assert_eq!(info.txid, tx.compute_txid().into());
+        assert_eq!(
+            info.outputs,
+            vec![WithdrawOutput::new(
+                dest_addr,
+                (amt - operator_fee(amt)).into()
+            )]
+        );
+
+        assert!(
+            !info.spends_deposit,
+            "fulfillment is paid from the operator's wallet"
+        );
+        assert_eq!(info.change, None);
+
+        assert!(
+            extract_withdrawal_fulfillment_info(&tx, &get_deposit_tx_config()).is_none(),
+            "fulfillment with other magic bytes must be ignored"
+        );
+    }
+
+    #[test]
+    fn check_cooperative_withdrawal_parser() {
+        let mut generator = ArbitraryGenerator::new();
+        let deposit_outpoint: OutputRef = generator.generate();
+        let sk = SecretKey::from_slice(&[1u8; 32]).expect("must be a valid secret key");
+        let (x_only_pk, _) = Keypair::from_secret_key(SECP256K1, &sk).x_only_public_key();
+        let p2tr_script = ScriptBuf::new_p2tr(SECP256K1, x_only_pk, None);
+
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: *deposit_outpoint.outpoint(),
+                ..Default::default()
+            }],
+            output: vec![
+                TxOut {
+                    value: Amount::from_int_btc(9),
+                    script_pubkey: p2tr_script.clone(),
+                },
+                TxOut {
+                    value: Amount::from_int_btc(1),
+                    script_pubkey: p2tr_script,
+                },
+                TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: cooperative_withdrawal_script(
+                        deposit_outpoint.outpoint(),
+                        3,
+                        Some(1),
+                    ),
+                },
+            ],
+        };
+
+        let info = extract_withdrawal_fulfillment_info(&tx, &get_config())
+            .expect("withdrawal must be parsed");
+
+        let change_outpoint = OutPoint::new(tx.compute_txid(), 1);
+        assert_eq!(info.deposit_outpoint, deposit_outpoint);
+        assert_eq!(info.operator_idx, 3);
+        assert!(info.spends_deposit, "withdrawal is paid out of the deposit");
+        assert_eq!(
+            info.change,
+            Some((change_outpoint.into(), BitcoinAmount::from_int_btc(1))),
+            "change must be parsed"
+        );
+    }
+
+    #[test]
+    fn check_deposit_reimbursement_parser() {
+        let mut generator = ArbitraryGenerator::new();
+        let deposit_outpoint: OutputRef = generator.generate();
+        let fulfillment_txid: Txid = generator.generate::<Buf32>().into();
+
+        let sk = SecretKey::from_slice(&[1u8; 32]).expect("must be a valid secret key");
+        let (x_only_pk, _) = Keypair::from_secret_key(SECP256K1, &sk).x_only_public_key();
+        let p2tr_script = ScriptBuf::new_p2tr(SECP256K1, x_only_pk, None);
+
+        let mut tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: *deposit_outpoint.outpoint(),
+                ..Default::default()
+            }],
+            output: vec![
+                TxOut {
+                    value: Amount::from_int_btc(9),
+                    script_pubkey: p2tr_script.clone(),
+                },
+                TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: deposit_reimbursement_script(&fulfillment_txid),
+                },
+            ],
+        };
+
+        let info = extract_deposit_reimbursement_info(&tx, &get_config())
+            .expect("reimbursement must be parsed");
+
+        assert_eq!(info.deposit_outpoint, deposit_outpoint);
+        assert_eq!(info.fulfillment_txid, fulfillment_txid.into());
+        assert_eq!(info.change, None, "reimbursement without change");
+
+        tx.output.insert(
+            1,
+            TxOut {
+                value: Amount::from_int_btc(1),
+                script_pubkey: p2tr_script,
+            },
+        );
+        let info = extract_deposit_reimbursement_info(&tx, &get_config())
+            .expect("reimbursement must be parsed");
+
+        let change_outpoint = OutPoint::new(tx.compute_txid(), 1);
+        assert_eq!(
+            info.change,
+            Some((change_outpoint.into(), BitcoinAmount::from_int_btc(1))),
+            "change must be parsed"
+        );
+
+        assert!(
+            extract_withdrawal_fulfillment_info(&tx, &get_config()).is_none(),
+            "reimbursement must not be parsed as a fulfillment"
+        );
+    }
+}
@@ -12,7 +12,7 @@ pub(crate) const RECOVER_DELAY: u32 = 1008;
 /// federation.
 pub(crate) const BRIDGE_IN_AMOUNT: Amount = Amount::from_sat(1_001_000_000);
 
-/// Bridge outs are enforced to be exactly 10 BTC
+/// Amount bridged out by default in the tests
 #[allow(dead_code)] // TODO: Remove this when bridge out is implemented
 pub(crate) const BRIDGE_OUT_AMOUNT: Amount = Amount::from_int_btc(10);
 
@@ -63,6 +63,8 @@ class RollupConfig(BaseModel):
     dispatch_assignment_dur: int
     proof_publish_mode: ProofPublishMode
     max_deposits_in_block: int
+    forced_inclusion_delay: int
+    max_forced_incls_in_block: int
     network: str
 
     # Additional fields that aren't coming from datatool config generation (yet)
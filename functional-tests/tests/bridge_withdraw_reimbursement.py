@@ -0,0 +1,86 @@
+import flexitest
+from strata_utils import get_balance
+
+from envs import testenv
+from envs.rollup_params_cfg import RollupConfig
+from utils import get_bridge_pubkey, wait_until, wait_until_with_value
+from utils.constants import UNSPENDABLE_ADDRESS
+
+
+@flexitest.register
+class BridgeWithdrawReimbursementTest(testenv.BridgeTestBase):
+    """
+    Makes two DRT deposits, then makes a withdrawal.
+
+    Checks that the assigned operator pays out the withdrawal from its own wallet
+    and that the deposit it was dispatched against is then swept to reimburse it.
+    """
+
+    def __init__(self, ctx: flexitest.InitContext):
+        ctx.set_env("basic")
+
+    def main(self, ctx: flexitest.RunContext):
+        withdraw_address = ctx.env.gen_ext_btc_address()
+        el_address = self.eth_account.address
+
+        cfg: RollupConfig = ctx.env.rollup_cfg()
+        deposit_amount = cfg.deposit_amount
+        operator_fee = cfg.operator_fee
+        withdraw_extra_fee = cfg.withdraw_extra_fee
+
+        btc_url = self.btcrpc.base_url
+        btc_user = self.btc.get_prop("rpc_user")
+        btc_password = self.btc.get_prop("rpc_password")
+        bridge_pk = get_bridge_pubkey(self.seqrpc)
+
+        original_balance = get_balance(withdraw_address, btc_url, btc_user, btc_password)
+        self.debug(f"BTC balance before withdraw: {original_balance}")
+
+        self.deposit(ctx, el_address, bridge_pk)
+        self.deposit(ctx, el_address, bridge_pk)
+
+        self.withdraw(ctx, el_address, withdraw_address)
+
+        # Find the deposit the withdrawal was dispatched against, it may already be fulfilled
+        duties = wait_until_with_value(
+            lambda: [
+                d
+                for d in self.seqrpc.strata_getBridgeDuties(0, 0)["duties"]
+                if d["type"] in ("FulfillWithdrawal", "ReimburseOperator")
+            ],
+            predicate=lambda v: len(v) > 0,
+            error_with="Withdrawal was not dispatched",
+            timeout=20,
+        )
+        deposit_outpoint = duties[0]["payload"]["deposit_outpoint"]
+        deposit_idx = self.find_deposit_idx(deposit_outpoint)
+        self.debug(f"Withdrawal dispatched against deposit {deposit_idx}")
+
+        # The user is paid out by the operator
+        self.btcrpc.proxy.generatetoaddress(6, UNSPENDABLE_ADDRESS)
+        difference = deposit_amount - operator_fee - withdraw_extra_fee
+        wait_until_with_value(
+            lambda: get_balance(withdraw_address, btc_url, btc_user, btc_password),
+            predicate=lambda v: v == original_balance + difference,
+            error_with="Withdrawal was not paid out",
+            timeout=20,
+        )
+
+        # The operator is then reimbursed from the deposit
+        def is_executed():
+            self.btcrpc.proxy.generatetoaddress(1, UNSPENDABLE_ADDRESS)
+            deposit = self.seqrpc.strata_getCurrentDepositById(deposit_idx)
+            self.debug(f"Deposit state: {deposit['state']}")
+            return deposit["state"] == "executed"
+
+        wait_until(is_executed, error_with="Operator was not reimbursed", timeout=60, step=2)
+
+        return True
+
+    def find_deposit_idx(self, deposit_outpoint) -> int:
+        for deposit_idx in self.seqrpc.strata_getCurrentDeposits():
+            deposit = self.seqrpc.strata_getCurrentDepositById(deposit_idx)
+            if deposit["output"] == deposit_outpoint:
+                return deposit_idx
+
+        raise AssertionError(f"no deposit found for {deposit_outpoint}")
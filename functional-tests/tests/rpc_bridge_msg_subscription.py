@@ -0,0 +1,61 @@
+import json
+
+import flexitest
+from websockets.sync.client import connect as wsconnect
+
+from envs import testenv
+
+TIMEOUT = 30
+
+
+@flexitest.register
+class BridgeMsgSubscriptionTest(testenv.StrataTester):
+    def __init__(self, ctx: flexitest.InitContext):
+        ctx.set_env("basic")
+
+    def main(self, ctx: flexitest.RunContext):
+        seq = ctx.get_service("sequencer")
+        rpc_url = seq.get_prop("rpc_url")
+        seqrpc = seq.create_rpc()
+
+        # BridgeMessage { source_id: 1,
+        #                 sig: [00] * 64
+        #                 scope: Misc, payload: [43] }
+        raw_msg = "".join(
+            [
+                "01000000",
+                "00" * 64,
+                "01000000" + "00",
+                "01000000" + "43",
+            ]
+        )
+        # Same, but with another scope that must not be pushed to us
+        other_raw_msg = "".join(
+            [
+                "01000000",
+                "00" * 64,
+                "01000000" + "01",
+                "01000000" + "43",
+            ]
+        )
+
+        with wsconnect(rpc_url) as ws:
+            req = {
+                "jsonrpc": "2.0",
+                "method": "strata_subscribeBridgeMsgs",
+                "id": 0,
+                "params": ["00"],
+            }
+            ws.send(json.dumps(req))
+            resp = json.loads(ws.recv(timeout=TIMEOUT))
+            assert "error" not in resp, f"subscription failed: {resp}"
+            sub_id = resp["result"]
+
+            seqrpc.strata_submitBridgeMsg(other_raw_msg)
+            seqrpc.strata_submitBridgeMsg(raw_msg)
+
+            notif = json.loads(ws.recv(timeout=TIMEOUT))
+            self.debug(notif)
+            assert notif["method"] == "strata_bridgeMsgs"
+            assert notif["params"]["subscription"] == sub_id
+            assert notif["params"]["result"] == raw_msg, "not the message we expected"
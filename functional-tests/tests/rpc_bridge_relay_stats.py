@@ -0,0 +1,54 @@
+import time
+
+import flexitest
+
+from envs import testenv
+
+WAIT_TIME = 2
+
+# Default `max_payload_size` of the relayer.
+MAX_PAYLOAD_SIZE = 4096
+
+
+def make_misc_msg(source_id: int, payload: bytes) -> str:
+    # BridgeMessage { source_id, sig: [00] * 64, scope: Misc, payload }
+    return "".join(
+        [
+            source_id.to_bytes(4, "little").hex(),
+            "00" * 64,
+            "01000000" + "00",
+            len(payload).to_bytes(4, "little").hex() + payload.hex(),
+        ]
+    )
+
+
+@flexitest.register
+class BridgeRelayStatsTest(testenv.StrataTester):
+    def __init__(self, ctx: flexitest.InitContext):
+        ctx.set_env("basic")
+
+    def main(self, ctx: flexitest.RunContext):
+        seq = ctx.get_service("sequencer")
+        seqrpc = seq.create_rpc()
+
+        seqrpc.strata_submitBridgeMsg(make_misc_msg(2, b"\x42"))
+        seqrpc.strata_submitBridgeMsg(make_misc_msg(2, b"\x43" * (MAX_PAYLOAD_SIZE + 1)))
+
+        time.sleep(WAIT_TIME)
+
+        stats = seqrpc.strata_getBridgeRelayStats()
+        self.debug(stats)
+
+        # misc messages aren't signed, so they aren't counted under the operator
+        assert len([s for s in stats["operators"] if s["operator_idx"] == 2]) == 0, (
+            "unsigned messages should not be attributed to the operator"
+        )
+        unauth_stats = stats["unauthenticated"]
+        assert unauth_stats["relayed"] == 1, "small message should be relayed"
+        assert unauth_stats["oversized"] == 1, "large message should be dropped"
+
+        msgs = seqrpc.strata_getBridgeMsgsByScope("00")
+        assert make_misc_msg(2, b"\x42") in msgs, "relayed message should be stored"
+        assert len([m for m in msgs if len(m) > 2 * MAX_PAYLOAD_SIZE]) == 0, (
+            "oversized message should not be stored"
+        )
@@ -0,0 +1,56 @@
+import json
+
+import flexitest
+from websockets.sync.client import connect as wsconnect
+
+from envs import testenv
+
+NUM_HEADERS_TO_RECEIVE = 5
+TIMEOUT = 30
+
+
+@flexitest.register
+class SubscriptionsTest(testenv.StrataTester):
+    def __init__(self, ctx: flexitest.InitContext):
+        ctx.set_env("basic")
+
+    def main(self, ctx: flexitest.RunContext):
+        seq = ctx.get_service("sequencer")
+        rpc_url = seq.get_prop("rpc_url")
+
+        with wsconnect(rpc_url) as ws:
+            req = {"jsonrpc": "2.0", "method": "strata_subscribeNewHeaders", "id": 0, "params": []}
+            ws.send(json.dumps(req))
+            resp = json.loads(ws.recv(timeout=TIMEOUT))
+            assert "error" not in resp, f"subscription failed: {resp}"
+            sub_id = resp["result"]
+
+            headers = []
+            while len(headers) < NUM_HEADERS_TO_RECEIVE:
+                notif = json.loads(ws.recv(timeout=TIMEOUT))
+                assert notif["method"] == "strata_newHeaders"
+                assert notif["params"]["subscription"] == sub_id
+                headers.append(notif["params"]["result"])
+
+            self.debug(f"received headers: {headers}")
+
+            # the headers must extend each other
+            for prev, cur in zip(headers, headers[1:]):
+                assert cur["block_idx"] == prev["block_idx"] + 1
+                assert cur["prev_block"] == prev["block_id"]
+
+            req = {
+                "jsonrpc": "2.0",
+                "method": "strata_unsubscribeNewHeaders",
+                "id": 1,
+                "params": [sub_id],
+            }
+            ws.send(json.dumps(req))
+
+            # notifications may still be in flight before the unsubscribe response
+            while True:
+                resp = json.loads(ws.recv(timeout=TIMEOUT))
+                if resp.get("id") == 1:
+                    break
+
+            assert resp["result"] is True
@@ -14,7 +14,7 @@ use bitcoincore_rpc::{
     Auth, Client, RpcApi,
 };
 use corepc_node::{BitcoinD, Conf};
-use strata_bridge_sig_manager::prelude::SignatureManager;
+use strata_bridge_sig_manager::prelude::{KeypairSigner, SignatureManager};
 use strata_bridge_tx_builder::{
     prelude::{
         create_tx, create_tx_ins, create_tx_outs, get_aggregated_pubkey, metadata_script,
@@ -725,7 +725,11 @@ pub(crate) fn setup_sig_manager(index: OperatorIdx, keypair: Keypair) -> Signatu
 
     event!(Level::INFO, event = "database handler initialized");
 
-    SignatureManager::new(Arc::new(db_ops), index, keypair)
+    SignatureManager::new(
+        Arc::new(db_ops),
+        index,
+        Arc::new(KeypairSigner::new(keypair)),
+    )
 }
 
 #[allow(dead_code)] // This not used in the `cooperative-bridge-out-flow`.
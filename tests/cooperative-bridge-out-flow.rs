@@ -16,7 +16,7 @@ use strata_bridge_tx_builder::prelude::{
     create_taproot_addr, create_tx, create_tx_ins, create_tx_outs, get_aggregated_pubkey,
     CooperativeWithdrawalInfo, SpendPath, BRIDGE_DENOMINATION,
 };
-use strata_primitives::bridge::{OperatorIdx, PublickeyTable};
+use strata_primitives::bridge::{OperatorIdx, PublickeyTable, WithdrawOutput};
 use tokio::sync::Mutex;
 use tracing::{event, span, Level};
 
@@ -51,8 +51,16 @@ async fn withdrawal_flow() {
     let assigned_operator_idx = OsRng.gen_range(0..num_operators) as OperatorIdx;
     event!(Level::INFO, event = "assigning withdrawal", operator_idx = %assigned_operator_idx);
 
-    let withdrawal_info =
-        CooperativeWithdrawalInfo::new(outpoint, user_x_only_pk, assigned_operator_idx, 0);
+    let withdraw_outputs = vec![WithdrawOutput::new(user_x_only_pk, BRIDGE_DENOMINATION)];
+    let notary_operators = (0..num_operators as OperatorIdx).collect();
+    let withdrawal_info = CooperativeWithdrawalInfo::new(
+        outpoint,
+        BRIDGE_DENOMINATION.into(),
+        notary_operators,
+        withdraw_outputs,
+        assigned_operator_idx,
+        0,
+    );
 
     event!(Level::DEBUG, action = "creating withdrawal duty", withdrawal_info = ?withdrawal_info);
     let duty = BridgeDuty::Withdrawal(withdrawal_info);